@@ -907,11 +907,7 @@ impl AppState {
                     .with_playback(playback)
                     .with_output(Some(Arc::new(std::sync::Mutex::new(output))));
 
-                local_player
-                    .playback_handler
-                    .write()
-                    .unwrap()
-                    .replace(handler.clone());
+                local_player.set_playback_handler(&handler);
 
                 handler
             }
@@ -936,11 +932,7 @@ impl AppState {
                     .with_playback(playback)
                     .with_output(Some(Arc::new(std::sync::Mutex::new(output))));
 
-                upnp_player
-                    .playback_handler
-                    .write()
-                    .unwrap()
-                    .replace(handler.clone());
+                upnp_player.set_playback_handler(&handler);
 
                 handler
             }
@@ -1103,7 +1095,7 @@ impl AppState {
                         Some(playback.position),
                         Some(playback.progress),
                         Some(playback.volume.load(std::sync::atomic::Ordering::SeqCst)),
-                        Some(playback.tracks.clone()),
+                        Some(playback.tracks.iter().map(|x| x.track.clone()).collect()),
                         Some(playback.quality),
                         Some(playback.session_id),
                         Some(playback.profile),
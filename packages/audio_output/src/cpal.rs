@@ -16,7 +16,8 @@ use symphonia::core::conv::{ConvertibleSample, IntoSample};
 use symphonia::core::units::Duration;
 
 use crate::{
-    AudioOutputError, AudioOutputFactory, AudioWrite, ProgressTracker,
+    AudioOutputError, AudioOutputFactory, AudioWrite, LatencyMode, ProgressTracker,
+    UnderrunTracker,
     command::{AudioCommand, AudioHandle, AudioResponse, CommandMessage},
 };
 
@@ -35,6 +36,8 @@ pub enum StreamCommand {
 }
 
 const INITIAL_BUFFER_SECONDS: usize = 10;
+/// Upper bound the buffering threshold may grow to after repeated underruns.
+const MAX_BUFFER_SECONDS: usize = 20;
 
 /// A CPAL-based audio output implementation.
 ///
@@ -91,6 +94,14 @@ impl AudioWrite for CpalAudioOutput {
         self.write.set_progress_callback(callback);
     }
 
+    fn set_underrun_tracker(&mut self, tracker: UnderrunTracker) {
+        self.write.set_underrun_tracker(tracker);
+    }
+
+    fn set_latency_mode(&mut self, mode: LatencyMode) {
+        self.write.set_latency_mode(mode);
+    }
+
     fn handle(&self) -> AudioHandle {
         self.write.handle()
     }
@@ -203,6 +214,7 @@ struct CpalAudioOutputImpl<T: AudioOutputSample> {
     initial_buffering: bool,
     buffered_samples: usize,
     buffering_threshold: usize,
+    latency_mode: LatencyMode,
     consumed_samples_shared:
         std::sync::Arc<std::sync::RwLock<std::sync::Arc<std::sync::atomic::AtomicUsize>>>, // Track actual consumption by CPAL
     volume_shared: std::sync::Arc<std::sync::RwLock<std::sync::Arc<atomic_float::AtomicF64>>>, // For immediate volume changes
@@ -213,7 +225,9 @@ struct CpalAudioOutputImpl<T: AudioOutputSample> {
     completion_condvar: std::sync::Arc<std::sync::Condvar>,
     completion_mutex: std::sync::Arc<std::sync::Mutex<bool>>, // true when ring buffer is empty
     draining: std::sync::Arc<std::sync::atomic::AtomicBool>,  // true when we're in flush/drain mode
+    device_lost: std::sync::Arc<std::sync::atomic::AtomicBool>, // true once the output device has been disconnected
     progress_tracker: ProgressTracker,
+    underrun_tracker_shared: std::sync::Arc<std::sync::RwLock<UnderrunTracker>>, // Counts callback underruns and grows the buffering threshold
     // Command handling
     command_receiver: Option<flume::Receiver<CommandMessage>>,
     command_handle: AudioHandle,
@@ -287,10 +301,21 @@ impl<T: AudioOutputSample> CpalAudioOutputImpl<T> {
         // Flag to indicate we're in drain mode (flush called, no more data coming)
         let draining = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
 
+        // Flag set by the stream's error callback once the output device disappears
+        let device_lost = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+
         // Progress tracking setup using ProgressTracker
         let progress_tracker = ProgressTracker::new(Some(0.1)); // 0.1 second threshold
         progress_tracker.set_audio_spec(config.sample_rate, u32::try_from(num_channels).unwrap());
 
+        // Underrun tracking setup - the buffering threshold may grow up to MAX_BUFFER_SECONDS
+        let underrun_tracker = UnderrunTracker::new(None);
+        underrun_tracker.set_buffer_limits(
+            INITIAL_BUFFER_SECONDS * config.sample_rate as usize * num_channels,
+            MAX_BUFFER_SECONDS * config.sample_rate as usize * num_channels,
+        );
+        let underrun_tracker_shared = std::sync::Arc::new(std::sync::RwLock::new(underrun_tracker));
+
         // Command handling setup
         let (command_sender, command_receiver) = flume::unbounded();
         let command_handle = AudioHandle::new(command_sender);
@@ -334,6 +359,8 @@ impl<T: AudioOutputSample> CpalAudioOutputImpl<T> {
         let completion_mutex_callback = completion_mutex.clone();
         let completion_condvar_callback = completion_condvar.clone();
         let draining_callback = draining.clone();
+        let device_lost_callback = device_lost.clone();
+        let underrun_tracker_callback = underrun_tracker_shared.clone();
 
         // Move progress tracking variables into the daemon closure
         let (
@@ -353,6 +380,14 @@ impl<T: AudioOutputSample> CpalAudioOutputImpl<T> {
                             // Write out as many samples as possible from the ring buffer to the audio output
                             let written = ring_buf_consumer_clone.read(data).unwrap_or(0);
 
+                            // Count underruns while playing (running dry while draining is expected)
+                            if !draining_callback.load(std::sync::atomic::Ordering::SeqCst)
+                                && let Ok(tracker) = underrun_tracker_callback.read()
+                                && tracker.record_read(data.len(), written)
+                            {
+                                log::debug!("CPAL: output underrun - requested {} samples, supplied {written}", data.len());
+                            }
+
                             // Apply volume immediately in the CPAL callback for instant effect
                             // This bypasses the 10-15s ring buffer delay
                             let volume = volume_shared_for_daemon.read().map_or(1.0, |atomic| {
@@ -442,7 +477,12 @@ impl<T: AudioOutputSample> CpalAudioOutputImpl<T> {
                                 completion_condvar_callback.notify_one();
                             }
                         },
-                        move |err| log::error!("Audio output error: {err}"),
+                        move |err| {
+                            log::error!("Audio output error: {err}");
+                            if matches!(err, cpal::StreamError::DeviceNotAvailable) {
+                                device_lost_callback.store(true, std::sync::atomic::Ordering::SeqCst);
+                            }
+                        },
                         None,
                     )
                     .map_err(|e| format!("Failed to create CPAL stream: {e:?}"))
@@ -468,6 +508,7 @@ impl<T: AudioOutputSample> CpalAudioOutputImpl<T> {
             initial_buffering: true,
             buffered_samples: 0,
             buffering_threshold,
+            latency_mode: LatencyMode::default(),
             consumed_samples_shared,
             volume_shared,
             total_samples_written: std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0)),
@@ -476,7 +517,9 @@ impl<T: AudioOutputSample> CpalAudioOutputImpl<T> {
             completion_condvar,
             completion_mutex,
             draining,
+            device_lost,
             progress_tracker,
+            underrun_tracker_shared,
             command_receiver: Some(command_receiver),
             command_handle,
             stream_handle,
@@ -489,6 +532,21 @@ impl<T: AudioOutputSample> CpalAudioOutputImpl<T> {
         Ok(instance)
     }
 
+    /// The buffering threshold, grown by the underrun tracker after repeated underruns.
+    ///
+    /// In [`LatencyMode::Monitoring`] the threshold is never grown.
+    fn current_buffering_threshold(&self) -> usize {
+        if self.latency_mode == LatencyMode::Monitoring {
+            return self.buffering_threshold;
+        }
+
+        self.underrun_tracker_shared
+            .read()
+            .map_or(self.buffering_threshold, |tracker| {
+                tracker.buffer_samples().max(self.buffering_threshold)
+            })
+    }
+
     fn init_sample_buf(&mut self, duration: Duration) -> &mut SampleBuffer<T> {
         if self.sample_buf.is_none() {
             let spec = self.spec;
@@ -504,6 +562,10 @@ impl<T: AudioOutputSample> AudioWrite for CpalAudioOutputImpl<T> {
         // Stream commands are now processed immediately by the dedicated thread
         // No need for lazy processing here
 
+        if self.device_lost.load(std::sync::atomic::Ordering::SeqCst) {
+            return Err(AudioOutputError::DeviceLost);
+        }
+
         // Do nothing if there are no audio frames.
         if decoded.frames() == 0 {
             return Ok(0);
@@ -547,7 +609,7 @@ impl<T: AudioOutputSample> AudioWrite for CpalAudioOutputImpl<T> {
 
                         // Start stream once we have 10 seconds buffered OR when flush is called
                         // (which indicates we have all the available audio data)
-                        if self.buffered_samples >= self.buffering_threshold {
+                        if self.buffered_samples >= self.current_buffering_threshold() {
                             log::debug!(
                                 "Initial buffering complete: {buffered_seconds:.2} seconds buffered, starting stream now"
                             );
@@ -570,6 +632,11 @@ impl<T: AudioOutputSample> AudioWrite for CpalAudioOutputImpl<T> {
                     samples = &samples[written..];
                 }
                 Ok(None) => {
+                    // The buffer never drains once the device is gone
+                    if self.device_lost.load(std::sync::atomic::Ordering::SeqCst) {
+                        return Err(AudioOutputError::DeviceLost);
+                    }
+
                     // Buffer is full, wait a bit and try again
                     std::thread::sleep(std::time::Duration::from_millis(10));
                 }
@@ -587,6 +654,10 @@ impl<T: AudioOutputSample> AudioWrite for CpalAudioOutputImpl<T> {
         // Stream commands are now processed immediately by the dedicated thread
         // No need for lazy processing here
 
+        if self.device_lost.load(std::sync::atomic::Ordering::SeqCst) {
+            return Err(AudioOutputError::DeviceLost);
+        }
+
         // If there is a resampler, then it may need to be flushed
         // depending on the number of samples it has.
 
@@ -746,6 +817,33 @@ impl<T: AudioOutputSample> AudioWrite for CpalAudioOutputImpl<T> {
         self.progress_tracker.set_callback(callback);
     }
 
+    fn set_underrun_tracker(&mut self, tracker: UnderrunTracker) {
+        let (buffer_samples, max_buffer_samples) = self.underrun_tracker_shared.read().map_or(
+            (self.buffering_threshold, self.buffering_threshold),
+            |current| (current.buffer_samples(), current.max_buffer_samples()),
+        );
+        tracker.set_buffer_limits(buffer_samples, max_buffer_samples);
+
+        if let Ok(mut current) = self.underrun_tracker_shared.write() {
+            *current = tracker;
+            log::debug!("CPAL impl: underrun tracker reference set");
+        } else {
+            log::error!("CPAL impl: failed to acquire write lock for underrun tracker");
+        }
+    }
+
+    fn set_latency_mode(&mut self, mode: LatencyMode) {
+        let channels = self.spec.channels.count();
+        self.latency_mode = mode;
+        self.buffering_threshold = mode
+            .buffer_samples(self.spec.rate, channels)
+            .unwrap_or(INITIAL_BUFFER_SECONDS * self.spec.rate as usize * channels);
+        log::debug!(
+            "CPAL impl: latency mode set to {mode:?} - buffering threshold: {} samples",
+            self.buffering_threshold
+        );
+    }
+
     fn handle(&self) -> AudioHandle {
         self.command_handle.clone()
     }
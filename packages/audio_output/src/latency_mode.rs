@@ -0,0 +1,62 @@
+//! Trade-off between resilience and latency of an audio output's buffering.
+//!
+//! This module provides [`LatencyMode`]. By default outputs buffer several seconds of audio
+//! before starting and grow that buffer after repeated underruns. [`LatencyMode::Monitoring`]
+//! instead starts output as soon as [`MONITORING_BUFFER_MILLIS`] of audio is buffered, for
+//! monitoring local input where the added delay matters more than the odd underrun.
+
+/// Milliseconds of audio buffered before output starts in [`LatencyMode::Monitoring`].
+pub const MONITORING_BUFFER_MILLIS: usize = 50;
+
+/// How much audio an output buffers before it starts playing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LatencyMode {
+    /// Buffer several seconds of audio, growing the buffer after repeated underruns
+    #[default]
+    Buffered,
+    /// Buffer as little audio as is safe, without growing it after underruns
+    Monitoring,
+}
+
+impl LatencyMode {
+    /// The number of interleaved samples to buffer before output starts, or `None` to
+    /// leave it to the output's default buffering.
+    ///
+    /// # Arguments
+    /// * `sample_rate` - Sample rate of the output in Hz
+    /// * `channels` - Number of output channels
+    #[must_use]
+    pub const fn buffer_samples(self, sample_rate: u32, channels: usize) -> Option<usize> {
+        match self {
+            Self::Buffered => None,
+            Self::Monitoring => {
+                Some((MONITORING_BUFFER_MILLIS * sample_rate as usize / 1000).max(1) * channels)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn test_latency_mode_defaults_to_buffered() {
+        assert_eq!(LatencyMode::default(), LatencyMode::Buffered);
+        assert_eq!(LatencyMode::Buffered.buffer_samples(44100, 2), None);
+    }
+
+    #[test_log::test]
+    fn test_monitoring_buffers_a_few_milliseconds_of_audio() {
+        assert_eq!(
+            LatencyMode::Monitoring.buffer_samples(44100, 2),
+            Some(2205 * 2)
+        );
+        assert_eq!(LatencyMode::Monitoring.buffer_samples(48000, 1), Some(2400));
+    }
+
+    #[test_log::test]
+    fn test_monitoring_buffers_at_least_one_frame() {
+        assert_eq!(LatencyMode::Monitoring.buffer_samples(1, 2), Some(2));
+    }
+}
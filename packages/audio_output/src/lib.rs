@@ -45,7 +45,7 @@ pub mod cpal_daemon;
 use std::sync::{Arc, LazyLock};
 
 use moosicbox_audio_decoder::{AudioDecode, AudioDecodeError};
-use moosicbox_resampler::{Resampler, to_audio_buffer};
+use moosicbox_resampler::{Resampler, ResamplerQuality, to_audio_buffer};
 use switchy_async::sync::Mutex;
 use switchy_async::task::JoinError;
 use symphonia::core::audio::{AudioBuffer, Signal as _};
@@ -63,6 +63,12 @@ pub use symphonia::core::units::Duration;
 // Export ProgressTracker for use by AudioOutput implementations
 pub use progress_tracker::ProgressTracker;
 
+// Export UnderrunTracker for use by AudioOutput implementations
+pub use underrun_tracker::UnderrunTracker;
+
+// Export LatencyMode for use by AudioOutput implementations
+pub use latency_mode::LatencyMode;
+
 // Export command types for use by AudioOutput implementations
 pub use command::{AudioCommand, AudioError, AudioHandle, AudioResponse, CommandMessage};
 
@@ -79,9 +85,15 @@ pub mod api;
 /// CPAL (Cross-Platform Audio Library) audio output implementation.
 pub mod cpal;
 
+/// Buffering latency of audio outputs.
+pub mod latency_mode;
+
 /// Progress tracking for audio playback.
 pub mod progress_tracker;
 
+/// Buffer underrun tracking for audio playback.
+pub mod underrun_tracker;
+
 /// An audio output that writes decoded audio samples to an underlying audio device or stream.
 ///
 /// This struct handles audio resampling when the decoded sample rate doesn't match the output
@@ -94,6 +106,7 @@ pub struct AudioOutput {
     /// Audio signal specification (sample rate, channels, etc.)
     pub spec: SignalSpec,
     resampler: Option<Resampler<f32>>,
+    resampler_quality: ResamplerQuality,
     writer: Box<dyn AudioWrite>,
 }
 
@@ -122,10 +135,20 @@ impl AudioOutput {
             name,
             spec,
             resampler: None,
+            resampler_quality: ResamplerQuality::default(),
             writer,
         }
     }
 
+    /// Sets the quality of the resampler used when the decoded sample rate doesn't match
+    /// the output's.
+    ///
+    /// Takes effect when the resampler is created, i.e. before the first write that needs
+    /// resampling.
+    pub const fn set_resampler_quality(&mut self, quality: ResamplerQuality) {
+        self.resampler_quality = quality;
+    }
+
     fn resample_if_needed(
         &mut self,
         decoded: AudioBuffer<f32>,
@@ -147,10 +170,11 @@ impl AudioOutput {
                 decoded.capacity(),
                 duration,
             );
-            self.resampler.replace(Resampler::new(
+            self.resampler.replace(Resampler::with_quality(
                 *decoded.spec(),
                 self.spec.rate as usize,
                 duration as u64,
+                self.resampler_quality,
             ));
             self.resample_if_needed(decoded)?
         } else {
@@ -204,6 +228,14 @@ impl AudioWrite for AudioOutput {
         self.writer.set_progress_callback(callback);
     }
 
+    fn set_underrun_tracker(&mut self, tracker: UnderrunTracker) {
+        self.writer.set_underrun_tracker(tracker);
+    }
+
+    fn set_latency_mode(&mut self, mode: LatencyMode) {
+        self.writer.set_latency_mode(mode);
+    }
+
     fn handle(&self) -> AudioHandle {
         self.writer.handle()
     }
@@ -330,6 +362,7 @@ impl TryFrom<AudioOutputFactory> for AudioOutput {
             name: value.name,
             spec: value.spec,
             resampler: None,
+            resampler_quality: ResamplerQuality::default(),
             writer: (value.get_writer.lock().unwrap())()?,
         })
     }
@@ -344,6 +377,7 @@ impl TryFrom<&AudioOutputFactory> for AudioOutput {
             name: value.name.clone(),
             spec: value.spec,
             resampler: None,
+            resampler_quality: ResamplerQuality::default(),
             writer: (value.get_writer.lock().unwrap())()?,
         })
     }
@@ -403,6 +437,14 @@ pub trait AudioWrite {
     ) {
     }
 
+    /// Set a shared underrun tracker that records buffer underruns from the output callback
+    /// Default implementation does nothing
+    fn set_underrun_tracker(&mut self, _tracker: UnderrunTracker) {}
+
+    /// Set how much audio is buffered before output starts
+    /// Default implementation does nothing
+    fn set_latency_mode(&mut self, _mode: LatencyMode) {}
+
     /// Get a communication handle for sending commands to this audio output.
     ///
     /// The handle can be used to control playback (pause, resume, seek, etc.)
@@ -483,6 +525,9 @@ pub enum AudioOutputError {
     /// Audio playback was interrupted
     #[error("InterruptError")]
     Interrupt,
+    /// The audio output device was disconnected (e.g. headphones were unplugged)
+    #[error("DeviceLostError")]
+    DeviceLost,
     /// An I/O error occurred
     #[error(transparent)]
     IO(#[from] std::io::Error),
@@ -739,6 +784,9 @@ mod tests {
 
         let err = AudioOutputError::Interrupt;
         assert_eq!(format!("{err:?}"), "Interrupt");
+
+        let err = AudioOutputError::DeviceLost;
+        assert_eq!(format!("{err:?}"), "DeviceLost");
     }
 
     #[test_log::test]
@@ -766,6 +814,9 @@ mod tests {
 
         let err = AudioOutputError::Interrupt;
         assert_eq!(format!("{err}"), "InterruptError");
+
+        let err = AudioOutputError::DeviceLost;
+        assert_eq!(format!("{err}"), "DeviceLostError");
     }
 
     #[test_log::test]
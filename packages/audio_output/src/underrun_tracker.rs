@@ -0,0 +1,218 @@
+//! Buffer underrun tracking for audio playback.
+//!
+//! This module provides [`UnderrunTracker`], which counts how often an audio output callback
+//! requests more samples than the decode buffer can supply. Once underruns exceed a threshold,
+//! the tracker grows the target buffer size (up to a maximum) so that the next buffering phase
+//! holds more audio and is less likely to run dry.
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
+/// Default number of underruns tolerated before the buffer is grown.
+pub const DEFAULT_GROWTH_THRESHOLD: u64 = 3;
+
+/// Tracks output buffer underruns and adapts the target buffer size.
+///
+/// Cloning an `UnderrunTracker` shares the underlying counters, so a player can hold onto a
+/// clone and report the count while the audio output records underruns from its callback.
+#[derive(Debug, Clone)]
+pub struct UnderrunTracker {
+    /// Total number of underruns recorded
+    underrun_count: Arc<AtomicU64>,
+    /// Underruns recorded since the buffer was last grown
+    underruns_since_growth: Arc<AtomicU64>,
+    /// Target number of samples to buffer before starting output
+    buffer_samples: Arc<AtomicUsize>,
+    /// Upper bound for `buffer_samples`
+    max_buffer_samples: Arc<AtomicUsize>,
+    /// Number of underruns to tolerate before growing the buffer
+    growth_threshold: u64,
+}
+
+impl UnderrunTracker {
+    /// Create a new `UnderrunTracker` with the specified growth threshold
+    ///
+    /// # Arguments
+    /// * `growth_threshold` - Number of underruns before the buffer is grown (default: 3)
+    #[must_use]
+    pub fn new(growth_threshold: Option<u64>) -> Self {
+        Self {
+            underrun_count: Arc::new(AtomicU64::new(0)),
+            underruns_since_growth: Arc::new(AtomicU64::new(0)),
+            buffer_samples: Arc::new(AtomicUsize::new(0)),
+            max_buffer_samples: Arc::new(AtomicUsize::new(0)),
+            growth_threshold: growth_threshold.unwrap_or(DEFAULT_GROWTH_THRESHOLD).max(1),
+        }
+    }
+
+    /// Set the buffer size limits in samples
+    ///
+    /// If the buffer has already been grown by earlier underruns, the larger size is kept
+    /// (clamped to `max_buffer_samples`).
+    pub fn set_buffer_limits(&self, buffer_samples: usize, max_buffer_samples: usize) {
+        let max_buffer_samples = max_buffer_samples.max(buffer_samples);
+        let current = self.buffer_samples.load(Ordering::SeqCst);
+
+        self.max_buffer_samples
+            .store(max_buffer_samples, Ordering::SeqCst);
+        self.buffer_samples.store(
+            current.max(buffer_samples).min(max_buffer_samples),
+            Ordering::SeqCst,
+        );
+
+        log::debug!(
+            "UnderrunTracker: buffer limits set - buffer_samples={}, max_buffer_samples={max_buffer_samples}",
+            self.buffer_samples.load(Ordering::SeqCst)
+        );
+    }
+
+    /// Record a read from the output callback
+    ///
+    /// Counts an underrun if fewer samples were supplied than requested, and grows the
+    /// target buffer once the growth threshold is reached. Returns whether an underrun
+    /// occurred.
+    ///
+    /// # Arguments
+    /// * `requested` - Number of samples the output requested
+    /// * `supplied` - Number of samples the buffer could supply
+    pub fn record_read(&self, requested: usize, supplied: usize) -> bool {
+        if supplied >= requested {
+            return false;
+        }
+
+        let count = self.underrun_count.fetch_add(1, Ordering::SeqCst) + 1;
+        let since_growth = self.underruns_since_growth.fetch_add(1, Ordering::SeqCst) + 1;
+
+        log::debug!(
+            "UnderrunTracker: underrun #{count} - requested={requested}, supplied={supplied}"
+        );
+
+        if since_growth >= self.growth_threshold {
+            self.underruns_since_growth.store(0, Ordering::SeqCst);
+            self.grow_buffer();
+        }
+
+        true
+    }
+
+    fn grow_buffer(&self) {
+        let max = self.max_buffer_samples.load(Ordering::SeqCst);
+        let current = self.buffer_samples.load(Ordering::SeqCst);
+        let grown = current.saturating_mul(2).min(max);
+
+        if grown > current {
+            self.buffer_samples.store(grown, Ordering::SeqCst);
+            log::info!("UnderrunTracker: growing buffer from {current} to {grown} samples");
+        } else {
+            log::debug!("UnderrunTracker: buffer already at maximum of {max} samples");
+        }
+    }
+
+    /// Get the total number of underruns recorded
+    #[must_use]
+    pub fn underrun_count(&self) -> u64 {
+        self.underrun_count.load(Ordering::SeqCst)
+    }
+
+    /// Get the current target buffer size in samples
+    #[must_use]
+    pub fn buffer_samples(&self) -> usize {
+        self.buffer_samples.load(Ordering::SeqCst)
+    }
+
+    /// Get the maximum buffer size in samples
+    #[must_use]
+    pub fn max_buffer_samples(&self) -> usize {
+        self.max_buffer_samples.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for UnderrunTracker {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mock output that requests a fixed number of samples per callback from a
+    /// decode buffer that only ever has `available` samples ready.
+    struct OverRequestingOutput {
+        tracker: UnderrunTracker,
+        request_size: usize,
+        available: usize,
+    }
+
+    impl OverRequestingOutput {
+        fn callback(&self) {
+            let supplied = self.available.min(self.request_size);
+            self.tracker.record_read(self.request_size, supplied);
+        }
+    }
+
+    #[test_log::test]
+    fn test_underrun_tracker_default() {
+        let tracker = UnderrunTracker::default();
+        assert_eq!(tracker.growth_threshold, DEFAULT_GROWTH_THRESHOLD);
+        assert_eq!(tracker.underrun_count(), 0);
+        assert_eq!(tracker.buffer_samples(), 0);
+    }
+
+    #[test_log::test]
+    fn test_record_read_without_underrun() {
+        let tracker = UnderrunTracker::new(None);
+        tracker.set_buffer_limits(1000, 4000);
+
+        assert!(!tracker.record_read(512, 512));
+        assert_eq!(tracker.underrun_count(), 0);
+        assert_eq!(tracker.buffer_samples(), 1000);
+    }
+
+    #[test_log::test]
+    fn test_over_requesting_output_increments_count_and_grows_buffer() {
+        let tracker = UnderrunTracker::new(Some(2));
+        tracker.set_buffer_limits(1000, 4000);
+
+        let output = OverRequestingOutput {
+            tracker: tracker.clone(),
+            request_size: 1024,
+            available: 256,
+        };
+
+        output.callback();
+        assert_eq!(tracker.underrun_count(), 1);
+        assert_eq!(tracker.buffer_samples(), 1000);
+
+        output.callback();
+        assert_eq!(tracker.underrun_count(), 2);
+        assert_eq!(tracker.buffer_samples(), 2000);
+
+        for _ in 0..10 {
+            output.callback();
+        }
+        assert_eq!(tracker.underrun_count(), 12);
+        assert_eq!(
+            tracker.buffer_samples(),
+            4000,
+            "Buffer growth must be clamped to the maximum buffer size"
+        );
+    }
+
+    #[test_log::test]
+    fn test_set_buffer_limits_keeps_grown_buffer() {
+        let tracker = UnderrunTracker::new(Some(1));
+        tracker.set_buffer_limits(1000, 4000);
+        tracker.record_read(10, 0);
+        assert_eq!(tracker.buffer_samples(), 2000);
+
+        tracker.set_buffer_limits(1000, 4000);
+        assert_eq!(tracker.buffer_samples(), 2000);
+
+        tracker.set_buffer_limits(1000, 1500);
+        assert_eq!(tracker.buffer_samples(), 1500);
+    }
+}
@@ -0,0 +1,201 @@
+//! Base config inheritance for `clippier.toml` files.
+//!
+//! A package's `clippier.toml` can inherit a shared base config with a top-level
+//! `extends` key:
+//!
+//! ```toml
+//! extends = "../shared/clippier.base.toml"
+//!
+//! [env]
+//! RUST_LOG = "debug"
+//! ```
+//!
+//! The path is relative to the directory of the file declaring it, and base configs can
+//! themselves extend other configs. Fields are merged with the same precedence that
+//! propagated dependency configs use: `git-submodules` is enabled if either config
+//! enables it, `dependencies` and `ci-steps` are concatenated without duplicates, `env`
+//! entries are overridden per variable, and every other field set in the extending
+//! config replaces the base value. `[[config]]` entries are merged per `os`/`name` pair.
+
+use std::path::{Component, Path, PathBuf};
+
+use itertools::Itertools;
+use toml::{Table, Value};
+
+use crate::{BoxError, ClippierConf};
+
+/// Key referencing the base config to inherit from
+pub const EXTENDS_KEY: &str = "extends";
+
+/// Loads a `clippier.toml` file, resolving its `extends` chain.
+///
+/// The returned value no longer contains the `extends` key.
+///
+/// # Errors
+///
+/// * If the file or any base config cannot be read or parsed
+/// * If `extends` is not a string
+/// * If a base config does not exist
+/// * If the `extends` chain is circular
+pub fn load_clippier_toml(path: &Path) -> Result<Value, BoxError> {
+    load_extending(path, &mut vec![])
+}
+
+/// Loads and deserializes a `clippier.toml` file, resolving its `extends` chain.
+///
+/// # Errors
+///
+/// * If the file cannot be loaded (see [`load_clippier_toml`])
+/// * If the merged config is not a valid [`ClippierConf`]
+pub fn load_clippier_conf(path: &Path) -> Result<ClippierConf, BoxError> {
+    Ok(load_clippier_toml(path)?.try_into()?)
+}
+
+fn load_extending(path: &Path, chain: &mut Vec<PathBuf>) -> Result<Value, BoxError> {
+    let path = normalize_path(path);
+
+    if chain.contains(&path) {
+        let cycle = chain
+            .iter()
+            .chain(std::iter::once(&path))
+            .map(|x| x.display())
+            .join(" -> ");
+        return Err(format!("Circular `extends` in clippier.toml: {cycle}").into());
+    }
+
+    let source = switchy_fs::sync::read_to_string(&path)
+        .map_err(|e| format!("Failed to read '{}': {e}", path.display()))?;
+    let mut value: Value = toml::from_str(&source)
+        .map_err(|e| format!("Failed to parse '{}': {e}", path.display()))?;
+
+    let Some(extends) = value.as_table_mut().and_then(|x| x.remove(EXTENDS_KEY)) else {
+        return Ok(value);
+    };
+
+    let Value::String(extends) = extends else {
+        return Err(format!(
+            "`{EXTENDS_KEY}` in '{}' must be a path string, got: {extends}",
+            path.display()
+        )
+        .into());
+    };
+
+    let base_path = path
+        .parent()
+        .map_or_else(|| PathBuf::from(&extends), |dir| dir.join(&extends));
+
+    if !switchy_fs::exists(&base_path) {
+        return Err(format!(
+            "'{}' extends '{extends}', but '{}' does not exist",
+            path.display(),
+            normalize_path(&base_path).display()
+        )
+        .into());
+    }
+
+    log::trace!("{} extends {}", path.display(), base_path.display());
+
+    chain.push(path);
+    let base = load_extending(&base_path, chain)?;
+    chain.pop();
+
+    Ok(merge_clippier_toml(base, value))
+}
+
+/// Merges `overlay` on top of `base` following the `clippier.toml` inheritance rules.
+#[must_use]
+pub fn merge_clippier_toml(base: Value, overlay: Value) -> Value {
+    match (base, overlay) {
+        (Value::Table(base), Value::Table(overlay)) => Value::Table(merge_tables(base, overlay)),
+        (_, overlay) => overlay,
+    }
+}
+
+fn merge_tables(mut base: Table, overlay: Table) -> Table {
+    for (key, value) in overlay {
+        let merged = match (key.as_str(), base.remove(&key)) {
+            (_, None) => value,
+            ("git-submodules", Some(Value::Boolean(enabled))) => {
+                Value::Boolean(enabled || value.as_bool() == Some(true))
+            }
+            ("env", Some(Value::Table(mut existing))) => match value {
+                Value::Table(overlay) => {
+                    existing.extend(overlay);
+                    Value::Table(existing)
+                }
+                value => value,
+            },
+            ("dependencies" | "ci-steps", Some(existing)) => merge_steps(existing, value),
+            ("config", Some(Value::Array(existing))) => merge_configurations(existing, value),
+            (_, Some(existing)) => merge_clippier_toml(existing, value),
+        };
+        base.insert(key, merged);
+    }
+
+    base
+}
+
+/// Concatenates steps, skipping overlay steps that match a base step's command,
+/// toolchain and features.
+fn merge_steps(base: Value, overlay: Value) -> Value {
+    let key =
+        |step: &Value| ["command", "toolchain", "features"].map(|field| step.get(field).cloned());
+
+    let mut steps = into_array(base);
+
+    for step in into_array(overlay) {
+        if !steps.iter().any(|x| key(x) == key(&step)) {
+            steps.push(step);
+        }
+    }
+
+    Value::Array(steps)
+}
+
+/// Merges `[[config]]` entries that share the same `os` and `name`, appending the rest.
+fn merge_configurations(mut base: Vec<Value>, overlay: Value) -> Value {
+    let key = |config: &Value| (config.get("os").cloned(), config.get("name").cloned());
+
+    for config in into_array(overlay) {
+        if let Some(index) = base.iter().position(|x| key(x) == key(&config)) {
+            let existing = base.remove(index);
+            base.insert(index, merge_clippier_toml(existing, config));
+        } else {
+            base.push(config);
+        }
+    }
+
+    Value::Array(base)
+}
+
+fn into_array(value: Value) -> Vec<Value> {
+    match value {
+        Value::Array(values) => values,
+        value => vec![value],
+    }
+}
+
+/// Lexically resolves `.` and `..` components so that cycles are detected regardless of
+/// how the path was spelled.
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) {
+                    normalized.pop();
+                } else {
+                    normalized.push(component);
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
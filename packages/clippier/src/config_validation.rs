@@ -0,0 +1,701 @@
+//! Strict schema validation of `clippier.toml` files.
+//!
+//! Loading a `clippier.toml` ignores keys it doesn't recognize, so a typo like
+//! `skip_features` instead of `skip-features` silently does nothing. The checks here walk
+//! the raw TOML instead and report:
+//!
+//! * Unknown keys, with a suggestion when one is close to a known key
+//! * Missing required keys, such as `os` on a `[[config]]` entry
+//! * Values of the wrong type
+//! * Obviously wrong values, such as an empty `os` or `chunked = 0`
+//!
+//! Files are checked as written, before their `extends` chain is merged, and the base
+//! configs they extend are checked as well.
+
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+use itertools::Itertools;
+use serde::Serialize;
+use toml::Value;
+
+use crate::{
+    BoxError,
+    config_extends::{EXTENDS_KEY, normalize_path},
+    expand_workspace_member_globs,
+};
+
+/// Maximum edit distance between an unknown key and a known key to suggest the latter
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// A problem found in a `clippier.toml` file
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct ConfigDiagnostic {
+    /// Path of the file the problem was found in
+    pub file: PathBuf,
+    /// Path of the offending key, e.g. `config[0].rust.skip_features`. Empty for problems
+    /// with the file as a whole
+    pub key: String,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl std::fmt::Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.key.is_empty() {
+            write!(f, "{}: {}", self.file.display(), self.message)
+        } else {
+            write!(
+                f,
+                "{}: `{}`: {}",
+                self.file.display(),
+                self.key,
+                self.message
+            )
+        }
+    }
+}
+
+/// Result of validating every `clippier.toml` in a workspace
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigValidationReport {
+    /// Files that were checked, relative to the workspace root
+    pub files_checked: Vec<PathBuf>,
+    /// Problems found, in file order
+    pub diagnostics: Vec<ConfigDiagnostic>,
+}
+
+impl ConfigValidationReport {
+    /// Whether no problems were found
+    #[must_use]
+    pub const fn is_clean(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+}
+
+/// Expected shape of a value in a `clippier.toml` file
+enum Schema {
+    /// Any string
+    String,
+    /// A string that isn't empty or whitespace
+    NonEmptyString,
+    /// A boolean
+    Bool,
+    /// An integer within the inclusive range
+    Integer { min: i64, max: i64 },
+    /// One of the given strings
+    OneOf(&'static [&'static str]),
+    /// An array whose items all match the schema
+    Array(Box<Self>),
+    /// A table with the given known keys
+    ///
+    /// With `allow_extra_tables`, unknown keys holding tables are allowed as well, for
+    /// sections that other tools read their own settings from.
+    Table {
+        fields: Vec<Field>,
+        allow_extra_tables: bool,
+    },
+    /// A table with arbitrary keys whose values all match the schema
+    Map(Box<Self>),
+    /// A value matching any of the schemas, picked by the value's type
+    Either(Vec<Self>),
+}
+
+/// A known key of a [`Schema::Table`]
+struct Field {
+    name: &'static str,
+    schema: Schema,
+    required: bool,
+}
+
+const fn optional(name: &'static str, schema: Schema) -> Field {
+    Field {
+        name,
+        schema,
+        required: false,
+    }
+}
+
+const fn required(name: &'static str, schema: Schema) -> Field {
+    Field {
+        name,
+        schema,
+        required: true,
+    }
+}
+
+const fn table(fields: Vec<Field>) -> Schema {
+    Schema::Table {
+        fields,
+        allow_extra_tables: false,
+    }
+}
+
+fn array(schema: Schema) -> Schema {
+    Schema::Array(Box::new(schema))
+}
+
+fn strings() -> Schema {
+    array(Schema::String)
+}
+
+fn vec_or_item(schema: impl Fn() -> Schema) -> Schema {
+    Schema::Either(vec![schema(), array(schema())])
+}
+
+impl Schema {
+    /// Whether `value` has the type this schema expects, regardless of its contents
+    fn accepts_type(&self, value: &Value) -> bool {
+        match self {
+            Self::String | Self::NonEmptyString | Self::OneOf(..) => value.is_str(),
+            Self::Bool => value.is_bool(),
+            Self::Integer { .. } => value.is_integer(),
+            Self::Array(..) => value.is_array(),
+            Self::Table { .. } | Self::Map(..) => value.is_table(),
+            Self::Either(schemas) => schemas.iter().any(|x| x.accepts_type(value)),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::String => "a string".to_string(),
+            Self::NonEmptyString => "a non-empty string".to_string(),
+            Self::Bool => "a boolean".to_string(),
+            Self::Integer { .. } => "an integer".to_string(),
+            Self::OneOf(values) => format!(
+                "one of {}",
+                values.iter().map(|x| format!("`{x}`")).join(", ")
+            ),
+            Self::Array(..) => "an array".to_string(),
+            Self::Table { .. } | Self::Map(..) => "a table".to_string(),
+            Self::Either(schemas) => schemas.iter().map(Self::describe).join(" or "),
+        }
+    }
+}
+
+fn step_schema() -> Schema {
+    table(vec![
+        optional("command", Schema::String),
+        optional("toolchain", Schema::String),
+        optional("features", strings()),
+    ])
+}
+
+fn env_schema() -> Schema {
+    Schema::Map(Box::new(Schema::Either(vec![
+        Schema::String,
+        table(vec![
+            required("value", Schema::String),
+            optional("features", strings()),
+            optional("profile", strings()),
+        ]),
+    ])))
+}
+
+fn rust_schema() -> Schema {
+    table(vec![
+        optional("cargo", vec_or_item(|| Schema::String)),
+        optional("nightly", Schema::Bool),
+        optional("skip-features", strings()),
+        optional(
+            "required-features",
+            array(Schema::Either(vec![
+                Schema::String,
+                table(vec![
+                    required("feature", Schema::String),
+                    required("os", vec_or_item(|| Schema::NonEmptyString)),
+                ]),
+            ])),
+        ),
+    ])
+}
+
+fn node_schema() -> Schema {
+    table(vec![
+        optional("package-manager", Schema::OneOf(&["npm", "pnpm", "bun"])),
+        optional("node-version", Schema::String),
+        optional("skip-packages", strings()),
+        optional("args", vec_or_item(|| Schema::String)),
+    ])
+}
+
+fn configuration_schema() -> Schema {
+    table(vec![
+        optional("ci-steps", vec_or_item(step_schema)),
+        optional("env", env_schema()),
+        optional("dependencies", array(step_schema())),
+        required("os", Schema::NonEmptyString),
+        optional("name", Schema::NonEmptyString),
+        optional("git-submodules", Schema::Bool),
+        optional("rust", rust_schema()),
+        optional("node", node_schema()),
+    ])
+}
+
+fn prefix_override_schema() -> Schema {
+    table(vec![
+        required("dependency", Schema::String),
+        required("prefix", Schema::String),
+    ])
+}
+
+fn depth_schema() -> Schema {
+    Schema::Integer {
+        min: 0,
+        max: i64::from(u8::MAX),
+    }
+}
+
+fn feature_validation_schema() -> Schema {
+    table(vec![
+        optional(
+            "override",
+            array(table(vec![
+                required("feature", Schema::String),
+                optional("dependency", vec_or_item(|| Schema::String)),
+                optional("dependencies", vec_or_item(|| Schema::String)),
+                required(
+                    "type",
+                    Schema::OneOf(&["allow-missing", "allow-incorrect", "suppress"]),
+                ),
+                required("reason", Schema::String),
+                optional("expires", Schema::String),
+            ])),
+        ),
+        optional(
+            "parent",
+            table(vec![
+                optional("enabled", Schema::Bool),
+                optional("depth", depth_schema()),
+                optional("skip-features", strings()),
+                optional("prefix", array(prefix_override_schema())),
+            ]),
+        ),
+        optional(
+            "parent-packages",
+            array(table(vec![
+                required("package", Schema::String),
+                optional("depth", depth_schema()),
+                optional("skip-features", strings()),
+            ])),
+        ),
+        optional("parent-prefix", array(prefix_override_schema())),
+    ])
+}
+
+fn tools_schema() -> Schema {
+    Schema::Table {
+        fields: vec![
+            optional("required", strings()),
+            optional("skip", strings()),
+            optional("paths", Schema::Map(Box::new(Schema::String))),
+            optional("runner-fallback", Schema::Bool),
+            optional("biome-use-editorconfig", Schema::Bool),
+            optional("biome-use-vcs-ignore", Schema::Bool),
+            optional(
+                "overlap-warning-suppress",
+                array(table(vec![
+                    required("capability", Schema::OneOf(&["format", "lint"])),
+                    required("tools", strings()),
+                    optional("extensions", strings()),
+                ])),
+            ),
+            optional("nix-fallback", Schema::Bool),
+            optional("nix-packages", Schema::Map(Box::new(Schema::String))),
+        ],
+        allow_extra_tables: true,
+    }
+}
+
+/// Schema of a whole `clippier.toml`, covering both package and workspace-level configs
+fn clippier_toml_schema() -> Schema {
+    table(vec![
+        optional(EXTENDS_KEY, Schema::NonEmptyString),
+        optional("ci-steps", vec_or_item(step_schema)),
+        optional("config", array(configuration_schema())),
+        optional("env", env_schema()),
+        optional(
+            "parallelization",
+            table(vec![required(
+                "chunked",
+                Schema::Integer {
+                    min: 1,
+                    max: i64::from(u16::MAX),
+                },
+            )]),
+        ),
+        optional("git-submodules", Schema::Bool),
+        optional("dependencies", array(step_schema())),
+        optional("rust", rust_schema()),
+        optional("node", node_schema()),
+        optional(
+            "mutually-exclusive",
+            array(table(vec![required("features", strings())])),
+        ),
+        optional("feature-validation", feature_validation_schema()),
+        optional("dockerignore-extra", strings()),
+        optional("flaky", Schema::Bool),
+        optional(
+            "flaky-retries",
+            Schema::Integer {
+                min: 0,
+                max: i64::from(u32::MAX),
+            },
+        ),
+        optional("tools", tools_schema()),
+        optional("members-file", Schema::NonEmptyString),
+        optional(
+            "affects",
+            array(table(vec![
+                required("glob", Schema::NonEmptyString),
+                required("packages", strings()),
+            ])),
+        ),
+    ])
+}
+
+/// Collects the diagnostics for a single file
+struct Checker<'a> {
+    file: &'a Path,
+    diagnostics: Vec<ConfigDiagnostic>,
+}
+
+impl Checker<'_> {
+    fn report(&mut self, key: &str, message: String) {
+        self.diagnostics.push(ConfigDiagnostic {
+            file: self.file.to_path_buf(),
+            key: key.to_string(),
+            message,
+        });
+    }
+
+    fn type_mismatch(&mut self, key: &str, schema: &Schema, value: &Value) {
+        self.report(
+            key,
+            format!("expected {}, found {}", schema.describe(), value.type_str()),
+        );
+    }
+
+    fn check(&mut self, key: &str, value: &Value, schema: &Schema) {
+        if !schema.accepts_type(value) {
+            self.type_mismatch(key, schema, value);
+            return;
+        }
+
+        match schema {
+            Schema::String | Schema::Bool => {}
+            Schema::NonEmptyString => {
+                if value.as_str().is_some_and(|x| x.trim().is_empty()) {
+                    self.report(key, "must not be empty".to_string());
+                }
+            }
+            Schema::Integer { min, max } => {
+                if let Some(x) = value.as_integer()
+                    && !(*min..=*max).contains(&x)
+                {
+                    self.report(key, format!("must be between {min} and {max}, found {x}"));
+                }
+            }
+            Schema::OneOf(values) => {
+                if let Some(x) = value.as_str()
+                    && !values.contains(&x)
+                {
+                    self.report(key, format!("expected {}, found `{x}`", schema.describe()));
+                }
+            }
+            Schema::Array(item) => {
+                for (index, x) in value.as_array().into_iter().flatten().enumerate() {
+                    self.check(&format!("{key}[{index}]"), x, item);
+                }
+            }
+            Schema::Table {
+                fields,
+                allow_extra_tables,
+            } => self.check_table(key, value, fields, *allow_extra_tables),
+            Schema::Map(item) => {
+                for (name, x) in value.as_table().into_iter().flatten() {
+                    self.check(&child_key(key, name), x, item);
+                }
+            }
+            Schema::Either(schemas) => {
+                if let Some(schema) = schemas.iter().find(|x| x.accepts_type(value)) {
+                    self.check(key, value, schema);
+                }
+            }
+        }
+    }
+
+    fn check_table(
+        &mut self,
+        key: &str,
+        value: &Value,
+        fields: &[Field],
+        allow_extra_tables: bool,
+    ) {
+        let Some(table) = value.as_table() else {
+            return;
+        };
+
+        for (name, x) in table {
+            if let Some(field) = fields.iter().find(|field| field.name == name.as_str()) {
+                self.check(&child_key(key, name), x, &field.schema);
+            } else if !(allow_extra_tables && x.is_table()) {
+                let message = suggest_key(name, fields.iter().map(|x| x.name)).map_or_else(
+                    || "unknown key".to_string(),
+                    |suggestion| format!("unknown key, did you mean `{suggestion}`?"),
+                );
+                self.report(&child_key(key, name), message);
+            }
+        }
+
+        for field in fields {
+            if field.required && !table.contains_key(field.name) {
+                self.report(key, format!("missing required key `{}`", field.name));
+            }
+        }
+    }
+}
+
+fn child_key(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{parent}.{name}")
+    }
+}
+
+/// Returns the known key closest to the unknown key `name`, if any is close enough to be
+/// a likely typo.
+///
+/// `snake_case` spellings of `kebab-case` keys always match.
+fn suggest_key<'a>(name: &str, known: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let normalized = name.replace('_', "-").to_lowercase();
+
+    known
+        .map(|x| (edit_distance(&normalized, x), x))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, x)| x)
+}
+
+/// Levenshtein distance between `a` and `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, x) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+
+        for (j, y) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(x != *y);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Validates a parsed `clippier.toml` against the schema.
+///
+/// Diagnostics are attributed to `file`. A missing `extends` base config is reported
+/// relative to `file`'s directory.
+#[must_use]
+pub fn validate_clippier_value(file: &Path, value: &Value) -> Vec<ConfigDiagnostic> {
+    let mut checker = Checker {
+        file,
+        diagnostics: vec![],
+    };
+
+    checker.check("", value, &clippier_toml_schema());
+
+    if let Some(base_path) = extends_path(file, value)
+        && !switchy_fs::exists(&base_path)
+    {
+        checker.report(
+            EXTENDS_KEY,
+            format!("base config '{}' does not exist", base_path.display()),
+        );
+    }
+
+    checker.diagnostics
+}
+
+/// Validates a `clippier.toml` file against the schema.
+///
+/// A file that isn't valid TOML is reported as a single diagnostic.
+///
+/// # Errors
+///
+/// * If the file cannot be read
+pub fn validate_clippier_toml(path: &Path) -> Result<Vec<ConfigDiagnostic>, BoxError> {
+    let source = switchy_fs::sync::read_to_string(path)
+        .map_err(|e| format!("Failed to read '{}': {e}", path.display()))?;
+
+    Ok(match toml::from_str::<Value>(&source) {
+        Ok(value) => validate_clippier_value(path, &value),
+        Err(e) => vec![ConfigDiagnostic {
+            file: path.to_path_buf(),
+            key: String::new(),
+            message: format!("invalid TOML: {}", e.message()),
+        }],
+    })
+}
+
+/// Path of the base config `value` extends, resolved relative to `file`
+fn extends_path(file: &Path, value: &Value) -> Option<PathBuf> {
+    let extends = value.get(EXTENDS_KEY)?.as_str()?;
+
+    Some(normalize_path(&file.parent().map_or_else(
+        || PathBuf::from(extends),
+        |dir| dir.join(extends),
+    )))
+}
+
+/// Returns the workspace's `clippier.toml` files: the root one, those of every workspace
+/// member, and the base configs they extend.
+///
+/// # Errors
+///
+/// * If the workspace `Cargo.toml` exists but cannot be read or parsed
+pub fn find_clippier_tomls(workspace_root: &Path) -> Result<Vec<PathBuf>, BoxError> {
+    let mut candidates = vec![workspace_root.join("clippier.toml")];
+
+    let cargo_path = workspace_root.join("Cargo.toml");
+    if switchy_fs::exists(&cargo_path) {
+        let workspace_toml: Value =
+            toml::from_str(&switchy_fs::sync::read_to_string(&cargo_path)?)?;
+        let member_patterns: Vec<&str> = workspace_toml
+            .get("workspace")
+            .and_then(|w| w.get("members"))
+            .and_then(Value::as_array)
+            .map(|a| a.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        candidates.extend(
+            expand_workspace_member_globs(workspace_root, &member_patterns)
+                .into_iter()
+                .map(|member| workspace_root.join(member).join("clippier.toml")),
+        );
+    }
+
+    let mut seen = BTreeSet::new();
+    let mut files = vec![];
+    let mut index = 0;
+
+    while index < candidates.len() {
+        let path = normalize_path(&candidates[index]);
+        index += 1;
+
+        if !switchy_fs::exists(&path) || !seen.insert(path.clone()) {
+            continue;
+        }
+
+        if let Some(base_path) = switchy_fs::sync::read_to_string(&path)
+            .ok()
+            .and_then(|source| toml::from_str::<Value>(&source).ok())
+            .and_then(|value| extends_path(&path, &value))
+        {
+            candidates.push(base_path);
+        }
+
+        files.push(path);
+    }
+
+    Ok(files)
+}
+
+/// Validates every `clippier.toml` in the workspace (see [`find_clippier_tomls`]).
+///
+/// Files in the report are relative to `workspace_root`.
+///
+/// # Errors
+///
+/// * If the workspace `Cargo.toml` exists but cannot be read or parsed
+/// * If a `clippier.toml` file cannot be read
+pub fn validate_workspace_configs(
+    workspace_root: &Path,
+) -> Result<ConfigValidationReport, BoxError> {
+    let workspace_root = normalize_path(workspace_root);
+    let relative = |path: &Path| {
+        path.strip_prefix(&workspace_root)
+            .map_or_else(|_| path.to_path_buf(), Path::to_path_buf)
+    };
+
+    let mut files_checked = vec![];
+    let mut diagnostics = vec![];
+
+    for path in find_clippier_tomls(&workspace_root)? {
+        let file = relative(&path);
+
+        diagnostics.extend(
+            validate_clippier_toml(&path)?
+                .into_iter()
+                .map(|diagnostic| ConfigDiagnostic {
+                    file: file.clone(),
+                    ..diagnostic
+                }),
+        );
+        files_checked.push(file);
+    }
+
+    Ok(ConfigValidationReport {
+        files_checked,
+        diagnostics,
+    })
+}
+
+/// Formats a config validation report for human consumption
+#[must_use]
+pub fn format_config_validation_report(report: &ConfigValidationReport) -> String {
+    use std::fmt::Write as _;
+
+    let mut output = String::new();
+
+    let _ = writeln!(
+        output,
+        "Checked {} clippier.toml files",
+        report.files_checked.len()
+    );
+
+    if report.is_clean() {
+        let _ = writeln!(output, "✅ All clippier.toml files are valid");
+        return output;
+    }
+
+    let _ = writeln!(output, "❌ Found {} problems:", report.diagnostics.len());
+
+    for diagnostic in &report.diagnostics {
+        let _ = writeln!(output, "  📄 {diagnostic}");
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggest_key_matches_snake_case_spelling() {
+        let known = ["skip-features", "required-features", "nightly"];
+
+        assert_eq!(
+            suggest_key("skip_features", known.into_iter()),
+            Some("skip-features")
+        );
+        assert_eq!(suggest_key("nightyl", known.into_iter()), Some("nightly"));
+        assert_eq!(suggest_key("toolchain", known.into_iter()), None);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", "os"), 2);
+        assert_eq!(edit_distance("os", "os"), 0);
+        assert_eq!(edit_distance("nightly", "nigthly"), 2);
+        assert_eq!(edit_distance("env", "envs"), 1);
+    }
+}
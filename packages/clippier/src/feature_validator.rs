@@ -48,6 +48,7 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{Result, anyhow};
 use chrono;
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 use toml::Value;
 
@@ -282,8 +283,29 @@ pub struct MissingFeatureExposure {
     pub chain: Vec<String>,
 }
 
+/// Output format of the `validate-feature-propagation` command
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+#[clap(rename_all = "kebab_case")]
+pub enum ValidationOutputType {
+    /// Human-readable report
+    Raw,
+    /// [`ValidationResult`] as JSON
+    Json,
+    /// SARIF 2.1.0 log for code scanning (see [`validation_result_to_sarif`])
+    Sarif,
+}
+
+impl From<ValidationOutputType> for OutputType {
+    fn from(value: ValidationOutputType) -> Self {
+        match value {
+            ValidationOutputType::Raw => Self::Raw,
+            ValidationOutputType::Json | ValidationOutputType::Sarif => Self::Json,
+        }
+    }
+}
+
 /// Validation results for feature propagation
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 pub struct ValidationResult {
     /// Total number of packages validated
     pub total_packages: usize,
@@ -302,6 +324,13 @@ pub struct ValidationResult {
     /// Parent package validation results
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub parent_results: Vec<ParentValidationResult>,
+    /// Root of the validated workspace
+    #[serde(skip)]
+    pub workspace_root: PathBuf,
+    /// Paths of the validated workspace members relative to the workspace root, by
+    /// package name
+    #[serde(skip)]
+    pub package_paths: BTreeMap<String, String>,
 }
 
 /// An error that was overridden by configuration
@@ -660,6 +689,8 @@ impl FeatureValidator {
             overridden_errors,
             override_summary,
             parent_results,
+            workspace_root: self.workspace_root.clone(),
+            package_paths: self.package_paths.clone(),
         })
     }
 
@@ -974,8 +1005,7 @@ impl FeatureValidator {
             return Ok(Vec::new());
         }
 
-        let content = switchy_fs::sync::read_to_string(&config_path)?;
-        let value: Value = toml::from_str(&content)?;
+        let value = crate::load_clippier_toml(&config_path).map_err(|e| anyhow!("{e}"))?;
 
         let mut overrides = Vec::new();
         if let Some(feature_validation) = value.get("feature-validation")
@@ -1460,8 +1490,7 @@ impl FeatureValidator {
             return Ok(None);
         }
 
-        let content = switchy_fs::sync::read_to_string(&config_path)?;
-        let value: Value = toml::from_str(&content)?;
+        let value = crate::load_clippier_toml(&config_path).map_err(|e| anyhow!("{e}"))?;
 
         if let Some(feature_validation) = value.get("feature-validation")
             && let Some(parent) = feature_validation.get("parent")
@@ -2030,6 +2059,175 @@ pub fn print_github_output(result: &ValidationResult) {
     }
 }
 
+/// SARIF rule id for a dependency feature that is not propagated
+pub const SARIF_RULE_MISSING_PROPAGATION: &str = "missing-feature-propagation";
+/// SARIF rule id for a propagation entry that references a missing dependency or feature
+pub const SARIF_RULE_INCORRECT_PROPAGATION: &str = "incorrect-feature-propagation";
+/// SARIF rule id for a dependency feature a parent package does not expose
+pub const SARIF_RULE_MISSING_EXPOSURE: &str = "missing-feature-exposure";
+
+/// Converts validation results into a SARIF 2.1.0 log
+///
+/// Every missing propagation, incorrect propagation and missing parent exposure becomes a
+/// SARIF result located in the package's `Cargo.toml`, using the member paths the
+/// validation loaded ([`ValidationResult::package_paths`]). When the feature's entry can
+/// be found in the `[features]` table, the result points at that line. Results for
+/// packages without a known path have no location.
+#[must_use]
+pub fn validation_result_to_sarif(result: &ValidationResult) -> serde_json::Value {
+    let mut sources = BTreeMap::new();
+    let mut results = Vec::new();
+
+    let mut push = |rule_id: &str, package: &str, feature: Option<&str>, message: String| {
+        let locations = result.package_paths.get(package).map(|member_path| {
+            let uri = if member_path == "." {
+                "Cargo.toml".to_string()
+            } else {
+                format!("{}/Cargo.toml", member_path.trim_end_matches('/'))
+            };
+            let source = sources.entry(uri.clone()).or_insert_with(|| {
+                switchy_fs::sync::read_to_string(result.workspace_root.join(&uri))
+                    .unwrap_or_default()
+            });
+
+            let mut location = serde_json::json!({
+                "physicalLocation": {
+                    "artifactLocation": { "uri": uri },
+                },
+            });
+            if let Some(line) = find_feature_line(source, feature) {
+                location["physicalLocation"]["region"] = serde_json::json!({ "startLine": line });
+            }
+
+            vec![location]
+        });
+
+        results.push(serde_json::json!({
+            "ruleId": rule_id,
+            "level": "error",
+            "message": { "text": message },
+            "locations": locations.unwrap_or_default(),
+        }));
+    };
+
+    for error in &result.errors {
+        for feature_error in &error.errors {
+            let feature = feature_error.feature.as_str();
+
+            for missing in &feature_error.missing_propagations {
+                push(
+                    SARIF_RULE_MISSING_PROPAGATION,
+                    &error.package,
+                    Some(feature),
+                    format!(
+                        "Missing feature propagation '{}' for feature '{feature}': {}",
+                        missing.expected, missing.reason
+                    ),
+                );
+            }
+
+            for incorrect in &feature_error.incorrect_propagations {
+                push(
+                    SARIF_RULE_INCORRECT_PROPAGATION,
+                    &error.package,
+                    Some(feature),
+                    format!(
+                        "Incorrect feature propagation '{}' for feature '{feature}': {}",
+                        incorrect.entry, incorrect.reason
+                    ),
+                );
+            }
+        }
+    }
+
+    for parent_result in &result.parent_results {
+        for exposure in &parent_result.missing_exposures {
+            push(
+                SARIF_RULE_MISSING_EXPOSURE,
+                &parent_result.package,
+                None,
+                format!(
+                    "Missing feature exposure '{}' for dependency '{}' feature '{}'",
+                    exposure.expected_parent_feature,
+                    exposure.dependency,
+                    exposure.dependency_feature
+                ),
+            );
+        }
+    }
+
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "clippier",
+                    "informationUri": "https://github.com/MoosicBox/MoosicBox",
+                    "rules": [
+                        {
+                            "id": SARIF_RULE_MISSING_PROPAGATION,
+                            "shortDescription": {
+                                "text": "A dependency feature is not propagated",
+                            },
+                        },
+                        {
+                            "id": SARIF_RULE_INCORRECT_PROPAGATION,
+                            "shortDescription": {
+                                "text": "A feature propagates to a missing dependency or feature",
+                            },
+                        },
+                        {
+                            "id": SARIF_RULE_MISSING_EXPOSURE,
+                            "shortDescription": {
+                                "text": "A parent package does not expose a dependency feature",
+                            },
+                        },
+                    ],
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// Finds the 1-based line of `feature` in the `[features]` table of a `Cargo.toml`
+///
+/// Falls back to the `[features]` header line when the feature's entry can't be found.
+fn find_feature_line(source: &str, feature: Option<&str>) -> Option<usize> {
+    let mut features_line = None;
+
+    for (index, line) in source.lines().enumerate() {
+        let line = line.trim();
+
+        if line.starts_with('[') {
+            if features_line.is_some() {
+                break;
+            }
+            if line == "[features]" {
+                features_line = Some(index + 1);
+            }
+            continue;
+        }
+
+        if features_line.is_none() {
+            continue;
+        }
+
+        let Some(feature) = feature else {
+            break;
+        };
+
+        if let Some((key, _)) = line.split_once('=')
+            && key.trim().trim_matches('"') == feature
+        {
+            return Some(index + 1);
+        }
+    }
+
+    features_line
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2512,6 +2710,7 @@ dev_dep = "1.0"
             overridden_errors: vec![],
             override_summary: None,
             parent_results: vec![],
+            ..Default::default()
         };
 
         // Should be able to serialize to JSON
@@ -234,6 +234,43 @@ pub fn get_changed_files_from_git(
     Ok(changed_files)
 }
 
+/// Extract list of files with uncommitted changes in the git working tree
+///
+/// Includes staged, unstaged and untracked (but not ignored) files, relative to the
+/// repository root.
+///
+/// # Errors
+///
+/// * If the repository is not found
+/// * If the repository status cannot be read
+pub fn get_uncommitted_changed_files(workspace_root: &Path) -> Result<Vec<String>, BoxError> {
+    let repo = Repository::open(workspace_root)?;
+
+    let mut options = git2::StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false);
+
+    let statuses = repo.statuses(Some(&mut options))?;
+
+    let mut changed_files: Vec<String> = statuses
+        .iter()
+        .filter(|entry| entry.status() != git2::Status::CURRENT)
+        .filter_map(|entry| entry.path().map(ToString::to_string))
+        .collect();
+
+    changed_files.sort();
+    changed_files.dedup();
+
+    log::debug!(
+        "Found {} uncommitted changed files: {changed_files:?}",
+        changed_files.len()
+    );
+
+    Ok(changed_files)
+}
+
 /// Extract changed external dependencies from git diff
 ///
 /// # Errors
@@ -0,0 +1,501 @@
+//! Detection of default features that pull in heavy optional dependencies.
+//!
+//! An optional dependency counts as enabled by `default` when a feature reachable from
+//! `default` activates it, through any of:
+//!
+//! * `dep:name`
+//! * `name/feature` (but not `name?/feature`, which only forwards the feature if the
+//!   dependency is already enabled)
+//! * `name`, the implicit feature of an optional dependency
+//!
+//! The cost of a dependency is the number of packages it transitively depends on in the
+//! workspace's `Cargo.lock`. `Cargo.lock` lists the dependencies of every feature, so this
+//! is an upper bound. Dependencies missing from `Cargo.lock` count as having none.
+//!
+//! Packages that use a workspace member with its default features, while that member's
+//! defaults pull in heavy optional dependencies, are reported as well, since they could
+//! set `default-features = false` to avoid building them.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    path::Path,
+};
+
+use serde::Serialize;
+use toml::Value;
+
+use crate::{
+    BoxError, get_dependency_default_features,
+    unused_features::{Member, dependency_package_name, load_members, string_array},
+};
+
+/// An optional dependency that a package's `default` feature pulls in
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct HeavyDefaultDependency {
+    /// Name of the package whose defaults enable the dependency
+    pub package: String,
+    /// Name of the dependency, as keyed in the package's `Cargo.toml`
+    pub dependency: String,
+    /// Number of packages the dependency transitively depends on
+    pub transitive_dependencies: usize,
+    /// Features from `default` to the entry that activates the dependency, e.g.
+    /// `["default", "net", "dep:reqwest"]`
+    pub chain: Vec<String>,
+}
+
+/// A workspace dependency that a package uses with its default features, although they
+/// pull in heavy optional dependencies
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct HeavyDefaultFeaturesUse {
+    /// Name of the package using the dependency
+    pub package: String,
+    /// Name of the dependency, as keyed in the package's `Cargo.toml`
+    pub dependency: String,
+    /// Heavy optional dependencies of the dependency that its defaults pull in
+    pub heavy_dependencies: Vec<String>,
+    /// Number of distinct packages the heavy optional dependencies transitively depend on
+    pub transitive_dependencies: usize,
+}
+
+/// Result of auditing the workspace for heavy default dependencies
+#[derive(Debug, Clone, Serialize)]
+pub struct HeavyDefaultsReport {
+    /// Number of packages checked
+    pub packages_checked: usize,
+    /// Minimum number of transitive dependencies for a dependency to be flagged
+    pub min_transitive_dependencies: usize,
+    /// Flagged dependencies, sorted by package and dependency
+    pub flagged: Vec<HeavyDefaultDependency>,
+    /// Workspace dependencies used with default features that pull in flagged
+    /// dependencies, sorted by package and dependency
+    pub default_features_uses: Vec<HeavyDefaultFeaturesUse>,
+}
+
+impl HeavyDefaultsReport {
+    /// Whether no package's defaults pull in a heavy optional dependency
+    #[must_use]
+    pub const fn is_clean(&self) -> bool {
+        self.flagged.is_empty()
+    }
+}
+
+/// Dependency graph of the packages in a `Cargo.lock`
+#[derive(Debug, Default)]
+pub(crate) struct LockGraph {
+    /// Package indices by name, one per locked version
+    by_name: BTreeMap<String, Vec<usize>>,
+    /// Name, version and dependency specs of each package
+    packages: Vec<(String, String, Vec<String>)>,
+}
+
+impl LockGraph {
+    /// Loads the `Cargo.lock` of `workspace_root`, or an empty graph if it has none.
+    ///
+    /// # Errors
+    ///
+    /// * If the `Cargo.lock` cannot be read or parsed
+    pub(crate) fn load(workspace_root: &Path) -> Result<Self, BoxError> {
+        let path = workspace_root.join("Cargo.lock");
+        if !switchy_fs::exists(&path) {
+            log::warn!(
+                "No Cargo.lock in {}, dependency costs are unknown",
+                workspace_root.display()
+            );
+            return Ok(Self::default());
+        }
+
+        let lock: Value = toml::from_str(&switchy_fs::sync::read_to_string(&path)?)?;
+        let mut graph = Self::default();
+
+        for package in lock
+            .get("package")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let (Some(name), Some(version)) = (
+                package.get("name").and_then(Value::as_str),
+                package.get("version").and_then(Value::as_str),
+            ) else {
+                continue;
+            };
+
+            graph
+                .by_name
+                .entry(name.to_string())
+                .or_default()
+                .push(graph.packages.len());
+            graph.packages.push((
+                name.to_string(),
+                version.to_string(),
+                string_array(package.get("dependencies")).collect(),
+            ));
+        }
+
+        Ok(graph)
+    }
+
+    /// Resolves a `Cargo.lock` dependency spec (`name`, `name version` or
+    /// `name version (source)`) to package indices
+    fn resolve(&self, spec: &str) -> impl Iterator<Item = usize> + '_ {
+        let mut parts = spec.split_whitespace();
+        let name = parts.next().unwrap_or_default();
+        let version = parts.next();
+
+        self.by_name
+            .get(name)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(move |&index| version.is_none_or(|v| self.packages[index].1 == v))
+    }
+
+    /// Number of distinct packages that the package `name` transitively depends on,
+    /// across all of its locked versions
+    pub(crate) fn transitive_dependency_count(&self, name: &str) -> usize {
+        self.transitive_dependencies(name).len()
+    }
+
+    /// Indices of the packages that the package `name` transitively depends on, across all
+    /// of its locked versions
+    fn transitive_dependencies(&self, name: &str) -> BTreeSet<usize> {
+        let roots: BTreeSet<usize> = self
+            .by_name
+            .get(name)
+            .into_iter()
+            .flatten()
+            .copied()
+            .collect();
+
+        let mut seen = BTreeSet::new();
+        let mut queue: VecDeque<usize> = roots.iter().copied().collect();
+
+        while let Some(index) = queue.pop_front() {
+            for spec in &self.packages[index].2 {
+                for dependency in self.resolve(spec) {
+                    if !roots.contains(&dependency) && seen.insert(dependency) {
+                        queue.push_back(dependency);
+                    }
+                }
+            }
+        }
+
+        seen
+    }
+}
+
+/// Optional normal dependencies of `cargo_toml`, keyed by dependency key, with their
+/// package names
+fn optional_dependencies(
+    cargo_toml: &Value,
+    workspace_dependencies: Option<&toml::map::Map<String, Value>>,
+) -> BTreeMap<String, String> {
+    let targets = cargo_toml
+        .get("target")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values());
+
+    std::iter::once(cargo_toml)
+        .chain(targets)
+        .filter_map(|table| table.get("dependencies").and_then(Value::as_table))
+        .flatten()
+        .filter(|(_, value)| value.get("optional").and_then(Value::as_bool) == Some(true))
+        .map(|(key, value)| {
+            let workspace_value = workspace_dependencies.and_then(|deps| deps.get(key));
+            (
+                key.clone(),
+                dependency_package_name(key, value, workspace_value),
+            )
+        })
+        .collect()
+}
+
+/// Finds the optional dependencies that `feature` enables, directly or through the
+/// features it enables, along with the feature chain activating each.
+///
+/// Keys are dependency keys of `optional`.
+pub(crate) fn feature_enabled_optional_dependencies(
+    cargo_toml: &Value,
+    optional: &BTreeMap<String, String>,
+    feature: &str,
+) -> BTreeMap<String, Vec<String>> {
+    let features = cargo_toml.get("features").and_then(Value::as_table);
+    let mut enabled: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    let mut visited = BTreeSet::from([feature.to_string()]);
+    let mut queue = VecDeque::from([vec![feature.to_string()]]);
+
+    while let Some(chain) = queue.pop_front() {
+        let current = chain.last().map(String::as_str).unwrap_or_default();
+
+        for entry in string_array(features.and_then(|f| f.get(current))) {
+            let activated = match entry.split_once('/') {
+                Some((dependency, _)) if dependency.ends_with('?') => None,
+                Some((dependency, _)) => Some(dependency.to_string()),
+                None => entry.strip_prefix("dep:").map(ToString::to_string),
+            };
+
+            if let Some(dependency) = activated {
+                if optional.contains_key(&dependency) && !enabled.contains_key(&dependency) {
+                    let mut chain = chain.clone();
+                    chain.push(entry.clone());
+                    enabled.insert(dependency, chain);
+                }
+            } else if features.is_some_and(|f| f.contains_key(&entry)) {
+                if visited.insert(entry.clone()) {
+                    let mut chain = chain.clone();
+                    chain.push(entry);
+                    queue.push_back(chain);
+                }
+            } else if optional.contains_key(&entry) && !enabled.contains_key(&entry) {
+                // Implicit feature of an optional dependency
+                let mut chain = chain.clone();
+                chain.push(entry.clone());
+                enabled.insert(entry, chain);
+            }
+        }
+    }
+
+    enabled
+}
+
+/// Finds the optional dependencies the `default` feature of `member` pulls in that
+/// transitively depend on at least `min_transitive_dependencies` packages.
+pub(crate) fn heavy_default_dependencies(
+    member: &Member,
+    workspace_dependencies: Option<&toml::map::Map<String, Value>>,
+    lock: &LockGraph,
+    min_transitive_dependencies: usize,
+    ignore_dependencies: &[String],
+) -> Vec<HeavyDefaultDependency> {
+    let optional = optional_dependencies(&member.cargo_toml, workspace_dependencies);
+
+    feature_enabled_optional_dependencies(&member.cargo_toml, &optional, "default")
+        .into_iter()
+        .filter(|(dependency, _)| !ignore_dependencies.contains(dependency))
+        .filter_map(|(dependency, chain)| {
+            let transitive_dependencies = lock.transitive_dependency_count(&optional[&dependency]);
+
+            (transitive_dependencies >= min_transitive_dependencies).then(|| {
+                HeavyDefaultDependency {
+                    package: member.name.clone(),
+                    dependency,
+                    transitive_dependencies,
+                    chain,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Whether a dependency declared as `value` keeps its default features.
+///
+/// A dependency inherited with `workspace = true` can only disable default features that
+/// the `[workspace.dependencies]` entry disables as well.
+fn keeps_default_features(value: &Value, workspace_value: Option<&Value>) -> bool {
+    let default_features = get_dependency_default_features(value);
+
+    if value.get("workspace").and_then(Value::as_bool) == Some(true) {
+        workspace_value
+            .and_then(get_dependency_default_features)
+            .unwrap_or(true)
+            || default_features == Some(true)
+    } else {
+        default_features.unwrap_or(true)
+    }
+}
+
+/// Finds the workspace members that `member` depends on with their default features
+/// while those pull in the `flagged` heavy optional dependencies, keyed by package name.
+fn heavy_default_features_uses(
+    member: &Member,
+    workspace_dependencies: Option<&toml::map::Map<String, Value>>,
+    lock: &LockGraph,
+    flagged: &BTreeMap<&str, Vec<&HeavyDefaultDependency>>,
+    members: &BTreeMap<&str, &Member>,
+) -> Vec<HeavyDefaultFeaturesUse> {
+    let targets = member
+        .cargo_toml
+        .get("target")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values());
+
+    std::iter::once(&member.cargo_toml)
+        .chain(targets)
+        .filter_map(|table| table.get("dependencies").and_then(Value::as_table))
+        .flatten()
+        .filter_map(|(key, value)| {
+            let workspace_value = workspace_dependencies.and_then(|deps| deps.get(key));
+            let name = dependency_package_name(key, value, workspace_value);
+            let heavy = flagged.get(name.as_str())?;
+
+            if !keeps_default_features(value, workspace_value) {
+                return None;
+            }
+
+            let dependency_member = members.get(name.as_str())?;
+            let optional =
+                optional_dependencies(&dependency_member.cargo_toml, workspace_dependencies);
+            let transitive_dependencies = heavy
+                .iter()
+                .filter_map(|x| optional.get(&x.dependency))
+                .flat_map(|x| lock.transitive_dependencies(x))
+                .collect::<BTreeSet<_>>()
+                .len();
+
+            Some(HeavyDefaultFeaturesUse {
+                package: member.name.clone(),
+                dependency: key.clone(),
+                heavy_dependencies: heavy.iter().map(|x| x.dependency.clone()).collect(),
+                transitive_dependencies,
+            })
+        })
+        .collect()
+}
+
+/// Finds packages whose `default` feature enables optional dependencies that transitively
+/// depend on at least `min_transitive_dependencies` packages, and the packages that use
+/// them with their default features.
+///
+/// Dependencies listed in `ignore_dependencies` are never reported.
+///
+/// # Errors
+///
+/// * If the workspace `Cargo.toml` cannot be read or parsed
+/// * If any member `Cargo.toml` cannot be read or parsed
+/// * If the workspace `Cargo.lock` cannot be read or parsed
+pub fn find_heavy_default_dependencies(
+    workspace_root: &Path,
+    min_transitive_dependencies: usize,
+    ignore_dependencies: &[String],
+) -> Result<HeavyDefaultsReport, BoxError> {
+    let workspace_source = switchy_fs::sync::read_to_string(workspace_root.join("Cargo.toml"))?;
+    let workspace_toml: Value = toml::from_str(&workspace_source)?;
+
+    let workspace_dependencies = workspace_toml
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(Value::as_table);
+
+    let members = load_members(workspace_root, &workspace_toml)?;
+    let lock = LockGraph::load(workspace_root)?;
+
+    let mut flagged = members
+        .iter()
+        .flat_map(|member| {
+            heavy_default_dependencies(
+                member,
+                workspace_dependencies,
+                &lock,
+                min_transitive_dependencies,
+                ignore_dependencies,
+            )
+        })
+        .collect::<Vec<_>>();
+    flagged.sort();
+
+    for entry in &flagged {
+        log::debug!(
+            "{}: default enables '{}' ({} transitive dependencies)",
+            entry.package,
+            entry.dependency,
+            entry.transitive_dependencies
+        );
+    }
+
+    let mut flagged_by_package: BTreeMap<&str, Vec<&HeavyDefaultDependency>> = BTreeMap::new();
+    for entry in &flagged {
+        flagged_by_package
+            .entry(entry.package.as_str())
+            .or_default()
+            .push(entry);
+    }
+    let members_by_name = members
+        .iter()
+        .map(|member| (member.name.as_str(), member))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut default_features_uses = members
+        .iter()
+        .flat_map(|member| {
+            heavy_default_features_uses(
+                member,
+                workspace_dependencies,
+                &lock,
+                &flagged_by_package,
+                &members_by_name,
+            )
+        })
+        .collect::<Vec<_>>();
+    default_features_uses.sort();
+
+    Ok(HeavyDefaultsReport {
+        packages_checked: members.len(),
+        min_transitive_dependencies,
+        flagged,
+        default_features_uses,
+    })
+}
+
+/// Formats a heavy defaults report for human consumption
+#[must_use]
+pub fn format_heavy_defaults_report(report: &HeavyDefaultsReport) -> String {
+    use std::fmt::Write as _;
+
+    let mut output = String::new();
+
+    let _ = writeln!(
+        output,
+        "Checked the default features of {} packages",
+        report.packages_checked
+    );
+
+    if report.is_clean() {
+        let _ = writeln!(
+            output,
+            "✅ No default features pull in optional dependencies with {}+ transitive dependencies",
+            report.min_transitive_dependencies
+        );
+        return output;
+    }
+
+    let _ = writeln!(
+        output,
+        "⚠️  Found {} heavy optional dependencies enabled by default:",
+        report.flagged.len()
+    );
+
+    for entry in &report.flagged {
+        let _ = writeln!(
+            output,
+            "  📦 {}: {} ({} transitive dependencies) via {}",
+            entry.package,
+            entry.dependency,
+            entry.transitive_dependencies,
+            entry.chain.join(" → ")
+        );
+    }
+
+    if !report.default_features_uses.is_empty() {
+        let _ = writeln!(
+            output,
+            "⚠️  Found {} workspace dependencies used with heavy default features:",
+            report.default_features_uses.len()
+        );
+
+        for entry in &report.default_features_uses {
+            let _ = writeln!(
+                output,
+                "  📦 {}: {} pulls in {} ({} transitive dependencies), consider `default-features = false`",
+                entry.package,
+                entry.dependency,
+                entry.heavy_dependencies.join(", "),
+                entry.transitive_dependencies
+            );
+        }
+    }
+
+    output
+}
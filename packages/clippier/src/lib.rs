@@ -172,6 +172,79 @@ pub mod git_diff;
 /// ```
 pub mod feature_validator;
 
+/// Base config inheritance for `clippier.toml` files.
+///
+/// Resolves the `extends` key that lets a package's `clippier.toml` inherit a shared
+/// base config and override individual fields.
+pub mod config_extends;
+
+/// Strict schema validation of `clippier.toml` files.
+///
+/// Reports unknown keys, type mismatches and obviously wrong values that loading a
+/// `clippier.toml` would otherwise silently ignore.
+pub mod config_validation;
+
+/// Workspace-hack feature completeness checking.
+///
+/// Computes the union of features enabled on each external dependency across the
+/// workspace and reports entries missing from a designated workspace-hack package.
+pub mod workspace_hack;
+
+/// Unreferenced feature flag detection.
+///
+/// Reports features defined by workspace members that no other feature, dependent
+/// package, `clippier.toml` filter or target `required-features` list references.
+pub mod unused_features;
+
+/// Heavy default dependency detection.
+///
+/// Reports optional dependencies that a package's `default` feature enables, directly or
+/// through other features, when they pull a large dependency tree into `Cargo.lock`.
+pub mod heavy_defaults;
+
+/// Structured export of the workspace dependency graph.
+///
+/// Describes every workspace member and the dependencies between them, including the
+/// section each dependency is declared in and the features that enable optional ones,
+/// for consumption by external tooling.
+pub mod package_graph;
+
+/// Features matrix comparison.
+///
+/// Compares two previously generated features matrices and reports the jobs added,
+/// removed and changed between them, keyed by package, OS and feature set.
+pub mod matrix_diff;
+
+/// Typed features matrix entries.
+///
+/// Provides [`MatrixEntry`], the structured form of the JSON objects emitted by the
+/// `features` command, for library consumers of [`features_matrix_entries`].
+pub mod matrix_entry;
+
+/// Target triple and `cfg(...)` expression evaluation.
+///
+/// Evaluates `[target.'cfg(...)'.dependencies]` keys against a `--target` triple so the
+/// features matrix and affected-package analysis only consider active dependencies.
+pub mod target_cfg;
+
+/// Workspace-wide system dependency aggregation.
+///
+/// Merges the package install commands of every `clippier.toml` in the workspace into one
+/// deduplicated install command per package manager.
+pub mod system_deps;
+
+/// Nix development shell generation.
+///
+/// Maps the workspace's system dependencies and toolchains to `nixpkgs` attributes and
+/// renders them as a `shell.nix` or `flake.nix` `devShell`.
+pub mod nix_shell;
+
+/// Regenerating command output when workspace files change.
+///
+/// Watches the workspace's `Cargo.toml` and `clippier.toml` files and reruns a command,
+/// debounced, whenever they change. Backs the `--watch` option of the CLI.
+pub mod watch;
+
 /// Testing utilities for workspace analysis.
 ///
 /// This module provides test helpers and utilities for creating test workspaces
@@ -184,11 +257,38 @@ pub mod test_utils;
 #[cfg(any(test, feature = "test-utils"))]
 pub use test_utils::*;
 
+pub use config_extends::{load_clippier_conf, load_clippier_toml};
+pub use config_validation::{
+    ConfigDiagnostic, ConfigValidationReport, format_config_validation_report,
+};
 pub use feature_validator::{
-    FeatureValidator, ValidationResult, ValidatorConfig, print_github_output, print_human_output,
+    FeatureValidator, ValidationOutputType, ValidationResult, ValidatorConfig, print_github_output,
+    print_human_output, validation_result_to_sarif,
+};
+pub use heavy_defaults::{
+    HeavyDefaultDependency, HeavyDefaultFeaturesUse, HeavyDefaultsReport,
+    format_heavy_defaults_report,
+};
+pub use matrix_diff::{
+    MatrixDiff, MatrixJob, PackageMatrixChange, diff_matrices, format_matrix_diff,
+    parse_matrix_jobs,
+};
+pub use matrix_entry::MatrixEntry;
+pub use nix_shell::{
+    NixShell, NixShellFormat, UnmappedDependency, format_nix_shell, nixpkgs_attrs,
+    parse_nix_overrides,
+};
+pub use package_graph::{
+    DependencyEdge, DependencyEdgeKind, PackageGraph, PackageNode, format_package_graph,
 };
 #[cfg(feature = "publish")]
 pub use publish::{PublishConfig, handle_publish_command};
+pub use system_deps::{PackageManager, WorkspaceSystemDeps, format_workspace_system_deps};
+pub use target_cfg::{CfgExpr, TargetCfgError, TargetTriple, filter_manifest_for_target};
+pub use unused_features::{
+    UnreferencedFeature, UnusedFeaturesReport, format_unused_features_report,
+};
+pub use workspace_hack::{WorkspaceHackReport, format_workspace_hack_report};
 
 /// Output format for CLI commands
 #[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
@@ -264,21 +364,51 @@ pub struct Step {
     features: Option<Vec<String>>,
 }
 
-/// Environment variable configuration that can be filtered by features
+/// Environment variable configuration that can be filtered by features and profiles
 #[derive(Debug, Clone, Deserialize)]
 #[serde(untagged)]
 pub enum ClippierEnv {
     /// Simple string value
     Value(String),
-    /// Value with optional feature filtering
+    /// Value with optional feature and profile filtering
     FilteredValue {
         /// The environment variable value
         value: String,
         /// Features that activate this environment variable
         features: Option<Vec<String>>,
+        /// Environment profiles (e.g. "ci", "release") that activate this environment variable
+        profile: Option<Vec<String>>,
     },
 }
 
+impl ClippierEnv {
+    /// Whether this environment variable applies to the given active features and selected
+    /// environment profiles.
+    ///
+    /// Feature and profile filters are independent: when both are specified, both must match.
+    /// A profile-gated value is only included when one of its profiles was selected.
+    #[must_use]
+    pub fn matches(
+        &self,
+        expanded_features: &BTreeSet<String>,
+        env_profile: Option<&[String]>,
+    ) -> bool {
+        match self {
+            Self::Value(..) => true,
+            Self::FilteredValue {
+                features, profile, ..
+            } => {
+                features.as_ref().is_none_or(|f| {
+                    f.iter()
+                        .any(|required| expanded_features.contains(required))
+                }) && profile.as_ref().is_none_or(|p| {
+                    env_profile.is_some_and(|selected| p.iter().any(|x| selected.contains(x)))
+                })
+            }
+        }
+    }
+}
+
 /// Helper type that accepts either a single item or a vector of items
 #[derive(Debug, Clone, Deserialize)]
 #[serde(untagged)]
@@ -317,7 +447,7 @@ impl<T> Default for VecOrItem<T> {
 /// nightly = true
 /// cargo = ["--locked"]
 /// skip-features = ["dev", "test"]
-/// required-features = ["production"]
+/// required-features = ["production", { feature = "pulseaudio", os = "ubuntu" }]
 /// ```
 #[derive(Debug, Clone, Default, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -329,7 +459,90 @@ pub struct RustConfig {
     /// Features to skip during testing/CI
     pub skip_features: Option<Vec<String>>,
     /// Features required for this configuration
-    pub required_features: Option<Vec<String>>,
+    pub required_features: Option<Vec<RequiredFeature>>,
+}
+
+/// An entry of `required-features`, optionally scoped to some OSes.
+///
+/// A plain string is required on every OS. A table with `feature` and `os` is only
+/// required in matrix entries for those OSes, and is skipped on every other OS.
+///
+/// # Example
+///
+/// ```toml
+/// [rust]
+/// required-features = [
+///     "production",
+///     { feature = "pulseaudio", os = "ubuntu" },
+///     { feature = "coreaudio", os = ["macos"] },
+/// ]
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum RequiredFeature {
+    /// A feature required on every OS
+    Feature(String),
+    /// A feature only required on the given OSes
+    Scoped {
+        /// Name of the feature (supports wildcards)
+        feature: String,
+        /// OSes the feature is required on
+        os: VecOrItem<String>,
+    },
+}
+
+impl RequiredFeature {
+    /// Name of the feature (may contain wildcards)
+    #[must_use]
+    pub fn feature(&self) -> &str {
+        match self {
+            Self::Feature(feature) | Self::Scoped { feature, .. } => feature,
+        }
+    }
+
+    /// Whether the feature is required on `os`
+    #[must_use]
+    pub fn applies_to(&self, os: &str) -> bool {
+        match self {
+            Self::Feature(..) => true,
+            Self::Scoped {
+                os: VecOrItem::Value(x),
+                ..
+            } => x == os,
+            Self::Scoped {
+                os: VecOrItem::Values(x),
+                ..
+            } => x.iter().any(|x| x == os),
+        }
+    }
+}
+
+/// Splits `required_features` into the features required on `os` and the OS-scoped
+/// features that only apply to other OSes.
+///
+/// A feature scoped to other OSes that is also required on `os` by another entry is only
+/// returned as required.
+#[must_use]
+pub fn required_features_for_os(
+    required_features: &[RequiredFeature],
+    os: &str,
+) -> (Vec<String>, Vec<String>) {
+    let (required, other): (Vec<_>, Vec<_>) =
+        required_features.iter().partition(|x| x.applies_to(os));
+
+    let required = required
+        .into_iter()
+        .map(|x| x.feature().to_string())
+        .unique()
+        .collect::<Vec<_>>();
+    let other = other
+        .into_iter()
+        .map(|x| x.feature().to_string())
+        .filter(|x| !required.contains(x))
+        .unique()
+        .collect();
+
+    (required, other)
 }
 
 /// Node.js-specific configuration options.
@@ -390,6 +603,40 @@ pub struct ParallelizationConfig {
     pub chunked: u16,
 }
 
+/// A group of features that cannot be enabled in the same job
+///
+/// # Example
+///
+/// ```toml
+/// [[mutually-exclusive]]
+/// features = ["sqlite", "postgres"]
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MutuallyExclusiveConfig {
+    /// Features in the group. At most one of them may be activated per job
+    pub features: Vec<String>,
+}
+
+/// Files outside of any package, such as migrations or shared assets, that affect
+/// specific packages when they change
+///
+/// # Example
+///
+/// ```toml
+/// [[affects]]
+/// glob = "migrations/**"
+/// packages = ["moosicbox_schema"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AffectsMapping {
+    /// Glob, relative to the workspace root, of the files the mapping applies to
+    pub glob: String,
+    /// Names of the packages affected by a change to a matching file
+    pub packages: Vec<String>,
+}
+
 /// Root configuration structure for clippier.toml files
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -408,11 +655,42 @@ pub struct ClippierConf {
     pub rust: Option<RustConfig>,
     /// Node.js-specific configuration
     pub node: Option<NodeConfig>,
+    /// Groups of features that must never be combined in a single job
+    pub mutually_exclusive: Option<Vec<MutuallyExclusiveConfig>>,
+    /// Paths relative to the package directory to exclude from generated `.dockerignore`
+    /// files (e.g. `tests/fixtures/**`, `target/**`)
+    pub dockerignore_extra: Option<Vec<String>>,
+    /// Whether the package's tests are known to be flaky. Jobs of flaky packages are
+    /// retried [`DEFAULT_FLAKY_RETRIES`] times unless `flaky-retries` is set
+    pub flaky: Option<bool>,
+    /// Number of times CI should re-run a failed job of the package
+    pub flaky_retries: Option<u32>,
     /// Tool configuration for check/format commands
     #[cfg(feature = "_tools")]
     pub tools: Option<tools::ToolsConfig>,
 }
 
+/// Number of retries for packages declaring `flaky = true` without `flaky-retries`
+pub const DEFAULT_FLAKY_RETRIES: u32 = 2;
+
+impl ClippierConf {
+    /// Number of times CI should re-run a failed job of the package, or `None` if the
+    /// package isn't flaky
+    ///
+    /// Setting `flaky-retries` implies `flaky = true`, while `flaky = false` disables
+    /// retries regardless of `flaky-retries`.
+    #[must_use]
+    pub fn retries(&self) -> Option<u32> {
+        if self.flaky == Some(false) {
+            return None;
+        }
+
+        self.flaky_retries
+            .or_else(|| (self.flaky == Some(true)).then_some(DEFAULT_FLAKY_RETRIES))
+            .filter(|retries| *retries > 0)
+    }
+}
+
 /// Workspace-level configuration (root clippier.toml)
 #[derive(Debug, Clone, Default, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -431,6 +709,11 @@ pub struct WorkspaceClippierConf {
     pub rust: Option<RustConfig>,
     /// Node.js-specific configuration
     pub node: Option<NodeConfig>,
+    /// Path, relative to the workspace root, of a TOML file listing additional workspace
+    /// members (see [`resolve_members_file`])
+    pub members_file: Option<String>,
+    /// Non-package files that affect specific packages (see [`load_affects_mappings`])
+    pub affects: Option<Vec<AffectsMapping>>,
 }
 
 /// List of features that may be chunked for parallel processing
@@ -442,6 +725,18 @@ pub enum FeaturesList {
     NotChunked(Vec<String>),
 }
 
+/// Strategy for combining features into a reduced set of feature combinations
+///
+/// See [`cover_features`].
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+#[clap(rename_all = "kebab_case")]
+pub enum CoverageStrategy {
+    /// Every feature is enabled in at least one combination
+    EachOnce,
+    /// Every pair of features is enabled together in at least one combination
+    Pairwise,
+}
+
 /// Configuration that propagates from workspace dependencies
 #[derive(Debug, Clone, Default)]
 pub struct PropagatedConfig {
@@ -499,6 +794,25 @@ impl<'a, T> Iterator for SplitIter<'a, T> {
     }
 }
 
+/// Derives the randomization seed for a single package from the base seed of a run
+///
+/// The derivation is a stable FNV-1a hash of the base seed and the package name, so
+/// re-running with the same base seed reproduces every package's feature order while
+/// different packages in the same run are shuffled differently.
+#[must_use]
+pub fn package_seed(base_seed: u64, package_name: &str) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    base_seed
+        .to_le_bytes()
+        .iter()
+        .chain(package_name.as_bytes())
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+        })
+}
+
 /// Processes a list of features with optional chunking, spreading, and randomization
 #[must_use]
 pub fn process_features(
@@ -568,6 +882,239 @@ pub fn process_features(
     }
 }
 
+/// Computes a reduced set of feature combinations covering `features` according to
+/// `strategy`
+///
+/// Each combination enables at most `max_features_per_combination` features, or all of
+/// them if `None`. With [`CoverageStrategy::EachOnce`] every feature appears in at least
+/// one combination, and with [`CoverageStrategy::Pairwise`] every pair of features
+/// appears together in at least one combination, so combinations are at least two
+/// features wide. Combinations are built greedily, following the order of `features` to
+/// break ties, so the result is deterministic for a given order.
+#[must_use]
+pub fn cover_features(
+    features: &[String],
+    max_features_per_combination: Option<usize>,
+    strategy: CoverageStrategy,
+) -> Vec<Vec<String>> {
+    cover_features_with_conflicts(features, max_features_per_combination, strategy, |_, _| {
+        false
+    })
+}
+
+/// Like [`cover_features`], never enabling two features in the same combination if
+/// `conflicts` returns `true` for their indices
+///
+/// Pairs of conflicting features can't be covered, so [`CoverageStrategy::Pairwise`]
+/// only covers the pairs of compatible features.
+fn cover_features_with_conflicts(
+    features: &[String],
+    max_features_per_combination: Option<usize>,
+    strategy: CoverageStrategy,
+    conflicts: impl Fn(usize, usize) -> bool,
+) -> Vec<Vec<String>> {
+    let max_size = max_features_per_combination.unwrap_or(usize::MAX).max(1);
+    let compatible = |combination: &[usize], candidate: usize| {
+        combination.iter().all(|&x| !conflicts(x, candidate))
+    };
+
+    let mut combinations: Vec<Vec<usize>> = vec![];
+    let mut covered = vec![false; features.len()];
+
+    if strategy == CoverageStrategy::Pairwise {
+        let max_size = max_size.max(2);
+        let mut uncovered = (0..features.len())
+            .flat_map(|a| ((a + 1)..features.len()).map(move |b| (a, b)))
+            .filter(|&(a, b)| !conflicts(a, b))
+            .collect::<BTreeSet<_>>();
+
+        while let Some(&(a, b)) = uncovered.first() {
+            let mut combination = vec![a, b];
+
+            // Add the feature that covers the most remaining pairs until none does
+            while combination.len() < max_size {
+                let best = (0..features.len())
+                    .filter(|x| !combination.contains(x) && compatible(&combination, *x))
+                    .map(|x| {
+                        let gain = combination
+                            .iter()
+                            .filter(|&&y| uncovered.contains(&(x.min(y), x.max(y))))
+                            .count();
+                        (gain, std::cmp::Reverse(x))
+                    })
+                    .max();
+
+                match best {
+                    Some((gain, std::cmp::Reverse(x))) if gain > 0 => combination.push(x),
+                    _ => break,
+                }
+            }
+
+            for (i, &x) in combination.iter().enumerate() {
+                covered[x] = true;
+                for &y in &combination[(i + 1)..] {
+                    uncovered.remove(&(x.min(y), x.max(y)));
+                }
+            }
+
+            combination.sort_unstable();
+            combinations.push(combination);
+        }
+    }
+
+    // Pack the features not covered yet into the first combination with room for them
+    let first_new = combinations.len();
+    for x in (0..features.len()).filter(|&x| !covered[x]) {
+        let existing = combinations[first_new..].iter_mut().find(|combination| {
+            combination.len() < max_size && compatible(combination.as_slice(), x)
+        });
+
+        if let Some(combination) = existing {
+            combination.push(x);
+        } else {
+            combinations.push(vec![x]);
+        }
+    }
+
+    combinations
+        .into_iter()
+        .map(|combination| {
+            combination
+                .into_iter()
+                .map(|x| features[x].clone())
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns, for each of `groups`, the members that `feature` activates through the
+/// `[features]` table of `cargo_toml`
+fn activated_group_members<'a>(
+    cargo_toml: &Value,
+    feature: &String,
+    groups: &'a [MutuallyExclusiveConfig],
+) -> Vec<BTreeSet<&'a String>> {
+    let active = expand_active_package_features(cargo_toml, std::slice::from_ref(feature));
+
+    groups
+        .iter()
+        .map(|group| {
+            group
+                .features
+                .iter()
+                .filter(|x| active.contains(*x))
+                .collect()
+        })
+        .collect()
+}
+
+/// Computes the feature combinations covering `features` like [`cover_features`],
+/// never activating more than one member of a mutually-exclusive group in the same
+/// combination
+///
+/// Like [`split_mutually_exclusive_features`], a feature that on its own activates more
+/// than one member of a group is pruned.
+fn cover_mutually_exclusive_features(
+    cargo_toml: &Value,
+    features: Vec<String>,
+    max_features_per_combination: Option<usize>,
+    strategy: CoverageStrategy,
+    groups: &[MutuallyExclusiveConfig],
+) -> Vec<Vec<String>> {
+    let (features, members): (Vec<_>, Vec<_>) = features
+        .into_iter()
+        .map(|feature| {
+            let members = activated_group_members(cargo_toml, &feature, groups);
+            (feature, members)
+        })
+        .filter(|(feature, members)| {
+            let valid = members.iter().all(|x| x.len() <= 1);
+            if !valid {
+                log::warn!(
+                    "Skipping feature '{feature}' since it activates multiple mutually-exclusive features"
+                );
+            }
+            valid
+        })
+        .unzip();
+
+    cover_features_with_conflicts(&features, max_features_per_combination, strategy, |a, b| {
+        members[a]
+            .iter()
+            .zip(&members[b])
+            .any(|(x, y)| !x.is_empty() && !y.is_empty() && x != y)
+    })
+}
+
+/// Splits feature lists so that no job activates more than one member of a
+/// mutually-exclusive group
+///
+/// Feature activation is resolved through the `[features]` table of `cargo_toml`,
+/// so a feature that implies a group member counts as that member. Conflicting
+/// features are moved into additional chunks rather than dropped. A feature that
+/// on its own activates more than one member of a group can never produce a valid
+/// job and is pruned.
+#[must_use]
+pub fn split_mutually_exclusive_features(
+    cargo_toml: &Value,
+    features: FeaturesList,
+    groups: &[MutuallyExclusiveConfig],
+) -> FeaturesList {
+    if groups.is_empty() {
+        return features;
+    }
+
+    let activated_members = |feature: &String| activated_group_members(cargo_toml, feature, groups);
+
+    let split_chunk = |chunk: Vec<String>| -> Vec<Vec<String>> {
+        let mut bins: Vec<(Vec<String>, Vec<BTreeSet<&String>>)> = vec![];
+
+        for feature in chunk {
+            let members = activated_members(&feature);
+
+            if members.iter().any(|x| x.len() > 1) {
+                log::warn!(
+                    "Skipping feature '{feature}' since it activates multiple mutually-exclusive features"
+                );
+                continue;
+            }
+
+            let compatible = bins.iter_mut().find(|(_, bin_members)| {
+                bin_members
+                    .iter()
+                    .zip(&members)
+                    .all(|(a, b)| b.is_empty() || a.is_empty() || a == b)
+            });
+
+            if let Some((bin, bin_members)) = compatible {
+                bin.push(feature);
+                for (a, b) in bin_members.iter_mut().zip(members) {
+                    a.extend(b);
+                }
+            } else {
+                bins.push((vec![feature], members));
+            }
+        }
+
+        bins.into_iter().map(|(bin, _)| bin).collect()
+    };
+
+    match features {
+        FeaturesList::Chunked(chunks) => {
+            FeaturesList::Chunked(chunks.into_iter().flat_map(split_chunk).collect())
+        }
+        FeaturesList::NotChunked(features) => {
+            let mut bins = split_chunk(features);
+
+            if bins.len() > 1 {
+                FeaturesList::Chunked(bins)
+            } else {
+                FeaturesList::NotChunked(bins.pop().unwrap_or_default())
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum DependencyKind {
     WorkspaceReference,
@@ -578,6 +1125,8 @@ enum DependencyKind {
 #[derive(Debug, Clone)]
 struct DependencyInfo<'a> {
     name: &'a str,
+    /// `Cargo.toml` section the dependency is declared in, e.g. `dev-dependencies`
+    section: &'static str,
     kind: DependencyKind,
     is_optional: bool,
 }
@@ -595,21 +1144,30 @@ pub struct WorkspaceContext {
 
 impl WorkspaceContext {
     fn new(workspace_root: &Path) -> Result<Self, BoxError> {
+        Self::with_members_file(workspace_root, None)
+    }
+
+    /// Creates the context, reading additional members from `members_file` instead of the
+    /// `members-file` configured in the root `clippier.toml` if it is set
+    fn with_members_file(
+        workspace_root: &Path,
+        members_file: Option<&Path>,
+    ) -> Result<Self, BoxError> {
         let workspace_cargo = workspace_root.join("Cargo.toml");
         let content = switchy_fs::sync::read_to_string(&workspace_cargo)?;
         let root_toml: Value = toml::from_str(&content)?;
 
-        let mut raw_patterns = Vec::new();
+        let workspace_config = load_workspace_clippier_conf(workspace_root)?;
+        let members_file = members_file.map(|x| workspace_root.join(x)).or_else(|| {
+            workspace_config
+                .as_ref()
+                .and_then(|x| x.members_file.as_ref())
+                .map(|x| workspace_root.join(x))
+        });
 
-        if let Some(Value::Table(workspace)) = root_toml.get("workspace")
-            && let Some(Value::Array(member_list)) = workspace.get("members")
-        {
-            for member in member_list {
-                if let Value::String(member_pattern) = member {
-                    raw_patterns.push(member_pattern.clone());
-                }
-            }
-        }
+        let raw_patterns =
+            workspace_member_patterns(workspace_root, &root_toml, members_file.as_deref())?
+                .unwrap_or_default();
 
         // Expand glob patterns (e.g., "packages/*" -> ["packages/foo", "packages/bar"])
         let member_patterns = Self::expand_member_globs(workspace_root, &raw_patterns);
@@ -626,7 +1184,7 @@ impl WorkspaceContext {
             member_cache: RefCell::new(BTreeMap::new()),
             path_cache: RefCell::new(BTreeSet::new()),
             fully_loaded: RefCell::new(false),
-            workspace_config: RefCell::new(None),
+            workspace_config: RefCell::new(Some(workspace_config)),
         })
     }
 
@@ -638,8 +1196,7 @@ impl WorkspaceContext {
     /// Uses `switchy_fs` for filesystem operations to work correctly in both
     /// real and simulated filesystem modes.
     fn expand_member_globs(workspace_root: &Path, patterns: &[String]) -> Vec<String> {
-        let patterns_ref: Vec<&str> = patterns.iter().map(String::as_str).collect();
-        expand_workspace_member_globs(workspace_root, &patterns_ref)
+        expand_workspace_member_globs(workspace_root, patterns)
     }
 
     fn is_member_by_path(&self, path: &Path) -> bool {
@@ -733,24 +1290,7 @@ impl WorkspaceContext {
             return Ok(cached.clone());
         }
 
-        // Try to load workspace config
-        let workspace_conf_path = self.root.join("clippier.toml");
-
-        let result = if switchy_fs::exists(&workspace_conf_path) {
-            log::trace!(
-                "Loading workspace config from: {}",
-                workspace_conf_path.display()
-            );
-            let content = switchy_fs::sync::read_to_string(&workspace_conf_path)?;
-            let conf: WorkspaceClippierConf = toml::from_str(&content)?;
-            Some(conf)
-        } else {
-            log::trace!(
-                "No workspace-level clippier.toml found at: {}",
-                workspace_conf_path.display()
-            );
-            None
-        };
+        let result = load_workspace_clippier_conf(&self.root)?;
 
         // Cache the result (None means we tried and found nothing)
         *self.workspace_config.borrow_mut() = Some(result.clone());
@@ -803,6 +1343,7 @@ fn iterate_dependencies<'a>(
 
                     DependencyInfo {
                         name: dep_name,
+                        section: section_name,
                         kind,
                         is_optional,
                     }
@@ -878,6 +1419,58 @@ fn merge_propagated_configs(base: PropagatedConfig, overlay: PropagatedConfig) -
     }
 }
 
+/// Loads the propagatable parts of a package's own `clippier.toml`, ignoring its
+/// workspace dependencies
+fn load_own_config(
+    package_path: &Path,
+    os_filter: Option<&str>,
+) -> Result<PropagatedConfig, BoxError> {
+    let clippier_toml_path = package_path.join("clippier.toml");
+    if !switchy_fs::exists(&clippier_toml_path) {
+        return Ok(PropagatedConfig::default());
+    }
+
+    let conf = load_clippier_conf(&clippier_toml_path)?;
+
+    let mut prop = PropagatedConfig {
+        git_submodules: conf.git_submodules,
+        dependencies: Vec::new(),
+        ci_steps: Vec::new(),
+        env: conf.env.unwrap_or_default(),
+    };
+
+    if let Some(configs) = &conf.config {
+        for config in configs {
+            let os_matches = os_filter.is_none() || os_filter == Some(&config.os);
+
+            if os_matches {
+                if let Some(deps) = &config.dependencies {
+                    prop.dependencies.extend(deps.clone());
+                }
+                if let Some(steps) = &config.ci_steps {
+                    match steps {
+                        VecOrItem::Value(step) => prop.ci_steps.push(step.clone()),
+                        VecOrItem::Values(steps) => prop.ci_steps.extend(steps.clone()),
+                    }
+                }
+            }
+
+            if config.git_submodules.is_some() {
+                prop.git_submodules = prop.git_submodules.or(config.git_submodules);
+            }
+        }
+    }
+
+    if let Some(steps) = &conf.ci_steps {
+        match steps {
+            VecOrItem::Value(step) => prop.ci_steps.push(step.clone()),
+            VecOrItem::Values(steps) => prop.ci_steps.extend(steps.clone()),
+        }
+    }
+
+    Ok(prop)
+}
+
 #[allow(clippy::too_many_lines, clippy::similar_names)]
 fn collect_propagated_config(
     context: &WorkspaceContext,
@@ -913,51 +1506,7 @@ fn collect_propagated_config(
     let cargo_toml_content = switchy_fs::sync::read_to_string(&cargo_toml_path)?;
     let cargo_toml: Value = toml::from_str(&cargo_toml_content)?;
 
-    let clippier_toml_path = package_path.join("clippier.toml");
-    let own_config = if switchy_fs::exists(&clippier_toml_path) {
-        let content = switchy_fs::sync::read_to_string(&clippier_toml_path)?;
-        let conf: ClippierConf = toml::from_str(&content)?;
-
-        let mut prop = PropagatedConfig {
-            git_submodules: conf.git_submodules,
-            dependencies: Vec::new(),
-            ci_steps: Vec::new(),
-            env: conf.env.unwrap_or_default(),
-        };
-
-        if let Some(configs) = &conf.config {
-            for config in configs {
-                let os_matches = os_filter.is_none() || os_filter == Some(&config.os);
-
-                if os_matches {
-                    if let Some(deps) = &config.dependencies {
-                        prop.dependencies.extend(deps.clone());
-                    }
-                    if let Some(steps) = &config.ci_steps {
-                        match steps {
-                            VecOrItem::Value(step) => prop.ci_steps.push(step.clone()),
-                            VecOrItem::Values(steps) => prop.ci_steps.extend(steps.clone()),
-                        }
-                    }
-                }
-
-                if config.git_submodules.is_some() {
-                    prop.git_submodules = prop.git_submodules.or(config.git_submodules);
-                }
-            }
-        }
-
-        if let Some(steps) = &conf.ci_steps {
-            match steps {
-                VecOrItem::Value(step) => prop.ci_steps.push(step.clone()),
-                VecOrItem::Values(steps) => prop.ci_steps.extend(steps.clone()),
-            }
-        }
-
-        prop
-    } else {
-        PropagatedConfig::default()
-    };
+    let own_config = load_own_config(&package_path, os_filter)?;
 
     let workspace_deps = extract_workspace_deps_simple(&cargo_toml, context, &package_path);
 
@@ -1210,16 +1759,22 @@ pub fn expand_features_from_cargo_toml(cargo_toml: &Value, patterns: &[String])
 ///
 /// This follows Cargo feature-to-feature edges defined in `[features]` and is cycle-safe.
 /// Dependency feature edges like `dep:foo`, `foo/bar`, and `foo?/bar` are ignored unless the
-/// left-hand side is also a package feature name.
+/// left-hand side is also a package feature name. Requested entries may be comma-separated
+/// feature combinations (see [`cover_features`]).
 #[must_use]
 pub fn expand_active_package_features(
     cargo_toml: &Value,
     requested_features: &[String],
 ) -> BTreeSet<String> {
     let mut visited = BTreeSet::new();
+    let requested_features = requested_features
+        .iter()
+        .flat_map(|x| x.split(','))
+        .map(str::to_string)
+        .collect::<Vec<_>>();
 
     let Some(Value::Table(features_table)) = cargo_toml.get("features") else {
-        visited.extend(requested_features.iter().cloned());
+        visited.extend(requested_features);
         return visited;
     };
 
@@ -1251,7 +1806,7 @@ pub fn expand_active_package_features(
         adjacency.insert(feature_name.clone(), next_features);
     }
 
-    let mut stack = requested_features.to_vec();
+    let mut stack = requested_features;
 
     while let Some(feature) = stack.pop() {
         if !visited.insert(feature.clone()) {
@@ -1417,40 +1972,162 @@ pub fn get_binary_name(
     target_package.replace('-', "_")
 }
 
+/// Maps each binary name produced by the workspace to the packages that produce it
+///
+/// Only members with a binary target (a `bin` section or `src/main.rs`) are included.
+/// Names are resolved the same way as [`get_binary_name`], so packages whose names only
+/// differ by dashes and underscores map to the same binary.
+///
+/// # Errors
+///
+/// * If the workspace `Cargo.toml` cannot be read or parsed
+/// * If no workspace members are found
+/// * If a member `Cargo.toml` cannot be read or parsed
+pub fn workspace_binary_names(
+    workspace_root: &Path,
+) -> Result<BTreeMap<String, BTreeSet<String>>, BoxError> {
+    let workspace_source = switchy_fs::sync::read_to_string(workspace_root.join("Cargo.toml"))?;
+    let workspace_value: Value = toml::from_str(&workspace_source)?;
+    let members_file = resolve_members_file(workspace_root, None)?;
+
+    let workspace_members_raw =
+        workspace_member_patterns(workspace_root, &workspace_value, members_file.as_deref())?
+            .ok_or("No workspace members found")?;
+
+    let mut binaries: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    for member_path in expand_workspace_member_globs(workspace_root, &workspace_members_raw) {
+        let full_path = workspace_root.join(&member_path);
+        let cargo_path = full_path.join("Cargo.toml");
+
+        if !switchy_fs::exists(&cargo_path) {
+            continue;
+        }
+
+        let source = switchy_fs::sync::read_to_string(&cargo_path)?;
+        let value: Value = toml::from_str(&source)?;
+
+        let Some(package_name) = value
+            .get("package")
+            .and_then(|x| x.get("name"))
+            .and_then(|x| x.as_str())
+        else {
+            continue;
+        };
+
+        let has_binary =
+            value.get("bin").is_some() || switchy_fs::exists(full_path.join("src/main.rs"));
+
+        if !has_binary {
+            continue;
+        }
+
+        let binary_name = get_binary_name(workspace_root, package_name, &member_path, None);
+        binaries
+            .entry(binary_name)
+            .or_default()
+            .insert(package_name.to_string());
+    }
+
+    Ok(binaries)
+}
+
+/// Finds binary names that are produced by more than one workspace package
+///
+/// Colliding binaries overwrite each other in `target/release`, which makes the
+/// `COPY --from=builder` of a generated Dockerfile ambiguous.
+///
+/// # Errors
+///
+/// * If the workspace binaries cannot be resolved (see [`workspace_binary_names`])
+pub fn find_binary_name_collisions(
+    workspace_root: &Path,
+) -> Result<BTreeMap<String, BTreeSet<String>>, BoxError> {
+    Ok(workspace_binary_names(workspace_root)?
+        .into_iter()
+        .filter(|(_, packages)| packages.len() > 1)
+        .collect())
+}
+
+/// Fails if the binary that the target package's Dockerfile copies is also produced by
+/// another workspace package
+fn check_target_binary_collision(
+    workspace_root: &Path,
+    target_package: &str,
+    target_package_path: &str,
+    bin: Option<&str>,
+) -> Result<(), BoxError> {
+    let binary_name = get_binary_name(workspace_root, target_package, target_package_path, bin);
+    let binaries = workspace_binary_names(workspace_root)?;
+
+    let others = binaries
+        .get(&binary_name)
+        .into_iter()
+        .flatten()
+        .filter(|package| *package != target_package)
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+
+    if others.is_empty() {
+        return Ok(());
+    }
+
+    Err(format!(
+        "Binary name '{binary_name}' of package '{target_package}' collides with package(s): {}. \
+         Set an explicit binary name with --bin or rename the [[bin]] target",
+        others.join(", ")
+    )
+    .into())
+}
+
 /// Processes a Cargo.toml file and returns a list of packages with their features
 ///
+/// When `options.target` is set, features that only enable dependencies declared under
+/// non-matching `[target.<key>]` tables are left out (see [`filter_manifest_for_target`]).
+///
+/// With `options.no_propagation`, the system dependencies, CI steps, environment variables
+/// and git submodule settings of the package's workspace dependencies are not collected, see
+/// [`create_map`].
+///
+/// With an `options.coverage_strategy`, each package gets a single entry whose features are
+/// the comma-separated combinations computed by [`cover_features`], each with at most
+/// `chunked` features, instead of chunks of single features.
+///
 /// # Errors
 ///
 /// * If the Cargo.toml file is not found or cannot be read
 /// * If the Cargo.toml file has an invalid format
 /// * If the Cargo.toml file has a syntax error
+/// * If `options.target` is not a valid target triple
 ///
 /// # Panics
 ///
 /// * If the `path` argument cannot be converted to a string
-#[allow(clippy::too_many_arguments, clippy::too_many_lines)]
+#[allow(clippy::too_many_lines)]
 pub async fn process_configs(
     path: &Path,
-    offset: Option<u16>,
-    max: Option<u16>,
-    chunked: Option<u16>,
-    spread: bool,
-    randomize: bool,
-    seed: Option<u64>,
-    specific_features: Option<&[String]>,
-    skip_features_override: Option<&[String]>,
-    required_features_override: Option<&[String]>,
+    options: &FeaturesOptions,
 ) -> Result<Vec<serde_json::Map<String, serde_json::Value>>, BoxError> {
+    let split = |x: Option<&str>| x.map(|f| f.split(',').map(str::to_string).collect::<Vec<_>>());
+    let specific_features = split(options.features.as_deref());
+    let skip_features_override = split(options.skip_features.as_deref());
+    let required_features_override = split(options.required_features.as_deref());
+    let specific_features = specific_features.as_deref();
+    let skip_features_override = skip_features_override.as_deref();
+    let required_features_override = required_features_override.as_deref();
+
     log::debug!("Loading file '{}'", path.display());
     let cargo_path = path.join("Cargo.toml");
     let source = switchy_fs::unsync::read_to_string(cargo_path).await?;
     let value: Value = toml::from_str(&source)?;
+    let value = match options.target.as_deref() {
+        Some(target) => filter_manifest_for_target(&value, &target.parse::<TargetTriple>()?),
+        None => value,
+    };
 
     let conf_path = path.join("clippier.toml");
     let conf = if switchy_fs::unsync::is_file(&conf_path).await {
-        let source = switchy_fs::unsync::read_to_string(conf_path).await?;
-        let value: ClippierConf = toml::from_str(&source)?;
-        Some(value)
+        Some(load_clippier_conf(&conf_path)?)
     } else {
         None
     };
@@ -1470,7 +2147,8 @@ pub async fn process_configs(
 
     let workspace_root =
         find_workspace_root_from_package(path).unwrap_or_else(|_| path.to_path_buf());
-    let workspace_context = WorkspaceContext::new(&workspace_root)?;
+    let workspace_context =
+        WorkspaceContext::with_members_file(&workspace_root, options.members_file.as_deref())?;
     let workspace_conf = workspace_context.workspace_config().ok().flatten();
 
     let configs = conf
@@ -1491,7 +2169,32 @@ pub async fn process_configs(
             // Get Rust-specific config for this OS config
             let config_rust = config.rust.as_ref();
             let config_skip_features = config_rust.and_then(|r| r.skip_features.as_deref());
-            let config_required_features = config_rust.and_then(|r| r.required_features.as_deref());
+
+            // Required features of the OS config, falling back to the package's and then the
+            // workspace's `[rust]` section. Features scoped to other OSes are skipped.
+            let (config_required_features, other_os_features) = config_rust
+                .and_then(|r| r.required_features.as_deref())
+                .or_else(|| {
+                    conf.as_ref()
+                        .and_then(|x| x.rust.as_ref())
+                        .and_then(|r| r.required_features.as_deref())
+                })
+                .or_else(|| {
+                    workspace_conf
+                        .as_ref()
+                        .and_then(|x| x.rust.as_ref())
+                        .and_then(|r| r.required_features.as_deref())
+                })
+                .map(|x| required_features_for_os(x, &config.os))
+                .map_or((None, None), |(required, other)| {
+                    (Some(required), (!other.is_empty()).then_some(other))
+                });
+            let config_skip_features = match (config_skip_features, other_os_features) {
+                (Some(skip), Some(other)) => Some([skip, &other].concat()),
+                (skip, other) => skip.map(<[String]>::to_vec).or(other),
+            };
+            let config_skip_features = config_skip_features.as_deref();
+            let config_required_features = config_required_features.as_deref();
 
             // Combine skip_features from command line and config file
             let combined_skip_features = match (skip_features_override, config_skip_features) {
@@ -1512,21 +2215,47 @@ pub async fn process_configs(
 
             let features = fetch_features(
                 &value,
-                offset,
-                max,
+                options.offset,
+                options.max,
                 specific_features,
                 combined_skip_features.as_deref(),
                 required_features_override.or(config_required_features),
             );
-            let features = process_features(
-                features,
-                conf.as_ref()
-                    .and_then(|x| x.parallelization.as_ref().map(|x| x.chunked))
-                    .or(chunked),
-                spread,
-                randomize,
-                seed,
-            );
+            let chunked = conf
+                .as_ref()
+                .and_then(|x| x.parallelization.as_ref().map(|x| x.chunked))
+                .or(options.chunked);
+            let seed = options.seed.map(|seed| package_seed(seed, &name));
+            let mutually_exclusive = conf.as_ref().and_then(|x| x.mutually_exclusive.as_deref());
+
+            let features = if let Some(strategy) = options.coverage_strategy {
+                // Shuffle before covering so randomization still varies the combinations
+                let features =
+                    match process_features(features, None, false, options.randomize, seed) {
+                        FeaturesList::Chunked(x) => x.concat(),
+                        FeaturesList::NotChunked(x) => x,
+                    };
+                let max_features = chunked.map(usize::from);
+                let combinations = match mutually_exclusive {
+                    Some(groups) => cover_mutually_exclusive_features(
+                        &value,
+                        features,
+                        max_features,
+                        strategy,
+                        groups,
+                    ),
+                    None => cover_features(&features, max_features, strategy),
+                };
+
+                FeaturesList::NotChunked(combinations.iter().map(|x| x.join(",")).collect())
+            } else {
+                let features =
+                    process_features(features, chunked, options.spread, options.randomize, seed);
+                match mutually_exclusive {
+                    Some(groups) => split_mutually_exclusive_features(&value, features, groups),
+                    None => features,
+                }
+            };
 
             // Expand wildcards in required_features
             let expanded_required_features = required_features_override
@@ -1548,6 +2277,7 @@ pub async fn process_configs(
                             &name,
                             expanded_required_features.as_deref(),
                             &[],
+                            options,
                         )?);
                     } else {
                         for features in x {
@@ -1561,6 +2291,7 @@ pub async fn process_configs(
                                 &name,
                                 expanded_required_features.as_deref(),
                                 features,
+                                options,
                             )?);
                         }
                     }
@@ -1576,6 +2307,7 @@ pub async fn process_configs(
                         &name,
                         expanded_required_features.as_deref(),
                         x,
+                        options,
                     )?);
                 }
             }
@@ -1688,6 +2420,12 @@ pub fn apply_max_parallel_rechunking(
 
 /// Creates a JSON map from a configuration
 ///
+/// Unless `options.no_propagation` is set, the `clippier.toml` configs of the package's
+/// workspace dependencies are walked recursively and their system dependencies, CI steps,
+/// environment variables and git submodule settings merged into the entry. With
+/// `no_propagation`, only the package's own `clippier.toml` and the workspace defaults are
+/// used, which is faster but misses anything a dependency contributes.
+///
 /// # Errors
 ///
 /// * If the configuration is invalid
@@ -1702,17 +2440,25 @@ pub fn create_map(
     name: &str,
     required_features: Option<&[String]>,
     features: &[String],
+    options: &FeaturesOptions,
 ) -> Result<serde_json::Map<String, serde_json::Value>, BoxError> {
-    let mut visited = BTreeSet::new();
-    let mut cache = BTreeMap::new();
-    let propagated = collect_propagated_config(
-        context,
-        package_name,
-        Some(&config.os),
-        &mut visited,
-        &mut cache,
-    )
-    .unwrap_or_default();
+    let propagated = if options.no_propagation {
+        context
+            .find_member(package_name)
+            .and_then(|package_path| load_own_config(&package_path, Some(&config.os)).ok())
+            .unwrap_or_default()
+    } else {
+        let mut visited = BTreeSet::new();
+        let mut cache = BTreeMap::new();
+        collect_propagated_config(
+            context,
+            package_name,
+            Some(&config.os),
+            &mut visited,
+            &mut cache,
+        )
+        .unwrap_or_default()
+    };
 
     // Get workspace config for defaults
     let workspace_conf = context.workspace_config().ok().flatten();
@@ -1817,13 +2563,7 @@ pub fn create_map(
 
     let matches = env
         .iter()
-        .filter(|(_k, v)| match v {
-            ClippierEnv::Value(..) => true,
-            ClippierEnv::FilteredValue { features: f, .. } => f.as_ref().is_none_or(|f| {
-                f.iter()
-                    .any(|required| expanded_features.contains(required))
-            }),
-        })
+        .filter(|(_k, v)| v.matches(&expanded_features, options.env_profile.as_deref()))
         .map(|(k, v)| {
             (
                 k,
@@ -1938,11 +2678,24 @@ pub fn create_map(
         );
     }
 
+    if let Some(retries) = conf.and_then(ClippierConf::retries) {
+        map.insert("retries".to_string(), serde_json::to_value(retries)?);
+    }
+
     Ok(map)
 }
 
 /// Finds workspace dependencies for a target package
 ///
+/// Features propagate down the dependency chain: a dependency is resolved with its
+/// `default` feature, the features its dependent declares on it (`features = [...]`, also
+/// in `[workspace.dependencies]`), and the features its dependent's active features enable
+/// on it (`dep/feature` or `dep?/feature`). A package reached through several dependents
+/// is resolved with the union of their features.
+///
+/// `default-features = false` is not taken into account, so the result may include
+/// dependencies that a build with those defaults disabled wouldn't compile.
+///
 /// # Errors
 ///
 /// * If the workspace root directory is not found or cannot be read
@@ -1981,13 +2734,11 @@ pub fn find_workspace_dependencies(
     );
     let workspace_source = switchy_fs::sync::read_to_string(&workspace_cargo_path)?;
     let workspace_value: Value = toml::from_str(&workspace_source)?;
+    let members_file = resolve_members_file(workspace_root, None)?;
 
-    let workspace_members_raw = workspace_value
-        .get("workspace")
-        .and_then(|x| x.get("members"))
-        .and_then(|x| x.as_array())
-        .and_then(|x| x.iter().map(|x| x.as_str()).collect::<Option<Vec<_>>>())
-        .ok_or("No workspace members found")?;
+    let workspace_members_raw =
+        workspace_member_patterns(workspace_root, &workspace_value, members_file.as_deref())?
+            .ok_or("No workspace members found")?;
 
     // Expand glob patterns in workspace members
     let workspace_members = expand_workspace_member_globs(workspace_root, &workspace_members_raw);
@@ -2022,9 +2773,9 @@ pub fn find_workspace_dependencies(
             package_paths.insert(package_name.to_string(), member_path.clone());
             package_cargo_values.insert(package_name.to_string(), value.clone());
 
-            // Extract dependencies that are workspace members - we'll resolve them later
-            let deps =
-                extract_workspace_dependencies(&value, &workspace_context, all_potential_deps);
+            // Extract dependencies that are workspace members - we'll resolve them later, once
+            // the features enabled on the package are known
+            let deps = extract_workspace_dependencies(&value, &workspace_context, true);
             log::trace!("📊 Direct dependencies for {package_name}: {deps:?}");
             package_dependencies.insert(package_name.to_string(), deps);
         }
@@ -2038,21 +2789,42 @@ pub fn find_workspace_dependencies(
         "🚀 Starting recursive dependency resolution from target package: {target_package}"
     );
 
-    // Perform recursive dependency resolution to find all transitive dependencies
+    let workspace_dependencies = workspace_value
+        .get("workspace")
+        .and_then(|x| x.get("dependencies"))
+        .and_then(Value::as_table);
+
+    // Perform recursive dependency resolution to find all transitive dependencies. A package
+    // is processed again whenever it's reached with features it didn't have yet, as those
+    // can activate more of its dependencies.
     let mut resolved_dependencies = BTreeSet::new();
     let mut processing_queue = VecDeque::new();
-    let mut visited = BTreeSet::new();
+    let mut package_features: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
 
     processing_queue.push_back((
         target_package.to_string(),
-        enabled_features.map(<[String]>::to_vec),
+        enabled_features.map_or_else(
+            || BTreeSet::from(["default".to_string()]),
+            |features| features.iter().cloned().collect(),
+        ),
     ));
 
-    while let Some((current_package, current_features)) = processing_queue.pop_front() {
-        if visited.contains(&current_package) {
-            continue;
-        }
-        visited.insert(current_package.clone());
+    while let Some((current_package, features)) = processing_queue.pop_front() {
+        let current_features = match package_features.get_mut(&current_package) {
+            Some(known) => {
+                let known_count = known.len();
+                known.extend(features);
+                if known.len() == known_count {
+                    continue;
+                }
+                known.clone()
+            }
+            None => {
+                package_features.insert(current_package.clone(), features.clone());
+                features
+            }
+        };
+        log::trace!("📋 Features of {current_package}: {current_features:?}");
 
         // Add current package to result if it's not the target package
         if current_package != target_package
@@ -2061,31 +2833,48 @@ pub fn find_workspace_dependencies(
             resolved_dependencies.insert((current_package.clone(), package_path.clone()));
         }
 
+        let Some(cargo_value) = package_cargo_values.get(&current_package) else {
+            continue;
+        };
+        let active_features: Vec<String> = expand_enabled_features(cargo_value, &current_features)
+            .into_iter()
+            .collect();
+
         // Get dependencies for current package
         if let Some(direct_deps) = package_dependencies.get(&current_package) {
             // For each direct dependency, check if it's activated and add to queue
             for dep_name in direct_deps {
-                if !visited.contains(dep_name) && package_paths.contains_key(dep_name) {
-                    // Check if this dependency is activated by current features
-                    let is_activated = if all_potential_deps {
-                        true // Include all in potential mode
-                    } else {
-                        is_dependency_activated(
-                            &package_cargo_values,
-                            &current_package,
-                            dep_name,
-                            current_features.as_deref(),
-                        )
-                    };
+                // The target's features are exactly the requested ones, even if a dependency
+                // cycles back to it (e.g. through a dev-dependency)
+                if dep_name == target_package || !package_paths.contains_key(dep_name) {
+                    continue;
+                }
 
-                    if is_activated {
-                        log::trace!("  ✅ Adding activated dependency: {dep_name}");
-                        // For the dependency, we need to determine what features to enable
-                        // For now, use default features (empty feature set)
-                        processing_queue.push_back((dep_name.clone(), None));
-                    } else {
-                        log::trace!("  ⏸️  Skipping dependency (not activated): {dep_name}");
-                    }
+                // Check if this dependency is activated by current features
+                let is_activated = if all_potential_deps {
+                    true // Include all in potential mode
+                } else {
+                    is_dependency_activated(
+                        &package_cargo_values,
+                        &current_package,
+                        dep_name,
+                        Some(&active_features),
+                    )
+                };
+
+                if is_activated {
+                    let dep_features = dependency_enabled_features(
+                        cargo_value,
+                        workspace_dependencies,
+                        dep_name,
+                        &active_features,
+                    );
+                    log::trace!(
+                        "  ✅ Adding activated dependency: {dep_name} with features {dep_features:?}"
+                    );
+                    processing_queue.push_back((dep_name.clone(), dep_features));
+                } else {
+                    log::trace!("  ⏸️  Skipping dependency (not activated): {dep_name}");
                 }
             }
         }
@@ -2098,6 +2887,90 @@ pub fn find_workspace_dependencies(
     Ok(result_paths)
 }
 
+/// Expands `features` with the features of the same package that they enable, directly or
+/// transitively
+fn expand_enabled_features(cargo_value: &Value, features: &BTreeSet<String>) -> BTreeSet<String> {
+    let Some(features_table) = cargo_value.get("features").and_then(Value::as_table) else {
+        return features.clone();
+    };
+
+    let mut expanded = features.clone();
+    let mut queue: Vec<String> = features.iter().cloned().collect();
+
+    while let Some(feature) = queue.pop() {
+        let items = features_table
+            .get(&feature)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str);
+
+        for item in items {
+            if features_table.contains_key(item) && expanded.insert(item.to_string()) {
+                queue.push(item.to_string());
+            }
+        }
+    }
+
+    expanded
+}
+
+/// Determines the features enabled on the dependency `dep_name` of a package whose
+/// `active_features` are enabled (see [`expand_enabled_features`])
+///
+/// These are `default`, the features the dependency is declared with, including in
+/// `[workspace.dependencies]` for `workspace = true` dependencies, and the `dep_name/feature`
+/// and `dep_name?/feature` entries of the active features.
+fn dependency_enabled_features(
+    cargo_value: &Value,
+    workspace_dependencies: Option<&toml::map::Map<String, Value>>,
+    dep_name: &str,
+    active_features: &[String],
+) -> BTreeSet<String> {
+    let declared_features = |dep_value: &Value| {
+        dep_value
+            .get("features")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    let mut features = BTreeSet::from(["default".to_string()]);
+
+    for section_name in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        if let Some(dep_value) = cargo_value.get(section_name).and_then(|x| x.get(dep_name)) {
+            features.extend(declared_features(dep_value));
+
+            if dep_value.get("workspace").and_then(Value::as_bool) == Some(true)
+                && let Some(workspace_value) = workspace_dependencies.and_then(|x| x.get(dep_name))
+            {
+                features.extend(declared_features(workspace_value));
+            }
+        }
+    }
+
+    if let Some(features_table) = cargo_value.get("features").and_then(Value::as_table) {
+        let items = active_features
+            .iter()
+            .filter_map(|feature| features_table.get(feature).and_then(Value::as_array))
+            .flatten()
+            .filter_map(Value::as_str);
+
+        for item in items {
+            if let Some((dep, feature)) = item.split_once('/')
+                && dep.strip_suffix('?').unwrap_or(dep) == dep_name
+            {
+                features.insert(feature.to_string());
+            }
+        }
+    }
+
+    features
+}
+
 /// Extracts all workspace dependencies from a Cargo.toml value
 fn extract_workspace_dependencies(
     cargo_value: &Value,
@@ -2262,56 +3135,350 @@ pub fn get_feature_dependencies(
     feature_deps
 }
 
-/// Generates a Dockerfile for a target package
+/// What a single feature of a package turns on
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct FeatureEnablement {
+    /// Feature name
+    pub name: String,
+    /// Features of the same package enabled by this feature, directly or transitively
+    pub enables: BTreeSet<String>,
+    /// Features enabled on dependencies (e.g. `serde/std`)
+    pub dependency_features: BTreeSet<String>,
+    /// Optional dependencies activated by this feature
+    pub optional_dependencies: BTreeSet<String>,
+}
+
+/// Lists every feature in a Cargo.toml's `[features]` table along with everything it
+/// transitively enables.
+///
+/// Intra-package feature references are expanded recursively. Optional dependencies are
+/// activated by `dep:name`, `name/feature` and implicit `name` feature entries, but not by
+/// weak `name?/feature` entries.
+#[must_use]
+pub fn list_package_features(cargo_toml: &Value) -> Vec<FeatureEnablement> {
+    let Some(features_table) = cargo_toml.get("features").and_then(|f| f.as_table()) else {
+        return vec![];
+    };
+
+    let optional_dependencies = optional_dependency_names(cargo_toml);
+
+    features_table
+        .keys()
+        .map(|name| {
+            let mut enables = BTreeSet::new();
+            let mut dependency_features = BTreeSet::new();
+            let mut activated = BTreeSet::new();
+            let mut queue = vec![name.as_str()];
+
+            while let Some(feature) = queue.pop() {
+                let items = features_table
+                    .get(feature)
+                    .and_then(|f| f.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|item| item.as_str());
+
+                for item in items {
+                    if item.contains('/') {
+                        dependency_features.insert(item.to_string());
+                    } else if features_table.contains_key(item) {
+                        if item != name && enables.insert(item.to_string()) {
+                            queue.push(item);
+                        }
+                    } else if optional_dependencies.contains(item) {
+                        // Implicit feature of an optional dependency
+                        activated.insert(item.to_string());
+                    }
+                }
+            }
+
+            let mut expanded = enables.clone();
+            expanded.insert(name.clone());
+
+            activated.extend(
+                get_feature_dependencies(cargo_toml, &expanded)
+                    .into_iter()
+                    .filter(|dep| optional_dependencies.contains(dep)),
+            );
+
+            FeatureEnablement {
+                name: name.clone(),
+                enables,
+                dependency_features,
+                optional_dependencies: activated,
+            }
+        })
+        .collect()
+}
+
+/// Collects the names of optional dependencies, including target-specific ones
+fn optional_dependency_names(cargo_toml: &Value) -> BTreeSet<String> {
+    let targets = cargo_toml
+        .get("target")
+        .and_then(|t| t.as_table())
+        .into_iter()
+        .flat_map(|targets| targets.values());
+
+    std::iter::once(cargo_toml)
+        .chain(targets)
+        .flat_map(|table| {
+            ["dependencies", "dev-dependencies", "build-dependencies"]
+                .into_iter()
+                .filter_map(|section| table.get(section).and_then(|d| d.as_table()))
+        })
+        .flatten()
+        .filter(|(_, value)| value.get("optional").and_then(Value::as_bool) == Some(true))
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+/// Formats a package's features and what they enable for human consumption
+#[must_use]
+pub fn format_feature_enablements(package: &str, features: &[FeatureEnablement]) -> String {
+    use std::fmt::Write as _;
+
+    let mut output = String::new();
+
+    let _ = writeln!(output, "{package} exposes {} features", features.len());
+
+    for feature in features {
+        let _ = writeln!(output, "\n{}", feature.name);
+
+        for (label, values) in [
+            ("enables", &feature.enables),
+            ("dependency features", &feature.dependency_features),
+            ("optional dependencies", &feature.optional_dependencies),
+        ] {
+            if !values.is_empty() {
+                let _ = writeln!(output, "  {label}: {}", values.iter().join(", "));
+            }
+        }
+    }
+
+    output
+}
+
+/// Default `clippier.toml` configuration OS used when generating Dockerfiles.
+pub const DEFAULT_DOCKER_TARGET_OS: &str = "ubuntu";
+
+/// Configuration OSes that can't be built in a (Linux) Docker image.
+const NON_LINUX_OSES: [&str; 2] = ["windows", "macos"];
+
+/// Ensures the environment variables and system dependencies for `target_os` can be used
+/// in a Dockerfile.
+///
+/// Docker images are Linux-based, so `target_os` must not be a Windows or macOS
+/// configuration, and a package whose `clippier.toml` only has Windows or macOS
+/// configurations can't be containerized.
 ///
 /// # Errors
 ///
-/// * If fails to find the workspace dependencies
-/// * If fails to generate the dockerfile content
-/// * If fails to write the dockerfile to the specified path
-#[allow(clippy::too_many_arguments)]
-pub async fn generate_dockerfile(
+/// * If `target_os` is `windows` or `macos`
+/// * If the target package only has `windows`/`macos` configurations
+/// * If the target package's `clippier.toml` cannot be loaded
+fn validate_docker_target_os(
     workspace_root: &Path,
     target_package: &str,
-    enabled_features: Option<&[String]>,
-    no_default_features: bool,
-    dockerfile_path: &Path,
-    base_image: &str,
-    final_image: &str,
-    args: &[String],
-    build_args: Option<&str>,
-    generate_dockerignore: bool,
-    custom_env_vars: &[String],
-    build_env_vars: &[String],
-    bin: Option<&str>,
+    target_package_path: &str,
+    target_os: &str,
 ) -> Result<(), BoxError> {
-    // Get all potential dependencies for the target package (needed for Docker build compatibility)
-    // Docker builds require all possible dependencies to ensure proper layer caching
-    let mut dependencies =
-        find_workspace_dependencies(workspace_root, target_package, enabled_features, true)?;
+    if NON_LINUX_OSES.contains(&target_os) {
+        return Err(format!(
+            "Cannot generate a Dockerfile for target OS '{target_os}': Docker images are Linux-based"
+        )
+        .into());
+    }
 
-    // Add the target package itself to the dependencies list if not already present
-    let default_target_path = format!(
-        "packages/{}",
-        target_package
-            .strip_prefix("moosicbox_")
-            .unwrap_or(target_package)
-    );
-    if !dependencies.iter().any(|(name, _)| name == target_package) {
-        dependencies.push((target_package.to_string(), default_target_path.clone()));
+    let clippier_path = workspace_root
+        .join(target_package_path)
+        .join("clippier.toml");
+    if !switchy_fs::exists(&clippier_path) {
+        return Ok(());
     }
 
-    // Get target package path
-    let target_package_path = dependencies
+    let conf = load_clippier_conf(&clippier_path)?;
+    let oses: BTreeSet<&str> = conf
+        .config
         .iter()
-        .find(|(name, _)| name == target_package)
-        .map_or_else(|| default_target_path.as_str(), |(_, path)| path.as_str());
+        .flatten()
+        .map(|x| x.os.as_str())
+        .collect();
 
-    // Create the Dockerfile content
-    let dockerfile_content = generate_dockerfile_content(
-        &dependencies,
-        target_package,
-        enabled_features,
+    if oses.is_empty() || oses.contains(target_os) {
+        return Ok(());
+    }
+
+    if oses.iter().all(|os| NON_LINUX_OSES.contains(os)) {
+        return Err(format!(
+            "Cannot generate a Dockerfile for {target_package}: it only has clippier.toml configurations for {}",
+            oses.into_iter().collect::<Vec<_>>().join(", ")
+        )
+        .into());
+    }
+
+    log::warn!(
+        "{target_package} has no clippier.toml configuration for '{target_os}' (available: {}), so no environment variables or system dependencies are collected from it",
+        oses.into_iter().collect::<Vec<_>>().join(", ")
+    );
+
+    Ok(())
+}
+
+/// Contents of a generated Dockerfile and its `.dockerignore`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedDockerfile {
+    /// Content of the Dockerfile
+    pub dockerfile: String,
+    /// Content of the `.dockerignore`, if one is generated
+    pub dockerignore: Option<String>,
+}
+
+impl GeneratedDockerfile {
+    /// Returns the path of the `.dockerignore` written next to a Dockerfile at `dockerfile_path`
+    #[must_use]
+    pub fn dockerignore_path(dockerfile_path: &Path) -> std::path::PathBuf {
+        dockerfile_path.with_extension("dockerignore")
+    }
+
+    /// Writes the Dockerfile to `dockerfile_path` and the `.dockerignore`, if any, next to it
+    ///
+    /// # Errors
+    ///
+    /// * If fails to write either file
+    pub fn write(&self, dockerfile_path: &Path) -> Result<(), BoxError> {
+        switchy_fs::sync::write(dockerfile_path, &self.dockerfile)?;
+
+        if let Some(dockerignore) = &self.dockerignore {
+            switchy_fs::sync::write(Self::dockerignore_path(dockerfile_path), dockerignore)?;
+        }
+
+        Ok(())
+    }
+
+    /// Formats the contents as they would be written for `dockerfile_path`, each file
+    /// preceded by a `==> path <==` header
+    #[must_use]
+    pub fn to_dry_run_output(&self, dockerfile_path: &Path) -> String {
+        use std::fmt::Write as _;
+
+        let mut output = format!("==> {} <==\n{}", dockerfile_path.display(), self.dockerfile);
+
+        if let Some(dockerignore) = &self.dockerignore {
+            if !output.ends_with('\n') {
+                output.push('\n');
+            }
+            let _ = write!(
+                output,
+                "==> {} <==\n{dockerignore}",
+                Self::dockerignore_path(dockerfile_path).display()
+            );
+        }
+
+        output
+    }
+}
+
+/// Generates a Dockerfile for a target package
+///
+/// Environment variables and system dependencies are collected from the `clippier.toml`
+/// configurations for `target_os` (see [`DEFAULT_DOCKER_TARGET_OS`]).
+///
+/// # Errors
+///
+/// * If fails to find the workspace dependencies
+/// * If `target_os` is not a Linux OS, or the package only has non-Linux configurations
+/// * If fails to generate the dockerfile content
+/// * If fails to write the dockerfile to the specified path
+#[allow(clippy::too_many_arguments)]
+pub async fn generate_dockerfile(
+    workspace_root: &Path,
+    target_package: &str,
+    enabled_features: Option<&[String]>,
+    no_default_features: bool,
+    dockerfile_path: &Path,
+    base_image: &str,
+    final_image: &str,
+    args: &[String],
+    build_args: Option<&str>,
+    generate_dockerignore: bool,
+    custom_env_vars: &[String],
+    build_env_vars: &[String],
+    bin: Option<&str>,
+    target_os: &str,
+) -> Result<(), BoxError> {
+    render_dockerfile(
+        workspace_root,
+        target_package,
+        enabled_features,
+        no_default_features,
+        base_image,
+        final_image,
+        args,
+        build_args,
+        generate_dockerignore,
+        custom_env_vars,
+        build_env_vars,
+        bin,
+        target_os,
+    )
+    .await?
+    .write(dockerfile_path)
+}
+
+/// Generates the contents of a Dockerfile for a target package without writing them
+///
+/// See [`generate_dockerfile`].
+///
+/// # Errors
+///
+/// * If fails to find the workspace dependencies
+/// * If `target_os` is not a Linux OS, or the package only has non-Linux configurations
+/// * If fails to generate the dockerfile content
+#[allow(clippy::too_many_arguments)]
+pub async fn render_dockerfile(
+    workspace_root: &Path,
+    target_package: &str,
+    enabled_features: Option<&[String]>,
+    no_default_features: bool,
+    base_image: &str,
+    final_image: &str,
+    args: &[String],
+    build_args: Option<&str>,
+    generate_dockerignore: bool,
+    custom_env_vars: &[String],
+    build_env_vars: &[String],
+    bin: Option<&str>,
+    target_os: &str,
+) -> Result<GeneratedDockerfile, BoxError> {
+    // Get all potential dependencies for the target package (needed for Docker build compatibility)
+    // Docker builds require all possible dependencies to ensure proper layer caching
+    let mut dependencies =
+        find_workspace_dependencies(workspace_root, target_package, enabled_features, true)?;
+
+    // Add the target package itself to the dependencies list if not already present
+    let default_target_path = format!(
+        "packages/{}",
+        target_package
+            .strip_prefix("moosicbox_")
+            .unwrap_or(target_package)
+    );
+    if !dependencies.iter().any(|(name, _)| name == target_package) {
+        dependencies.push((target_package.to_string(), default_target_path.clone()));
+    }
+
+    // Get target package path
+    let target_package_path = dependencies
+        .iter()
+        .find(|(name, _)| name == target_package)
+        .map_or_else(|| default_target_path.as_str(), |(_, path)| path.as_str());
+
+    check_target_binary_collision(workspace_root, target_package, target_package_path, bin)?;
+
+    // Create the Dockerfile content
+    let dockerfile_content = generate_dockerfile_content(
+        &dependencies,
+        target_package,
+        enabled_features,
         no_default_features,
         base_image,
         final_image,
@@ -2322,20 +3489,25 @@ pub async fn generate_dockerfile(
         custom_env_vars,
         build_env_vars,
         bin,
+        target_os,
     )
     .await?;
 
-    // Write the Dockerfile
-    switchy_fs::sync::write(dockerfile_path, dockerfile_content)?;
-
-    if generate_dockerignore {
-        let dockerignore_content =
-            generate_dockerignore_content(&dependencies, target_package, enabled_features)?;
-        let dockerignore_path = dockerfile_path.with_extension("dockerignore");
-        switchy_fs::sync::write(dockerignore_path, dockerignore_content)?;
-    }
+    let dockerignore_content = if generate_dockerignore {
+        Some(generate_dockerignore_content(
+            workspace_root,
+            &dependencies,
+            target_package,
+            enabled_features,
+        )?)
+    } else {
+        None
+    };
 
-    Ok(())
+    Ok(GeneratedDockerfile {
+        dockerfile: dockerfile_content,
+        dockerignore: dockerignore_content,
+    })
 }
 
 /// Generates a Dockerfile for a target package from a git URL
@@ -2361,6 +3533,48 @@ pub fn generate_dockerfile_from_git(
     build_env_vars: &[String],
     bin: Option<&str>,
 ) -> Result<(), BoxError> {
+    render_dockerfile_from_git(
+        git_url,
+        git_ref,
+        target_package,
+        enabled_features,
+        no_default_features,
+        base_image,
+        final_image,
+        args,
+        build_args,
+        generate_dockerignore,
+        custom_env_vars,
+        build_env_vars,
+        bin,
+    )?
+    .write(dockerfile_path)
+}
+
+/// Generates the contents of a Dockerfile for a target package from a git URL without
+/// writing them
+///
+/// See [`generate_dockerfile_from_git`].
+///
+/// # Errors
+///
+/// * If fails to generate the dockerfile content
+#[allow(clippy::too_many_arguments)]
+pub fn render_dockerfile_from_git(
+    git_url: &str,
+    git_ref: &str,
+    target_package: &str,
+    enabled_features: Option<&[String]>,
+    no_default_features: bool,
+    base_image: &str,
+    final_image: &str,
+    args: &[String],
+    build_args: Option<&str>,
+    generate_dockerignore: bool,
+    custom_env_vars: &[String],
+    build_env_vars: &[String],
+    bin: Option<&str>,
+) -> Result<GeneratedDockerfile, BoxError> {
     // Create the Dockerfile content
     let dockerfile_content = generate_dockerfile_content_from_git(
         git_url,
@@ -2377,17 +3591,17 @@ pub fn generate_dockerfile_from_git(
         bin,
     )?;
 
-    // Write the Dockerfile
-    switchy_fs::sync::write(dockerfile_path, dockerfile_content)?;
-
-    if generate_dockerignore {
-        // For git mode, create a minimal dockerignore
-        let dockerignore_content = generate_dockerignore_content_for_git()?;
-        let dockerignore_path = dockerfile_path.with_extension("dockerignore");
-        switchy_fs::sync::write(dockerignore_path, dockerignore_content)?;
-    }
+    // For git mode, create a minimal dockerignore
+    let dockerignore_content = if generate_dockerignore {
+        Some(generate_dockerignore_content_for_git()?)
+    } else {
+        None
+    };
 
-    Ok(())
+    Ok(GeneratedDockerfile {
+        dockerfile: dockerfile_content,
+        dockerignore: dockerignore_content,
+    })
 }
 
 /// Generates Dockerfile content for a package built from a git repository.
@@ -2450,12 +3664,11 @@ pub fn generate_dockerfile_content_from_git(
     writeln!(content, "    rm -rf /var/lib/apt/lists/*\n")?;
 
     // Set build-time environment variables
+    let build_env_vars = env_assignments(build_env_vars);
     if !build_env_vars.is_empty() {
         writeln!(content, "# Set build-time environment variables")?;
-        for env_var in build_env_vars {
-            if let Some((key, value)) = env_var.split_once('=') {
-                writeln!(content, "ENV {key}={value}")?;
-            }
+        for (key, value) in build_env_vars {
+            writeln!(content, "ENV {key}={value}")?;
         }
         content.push('\n');
     }
@@ -2522,10 +3735,8 @@ pub fn generate_dockerfile_content_from_git(
     )?;
 
     // Custom environment variables
-    for env_var in custom_env_vars {
-        if let Some((key, value)) = env_var.split_once('=') {
-            writeln!(content, "ENV {key}={value}")?;
-        }
+    for (key, value) in env_assignments(custom_env_vars) {
+        writeln!(content, "ENV {key}={value}")?;
     }
 
     // Final command
@@ -2586,9 +3797,13 @@ pub fn generate_dockerignore_content_for_git() -> Result<String, BoxError> {
 /// * `custom_env_vars` - Environment variables for runtime
 /// * `build_env_vars` - Environment variables for build stage
 /// * `bin` - Optional binary name (if different from package name)
+/// * `target_os` - `clippier.toml` configuration OS to collect environment variables and
+///   system dependencies for (e.g. "ubuntu")
 ///
 /// # Errors
 ///
+/// * If `target_os` is not a Linux OS, or the package only has non-Linux configurations
+/// * If fails to collect the environment variables or system dependencies
 /// * `std::fmt::Error` - If writing to the string fails
 #[allow(
     clippy::too_many_lines,
@@ -2609,9 +3824,17 @@ pub async fn generate_dockerfile_content(
     custom_env_vars: &[String],
     build_env_vars: &[String],
     bin: Option<&str>,
+    target_os: &str,
 ) -> Result<String, BoxError> {
     use std::fmt::Write as _;
 
+    validate_docker_target_os(
+        workspace_root,
+        target_package,
+        target_package_path,
+        target_os,
+    )?;
+
     let mut content = String::new();
 
     // Collect environment variables for the target package early
@@ -2620,7 +3843,7 @@ pub async fn generate_dockerfile_content(
         target_package,
         target_package_path,
         enabled_features,
-        "ubuntu",
+        target_os,
     )
     .await?;
 
@@ -2642,7 +3865,7 @@ pub async fn generate_dockerfile_content(
 
     // Collect and install system dependencies early for better caching
     let system_deps =
-        collect_system_dependencies(workspace_root, dependencies, enabled_features, "ubuntu")
+        collect_system_dependencies(workspace_root, dependencies, enabled_features, target_os)
             .await?;
 
     // Always ensure essential build tools are available
@@ -2708,12 +3931,11 @@ pub async fn generate_dockerfile_content(
     }
 
     // Set build-time environment variables
+    let build_env_vars = env_assignments(build_env_vars);
     if !build_env_vars.is_empty() {
         writeln!(content, "# Set build-time environment variables")?;
-        for env_var in build_env_vars {
-            if let Some((key, value)) = env_var.split_once('=') {
-                writeln!(content, "ENV {key}={value}")?;
-            }
+        for (key, value) in build_env_vars {
+            writeln!(content, "ENV {key}={value}")?;
         }
         content.push('\n');
     }
@@ -2819,7 +4041,11 @@ pub async fn generate_dockerfile_content(
             content,
             "\n# Accept build args and set as env vars for build process"
         )?;
-        for (key, _) in &env_vars {
+        let keys = env_vars
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .collect::<BTreeSet<_>>();
+        for key in keys {
             writeln!(content, "ARG {key}")?;
             writeln!(content, "ENV {key}=${{{key}}}")?;
         }
@@ -2860,20 +4086,18 @@ pub async fn generate_dockerfile_content(
         "COPY --from=builder /app/target/release/{binary_name} /"
     )?;
 
-    // Runtime environment
-    if let Some(args) = build_args {
-        for arg in args.split(',') {
-            let arg = arg.trim();
-            writeln!(content, "ARG {arg}\nENV {arg}=${{{arg}}}")?;
-        }
-    }
-
-    // Add package-specific environment variables at runtime
-    if !env_vars.is_empty() {
-        for (key, _) in &env_vars {
-            writeln!(content, "ARG {key}")?;
-            writeln!(content, "ENV {key}=${{{key}}}")?;
-        }
+    // Runtime environment: build args and package-specific environment variables, each
+    // declared once and sorted so the Dockerfile is byte-stable across runs
+    let runtime_args = build_args
+        .into_iter()
+        .flat_map(|args| args.split(','))
+        .map(str::trim)
+        .filter(|arg| !arg.is_empty())
+        .chain(env_vars.iter().map(|(key, _)| key.as_str()))
+        .collect::<BTreeSet<_>>();
+    for arg in runtime_args {
+        writeln!(content, "ARG {arg}")?;
+        writeln!(content, "ENV {arg}=${{{arg}}}")?;
     }
 
     writeln!(
@@ -2882,10 +4106,8 @@ pub async fn generate_dockerfile_content(
     )?;
 
     // Add custom environment variables if provided
-    for env_var in custom_env_vars {
-        if let Some((key, value)) = env_var.split_once('=') {
-            writeln!(content, "ENV {key}={value}")?;
-        }
+    for (key, value) in env_assignments(custom_env_vars) {
+        writeln!(content, "ENV {key}={value}")?;
     }
 
     // Final command
@@ -2905,10 +4127,16 @@ pub async fn generate_dockerfile_content(
 
 /// Generates the content of a .dockerignore file for a target package
 ///
+/// After re-including the required packages, appends the `dockerignore-extra` paths from
+/// each package's `clippier.toml` as exclusions, so heavy subdirectories such as test
+/// fixtures stay out of the build context.
+///
 /// # Errors
 ///
 /// * If IO error occurs
+/// * If a package's clippier.toml cannot be read or parsed
 pub fn generate_dockerignore_content(
+    workspace_root: &Path,
     dependencies: &[(String, String)],
     _target_package: &str,
     _enabled_features: Option<&[String]>,
@@ -2925,11 +4153,80 @@ pub fn generate_dockerignore_content(
         writeln!(content, "!/{path}")?;
     }
 
+    // Exclude heavy subdirectories of the included packages
+    let mut exclusions = vec![];
+    for (_, path) in dependencies {
+        let clippier_path = workspace_root.join(path).join("clippier.toml");
+        if !switchy_fs::exists(&clippier_path) {
+            continue;
+        }
+
+        let conf = load_clippier_conf(&clippier_path)?;
+        for extra in conf.dockerignore_extra.iter().flatten() {
+            let extra = extra
+                .trim()
+                .trim_start_matches("./")
+                .trim_start_matches('/');
+            if !extra.is_empty() {
+                exclusions.push(format!("/{path}/{extra}"));
+            }
+        }
+    }
+
+    if !exclusions.is_empty() {
+        content.push('\n');
+        for exclusion in exclusions {
+            writeln!(content, "{exclusion}")?;
+        }
+    }
+
     content.push('\n');
 
     Ok(content)
 }
 
+/// Name of the file at the workspace root listing ignore patterns for affected package
+/// detection, one per line
+pub const CLIPPIER_IGNORE_FILE: &str = ".clippierignore";
+
+/// Returns the ignore patterns of the workspace's [`CLIPPIER_IGNORE_FILE`] followed by
+/// `ignore_patterns`
+///
+/// The file uses the same syntax as `--ignore` patterns, one per line, with blank lines
+/// and lines starting with `#` skipped. Since later patterns override earlier ones,
+/// `ignore_patterns` take precedence over the file.
+///
+/// # Errors
+///
+/// * If the ignore file exists but cannot be read
+pub fn load_ignore_patterns(
+    workspace_root: &Path,
+    ignore_patterns: &[String],
+) -> Result<Vec<String>, BoxError> {
+    let path = workspace_root.join(CLIPPIER_IGNORE_FILE);
+    if !switchy_fs::exists(&path) {
+        return Ok(ignore_patterns.to_vec());
+    }
+
+    let source = switchy_fs::sync::read_to_string(&path)?;
+    let mut patterns = source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    log::trace!(
+        "📄 Loaded {} ignore patterns from {}",
+        patterns.len(),
+        path.display()
+    );
+
+    patterns.extend(ignore_patterns.iter().cloned());
+
+    Ok(patterns)
+}
+
 /// Checks if a file should be ignored based on glob patterns
 ///
 /// Handles negation patterns (!) similar to GitHub Actions path filters.
@@ -3068,73 +4365,336 @@ fn walk_and_match_glob(
     }
 }
 
-/// # Returns
+/// Loads the workspace-level configuration from the root `clippier.toml` of the workspace
+/// at `workspace_root`
 ///
-/// Vector of expanded member paths (without glob patterns)
+/// Returns `None` if the workspace has no root `clippier.toml`.
 ///
 /// # Errors
 ///
-/// * If glob pattern compilation fails
-/// * If directory reading fails
-fn expand_workspace_member_globs(workspace_root: &Path, members: &[&str]) -> Vec<String> {
-    let mut expanded = Vec::new();
-
-    for member in members {
-        // Check if this member contains glob characters
-        if member.contains('*') || member.contains('?') || member.contains('[') {
-            // Use our switchy_fs-aware glob expansion
-            if let Some(expanded_paths) = expand_simple_glob_pattern(workspace_root, member) {
-                for path in expanded_paths {
-                    log::trace!("Expanded glob '{member}' -> '{path}'");
-                    expanded.push(path);
-                }
-            } else {
-                log::warn!("Failed to expand glob pattern '{member}'");
-            }
-        } else {
-            // Not a glob, use as-is
-            expanded.push((*member).to_string());
-        }
+/// * If the root `clippier.toml` cannot be read or parsed
+pub fn load_workspace_clippier_conf(
+    workspace_root: &Path,
+) -> Result<Option<WorkspaceClippierConf>, BoxError> {
+    let conf_path = workspace_root.join("clippier.toml");
+    if !switchy_fs::exists(&conf_path) {
+        log::trace!(
+            "No workspace-level clippier.toml found at: {}",
+            conf_path.display()
+        );
+        return Ok(None);
     }
 
-    // Sort for deterministic output
-    expanded.sort();
-
-    log::debug!(
-        "Expanded {} workspace member patterns to {} paths",
-        members.len(),
-        expanded.len()
-    );
+    log::trace!("Loading workspace config from: {}", conf_path.display());
+    let source = switchy_fs::sync::read_to_string(&conf_path)?;
 
-    expanded
+    Ok(Some(toml::from_str(&source)?))
+}
+
+/// Resolves the members file of the workspace at `workspace_root`
+///
+/// `members_file` (e.g. from `--members-file`) takes precedence over the `members-file`
+/// configured in the workspace's root `clippier.toml`, which is only read if it isn't set.
+/// Relative paths are relative to the workspace root.
+///
+/// # Errors
+///
+/// * If the root `clippier.toml` cannot be read or parsed
+pub fn resolve_members_file(
+    workspace_root: &Path,
+    members_file: Option<&Path>,
+) -> Result<Option<std::path::PathBuf>, BoxError> {
+    if let Some(members_file) = members_file {
+        return Ok(Some(workspace_root.join(members_file)));
+    }
+
+    Ok(load_workspace_clippier_conf(workspace_root)?
+        .and_then(|conf| conf.members_file)
+        .map(|members_file| workspace_root.join(members_file)))
+}
+
+/// Returns the workspace member patterns of the workspace at `workspace_root`
+///
+/// These are the `workspace.members` of the root `Cargo.toml` (`workspace_value`),
+/// followed by the members listed in `members_file` (see [`resolve_members_file`]), for
+/// monorepos that keep a generated members list outside of the root manifest. The members
+/// file lists them the same way as a manifest, either as `[workspace] members = [...]` or
+/// as a top-level `members = [...]`.
+///
+/// Returns `None` if `workspace_value` is not a workspace manifest or neither lists any
+/// members.
+///
+/// # Errors
+///
+/// * If the members file cannot be read or parsed
+/// * If the members file has no `members` array of strings
+/// * If a member listed in the members file is not a directory
+pub fn workspace_member_patterns(
+    workspace_root: &Path,
+    workspace_value: &Value,
+    members_file: Option<&Path>,
+) -> Result<Option<Vec<String>>, BoxError> {
+    let Some(workspace) = workspace_value.get("workspace") else {
+        return Ok(None);
+    };
+
+    let inline = workspace
+        .get("members")
+        .and_then(|x| x.as_array())
+        .and_then(|x| {
+            x.iter()
+                .map(|x| x.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+        });
+
+    let Some(path) = members_file else {
+        return Ok(inline);
+    };
+
+    let source = switchy_fs::sync::read_to_string(path)
+        .map_err(|e| format!("Failed to read members file '{}': {e}", path.display()))?;
+    let value: Value = toml::from_str(&source)
+        .map_err(|e| format!("Failed to parse members file '{}': {e}", path.display()))?;
+
+    let from_file = value
+        .get("workspace")
+        .and_then(|x| x.get("members"))
+        .or_else(|| value.get("members"))
+        .and_then(|x| x.as_array())
+        .and_then(|x| {
+            x.iter()
+                .map(|x| x.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or_else(|| {
+            format!(
+                "Members file '{}' has no `members` array of strings",
+                path.display()
+            )
+        })?;
+
+    for member in &from_file {
+        let is_glob = member.contains('*') || member.contains('?') || member.contains('[');
+        if !is_glob && switchy_fs::sync::read_dir_sorted(workspace_root.join(member)).is_err() {
+            return Err(format!(
+                "Members file '{}' lists '{member}', which is not a directory in the workspace",
+                path.display()
+            )
+            .into());
+        }
+    }
+
+    log::debug!(
+        "Read {} workspace members from '{}'",
+        from_file.len(),
+        path.display()
+    );
+
+    let mut members = inline.unwrap_or_default();
+    for member in from_file {
+        if !members.contains(&member) {
+            members.push(member);
+        }
+    }
+
+    Ok(Some(members))
+}
+
+/// Reads the `affects` mappings from the workspace's root `clippier.toml`
+///
+/// A changed file matching a mapping's glob marks the mapping's packages as directly
+/// affected, so files outside of every package directory can still affect packages.
+///
+/// # Errors
+///
+/// * If the root `clippier.toml` cannot be read or parsed
+/// * If `affects` is not an array of `{ glob, packages }` tables
+pub fn load_affects_mappings(workspace_root: &Path) -> Result<Vec<AffectsMapping>, BoxError> {
+    let conf_path = workspace_root.join("clippier.toml");
+    if !switchy_fs::exists(&conf_path) {
+        return Ok(vec![]);
+    }
+
+    let source = switchy_fs::sync::read_to_string(&conf_path)?;
+    let conf: Value = toml::from_str(&source)?;
+
+    let Some(affects) = conf.get("affects") else {
+        return Ok(vec![]);
+    };
+
+    affects
+        .clone()
+        .try_into()
+        .map_err(|e| format!("Invalid `affects` in '{}': {e}", conf_path.display()).into())
+}
+
+/// Compiles the globs of the workspace's `affects` mappings, dropping packages that are
+/// not workspace members
+///
+/// # Errors
+///
+/// * If the mappings cannot be loaded (see [`load_affects_mappings`])
+/// * If a glob is invalid
+fn compile_affects_mappings(
+    workspace_root: &Path,
+    package_dependencies: &BTreeMap<String, Vec<String>>,
+) -> Result<Vec<(globset::GlobMatcher, AffectsMapping)>, BoxError> {
+    load_affects_mappings(workspace_root)?
+        .into_iter()
+        .map(|mut mapping| {
+            let matcher = globset::Glob::new(&mapping.glob)
+                .map_err(|e| format!("Invalid `affects` glob '{}': {e}", mapping.glob))?
+                .compile_matcher();
+
+            mapping.packages.retain(|package| {
+                let known = package_dependencies.contains_key(package);
+                if !known {
+                    log::warn!(
+                        "⚠️  `affects` glob '{}' maps to unknown package {package}",
+                        mapping.glob
+                    );
+                }
+                known
+            });
+
+            Ok((matcher, mapping))
+        })
+        .collect()
+}
+
+/// # Returns
+///
+/// Vector of expanded member paths (without glob patterns)
+///
+/// # Errors
+///
+/// * If glob pattern compilation fails
+/// * If directory reading fails
+fn expand_workspace_member_globs<S: AsRef<str>>(
+    workspace_root: &Path,
+    members: &[S],
+) -> Vec<String> {
+    let mut expanded = Vec::new();
+
+    for member in members {
+        let member = member.as_ref();
+        // Check if this member contains glob characters
+        if member.contains('*') || member.contains('?') || member.contains('[') {
+            // Use our switchy_fs-aware glob expansion
+            if let Some(expanded_paths) = expand_simple_glob_pattern(workspace_root, member) {
+                for path in expanded_paths {
+                    log::trace!("Expanded glob '{member}' -> '{path}'");
+                    expanded.push(path);
+                }
+            } else {
+                log::warn!("Failed to expand glob pattern '{member}'");
+            }
+        } else {
+            // Not a glob, use as-is
+            expanded.push(member.to_string());
+        }
+    }
+
+    // Sort for deterministic output
+    expanded.sort();
+
+    log::debug!(
+        "Expanded {} workspace member patterns to {} paths",
+        members.len(),
+        expanded.len()
+    );
+
+    expanded
+}
+
+/// Collects the workspace-inherited dependencies of a package manifest
+///
+/// Includes regular, dev and build dependencies, both top-level and from every
+/// `[target.<key>]` table left in the manifest.
+fn workspace_member_dependencies(value: &Value) -> Vec<String> {
+    let targets = value
+        .get("target")
+        .and_then(|x| x.as_table())
+        .into_iter()
+        .flat_map(|x| x.values());
+
+    let mut deps = Vec::new();
+
+    for table in std::iter::once(value).chain(targets) {
+        for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+            let Some(dependencies) = table.get(section).and_then(|x| x.as_table()) else {
+                continue;
+            };
+
+            for (dep_name, dep_value) in dependencies {
+                if is_workspace_dependency(dep_value) && !deps.contains(dep_name) {
+                    deps.push(dep_name.clone());
+                }
+            }
+        }
+    }
+
+    deps
 }
 
 /// Finds packages that are affected by changed files
 ///
+/// Patterns from the workspace's [`CLIPPIER_IGNORE_FILE`] are applied before
+/// `ignore_patterns` (see [`load_ignore_patterns`]).
+/// Files matching an `affects` glob of the root `clippier.toml` also affect the mapped
+/// packages (see [`load_affects_mappings`]).
+///
 /// # Errors
 ///
 /// * If IO error occurs
+/// * If the ignore file cannot be read
 /// * If no workspace members are found
 /// * If ignore pattern compilation fails
-#[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
+/// * If the `affects` mappings cannot be loaded or contain an invalid glob
 pub fn find_affected_packages(
     workspace_root: &Path,
     changed_files: &[String],
     ignore_patterns: &[String],
+) -> Result<Vec<String>, BoxError> {
+    find_affected_packages_for_target(workspace_root, changed_files, ignore_patterns, None, None)
+}
+
+/// Finds packages that are affected by changed files, only following dependencies
+/// active for `target`
+///
+/// Without a target, dependencies from every `[target.<key>]` table are followed.
+/// Additional workspace members are read from `members_file` if it is set (see
+/// [`resolve_members_file`]).
+///
+/// # Errors
+///
+/// * If IO error occurs
+/// * If the ignore file cannot be read
+/// * If the members file cannot be read or lists a member that is not a directory
+/// * If no workspace members are found
+/// * If ignore pattern compilation fails
+/// * If the `affects` mappings cannot be loaded or contain an invalid glob
+#[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
+pub fn find_affected_packages_for_target(
+    workspace_root: &Path,
+    changed_files: &[String],
+    ignore_patterns: &[String],
+    target: Option<&TargetTriple>,
+    members_file: Option<&Path>,
 ) -> Result<Vec<String>, BoxError> {
     log::trace!("🔍 Finding affected packages for changed files: {changed_files:?}");
 
+    let ignore_patterns = &load_ignore_patterns(workspace_root, ignore_patterns)?;
+
     // First, load the workspace and get all members
     let workspace_cargo_path = workspace_root.join("Cargo.toml");
     let workspace_source = switchy_fs::sync::read_to_string(&workspace_cargo_path)?;
     let workspace_value: Value = toml::from_str(&workspace_source)?;
+    let members_file = resolve_members_file(workspace_root, members_file)?;
 
-    let workspace_members_raw = workspace_value
-        .get("workspace")
-        .and_then(|x| x.get("members"))
-        .and_then(|x| x.as_array())
-        .and_then(|x| x.iter().map(|x| x.as_str()).collect::<Option<Vec<_>>>())
-        .ok_or("No workspace members found")?;
+    let workspace_members_raw =
+        workspace_member_patterns(workspace_root, &workspace_value, members_file.as_deref())?
+            .ok_or("No workspace members found")?;
 
     // Expand glob patterns in workspace members
     let workspace_members = expand_workspace_member_globs(workspace_root, &workspace_members_raw);
@@ -3157,6 +4717,10 @@ pub fn find_affected_packages(
         log::trace!("📄 Processing package: {member_path}");
         let source = switchy_fs::sync::read_to_string(&cargo_path)?;
         let value: Value = toml::from_str(&source)?;
+        let value = match target {
+            Some(target) => filter_manifest_for_target(&value, target),
+            None => value,
+        };
 
         // Get package name
         if let Some(package_name) = value
@@ -3168,43 +4732,15 @@ pub fn find_affected_packages(
             package_path_to_name.insert(member_path.clone(), package_name.to_string());
 
             // Extract dependencies that are workspace members
-            let mut deps = Vec::new();
-
-            // Check regular dependencies
-            if let Some(dependencies) = value.get("dependencies").and_then(|x| x.as_table()) {
-                for (dep_name, dep_value) in dependencies {
-                    if is_workspace_dependency(dep_value) {
-                        deps.push(dep_name.clone());
-                    }
-                }
-            }
-
-            // Check dev dependencies
-            if let Some(dev_dependencies) = value.get("dev-dependencies").and_then(|x| x.as_table())
-            {
-                for (dep_name, dep_value) in dev_dependencies {
-                    if is_workspace_dependency(dep_value) && !deps.contains(dep_name) {
-                        deps.push(dep_name.clone());
-                    }
-                }
-            }
-
-            // Check build dependencies
-            if let Some(build_dependencies) =
-                value.get("build-dependencies").and_then(|x| x.as_table())
-            {
-                for (dep_name, dep_value) in build_dependencies {
-                    if is_workspace_dependency(dep_value) && !deps.contains(dep_name) {
-                        deps.push(dep_name.clone());
-                    }
-                }
-            }
+            let deps = workspace_member_dependencies(&value);
 
             log::trace!("📊 Dependencies for {package_name}: {deps:?}");
             package_dependencies.insert(package_name.to_string(), deps);
         }
     }
 
+    let affects = compile_affects_mappings(workspace_root, &package_dependencies)?;
+
     // Find packages directly affected by changed files
     let mut directly_affected_packages = BTreeSet::new();
 
@@ -3242,6 +4778,18 @@ pub fn find_affected_packages(
             );
             directly_affected_packages.insert(package_name.clone());
         }
+
+        for (matcher, mapping) in &affects {
+            if matcher.is_match(changed_file) {
+                for package_name in &mapping.packages {
+                    log::trace!(
+                        "📝 File {changed_file} affects package {package_name} (affects glob: {})",
+                        mapping.glob
+                    );
+                    directly_affected_packages.insert(package_name.clone());
+                }
+            }
+        }
     }
 
     log::trace!("🎯 Directly affected packages: {directly_affected_packages:?}");
@@ -3291,30 +4839,68 @@ pub fn find_affected_packages(
 
 /// Finds packages that are affected by changed files with reasoning
 ///
+/// Patterns from the workspace's [`CLIPPIER_IGNORE_FILE`] are applied before
+/// `ignore_patterns` (see [`load_ignore_patterns`]).
+/// Files matching an `affects` glob of the root `clippier.toml` also affect the mapped
+/// packages (see [`load_affects_mappings`]).
+///
 /// # Errors
 ///
 /// * If IO error occurs
+/// * If the ignore file cannot be read
 /// * If no workspace members are found
 /// * If ignore pattern compilation fails
-#[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
+/// * If the `affects` mappings cannot be loaded or contain an invalid glob
 pub fn find_affected_packages_with_reasoning(
     workspace_root: &Path,
     changed_files: &[String],
     ignore_patterns: &[String],
+) -> Result<Vec<AffectedPackageInfo>, BoxError> {
+    find_affected_packages_with_reasoning_for_target(
+        workspace_root,
+        changed_files,
+        ignore_patterns,
+        None,
+        None,
+    )
+}
+
+/// Finds packages that are affected by changed files with reasoning, only following
+/// dependencies active for `target`
+///
+/// Without a target, dependencies from every `[target.<key>]` table are followed.
+/// Additional workspace members are read from `members_file` if it is set (see
+/// [`resolve_members_file`]).
+///
+/// # Errors
+///
+/// * If IO error occurs
+/// * If the ignore file cannot be read
+/// * If the members file cannot be read or lists a member that is not a directory
+/// * If no workspace members are found
+/// * If ignore pattern compilation fails
+/// * If the `affects` mappings cannot be loaded or contain an invalid glob
+#[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
+pub fn find_affected_packages_with_reasoning_for_target(
+    workspace_root: &Path,
+    changed_files: &[String],
+    ignore_patterns: &[String],
+    target: Option<&TargetTriple>,
+    members_file: Option<&Path>,
 ) -> Result<Vec<AffectedPackageInfo>, BoxError> {
     log::trace!("🔍 Finding affected packages with reasoning for changed files: {changed_files:?}");
 
+    let ignore_patterns = &load_ignore_patterns(workspace_root, ignore_patterns)?;
+
     // First, load the workspace and get all members
     let workspace_cargo_path = workspace_root.join("Cargo.toml");
     let workspace_source = switchy_fs::sync::read_to_string(&workspace_cargo_path)?;
     let workspace_value: Value = toml::from_str(&workspace_source)?;
+    let members_file = resolve_members_file(workspace_root, members_file)?;
 
-    let workspace_members_raw = workspace_value
-        .get("workspace")
-        .and_then(|x| x.get("members"))
-        .and_then(|x| x.as_array())
-        .and_then(|x| x.iter().map(|x| x.as_str()).collect::<Option<Vec<_>>>())
-        .ok_or("No workspace members found")?;
+    let workspace_members_raw =
+        workspace_member_patterns(workspace_root, &workspace_value, members_file.as_deref())?
+            .ok_or("No workspace members found")?;
 
     // Expand glob patterns in workspace members
     let workspace_members = expand_workspace_member_globs(workspace_root, &workspace_members_raw);
@@ -3337,6 +4923,10 @@ pub fn find_affected_packages_with_reasoning(
         log::trace!("📄 Processing package: {member_path}");
         let source = switchy_fs::sync::read_to_string(&cargo_path)?;
         let value: Value = toml::from_str(&source)?;
+        let value = match target {
+            Some(target) => filter_manifest_for_target(&value, target),
+            None => value,
+        };
 
         // Get package name
         if let Some(package_name) = value
@@ -3348,43 +4938,15 @@ pub fn find_affected_packages_with_reasoning(
             package_path_to_name.insert(member_path.clone(), package_name.to_string());
 
             // Extract dependencies that are workspace members
-            let mut deps = Vec::new();
-
-            // Check regular dependencies
-            if let Some(dependencies) = value.get("dependencies").and_then(|x| x.as_table()) {
-                for (dep_name, dep_value) in dependencies {
-                    if is_workspace_dependency(dep_value) {
-                        deps.push(dep_name.clone());
-                    }
-                }
-            }
-
-            // Check dev dependencies
-            if let Some(dev_dependencies) = value.get("dev-dependencies").and_then(|x| x.as_table())
-            {
-                for (dep_name, dep_value) in dev_dependencies {
-                    if is_workspace_dependency(dep_value) && !deps.contains(dep_name) {
-                        deps.push(dep_name.clone());
-                    }
-                }
-            }
-
-            // Check build dependencies
-            if let Some(build_dependencies) =
-                value.get("build-dependencies").and_then(|x| x.as_table())
-            {
-                for (dep_name, dep_value) in build_dependencies {
-                    if is_workspace_dependency(dep_value) && !deps.contains(dep_name) {
-                        deps.push(dep_name.clone());
-                    }
-                }
-            }
+            let deps = workspace_member_dependencies(&value);
 
             log::trace!("📊 Dependencies for {package_name}: {deps:?}");
             package_dependencies.insert(package_name.to_string(), deps);
         }
     }
 
+    let affects = compile_affects_mappings(workspace_root, &package_dependencies)?;
+
     // Find packages directly affected by changed files
     let mut directly_affected_packages = BTreeMap::new(); // package name -> list of changed files
     let mut reasoning_map: BTreeMap<String, Vec<String>> = BTreeMap::new();
@@ -3431,6 +4993,29 @@ pub fn find_affected_packages_with_reasoning(
                 .or_default()
                 .push(format!("Contains changed file: {changed_file}"));
         }
+
+        for (matcher, mapping) in &affects {
+            if matcher.is_match(changed_file) {
+                for package_name in &mapping.packages {
+                    log::trace!(
+                        "📝 File {changed_file} affects package {package_name} (affects glob: {})",
+                        mapping.glob
+                    );
+                    directly_affected_packages
+                        .entry(package_name.clone())
+                        .or_insert_with(Vec::new)
+                        .push(changed_file.clone());
+
+                    reasoning_map
+                        .entry(package_name.clone())
+                        .or_default()
+                        .push(format!(
+                            "Changed file {changed_file} matches affects glob: {}",
+                            mapping.glob
+                        ));
+                }
+            }
+        }
     }
 
     log::trace!("🎯 Directly affected packages: {directly_affected_packages:?}");
@@ -3495,6 +5080,10 @@ pub fn find_affected_packages_with_reasoning(
 
 /// Collects environment variables for a target package
 ///
+/// Variables are returned in declaration order so values can reference earlier variables.
+/// Variables set by several matching configurations are only returned once, at their first
+/// position with the last value.
+///
 /// # Errors
 ///
 /// * If fails to process configs
@@ -3515,28 +5104,13 @@ pub async fn collect_environment_variables(
 
     // Convert features to comma-separated string for the dependencies command
     let features_str = enabled_features.map(|f| f.join(",")).unwrap_or_default();
-    let specific_features = if features_str.is_empty() {
-        None
-    } else {
-        Some(
-            features_str
-                .split(',')
-                .map(str::to_string)
-                .collect::<Vec<_>>(),
-        )
-    };
 
     let packages = process_configs(
         &path,
-        None,
-        None,
-        None,
-        false,
-        false, // randomize = false for system dependencies collection
-        None,  // seed = None for system dependencies collection
-        specific_features.as_deref(),
-        None,
-        None,
+        &FeaturesOptions {
+            features: (!features_str.is_empty()).then_some(features_str),
+            ..Default::default()
+        },
     )
     .await?;
 
@@ -3550,7 +5124,7 @@ pub async fn collect_environment_variables(
         {
             for line in env_str.lines() {
                 if let Some((key, value)) = line.split_once('=') {
-                    env_vars.push((key.to_string(), value.to_string()));
+                    set_env_assignment(&mut env_vars, key.to_string(), value.to_string());
                 }
             }
         }
@@ -3559,6 +5133,33 @@ pub async fn collect_environment_variables(
     Ok(env_vars)
 }
 
+/// Parses `KEY=value` environment variable assignments in declaration order
+///
+/// Later assignments to the same key override the value of earlier ones without moving
+/// them, so values can still reference previously declared variables. Entries without an
+/// `=` are ignored.
+fn env_assignments(env_vars: &[String]) -> Vec<(&str, &str)> {
+    let mut assignments = Vec::new();
+
+    for (key, value) in env_vars
+        .iter()
+        .filter_map(|env_var| env_var.split_once('='))
+    {
+        set_env_assignment(&mut assignments, key, value);
+    }
+
+    assignments
+}
+
+/// Sets `key` to `value`, replacing the value of an existing assignment in place
+fn set_env_assignment<K: PartialEq, V>(assignments: &mut Vec<(K, V)>, key: K, value: V) {
+    if let Some((_, existing)) = assignments.iter_mut().find(|(x, _)| *x == key) {
+        *existing = value;
+    } else {
+        assignments.push((key, value));
+    }
+}
+
 /// Collects system dependencies for a target package
 ///
 /// # Errors
@@ -3585,28 +5186,12 @@ pub async fn collect_system_dependencies(
         }
 
         // Use the existing process_configs function to get dependencies
-        let specific_features = if features_str.is_empty() {
-            None
-        } else {
-            Some(
-                features_str
-                    .split(',')
-                    .map(str::to_string)
-                    .collect::<Vec<_>>(),
-            )
-        };
-
         let packages = process_configs(
             &path,
-            None,
-            None,
-            None,
-            false,
-            false, // randomize = false for dependencies collection
-            None,  // seed = None for dependencies collection
-            specific_features.as_deref(),
-            None,
-            None,
+            &FeaturesOptions {
+                features: (!features_str.is_empty()).then(|| features_str.clone()),
+                ..Default::default()
+            },
         )
         .await?;
 
@@ -3679,35 +5264,551 @@ pub struct SinglePackageResult {
     pub all_affected: Vec<AffectedPackageInfo>,
 }
 
-// Business logic functions for CLI commands
+/// Summary statistics for a generated feature matrix
+///
+/// Emitted by [`handle_features_command`] in summary mode so CI dashboards can track the
+/// matrix size and catch accidental matrix explosions without parsing every job.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeaturesMatrixSummary {
+    /// Total number of jobs in the matrix
+    pub total_jobs: usize,
+    /// Number of distinct packages covered by the matrix
+    pub packages: usize,
+    /// Number of distinct `package:feature` pairs covered by the matrix
+    pub distinct_features: usize,
+    /// Average number of features per job
+    pub average_features_per_job: f64,
+    /// Maximum number of parallel jobs requested, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_parallel: Option<u16>,
+    /// Ratio of jobs to the `max_parallel` limit (`1.0` means every slot is used)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_parallel_utilization: Option<f64>,
+}
 
-/// Handles the dependencies command
-///
-/// # Errors
-///
-/// * If fails to process configs or output results
-pub async fn handle_dependencies_command(
-    file: &str,
-    os: Option<&str>,
-    features: Option<&str>,
-    output: OutputType,
+/// One runner's share of a features matrix split with [`shard_features_matrix`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeaturesMatrixShard {
+    /// Zero-based index of the shard
+    pub shard: usize,
+    /// Matrix entries to run sequentially on this shard's runner
+    pub jobs: Vec<serde_json::Map<String, serde_json::Value>>,
+}
+
+/// Computes summary statistics for an already generated feature matrix
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn summarize_features_matrix(
+    matrix: &[serde_json::Map<String, serde_json::Value>],
+    max_parallel: Option<u16>,
+) -> FeaturesMatrixSummary {
+    let mut packages = BTreeSet::new();
+    let mut distinct_features = BTreeSet::new();
+    let mut total_features = 0usize;
+
+    for entry in matrix {
+        let name = entry
+            .get("name")
+            .and_then(|x| x.as_str())
+            .unwrap_or_default();
+        packages.insert(name);
+
+        if let Some(features) = entry.get("features").and_then(|x| x.as_array()) {
+            total_features += features.len();
+            for feature in features.iter().filter_map(|x| x.as_str()) {
+                distinct_features.insert((name, feature));
+            }
+        }
+    }
+
+    let total_jobs = matrix.len();
+
+    FeaturesMatrixSummary {
+        total_jobs,
+        packages: packages.len(),
+        distinct_features: distinct_features.len(),
+        average_features_per_job: if total_jobs == 0 {
+            0.0
+        } else {
+            total_features as f64 / total_jobs as f64
+        },
+        max_parallel,
+        max_parallel_utilization: max_parallel
+            .filter(|x| *x > 0)
+            .map(|x| total_jobs as f64 / f64::from(x)),
+    }
+}
+
+/// Describes the filters applied to produce a matrix, used in empty matrix errors
+fn describe_matrix_filters(filters: &[(&str, Option<String>)]) -> String {
+    let applied = filters
+        .iter()
+        .filter_map(|(name, value)| value.as_ref().map(|value| format!("--{name}={value}")))
+        .collect::<Vec<_>>();
+
+    if applied.is_empty() {
+        "none".to_string()
+    } else {
+        applied.join(" ")
+    }
+}
+
+/// Returns an error if `fail_on_empty_matrix` filters were given and the matrix is empty
+///
+/// # Errors
+///
+/// * If the matrix is empty and `empty_matrix_filters` is `Some`
+fn check_empty_matrix<T>(matrix: &[T], empty_matrix_filters: Option<&str>) -> Result<(), BoxError> {
+    match empty_matrix_filters {
+        Some(filters) if matrix.is_empty() => {
+            Err(format!("Resulting matrix is empty (filters applied: {filters})").into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Number of packages listed in the error when a matrix exceeds `--max-total-jobs`
+const MAX_TOTAL_JOBS_OFFENDERS: usize = 5;
+
+/// Returns an error if the matrix has more than `max_total_jobs` entries
+///
+/// The error lists the packages contributing the most jobs, so the packages that need
+/// chunking or feature skipping can be spotted at a glance.
+///
+/// # Errors
+///
+/// * If `max_total_jobs` is `Some` and the matrix has more entries than it allows
+pub fn check_max_total_jobs(
+    matrix: &[serde_json::Map<String, serde_json::Value>],
+    max_total_jobs: Option<usize>,
+) -> Result<(), BoxError> {
+    let Some(max_total_jobs) = max_total_jobs else {
+        return Ok(());
+    };
+
+    if matrix.len() <= max_total_jobs {
+        return Ok(());
+    }
+
+    let mut jobs_per_package: BTreeMap<&str, usize> = BTreeMap::new();
+    for entry in matrix {
+        let name = entry
+            .get("name")
+            .and_then(|x| x.as_str())
+            .unwrap_or_default();
+        *jobs_per_package.entry(name).or_default() += 1;
+    }
+
+    let mut offenders = jobs_per_package.into_iter().collect::<Vec<_>>();
+    offenders.sort_by(|(a_name, a_jobs), (b_name, b_jobs)| {
+        b_jobs.cmp(a_jobs).then_with(|| a_name.cmp(b_name))
+    });
+
+    let offenders = offenders
+        .iter()
+        .take(MAX_TOTAL_JOBS_OFFENDERS)
+        .map(|(name, jobs)| format!("{name} ({jobs} jobs)"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Err(format!(
+        "Resulting matrix has {} jobs, exceeding --max-total-jobs={max_total_jobs} \
+         (largest packages: {offenders}). Use --chunked, --max or --skip-features to reduce it",
+        matrix.len()
+    )
+    .into())
+}
+
+/// Groups matrix entries by their `os`, keeping the order of the entries within each OS.
+///
+/// Produces one list per OS so that CI setups can define one job per OS with its entries
+/// nested. Entries without an `os` are dropped.
+#[must_use]
+pub fn group_features_matrix_by_os(
+    matrix: Vec<serde_json::Map<String, serde_json::Value>>,
+) -> BTreeMap<String, Vec<serde_json::Map<String, serde_json::Value>>> {
+    let mut groups: BTreeMap<String, Vec<_>> = BTreeMap::new();
+
+    for entry in matrix {
+        let Some(os) = entry
+            .get("os")
+            .and_then(|x| x.as_str())
+            .map(ToString::to_string)
+        else {
+            log::warn!("Dropping matrix entry without an os: {entry:?}");
+            continue;
+        };
+
+        groups.entry(os).or_default().push(entry);
+    }
+
+    groups
+}
+
+/// Splits matrix entries into exactly `shards` shards for a fixed pool of runners
+///
+/// Shards are balanced by job count, so their sizes differ by at most one job. Entries
+/// stay in matrix order and each shard takes a contiguous run of them, which keeps the
+/// jobs of a package together on one runner. Every entry ends up in exactly one shard;
+/// when there are fewer entries than shards, the trailing shards are empty.
+///
+/// # Errors
+///
+/// * If `shards` is zero
+pub fn shard_features_matrix(
+    matrix: Vec<serde_json::Map<String, serde_json::Value>>,
+    shards: usize,
+) -> Result<Vec<FeaturesMatrixShard>, BoxError> {
+    if shards == 0 {
+        return Err("Number of shards must be greater than zero".into());
+    }
+
+    let base_jobs_per_shard = matrix.len() / shards;
+    let extra_jobs = matrix.len() % shards;
+    let mut entries = matrix.into_iter();
+
+    Ok((0..shards)
+        .map(|shard| {
+            let jobs_in_shard = base_jobs_per_shard + usize::from(shard < extra_jobs);
+            FeaturesMatrixShard {
+                shard,
+                jobs: entries.by_ref().take(jobs_in_shard).collect(),
+            }
+        })
+        .collect())
+}
+
+/// Annotates each matrix entry with a `needs` list naming the entries it depends on
+///
+/// An entry needs every other entry whose package is a workspace dependency of its own
+/// package with the entry's features enabled (see [`find_workspace_dependencies`]), so
+/// CI pipelines can build leaf packages in earlier stages than their dependents. Entries
+/// are referenced by their `name`. Edges that would close a dependency cycle are omitted
+/// with a warning, keeping the `needs` graph acyclic.
+///
+/// # Errors
+///
+/// * If an entry's `Cargo.toml` cannot be read or parsed
+/// * If resolving an entry's workspace dependencies fails
+pub fn annotate_features_matrix_needs(
+    workspace_root: &Path,
+    matrix: &mut [serde_json::Map<String, serde_json::Value>],
+) -> Result<(), BoxError> {
+    let mut package_names: BTreeMap<String, String> = BTreeMap::new();
+    let mut entry_packages = Vec::with_capacity(matrix.len());
+
+    for entry in matrix.iter() {
+        let path = entry
+            .get("path")
+            .and_then(|x| x.as_str())
+            .ok_or("Matrix entry is missing its path")?;
+
+        if !package_names.contains_key(path) {
+            let source = switchy_fs::sync::read_to_string(Path::new(path).join("Cargo.toml"))?;
+            let value: Value = toml::from_str(&source)?;
+            let package_name = value
+                .get("package")
+                .and_then(|x| x.get("name"))
+                .and_then(|x| x.as_str())
+                .ok_or_else(|| format!("No package name in {path}/Cargo.toml"))?;
+            package_names.insert(path.to_string(), package_name.to_string());
+        }
+
+        entry_packages.push(package_names[path].clone());
+    }
+
+    let entry_name = |entry: &serde_json::Map<String, serde_json::Value>| {
+        entry
+            .get("name")
+            .and_then(|x| x.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    let mut package_entries: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    for (entry, package) in matrix.iter().zip(&entry_packages) {
+        package_entries
+            .entry(package.as_str())
+            .or_default()
+            .insert(entry_name(entry));
+    }
+
+    let mut dependency_cache: BTreeMap<(String, Vec<String>), Vec<String>> = BTreeMap::new();
+    let mut edges: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    for (entry, package) in matrix.iter().zip(&entry_packages) {
+        let features = entry
+            .get("features")
+            .and_then(|x| x.as_array())
+            .map(|x| {
+                x.iter()
+                    .filter_map(|x| x.as_str().map(str::to_string))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let key = (package.clone(), features);
+        if !dependency_cache.contains_key(&key) {
+            let dependencies = find_workspace_dependencies(
+                workspace_root,
+                package,
+                Some(key.1.as_slice()),
+                false,
+            )?
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+            dependency_cache.insert(key.clone(), dependencies);
+        }
+
+        let name = entry_name(entry);
+        let needs = edges.entry(name.clone()).or_default();
+
+        for dependency in &dependency_cache[&key] {
+            if let Some(names) = package_entries.get(dependency.as_str()) {
+                needs.extend(names.iter().filter(|x| **x != name).cloned());
+            }
+        }
+    }
+
+    let edges = remove_cyclic_edges(edges);
+
+    for entry in matrix.iter_mut() {
+        let needs = edges
+            .get(&entry_name(entry))
+            .map(|x| x.iter().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        entry.insert("needs".to_string(), needs.into());
+    }
+
+    Ok(())
+}
+
+/// Maximum length, in characters, of a [`github_display_name`], keeping generated names
+/// within GitHub's job name length limit
+pub const GITHUB_NAME_MAX_LENGTH: usize = 100;
+
+/// Builds a human-readable GitHub Actions job name for a matrix entry
+///
+/// The name is composed of the entry's `name`, its feature chunk in brackets and its
+/// `os`, e.g. `moosicbox_player [flac,mp3] ubuntu`. Names longer than
+/// [`GITHUB_NAME_MAX_LENGTH`] characters are shortened by truncating the feature chunk with
+/// an ellipsis, or the whole name if the package name and OS alone are too long.
+#[must_use]
+pub fn github_display_name(entry: &serde_json::Map<String, serde_json::Value>) -> String {
+    const ELLIPSIS: char = '…';
+
+    let name = entry
+        .get("name")
+        .and_then(|x| x.as_str())
+        .unwrap_or_default();
+    let os = entry.get("os").and_then(|x| x.as_str()).unwrap_or_default();
+    let features = match entry.get("features") {
+        Some(serde_json::Value::Array(features)) => features
+            .iter()
+            .filter_map(|x| x.as_str())
+            .collect::<Vec<_>>()
+            .join(","),
+        Some(serde_json::Value::String(features)) => features.clone(),
+        _ => String::new(),
+    };
+
+    if features.is_empty() {
+        let display_name = format!("{name} {os}");
+        return truncate_with_ellipsis(display_name.trim(), GITHUB_NAME_MAX_LENGTH, ELLIPSIS);
+    }
+
+    let display_name = format!("{name} [{features}] {os}");
+    if display_name.chars().count() <= GITHUB_NAME_MAX_LENGTH {
+        return display_name;
+    }
+
+    // Everything but the features: "{name} [" + "…] {os}"
+    let fixed_length = name.chars().count() + os.chars().count() + 5;
+    if fixed_length < GITHUB_NAME_MAX_LENGTH {
+        let features = features
+            .chars()
+            .take(GITHUB_NAME_MAX_LENGTH - fixed_length)
+            .collect::<String>();
+        return format!("{name} [{features}{ELLIPSIS}] {os}");
+    }
+
+    truncate_with_ellipsis(&display_name, GITHUB_NAME_MAX_LENGTH, ELLIPSIS)
+}
+
+/// Truncates `value` to at most `max_length` characters, ending it with `ellipsis` if it
+/// was shortened
+fn truncate_with_ellipsis(value: &str, max_length: usize, ellipsis: char) -> String {
+    if value.chars().count() <= max_length {
+        return value.to_string();
+    }
+
+    let mut truncated = value
+        .chars()
+        .take(max_length.saturating_sub(1))
+        .collect::<String>();
+    truncated.push(ellipsis);
+    truncated
+}
+
+/// Annotates each matrix entry with a `displayName` naming its GitHub Actions job (see
+/// [`github_display_name`])
+pub fn annotate_features_matrix_github_names(
+    matrix: &mut [serde_json::Map<String, serde_json::Value>],
+) {
+    for entry in matrix.iter_mut() {
+        let display_name = github_display_name(entry);
+        entry.insert("displayName".to_string(), display_name.into());
+    }
+}
+
+/// Drops the edges that close a cycle in `edges`, visiting nodes in name order
+fn remove_cyclic_edges(
+    mut edges: BTreeMap<String, BTreeSet<String>>,
+) -> BTreeMap<String, BTreeSet<String>> {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum State {
+        Visiting,
+        Done,
+    }
+
+    fn visit(
+        node: &str,
+        edges: &mut BTreeMap<String, BTreeSet<String>>,
+        states: &mut BTreeMap<String, State>,
+    ) {
+        states.insert(node.to_string(), State::Visiting);
+
+        let targets = edges.get(node).cloned().unwrap_or_default();
+        for target in targets {
+            match states.get(&target) {
+                Some(State::Visiting) => {
+                    log::warn!(
+                        "Omitting needs edge {node} -> {target} since it would create a dependency cycle"
+                    );
+                    if let Some(needs) = edges.get_mut(node) {
+                        needs.remove(&target);
+                    }
+                }
+                Some(State::Done) => {}
+                None => visit(&target, edges, states),
+            }
+        }
+
+        states.insert(node.to_string(), State::Done);
+    }
+
+    let mut states = BTreeMap::new();
+    let nodes = edges.keys().cloned().collect::<Vec<_>>();
+
+    for node in nodes {
+        if !states.contains_key(&node) {
+            visit(&node, &mut edges, &mut states);
+        }
+    }
+
+    edges
+}
+
+/// Checks the final features matrix against `empty_matrix_filters` and `max_total_jobs`
+/// and converts its entries into [`MatrixEntry`]s
+///
+/// When `needs_workspace_root` is set, entries are first annotated with their `needs` (see
+/// [`annotate_features_matrix_needs`]). When `github_names` is set, entries are annotated
+/// with their `displayName` (see [`annotate_features_matrix_github_names`]).
+fn finish_features_matrix(
+    mut packages: Vec<serde_json::Map<String, serde_json::Value>>,
+    needs_workspace_root: Option<&Path>,
+    github_names: bool,
+    empty_matrix_filters: Option<&str>,
+    max_total_jobs: Option<usize>,
+) -> Result<Vec<MatrixEntry>, BoxError> {
+    check_empty_matrix(&packages, empty_matrix_filters)?;
+    check_max_total_jobs(&packages, max_total_jobs)?;
+
+    if let Some(workspace_root) = needs_workspace_root {
+        annotate_features_matrix_needs(workspace_root, &mut packages)?;
+    }
+
+    if github_names {
+        annotate_features_matrix_github_names(&mut packages);
+    }
+
+    packages.into_iter().map(MatrixEntry::from_map).collect()
+}
+
+/// Serializes the final features matrix, its summary when `summary` is set, the matrix
+/// grouped by OS when `group_by_os` is set, or the matrix split into shards when `shards`
+/// is set
+fn format_features_output(
+    entries: Vec<MatrixEntry>,
+    summary: bool,
+    group_by_os: bool,
+    max_parallel: Option<u16>,
+    shards: Option<u16>,
+    output: OutputType,
+) -> Result<String, BoxError> {
+    let packages = entries
+        .into_iter()
+        .map(MatrixEntry::into_map)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if summary {
+        return Ok(serde_json::to_string(&summarize_features_matrix(
+            &packages,
+            max_parallel,
+        ))?);
+    }
+
+    if group_by_os {
+        return Ok(serde_json::to_string(&group_features_matrix_by_os(
+            packages,
+        ))?);
+    }
+
+    if let Some(shards) = shards {
+        return Ok(serde_json::to_string(&shard_features_matrix(
+            packages,
+            usize::from(shards),
+        )?)?);
+    }
+
+    Ok(match output {
+        OutputType::Json => serde_json::to_string(&packages)?,
+        OutputType::Raw => {
+            let mut results = Vec::new();
+            for package in packages {
+                if let Some(features) = package.get("features") {
+                    results.push(features.to_string());
+                }
+            }
+            results.join("\n")
+        }
+    })
+}
+
+// Business logic functions for CLI commands
+
+/// Handles the dependencies command
+///
+/// # Errors
+///
+/// * If fails to process configs or output results
+pub async fn handle_dependencies_command(
+    file: &str,
+    os: Option<&str>,
+    features: Option<&str>,
+    output: OutputType,
 ) -> Result<String, BoxError> {
     use std::str::FromStr;
 
     let path = std::path::PathBuf::from_str(file)?;
-    let specific_features = features.map(|f| f.split(',').map(str::to_string).collect::<Vec<_>>());
-
     let packages = process_workspace_configs(
         &path,
-        None,
-        None,
-        None,
-        false,
-        false, // randomize = false for dependencies command
-        None,  // seed = None for dependencies command
-        specific_features.as_deref(),
-        None,
-        None,
+        &FeaturesOptions {
+            features: features.map(str::to_string),
+            ..Default::default()
+        },
     )
     .await?;
 
@@ -3748,19 +5849,12 @@ pub async fn handle_environment_command(
     use std::str::FromStr;
 
     let path = std::path::PathBuf::from_str(file)?;
-    let specific_features = features.map(|f| f.split(',').map(str::to_string).collect::<Vec<_>>());
-
     let packages = process_workspace_configs(
         &path,
-        None,
-        None,
-        None,
-        false,
-        false, // randomize = false for environment command
-        None,  // seed = None for environment command
-        specific_features.as_deref(),
-        None,
-        None,
+        &FeaturesOptions {
+            features: features.map(str::to_string),
+            ..Default::default()
+        },
     )
     .await?;
 
@@ -3801,19 +5895,12 @@ pub async fn handle_ci_steps_command(
     use std::str::FromStr;
 
     let path = std::path::PathBuf::from_str(file)?;
-    let specific_features = features.map(|f| f.split(',').map(str::to_string).collect::<Vec<_>>());
-
     let packages = process_workspace_configs(
         &path,
-        None,
-        None,
-        None,
-        false,
-        false, // randomize = false for ci steps command
-        None,  // seed = None for ci steps command
-        specific_features.as_deref(),
-        None,
-        None,
+        &FeaturesOptions {
+            features: features.map(str::to_string),
+            ..Default::default()
+        },
     )
     .await?;
 
@@ -3840,77 +5927,309 @@ pub async fn handle_ci_steps_command(
     }
 }
 
+/// Options for generating a feature matrix with [`handle_features_command`],
+/// [`features_matrix_entries`] and [`process_configs`].
+///
+/// The defaults generate the whole matrix of the workspace in the current directory in the
+/// raw output format, like the `features` command without any flags, so callers only set
+/// the options they need:
+///
+/// ```rust,no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// use clippier::{FeaturesOptions, OutputType, handle_features_command};
+///
+/// let matrix = handle_features_command(&FeaturesOptions {
+///     file: "path/to/workspace".to_string(),
+///     os: Some("ubuntu".to_string()),
+///     output: OutputType::Json,
+///     ..Default::default()
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct FeaturesOptions {
+    /// Path to the workspace root or Cargo.toml
+    pub file: String,
+    /// Only include entries for this OS
+    pub os: Option<String>,
+    /// Number of features to skip per package
+    pub offset: Option<u16>,
+    /// Maximum number of features per package
+    pub max: Option<u16>,
+    /// Maximum number of parallel jobs, rechunking the matrix to fit
+    pub max_parallel: Option<u16>,
+    /// Number of features per chunk
+    pub chunked: Option<u16>,
+    /// Spread features across chunks instead of filling them in order
+    pub spread: bool,
+    /// Randomize features before chunking/spreading
+    pub randomize: bool,
+    /// Base seed for randomization, from which each package's seed is derived (generated
+    /// and printed to stderr when randomizing without one)
+    pub seed: Option<u64>,
+    /// Comma-separated features to generate combinations for
+    pub features: Option<String>,
+    /// Comma-separated features to leave out
+    pub skip_features: Option<String>,
+    /// Comma-separated features added to every entry
+    pub required_features: Option<String>,
+    /// Specific packages to process
+    pub packages: Option<Vec<String>>,
+    /// Changed files (relative to the workspace root), only including affected packages
+    pub changed_files: Option<Vec<String>>,
+    /// Git base commit for external dependency analysis
+    #[cfg(feature = "git-diff")]
+    pub git_base: Option<String>,
+    /// Git head commit for external dependency analysis
+    #[cfg(feature = "git-diff")]
+    pub git_head: Option<String>,
+    /// Include why each package is affected in the output (only with `changed_files`)
+    pub include_reasoning: bool,
+    /// Glob patterns of changed files to ignore when detecting affected packages
+    pub ignore_patterns: Option<Vec<String>>,
+    /// Skip packages matching any of these criteria (e.g. `package.publish=false`)
+    pub skip_if: Vec<String>,
+    /// Only include packages matching all of these criteria (e.g. `categories@=audio`)
+    pub include_if: Vec<String>,
+    /// Lua transform scripts to apply to the generated matrix
+    #[cfg(feature = "_transforms")]
+    pub transform_scripts: Vec<std::path::PathBuf>,
+    /// Enable trace mode for transform debugging
+    #[cfg(feature = "_transforms")]
+    pub transform_trace: bool,
+    /// Workspace types to detect, all of them if `None`
+    #[cfg(feature = "_workspace")]
+    pub workspace_type: Option<Vec<workspace::WorkspaceType>>,
+    /// Emit a [`FeaturesMatrixSummary`] JSON object instead of the matrix
+    pub summary: bool,
+    /// Environment profiles selecting profile-gated env vars
+    pub env_profile: Option<Vec<String>>,
+    /// Error out if the resulting matrix is empty
+    pub fail_on_empty_matrix: bool,
+    /// Emit a JSON object mapping each OS to its matrix entries (see
+    /// [`group_features_matrix_by_os`])
+    pub group_by_os: bool,
+    /// Target triple to evaluate `[target.'cfg(...)']` tables against, leaving out
+    /// dependencies and features inactive for it
+    pub target: Option<String>,
+    /// Annotate each entry with the entries it depends on (see
+    /// [`annotate_features_matrix_needs`])
+    pub needs: bool,
+    /// Error out if the matrix has more jobs than this
+    pub max_total_jobs: Option<usize>,
+    /// Skip collecting config from workspace dependencies (see [`create_map`])
+    pub no_propagation: bool,
+    /// Emit the matrix split into this many [`FeaturesMatrixShard`]s (see
+    /// [`shard_features_matrix`])
+    pub shards: Option<u16>,
+    /// Replace each package's feature chunks with the reduced set of feature combinations
+    /// computed by [`cover_features`]
+    pub coverage_strategy: Option<CoverageStrategy>,
+    /// Annotate each entry with a `displayName` for GitHub Actions jobs (see
+    /// [`annotate_features_matrix_github_names`])
+    pub github_names: bool,
+    /// TOML file listing additional workspace members, overriding the `members-file` of
+    /// the root `clippier.toml` (see [`resolve_members_file`])
+    pub members_file: Option<std::path::PathBuf>,
+    /// Output format (JSON or raw)
+    pub output: OutputType,
+}
+
+impl Default for FeaturesOptions {
+    fn default() -> Self {
+        Self {
+            file: ".".to_string(),
+            os: None,
+            offset: None,
+            max: None,
+            max_parallel: None,
+            chunked: None,
+            spread: false,
+            randomize: false,
+            seed: None,
+            features: None,
+            skip_features: None,
+            required_features: None,
+            packages: None,
+            changed_files: None,
+            #[cfg(feature = "git-diff")]
+            git_base: None,
+            #[cfg(feature = "git-diff")]
+            git_head: None,
+            include_reasoning: false,
+            ignore_patterns: None,
+            skip_if: vec![],
+            include_if: vec![],
+            #[cfg(feature = "_transforms")]
+            transform_scripts: vec![],
+            #[cfg(feature = "_transforms")]
+            transform_trace: false,
+            #[cfg(feature = "_workspace")]
+            workspace_type: None,
+            summary: false,
+            env_profile: None,
+            fail_on_empty_matrix: false,
+            group_by_os: false,
+            target: None,
+            needs: false,
+            max_total_jobs: None,
+            no_propagation: false,
+            shards: None,
+            coverage_strategy: None,
+            github_names: false,
+            members_file: None,
+            output: OutputType::Raw,
+        }
+    }
+}
+
 /// Generates a feature matrix for workspace packages.
 ///
 /// Analyzes the workspace to determine all valid feature combinations for each package,
 /// optionally filtering by affected packages and applying feature constraints. Returns
-/// the matrix in JSON or raw format for use in CI/CD pipelines.
+/// the matrix in JSON or raw format for use in CI/CD pipelines. The matrix is generated by
+/// [`features_matrix_entries`]; use it directly to get the entries as [`MatrixEntry`]s.
 ///
-/// # Arguments
+/// The `summary`, `group_by_os`, `shards` and `output` [`FeaturesOptions`] select how the
+/// matrix is emitted. Annotating entries with `needs` and `github_names` is skipped in
+/// `summary` mode. Shards are only emitted as JSON.
 ///
-/// * `file` - Path to the workspace root or Cargo.toml
-/// * `only_affected` - Only include packages affected by changes
-/// * `change_ref` - Git reference for change detection (e.g., "origin/main")
-/// * `head_ref` - Git reference for the current state (e.g., "HEAD")
-/// * `skip_filters` - Package filters to skip (excluded packages)
-/// * `include_filters` - Package filters to include (only these packages)
-/// * `enable` - Features to enable for all packages
-/// * `disable` - Features to disable for all packages
-/// * `features` - Specific features to generate combinations for
-/// * `max_parallel` - Maximum parallel jobs (for rechunking)
-/// * `randomize` - Randomize the order of feature combinations
-/// * `seed` - Seed for randomization
-/// * `output` - Output format (JSON or raw)
+/// # Errors
+///
+/// * If `shards` is set with [`OutputType::Raw`]
+/// * If generating the matrix fails (see [`features_matrix_entries`])
+/// * `serde_json::Error` - If JSON serialization fails
+#[allow(clippy::future_not_send)]
+pub async fn handle_features_command(options: &FeaturesOptions) -> Result<String, BoxError> {
+    if options.shards.is_some() && options.output == OutputType::Raw {
+        return Err("--shards is only supported with --output json".into());
+    }
+
+    let entries = features_matrix_entries(&FeaturesOptions {
+        needs: options.needs && !options.summary,
+        github_names: options.github_names && !options.summary,
+        ..options.clone()
+    })
+    .await?;
+
+    format_features_output(
+        entries,
+        options.summary,
+        options.group_by_os,
+        options.max_parallel,
+        options.shards,
+        options.output,
+    )
+}
+
+/// Generates the entries of a feature matrix for workspace packages.
+///
+/// This is the typed counterpart of [`handle_features_command`]: it generates the same
+/// matrix, but returns its entries as [`MatrixEntry`]s instead of serializing them. The
+/// `summary`, `group_by_os`, `shards` and `output` [`FeaturesOptions`] only affect how
+/// [`handle_features_command`] emits the matrix and are ignored.
 ///
 /// # Errors
 ///
 /// * `std::io::Error` - If file operations fail
 /// * `serde_json::Error` - If JSON serialization fails
+/// * If an entry doesn't convert to a [`MatrixEntry`], e.g. after a transform script
 /// * `anyhow::Error` - If workspace processing or filtering fails
-#[allow(
-    clippy::too_many_arguments,
-    clippy::too_many_lines,
-    clippy::cognitive_complexity
-)]
-#[allow(clippy::fn_params_excessive_bools, clippy::future_not_send)]
-pub async fn handle_features_command(
-    file: &str,
-    os: Option<&str>,
-    offset: Option<u16>,
-    max: Option<u16>,
-    max_parallel: Option<u16>,
-    chunked: Option<u16>,
-    spread: bool,
-    randomize: bool,
-    seed: Option<u64>,
-    features: Option<&str>,
-    skip_features: Option<&str>,
-    required_features: Option<&str>,
-    packages: Option<&[String]>,
-    changed_files: Option<&[String]>,
-    #[cfg(feature = "git-diff")] git_base: Option<&str>,
-    #[cfg(feature = "git-diff")] git_head: Option<&str>,
-    include_reasoning: bool,
-    ignore_patterns: Option<&[String]>,
-    skip_if: &[String],
-    include_if: &[String],
-    #[cfg(feature = "_transforms")] transform_scripts: &[std::path::PathBuf],
-    #[cfg(feature = "_transforms")] transform_trace: bool,
-    #[cfg(feature = "_workspace")] workspace_type: Option<&[workspace::WorkspaceType]>,
-    output: OutputType,
-) -> Result<String, BoxError> {
+/// * If `target` is not a valid target triple
+#[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
+#[allow(clippy::future_not_send)]
+pub async fn features_matrix_entries(
+    options: &FeaturesOptions,
+) -> Result<Vec<MatrixEntry>, BoxError> {
     use std::str::FromStr;
 
+    let file = options.file.as_str();
+    let os = options.os.as_deref();
+    let offset = options.offset;
+    let max = options.max;
+    let max_parallel = options.max_parallel;
+    let chunked = options.chunked;
+    let features = options.features.as_deref();
+    let skip_features = options.skip_features.as_deref();
+    let required_features = options.required_features.as_deref();
+    let packages = options.packages.as_deref();
+    let changed_files = options.changed_files.as_deref();
+    #[cfg(feature = "git-diff")]
+    let git_base = options.git_base.as_deref();
+    #[cfg(feature = "git-diff")]
+    let git_head = options.git_head.as_deref();
+    let include_reasoning = options.include_reasoning;
+    let ignore_patterns = options.ignore_patterns.as_deref();
+    let skip_if = options.skip_if.as_slice();
+    let include_if = options.include_if.as_slice();
+    #[cfg(feature = "_transforms")]
+    let transform_scripts = options.transform_scripts.as_slice();
+    #[cfg(feature = "_transforms")]
+    let transform_trace = options.transform_trace;
+    #[cfg(feature = "_workspace")]
+    let workspace_type = options.workspace_type.as_deref();
+    let env_profile = options.env_profile.as_deref();
+    let fail_on_empty_matrix = options.fail_on_empty_matrix;
+    let target = options.target.as_deref();
+    let needs = options.needs;
+    let max_total_jobs = options.max_total_jobs;
+    let github_names = options.github_names;
+
     // Log the workspace type for debugging
     #[cfg(feature = "_workspace")]
     log::debug!("Using workspace type filter: {workspace_type:?}");
 
+    // Resolve the base seed once so every package derives its seed from the same value
+    let seed = if options.randomize {
+        let seed = options
+            .seed
+            .unwrap_or_else(|| switchy_random::rng().next_u64());
+        eprintln!("Base seed: {seed} (rerun with --seed {seed} to reproduce)");
+        Some(seed)
+    } else {
+        options.seed
+    };
+    let options = &FeaturesOptions {
+        seed,
+        ..options.clone()
+    };
+
+    let empty_matrix_filters = fail_on_empty_matrix.then(|| {
+        describe_matrix_filters(&[
+            ("os", os.map(str::to_string)),
+            ("offset", offset.as_ref().map(ToString::to_string)),
+            ("max", max.as_ref().map(ToString::to_string)),
+            ("features", features.map(str::to_string)),
+            ("skip-features", skip_features.map(str::to_string)),
+            ("required-features", required_features.map(str::to_string)),
+            ("packages", packages.map(|x| x.join(","))),
+            ("changed-files", changed_files.map(|x| x.join(","))),
+            #[cfg(feature = "git-diff")]
+            ("git-base", git_base.map(str::to_string)),
+            #[cfg(feature = "git-diff")]
+            ("git-head", git_head.map(str::to_string)),
+            (
+                "ignore",
+                ignore_patterns
+                    .filter(|x| !x.is_empty())
+                    .map(|x| x.join(",")),
+            ),
+            ("skip-if", (!skip_if.is_empty()).then(|| skip_if.join(","))),
+            (
+                "include-if",
+                (!include_if.is_empty()).then(|| include_if.join(",")),
+            ),
+            ("env-profile", env_profile.map(|x| x.join(","))),
+            ("target", target.map(str::to_string)),
+        ])
+    });
+    let empty_matrix_filters = empty_matrix_filters.as_deref();
+    let target = target.map(TargetTriple::from_str).transpose()?;
+
     let path = std::path::PathBuf::from_str(file)?;
-    let specific_features = features.map(|f| f.split(',').map(str::to_string).collect::<Vec<_>>());
-    let skip_features_list =
-        skip_features.map(|f| f.split(',').map(str::to_string).collect::<Vec<_>>());
-    let required_features_list =
-        required_features.map(|f| f.split(',').map(str::to_string).collect::<Vec<_>>());
 
     // If specific packages are requested, filter to only those packages
     if let Some(selected_packages) = packages
@@ -3938,13 +6257,11 @@ pub async fn handle_features_command(
             let workspace_source =
                 switchy_fs::unsync::read_to_string(&workspace_cargo_path).await?;
             let workspace_value: Value = toml::from_str(&workspace_source)?;
+            let members_file = resolve_members_file(&path, options.members_file.as_deref())?;
 
-            let workspace_members_raw = workspace_value
-                .get("workspace")
-                .and_then(|x| x.get("members"))
-                .and_then(|x| x.as_array())
-                .and_then(|x| x.iter().map(|x| x.as_str()).collect::<Option<Vec<_>>>())
-                .unwrap_or_default();
+            let workspace_members_raw =
+                workspace_member_patterns(&path, &workspace_value, members_file.as_deref())?
+                    .unwrap_or_default();
 
             let workspace_members = expand_workspace_member_globs(&path, &workspace_members_raw);
 
@@ -3994,19 +6311,7 @@ pub async fn handle_features_command(
         for selected_pkg in &filtered_packages {
             if let Some(package_path) = package_name_to_path.get(selected_pkg) {
                 let package_dir = path.join(package_path);
-                let packages = process_configs(
-                    &package_dir,
-                    offset,
-                    max,
-                    chunked,
-                    spread,
-                    randomize,
-                    seed,
-                    specific_features.as_deref(),
-                    skip_features_list.as_deref(),
-                    required_features_list.as_deref(),
-                )
-                .await?;
+                let packages = process_configs(&package_dir, options).await?;
 
                 all_filtered_packages.extend(packages);
             } else {
@@ -4033,19 +6338,13 @@ pub async fn handle_features_command(
             )?;
         }
 
-        let result = match output {
-            OutputType::Json => serde_json::to_string(&all_filtered_packages)?,
-            OutputType::Raw => {
-                let mut results = Vec::new();
-                for package in all_filtered_packages {
-                    if let Some(features) = package.get("features") {
-                        results.push(features.to_string());
-                    }
-                }
-                results.join("\n")
-            }
-        };
-        return Ok(result);
+        return finish_features_matrix(
+            all_filtered_packages,
+            needs.then_some(path.as_path()),
+            github_names,
+            empty_matrix_filters,
+            max_total_jobs,
+        );
     }
 
     // Determine if we should use filtering logic based on changed files
@@ -4067,6 +6366,8 @@ pub async fn handle_features_command(
             find_packages_affected_by_external_deps_with_mapping, get_changed_files_from_git,
         };
 
+        let members_file = resolve_members_file(&path, options.members_file.as_deref())?;
+
         // Handle changed files filtering (from manual specification or git)
         let mut all_changed_files = Vec::new();
 
@@ -4110,11 +6411,8 @@ pub async fn handle_features_command(
                         switchy_fs::unsync::read_to_string(&workspace_cargo_path).await?;
                     let workspace_value: Value = toml::from_str(&workspace_source)?;
 
-                    if let Some(workspace_members_raw) = workspace_value
-                        .get("workspace")
-                        .and_then(|x| x.get("members"))
-                        .and_then(|x| x.as_array())
-                        .and_then(|x| x.iter().map(|x| x.as_str()).collect::<Option<Vec<_>>>())
+                    if let Some(workspace_members_raw) =
+                        workspace_member_patterns(&path, &workspace_value, members_file.as_deref())?
                     {
                         // Expand glob patterns and convert to Vec<String> for build_external_dependency_map
                         let workspace_members_owned =
@@ -4148,25 +6446,36 @@ pub async fn handle_features_command(
 
         // If no files were found, return empty result
         if all_changed_files.is_empty() {
-            return match output {
-                OutputType::Json => Ok("[]".to_string()),
-                OutputType::Raw => Ok(String::new()),
-            };
+            return finish_features_matrix(
+                vec![],
+                needs.then_some(path.as_path()),
+                github_names,
+                empty_matrix_filters,
+                max_total_jobs,
+            );
         }
 
         // First find affected packages from file changes
         let ignore_patterns_vec = ignore_patterns.unwrap_or(&[]).to_vec();
         let (mut affected_packages, affected_with_reasoning) = if include_reasoning {
-            let with_reasoning = find_affected_packages_with_reasoning(
+            let with_reasoning = find_affected_packages_with_reasoning_for_target(
                 &path,
                 &all_changed_files,
                 &ignore_patterns_vec,
+                target.as_ref(),
+                members_file.as_deref(),
             )?;
             let packages: Vec<String> = with_reasoning.iter().map(|pkg| pkg.name.clone()).collect();
             (packages, Some(with_reasoning))
         } else {
             (
-                find_affected_packages(&path, &all_changed_files, &ignore_patterns_vec)?,
+                find_affected_packages_for_target(
+                    &path,
+                    &all_changed_files,
+                    &ignore_patterns_vec,
+                    target.as_ref(),
+                    members_file.as_deref(),
+                )?,
                 None,
             )
         };
@@ -4208,12 +6517,9 @@ pub async fn handle_features_command(
         let workspace_source = switchy_fs::unsync::read_to_string(&workspace_cargo_path).await?;
         let workspace_value: Value = toml::from_str(&workspace_source)?;
 
-        let workspace_members_raw = workspace_value
-            .get("workspace")
-            .and_then(|x| x.get("members"))
-            .and_then(|x| x.as_array())
-            .and_then(|x| x.iter().map(|x| x.as_str()).collect::<Option<Vec<_>>>())
-            .unwrap_or_default();
+        let workspace_members_raw =
+            workspace_member_patterns(&path, &workspace_value, members_file.as_deref())?
+                .unwrap_or_default();
 
         // Expand glob patterns in workspace members
         let workspace_members = expand_workspace_member_globs(&path, &workspace_members_raw);
@@ -4243,19 +6549,8 @@ pub async fn handle_features_command(
         for affected_package in affected_packages {
             if let Some(package_path) = package_name_to_path.get(&affected_package) {
                 let package_dir = path.join(package_path);
-                let mut packages = process_configs(
-                    &package_dir,
-                    offset,
-                    max,
-                    chunked,   // Respect chunking when filtering by changed files
-                    spread,    // Respect spreading when filtering by changed files
-                    randomize, // Respect randomization when filtering by changed files
-                    seed,      // Respect seed when filtering by changed files
-                    specific_features.as_deref(),
-                    skip_features_list.as_deref(),
-                    required_features_list.as_deref(),
-                )
-                .await?;
+                // Respect chunking, spreading and randomization when filtering by changed files
+                let mut packages = process_configs(&package_dir, options).await?;
 
                 // Add reasoning to packages if include_reasoning is true
                 if let Some(ref reasoning_data) = affected_with_reasoning
@@ -4292,35 +6587,22 @@ pub async fn handle_features_command(
             )?;
         }
 
-        let result = match output {
-            OutputType::Json => serde_json::to_string(&all_filtered_packages)?,
-            OutputType::Raw => {
-                let mut results = Vec::new();
-                for package in all_filtered_packages {
-                    if let Some(features) = package.get("features") {
-                        results.push(features.to_string());
-                    }
-                }
-                results.join("\n")
-            }
-        };
-        return Ok(result);
+        return finish_features_matrix(
+            all_filtered_packages,
+            needs.then_some(path.as_path()),
+            github_names,
+            empty_matrix_filters,
+            max_total_jobs,
+        );
     }
 
     // Use max_parallel as chunked if chunked is not provided
-    let effective_chunked = chunked.or(max_parallel);
-
     let mut packages = process_workspace_configs(
         &path,
-        offset,
-        max,
-        effective_chunked,
-        spread,
-        randomize,
-        seed,
-        specific_features.as_deref(),
-        skip_features_list.as_deref(),
-        required_features_list.as_deref(),
+        &FeaturesOptions {
+            chunked: chunked.or(max_parallel),
+            ..options.clone()
+        },
     )
     .await?;
 
@@ -4362,20 +6644,13 @@ pub async fn handle_features_command(
         );
     }
 
-    let result = match output {
-        OutputType::Json => serde_json::to_string(&packages)?,
-        OutputType::Raw => {
-            let mut results = Vec::new();
-            for package in packages {
-                if let Some(features) = package.get("features") {
-                    results.push(features.to_string());
-                }
-            }
-            results.join("\n")
-        }
-    };
-
-    Ok(result)
+    finish_features_matrix(
+        packages,
+        needs.then_some(path.as_path()),
+        github_names,
+        empty_matrix_filters,
+        max_total_jobs,
+    )
 }
 
 /// Handles the workspace deps command
@@ -4413,6 +6688,12 @@ pub fn handle_workspace_deps_command(
 
 /// Handles the generate dockerfile command
 ///
+/// `target_os` selects the `clippier.toml` configurations used for local workspaces; git
+/// builds don't read package configurations.
+///
+/// With `dry_run`, nothing is written and the Dockerfile and `.dockerignore` contents that
+/// would be written are returned instead (see [`GeneratedDockerfile::to_dry_run_output`]).
+///
 /// # Errors
 ///
 /// * If fails to generate dockerfile
@@ -4432,18 +6713,19 @@ pub async fn handle_generate_dockerfile_command(
     env: &[String],
     build_env: &[String],
     bin: Option<&str>,
+    target_os: &str,
+    dry_run: bool,
 ) -> Result<String, BoxError> {
     let workspace_root_str = workspace_root.to_string_lossy();
 
-    if is_git_url(&workspace_root_str) {
+    let generated = if is_git_url(&workspace_root_str) {
         // Git mode - generate dockerfile that clones from git
-        generate_dockerfile_from_git(
+        render_dockerfile_from_git(
             &workspace_root_str,
             git_ref,
             package,
             features,
             no_default_features,
-            output,
             base_image,
             final_image,
             args,
@@ -4452,15 +6734,14 @@ pub async fn handle_generate_dockerfile_command(
             env,
             build_env,
             bin,
-        )?;
+        )?
     } else {
         // Local mode - existing logic unchanged
-        generate_dockerfile(
+        render_dockerfile(
             workspace_root,
             package,
             features,
             no_default_features,
-            output,
             base_image,
             final_image,
             args,
@@ -4469,18 +6750,60 @@ pub async fn handle_generate_dockerfile_command(
             env,
             build_env,
             bin,
+            target_os,
         )
-        .await?;
+        .await?
+    };
+
+    if dry_run {
+        return Ok(generated.to_dry_run_output(output));
     }
 
+    generated.write(output)?;
+
     Ok(format!("Generated Dockerfile at: {}", output.display()))
 }
 
+/// Parses a newline-separated list of changed files, e.g. piped to stdin by a prior CI step.
+///
+/// Handles CRLF line endings and surrounding whitespace, and skips blank lines.
+#[must_use]
+pub fn parse_changed_files_list(input: &str) -> Vec<String> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Merges the changed files passed as arguments with `additional` ones, keeping the first
+/// occurrence of each file.
+#[must_use]
+pub fn merge_changed_files(
+    changed_files: &[String],
+    additional: impl IntoIterator<Item = String>,
+) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+
+    changed_files
+        .iter()
+        .cloned()
+        .chain(additional)
+        .filter(|x| seen.insert(x.clone()))
+        .collect()
+}
+
 /// Handles the affected packages command
 ///
+/// When `target` is set, only dependencies active for that target triple are followed.
+/// Additional workspace members are read from `members_file` if it is set (see
+/// [`resolve_members_file`]).
+///
 /// # Errors
 ///
 /// * If fails to find affected packages
+/// * If `target` is not a valid target triple
 #[allow(
     clippy::too_many_arguments,
     clippy::too_many_lines,
@@ -4495,6 +6818,8 @@ pub async fn handle_affected_packages_command(
     include_reasoning: bool,
     ignore_patterns: Option<&[String]>,
     #[cfg(feature = "_workspace")] workspace_type: Option<&[workspace::WorkspaceType]>,
+    target: Option<&str>,
+    members_file: Option<&Path>,
     output: OutputType,
 ) -> Result<String, BoxError> {
     #[cfg(feature = "git-diff")]
@@ -4548,14 +6873,16 @@ pub async fn handle_affected_packages_command(
                             toml::from_str::<Value>(&workspace_source).ok()
                         })
                         .and_then(|workspace_value| {
-                            workspace_value
-                                .get("workspace")
-                                .and_then(|x| x.get("members"))
-                                .and_then(|x| x.as_array())
-                                .and_then(|x| {
-                                    x.iter().map(|x| x.as_str()).collect::<Option<Vec<_>>>()
-                                })
-                                .map(|raw| expand_workspace_member_globs(workspace_root, &raw))
+                            let members_file =
+                                resolve_members_file(workspace_root, members_file).ok()?;
+                            workspace_member_patterns(
+                                workspace_root,
+                                &workspace_value,
+                                members_file.as_deref(),
+                            )
+                            .ok()
+                            .flatten()
+                            .map(|raw| expand_workspace_member_globs(workspace_root, &raw))
                         })
                         .unwrap_or_default()
                 };
@@ -4583,20 +6910,29 @@ pub async fn handle_affected_packages_command(
 
     // Find affected packages from file changes
     let ignore_patterns_vec = ignore_patterns.unwrap_or(&[]).to_vec();
+    let target = target.map(str::parse::<TargetTriple>).transpose()?;
     let mut affected = if include_reasoning {
-        find_affected_packages_with_reasoning(
+        find_affected_packages_with_reasoning_for_target(
             workspace_root,
             &all_changed_files,
             &ignore_patterns_vec,
+            target.as_ref(),
+            members_file,
         )?
     } else {
-        find_affected_packages(workspace_root, &all_changed_files, &ignore_patterns_vec)?
-            .into_iter()
-            .map(|name| AffectedPackageInfo {
-                name,
-                reasoning: None,
-            })
-            .collect()
+        find_affected_packages_for_target(
+            workspace_root,
+            &all_changed_files,
+            &ignore_patterns_vec,
+            target.as_ref(),
+            members_file,
+        )?
+        .into_iter()
+        .map(|name| AffectedPackageInfo {
+            name,
+            reasoning: None,
+        })
+        .collect()
     };
 
     // Add packages affected by external dependency changes
@@ -4669,18 +7005,9 @@ pub async fn handle_affected_packages_command(
 /// * If the workspace root directory is not found or cannot be read
 /// * If workspace member directories cannot be processed
 /// * If any workspace member has invalid configuration
-#[allow(clippy::too_many_arguments)]
 pub async fn process_workspace_configs(
     workspace_path: &Path,
-    offset: Option<u16>,
-    max: Option<u16>,
-    chunked: Option<u16>,
-    spread: bool,
-    randomize: bool,
-    seed: Option<u64>,
-    specific_features: Option<&[String]>,
-    skip_features_override: Option<&[String]>,
-    required_features_override: Option<&[String]>,
+    options: &FeaturesOptions,
 ) -> Result<Vec<serde_json::Map<String, serde_json::Value>>, BoxError> {
     log::debug!(
         "Processing workspace configs from '{}'",
@@ -4691,29 +7018,13 @@ pub async fn process_workspace_configs(
     let workspace_cargo_path = workspace_path.join("Cargo.toml");
     let workspace_source = switchy_fs::unsync::read_to_string(&workspace_cargo_path).await?;
     let workspace_value: Value = toml::from_str(&workspace_source)?;
+    let members_file = resolve_members_file(workspace_path, None)?;
 
-    let workspace_members_raw = workspace_value
-        .get("workspace")
-        .and_then(|x| x.get("members"))
-        .and_then(|x| x.as_array())
-        .and_then(|x| x.iter().map(|x| x.as_str()).collect::<Option<Vec<_>>>());
+    let workspace_members_raw =
+        workspace_member_patterns(workspace_path, &workspace_value, members_file.as_deref())?;
 
     match workspace_members_raw {
-        None => {
-            process_configs(
-                workspace_path,
-                offset,
-                max,
-                chunked,
-                spread,
-                randomize,
-                seed,
-                specific_features,
-                skip_features_override,
-                required_features_override,
-            )
-            .await
-        }
+        None => process_configs(workspace_path, options).await,
         Some(members_raw) => {
             // Expand glob patterns in workspace members (e.g., "packages/*" -> ["packages/foo", "packages/bar"])
             let members = expand_workspace_member_globs(workspace_path, &members_raw);
@@ -4734,20 +7045,7 @@ pub async fn process_workspace_configs(
                 log::debug!("Processing workspace member: {member_path}");
 
                 // Process this member's configs (with default config if no clippier.toml)
-                match process_configs(
-                    &full_path,
-                    offset,
-                    max,
-                    chunked,
-                    spread,
-                    randomize,
-                    seed,
-                    specific_features,
-                    skip_features_override,
-                    required_features_override,
-                )
-                .await
-                {
+                match process_configs(&full_path, options).await {
                     Ok(mut packages) => {
                         all_packages.append(&mut packages);
                     }
@@ -4887,6 +7185,142 @@ pub fn handle_validate_feature_propagation_command(
     Ok(validator.validate()?)
 }
 
+/// Handles the workspace hack command
+///
+/// Compares the union of external dependency features enabled across the workspace
+/// against the features enabled by the `hack_package` workspace-hack package.
+///
+/// # Errors
+///
+/// * If the workspace Cargo.toml file cannot be read or parsed
+/// * If any package Cargo.toml file cannot be read or parsed
+/// * If `hack_package` is not a workspace member
+pub fn handle_workspace_hack_command(
+    workspace_root: &Path,
+    hack_package: &str,
+) -> Result<WorkspaceHackReport, BoxError> {
+    workspace_hack::check_workspace_hack(workspace_root, hack_package)
+}
+
+/// Handles the unused features command
+///
+/// Reports features defined by workspace members that are never referenced. `default`
+/// and any feature in `ignore_features` are never reported.
+///
+/// # Errors
+///
+/// * If the workspace Cargo.toml file cannot be read or parsed
+/// * If any package Cargo.toml file cannot be read or parsed
+/// * If any package clippier.toml file cannot be loaded
+pub fn handle_unused_features_command(
+    workspace_root: &Path,
+    ignore_features: &[String],
+) -> Result<UnusedFeaturesReport, BoxError> {
+    unused_features::find_unreferenced_features(workspace_root, ignore_features)
+}
+
+/// Handles the heavy defaults command
+///
+/// Reports optional dependencies enabled by a package's `default` feature that
+/// transitively depend on at least `min_transitive_dependencies` packages. Dependencies
+/// in `ignore_dependencies` are never reported.
+///
+/// # Errors
+///
+/// * If the workspace Cargo.toml file cannot be read or parsed
+/// * If any package Cargo.toml file cannot be read or parsed
+/// * If the workspace Cargo.lock file cannot be read or parsed
+pub fn handle_heavy_defaults_command(
+    workspace_root: &Path,
+    min_transitive_dependencies: usize,
+    ignore_dependencies: &[String],
+) -> Result<HeavyDefaultsReport, BoxError> {
+    heavy_defaults::find_heavy_default_dependencies(
+        workspace_root,
+        min_transitive_dependencies,
+        ignore_dependencies,
+    )
+}
+
+/// Handles the package graph command
+///
+/// Exports the dependency graph between workspace members for external tooling.
+///
+/// # Errors
+///
+/// * If the workspace Cargo.toml file cannot be read or parsed
+/// * If any package Cargo.toml file cannot be read or parsed
+pub fn handle_package_graph_command(workspace_root: &Path) -> Result<PackageGraph, BoxError> {
+    package_graph::build_package_graph(workspace_root)
+}
+
+/// Handles the validate command
+///
+/// Checks the root `clippier.toml`, every workspace member's `clippier.toml` and the
+/// base configs they extend against the config schema.
+///
+/// # Errors
+///
+/// * If the workspace Cargo.toml file cannot be read or parsed
+/// * If any clippier.toml file cannot be read
+pub fn handle_validate_command(workspace_root: &Path) -> Result<ConfigValidationReport, BoxError> {
+    config_validation::validate_workspace_configs(workspace_root)
+}
+
+/// Handles the matrix diff command
+///
+/// Reads two features matrices previously emitted by the features command (flat or
+/// grouped by OS) and computes the jobs added, removed and changed between them.
+///
+/// # Errors
+///
+/// * If either file cannot be read
+/// * If either file is not valid JSON
+/// * If either file is not a features matrix
+pub fn handle_matrix_diff_command(before: &Path, after: &Path) -> Result<MatrixDiff, BoxError> {
+    let load = |path: &Path| -> Result<Vec<MatrixJob>, BoxError> {
+        let source = switchy_fs::sync::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let matrix: serde_json::Value = serde_json::from_str(&source)
+            .map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+        parse_matrix_jobs(&matrix)
+    };
+
+    Ok(diff_matrices(&load(before)?, &load(after)?))
+}
+
+/// Handles the list features command
+///
+/// Lists each feature of `package` with the features it transitively enables, the
+/// dependency features it sets and the optional dependencies it activates.
+///
+/// # Errors
+///
+/// * If the workspace Cargo.toml file cannot be read or parsed
+/// * If `package` is not a workspace member
+/// * If the package's Cargo.toml file cannot be read or parsed
+/// * If JSON serialization fails
+pub fn handle_list_features_command(
+    workspace_root: &Path,
+    package: &str,
+    output: OutputType,
+) -> Result<String, BoxError> {
+    let context = WorkspaceContext::new(workspace_root)?;
+    let package_path = context
+        .find_member(package)
+        .ok_or_else(|| format!("Package {package} not found in workspace"))?;
+
+    let source = switchy_fs::sync::read_to_string(package_path.join("Cargo.toml"))?;
+    let cargo_toml: Value = toml::from_str(&source)?;
+
+    let features = list_package_features(&cargo_toml);
+
+    Ok(match output {
+        OutputType::Json => serde_json::to_string(&features)?,
+        OutputType::Raw => format_feature_enablements(package, &features),
+    })
+}
+
 /// # Errors
 ///
 /// * If the workspace path is invalid or cannot be read
@@ -4910,6 +7344,7 @@ pub async fn handle_packages_command(
     skip_if: &[String],
     include_if: &[String],
     #[cfg(feature = "_workspace")] workspace_type: Option<&[workspace::WorkspaceType]>,
+    fail_on_empty_matrix: bool,
     output: OutputType,
 ) -> Result<String, BoxError> {
     use std::str::FromStr;
@@ -4944,13 +7379,11 @@ pub async fn handle_packages_command(
         let workspace_cargo_path = path.join("Cargo.toml");
         let workspace_source = switchy_fs::unsync::read_to_string(&workspace_cargo_path).await?;
         let workspace_value: Value = toml::from_str(&workspace_source)?;
+        let members_file = resolve_members_file(&path, None)?;
 
-        let workspace_members_raw = workspace_value
-            .get("workspace")
-            .and_then(|x| x.get("members"))
-            .and_then(|x| x.as_array())
-            .and_then(|x| x.iter().map(|x| x.as_str()).collect::<Option<Vec<_>>>())
-            .unwrap_or_default();
+        let workspace_members_raw =
+            workspace_member_patterns(&path, &workspace_value, members_file.as_deref())?
+                .unwrap_or_default();
 
         let workspace_members = expand_workspace_member_globs(&path, &workspace_members_raw);
 
@@ -5146,6 +7579,32 @@ pub async fn handle_packages_command(
         package_list.truncate(limit as usize);
     }
 
+    if fail_on_empty_matrix {
+        #[cfg(feature = "git-diff")]
+        let ignore = ignore_patterns
+            .filter(|x| !x.is_empty())
+            .map(|x| x.join(","));
+        #[cfg(not(feature = "git-diff"))]
+        let ignore = None;
+
+        let filters = describe_matrix_filters(&[
+            ("os", os.map(str::to_string)),
+            ("packages", packages.map(|x| x.join(","))),
+            ("changed-files", changed_files.map(|x| x.join(","))),
+            #[cfg(feature = "git-diff")]
+            ("git-base", git_base.map(str::to_string)),
+            #[cfg(feature = "git-diff")]
+            ("git-head", git_head.map(str::to_string)),
+            ("ignore", ignore),
+            ("skip-if", (!skip_if.is_empty()).then(|| skip_if.join(","))),
+            (
+                "include-if",
+                (!include_if.is_empty()).then(|| include_if.join(",")),
+            ),
+        ]);
+        check_empty_matrix(&package_list, Some(&filters))?;
+    }
+
     let result = match output {
         OutputType::Json => serde_json::to_string(&package_list)?,
         OutputType::Raw => package_list
@@ -5158,6 +7617,57 @@ pub async fn handle_packages_command(
     Ok(result)
 }
 
+/// Emits the deduplicated union of the system dependencies of every package for `os`.
+///
+/// Unlike [`handle_workspace_toolchains_command`], which lists every dependency command
+/// as written, this extracts the packages installed by `apt-get`, `brew` and `vcpkg`
+/// commands and merges them into one sorted install command per package manager.
+/// Dependency commands that do more than install packages are passed through verbatim.
+///
+/// # Errors
+///
+/// * If the workspace Cargo.toml or root clippier.toml cannot be read or parsed
+/// * If JSON serialization fails
+pub fn handle_workspace_system_deps_command(
+    workspace_root: &Path,
+    os: &str,
+    output: OutputType,
+) -> Result<String, BoxError> {
+    let deps = system_deps::collect_workspace_system_deps(workspace_root, os)?;
+
+    Ok(match output {
+        OutputType::Json => serde_json::to_string(&deps)?,
+        OutputType::Raw => format_workspace_system_deps(&deps),
+    })
+}
+
+/// Generates a Nix development shell equivalent to the workspace's CI setup for `os`.
+///
+/// Maps the system packages installed by the workspace's dependency commands and the
+/// toolchains of every package to `nixpkgs` attributes. `overrides` are `name=attr1,attr2`
+/// entries that extend or replace the built-in mapping. Unmapped dependencies are listed
+/// as `TODO` comments.
+///
+/// # Errors
+///
+/// * If the workspace Cargo.toml or any clippier.toml cannot be read or parsed
+/// * If an override entry is malformed
+pub fn handle_nix_shell_command(
+    workspace_root: &Path,
+    os: &str,
+    format: NixShellFormat,
+    overrides: &[String],
+) -> Result<String, BoxError> {
+    let overrides = parse_nix_overrides(overrides)?;
+    let deps = system_deps::collect_workspace_system_deps(workspace_root, os)?;
+    let toolchains = collect_workspace_toolchains(workspace_root, os)?;
+
+    Ok(format_nix_shell(
+        &NixShell::new(&deps, &toolchains, &overrides),
+        format,
+    ))
+}
+
 /// Aggregated toolchain information for workspace-level CI setup
 #[derive(Debug, Clone, Serialize)]
 pub struct WorkspaceToolchains {
@@ -5175,33 +7685,82 @@ pub struct WorkspaceToolchains {
     pub git_submodules: bool,
 }
 
-/// Aggregates all toolchains and dependencies from workspace packages for CI setup.
-///
-/// Scans all packages in the workspace and collects their toolchains, dependencies,
-/// CI steps, and environment variables for the specified OS. This enables workspace-level
-/// CI setup without needing to specify a specific package.
-///
-/// # Arguments
-///
-/// * `workspace_root` - Path to the workspace root
-/// * `os` - Target operating system (e.g., "ubuntu", "windows", "macos")
-/// * `output` - Output format (JSON or Raw)
-///
-/// # Returns
-///
-/// JSON or raw text containing aggregated toolchain information
+/// Aggregates all toolchains and dependencies from workspace packages for CI setup.
+///
+/// Scans all packages in the workspace and collects their toolchains, dependencies,
+/// CI steps, and environment variables for the specified OS. This enables workspace-level
+/// CI setup without needing to specify a specific package.
+///
+/// # Arguments
+///
+/// * `workspace_root` - Path to the workspace root
+/// * `os` - Target operating system (e.g., "ubuntu", "windows", "macos")
+/// * `output` - Output format (JSON or Raw)
+///
+/// # Returns
+///
+/// JSON or raw text containing aggregated toolchain information
+///
+/// # Errors
+///
+/// * If workspace cannot be read
+/// * If any clippier.toml has invalid format
+/// * If JSON serialization fails
+pub fn handle_workspace_toolchains_command(
+    workspace_root: &Path,
+    os: &str,
+    output: OutputType,
+) -> Result<String, BoxError> {
+    let result = collect_workspace_toolchains(workspace_root, os)?;
+
+    match output {
+        OutputType::Json => Ok(serde_json::to_string(&result)?),
+        OutputType::Raw => {
+            use std::fmt::Write as _;
+
+            let mut output = String::new();
+            output.push_str("Dependencies:\n");
+            for dep in &result.dependencies {
+                writeln!(output, "  {dep}")?;
+            }
+            output.push_str("\nToolchains:\n");
+            for toolchain in &result.toolchains {
+                writeln!(output, "  {toolchain}")?;
+            }
+            output.push_str("\nCI Steps:\n");
+            for step in &result.ci_steps {
+                writeln!(output, "  {step}")?;
+            }
+            if !result.env.is_empty() {
+                output.push_str("\nEnvironment:\n");
+                for (key, value) in &result.env {
+                    writeln!(output, "  {key}={value}")?;
+                }
+            }
+            if !result.nightly_packages.is_empty() {
+                output.push_str("\nNightly Packages:\n");
+                for pkg in &result.nightly_packages {
+                    writeln!(output, "  {pkg}")?;
+                }
+            }
+            writeln!(output, "\nGit Submodules: {}", result.git_submodules)?;
+            Ok(output)
+        }
+    }
+}
+
+/// Collects the toolchains, dependencies, CI steps and environment variables of every
+/// package in the workspace for `os`.
 ///
 /// # Errors
 ///
 /// * If workspace cannot be read
 /// * If any clippier.toml has invalid format
-/// * If JSON serialization fails
 #[allow(clippy::too_many_lines)]
-pub fn handle_workspace_toolchains_command(
+pub fn collect_workspace_toolchains(
     workspace_root: &Path,
     os: &str,
-    output: OutputType,
-) -> Result<String, BoxError> {
+) -> Result<WorkspaceToolchains, BoxError> {
     let mut all_dependencies: BTreeSet<String> = BTreeSet::new();
     let mut all_toolchains: BTreeSet<String> = BTreeSet::new();
     let mut all_ci_steps: BTreeSet<String> = BTreeSet::new();
@@ -5344,11 +7903,10 @@ pub fn handle_workspace_toolchains_command(
                     .to_string()
             });
 
-            let content = switchy_fs::sync::read_to_string(&clippier_path)?;
-            let conf: ClippierConf = match toml::from_str(&content) {
+            let conf = match load_clippier_conf(&clippier_path) {
                 Ok(c) => c,
                 Err(e) => {
-                    log::warn!("Failed to parse {}: {}", clippier_path.display(), e);
+                    log::warn!("Failed to load {}: {}", clippier_path.display(), e);
                     continue;
                 }
             };
@@ -5442,63 +8000,131 @@ pub fn handle_workspace_toolchains_command(
         }
     }
 
-    let result = WorkspaceToolchains {
+    Ok(WorkspaceToolchains {
         dependencies: all_dependencies.into_iter().collect(),
         toolchains: all_toolchains.into_iter().collect(),
         ci_steps: all_ci_steps.into_iter().collect(),
         env: all_env,
         nightly_packages: nightly_packages.into_iter().collect(),
         git_submodules: needs_git_submodules,
-    };
+    })
+}
 
-    match output {
-        OutputType::Json => Ok(serde_json::to_string(&result)?),
-        OutputType::Raw => {
-            use std::fmt::Write as _;
+/// Finds the directories of packages affected by uncommitted changes in the git working
+/// tree.
+///
+/// Staged, unstaged and untracked files are mapped to the packages containing them, along
+/// with the packages that depend on those packages.
+///
+/// # Errors
+///
+/// * If the git repository status cannot be read
+/// * If the workspace Cargo.toml file cannot be read or parsed
+/// * If any package Cargo.toml file cannot be read or parsed
+#[cfg(feature = "git-diff")]
+pub fn find_changed_package_dirs(
+    workspace_root: &Path,
+) -> Result<Vec<std::path::PathBuf>, BoxError> {
+    let changed_files = git_diff::get_uncommitted_changed_files(workspace_root)?;
+    if changed_files.is_empty() {
+        return Ok(vec![]);
+    }
 
-            let mut output = String::new();
-            output.push_str("Dependencies:\n");
-            for dep in &result.dependencies {
-                writeln!(output, "  {dep}")?;
-            }
-            output.push_str("\nToolchains:\n");
-            for toolchain in &result.toolchains {
-                writeln!(output, "  {toolchain}")?;
-            }
-            output.push_str("\nCI Steps:\n");
-            for step in &result.ci_steps {
-                writeln!(output, "  {step}")?;
-            }
-            if !result.env.is_empty() {
-                output.push_str("\nEnvironment:\n");
-                for (key, value) in &result.env {
-                    writeln!(output, "  {key}={value}")?;
-                }
-            }
-            if !result.nightly_packages.is_empty() {
-                output.push_str("\nNightly Packages:\n");
-                for pkg in &result.nightly_packages {
-                    writeln!(output, "  {pkg}")?;
-                }
-            }
-            writeln!(output, "\nGit Submodules: {}", result.git_submodules)?;
-            Ok(output)
+    let affected = find_affected_packages(workspace_root, &changed_files, &[])?;
+    let context = WorkspaceContext::new(workspace_root)?;
+
+    let mut dirs: Vec<std::path::PathBuf> = affected
+        .iter()
+        .filter_map(|name| context.find_member(name))
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+
+    log::debug!("Packages affected by uncommitted changes: {dirs:?}");
+
+    Ok(dirs)
+}
+
+/// Runs `names` with a runner for each of `package_dirs`, or a single runner over the
+/// working directory when `package_dirs` is `None`, and combines the results.
+#[cfg(feature = "_tools")]
+fn run_tools_in_scope(
+    registry: &tools::ToolRegistry,
+    working_dir: Option<&Path>,
+    package_dirs: Option<&[std::path::PathBuf]>,
+    names: &[&str],
+    check_mode: bool,
+    color: ColorMode,
+    use_tui: bool,
+) -> Result<tools::AggregatedResults, BoxError> {
+    use tools::{AggregatedResults, ToolRunner};
+
+    let run = |dir: Option<&Path>| {
+        let runner = dir.map_or_else(
+            || ToolRunner::new(registry),
+            |dir| ToolRunner::new(registry).with_working_dir(dir),
+        );
+        let runner = runner.with_color_mode(color);
+
+        if use_tui {
+            runner.run_specific_with_tui(names, &[], check_mode)
+        } else {
+            runner.run_specific(names, &[], check_mode)
         }
+    };
+
+    let Some(package_dirs) = package_dirs else {
+        return Ok(run(working_dir)?);
+    };
+
+    let mut combined = AggregatedResults {
+        results: vec![],
+        total_duration: std::time::Duration::ZERO,
+        success_count: 0,
+        failure_count: 0,
+    };
+
+    let root = switchy_fs::sync::canonicalize(working_dir.unwrap_or_else(|| Path::new("."))).ok();
+
+    for dir in package_dirs {
+        log::debug!("Running {names:?} in {}", dir.display());
+        let results = run(Some(dir))?;
+        let relative = root
+            .as_deref()
+            .and_then(|root| dir.strip_prefix(root).ok())
+            .unwrap_or(dir);
+
+        combined.total_duration += results.total_duration;
+        combined.success_count += results.success_count;
+        combined.failure_count += results.failure_count;
+        combined
+            .results
+            .extend(results.results.into_iter().map(|mut result| {
+                result.display_name = format!("{} ({})", result.display_name, relative.display());
+                result
+            }));
     }
+
+    Ok(combined)
 }
 
 /// Handles the check command (run linters and format checkers)
 ///
 /// Requires the `check` feature.
 ///
+/// When `package_dirs` is set (see [`find_changed_package_dirs`]), the tools run in each
+/// of those package directories instead of over the whole working directory.
+///
 /// # Errors
 ///
 /// * If tool detection fails
 /// * If a required tool is not found
 /// * If tool execution fails
 #[cfg(feature = "check")]
+#[allow(clippy::too_many_arguments)]
 pub fn handle_check_command(
     working_dir: Option<&Path>,
+    package_dirs: Option<&[std::path::PathBuf]>,
     tool_names: Option<&[String]>,
     list_tools: bool,
     config: tools::ToolsConfig,
@@ -5506,7 +8132,7 @@ pub fn handle_check_command(
     color: ColorMode,
     enable_tui: bool,
 ) -> Result<String, BoxError> {
-    use tools::{ToolRegistry, ToolRunner};
+    use tools::ToolRegistry;
 
     let required_tools = config.required.clone();
     let overlap_warning_suppress = config.overlap_warning_suppress.clone();
@@ -5557,14 +8183,10 @@ pub fn handle_check_command(
         };
     }
 
-    let runner = working_dir.map_or_else(
-        || ToolRunner::new(&registry),
-        |dir| ToolRunner::new(&registry).with_working_dir(dir),
-    );
-    let runner = runner.with_color_mode(match (output, color) {
+    let color = match (output, color) {
         (OutputType::Json, ColorMode::Auto) => ColorMode::Never,
         (_, value) => value,
-    });
+    };
 
     let names = if let Some(names) = tool_names {
         names.to_vec()
@@ -5587,11 +8209,15 @@ pub fn handle_check_command(
     let name_refs: Vec<&str> = names.iter().map(String::as_str).collect();
     let should_use_tui =
         enable_tui && !list_tools && output == OutputType::Raw && can_use_interactive_tui();
-    let results = if should_use_tui {
-        runner.run_specific_with_tui(&name_refs, &[], true)?
-    } else {
-        runner.run_specific(&name_refs, &[], true)?
-    };
+    let results = run_tools_in_scope(
+        &registry,
+        working_dir,
+        package_dirs,
+        &name_refs,
+        true,
+        color,
+        should_use_tui,
+    )?;
 
     match output {
         OutputType::Json => Ok(tools::results_to_json(&results)?),
@@ -5606,6 +8232,9 @@ pub fn handle_check_command(
 ///
 /// Requires the `format` feature.
 ///
+/// When `package_dirs` is set (see [`find_changed_package_dirs`]), the formatters run in
+/// each of those package directories instead of over the whole working directory.
+///
 /// # Errors
 ///
 /// * If tool detection fails
@@ -5615,6 +8244,7 @@ pub fn handle_check_command(
 #[allow(clippy::too_many_arguments)]
 pub fn handle_fmt_command(
     working_dir: Option<&Path>,
+    package_dirs: Option<&[std::path::PathBuf]>,
     tool_names: Option<&[String]>,
     check_only: bool,
     list_tools: bool,
@@ -5623,7 +8253,7 @@ pub fn handle_fmt_command(
     color: ColorMode,
     enable_tui: bool,
 ) -> Result<String, BoxError> {
-    use tools::{ToolRegistry, ToolRunner};
+    use tools::ToolRegistry;
 
     let required_tools = config.required.clone();
     let overlap_warning_suppress = config.overlap_warning_suppress.clone();
@@ -5679,14 +8309,10 @@ pub fn handle_fmt_command(
         };
     }
 
-    let runner = working_dir.map_or_else(
-        || ToolRunner::new(&registry),
-        |dir| ToolRunner::new(&registry).with_working_dir(dir),
-    );
-    let runner = runner.with_color_mode(match (output, color) {
+    let color = match (output, color) {
         (OutputType::Json, ColorMode::Auto) => ColorMode::Never,
         (_, value) => value,
-    });
+    };
 
     let names = if let Some(names) = tool_names {
         names.to_vec()
@@ -5709,11 +8335,15 @@ pub fn handle_fmt_command(
     let name_refs: Vec<&str> = names.iter().map(String::as_str).collect();
     let should_use_tui =
         enable_tui && !list_tools && output == OutputType::Raw && can_use_interactive_tui();
-    let results = if should_use_tui {
-        runner.run_specific_with_tui(&name_refs, &[], check_only)?
-    } else {
-        runner.run_specific(&name_refs, &[], check_only)?
-    };
+    let results = run_tools_in_scope(
+        &registry,
+        working_dir,
+        package_dirs,
+        &name_refs,
+        check_only,
+        color,
+        should_use_tui,
+    )?;
 
     match output {
         OutputType::Json => Ok(tools::results_to_json(&results)?),
@@ -5823,11 +8453,9 @@ feature-a = []
         )
         .unwrap();
 
-        let result = process_workspace_configs(
-            temp_path, None, None, None, false, false, None, None, None, None,
-        )
-        .await
-        .unwrap();
+        let result = process_workspace_configs(temp_path, &FeaturesOptions::default())
+            .await
+            .unwrap();
         let oses = result
             .iter()
             .map(|package| package.get("os").unwrap().as_str().unwrap())
@@ -5887,11 +8515,9 @@ os = "ubuntu"
         )
         .unwrap();
 
-        let result = process_workspace_configs(
-            temp_path, None, None, None, false, false, None, None, None, None,
-        )
-        .await
-        .unwrap();
+        let result = process_workspace_configs(temp_path, &FeaturesOptions::default())
+            .await
+            .unwrap();
         let oses = result
             .iter()
             .map(|package| package.get("os").unwrap().as_str().unwrap())
@@ -6003,15 +8629,10 @@ skip-features = ["simd"]
         // Test the combination: command line skip_features + config skip_features
         let result = process_configs(
             temp_path,
-            None,
-            None,
-            None,
-            false,
-            false,
-            None,
-            None,
-            Some(&["fail-on-warnings".to_string()]), // Command line skip_features
-            None,
+            &FeaturesOptions {
+                skip_features: Some("fail-on-warnings".to_string()),
+                ..Default::default()
+            },
         )
         .await
         .unwrap();
@@ -6064,11 +8685,9 @@ skip-features = ["*-default"]
 "#;
         switchy_fs::sync::write(temp_path.join("clippier.toml"), clippier_toml).unwrap();
 
-        let result = process_configs(
-            temp_path, None, None, None, false, false, None, None, None, None,
-        )
-        .await
-        .unwrap();
+        let result = process_configs(temp_path, &FeaturesOptions::default())
+            .await
+            .unwrap();
 
         assert!(!result.is_empty());
         let features = result[0].get("features").unwrap().as_array().unwrap();
@@ -6119,11 +8738,9 @@ skip-features = ["test-*"]
 "#;
         switchy_fs::sync::write(temp_path.join("clippier.toml"), clippier_toml).unwrap();
 
-        let result = process_configs(
-            temp_path, None, None, None, false, false, None, None, None, None,
-        )
-        .await
-        .unwrap();
+        let result = process_configs(temp_path, &FeaturesOptions::default())
+            .await
+            .unwrap();
 
         assert!(!result.is_empty());
         let features = result[0].get("features").unwrap().as_array().unwrap();
@@ -6173,11 +8790,9 @@ skip-features = ["v?"]
 "#;
         switchy_fs::sync::write(temp_path.join("clippier.toml"), clippier_toml).unwrap();
 
-        let result = process_configs(
-            temp_path, None, None, None, false, false, None, None, None, None,
-        )
-        .await
-        .unwrap();
+        let result = process_configs(temp_path, &FeaturesOptions::default())
+            .await
+            .unwrap();
 
         assert!(!result.is_empty());
         let features = result[0].get("features").unwrap().as_array().unwrap();
@@ -6226,11 +8841,9 @@ skip-features = ["*", "!enable-bob"]
 "#;
         switchy_fs::sync::write(temp_path.join("clippier.toml"), clippier_toml).unwrap();
 
-        let result = process_configs(
-            temp_path, None, None, None, false, false, None, None, None, None,
-        )
-        .await
-        .unwrap();
+        let result = process_configs(temp_path, &FeaturesOptions::default())
+            .await
+            .unwrap();
 
         assert!(!result.is_empty());
         let features = result[0].get("features").unwrap().as_array().unwrap();
@@ -6279,11 +8892,9 @@ skip-features = ["*", "!enable-*"]
 "#;
         switchy_fs::sync::write(temp_path.join("clippier.toml"), clippier_toml).unwrap();
 
-        let result = process_configs(
-            temp_path, None, None, None, false, false, None, None, None, None,
-        )
-        .await
-        .unwrap();
+        let result = process_configs(temp_path, &FeaturesOptions::default())
+            .await
+            .unwrap();
 
         assert!(!result.is_empty());
         let features = result[0].get("features").unwrap().as_array().unwrap();
@@ -6335,11 +8946,9 @@ skip-features = ["*-default", "test-*", "!test-utils"]
 "#;
         switchy_fs::sync::write(temp_path.join("clippier.toml"), clippier_toml).unwrap();
 
-        let result = process_configs(
-            temp_path, None, None, None, false, false, None, None, None, None,
-        )
-        .await
-        .unwrap();
+        let result = process_configs(temp_path, &FeaturesOptions::default())
+            .await
+            .unwrap();
 
         assert!(!result.is_empty());
         let features = result[0].get("features").unwrap().as_array().unwrap();
@@ -6395,15 +9004,10 @@ skip-features = ["*-default"]
 
         let result = process_configs(
             temp_path,
-            None,
-            None,
-            None,
-            false,
-            false,
-            None,
-            None,
-            Some(&["test-*".to_string()]), // Command line wildcard
-            None,
+            &FeaturesOptions {
+                skip_features: Some("test-*".to_string()),
+                ..Default::default()
+            },
         )
         .await
         .unwrap();
@@ -6585,15 +9189,10 @@ os = "ubuntu"
         // Test with wildcard pattern in specific_features
         let result = process_configs(
             temp_path,
-            None,
-            None,
-            None,
-            false,
-            false,
-            None,
-            Some(&["enable-*".to_string()]), // specific_features with wildcard
-            None,
-            None,
+            &FeaturesOptions {
+                features: Some("enable-*".to_string()),
+                ..Default::default()
+            },
         )
         .await
         .unwrap();
@@ -6647,15 +9246,10 @@ os = "ubuntu"
         // Test with multiple wildcard patterns
         let result = process_configs(
             temp_path,
-            None,
-            None,
-            None,
-            false,
-            false,
-            None,
-            Some(&["enable-*".to_string(), "test-*".to_string()]),
-            None,
-            None,
+            &FeaturesOptions {
+                features: Some("enable-*,test-*".to_string()),
+                ..Default::default()
+            },
         )
         .await
         .unwrap();
@@ -6708,15 +9302,10 @@ os = "ubuntu"
         // Test with mix of exact and wildcard
         let result = process_configs(
             temp_path,
-            None,
-            None,
-            None,
-            false,
-            false,
-            None,
-            Some(&["enable-*".to_string(), "production".to_string()]),
-            None,
-            None,
+            &FeaturesOptions {
+                features: Some("enable-*,production".to_string()),
+                ..Default::default()
+            },
         )
         .await
         .unwrap();
@@ -6820,15 +9409,10 @@ os = "ubuntu"
         // Test with wildcard pattern in required_features
         let result = process_configs(
             temp_path,
-            None,
-            None,
-            None,
-            false,
-            false,
-            None,
-            None,                                                      // specific_features
-            None,                                                      // skip_features
-            Some(&["enable-*".to_string(), "production".to_string()]), // required_features
+            &FeaturesOptions {
+                required_features: Some("enable-*,production".to_string()),
+                ..Default::default()
+            },
         )
         .await
         .unwrap();
@@ -6887,12 +9471,9 @@ required-features = ["test-*"]
 "#;
         switchy_fs::sync::write(temp_path.join("clippier.toml"), clippier_toml).unwrap();
 
-        let result = process_configs(
-            temp_path, None, None, None, false, false, None, None, None,
-            None, // No command line override
-        )
-        .await
-        .unwrap();
+        let result = process_configs(temp_path, &FeaturesOptions::default())
+            .await
+            .unwrap();
 
         assert!(!result.is_empty());
         let required_features = result[0]
@@ -6918,6 +9499,181 @@ required-features = ["test-*"]
         assert!(!required_feature_names.contains(&"production".to_string()));
     }
 
+    fn string_array(entry: &serde_json::Map<String, serde_json::Value>, key: &str) -> Vec<String> {
+        entry
+            .get(key)
+            .and_then(|x| x.as_array())
+            .map(|x| x.iter().map(|f| f.as_str().unwrap().to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_required_features_for_os_splits_scoped_features() {
+        #[derive(serde::Deserialize)]
+        struct Rust {
+            features: Vec<RequiredFeature>,
+        }
+
+        let Rust {
+            features: required_features,
+        } = toml::from_str(
+            r#"
+features = [
+    "production",
+    { feature = "pulseaudio", os = "ubuntu" },
+    { feature = "coreaudio", os = ["macos", "ios"] },
+    { feature = "shared", os = "macos" },
+    { feature = "shared", os = "ubuntu" },
+]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            required_features_for_os(&required_features, "ubuntu"),
+            (
+                vec![
+                    "production".to_string(),
+                    "pulseaudio".to_string(),
+                    "shared".to_string()
+                ],
+                vec!["coreaudio".to_string()],
+            )
+        );
+        assert_eq!(
+            required_features_for_os(&required_features, "ios"),
+            (
+                vec!["production".to_string(), "coreaudio".to_string()],
+                vec!["pulseaudio".to_string(), "shared".to_string()],
+            )
+        );
+    }
+
+    #[switchy_async::test]
+    async fn test_os_scoped_required_features_per_os_matrix() {
+        let temp_dir = switchy_fs::tempdir().unwrap();
+        let temp_path = temp_dir.path();
+
+        let cargo_toml = r#"
+[package]
+name = "test-package"
+version = "0.1.0"
+
+[features]
+default = []
+production = []
+pulseaudio = []
+coreaudio = []
+opus = []
+"#;
+        switchy_fs::sync::write(temp_path.join("Cargo.toml"), cargo_toml).unwrap();
+
+        let clippier_toml = r#"
+[rust]
+required-features = [
+    "production",
+    { feature = "pulseaudio", os = "ubuntu" },
+    { feature = "coreaudio", os = "macos" },
+]
+
+[[config]]
+os = "ubuntu"
+
+[[config]]
+os = "macos"
+
+[[config]]
+os = "windows"
+"#;
+        switchy_fs::sync::write(temp_path.join("clippier.toml"), clippier_toml).unwrap();
+
+        let result = process_configs(temp_path, &FeaturesOptions::default())
+            .await
+            .unwrap();
+
+        for (os, required, excluded) in [
+            (
+                "ubuntu",
+                vec!["production", "pulseaudio"],
+                vec!["coreaudio"],
+            ),
+            ("macos", vec!["production", "coreaudio"], vec!["pulseaudio"]),
+            (
+                "windows",
+                vec!["production"],
+                vec!["pulseaudio", "coreaudio"],
+            ),
+        ] {
+            let entries = result
+                .iter()
+                .filter(|x| x.get("os").and_then(|x| x.as_str()) == Some(os))
+                .collect::<Vec<_>>();
+            assert!(!entries.is_empty(), "no {os} entries");
+
+            for entry in entries {
+                assert_eq!(string_array(entry, "requiredFeatures"), required, "{os}");
+
+                let features = string_array(entry, "features");
+                for feature in &excluded {
+                    assert!(
+                        !features.iter().any(|x| x == feature),
+                        "{os} entry has {feature}: {features:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[switchy_async::test]
+    async fn test_config_required_features_override_package_required_features() {
+        let temp_dir = switchy_fs::tempdir().unwrap();
+        let temp_path = temp_dir.path();
+
+        let cargo_toml = r#"
+[package]
+name = "test-package"
+version = "0.1.0"
+
+[features]
+default = []
+production = []
+pulseaudio = []
+"#;
+        switchy_fs::sync::write(temp_path.join("Cargo.toml"), cargo_toml).unwrap();
+
+        let clippier_toml = r#"
+[rust]
+required-features = ["production"]
+
+[[config]]
+os = "ubuntu"
+
+[config.rust]
+required-features = [{ feature = "pulseaudio", os = "ubuntu" }]
+
+[[config]]
+os = "macos"
+"#;
+        switchy_fs::sync::write(temp_path.join("clippier.toml"), clippier_toml).unwrap();
+
+        let result = process_configs(temp_path, &FeaturesOptions::default())
+            .await
+            .unwrap();
+
+        let required_for = |os: &str| {
+            string_array(
+                result
+                    .iter()
+                    .find(|x| x.get("os").and_then(|x| x.as_str()) == Some(os))
+                    .unwrap(),
+                "requiredFeatures",
+            )
+        };
+
+        assert_eq!(required_for("ubuntu"), vec!["pulseaudio"]);
+        assert_eq!(required_for("macos"), vec!["production"]);
+    }
+
     #[switchy_async::test]
     async fn test_expand_features_from_cargo_toml_helper() {
         // Test the expand_features_from_cargo_toml helper function
@@ -7012,15 +9768,10 @@ features = ["duckdb"]
 
         let result = process_configs(
             temp_path,
-            None,
-            None,
-            None,
-            false,
-            false,
-            None,
-            Some(&["default".to_string()]),
-            None,
-            None,
+            &FeaturesOptions {
+                features: Some("default".to_string()),
+                ..Default::default()
+            },
         )
         .await
         .unwrap();
@@ -7058,15 +9809,10 @@ os = "ubuntu"
         // Test: --features "*,!enable-experimental"
         let result = process_configs(
             temp_path,
-            None,
-            None,
-            None,
-            false,
-            false,
-            None,
-            Some(&["*".to_string(), "!enable-experimental".to_string()]),
-            None,
-            None,
+            &FeaturesOptions {
+                features: Some("*,!enable-experimental".to_string()),
+                ..Default::default()
+            },
         )
         .await
         .unwrap();
@@ -7118,15 +9864,10 @@ os = "ubuntu"
         // Test: --features "*,!test-*"
         let result = process_configs(
             temp_path,
-            None,
-            None,
-            None,
-            false,
-            false,
-            None,
-            Some(&["*".to_string(), "!test-*".to_string()]),
-            None,
-            None,
+            &FeaturesOptions {
+                features: Some("*,!test-*".to_string()),
+                ..Default::default()
+            },
         )
         .await
         .unwrap();
@@ -7179,19 +9920,10 @@ os = "ubuntu"
         // Test: --features "enable-*,!enable-experimental,production"
         let result = process_configs(
             temp_path,
-            None,
-            None,
-            None,
-            false,
-            false,
-            None,
-            Some(&[
-                "enable-*".to_string(),
-                "!enable-experimental".to_string(),
-                "production".to_string(),
-            ]),
-            None,
-            None,
+            &FeaturesOptions {
+                features: Some("enable-*,!enable-experimental,production".to_string()),
+                ..Default::default()
+            },
         )
         .await
         .unwrap();
@@ -7243,15 +9975,10 @@ os = "ubuntu"
         // Test: --required-features "enable-*,!enable-experimental"
         let result = process_configs(
             temp_path,
-            None,
-            None,
-            None,
-            false,
-            false,
-            None,
-            None,
-            None,
-            Some(&["enable-*".to_string(), "!enable-experimental".to_string()]),
+            &FeaturesOptions {
+                required_features: Some("enable-*,!enable-experimental".to_string()),
+                ..Default::default()
+            },
         )
         .await
         .unwrap();
@@ -7,7 +7,7 @@
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
 #![allow(clippy::multiple_crate_versions)]
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clap::{Parser, Subcommand};
 
@@ -15,12 +15,20 @@ use clap::{Parser, Subcommand};
 use clippier::ColorMode;
 #[cfg(feature = "versioning")]
 use clippier::versioning::{VersionBump, VersionConfig, VersionOperation, handle_version_command};
+use clippier::watch::{DEFAULT_DEBOUNCE, DEFAULT_POLL_INTERVAL, PollingWatcher, WatchOutput};
 use clippier::{
-    OutputType, handle_affected_packages_command, handle_ci_steps_command,
-    handle_dependencies_command, handle_environment_command, handle_features_command,
-    handle_generate_dockerfile_command, handle_packages_command,
+    CoverageStrategy, FeaturesOptions, NixShellFormat, OutputType, ValidationOutputType,
+    format_config_validation_report, format_heavy_defaults_report, format_matrix_diff,
+    format_package_graph, format_unused_features_report, format_workspace_hack_report,
+    handle_affected_packages_command, handle_ci_steps_command, handle_dependencies_command,
+    handle_environment_command, handle_features_command, handle_generate_dockerfile_command,
+    handle_heavy_defaults_command, handle_list_features_command, handle_matrix_diff_command,
+    handle_nix_shell_command, handle_package_graph_command, handle_packages_command,
+    handle_unused_features_command, handle_validate_command,
     handle_validate_feature_propagation_command, handle_workspace_deps_command,
-    handle_workspace_toolchains_command, print_human_output,
+    handle_workspace_hack_command, handle_workspace_system_deps_command,
+    handle_workspace_toolchains_command, merge_changed_files, parse_changed_files_list,
+    print_human_output, validation_result_to_sarif,
 };
 #[cfg(feature = "publish")]
 use clippier::{PublishConfig, handle_publish_command};
@@ -168,7 +176,8 @@ enum Commands {
         #[arg(long)]
         randomize: bool,
 
-        /// Seed for randomization (enables deterministic randomization when provided)
+        /// Base seed for randomization (enables deterministic randomization when provided).
+        /// Each package's seed is derived from it, so packages are shuffled differently
         #[arg(long)]
         seed: Option<u64>,
 
@@ -185,6 +194,10 @@ enum Commands {
         #[arg(long, value_delimiter = ',')]
         changed_files: Option<Vec<String>>,
 
+        /// Read additional newline-separated changed files from stdin
+        #[arg(long, default_value_t = false)]
+        changed_files_stdin: bool,
+
         /// Git base commit for external dependency analysis (requires git-diff feature)
         #[cfg(feature = "git-diff")]
         #[arg(long)]
@@ -234,6 +247,85 @@ enum Commands {
         #[arg(long, value_enum)]
         workspace_type: Option<Vec<clippier::workspace::WorkspaceType>>,
 
+        /// Emit a JSON summary (job count, packages covered, distinct features, average
+        /// features per job and max-parallel utilization) instead of the matrix
+        #[arg(long)]
+        summary: bool,
+
+        /// Environment profiles (comma-separated, e.g. "ci,release") selecting which
+        /// profile-gated env vars from clippier.toml are included in the matrix
+        #[arg(long, value_delimiter = ',')]
+        env_profile: Option<Vec<String>>,
+
+        /// Exit with an error listing the applied filters if the resulting matrix is empty
+        #[arg(long)]
+        fail_on_empty_matrix: bool,
+
+        /// Emit a JSON object mapping each OS to its matrix entries (e.g.
+        /// `{"ubuntu": [...], "macos": [...]}`) for one CI job definition per OS
+        #[arg(long, conflicts_with = "summary")]
+        group_by_os: bool,
+
+        /// Target triple (e.g. "x86_64-unknown-linux-gnu") to evaluate
+        /// `[target.'cfg(...)'.dependencies]` tables against. Features and dependencies
+        /// inactive for the target are left out of the matrix and affected-package analysis
+        #[arg(long)]
+        target: Option<String>,
+
+        /// Annotate each matrix entry with a `needs` list naming the entries for its
+        /// workspace dependencies, for topologically ordered CI stages
+        #[arg(long, conflicts_with = "summary")]
+        needs: bool,
+
+        /// Exit with an error listing the packages with the most jobs if the resulting
+        /// matrix has more than this many jobs
+        #[arg(long)]
+        max_total_jobs: Option<usize>,
+
+        /// Only use each package's own `clippier.toml` and the workspace defaults instead of
+        /// also collecting config from its workspace dependencies. Faster, but system
+        /// dependencies, CI steps and env vars contributed by dependencies are missed
+        #[arg(long)]
+        no_propagation: bool,
+
+        /// Emit the matrix split into exactly this many shards balanced by job count (e.g.
+        /// `[{"shard": 0, "jobs": [...]}, ...]`), one per runner of a fixed runner pool.
+        /// Requires `--output json`
+        #[arg(
+            long,
+            value_parser = clap::value_parser!(u16).range(1..),
+            conflicts_with_all = ["summary", "group_by_os"]
+        )]
+        shards: Option<u16>,
+
+        /// Instead of chunking features one by one, emit a reduced set of comma-separated
+        /// feature combinations per package, each with at most `--chunked` features, that
+        /// enables every feature (`each-once`) or every pair of features (`pairwise`) in at
+        /// least one combination
+        #[arg(long, value_enum, conflicts_with = "spread")]
+        coverage_strategy: Option<CoverageStrategy>,
+
+        /// Add a human-readable `displayName` to each matrix entry (e.g.
+        /// `moosicbox_player [flac,mp3] ubuntu`) for naming GitHub Actions jobs, truncated
+        /// with an ellipsis to fit GitHub's name-length limit
+        #[arg(long, conflicts_with = "summary")]
+        github_names: bool,
+
+        /// TOML file listing additional workspace members, relative to the workspace root.
+        /// Overrides the `members-file` of the root `clippier.toml`
+        #[arg(long)]
+        members_file: Option<PathBuf>,
+
+        /// Keep running and regenerate the matrix whenever a `Cargo.toml` or
+        /// `clippier.toml` of the workspace changes
+        #[arg(long, conflicts_with = "changed_files_stdin")]
+        watch: bool,
+
+        /// Write the matrix to this file instead of stdout, rewriting it on every
+        /// regeneration with `--watch`
+        #[arg(long)]
+        output_file: Option<PathBuf>,
+
         #[arg(short, long, value_enum, default_value_t=OutputType::Raw)]
         output: OutputType,
     },
@@ -293,6 +385,12 @@ enum Commands {
         /// Specify the binary name to build and use in the Dockerfile (overrides automatic detection)
         #[arg(long)]
         bin: Option<String>,
+        /// clippier.toml configuration OS to collect environment variables and system dependencies for
+        #[arg(long, default_value = clippier::DEFAULT_DOCKER_TARGET_OS)]
+        target_os: String,
+        /// Print the generated Dockerfile and dockerignore instead of writing them
+        #[arg(long)]
+        dry_run: bool,
     },
     AffectedPackages {
         /// Path to the workspace root
@@ -300,6 +398,9 @@ enum Commands {
         /// List of changed files (paths relative to workspace root)
         #[arg(long, value_delimiter = ',')]
         changed_files: Vec<String>,
+        /// Read additional newline-separated changed files from stdin
+        #[arg(long, default_value_t = false)]
+        changed_files_stdin: bool,
         /// Package to check if affected (optional - if not provided, returns all affected packages)
         #[arg(long)]
         target_package: Option<String>,
@@ -323,6 +424,23 @@ enum Commands {
         #[cfg(feature = "_workspace")]
         #[arg(long, value_enum)]
         workspace_type: Option<Vec<clippier::workspace::WorkspaceType>>,
+        /// Target triple (e.g. "x86_64-unknown-linux-gnu") to evaluate
+        /// `[target.'cfg(...)'.dependencies]` tables against. Only dependencies active for
+        /// the target are followed
+        #[arg(long)]
+        target: Option<String>,
+        /// TOML file listing additional workspace members, relative to the workspace root.
+        /// Overrides the `members-file` of the root `clippier.toml`
+        #[arg(long)]
+        members_file: Option<PathBuf>,
+        /// Keep running and recompute the affected packages whenever a `Cargo.toml` or
+        /// `clippier.toml` of the workspace changes
+        #[arg(long, conflicts_with = "changed_files_stdin")]
+        watch: bool,
+        /// Write the output to this file instead of stdout, rewriting it on every
+        /// recomputation with `--watch`
+        #[arg(long)]
+        output_file: Option<PathBuf>,
         /// Output format
         #[arg(long, value_enum, default_value_t=OutputType::Json)]
         output: OutputType,
@@ -350,8 +468,8 @@ enum Commands {
         workspace_only: bool,
 
         /// Output format
-        #[arg(short, long, value_enum, default_value_t = OutputType::Raw)]
-        output: OutputType,
+        #[arg(short, long, value_enum, default_value_t = ValidationOutputType::Raw)]
+        output: ValidationOutputType,
 
         /// Exit with error code if validation fails (for CI)
         #[arg(long, default_value_t = true)]
@@ -485,6 +603,10 @@ enum Commands {
         #[arg(long, value_enum)]
         workspace_type: Option<Vec<clippier::workspace::WorkspaceType>>,
 
+        /// Exit with an error listing the applied filters if the resulting matrix is empty
+        #[arg(long)]
+        fail_on_empty_matrix: bool,
+
         #[arg(short, long, value_enum, default_value_t=OutputType::Json)]
         output: OutputType,
     },
@@ -551,6 +673,153 @@ enum Commands {
         #[arg(short, long, value_enum, default_value_t=OutputType::Json)]
         output: OutputType,
     },
+    /// Emit one deduplicated install command per package manager for all workspace packages
+    WorkspaceSystemDeps {
+        /// Path to the workspace root
+        #[arg(index = 1, default_value = ".")]
+        workspace_root: PathBuf,
+
+        /// Target operating system (ubuntu, windows, macos)
+        #[arg(long)]
+        os: String,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t=OutputType::Json)]
+        output: OutputType,
+    },
+    /// Generate a Nix devShell providing the workspace's system dependencies and toolchains
+    NixShell {
+        /// Path to the workspace root
+        #[arg(index = 1, default_value = ".")]
+        workspace_root: PathBuf,
+
+        /// Operating system whose dependencies to translate (ubuntu, windows, macos)
+        #[arg(long, default_value = "ubuntu")]
+        os: String,
+
+        /// Nix expression to generate
+        #[arg(long, value_enum, default_value_t = NixShellFormat::Shell)]
+        format: NixShellFormat,
+
+        /// Map a package or toolchain to nixpkgs attributes (`name=attr1,attr2`, or
+        /// `name=` to drop it)
+        #[arg(long = "map")]
+        overrides: Vec<String>,
+    },
+    /// Check that a workspace-hack package enables the union of external dependency features
+    WorkspaceHack {
+        /// Path to the workspace root
+        #[arg(index = 1, default_value = ".")]
+        workspace_root: PathBuf,
+
+        /// Name of the workspace-hack package
+        #[arg(long)]
+        hack_package: String,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = OutputType::Raw)]
+        output: OutputType,
+
+        /// Exit with error code if the workspace-hack package is missing entries (for CI)
+        #[arg(long, default_value_t = true, action = clap::ArgAction::Set)]
+        fail_on_missing: bool,
+    },
+    /// Report features that are defined but never referenced anywhere in the workspace
+    UnusedFeatures {
+        /// Path to the workspace root
+        #[arg(index = 1, default_value = ".")]
+        workspace_root: PathBuf,
+
+        /// Features to never report (comma-separated), e.g. public API features
+        #[arg(long, value_delimiter = ',')]
+        ignore_feature: Vec<String>,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = OutputType::Raw)]
+        output: OutputType,
+
+        /// Exit with error code if any unreferenced features are found (for CI)
+        #[arg(long, default_value_t = false)]
+        fail_on_unused: bool,
+    },
+    /// Find optional dependencies with large dependency trees enabled by default features
+    HeavyDefaults {
+        /// Path to the workspace root
+        #[arg(index = 1, default_value = ".")]
+        workspace_root: PathBuf,
+
+        /// Minimum number of transitive dependencies for an optional dependency to be flagged
+        #[arg(long, default_value_t = 20)]
+        min_deps: usize,
+
+        /// Dependencies to never report (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        ignore_dependency: Vec<String>,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = OutputType::Raw)]
+        output: OutputType,
+
+        /// Exit with error code if any heavy default dependencies are found (for CI)
+        #[arg(long, default_value_t = false)]
+        fail_on_heavy: bool,
+    },
+    /// Export the dependency graph between workspace members, e.g. for dashboards
+    PackageGraph {
+        /// Path to the workspace root
+        #[arg(index = 1, default_value = ".")]
+        workspace_root: PathBuf,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = OutputType::Json)]
+        output: OutputType,
+    },
+    /// Check every clippier.toml in the workspace for unknown keys and invalid values
+    Validate {
+        /// Path to the workspace root
+        #[arg(index = 1, default_value = ".")]
+        workspace_root: PathBuf,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = OutputType::Raw)]
+        output: OutputType,
+
+        /// Exit with error code if any problems are found (for CI)
+        #[arg(long, default_value_t = true, action = clap::ArgAction::Set)]
+        fail_on_error: bool,
+    },
+    /// Compare two features matrices (e.g. before/after a change) and report job changes
+    MatrixDiff {
+        /// Path to the old matrix JSON
+        #[arg(index = 1)]
+        before: PathBuf,
+
+        /// Path to the new matrix JSON
+        #[arg(index = 2)]
+        after: PathBuf,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = OutputType::Raw)]
+        output: OutputType,
+
+        /// Exit with error code if the matrices differ (for CI)
+        #[arg(long, default_value_t = false)]
+        fail_on_change: bool,
+    },
+    /// List a package's features and everything each of them transitively enables
+    ListFeatures {
+        /// Path to the workspace root
+        #[arg(index = 1, default_value = ".")]
+        workspace_root: PathBuf,
+
+        /// Name of the package to list features for
+        #[arg(long)]
+        package: String,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = OutputType::Raw)]
+        output: OutputType,
+    },
     /// Run linters and format checkers
     #[cfg(feature = "check")]
     Check {
@@ -558,6 +827,11 @@ enum Commands {
         #[arg(short, long)]
         working_dir: Option<PathBuf>,
 
+        /// Only run in packages affected by uncommitted changes in the git working tree
+        #[cfg(feature = "git-diff")]
+        #[arg(long, default_value_t = false)]
+        changed_only: bool,
+
         /// Specific tools to run (comma-separated)
         #[arg(long, value_delimiter = ',')]
         tools: Option<Vec<String>>,
@@ -617,6 +891,11 @@ enum Commands {
         #[arg(short, long)]
         working_dir: Option<PathBuf>,
 
+        /// Only run in packages affected by uncommitted changes in the git working tree
+        #[cfg(feature = "git-diff")]
+        #[arg(long, default_value_t = false)]
+        changed_only: bool,
+
         /// Only check formatting without modifying files
         #[arg(long)]
         check: bool,
@@ -675,6 +954,87 @@ enum Commands {
     },
 }
 
+/// Resolves the package directories to scope `check`/`fmt` to when `--changed-only` is
+/// passed.
+#[cfg(any(feature = "check", feature = "format"))]
+#[cfg_attr(not(feature = "git-diff"), allow(clippy::unnecessary_wraps))]
+fn changed_package_dirs(
+    working_dir: Option<&std::path::Path>,
+    #[cfg(feature = "git-diff")] changed_only: bool,
+) -> Result<Option<Vec<PathBuf>>, BoxError> {
+    #[cfg(feature = "git-diff")]
+    if changed_only {
+        let root = working_dir.unwrap_or_else(|| std::path::Path::new("."));
+        let dirs = clippier::find_changed_package_dirs(root)?;
+        if dirs.is_empty() {
+            eprintln!("No packages affected by uncommitted changes");
+        }
+        return Ok(Some(dirs));
+    }
+
+    #[cfg(not(feature = "git-diff"))]
+    let _ = working_dir;
+
+    Ok(None)
+}
+
+/// Runs a command generating output for the workspace at `path`
+///
+/// Without `watch`, the output is generated once and returned, or written to
+/// `output_file` if set. With `watch`, it is regenerated on every change to the
+/// workspace's manifests until interrupted and written to `output_file` or stdout each
+/// time, and nothing is returned.
+#[allow(clippy::future_not_send)]
+async fn run_command<G, F>(
+    path: &Path,
+    watch: bool,
+    output_file: Option<PathBuf>,
+    mut generate: G,
+) -> Result<String, BoxError>
+where
+    G: FnMut() -> F,
+    F: Future<Output = Result<String, BoxError>>,
+{
+    let output = output_file.map_or(WatchOutput::Stdout, WatchOutput::File);
+
+    if !watch {
+        let result = generate().await?;
+        return Ok(match output {
+            WatchOutput::Stdout => result,
+            WatchOutput::File(_) => {
+                output.write(&result)?;
+                String::new()
+            }
+        });
+    }
+
+    // The features command also accepts the path of the root `Cargo.toml`
+    let workspace_root = if path.file_name().is_some_and(|x| x == "Cargo.toml") {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    };
+
+    eprintln!(
+        "Watching {} for changes (press Ctrl+C to stop)",
+        workspace_root.display()
+    );
+    let mut watcher = PollingWatcher::new(workspace_root, DEFAULT_POLL_INTERVAL);
+    clippier::watch::watch(&mut watcher, DEFAULT_DEBOUNCE, generate, |x| {
+        output.write(x)
+    })
+    .await?;
+
+    Ok(String::new())
+}
+
+/// Reads the newline-separated changed files piped to stdin
+fn read_changed_files_stdin() -> Result<Vec<String>, BoxError> {
+    let mut input = String::new();
+    std::io::Read::read_to_string(&mut std::io::stdin(), &mut input)?;
+    Ok(parse_changed_files_list(&input))
+}
+
 #[switchy_async::main]
 async fn main() {
     if let Err(error) = run().await {
@@ -731,6 +1091,7 @@ async fn run() -> Result<(), BoxError> {
             skip_features,
             required_features,
             changed_files,
+            changed_files_stdin,
             #[cfg(feature = "git-diff")]
             git_base,
             #[cfg(feature = "git-diff")]
@@ -746,11 +1107,34 @@ async fn run() -> Result<(), BoxError> {
             transform_trace,
             #[cfg(feature = "_workspace")]
             workspace_type,
+            summary,
+            env_profile,
+            fail_on_empty_matrix,
+            group_by_os,
+            target,
+            needs,
+            max_total_jobs,
+            no_propagation,
+            shards,
+            coverage_strategy,
+            github_names,
+            members_file,
+            watch,
+            output_file,
             output,
         } => {
-            handle_features_command(
-                &file,
-                os.as_deref(),
+            let changed_files = if changed_files_stdin {
+                Some(merge_changed_files(
+                    changed_files.as_deref().unwrap_or_default(),
+                    read_changed_files_stdin()?,
+                ))
+            } else {
+                changed_files
+            };
+
+            let options = FeaturesOptions {
+                file,
+                os,
                 offset,
                 max,
                 max_parallel,
@@ -758,32 +1142,47 @@ async fn run() -> Result<(), BoxError> {
                 spread,
                 randomize,
                 seed,
-                features.as_deref(),
-                skip_features.as_deref(),
-                required_features.as_deref(),
-                packages.as_deref(),
-                changed_files.as_deref(),
+                features,
+                skip_features,
+                required_features,
+                packages,
+                changed_files,
                 #[cfg(feature = "git-diff")]
-                git_base.as_deref(),
+                git_base,
                 #[cfg(feature = "git-diff")]
-                git_head.as_deref(),
+                git_head,
                 include_reasoning,
-                if ignore.is_empty() {
+                ignore_patterns: if ignore.is_empty() {
                     None
                 } else {
-                    Some(&ignore)
+                    Some(ignore)
                 },
-                &skip_if,
-                &include_if,
+                skip_if,
+                include_if,
                 #[cfg(feature = "_transforms")]
-                &transform_scripts,
+                transform_scripts,
                 #[cfg(feature = "_transforms")]
                 transform_trace,
                 #[cfg(feature = "_workspace")]
-                workspace_type.as_deref(),
+                workspace_type,
+                summary,
+                env_profile,
+                fail_on_empty_matrix,
+                group_by_os,
+                target,
+                needs,
+                max_total_jobs,
+                no_propagation,
+                shards,
+                coverage_strategy,
+                github_names,
+                members_file,
                 output,
-            )
-            .await?
+            };
+
+            let generate = || handle_features_command(&options);
+
+            run_command(Path::new(&options.file), watch, output_file, generate).await?
         }
         Commands::WorkspaceDeps {
             workspace_root,
@@ -813,6 +1212,8 @@ async fn run() -> Result<(), BoxError> {
             env,
             build_env,
             bin,
+            target_os,
+            dry_run,
         } => {
             handle_generate_dockerfile_command(
                 &workspace_root,
@@ -829,12 +1230,15 @@ async fn run() -> Result<(), BoxError> {
                 &env,
                 &build_env,
                 bin.as_deref(),
+                &target_os,
+                dry_run,
             )
             .await?
         }
         Commands::AffectedPackages {
             workspace_root,
             changed_files,
+            changed_files_stdin,
             target_package,
             #[cfg(feature = "git-diff")]
             git_base,
@@ -844,27 +1248,42 @@ async fn run() -> Result<(), BoxError> {
             ignore,
             #[cfg(feature = "_workspace")]
             workspace_type,
+            target,
+            members_file,
+            watch,
+            output_file,
             output,
         } => {
-            handle_affected_packages_command(
-                &workspace_root,
-                &changed_files,
-                target_package.as_deref(),
-                #[cfg(feature = "git-diff")]
-                git_base.as_deref(),
-                #[cfg(feature = "git-diff")]
-                git_head.as_deref(),
-                include_reasoning,
-                if ignore.is_empty() {
-                    None
-                } else {
-                    Some(&ignore)
-                },
-                #[cfg(feature = "_workspace")]
-                workspace_type.as_deref(),
-                output,
-            )
-            .await?
+            let changed_files = if changed_files_stdin {
+                merge_changed_files(&changed_files, read_changed_files_stdin()?)
+            } else {
+                changed_files
+            };
+
+            let generate = || {
+                handle_affected_packages_command(
+                    &workspace_root,
+                    &changed_files,
+                    target_package.as_deref(),
+                    #[cfg(feature = "git-diff")]
+                    git_base.as_deref(),
+                    #[cfg(feature = "git-diff")]
+                    git_head.as_deref(),
+                    include_reasoning,
+                    if ignore.is_empty() {
+                        None
+                    } else {
+                        Some(&ignore)
+                    },
+                    #[cfg(feature = "_workspace")]
+                    workspace_type.as_deref(),
+                    target.as_deref(),
+                    members_file.as_deref(),
+                    output,
+                )
+            };
+
+            run_command(&workspace_root, watch, output_file, generate).await?
         }
         Commands::ValidateFeaturePropagation {
             features,
@@ -894,7 +1313,7 @@ async fn run() -> Result<(), BoxError> {
                 skip_features,
                 path,
                 workspace_only,
-                output,
+                output.into(),
                 strict_optional,
                 &allow_missing,
                 &allow_incorrect,
@@ -913,8 +1332,12 @@ async fn run() -> Result<(), BoxError> {
             )?;
 
             match output {
-                OutputType::Raw => print_human_output(&result),
-                OutputType::Json => println!("{}", serde_json::to_string(&result)?),
+                ValidationOutputType::Raw => print_human_output(&result),
+                ValidationOutputType::Json => println!("{}", serde_json::to_string(&result)?),
+                ValidationOutputType::Sarif => println!(
+                    "{}",
+                    serde_json::to_string(&validation_result_to_sarif(&result))?
+                ),
             }
 
             if fail_on_error
@@ -952,6 +1375,7 @@ async fn run() -> Result<(), BoxError> {
             include_if,
             #[cfg(feature = "_workspace")]
             workspace_type,
+            fail_on_empty_matrix,
             output,
         } => {
             handle_packages_command(
@@ -972,6 +1396,7 @@ async fn run() -> Result<(), BoxError> {
                 &include_if,
                 #[cfg(feature = "_workspace")]
                 workspace_type.as_deref(),
+                fail_on_empty_matrix,
                 output,
             )
             .await?
@@ -1045,9 +1470,136 @@ async fn run() -> Result<(), BoxError> {
             os,
             output,
         } => handle_workspace_toolchains_command(&workspace_root, &os, output)?,
+        Commands::WorkspaceSystemDeps {
+            workspace_root,
+            os,
+            output,
+        } => handle_workspace_system_deps_command(&workspace_root, &os, output)?,
+        Commands::NixShell {
+            workspace_root,
+            os,
+            format,
+            overrides,
+        } => handle_nix_shell_command(&workspace_root, &os, format, &overrides)?,
+        Commands::WorkspaceHack {
+            workspace_root,
+            hack_package,
+            output,
+            fail_on_missing,
+        } => {
+            let report = handle_workspace_hack_command(&workspace_root, &hack_package)?;
+
+            match output {
+                OutputType::Raw => print!("{}", format_workspace_hack_report(&report)),
+                OutputType::Json => println!("{}", serde_json::to_string(&report)?),
+            }
+
+            if fail_on_missing && !report.is_complete() {
+                std::process::exit(1);
+            }
+
+            return Ok(());
+        }
+        Commands::UnusedFeatures {
+            workspace_root,
+            ignore_feature,
+            output,
+            fail_on_unused,
+        } => {
+            let report = handle_unused_features_command(&workspace_root, &ignore_feature)?;
+
+            match output {
+                OutputType::Raw => print!("{}", format_unused_features_report(&report)),
+                OutputType::Json => println!("{}", serde_json::to_string(&report)?),
+            }
+
+            if fail_on_unused && !report.is_clean() {
+                std::process::exit(1);
+            }
+
+            return Ok(());
+        }
+        Commands::HeavyDefaults {
+            workspace_root,
+            min_deps,
+            ignore_dependency,
+            output,
+            fail_on_heavy,
+        } => {
+            let report =
+                handle_heavy_defaults_command(&workspace_root, min_deps, &ignore_dependency)?;
+
+            match output {
+                OutputType::Raw => print!("{}", format_heavy_defaults_report(&report)),
+                OutputType::Json => println!("{}", serde_json::to_string(&report)?),
+            }
+
+            if fail_on_heavy && !report.is_clean() {
+                std::process::exit(1);
+            }
+
+            return Ok(());
+        }
+        Commands::PackageGraph {
+            workspace_root,
+            output,
+        } => {
+            let graph = handle_package_graph_command(&workspace_root)?;
+
+            match output {
+                OutputType::Raw => print!("{}", format_package_graph(&graph)),
+                OutputType::Json => println!("{}", serde_json::to_string_pretty(&graph)?),
+            }
+
+            return Ok(());
+        }
+        Commands::Validate {
+            workspace_root,
+            output,
+            fail_on_error,
+        } => {
+            let report = handle_validate_command(&workspace_root)?;
+
+            match output {
+                OutputType::Raw => print!("{}", format_config_validation_report(&report)),
+                OutputType::Json => println!("{}", serde_json::to_string(&report)?),
+            }
+
+            if fail_on_error && !report.is_clean() {
+                std::process::exit(1);
+            }
+
+            return Ok(());
+        }
+        Commands::MatrixDiff {
+            before,
+            after,
+            output,
+            fail_on_change,
+        } => {
+            let diff = handle_matrix_diff_command(&before, &after)?;
+
+            match output {
+                OutputType::Raw => print!("{}", format_matrix_diff(&diff)),
+                OutputType::Json => println!("{}", serde_json::to_string(&diff)?),
+            }
+
+            if fail_on_change && !diff.is_empty() {
+                std::process::exit(1);
+            }
+
+            return Ok(());
+        }
+        Commands::ListFeatures {
+            workspace_root,
+            package,
+            output,
+        } => handle_list_features_command(&workspace_root, &package, output)?,
         #[cfg(feature = "check")]
         Commands::Check {
             working_dir,
+            #[cfg(feature = "git-diff")]
+            changed_only,
             tools,
             list,
             required,
@@ -1097,8 +1649,14 @@ async fn run() -> Result<(), BoxError> {
                 biome_editorconfig_override,
                 biome_vcs_ignore_override,
             )?;
+            let package_dirs = changed_package_dirs(
+                working_dir.as_deref(),
+                #[cfg(feature = "git-diff")]
+                changed_only,
+            )?;
             handle_check_command(
                 working_dir.as_deref(),
+                package_dirs.as_deref(),
                 tools.as_deref(),
                 list,
                 config,
@@ -1110,6 +1668,8 @@ async fn run() -> Result<(), BoxError> {
         #[cfg(feature = "format")]
         Commands::Fmt {
             working_dir,
+            #[cfg(feature = "git-diff")]
+            changed_only,
             check,
             tools,
             list,
@@ -1160,8 +1720,14 @@ async fn run() -> Result<(), BoxError> {
                 biome_editorconfig_override,
                 biome_vcs_ignore_override,
             )?;
+            let package_dirs = changed_package_dirs(
+                working_dir.as_deref(),
+                #[cfg(feature = "git-diff")]
+                changed_only,
+            )?;
             handle_fmt_command(
                 working_dir.as_deref(),
+                package_dirs.as_deref(),
                 tools.as_deref(),
                 check,
                 list,
@@ -0,0 +1,281 @@
+//! Comparison of two generated features matrices.
+//!
+//! Jobs are keyed by package, OS and normalized (sorted and deduplicated) feature set, so
+//! reordering the jobs of a matrix or the features within a job is not reported as a
+//! change. Only the keys are compared; other fields of the entries (dependencies, env
+//! vars, CI steps, etc.) are ignored.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use crate::BoxError;
+
+/// A matrix job identified by its package, OS and feature set
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct MatrixJob {
+    /// Name of the package the job builds
+    pub package: String,
+    /// OS the job runs on, empty if the entry has none
+    pub os: String,
+    /// Features enabled by the job, sorted and deduplicated
+    pub features: Vec<String>,
+}
+
+/// How the jobs of a package on one OS changed between two matrices
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PackageMatrixChange {
+    /// Name of the package
+    pub package: String,
+    /// OS the jobs run on
+    pub os: String,
+    /// Number of jobs for the package in the old matrix
+    pub jobs_before: usize,
+    /// Number of jobs for the package in the new matrix
+    pub jobs_after: usize,
+    /// Features only covered by the new matrix
+    pub features_added: Vec<String>,
+    /// Features only covered by the old matrix
+    pub features_removed: Vec<String>,
+}
+
+/// Difference between two features matrices
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MatrixDiff {
+    /// Number of jobs in the old matrix
+    pub jobs_before: usize,
+    /// Number of jobs in the new matrix
+    pub jobs_after: usize,
+    /// Jobs only in the new matrix, sorted by package, OS and features
+    pub added: Vec<MatrixJob>,
+    /// Jobs only in the old matrix, sorted by package, OS and features
+    pub removed: Vec<MatrixJob>,
+    /// Packages present in both matrices whose jobs differ, sorted by package and OS
+    pub changed: Vec<PackageMatrixChange>,
+}
+
+impl MatrixDiff {
+    /// Whether both matrices have the same jobs
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Extracts the jobs of a features matrix
+///
+/// Accepts both the flat matrix and the per-OS object emitted with `--group-by-os`.
+///
+/// # Errors
+///
+/// * If `matrix` is neither an array nor an object of arrays
+/// * If an entry is not an object or has no `name`
+/// * If an entry's `features` is not an array of strings
+pub fn parse_matrix_jobs(matrix: &serde_json::Value) -> Result<Vec<MatrixJob>, BoxError> {
+    let entries: Vec<&serde_json::Value> = match matrix {
+        serde_json::Value::Array(entries) => entries.iter().collect(),
+        serde_json::Value::Object(groups) => groups
+            .values()
+            .map(|group| {
+                group
+                    .as_array()
+                    .ok_or_else(|| format!("Expected an array of matrix entries, got {group}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .collect(),
+        other => return Err(format!("Expected a features matrix, got {other}").into()),
+    };
+
+    entries.into_iter().map(parse_matrix_job).collect()
+}
+
+fn parse_matrix_job(entry: &serde_json::Value) -> Result<MatrixJob, BoxError> {
+    let package = entry
+        .get("name")
+        .and_then(|x| x.as_str())
+        .ok_or_else(|| format!("Matrix entry has no name: {entry}"))?;
+
+    let os = entry.get("os").and_then(|x| x.as_str()).unwrap_or_default();
+
+    let features = match entry.get("features") {
+        None | Some(serde_json::Value::Null) => BTreeSet::new(),
+        Some(serde_json::Value::Array(features)) => features
+            .iter()
+            .map(|feature| {
+                feature
+                    .as_str()
+                    .map(ToString::to_string)
+                    .ok_or_else(|| format!("Invalid feature {feature} in matrix entry {package}"))
+            })
+            .collect::<Result<BTreeSet<_>, _>>()?,
+        Some(other) => {
+            return Err(format!("Invalid features {other} in matrix entry {package}").into());
+        }
+    };
+
+    Ok(MatrixJob {
+        package: package.to_string(),
+        os: os.to_string(),
+        features: features.into_iter().collect(),
+    })
+}
+
+/// Computes the jobs added, removed and changed between the `before` and `after` matrices
+///
+/// Jobs are compared as multisets, so a job listed twice in `after` but once in `before`
+/// counts as one added job.
+#[must_use]
+pub fn diff_matrices(before: &[MatrixJob], after: &[MatrixJob]) -> MatrixDiff {
+    fn group(jobs: &[MatrixJob]) -> BTreeMap<(&str, &str), Vec<&MatrixJob>> {
+        let mut groups: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for job in jobs {
+            groups
+                .entry((job.package.as_str(), job.os.as_str()))
+                .or_default()
+                .push(job);
+        }
+        groups
+    }
+
+    fn features<'a>(jobs: &[&'a MatrixJob]) -> BTreeSet<&'a str> {
+        jobs.iter()
+            .flat_map(|job| job.features.iter().map(String::as_str))
+            .collect()
+    }
+
+    fn excess(
+        from: &BTreeMap<&MatrixJob, usize>,
+        other: &BTreeMap<&MatrixJob, usize>,
+    ) -> Vec<MatrixJob> {
+        from.iter()
+            .flat_map(|(job, count)| {
+                let extra = count.saturating_sub(other.get(job).copied().unwrap_or_default());
+                std::iter::repeat_n((*job).clone(), extra)
+            })
+            .collect()
+    }
+
+    let before_counts = count(before);
+    let after_counts = count(after);
+
+    let before_groups = group(before);
+    let after_groups = group(after);
+
+    let changed = before_groups
+        .iter()
+        .filter_map(|(key, before_jobs)| {
+            let after_jobs = after_groups.get(key)?;
+            if count(before_jobs.iter().copied()) == count(after_jobs.iter().copied()) {
+                return None;
+            }
+
+            let before_features = features(before_jobs);
+            let after_features = features(after_jobs);
+
+            Some(PackageMatrixChange {
+                package: key.0.to_string(),
+                os: key.1.to_string(),
+                jobs_before: before_jobs.len(),
+                jobs_after: after_jobs.len(),
+                features_added: after_features
+                    .difference(&before_features)
+                    .map(ToString::to_string)
+                    .collect(),
+                features_removed: before_features
+                    .difference(&after_features)
+                    .map(ToString::to_string)
+                    .collect(),
+            })
+        })
+        .collect();
+
+    MatrixDiff {
+        jobs_before: before.len(),
+        jobs_after: after.len(),
+        added: excess(&after_counts, &before_counts),
+        removed: excess(&before_counts, &after_counts),
+        changed,
+    }
+}
+
+fn count<'a>(jobs: impl IntoIterator<Item = &'a MatrixJob>) -> BTreeMap<&'a MatrixJob, usize> {
+    let mut counts = BTreeMap::new();
+    for job in jobs {
+        *counts.entry(job).or_default() += 1;
+    }
+    counts
+}
+
+fn format_job(job: &MatrixJob) -> String {
+    let os = if job.os.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", job.os)
+    };
+    format!("{}{os}: [{}]", job.package, job.features.join(", "))
+}
+
+/// Formats a [`MatrixDiff`] for human-readable output
+#[must_use]
+pub fn format_matrix_diff(diff: &MatrixDiff) -> String {
+    use std::fmt::Write as _;
+
+    let mut output = String::new();
+
+    let delta = if diff.jobs_after >= diff.jobs_before {
+        format!("+{}", diff.jobs_after - diff.jobs_before)
+    } else {
+        format!("-{}", diff.jobs_before - diff.jobs_after)
+    };
+    let _ = writeln!(
+        output,
+        "Jobs: {} -> {} ({delta})",
+        diff.jobs_before, diff.jobs_after
+    );
+
+    if diff.is_empty() {
+        let _ = writeln!(output, "✅ No job changes");
+        return output;
+    }
+
+    if !diff.added.is_empty() {
+        let _ = writeln!(output, "➕ {} added jobs:", diff.added.len());
+        for job in &diff.added {
+            let _ = writeln!(output, "  + {}", format_job(job));
+        }
+    }
+
+    if !diff.removed.is_empty() {
+        let _ = writeln!(output, "➖ {} removed jobs:", diff.removed.len());
+        for job in &diff.removed {
+            let _ = writeln!(output, "  - {}", format_job(job));
+        }
+    }
+
+    if !diff.changed.is_empty() {
+        let _ = writeln!(output, "🔀 {} changed packages:", diff.changed.len());
+        for change in &diff.changed {
+            let os = if change.os.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", change.os)
+            };
+            let _ = write!(
+                output,
+                "  📦 {}{os}: {} -> {} jobs",
+                change.package, change.jobs_before, change.jobs_after
+            );
+            if !change.features_added.is_empty() {
+                let _ = write!(output, ", +[{}]", change.features_added.join(", "));
+            }
+            if !change.features_removed.is_empty() {
+                let _ = write!(output, ", -[{}]", change.features_removed.join(", "));
+            }
+            let _ = writeln!(output);
+        }
+    }
+
+    output
+}
@@ -0,0 +1,96 @@
+//! Typed features matrix entries.
+//!
+//! [`MatrixEntry`] mirrors the JSON objects emitted by the `features` command, so library
+//! consumers can work with the matrix returned by
+//! [`features_matrix_entries`](crate::features_matrix_entries) without re-parsing it.
+//! Fields added by transform scripts are kept in [`MatrixEntry::extra`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::BoxError;
+
+/// One job of a features matrix
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MatrixEntry {
+    /// Name of the package, or the name configured for the job in `clippier.toml`
+    pub name: String,
+    /// Path of the package relative to the workspace root
+    pub path: String,
+    /// OS the job runs on
+    pub os: String,
+    /// Features enabled by the job
+    pub features: Vec<String>,
+    /// Features every job of the package enables, if any are required
+    pub required_features: Option<Vec<String>>,
+    /// Whether the job builds with a nightly toolchain
+    pub nightly: bool,
+    /// Commands installing the system dependencies, one per line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<String>,
+    /// Toolchains the system dependencies need, one per line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toolchains: Option<String>,
+    /// Environment variables as `KEY=value` lines
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<String>,
+    /// Extra arguments passed to cargo
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cargo: Option<String>,
+    /// Commands to run before the job's cargo commands, one per line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ci_steps: Option<String>,
+    /// Toolchains the CI steps need, one per line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ci_toolchains: Option<String>,
+    /// Whether the job checks out git submodules
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_submodules: Option<bool>,
+    /// Number of times to retry the job if it fails, for flaky packages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    /// Names of the jobs this job depends on (see
+    /// [`annotate_features_matrix_needs`](crate::annotate_features_matrix_needs))
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub needs: Option<Vec<String>>,
+    /// Name of the GitHub Actions job (see
+    /// [`github_display_name`](crate::github_display_name))
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Why the package is affected by the changed files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<Vec<String>>,
+    /// Any other fields of the entry, e.g. ones added by transform scripts
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl MatrixEntry {
+    /// Converts a matrix entry from its JSON object
+    ///
+    /// # Errors
+    ///
+    /// * If a known field has an unexpected type
+    pub fn from_map(map: serde_json::Map<String, serde_json::Value>) -> Result<Self, BoxError> {
+        let name = map
+            .get("name")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+
+        serde_json::from_value(serde_json::Value::Object(map))
+            .map_err(|e| format!("Invalid features matrix entry '{name}': {e}").into())
+    }
+
+    /// Converts the matrix entry into its JSON object
+    ///
+    /// # Errors
+    ///
+    /// * If the entry fails to serialize
+    pub fn into_map(self) -> Result<serde_json::Map<String, serde_json::Value>, BoxError> {
+        match serde_json::to_value(self)? {
+            serde_json::Value::Object(map) => Ok(map),
+            value => Err(format!("Matrix entry serialized to a non-object: {value}").into()),
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! Nix development shell generation.
+//!
+//! [`collect_workspace_toolchains`](crate::collect_workspace_toolchains) and
+//! [`collect_workspace_system_deps`](crate::system_deps::collect_workspace_system_deps)
+//! gather what CI installs before building the workspace. This module maps the same
+//! system packages and toolchains to `nixpkgs` attributes and renders them as a
+//! `shell.nix` or `flake.nix` `devShell`, so Nix users get an equivalent environment.
+//!
+//! The mapping is best-effort: [`nixpkgs_attrs`] knows the packages used across this
+//! workspace, and callers can extend or replace it with overrides. Dependencies without a
+//! mapping, and dependency commands that do more than install packages, are emitted as
+//! `TODO` comments instead of failing.
+
+use std::{collections::BTreeMap, fmt::Write as _};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{BoxError, WorkspaceToolchains, system_deps::WorkspaceSystemDeps};
+
+/// Built-in mapping of system package and toolchain names to `nixpkgs` attributes.
+///
+/// An empty list means the dependency isn't needed in a development shell.
+const NIXPKGS_ATTRS: &[(&str, &[&str])] = &[
+    // apt
+    ("build-essential", &["gcc", "gnumake"]),
+    ("jackd2", &["jack2"]),
+    ("libasound2-dev", &["alsa-lib"]),
+    ("libgl1-mesa-dev", &["libGL"]),
+    ("libglu1-mesa-dev", &["libGLU"]),
+    ("libgtk-3-dev", &["gtk3"]),
+    ("libjack-jackd2-dev", &["jack2"]),
+    ("liblua5.4-dev", &["lua5_4"]),
+    ("libopus-dev", &["libopus"]),
+    ("libpango1.0-dev", &["pango"]),
+    ("libpulse-dev", &["libpulseaudio"]),
+    ("libsqlite3-dev", &["sqlite"]),
+    ("libssl-dev", &["openssl"]),
+    ("libvips-dev", &["vips"]),
+    ("libwebkit2gtk-4.1-dev", &["webkitgtk_4_1"]),
+    ("libx11-dev", &["xorg.libX11"]),
+    ("libxcursor-dev", &["xorg.libXcursor"]),
+    ("libxext-dev", &["xorg.libXext"]),
+    ("libxfixes-dev", &["xorg.libXfixes"]),
+    ("libxft-dev", &["xorg.libXft"]),
+    ("libxinerama-dev", &["xorg.libXinerama"]),
+    ("libxrender-dev", &["xorg.libXrender"]),
+    ("npm", &["nodejs"]),
+    ("nodejs", &["nodejs"]),
+    ("pkg-config", &["pkg-config"]),
+    ("protobuf-compiler", &["protobuf"]),
+    // brew
+    ("berkeley-db@5", &["db"]),
+    ("jack", &["jack2"]),
+    ("sqlite3", &["sqlite"]),
+    // shared
+    ("autoconf", &["autoconf"]),
+    ("automake", &["automake"]),
+    ("cmake", &["cmake"]),
+    ("libtool", &["libtool"]),
+    ("openssl", &["openssl"]),
+    // toolchains
+    ("bun", &["bun"]),
+    ("cargo-deny", &["cargo-deny"]),
+    ("cargo-machete", &["cargo-machete"]),
+    ("free_disk_space", &[]),
+    ("node", &["nodejs"]),
+    ("pnpm", &["pnpm"]),
+    ("taplo", &["taplo"]),
+];
+
+/// Packages every generated shell includes.
+const BASE_PACKAGES: &[&str] = &["rustup"];
+
+/// Format of the generated Nix expression
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+#[clap(rename_all = "kebab_case")]
+pub enum NixShellFormat {
+    /// `shell.nix` for `nix-shell`
+    Shell,
+    /// `flake.nix` exposing `devShells.default` for `nix develop`
+    Flake,
+}
+
+/// A dependency without a `nixpkgs` mapping
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case", tag = "kind", content = "name")]
+pub enum UnmappedDependency {
+    /// A system package installed by a package manager
+    Package(String),
+    /// A `toolchain` entry of a `clippier.toml`
+    Toolchain(String),
+    /// A dependency command that does more than install packages
+    Command(String),
+}
+
+/// A development shell derived from a workspace's dependencies
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct NixShell {
+    /// Sorted, deduplicated `nixpkgs` attributes to include
+    pub packages: Vec<String>,
+    /// Dependencies that couldn't be mapped to `nixpkgs` attributes
+    pub unmapped: Vec<UnmappedDependency>,
+    /// Environment variables to set in the shell
+    pub env: BTreeMap<String, String>,
+    /// Packages that need a nightly Rust toolchain
+    pub nightly_packages: Vec<String>,
+}
+
+impl NixShell {
+    /// Builds a shell from the collected system dependencies and toolchains.
+    ///
+    /// `overrides` maps package or toolchain names to `nixpkgs` attributes and takes
+    /// precedence over [`nixpkgs_attrs`]. Overriding a name to no attributes drops it.
+    #[must_use]
+    pub fn new(
+        deps: &WorkspaceSystemDeps,
+        toolchains: &WorkspaceToolchains,
+        overrides: &BTreeMap<String, Vec<String>>,
+    ) -> Self {
+        let mut packages = BASE_PACKAGES
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        let mut unmapped = vec![];
+
+        let mut map = |name: &str, unmapped_dependency: fn(String) -> UnmappedDependency| {
+            if let Some(attrs) = overrides.get(name) {
+                packages.extend(attrs.iter().cloned());
+            } else if let Some(attrs) = nixpkgs_attrs(name) {
+                packages.extend(attrs.iter().map(ToString::to_string));
+            } else {
+                unmapped.push(unmapped_dependency(name.to_string()));
+            }
+        };
+
+        for name in deps.packages.values().flatten() {
+            map(name, UnmappedDependency::Package);
+        }
+        for name in &toolchains.toolchains {
+            map(name, UnmappedDependency::Toolchain);
+        }
+
+        packages.sort();
+        packages.dedup();
+        unmapped.sort();
+        unmapped.dedup();
+        unmapped.extend(
+            deps.other_commands
+                .iter()
+                .cloned()
+                .map(UnmappedDependency::Command),
+        );
+
+        Self {
+            packages,
+            unmapped,
+            env: toolchains.env.clone(),
+            nightly_packages: toolchains.nightly_packages.clone(),
+        }
+    }
+}
+
+/// The built-in `nixpkgs` attributes for a system package or toolchain name, if known.
+#[must_use]
+pub fn nixpkgs_attrs(name: &str) -> Option<&'static [&'static str]> {
+    NIXPKGS_ATTRS
+        .iter()
+        .find(|(x, _)| *x == name)
+        .map(|(_, attrs)| *attrs)
+}
+
+/// Parses `name=attr1,attr2` override entries into an override table.
+///
+/// An entry with nothing after the `=` maps the name to no packages.
+///
+/// # Errors
+///
+/// * If an entry has no `=` or an empty name
+pub fn parse_nix_overrides(entries: &[String]) -> Result<BTreeMap<String, Vec<String>>, BoxError> {
+    entries
+        .iter()
+        .map(|entry| {
+            let (name, attrs) = entry
+                .split_once('=')
+                .filter(|(name, _)| !name.trim().is_empty())
+                .ok_or_else(|| format!("Invalid nixpkgs override '{entry}', expected name=attr"))?;
+
+            let attrs = attrs
+                .split(',')
+                .map(str::trim)
+                .filter(|x| !x.is_empty())
+                .map(str::to_string)
+                .collect();
+
+            Ok((name.trim().to_string(), attrs))
+        })
+        .collect()
+}
+
+/// Escapes `value` for use inside a double-quoted Nix string
+fn nix_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace("${", "\\${")
+        .replace('\n', "\\n");
+
+    format!("\"{escaped}\"")
+}
+
+/// Writes the `mkShell` call, each line indented by `indent`
+fn write_mk_shell(output: &mut String, shell: &NixShell, indent: &str) {
+    let _ = writeln!(output, "pkgs.mkShell {{");
+    let _ = writeln!(output, "{indent}  packages = with pkgs; [");
+    for package in &shell.packages {
+        let _ = writeln!(output, "{indent}    {package}");
+    }
+    let _ = writeln!(output, "{indent}  ];");
+
+    if !shell.unmapped.is_empty() {
+        let _ = writeln!(output);
+        for dependency in &shell.unmapped {
+            let todo = match dependency {
+                UnmappedDependency::Package(name) => {
+                    format!("no nixpkgs mapping for system package `{name}`")
+                }
+                UnmappedDependency::Toolchain(name) => {
+                    format!("no nixpkgs mapping for toolchain `{name}`")
+                }
+                UnmappedDependency::Command(command) => {
+                    format!(
+                        "translate dependency command `{}`",
+                        command.replace('\n', " ")
+                    )
+                }
+            };
+            let _ = writeln!(output, "{indent}  # TODO: {todo}");
+        }
+    }
+
+    if !shell.nightly_packages.is_empty() {
+        let _ = writeln!(output);
+        let _ = writeln!(
+            output,
+            "{indent}  # Nightly Rust is required by: {}",
+            shell.nightly_packages.join(", ")
+        );
+    }
+
+    if !shell.env.is_empty() {
+        let _ = writeln!(output);
+        for (key, value) in &shell.env {
+            let _ = writeln!(
+                output,
+                "{indent}  {} = {};",
+                nix_string(key),
+                nix_string(value)
+            );
+        }
+    }
+
+    let _ = write!(output, "{indent}}}");
+}
+
+/// Renders `shell` as a Nix expression in `format`.
+#[must_use]
+pub fn format_nix_shell(shell: &NixShell, format: NixShellFormat) -> String {
+    let mut output = String::new();
+    output.push_str("# Generated by clippier from the workspace's clippier.toml files\n");
+
+    match format {
+        NixShellFormat::Shell => {
+            output.push_str("{ pkgs ? import <nixpkgs> { } }:\n\n");
+            write_mk_shell(&mut output, shell, "");
+            output.push('\n');
+        }
+        NixShellFormat::Flake => {
+            output.push_str(
+                "{\n  inputs = {\n    nixpkgs.url = \"github:NixOS/nixpkgs/nixos-unstable\";\n    \
+                 flake-utils.url = \"github:numtide/flake-utils\";\n  };\n\n  \
+                 outputs =\n    { nixpkgs, flake-utils, ... }:\n    \
+                 flake-utils.lib.eachDefaultSystem (\n      system:\n      let\n        \
+                 pkgs = import nixpkgs { inherit system; };\n      in\n      {\n        \
+                 devShells.default = ",
+            );
+            write_mk_shell(&mut output, shell, "        ");
+            output.push_str(";\n      }\n    );\n}\n");
+        }
+    }
+
+    output
+}
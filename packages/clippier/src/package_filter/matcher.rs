@@ -50,7 +50,7 @@ pub fn evaluate_expression(
 /// * Returns error if value cannot be parsed for length comparisons
 pub fn matches(filter: &PackageFilter, cargo_toml: &Value) -> Result<bool, FilterError> {
     // Navigate to the nested property
-    let property_value = navigate_to_property(cargo_toml, &filter.property_path);
+    let property_value = resolve_property(cargo_toml, &filter.property_path);
 
     match filter.operator {
         FilterOperator::Equals => Ok(match_equals(property_value, &filter.value)),
@@ -108,6 +108,21 @@ fn navigate_to_property<'a>(toml: &'a Value, path: &[String]) -> Option<&'a Valu
     Some(current)
 }
 
+/// Resolve a property path, treating `metadata.*` as shorthand for `package.metadata.*`.
+///
+/// The full path is tried first, so a top-level `metadata` table still takes precedence.
+#[must_use]
+fn resolve_property<'a>(toml: &'a Value, path: &[String]) -> Option<&'a Value> {
+    navigate_to_property(toml, path).or_else(|| {
+        if path.first().is_some_and(|x| x == "metadata") {
+            toml.get("package")
+                .and_then(|package| navigate_to_property(package, path))
+        } else {
+            None
+        }
+    })
+}
+
 // Scalar matchers
 
 #[must_use]
@@ -347,4 +362,15 @@ mod tests {
                 .unwrap();
         assert!(matches(&filter, &value).unwrap());
     }
+
+    #[test]
+    fn test_metadata_shorthand_resolves_package_metadata() {
+        let toml = r"
+            [package.metadata.clippier]
+            tier = 'core'
+        ";
+        let value: Value = toml::from_str(toml).unwrap();
+        let filter = super::super::parser::parse_filter("metadata.clippier.tier=core").unwrap();
+        assert!(matches(&filter, &value).unwrap());
+    }
 }
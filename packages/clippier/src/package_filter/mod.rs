@@ -106,7 +106,7 @@
 //!
 //! Match against string, boolean, or integer values:
 //!
-//! * `=` - Exact match: `package.publish=false`
+//! * `=` - Exact match: `package.publish=false` (`==` is accepted as an alias)
 //! * `!=` - Not equal: `package.version!=0.1.0`
 //! * `^=` - Starts with: `package.version^=0.1`
 //! * `$=` - Ends with: `package.name$=_example`
@@ -141,6 +141,12 @@
 //! * `package.metadata.workspaces.independent=true`
 //! * `package.metadata.ci.skip-tests=true`
 //! * `package.metadata.custom.field=value`
+//!
+//! Paths starting with `metadata.` are shorthand for `package.metadata.`, so custom
+//! `[package.metadata.clippier]` keys can be matched directly:
+//!
+//! * `metadata.clippier.tier=core`
+//! * `metadata.clippier.tier == core AND NOT metadata.clippier.experimental=true`
 
 mod expression_parser;
 mod matcher;
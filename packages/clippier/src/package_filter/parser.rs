@@ -99,6 +99,7 @@ fn split_on_operator(filter: &str, op: &str) -> Option<(String, String)> {
 /// * `"package.version^=0.1"` - Check if version starts with "0.1"
 /// * `"package.categories@=audio"` - Check if categories array contains "audio"
 /// * `"package.metadata.workspaces.independent=true"` - Nested property check
+/// * `"metadata.clippier.tier == core"` - `==` is accepted as an alias for `=`
 /// * `"package.readme?"` - Check if readme property exists
 ///
 /// # Errors
@@ -125,6 +126,7 @@ pub fn parse_filter(filter: &str) -> Result<PackageFilter, FilterError> {
         ("$=", FilterOperator::EndsWith),
         ("*=", FilterOperator::Contains),
         ("!=", FilterOperator::NotEquals),
+        ("==", FilterOperator::Equals),
         ("=", FilterOperator::Equals),
         ("?", FilterOperator::Exists),
     ];
@@ -240,6 +242,14 @@ mod tests {
         assert_eq!(filter.value, "");
     }
 
+    #[test]
+    fn test_parse_double_equals_alias() {
+        let filter = parse_filter("metadata.clippier.tier == core").unwrap();
+        assert_eq!(filter.property_path, vec!["metadata", "clippier", "tier"]);
+        assert_eq!(filter.operator, FilterOperator::Equals);
+        assert_eq!(filter.value, "core");
+    }
+
     #[test]
     fn test_parse_invalid_empty_property() {
         let result = parse_filter("=value");
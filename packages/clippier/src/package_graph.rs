@@ -0,0 +1,229 @@
+//! Structured export of the workspace dependency graph.
+//!
+//! Nodes are the workspace members and edges the dependencies between them, classified
+//! the same way as for dependency resolution: a dependency is an edge when it references
+//! `[workspace.dependencies]` (`workspace = true`) or points at a member with `path`.
+//! External dependencies are left out.
+//!
+//! Only the top-level `dependencies`, `dev-dependencies` and `build-dependencies` sections
+//! are considered, not target-specific ones.
+
+use std::path::Path;
+
+use serde::Serialize;
+use toml::Value;
+
+use crate::{
+    BoxError, DependencyKind, WorkspaceContext, iterate_dependencies,
+    unused_features::{dependency_package_name, load_members, string_array},
+};
+
+/// Section of a `Cargo.toml` a dependency is declared in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyEdgeKind {
+    /// `[dependencies]`
+    Normal,
+    /// `[dev-dependencies]`
+    Dev,
+    /// `[build-dependencies]`
+    Build,
+}
+
+impl DependencyEdgeKind {
+    fn from_section(section: &str) -> Option<Self> {
+        match section {
+            "dependencies" => Some(Self::Normal),
+            "dev-dependencies" => Some(Self::Dev),
+            "build-dependencies" => Some(Self::Build),
+            _ => None,
+        }
+    }
+}
+
+/// A workspace member
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PackageNode {
+    /// Name of the package
+    pub name: String,
+    /// Directory of the package, relative to the workspace root
+    pub path: String,
+    /// Features the package defines, in `[features]`
+    pub features: Vec<String>,
+}
+
+/// A dependency of one workspace member on another
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct DependencyEdge {
+    /// Name of the dependent package
+    pub from: String,
+    /// Name of the package depended on
+    pub to: String,
+    /// Section the dependency is declared in
+    pub kind: DependencyEdgeKind,
+    /// Whether the dependency is optional, i.e. only enabled by features of `from`
+    pub optional: bool,
+    /// Features of `from` that enable the dependency (`dep:name`, `name/feature` or the
+    /// implicit `name` feature). Empty for non-optional dependencies.
+    pub activating_features: Vec<String>,
+}
+
+/// The dependency graph of a workspace
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageGraph {
+    /// Workspace members, sorted by name
+    pub nodes: Vec<PackageNode>,
+    /// Dependencies between workspace members, sorted by dependent, dependency and kind
+    pub edges: Vec<DependencyEdge>,
+}
+
+/// Features of `cargo_toml` that directly enable the optional dependency keyed `key`
+fn activating_features(cargo_toml: &Value, key: &str) -> Vec<String> {
+    let Some(features) = cargo_toml.get("features").and_then(Value::as_table) else {
+        return vec![];
+    };
+
+    let activates = |entry: &str| {
+        entry == key
+            || entry.strip_prefix("dep:") == Some(key)
+            || entry
+                .split_once('/')
+                .is_some_and(|(dependency, _)| dependency == key)
+    };
+
+    features
+        .iter()
+        .filter(|(_, entries)| string_array(Some(*entries)).any(|entry| activates(&entry)))
+        .map(|(feature, _)| feature.clone())
+        .collect()
+}
+
+/// Builds the dependency graph of the workspace at `workspace_root`.
+///
+/// # Errors
+///
+/// * If the workspace `Cargo.toml` cannot be read or parsed
+/// * If any member `Cargo.toml` cannot be read or parsed
+pub fn build_package_graph(workspace_root: &Path) -> Result<PackageGraph, BoxError> {
+    let workspace_source = switchy_fs::sync::read_to_string(workspace_root.join("Cargo.toml"))?;
+    let workspace_toml: Value = toml::from_str(&workspace_source)?;
+
+    let workspace_dependencies = workspace_toml
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(Value::as_table);
+
+    let context = WorkspaceContext::new(workspace_root)?;
+    let members = load_members(workspace_root, &workspace_toml)?;
+
+    let mut nodes = members
+        .iter()
+        .map(|member| PackageNode {
+            name: member.name.clone(),
+            path: member
+                .path
+                .strip_prefix(workspace_root)
+                .unwrap_or(&member.path)
+                .to_string_lossy()
+                .replace('\\', "/"),
+            features: member
+                .cargo_toml
+                .get("features")
+                .and_then(Value::as_table)
+                .map(|features| features.keys().cloned().collect())
+                .unwrap_or_default(),
+        })
+        .collect::<Vec<_>>();
+    nodes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut edges = vec![];
+
+    for member in &members {
+        for dependency in iterate_dependencies(&member.cargo_toml, &context, &member.path) {
+            let Some(kind) = DependencyEdgeKind::from_section(dependency.section) else {
+                continue;
+            };
+            let Some(value) = member
+                .cargo_toml
+                .get(dependency.section)
+                .and_then(|x| x.get(dependency.name))
+            else {
+                continue;
+            };
+
+            let to = match dependency.kind {
+                DependencyKind::WorkspaceReference => dependency_package_name(
+                    dependency.name,
+                    value,
+                    workspace_dependencies.and_then(|x| x.get(dependency.name)),
+                ),
+                DependencyKind::WorkspaceMember => {
+                    dependency_package_name(dependency.name, value, None)
+                }
+                DependencyKind::External => continue,
+            };
+
+            // `workspace = true` also covers external dependencies declared in the workspace
+            if !nodes.iter().any(|node| node.name == to) {
+                continue;
+            }
+
+            edges.push(DependencyEdge {
+                from: member.name.clone(),
+                to,
+                kind,
+                optional: dependency.is_optional,
+                activating_features: if dependency.is_optional {
+                    activating_features(&member.cargo_toml, dependency.name)
+                } else {
+                    vec![]
+                },
+            });
+        }
+    }
+    edges.sort();
+
+    log::debug!(
+        "Package graph has {} nodes and {} edges",
+        nodes.len(),
+        edges.len()
+    );
+
+    Ok(PackageGraph { nodes, edges })
+}
+
+/// Formats a package graph as one line per dependency
+#[must_use]
+pub fn format_package_graph(graph: &PackageGraph) -> String {
+    use std::fmt::Write as _;
+
+    let mut output = String::new();
+
+    let _ = writeln!(
+        output,
+        "📦 {} packages, {} dependencies",
+        graph.nodes.len(),
+        graph.edges.len()
+    );
+
+    for edge in &graph.edges {
+        let kind = match edge.kind {
+            DependencyEdgeKind::Normal => "",
+            DependencyEdgeKind::Dev => " (dev)",
+            DependencyEdgeKind::Build => " (build)",
+        };
+        let _ = write!(output, "  {} → {}{kind}", edge.from, edge.to);
+
+        if edge.optional {
+            let _ = write!(
+                output,
+                " [optional, enabled by: {}]",
+                edge.activating_features.join(", ")
+            );
+        }
+
+        let _ = writeln!(output);
+    }
+
+    output
+}
@@ -0,0 +1,216 @@
+//! Workspace-wide system dependency aggregation.
+//!
+//! [`collect_system_dependencies`](crate::collect_system_dependencies) returns the
+//! dependency commands of a single package. This module instead scans every
+//! `clippier.toml` in the workspace, extracts the packages their `apt-get`, `brew` and
+//! `vcpkg` install commands install for an OS, and merges them into one deduplicated
+//! install command per package manager, for CI jobs that install everything up front.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+use serde::Serialize;
+use toml::Value;
+
+use crate::{
+    BoxError, ClippierConfiguration, Step, WorkspaceClippierConf, expand_workspace_member_globs,
+    load_clippier_conf,
+};
+
+/// Package manager that system dependencies are installed with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PackageManager {
+    /// `apt-get` / `apt` (Debian, Ubuntu)
+    Apt,
+    /// Homebrew (macOS)
+    Brew,
+    /// vcpkg (Windows)
+    Vcpkg,
+}
+
+impl PackageManager {
+    /// Command that installs `packages` with this package manager
+    #[must_use]
+    pub fn install_command<'a>(self, packages: impl IntoIterator<Item = &'a str>) -> String {
+        let packages = packages.into_iter().collect::<Vec<_>>().join(" ");
+
+        match self {
+            Self::Apt => format!("sudo apt-get update && sudo apt-get install -y {packages}"),
+            Self::Brew => format!("brew install {packages}"),
+            Self::Vcpkg => format!("vcpkg install {packages}"),
+        }
+    }
+}
+
+/// Deduplicated system dependencies of a whole workspace for one OS
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WorkspaceSystemDeps {
+    /// Packages to install, by package manager
+    pub packages: BTreeMap<PackageManager, BTreeSet<String>>,
+    /// One install command per package manager, installing all of its packages
+    pub install_commands: Vec<String>,
+    /// Dependency commands that do more than install packages (e.g. downloads), kept
+    /// verbatim
+    pub other_commands: Vec<String>,
+}
+
+/// A shell command of a dependency step, split on `&&`, `;` and newlines
+enum CommandPart {
+    /// Installs packages
+    Install(PackageManager, Vec<String>),
+    /// Refreshes a package index, which the merged install command does itself
+    Update,
+    /// Anything else
+    Other,
+}
+
+fn parse_command_part(part: &str) -> CommandPart {
+    let mut tokens = part
+        .split_whitespace()
+        .skip_while(|x| *x == "sudo" || x.contains('='))
+        .peekable();
+
+    let manager = match tokens.next() {
+        Some("apt-get" | "apt") => PackageManager::Apt,
+        Some("brew") => PackageManager::Brew,
+        Some("vcpkg") => PackageManager::Vcpkg,
+        _ => return CommandPart::Other,
+    };
+
+    while tokens.next_if(|x| x.starts_with('-')).is_some() {}
+
+    match tokens.next() {
+        Some("install") => {
+            let packages = tokens
+                .filter(|x| !x.starts_with('-'))
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+
+            if packages.is_empty() {
+                CommandPart::Other
+            } else {
+                CommandPart::Install(manager, packages)
+            }
+        }
+        Some("update") if tokens.peek().is_none() => CommandPart::Update,
+        _ => CommandPart::Other,
+    }
+}
+
+/// Parses the packages installed by a dependency `command`.
+///
+/// Returns `None` if any part of the command does something other than installing
+/// packages or refreshing a package index.
+#[must_use]
+pub fn parse_install_command(command: &str) -> Option<Vec<(PackageManager, String)>> {
+    let command = command.replace("\\\n", " ");
+    let mut packages = vec![];
+
+    for part in command
+        .split(['\n', ';'])
+        .flat_map(|x| x.split("&&"))
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+    {
+        match parse_command_part(part) {
+            CommandPart::Install(manager, names) => {
+                packages.extend(names.into_iter().map(|name| (manager, name)));
+            }
+            CommandPart::Update => {}
+            CommandPart::Other => return None,
+        }
+    }
+
+    Some(packages)
+}
+
+impl WorkspaceSystemDeps {
+    fn add_steps<'a>(&mut self, steps: impl IntoIterator<Item = &'a Step>) {
+        for command in steps.into_iter().filter_map(|x| x.command.as_deref()) {
+            if let Some(packages) = parse_install_command(command) {
+                for (manager, name) in packages {
+                    self.packages.entry(manager).or_default().insert(name);
+                }
+            } else if !self.other_commands.iter().any(|x| x == command) {
+                self.other_commands.push(command.to_string());
+            }
+        }
+    }
+
+    fn add_configs(&mut self, configs: Option<&[ClippierConfiguration]>, os: &str) {
+        for config in configs.into_iter().flatten().filter(|x| x.os == os) {
+            self.add_steps(config.dependencies.iter().flatten());
+        }
+    }
+}
+
+/// Collects the system dependencies of every `clippier.toml` in the workspace for `os`.
+///
+/// Reads the root `clippier.toml` and those of all workspace members. Dependencies are
+/// included regardless of the features they are gated on, since the result is meant to
+/// set up every build.
+///
+/// # Errors
+///
+/// * If the workspace Cargo.toml or root clippier.toml cannot be read or parsed
+pub fn collect_workspace_system_deps(
+    workspace_root: &Path,
+    os: &str,
+) -> Result<WorkspaceSystemDeps, BoxError> {
+    let mut deps = WorkspaceSystemDeps::default();
+
+    let workspace_clippier_path = workspace_root.join("clippier.toml");
+    if switchy_fs::exists(&workspace_clippier_path) {
+        let conf: WorkspaceClippierConf =
+            toml::from_str(&switchy_fs::sync::read_to_string(&workspace_clippier_path)?)?;
+
+        deps.add_steps(conf.dependencies.iter().flatten());
+        deps.add_configs(conf.config.as_deref(), os);
+    }
+
+    let cargo_path = workspace_root.join("Cargo.toml");
+    if switchy_fs::exists(&cargo_path) {
+        let workspace_toml: Value =
+            toml::from_str(&switchy_fs::sync::read_to_string(&cargo_path)?)?;
+        let member_patterns: Vec<&str> = workspace_toml
+            .get("workspace")
+            .and_then(|w| w.get("members"))
+            .and_then(Value::as_array)
+            .map(|a| a.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        for member in expand_workspace_member_globs(workspace_root, &member_patterns) {
+            let clippier_path = workspace_root.join(&member).join("clippier.toml");
+            if !switchy_fs::exists(&clippier_path) {
+                continue;
+            }
+
+            match load_clippier_conf(&clippier_path) {
+                Ok(conf) => deps.add_configs(conf.config.as_deref(), os),
+                Err(e) => log::warn!("Failed to load {}: {e}", clippier_path.display()),
+            }
+        }
+    }
+
+    deps.install_commands = deps
+        .packages
+        .iter()
+        .map(|(manager, packages)| manager.install_command(packages.iter().map(String::as_str)))
+        .collect();
+
+    Ok(deps)
+}
+
+/// Formats the system dependencies as a shell script: the install commands followed by the
+/// other dependency commands, one per line
+#[must_use]
+pub fn format_workspace_system_deps(deps: &WorkspaceSystemDeps) -> String {
+    deps.install_commands
+        .iter()
+        .chain(&deps.other_commands)
+        .map(|x| format!("{x}\n"))
+        .collect()
+}
@@ -0,0 +1,635 @@
+//! Target triple and `cfg(...)` expression evaluation.
+//!
+//! `Cargo.toml` files can scope dependencies to a target with either a literal triple
+//! (`[target.x86_64-pc-windows-msvc.dependencies]`) or a cfg expression
+//! (`[target.'cfg(target_os = "linux")'.dependencies]`). This module derives the cfg
+//! values of a target triple (`target_arch`, `target_os`, `target_family`, etc.) and
+//! evaluates those keys against it, so the features matrix and affected-package analysis
+//! can ignore dependencies and features that are inactive for the target.
+//!
+//! Cfg names that are not derived from the triple (`debug_assertions`, `feature = "..."`,
+//! custom `--cfg` flags, ...) evaluate to `false`, mirroring how cargo evaluates target
+//! tables without extra `RUSTFLAGS`.
+
+use std::{collections::BTreeSet, fmt, str::FromStr};
+
+use toml::Value;
+
+const DEPENDENCY_SECTIONS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+const UNIX_OSES: [&str; 13] = [
+    "linux",
+    "android",
+    "macos",
+    "ios",
+    "tvos",
+    "watchos",
+    "freebsd",
+    "netbsd",
+    "openbsd",
+    "dragonfly",
+    "solaris",
+    "illumos",
+    "fuchsia",
+];
+
+const KNOWN_OSES: [&str; 18] = [
+    "linux",
+    "android",
+    "darwin",
+    "macos",
+    "ios",
+    "tvos",
+    "watchos",
+    "freebsd",
+    "netbsd",
+    "openbsd",
+    "dragonfly",
+    "solaris",
+    "illumos",
+    "fuchsia",
+    "windows",
+    "wasi",
+    "emscripten",
+    "none",
+];
+
+/// Errors from parsing target triples and cfg expressions
+#[derive(Debug, thiserror::Error)]
+pub enum TargetCfgError {
+    /// The target triple is malformed
+    #[error("Invalid target triple '{0}'")]
+    InvalidTriple(String),
+    /// The cfg expression is malformed
+    #[error("Invalid cfg expression '{expression}': {reason}")]
+    InvalidExpression {
+        /// The expression that failed to parse
+        expression: String,
+        /// What was wrong with it
+        reason: String,
+    },
+}
+
+/// The cfg values of a target triple
+///
+/// Parsed from a triple such as `x86_64-unknown-linux-gnu` or `aarch64-apple-darwin`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetTriple {
+    triple: String,
+    arch: String,
+    vendor: String,
+    os: String,
+    env: String,
+    families: Vec<&'static str>,
+    pointer_width: &'static str,
+    endian: &'static str,
+}
+
+impl TargetTriple {
+    /// The triple this target was parsed from
+    #[must_use]
+    pub fn triple(&self) -> &str {
+        &self.triple
+    }
+
+    /// The `target_arch` value (e.g. `x86_64`, `aarch64`, `arm`, `wasm32`)
+    #[must_use]
+    pub fn arch(&self) -> &str {
+        &self.arch
+    }
+
+    /// The `target_vendor` value (e.g. `unknown`, `apple`, `pc`)
+    #[must_use]
+    pub fn vendor(&self) -> &str {
+        &self.vendor
+    }
+
+    /// The `target_os` value (e.g. `linux`, `macos`, `windows`)
+    #[must_use]
+    pub fn os(&self) -> &str {
+        &self.os
+    }
+
+    /// The `target_env` value (e.g. `gnu`, `musl`, `msvc`), empty if the triple has none
+    #[must_use]
+    pub fn env(&self) -> &str {
+        &self.env
+    }
+
+    /// The `target_family` values (`unix`, `windows` and/or `wasm`)
+    #[must_use]
+    pub fn families(&self) -> &[&'static str] {
+        &self.families
+    }
+
+    /// Whether the target sets the cfg `key = "value"`
+    fn matches_key_value(&self, key: &str, value: &str) -> bool {
+        match key {
+            "target_arch" => self.arch == value,
+            "target_vendor" => self.vendor == value,
+            "target_os" => self.os == value,
+            "target_env" => self.env == value,
+            "target_family" => self.families.contains(&value),
+            "target_pointer_width" => self.pointer_width == value,
+            "target_endian" => self.endian == value,
+            _ => false,
+        }
+    }
+
+    /// Whether the target sets the bare cfg `name` (`unix` or `windows`)
+    fn matches_name(&self, name: &str) -> bool {
+        matches!(name, "unix" | "windows") && self.families.contains(&name)
+    }
+}
+
+impl FromStr for TargetTriple {
+    type Err = TargetCfgError;
+
+    fn from_str(triple: &str) -> Result<Self, Self::Err> {
+        let invalid = || TargetCfgError::InvalidTriple(triple.to_string());
+
+        let mut parts = triple.split('-');
+        let raw_arch = parts.next().filter(|x| !x.is_empty()).ok_or_else(invalid)?;
+        let rest = parts.collect::<Vec<_>>();
+
+        if rest.iter().any(|x| x.is_empty()) {
+            return Err(invalid());
+        }
+
+        let (vendor, os, env) = match rest.as_slice() {
+            [os] => ("unknown", *os, String::new()),
+            [os, env] if KNOWN_OSES.contains(os) => ("unknown", *os, (*env).to_string()),
+            [vendor, os] => (*vendor, *os, String::new()),
+            [vendor, os, env @ ..] => (*vendor, *os, env.join("-")),
+            [] => return Err(invalid()),
+        };
+
+        let (os, env) = match (os, env.as_str()) {
+            ("linux", "android" | "androideabi") => ("android".to_string(), String::new()),
+            ("darwin", _) => ("macos".to_string(), env),
+            (os, _) if os.starts_with("wasip") => ("wasi".to_string(), env),
+            (os, _) => (os.to_string(), env),
+        };
+
+        let arch = normalize_arch(raw_arch);
+
+        let mut families = vec![];
+        if os == "windows" {
+            families.push("windows");
+        } else if UNIX_OSES.contains(&os.as_str()) || os == "emscripten" {
+            families.push("unix");
+        }
+        if arch.starts_with("wasm") {
+            families.push("wasm");
+        }
+
+        let pointer_width = if arch.ends_with("64") || arch == "s390x" {
+            "64"
+        } else if arch == "msp430" || arch == "avr" {
+            "16"
+        } else {
+            "32"
+        };
+
+        let big_endian = arch == "s390x"
+            || arch.starts_with("sparc")
+            || (arch.starts_with("powerpc") && !raw_arch.ends_with("le"))
+            || (arch.starts_with("mips") && !raw_arch.contains("el"));
+
+        Ok(Self {
+            triple: triple.to_string(),
+            arch,
+            vendor: vendor.to_string(),
+            os,
+            env,
+            families,
+            pointer_width,
+            endian: if big_endian { "big" } else { "little" },
+        })
+    }
+}
+
+impl fmt::Display for TargetTriple {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.triple)
+    }
+}
+
+/// Maps the architecture component of a triple to its `target_arch` value
+fn normalize_arch(arch: &str) -> String {
+    match arch {
+        "i386" | "i586" | "i686" => "x86".to_string(),
+        "arm64" => "aarch64".to_string(),
+        "powerpc64le" => "powerpc64".to_string(),
+        "mipsel" => "mips".to_string(),
+        "mips64el" => "mips64".to_string(),
+        x if x.starts_with("arm") || x.starts_with("thumb") => "arm".to_string(),
+        x if x.starts_with("riscv64") => "riscv64".to_string(),
+        x if x.starts_with("riscv32") => "riscv32".to_string(),
+        x => x.to_string(),
+    }
+}
+
+/// A parsed `cfg(...)` predicate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CfgExpr {
+    /// A bare name, e.g. `unix`
+    Name(String),
+    /// A key-value pair, e.g. `target_os = "linux"`
+    KeyValue(String, String),
+    /// `all(...)`, true when every predicate is true (including when empty)
+    All(Vec<Self>),
+    /// `any(...)`, true when any predicate is true (false when empty)
+    Any(Vec<Self>),
+    /// `not(...)`
+    Not(Box<Self>),
+}
+
+impl CfgExpr {
+    /// Parses a predicate, with or without the surrounding `cfg(...)`
+    ///
+    /// # Errors
+    ///
+    /// * If the expression is malformed
+    pub fn parse(expression: &str) -> Result<Self, TargetCfgError> {
+        let trimmed = expression.trim();
+        let inner = trimmed
+            .strip_prefix("cfg")
+            .map(str::trim_start)
+            .and_then(|x| x.strip_prefix('('))
+            .and_then(|x| x.strip_suffix(')'))
+            .unwrap_or(trimmed);
+
+        let error = |reason: &str| TargetCfgError::InvalidExpression {
+            expression: expression.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let tokens = tokenize(inner).map_err(|x| error(&x))?;
+        let mut parser = CfgParser {
+            tokens,
+            position: 0,
+        };
+        let expr = parser.expr().map_err(|x| error(&x))?;
+
+        if parser.position != parser.tokens.len() {
+            return Err(error("unexpected trailing input"));
+        }
+
+        Ok(expr)
+    }
+
+    /// Evaluates the predicate against `target`
+    #[must_use]
+    pub fn eval(&self, target: &TargetTriple) -> bool {
+        match self {
+            Self::Name(name) => target.matches_name(name),
+            Self::KeyValue(key, value) => target.matches_key_value(key, value),
+            Self::All(exprs) => exprs.iter().all(|x| x.eval(target)),
+            Self::Any(exprs) => exprs.iter().any(|x| x.eval(target)),
+            Self::Not(expr) => !expr.eval(target),
+        }
+    }
+}
+
+impl FromStr for CfgExpr {
+    type Err = TargetCfgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    OpenParen,
+    CloseParen,
+    Comma,
+    Equals,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | ',' | '=' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => Token::OpenParen,
+                    ')' => Token::CloseParen,
+                    ',' => Token::Comma,
+                    _ => Token::Equals,
+                });
+            }
+            '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => value.push(c),
+                        None => return Err("unterminated string".to_string()),
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    ident.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            c => return Err(format!("unexpected character '{c}'")),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct CfgParser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl CfgParser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn expr(&mut self) -> Result<CfgExpr, String> {
+        let Some(Token::Ident(name)) = self.next() else {
+            return Err("expected a cfg name".to_string());
+        };
+
+        match self.peek() {
+            Some(Token::Equals) => {
+                self.position += 1;
+                let Some(Token::Str(value)) = self.next() else {
+                    return Err(format!("expected a string value for '{name}'"));
+                };
+                Ok(CfgExpr::KeyValue(name, value))
+            }
+            Some(Token::OpenParen) => {
+                self.position += 1;
+                let exprs = self.list()?;
+                match name.as_str() {
+                    "all" => Ok(CfgExpr::All(exprs)),
+                    "any" => Ok(CfgExpr::Any(exprs)),
+                    "not" => {
+                        let [expr] = <[CfgExpr; 1]>::try_from(exprs)
+                            .map_err(|_| "not() takes exactly one predicate".to_string())?;
+                        Ok(CfgExpr::Not(Box::new(expr)))
+                    }
+                    _ => Err(format!("unknown cfg operator '{name}'")),
+                }
+            }
+            _ => Ok(CfgExpr::Name(name)),
+        }
+    }
+
+    /// Parses a comma-separated predicate list up to and including the closing paren
+    fn list(&mut self) -> Result<Vec<CfgExpr>, String> {
+        let mut exprs = vec![];
+
+        loop {
+            if self.peek() == Some(&Token::CloseParen) {
+                self.position += 1;
+                return Ok(exprs);
+            }
+
+            exprs.push(self.expr()?);
+
+            match self.next() {
+                Some(Token::Comma) => {}
+                Some(Token::CloseParen) => return Ok(exprs),
+                _ => return Err("expected ',' or ')'".to_string()),
+            }
+        }
+    }
+}
+
+/// Checks whether a `[target.<key>]` table applies to `target`
+///
+/// `key` is either a `cfg(...)` expression or a literal target triple.
+///
+/// # Errors
+///
+/// * If `key` is a malformed cfg expression
+pub fn target_key_matches(key: &str, target: &TargetTriple) -> Result<bool, TargetCfgError> {
+    if key.trim_start().starts_with("cfg") {
+        Ok(CfgExpr::parse(key)?.eval(target))
+    } else {
+        Ok(key == target.triple())
+    }
+}
+
+/// Returns a copy of `cargo_toml` with everything inactive for `target` removed
+///
+/// Drops `[target.<key>]` tables that do not apply to `target`, then drops features
+/// whose every entry enables a dependency that was only declared in those tables (or
+/// another feature dropped this way). Features with no entries are always kept.
+/// Target keys that fail to parse are kept and logged.
+#[must_use]
+pub fn filter_manifest_for_target(cargo_toml: &Value, target: &TargetTriple) -> Value {
+    let mut filtered = cargo_toml.clone();
+
+    let Some(targets) = filtered.get_mut("target").and_then(Value::as_table_mut) else {
+        return filtered;
+    };
+
+    let mut removed_dependencies = BTreeSet::new();
+
+    targets.retain(|key, table| {
+        let matches = target_key_matches(key, target).unwrap_or_else(|e| {
+            log::warn!("Keeping target table '{key}': {e}");
+            true
+        });
+
+        if !matches {
+            log::debug!("Dropping target table '{key}' for target {target}");
+            removed_dependencies.extend(dependency_names(table));
+        }
+
+        matches
+    });
+
+    let retained_dependencies = std::iter::once(&filtered)
+        .chain(
+            filtered
+                .get("target")
+                .and_then(Value::as_table)
+                .into_iter()
+                .flat_map(|x| x.values()),
+        )
+        .flat_map(dependency_names)
+        .collect::<BTreeSet<_>>();
+
+    removed_dependencies.retain(|x| !retained_dependencies.contains(x));
+
+    if removed_dependencies.is_empty() {
+        return filtered;
+    }
+
+    let Some(features) = filtered.get_mut("features").and_then(Value::as_table_mut) else {
+        return filtered;
+    };
+
+    let mut removed_features = BTreeSet::new();
+
+    loop {
+        let newly_removed = features
+            .iter()
+            .filter(|(name, _)| !removed_features.contains(*name))
+            .filter(|(_, entries)| {
+                let entries = entries.as_array().map_or(&[][..], Vec::as_slice);
+                !entries.is_empty()
+                    && entries.iter().filter_map(Value::as_str).all(|entry| {
+                        let (name, explicit_dependency) = feature_entry_target(entry);
+                        removed_dependencies.contains(name)
+                            || (!explicit_dependency && removed_features.contains(name))
+                    })
+            })
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+
+        if newly_removed.is_empty() {
+            break;
+        }
+
+        removed_features.extend(newly_removed);
+    }
+
+    for feature in &removed_features {
+        log::debug!("Dropping feature '{feature}' for target {target}");
+        features.remove(feature);
+    }
+
+    filtered
+}
+
+/// Returns the dependency or feature a `[features]` entry refers to, and whether it
+/// can only refer to a dependency (`dep:name`, `name/feature` and `name?/feature`)
+fn feature_entry_target(entry: &str) -> (&str, bool) {
+    if let Some(dependency) = entry.strip_prefix("dep:") {
+        return (dependency, true);
+    }
+
+    entry
+        .split_once('/')
+        .map_or((entry, false), |(dependency, _)| {
+            (dependency.trim_end_matches('?'), true)
+        })
+}
+
+/// Names of the dependencies declared directly in `table`'s dependency sections
+fn dependency_names(table: &Value) -> impl Iterator<Item = String> + '_ {
+    DEPENDENCY_SECTIONS
+        .into_iter()
+        .filter_map(|section| table.get(section).and_then(Value::as_table))
+        .flat_map(|x| x.keys().cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(triple: &str) -> TargetTriple {
+        triple.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_common_triples() {
+        let linux = target("x86_64-unknown-linux-gnu");
+        assert_eq!(linux.arch(), "x86_64");
+        assert_eq!(linux.vendor(), "unknown");
+        assert_eq!(linux.os(), "linux");
+        assert_eq!(linux.env(), "gnu");
+        assert_eq!(linux.families(), &["unix"]);
+
+        let macos = target("aarch64-apple-darwin");
+        assert_eq!(macos.os(), "macos");
+        assert_eq!(macos.env(), "");
+
+        let windows = target("i686-pc-windows-msvc");
+        assert_eq!(windows.arch(), "x86");
+        assert_eq!(windows.families(), &["windows"]);
+
+        let android = target("armv7-linux-androideabi");
+        assert_eq!(android.arch(), "arm");
+        assert_eq!(android.os(), "android");
+
+        let wasm = target("wasm32-unknown-unknown");
+        assert_eq!(wasm.os(), "unknown");
+        assert_eq!(wasm.families(), &["wasm"]);
+
+        assert!("".parse::<TargetTriple>().is_err());
+        assert!("x86_64--linux".parse::<TargetTriple>().is_err());
+    }
+
+    #[test]
+    fn evaluates_cfg_expressions() {
+        let linux = target("x86_64-unknown-linux-gnu");
+        let windows = target("x86_64-pc-windows-msvc");
+
+        for (expression, on_linux, on_windows) in [
+            (r#"cfg(target_os = "linux")"#, true, false),
+            ("cfg(unix)", true, false),
+            ("cfg(windows)", false, true),
+            (r#"cfg(target_family = "windows")"#, false, true),
+            (r#"cfg(not(target_os = "linux"))"#, false, true),
+            (
+                r#"cfg(all(unix, target_arch = "x86_64", target_pointer_width = "64"))"#,
+                true,
+                false,
+            ),
+            (r#"cfg(any(target_os = "macos", windows))"#, false, true),
+            ("cfg(all())", true, true),
+            ("cfg(any())", false, false),
+            ("cfg(debug_assertions)", false, false),
+        ] {
+            let expr = CfgExpr::parse(expression).unwrap();
+            assert_eq!(expr.eval(&linux), on_linux, "{expression}");
+            assert_eq!(expr.eval(&windows), on_windows, "{expression}");
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_cfg_expressions() {
+        for expression in [
+            "cfg(",
+            r#"cfg(target_os = linux)"#,
+            r#"cfg(target_os = "linux)"#,
+            "cfg(not(unix, windows))",
+            "cfg(maybe(unix))",
+            "cfg(unix windows)",
+        ] {
+            assert!(CfgExpr::parse(expression).is_err(), "{expression}");
+        }
+    }
+
+    #[test]
+    fn matches_literal_triple_keys() {
+        let linux = target("x86_64-unknown-linux-gnu");
+
+        assert!(target_key_matches("x86_64-unknown-linux-gnu", &linux).unwrap());
+        assert!(!target_key_matches("x86_64-unknown-linux-musl", &linux).unwrap());
+    }
+}
@@ -0,0 +1,375 @@
+//! Detection of feature flags that are never referenced.
+//!
+//! A feature counts as referenced when any of the following names it:
+//!
+//! * Another feature of the same package (`feature = ["other"]`)
+//! * A workspace member depending on the package (`features = ["feature"]` on the
+//!   dependency, or `pkg/feature` and `pkg?/feature` in its `[features]` table)
+//! * The package's `clippier.toml` (step and environment feature filters, `skip-features`,
+//!   etc.)
+//! * The package's target `required-features` or `[package.metadata]` feature lists
+//!
+//! `default` is never reported since it is the entry point cargo enables itself. Features
+//! that are only reachable through a feature that is itself unreferenced are not reported
+//! either, so pruning may uncover more on the next run.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use toml::Value;
+
+use crate::{BoxError, expand_workspace_member_globs, load_clippier_toml};
+
+const DEPENDENCY_SECTIONS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+const TARGET_SECTIONS: [&str; 5] = ["lib", "bin", "example", "test", "bench"];
+
+/// A feature that is defined but never referenced
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct UnreferencedFeature {
+    /// Name of the package defining the feature
+    pub package: String,
+    /// Name of the feature
+    pub feature: String,
+    /// Whether the package is a library, in which case downstream crates outside the
+    /// workspace may still enable the feature
+    pub public_api: bool,
+}
+
+/// Result of auditing the workspace for unreferenced features
+#[derive(Debug, Clone, Serialize)]
+pub struct UnusedFeaturesReport {
+    /// Number of features checked across all packages
+    pub features_checked: usize,
+    /// Features that are defined but never referenced, sorted by package and feature
+    pub unreferenced: Vec<UnreferencedFeature>,
+}
+
+impl UnusedFeaturesReport {
+    /// Whether every feature is referenced somewhere
+    #[must_use]
+    pub const fn is_clean(&self) -> bool {
+        self.unreferenced.is_empty()
+    }
+}
+
+/// A workspace member's parsed manifest
+pub(crate) struct Member {
+    pub(crate) name: String,
+    pub(crate) path: PathBuf,
+    pub(crate) cargo_toml: Value,
+}
+
+/// Finds features defined by workspace members that are never referenced.
+///
+/// Features listed in `ignore_features` are never reported.
+///
+/// # Errors
+///
+/// * If the workspace `Cargo.toml` cannot be read or parsed
+/// * If any member `Cargo.toml` cannot be read or parsed
+/// * If any member `clippier.toml` cannot be loaded
+pub fn find_unreferenced_features(
+    workspace_root: &Path,
+    ignore_features: &[String],
+) -> Result<UnusedFeaturesReport, BoxError> {
+    let workspace_source = switchy_fs::sync::read_to_string(workspace_root.join("Cargo.toml"))?;
+    let workspace_toml: Value = toml::from_str(&workspace_source)?;
+
+    let workspace_dependencies = workspace_toml
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(Value::as_table);
+
+    let members = load_members(workspace_root, &workspace_toml)?;
+    let member_names: BTreeSet<&str> = members.iter().map(|m| m.name.as_str()).collect();
+
+    let mut references: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    if let Some(dependencies) = workspace_dependencies {
+        for (key, value) in dependencies {
+            let name = dependency_package_name(key, value, None);
+            if member_names.contains(name.as_str()) {
+                references
+                    .entry(name)
+                    .or_default()
+                    .extend(string_array(value.get("features")));
+            }
+        }
+    }
+
+    for member in &members {
+        collect_member_references(
+            member,
+            workspace_dependencies,
+            &member_names,
+            &mut references,
+        )?;
+    }
+
+    let mut features_checked = 0;
+    let mut unreferenced = vec![];
+
+    for member in &members {
+        let Some(features) = member.cargo_toml.get("features").and_then(Value::as_table) else {
+            continue;
+        };
+        let referenced = references.get(&member.name);
+        let public_api = is_library(member);
+
+        for feature in features.keys() {
+            features_checked += 1;
+
+            if feature == "default"
+                || ignore_features.contains(feature)
+                || referenced.is_some_and(|x| x.contains(feature))
+            {
+                continue;
+            }
+
+            log::debug!("{}: feature '{feature}' is never referenced", member.name);
+
+            unreferenced.push(UnreferencedFeature {
+                package: member.name.clone(),
+                feature: feature.clone(),
+                public_api,
+            });
+        }
+    }
+
+    unreferenced.sort();
+
+    Ok(UnusedFeaturesReport {
+        features_checked,
+        unreferenced,
+    })
+}
+
+pub(crate) fn load_members(
+    workspace_root: &Path,
+    workspace_toml: &Value,
+) -> Result<Vec<Member>, BoxError> {
+    let member_patterns: Vec<&str> = workspace_toml
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(Value::as_array)
+        .map(|a| a.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    let mut members = vec![];
+
+    for member_path in expand_workspace_member_globs(workspace_root, &member_patterns) {
+        let path = workspace_root.join(&member_path);
+        let cargo_path = path.join("Cargo.toml");
+
+        if !switchy_fs::exists(&cargo_path) {
+            log::debug!("Skipping workspace member without Cargo.toml: {member_path}");
+            continue;
+        }
+
+        let source = switchy_fs::sync::read_to_string(&cargo_path)?;
+        let cargo_toml: Value = toml::from_str(&source)?;
+
+        if let Some(name) = cargo_toml
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(Value::as_str)
+        {
+            members.push(Member {
+                name: name.to_string(),
+                path,
+                cargo_toml,
+            });
+        }
+    }
+
+    Ok(members)
+}
+
+/// Records the features `member` references, both on itself and on the workspace members
+/// it depends on.
+fn collect_member_references(
+    member: &Member,
+    workspace_dependencies: Option<&toml::map::Map<String, Value>>,
+    member_names: &BTreeSet<&str>,
+    references: &mut BTreeMap<String, BTreeSet<String>>,
+) -> Result<(), BoxError> {
+    let cargo_toml = &member.cargo_toml;
+    // Maps dependency keys (which may be renamed) to workspace member names
+    let mut dependency_names: BTreeMap<&str, String> = BTreeMap::new();
+
+    let targets = cargo_toml
+        .get("target")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values());
+
+    for table in std::iter::once(cargo_toml).chain(targets) {
+        for section in DEPENDENCY_SECTIONS {
+            let Some(dependencies) = table.get(section).and_then(Value::as_table) else {
+                continue;
+            };
+
+            for (key, value) in dependencies {
+                let workspace_value =
+                    if value.get("workspace").and_then(Value::as_bool) == Some(true) {
+                        workspace_dependencies.and_then(|deps| deps.get(key))
+                    } else {
+                        None
+                    };
+
+                let name = dependency_package_name(key, value, workspace_value);
+                if !member_names.contains(name.as_str()) {
+                    continue;
+                }
+
+                references
+                    .entry(name.clone())
+                    .or_default()
+                    .extend(string_array(value.get("features")));
+                dependency_names.insert(key.as_str(), name);
+            }
+        }
+    }
+
+    let own = references.entry(member.name.clone()).or_default();
+
+    let feature_entries = cargo_toml
+        .get("features")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flat_map(|features| features.values())
+        .flat_map(|x| string_array(Some(x)));
+
+    let mut dependency_references = vec![];
+
+    for entry in feature_entries {
+        if entry.starts_with("dep:") {
+            continue;
+        }
+
+        match entry.split_once('/') {
+            Some((dependency, feature)) => {
+                if let Some(name) = dependency_names.get(dependency.trim_end_matches('?')) {
+                    dependency_references.push((name.clone(), feature.to_string()));
+                }
+            }
+            None => {
+                own.insert(entry);
+            }
+        }
+    }
+
+    for section in TARGET_SECTIONS {
+        if let Some(targets) = cargo_toml.get(section) {
+            collect_feature_lists(targets, own);
+        }
+    }
+
+    if let Some(metadata) = cargo_toml.get("package").and_then(|p| p.get("metadata")) {
+        collect_feature_lists(metadata, own);
+    }
+
+    let clippier_toml = member.path.join("clippier.toml");
+    if switchy_fs::exists(&clippier_toml) {
+        collect_feature_lists(&load_clippier_toml(&clippier_toml)?, own);
+    }
+
+    for (name, feature) in dependency_references {
+        references.entry(name).or_default().insert(feature);
+    }
+
+    Ok(())
+}
+
+/// Resolves the package name of a dependency, which may be renamed with `package`.
+pub(crate) fn dependency_package_name(
+    key: &str,
+    value: &Value,
+    workspace_value: Option<&Value>,
+) -> String {
+    value
+        .get("package")
+        .or_else(|| workspace_value.and_then(|v| v.get("package")))
+        .and_then(Value::as_str)
+        .unwrap_or(key)
+        .to_string()
+}
+
+/// Collects every string in arrays stored under keys ending in `features` (`features`,
+/// `required-features`, `skip-features`, etc.), at any depth.
+fn collect_feature_lists(value: &Value, out: &mut BTreeSet<String>) {
+    match value {
+        Value::Table(table) => {
+            for (key, value) in table {
+                if key.ends_with("features") && value.is_array() {
+                    out.extend(string_array(Some(value)));
+                } else {
+                    collect_feature_lists(value, out);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                collect_feature_lists(value, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn string_array(value: Option<&Value>) -> impl Iterator<Item = String> + '_ {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(ToString::to_string)
+}
+
+/// Whether the member builds a library that crates outside the workspace could depend on.
+fn is_library(member: &Member) -> bool {
+    member.cargo_toml.get("lib").is_some() || switchy_fs::exists(member.path.join("src/lib.rs"))
+}
+
+/// Formats an unused features report for human consumption
+#[must_use]
+pub fn format_unused_features_report(report: &UnusedFeaturesReport) -> String {
+    use std::fmt::Write as _;
+
+    let mut output = String::new();
+
+    let _ = writeln!(
+        output,
+        "Checked {} features for references",
+        report.features_checked
+    );
+
+    if report.is_clean() {
+        let _ = writeln!(output, "✅ All features are referenced");
+        return output;
+    }
+
+    let _ = writeln!(
+        output,
+        "⚠️  Found {} unreferenced features:",
+        report.unreferenced.len()
+    );
+
+    for entry in &report.unreferenced {
+        if entry.public_api {
+            let _ = writeln!(
+                output,
+                "  📦 {}: {} (library feature, may be enabled by downstream crates)",
+                entry.package, entry.feature
+            );
+        } else {
+            let _ = writeln!(output, "  📦 {}: {}", entry.package, entry.feature);
+        }
+    }
+
+    output
+}
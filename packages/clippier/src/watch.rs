@@ -0,0 +1,285 @@
+//! Regenerating command output when workspace files change.
+//!
+//! [`watch`] runs a command once, then reruns it each time a [`WorkspaceWatcher`] reports
+//! a change to the workspace's manifests. Changes arriving in quick succession, like an
+//! editor saving several files at once, are debounced into a single regeneration.
+//!
+//! [`PollingWatcher`] watches the root and member `Cargo.toml`s and `clippier.toml`s (see
+//! [`watched_files`]) by periodically comparing their modification times, which works the
+//! same on every platform and filesystem.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    future::Future,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use async_trait::async_trait;
+use toml::Value;
+
+use crate::{
+    BoxError, expand_workspace_member_globs, resolve_members_file, workspace_member_patterns,
+};
+
+/// Default time to wait for further changes before regenerating.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Default interval between two checks of a [`PollingWatcher`].
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Files modified more recently than this are compared by contents too, as filesystems with
+/// coarse timestamps can give a file the same modification time before and after an edit.
+const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+/// Source of workspace file change notifications for [`watch`]
+#[async_trait]
+pub trait WorkspaceWatcher: Send {
+    /// Waits for watched files to change and returns the changed paths, or `None` once the
+    /// watcher stops reporting changes
+    ///
+    /// # Errors
+    ///
+    /// * If checking the watched files fails
+    async fn changed(&mut self) -> Result<Option<BTreeSet<PathBuf>>, BoxError>;
+}
+
+/// [`WorkspaceWatcher`] that checks the [`watched_files`] of a workspace for changes at a
+/// fixed interval
+///
+/// The watched files are listed again on every check, so members added to the workspace
+/// are picked up. A file that is created or deleted, or whose modification time changes,
+/// counts as changed. Only files modified within the last couple of seconds are read, to
+/// also notice edits that didn't move a coarse modification time.
+pub struct PollingWatcher {
+    workspace_root: PathBuf,
+    interval: Duration,
+    snapshot: BTreeMap<PathBuf, Option<FileState>>,
+}
+
+impl PollingWatcher {
+    /// Creates a watcher of the workspace at `workspace_root`, comparing against the
+    /// current state of its files
+    #[must_use]
+    pub fn new(workspace_root: &Path, interval: Duration) -> Self {
+        Self {
+            workspace_root: workspace_root.to_path_buf(),
+            interval,
+            snapshot: read_snapshot(workspace_root),
+        }
+    }
+}
+
+#[async_trait]
+impl WorkspaceWatcher for PollingWatcher {
+    async fn changed(&mut self) -> Result<Option<BTreeSet<PathBuf>>, BoxError> {
+        loop {
+            switchy_async::time::sleep(self.interval).await;
+
+            let snapshot = read_snapshot(&self.workspace_root);
+            let changed = snapshot
+                .iter()
+                .filter(|(path, state)| {
+                    self.snapshot.get(*path).is_none_or(|previous| {
+                        FileState::changed(previous.as_ref(), state.as_ref())
+                    })
+                })
+                .map(|(path, _)| path.clone())
+                .chain(
+                    self.snapshot
+                        .keys()
+                        .filter(|path| !snapshot.contains_key(*path))
+                        .cloned(),
+                )
+                .collect::<BTreeSet<_>>();
+            self.snapshot = snapshot;
+
+            if !changed.is_empty() {
+                log::debug!("PollingWatcher: changed files: {changed:?}");
+                return Ok(Some(changed));
+            }
+        }
+    }
+}
+
+/// State of a watched file at the time of a check
+#[derive(Debug, PartialEq, Eq)]
+struct FileState {
+    /// `None` if the filesystem doesn't report modification times
+    modified: Option<SystemTime>,
+    /// Only read if the file was modified within [`RACY_WINDOW`] of the check, or its
+    /// modification time is unknown
+    contents: Option<Vec<u8>>,
+}
+
+impl FileState {
+    /// Reads the state of the file at `path` at time `now`, `None` if it's missing
+    fn read(path: &Path, now: SystemTime) -> Option<Self> {
+        let modified = std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        let racy = modified.is_none_or(|modified| {
+            !now.duration_since(modified)
+                .is_ok_and(|age| age >= RACY_WINDOW)
+        });
+        let contents = if racy {
+            Some(switchy_fs::sync::read(path).ok()?)
+        } else {
+            None
+        };
+
+        Some(Self { modified, contents })
+    }
+
+    /// Whether the file changed between two checks
+    fn changed(previous: Option<&Self>, current: Option<&Self>) -> bool {
+        match (previous, current) {
+            (None, None) => false,
+            (Some(previous), Some(current)) => {
+                previous.modified != current.modified
+                    || previous.contents.as_ref().is_some_and(|previous| {
+                        current.contents.as_ref().is_some_and(|x| x != previous)
+                    })
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Reads the state of the watched files of the workspace, `None` for missing ones
+fn read_snapshot(workspace_root: &Path) -> BTreeMap<PathBuf, Option<FileState>> {
+    let now = SystemTime::now();
+
+    watched_files(workspace_root)
+        .into_iter()
+        .map(|path| {
+            let state = FileState::read(&path, now);
+            (path, state)
+        })
+        .collect()
+}
+
+/// Lists the files whose changes regenerate the output in watch mode: the root
+/// `Cargo.toml` and `clippier.toml`, and the `Cargo.toml` and `clippier.toml` of every
+/// workspace member
+///
+/// Files are listed whether they exist or not, so creating one is noticed too. Members
+/// are read from the root `Cargo.toml`; if it can't be read or parsed, only the root files
+/// are listed.
+#[must_use]
+pub fn watched_files(workspace_root: &Path) -> Vec<PathBuf> {
+    let members = switchy_fs::sync::read_to_string(workspace_root.join("Cargo.toml"))
+        .ok()
+        .and_then(|source| toml::from_str::<Value>(&source).ok())
+        .and_then(|value| {
+            let members_file = resolve_members_file(workspace_root, None).ok()?;
+            workspace_member_patterns(workspace_root, &value, members_file.as_deref())
+                .ok()
+                .flatten()
+        })
+        .map(|patterns| expand_workspace_member_globs(workspace_root, &patterns))
+        .unwrap_or_default();
+
+    std::iter::once(workspace_root.to_path_buf())
+        .chain(members.iter().map(|member| workspace_root.join(member)))
+        .flat_map(|dir| [dir.join("Cargo.toml"), dir.join("clippier.toml")])
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Writes the output of `generate`, then regenerates and writes it again each time
+/// `watcher` reports a change, until the watcher stops
+///
+/// After a change, regeneration waits until no further change arrives for `debounce`, so
+/// a burst of changes regenerates the output once. Failing to generate the output is
+/// reported on stderr and doesn't stop watching, as the workspace is often briefly
+/// invalid while being edited.
+///
+/// # Errors
+///
+/// * If the watcher fails to check for changes
+/// * If `emit` fails to write the output
+#[allow(clippy::future_not_send)]
+pub async fn watch<W, G, F, E>(
+    watcher: &mut W,
+    debounce: Duration,
+    mut generate: G,
+    mut emit: E,
+) -> Result<(), BoxError>
+where
+    W: WorkspaceWatcher + ?Sized,
+    G: FnMut() -> F,
+    F: Future<Output = Result<String, BoxError>>,
+    E: FnMut(&str) -> Result<(), BoxError>,
+{
+    regenerate(&mut generate, &mut emit).await?;
+
+    while let Some(mut changed) = watcher.changed().await? {
+        // Wait for the burst of changes to settle
+        let stopped = loop {
+            match switchy_async::time::timeout(debounce, watcher.changed()).await {
+                Ok(Ok(Some(more))) => changed.extend(more),
+                Ok(Ok(None)) => break true,
+                Ok(Err(e)) => return Err(e),
+                Err(_elapsed) => break false,
+            }
+        };
+
+        log::info!("Regenerating after changes to {changed:?}");
+        regenerate(&mut generate, &mut emit).await?;
+
+        if stopped {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates the output and writes it with `emit`, reporting generation failures on stderr
+#[allow(clippy::future_not_send)]
+async fn regenerate<G, F, E>(generate: &mut G, emit: &mut E) -> Result<(), BoxError>
+where
+    G: FnMut() -> F,
+    F: Future<Output = Result<String, BoxError>>,
+    E: FnMut(&str) -> Result<(), BoxError>,
+{
+    match generate().await {
+        Ok(output) => emit(&output),
+        Err(e) => {
+            eprintln!("Failed to generate output: {e}");
+            Ok(())
+        }
+    }
+}
+
+/// Where [`watch`] writes the regenerated output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchOutput {
+    /// Print each regenerated output to stdout
+    Stdout,
+    /// Rewrite the file with each regenerated output
+    File(PathBuf),
+}
+
+impl WatchOutput {
+    /// Writes `output`
+    ///
+    /// # Errors
+    ///
+    /// * If the output file can't be written
+    pub fn write(&self, output: &str) -> Result<(), BoxError> {
+        match self {
+            Self::Stdout => {
+                println!("{output}");
+            }
+            Self::File(path) => {
+                switchy_fs::sync::write(path, output)?;
+                eprintln!("Wrote {}", path.display());
+            }
+        }
+
+        Ok(())
+    }
+}
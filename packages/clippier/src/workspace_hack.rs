@@ -0,0 +1,348 @@
+//! Workspace-hack feature completeness checking.
+//!
+//! In large workspaces a dedicated "workspace-hack" package (as popularized by
+//! `cargo hakari`) depends on every external dependency with the union of the features
+//! enabled anywhere in the workspace. This keeps feature unification identical no matter
+//! which subset of packages is built, avoiding rebuild churn.
+//!
+//! This module computes that union per external dependency across all workspace members
+//! and compares it against what the designated workspace-hack package enables.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+use itertools::Itertools;
+use serde::Serialize;
+use toml::Value;
+
+use crate::{BoxError, expand_workspace_member_globs};
+
+const DEPENDENCY_SECTIONS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// Features of an external dependency that the workspace-hack package doesn't enable
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MissingHackEntry {
+    /// Name of the external dependency
+    pub dependency: String,
+    /// Whether the workspace-hack package doesn't depend on the dependency at all
+    pub missing_dependency: bool,
+    /// Features enabled by workspace members but not by the workspace-hack package
+    pub missing_features: BTreeSet<String>,
+    /// Workspace members that enable the missing features
+    pub required_by: BTreeSet<String>,
+}
+
+/// Result of comparing the workspace feature union against the workspace-hack package
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceHackReport {
+    /// Name of the workspace-hack package
+    pub hack_package: String,
+    /// Union of features enabled per external dependency across workspace members
+    pub unified_features: BTreeMap<String, BTreeSet<String>>,
+    /// Features enabled per external dependency by the workspace-hack package
+    pub hack_features: BTreeMap<String, BTreeSet<String>>,
+    /// Entries missing from the workspace-hack package
+    pub missing: Vec<MissingHackEntry>,
+}
+
+impl WorkspaceHackReport {
+    /// Whether the workspace-hack package enables every unified feature
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// A workspace member's parsed manifest
+struct Member {
+    name: String,
+    cargo_toml: Value,
+}
+
+/// Checks that the workspace-hack package enables the union of external dependency
+/// features used across the workspace.
+///
+/// The `default` feature is counted as enabled for a dependency unless it is declared
+/// with `default-features = false`. Features enabled through a member's `[features]`
+/// table (`dep/feature` and `dep?/feature`) are included in the union.
+///
+/// # Errors
+///
+/// * If the workspace `Cargo.toml` cannot be read or parsed
+/// * If any member `Cargo.toml` cannot be read or parsed
+/// * If `hack_package` is not a member of the workspace
+pub fn check_workspace_hack(
+    workspace_root: &Path,
+    hack_package: &str,
+) -> Result<WorkspaceHackReport, BoxError> {
+    let workspace_source = switchy_fs::sync::read_to_string(workspace_root.join("Cargo.toml"))?;
+    let workspace_toml: Value = toml::from_str(&workspace_source)?;
+
+    let workspace_dependencies = workspace_toml
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(Value::as_table);
+
+    let members = load_members(workspace_root, &workspace_toml)?;
+    let member_names: BTreeSet<&str> = members.iter().map(|m| m.name.as_str()).collect();
+
+    let hack = members
+        .iter()
+        .find(|m| m.name == hack_package)
+        .ok_or_else(|| {
+            format!("Workspace-hack package '{hack_package}' is not a workspace member")
+        })?;
+
+    let mut unified_features: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut enabled_by: BTreeMap<(String, String), BTreeSet<String>> = BTreeMap::new();
+
+    for member in members.iter().filter(|m| m.name != hack_package) {
+        let features =
+            external_dependency_features(&member.cargo_toml, workspace_dependencies, &member_names);
+
+        for (dependency, features) in features {
+            for feature in &features {
+                enabled_by
+                    .entry((dependency.clone(), feature.clone()))
+                    .or_default()
+                    .insert(member.name.clone());
+            }
+            unified_features
+                .entry(dependency)
+                .or_default()
+                .extend(features);
+        }
+    }
+
+    let hack_features =
+        external_dependency_features(&hack.cargo_toml, workspace_dependencies, &member_names);
+
+    let missing = unified_features
+        .iter()
+        .filter_map(|(dependency, features)| {
+            let hack_enabled = hack_features.get(dependency);
+            let missing_features: BTreeSet<String> = features
+                .iter()
+                .filter(|f| !hack_enabled.is_some_and(|enabled| enabled.contains(*f)))
+                .cloned()
+                .collect();
+
+            if hack_enabled.is_some() && missing_features.is_empty() {
+                return None;
+            }
+
+            let required_by = missing_features
+                .iter()
+                .filter_map(|f| enabled_by.get(&(dependency.clone(), f.clone())))
+                .flatten()
+                .cloned()
+                .collect();
+
+            Some(MissingHackEntry {
+                dependency: dependency.clone(),
+                missing_dependency: hack_enabled.is_none(),
+                missing_features,
+                required_by,
+            })
+        })
+        .collect();
+
+    Ok(WorkspaceHackReport {
+        hack_package: hack_package.to_string(),
+        unified_features,
+        hack_features,
+        missing,
+    })
+}
+
+fn load_members(workspace_root: &Path, workspace_toml: &Value) -> Result<Vec<Member>, BoxError> {
+    let member_patterns: Vec<&str> = workspace_toml
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(Value::as_array)
+        .map(|a| a.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    let mut members = vec![];
+
+    for member_path in expand_workspace_member_globs(workspace_root, &member_patterns) {
+        let cargo_path = workspace_root.join(&member_path).join("Cargo.toml");
+
+        if !switchy_fs::exists(&cargo_path) {
+            log::debug!("Skipping workspace member without Cargo.toml: {member_path}");
+            continue;
+        }
+
+        let source = switchy_fs::sync::read_to_string(&cargo_path)?;
+        let cargo_toml: Value = toml::from_str(&source)?;
+
+        if let Some(name) = cargo_toml
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(Value::as_str)
+        {
+            members.push(Member {
+                name: name.to_string(),
+                cargo_toml,
+            });
+        }
+    }
+
+    Ok(members)
+}
+
+/// Collects the features a package enables on each of its external dependencies, keyed
+/// by the dependency's package name.
+fn external_dependency_features(
+    cargo_toml: &Value,
+    workspace_dependencies: Option<&toml::map::Map<String, Value>>,
+    member_names: &BTreeSet<&str>,
+) -> BTreeMap<String, BTreeSet<String>> {
+    let mut result: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    // Maps dependency keys (which may be renamed) to package names
+    let mut dependency_names: BTreeMap<&str, String> = BTreeMap::new();
+
+    let targets = cargo_toml
+        .get("target")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values());
+
+    for table in std::iter::once(cargo_toml).chain(targets) {
+        for section in DEPENDENCY_SECTIONS {
+            let Some(dependencies) = table.get(section).and_then(Value::as_table) else {
+                continue;
+            };
+
+            for (key, value) in dependencies {
+                let workspace_value =
+                    if value.get("workspace").and_then(Value::as_bool) == Some(true) {
+                        workspace_dependencies.and_then(|deps| deps.get(key))
+                    } else {
+                        None
+                    };
+
+                if value.get("path").is_some()
+                    || workspace_value.is_some_and(|v| v.get("path").is_some())
+                {
+                    continue;
+                }
+
+                let name = value
+                    .get("package")
+                    .or_else(|| workspace_value.and_then(|v| v.get("package")))
+                    .and_then(Value::as_str)
+                    .unwrap_or(key)
+                    .to_string();
+
+                if member_names.contains(name.as_str()) {
+                    continue;
+                }
+
+                let features = result.entry(name.clone()).or_default();
+
+                // `default-features` is taken from the workspace entry for inherited dependencies
+                let default_features = workspace_value
+                    .or(Some(value))
+                    .and_then(|v| v.get("default-features"))
+                    .and_then(Value::as_bool)
+                    .unwrap_or(true);
+                if default_features {
+                    features.insert("default".to_string());
+                }
+
+                for source in [Some(value), workspace_value].into_iter().flatten() {
+                    features.extend(
+                        source
+                            .get("features")
+                            .and_then(Value::as_array)
+                            .into_iter()
+                            .flatten()
+                            .filter_map(Value::as_str)
+                            .map(ToString::to_string),
+                    );
+                }
+
+                dependency_names.insert(key.as_str(), name);
+            }
+        }
+    }
+
+    // Features enabled through the package's own features, e.g. `std = ["serde/std"]`
+    let feature_entries = cargo_toml
+        .get("features")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flat_map(|features| features.values())
+        .filter_map(Value::as_array)
+        .flatten()
+        .filter_map(Value::as_str);
+
+    for entry in feature_entries {
+        let Some((dependency, feature)) = entry.split_once('/') else {
+            continue;
+        };
+        let dependency = dependency.trim_end_matches('?');
+
+        if let Some(name) = dependency_names.get(dependency) {
+            result
+                .entry(name.clone())
+                .or_default()
+                .insert(feature.to_string());
+        }
+    }
+
+    result
+}
+
+/// Formats a workspace-hack report for human consumption
+#[must_use]
+pub fn format_workspace_hack_report(report: &WorkspaceHackReport) -> String {
+    use std::fmt::Write as _;
+
+    let mut output = String::new();
+
+    let _ = writeln!(
+        output,
+        "Checked {} external dependencies against workspace-hack package '{}'",
+        report.unified_features.len(),
+        report.hack_package
+    );
+
+    if report.is_complete() {
+        let _ = writeln!(
+            output,
+            "✅ Workspace-hack package enables all unified features"
+        );
+        return output;
+    }
+
+    let _ = writeln!(
+        output,
+        "❌ Found {} dependencies with missing workspace-hack entries:",
+        report.missing.len()
+    );
+
+    for entry in &report.missing {
+        let features = entry.missing_features.iter().join(", ");
+        let required_by = entry.required_by.iter().join(", ");
+
+        if entry.missing_dependency {
+            let _ = writeln!(
+                output,
+                "  📦 {} (not a dependency): [{features}] required by {required_by}",
+                entry.dependency
+            );
+        } else {
+            let _ = writeln!(
+                output,
+                "  📦 {}: missing [{features}] required by {required_by}",
+                entry.dependency
+            );
+        }
+    }
+
+    output
+}
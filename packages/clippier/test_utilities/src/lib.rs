@@ -65,6 +65,27 @@ pub fn seed_clippier_test_resources() {
     seed_test_resources(clippier_dir.to_str().expect("Invalid path"));
 }
 
+/// Generates the feature matrix of the workspace at `path` as JSON.
+///
+/// The `file` and `output` of `options` are overridden, so tests only set the options they
+/// exercise.
+///
+/// # Errors
+///
+/// * If generating the feature matrix fails
+#[allow(clippy::future_not_send)]
+pub async fn features_matrix(
+    path: &Path,
+    options: clippier::FeaturesOptions,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    clippier::handle_features_command(&clippier::FeaturesOptions {
+        file: path.display().to_string(),
+        output: clippier::OutputType::Json,
+        ..options
+    })
+    .await
+}
+
 pub mod test_resources {
     use super::{Path, TempDir};
     use serde::{Deserialize, Serialize};
@@ -0,0 +1,169 @@
+//! Tests for `affects` mappings of non-package files.
+//!
+//! These tests verify that a changed file matching an `affects` glob of the root
+//! `clippier.toml` marks the mapped packages and their transitive dependents as affected,
+//! while files matching no mapping still affect nothing.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::{
+    AffectsMapping, find_affected_packages, find_affected_packages_with_reasoning,
+    load_affects_mappings,
+};
+
+const CLIPPIER_TOML: &str = r#"
+[[affects]]
+glob = "migrations/**"
+packages = ["schema"]
+
+[[affects]]
+glob = "proto/*.proto"
+packages = ["api", "missing"]
+"#;
+
+/// Creates a workspace where `server` depends on `api`, which depends on `schema`, and
+/// `cli` depends on nothing
+fn create_workspace(clippier_toml: &str) -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+
+    switchy_fs::sync::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"packages/*\"]\n",
+    )
+    .unwrap();
+    switchy_fs::sync::write(root.join("clippier.toml"), clippier_toml).unwrap();
+
+    for (name, dependencies) in [
+        ("schema", ""),
+        ("api", "schema = { path = \"../schema\" }\n"),
+        ("server", "api = { path = \"../api\" }\n"),
+        ("cli", ""),
+    ] {
+        let package_dir = root.join("packages").join(name);
+        switchy_fs::sync::create_dir_all(package_dir.join("src")).unwrap();
+        switchy_fs::sync::write(
+            package_dir.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n\n[dependencies]\n{dependencies}"
+            ),
+        )
+        .unwrap();
+        switchy_fs::sync::write(package_dir.join("src/lib.rs"), "").unwrap();
+    }
+
+    temp_dir
+}
+
+#[test]
+fn test_load_affects_mappings() {
+    let temp_dir = create_workspace(CLIPPIER_TOML);
+
+    let mappings = load_affects_mappings(temp_dir.path()).unwrap();
+
+    assert_eq!(
+        mappings,
+        vec![
+            AffectsMapping {
+                glob: "migrations/**".to_string(),
+                packages: vec!["schema".to_string()],
+            },
+            AffectsMapping {
+                glob: "proto/*.proto".to_string(),
+                packages: vec!["api".to_string(), "missing".to_string()],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_mapped_file_affects_package_and_transitive_dependents() {
+    let temp_dir = create_workspace(CLIPPIER_TOML);
+
+    let affected = find_affected_packages(
+        temp_dir.path(),
+        &["migrations/2024-01-01/up.sql".to_string()],
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(affected, vec!["api", "schema", "server"]);
+}
+
+#[test]
+fn test_mapping_skips_unknown_packages() {
+    let temp_dir = create_workspace(CLIPPIER_TOML);
+
+    let affected =
+        find_affected_packages(temp_dir.path(), &["proto/player.proto".to_string()], &[]).unwrap();
+
+    assert_eq!(affected, vec!["api", "server"]);
+}
+
+#[test]
+fn test_unmapped_non_package_file_affects_nothing() {
+    let temp_dir = create_workspace(CLIPPIER_TOML);
+
+    let affected = find_affected_packages(
+        temp_dir.path(),
+        &["docs/README.md".to_string(), "proto/README".to_string()],
+        &[],
+    )
+    .unwrap();
+
+    assert!(affected.is_empty(), "{affected:?}");
+}
+
+#[test]
+fn test_ignored_file_does_not_apply_mapping() {
+    let temp_dir = create_workspace(CLIPPIER_TOML);
+
+    let affected = find_affected_packages(
+        temp_dir.path(),
+        &["migrations/README.md".to_string()],
+        &["**/*.md".to_string()],
+    )
+    .unwrap();
+
+    assert!(affected.is_empty(), "{affected:?}");
+}
+
+#[test]
+fn test_mapped_file_reasoning() {
+    let temp_dir = create_workspace(CLIPPIER_TOML);
+
+    let affected = find_affected_packages_with_reasoning(
+        temp_dir.path(),
+        &["migrations/2024-01-01/up.sql".to_string()],
+        &[],
+    )
+    .unwrap();
+
+    let schema = affected.iter().find(|x| x.name == "schema").unwrap();
+    assert_eq!(
+        schema.reasoning,
+        Some(vec![
+            "Changed file migrations/2024-01-01/up.sql matches affects glob: migrations/**"
+                .to_string()
+        ])
+    );
+
+    let server = affected.iter().find(|x| x.name == "server").unwrap();
+    assert_eq!(
+        server.reasoning,
+        Some(vec!["Depends on affected package: api".to_string()])
+    );
+}
+
+#[test]
+fn test_invalid_affects_errors() {
+    let temp_dir = create_workspace("[[affects]]\nglob = \"migrations/**\"\n");
+
+    let error = find_affected_packages(temp_dir.path(), &["migrations/x.sql".to_string()], &[])
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.contains("Invalid `affects`"), "{error}");
+}
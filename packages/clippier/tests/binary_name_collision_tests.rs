@@ -0,0 +1,203 @@
+//! Tests for binary name collision detection.
+//!
+//! These tests verify that packages producing the same binary filename are reported and
+//! that Dockerfile generation refuses to copy an ambiguous binary.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use clippier::find_binary_name_collisions;
+
+/// Creates a workspace from `(path, Cargo.toml, has_main)` package definitions
+fn create_workspace(packages: &[(&str, &str, bool)]) -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+
+    let members = packages
+        .iter()
+        .map(|(path, _, _)| format!("\"{path}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    switchy_fs::sync::write(
+        root.join("Cargo.toml"),
+        format!("[workspace]\nmembers = [{members}]\n"),
+    )
+    .unwrap();
+
+    for (path, cargo_toml, has_main) in packages {
+        let package_dir = root.join(path);
+        switchy_fs::sync::create_dir_all(package_dir.join("src")).unwrap();
+        switchy_fs::sync::write(package_dir.join("Cargo.toml"), cargo_toml).unwrap();
+
+        let entry = if *has_main {
+            "src/main.rs"
+        } else {
+            "src/lib.rs"
+        };
+        switchy_fs::sync::write(package_dir.join(entry), "").unwrap();
+    }
+
+    temp_dir
+}
+
+fn collision(binary: &str, packages: &[&str]) -> (String, BTreeSet<String>) {
+    (
+        binary.to_string(),
+        packages.iter().map(ToString::to_string).collect(),
+    )
+}
+
+#[test]
+fn test_derived_binary_names_collide() {
+    let temp_dir = create_workspace(&[
+        (
+            "packages/my-app",
+            "[package]\nname = \"my-app\"\nversion = \"0.1.0\"\n",
+            true,
+        ),
+        (
+            "packages/my_app",
+            "[package]\nname = \"my_app\"\nversion = \"0.1.0\"\n",
+            true,
+        ),
+    ]);
+
+    let collisions = find_binary_name_collisions(temp_dir.path()).unwrap();
+
+    assert_eq!(
+        collisions,
+        BTreeMap::from([collision("my_app", &["my-app", "my_app"])])
+    );
+}
+
+#[test]
+fn test_explicit_binary_name_collides_with_derived_name() {
+    let temp_dir = create_workspace(&[
+        (
+            "packages/api",
+            "[package]\nname = \"api\"\nversion = \"0.1.0\"\n\n[[bin]]\nname = \"server\"\npath = \"src/main.rs\"\n",
+            true,
+        ),
+        (
+            "packages/server",
+            "[package]\nname = \"server\"\nversion = \"0.1.0\"\n",
+            true,
+        ),
+    ]);
+
+    let collisions = find_binary_name_collisions(temp_dir.path()).unwrap();
+
+    assert_eq!(
+        collisions,
+        BTreeMap::from([collision("server", &["api", "server"])])
+    );
+}
+
+#[test]
+fn test_library_packages_do_not_collide() {
+    let temp_dir = create_workspace(&[
+        (
+            "packages/my-lib",
+            "[package]\nname = \"my-lib\"\nversion = \"0.1.0\"\n",
+            false,
+        ),
+        (
+            "packages/my_lib",
+            "[package]\nname = \"my_lib\"\nversion = \"0.1.0\"\n",
+            true,
+        ),
+    ]);
+
+    assert!(
+        find_binary_name_collisions(temp_dir.path())
+            .unwrap()
+            .is_empty()
+    );
+}
+
+async fn generate_dockerfile(
+    temp_dir: &switchy_fs::TempDir,
+    target_package: &str,
+    bin: Option<&str>,
+) -> Result<(), String> {
+    clippier::generate_dockerfile(
+        temp_dir.path(),
+        target_package,
+        None,
+        false,
+        &temp_dir.path().join("Dockerfile"),
+        "rust:1-bookworm",
+        "debian:bookworm-slim",
+        &[],
+        None,
+        false,
+        &[],
+        &[],
+        bin,
+        clippier::DEFAULT_DOCKER_TARGET_OS,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+fn colliding_workspace() -> switchy_fs::TempDir {
+    create_workspace(&[
+        (
+            "packages/my-app",
+            "[package]\nname = \"my-app\"\nversion = \"0.1.0\"\n",
+            true,
+        ),
+        (
+            "packages/my_app",
+            "[package]\nname = \"my_app\"\nversion = \"0.1.0\"\n",
+            true,
+        ),
+        (
+            "packages/other",
+            "[package]\nname = \"other\"\nversion = \"0.1.0\"\n",
+            true,
+        ),
+    ])
+}
+
+#[switchy_async::test]
+async fn test_dockerfile_generation_fails_when_target_binary_collides() {
+    let temp_dir = colliding_workspace();
+
+    let error = generate_dockerfile(&temp_dir, "my-app", None)
+        .await
+        .unwrap_err();
+
+    assert!(error.contains("Binary name 'my_app'"), "{error}");
+    assert!(
+        error.contains("collides with package(s): my_app"),
+        "{error}"
+    );
+    assert!(!switchy_fs::exists(temp_dir.path().join("Dockerfile")));
+}
+
+#[switchy_async::test]
+async fn test_dockerfile_generation_ignores_collisions_of_other_packages() {
+    let temp_dir = colliding_workspace();
+
+    generate_dockerfile(&temp_dir, "other", None).await.unwrap();
+
+    assert!(switchy_fs::exists(temp_dir.path().join("Dockerfile")));
+}
+
+#[switchy_async::test]
+async fn test_dockerfile_generation_fails_when_bin_override_collides() {
+    let temp_dir = colliding_workspace();
+
+    let error = generate_dockerfile(&temp_dir, "other", Some("my_app"))
+        .await
+        .unwrap_err();
+
+    assert!(
+        error.contains("collides with package(s): my-app, my_app"),
+        "{error}"
+    );
+}
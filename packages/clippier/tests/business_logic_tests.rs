@@ -1,8 +1,9 @@
 #[cfg(feature = "cargo-workspace")]
 use clippier::handle_packages_command;
 use clippier::{
-    OutputType, handle_ci_steps_command, handle_dependencies_command, handle_environment_command,
-    handle_features_command, handle_workspace_deps_command, process_workspace_configs,
+    FeaturesOptions, OutputType, handle_ci_steps_command, handle_dependencies_command,
+    handle_environment_command, handle_features_command, handle_workspace_deps_command,
+    process_workspace_configs,
 };
 use clippier_test_utilities::TempDir;
 use clippier_test_utilities::test_resources::{create_simple_workspace, load_test_workspace};
@@ -241,37 +242,12 @@ async fn test_ci_steps_with_command_and_toolchain() {
     let (temp_dir, _) = load_test_workspace("ci-steps-regression");
 
     // Test with features command to get the full matrix output
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,  // offset
-        None,  // max
-        None,  // max_parallel
-        None,  // chunked
-        false, // spread
-        false, // randomize
-        None,  // seed
-        None,  // features
-        None,  // skip_features
-        None,  // required_features
-        None,  // packages
-        None,  // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false, // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -319,37 +295,13 @@ async fn test_ci_steps_mixed_entries() {
     let (temp_dir, _) = load_test_workspace("ci-steps-regression");
 
     // Test with features that match different ci-steps entries
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,             // offset
-        None,             // max
-        None,             // max_parallel
-        None,             // chunked
-        false,            // spread
-        false,            // randomize
-        None,             // seed
-        Some("frontend"), // features - this should match entries with frontend feature
-        None,             // skip_features
-        None,             // required_features
-        None,             // packages
-        None,             // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,            // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        features: Some("frontend".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -388,37 +340,13 @@ async fn test_ci_steps_with_features() {
     let (temp_dir, _) = load_test_workspace("ci-steps-regression");
 
     // Test with tauri features - should match entries with frontend and tauri features
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,          // offset
-        None,          // max
-        None,          // max_parallel
-        None,          // chunked
-        false,         // spread
-        false,         // randomize
-        None,          // seed
-        Some("tauri"), // features - includes frontend (from tauri feature)
-        None,          // skip_features
-        None,          // required_features
-        None,          // packages
-        None,          // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,         // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        features: Some("tauri".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -448,37 +376,12 @@ async fn test_ci_steps_with_features() {
 async fn test_ci_steps_json_output_structure() {
     let (temp_dir, _) = load_test_workspace("ci-steps-regression");
 
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,  // offset
-        None,  // max
-        None,  // max_parallel
-        None,  // chunked
-        false, // spread
-        false, // randomize
-        None,  // seed
-        None,  // features - no filter to get all ci-steps
-        None,  // skip_features
-        None,  // required_features
-        None,  // packages
-        None,  // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false, // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -551,37 +454,11 @@ async fn test_ci_steps_json_output_structure() {
 async fn test_handle_features_command_basic() {
     let (temp_dir, _) = load_test_workspace("complex");
 
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,  // offset
-        None,  // max
-        None,  // max_parallel
-        None,  // chunked
-        false, // spread
-        false, // randomize
-        None,  // seed
-        None,  // features
-        None,  // skip_features
-        None,  // required_features
-        None,  // packages
-        None,  // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false, // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Raw,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -608,19 +485,7 @@ async fn test_handle_workspace_deps_command_basic() {
 async fn test_process_workspace_configs_workspace_root() {
     let (temp_dir, _) = load_test_workspace("complex");
 
-    let result = process_workspace_configs(
-        temp_dir.path(),
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-    )
-    .await;
+    let result = process_workspace_configs(temp_dir.path(), &FeaturesOptions::default()).await;
 
     assert!(result.is_ok());
     let configs = result.unwrap();
@@ -744,37 +609,19 @@ dependencies = [
 async fn test_handle_features_command_comprehensive() {
     let (temp_dir, _) = load_test_workspace("complex");
 
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        Some(0),              // offset
-        Some(3),              // max
-        None,                 // max_parallel
-        Some(2),              // chunked
-        true,                 // spread
-        false,                // randomize
-        None,                 // seed
-        Some("frontend,api"), // features
-        Some("deprecated"),   // skip_features
-        Some("core"),         // required_features
-        None,                 // packages
-        None,                 // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,                // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        offset: Some(0),
+        max: Some(3),
+        chunked: Some(2),
+        spread: true,
+        features: Some("frontend,api".to_string()),
+        skip_features: Some("deprecated".to_string()),
+        required_features: Some("core".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -884,37 +731,14 @@ serde = "1.0"
     switchy_fs::sync::write(temp_dir.path().join("Cargo.toml"), workspace_toml).unwrap();
 
     // Test with both chunked and max_parallel
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,     // offset
-        None,     // max
-        Some(10), // max_parallel - should limit to 10 results
-        Some(3),  // chunked - should create more than 10 results without limit
-        false,    // spread
-        false,    // randomize
-        None,     // seed
-        None,     // features
-        None,     // skip_features
-        None,     // required_features
-        None,     // packages
-        None,     // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,    // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        max_parallel: Some(10),
+        chunked: Some(3),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -938,37 +762,13 @@ serde = "1.0"
     );
 
     // Test with only max_parallel (backward compatibility)
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,    // offset
-        None,    // max
-        Some(5), // max_parallel - should limit to 5 results and also serve as chunked
-        None,    // chunked - not provided
-        false,   // spread
-        false,   // randomize
-        None,    // seed
-        None,    // features
-        None,    // skip_features
-        None,    // required_features
-        None,    // packages
-        None,    // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,   // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        max_parallel: Some(5),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -1150,37 +950,11 @@ dependencies = [
 async fn test_handle_features_command_with_git_submodules() {
     let (temp_dir, _) = load_test_workspace("git-submodules");
 
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -1220,20 +994,9 @@ async fn test_git_submodules_inheritance() {
     let (temp_dir, _) = load_test_workspace("git-submodules");
     let inherited_pkg = temp_dir.path().join("packages/inherited-submodules");
 
-    let result = clippier::process_configs(
-        &inherited_pkg,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-    )
-    .await
-    .unwrap();
+    let result = clippier::process_configs(&inherited_pkg, &clippier::FeaturesOptions::default())
+        .await
+        .unwrap();
 
     for config in &result {
         let json = serde_json::to_value(config).unwrap();
@@ -1272,11 +1035,9 @@ os = "macos"
     "#;
     switchy_fs::sync::write(pkg_dir.join("clippier.toml"), clippier_toml).unwrap();
 
-    let result = clippier::process_configs(
-        &pkg_dir, None, None, None, false, false, None, None, None, None,
-    )
-    .await
-    .unwrap();
+    let result = clippier::process_configs(&pkg_dir, &clippier::FeaturesOptions::default())
+        .await
+        .unwrap();
 
     let ubuntu = result
         .iter()
@@ -1326,6 +1087,7 @@ async fn test_handle_packages_command_basic() {
         &[],
         #[cfg(feature = "_workspace")]
         None,
+        false, // fail_on_empty_matrix
         OutputType::Json,
     )
     .await;
@@ -1371,6 +1133,7 @@ async fn test_handle_packages_command_json_output() {
         &[],
         #[cfg(feature = "_workspace")]
         None,
+        false, // fail_on_empty_matrix
         OutputType::Json,
     )
     .await;
@@ -1407,6 +1170,7 @@ async fn test_handle_packages_command_raw_output() {
         &[],
         #[cfg(feature = "_workspace")]
         None,
+        false, // fail_on_empty_matrix
         OutputType::Raw,
     )
     .await;
@@ -1448,6 +1212,7 @@ async fn test_handle_packages_command_with_specific_packages() {
         &[],
         #[cfg(feature = "_workspace")]
         None,
+        false, // fail_on_empty_matrix
         OutputType::Json,
     )
     .await;
@@ -1496,6 +1261,7 @@ async fn test_handle_packages_command_with_max_parallel() {
         &[],
         #[cfg(feature = "_workspace")]
         None,
+        false, // fail_on_empty_matrix
         OutputType::Json,
     )
     .await;
@@ -1533,6 +1299,7 @@ async fn test_handle_packages_command_no_os_filter() {
         &[],
         #[cfg(feature = "_workspace")]
         None,
+        false, // fail_on_empty_matrix
         OutputType::Json,
     )
     .await;
@@ -1576,6 +1343,7 @@ async fn test_handle_packages_command_nonexistent_package() {
         &[],
         #[cfg(feature = "_workspace")]
         None,
+        false, // fail_on_empty_matrix
         OutputType::Json,
     )
     .await;
@@ -1620,6 +1388,7 @@ members = []
         &[],
         #[cfg(feature = "_workspace")]
         None,
+        false, // fail_on_empty_matrix
         OutputType::Json,
     )
     .await;
@@ -1662,6 +1431,7 @@ async fn test_handle_packages_command_with_changed_files() {
         &[],
         #[cfg(feature = "_workspace")]
         None,
+        false, // fail_on_empty_matrix
         OutputType::Json,
     )
     .await;
@@ -1708,6 +1478,7 @@ async fn test_handle_packages_command_changed_files_with_dependencies() {
         &[],
         #[cfg(feature = "_workspace")]
         None,
+        false, // fail_on_empty_matrix
         OutputType::Json,
     )
     .await;
@@ -1751,6 +1522,7 @@ async fn test_handle_packages_command_with_include_reasoning() {
         &[],
         #[cfg(feature = "_workspace")]
         None,
+        false, // fail_on_empty_matrix
         OutputType::Json,
     )
     .await;
@@ -1792,6 +1564,7 @@ async fn test_handle_packages_command_mixed_valid_invalid() {
         &[],
         #[cfg(feature = "_workspace")]
         None,
+        false, // fail_on_empty_matrix
         OutputType::Json,
     )
     .await;
@@ -1838,6 +1611,7 @@ async fn test_handle_packages_command_all_packages() {
         &[],
         #[cfg(feature = "_workspace")]
         None,
+        false, // fail_on_empty_matrix
         OutputType::Json,
     )
     .await;
@@ -0,0 +1,83 @@
+//! Tests for reading changed files from stdin.
+//!
+//! These tests verify that a newline-separated changed-file list, as piped to
+//! `--changed-files-stdin`, is parsed tolerantly and yields the same affected packages as
+//! passing the files via `--changed-files`.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::{find_affected_packages, merge_changed_files, parse_changed_files_list};
+use clippier_test_utilities::test_resources::load_test_workspace;
+
+#[test]
+fn test_parse_changed_files_list_handles_crlf_and_whitespace() {
+    let stdin =
+        "packages/core/src/lib.rs\r\n  packages/web/src/lib.rs \t\r\n\r\n\npackages/api/Cargo.toml";
+
+    assert_eq!(
+        parse_changed_files_list(stdin),
+        vec![
+            "packages/core/src/lib.rs",
+            "packages/web/src/lib.rs",
+            "packages/api/Cargo.toml",
+        ]
+    );
+    assert!(parse_changed_files_list("\r\n  \n").is_empty());
+}
+
+#[test]
+fn test_merge_changed_files_deduplicates() {
+    let args = vec![
+        "packages/core/src/lib.rs".to_string(),
+        "packages/web/src/lib.rs".to_string(),
+    ];
+
+    assert_eq!(
+        merge_changed_files(
+            &args,
+            parse_changed_files_list("packages/web/src/lib.rs\npackages/api/src/lib.rs\n"),
+        ),
+        vec![
+            "packages/core/src/lib.rs",
+            "packages/web/src/lib.rs",
+            "packages/api/src/lib.rs",
+        ]
+    );
+}
+
+#[switchy_async::test]
+async fn test_stdin_changed_files_match_args() {
+    let (temp_dir, _) = load_test_workspace("complex");
+
+    let args = vec![
+        "packages/core/src/lib.rs".to_string(),
+        "packages/models/src/lib.rs".to_string(),
+    ];
+    let from_args = find_affected_packages(temp_dir.path(), &args, &[]).unwrap();
+
+    let stdin = "packages/core/src/lib.rs\r\npackages/models/src/lib.rs  \r\n";
+    let from_stdin = find_affected_packages(
+        temp_dir.path(),
+        &merge_changed_files(&[], parse_changed_files_list(stdin)),
+        &[],
+    )
+    .unwrap();
+
+    assert!(from_args.contains(&"core".to_string()), "{from_args:?}");
+    assert_eq!(from_stdin, from_args);
+
+    // Files given both ways are merged into the same set
+    let mixed = find_affected_packages(
+        temp_dir.path(),
+        &merge_changed_files(
+            &args[..1],
+            parse_changed_files_list("packages/models/src/lib.rs\n"),
+        ),
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(mixed, from_args);
+}
@@ -0,0 +1,113 @@
+//! Tests for scoping `check`/`fmt` to packages with uncommitted changes.
+//!
+//! These tests verify that files changed in the git working tree are mapped to the
+//! directories of the packages they affect.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use std::path::Path;
+
+use clippier::{find_changed_package_dirs, git_diff::get_uncommitted_changed_files};
+use git2::{Repository, Signature};
+
+const WORKSPACE: &str = r#"
+[workspace]
+members = ["packages/*"]
+
+[workspace.dependencies]
+api = { path = "packages/api" }
+"#;
+
+fn write(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+    switchy_fs::sync::create_dir_all(path.parent().unwrap()).unwrap();
+    switchy_fs::sync::write(path, content).unwrap();
+}
+
+/// Creates a committed workspace where `web` depends on `api` and `cli` is independent
+fn create_repo() -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+
+    write(root, "Cargo.toml", WORKSPACE);
+    write(
+        root,
+        "packages/api/Cargo.toml",
+        "[package]\nname = \"api\"\nversion = \"0.1.0\"\n",
+    );
+    write(
+        root,
+        "packages/web/Cargo.toml",
+        "[package]\nname = \"web\"\nversion = \"0.1.0\"\n\n[dependencies]\napi = { workspace = true }\n",
+    );
+    write(
+        root,
+        "packages/cli/Cargo.toml",
+        "[package]\nname = \"cli\"\nversion = \"0.1.0\"\n",
+    );
+    for package in ["api", "web", "cli"] {
+        write(root, &format!("packages/{package}/src/lib.rs"), "// lib\n");
+    }
+
+    let repo = Repository::init(root).unwrap();
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = Signature::now("test", "test@example.com").unwrap();
+    repo.commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[])
+        .unwrap();
+
+    temp_dir
+}
+
+fn package_dir_names(root: &Path) -> Vec<String> {
+    find_changed_package_dirs(root)
+        .unwrap()
+        .iter()
+        .map(|dir| dir.file_name().unwrap().to_string_lossy().to_string())
+        .collect()
+}
+
+#[test]
+fn test_changed_only_clean_tree_targets_nothing() {
+    let temp_dir = create_repo();
+
+    assert!(
+        get_uncommitted_changed_files(temp_dir.path())
+            .unwrap()
+            .is_empty()
+    );
+    assert!(package_dir_names(temp_dir.path()).is_empty());
+}
+
+#[test]
+fn test_changed_only_targets_changed_package() {
+    let temp_dir = create_repo();
+    write(temp_dir.path(), "packages/cli/src/lib.rs", "// changed\n");
+
+    assert_eq!(
+        get_uncommitted_changed_files(temp_dir.path()).unwrap(),
+        vec!["packages/cli/src/lib.rs".to_string()]
+    );
+    assert_eq!(package_dir_names(temp_dir.path()), vec!["cli"]);
+}
+
+#[test]
+fn test_changed_only_includes_untracked_files_and_dependents() {
+    let temp_dir = create_repo();
+    write(
+        temp_dir.path(),
+        "packages/api/src/new_module.rs",
+        "// new\n",
+    );
+
+    let mut names = package_dir_names(temp_dir.path());
+    names.sort();
+
+    assert_eq!(names, vec!["api", "web"]);
+}
@@ -1,4 +1,4 @@
-use clippier::{OutputType, handle_features_command};
+use clippier::{FeaturesOptions, OutputType, handle_features_command};
 use clippier_test_utilities::test_resources::create_simple_workspace;
 
 /// Helper function to create a test workspace with many packages and features
@@ -109,37 +109,13 @@ async fn test_basic_chunking_respects_limit() {
     let temp_dir = create_feature_rich_workspace();
 
     // Test chunking with limit of 5 features per package
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,    // offset
-        None,    // max
-        None,    // max_parallel
-        Some(5), // chunked - limit to 5 features
-        false,   // spread
-        false,   // randomize
-        None,    // seed
-        None,    // features
-        None,    // skip_features
-        None,    // required_features
-        None,    // packages
-        None,    // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,   // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        chunked: Some(5),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -157,37 +133,13 @@ async fn test_basic_chunking_respects_limit() {
     }
 
     // Test chunking with limit of 10 features per package
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,     // offset
-        None,     // max
-        None,     // max_parallel
-        Some(10), // chunked - limit to 10 features
-        false,    // spread
-        false,
-        None,
-        None, // features
-        None, // skip_features
-        None, // required_features
-        None, // packages
-        None, // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false, // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        chunked: Some(10),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -211,37 +163,13 @@ async fn test_basic_spreading_distributes_features() {
     let temp_dir = create_feature_rich_workspace();
 
     // Test spreading without chunking - should create many packages with different features
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None, // offset
-        None, // max
-        None, // max_parallel
-        None, // chunked
-        true, // spread - distribute features
-        false,
-        None,
-        None, // features
-        None, // skip_features
-        None, // required_features
-        None, // packages
-        None, // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false, // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        spread: true,
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -268,37 +196,14 @@ async fn test_chunking_and_spreading_combination() {
     let temp_dir = create_feature_rich_workspace();
 
     // Test chunking with spreading - should distribute features while respecting chunk limits
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,    // offset
-        None,    // max
-        None,    // max_parallel
-        Some(3), // chunked - limit to 3 features
-        true,    // spread - distribute features
-        false,   // randomize
-        None,    // seed
-        None,    // features
-        None,    // skip_features
-        None,    // required_features
-        None,    // packages
-        None,    // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,   // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        chunked: Some(3),
+        spread: true,
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -328,37 +233,14 @@ async fn test_max_parallel_with_chunking() {
     let temp_dir = create_feature_rich_workspace();
 
     // Test max-parallel with chunking - should limit total results while respecting chunk size
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,    // offset
-        None,    // max
-        Some(8), // max_parallel - limit to 8 total packages
-        Some(4), // chunked - limit to 4 features per package
-        false,   // spread
-        false,   // randomize
-        None,    // seed
-        None,    // features
-        None,    // skip_features
-        None,    // required_features
-        None,    // packages
-        None,    // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,   // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        max_parallel: Some(8),
+        chunked: Some(4),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -387,37 +269,14 @@ async fn test_changed_files_respects_chunking_and_spreading() {
     // Test the core regression case: chunking and spreading together
     // This was the main bug - when using both chunking and spreading,
     // some code paths were ignoring the chunking limits
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,    // offset
-        None,    // max
-        None,    // max_parallel
-        Some(6), // chunked - limit to 6 features
-        true,    // spread - distribute features
-        false,   // randomize
-        None,    // seed
-        None,    // features
-        None,    // skip_features
-        None,    // required_features
-        None,    // packages
-        None,    // changed_files - test main path, not changed files path
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,   // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        chunked: Some(6),
+        spread: true,
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -453,37 +312,13 @@ async fn test_chunking_with_small_limit() {
     let temp_dir = create_feature_rich_workspace();
 
     // Test with chunk size of 1 - should create many small packages
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,    // offset
-        None,    // max
-        None,    // max_parallel
-        Some(1), // chunked - limit to 1 feature per package
-        false,   // spread
-        false,   // randomize
-        None,    // seed
-        None,    // features
-        None,    // skip_features
-        None,    // required_features
-        None,    // packages
-        None,    // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,   // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        chunked: Some(1),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -507,37 +342,14 @@ async fn test_chunking_with_small_limit() {
 async fn test_empty_workspace_with_chunking_spreading() {
     let (temp_dir, _) = create_simple_workspace(&[], &[], &[]);
 
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,     // offset
-        None,     // max
-        None,     // max_parallel
-        Some(10), // chunked
-        true,     // spread
-        false,
-        None,
-        None, // features
-        None, // skip_features
-        None, // required_features
-        None, // packages
-        None, // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false, // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        chunked: Some(10),
+        spread: true,
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -603,37 +415,14 @@ serde = "1.0"
     switchy_fs::sync::write(temp_dir.path().join("Cargo.toml"), workspace_toml).unwrap();
 
     // Test chunking with spreading on single package
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,    // offset
-        None,    // max
-        None,    // max_parallel
-        Some(3), // chunked - limit to 3 features
-        true,    // spread
-        false,   // randomize
-        None,    // seed
-        None,    // features
-        None,    // skip_features
-        None,    // required_features
-        None,    // packages
-        None,    // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,   // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        chunked: Some(3),
+        spread: true,
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -662,37 +451,15 @@ async fn test_complex_scenario_all_flags() {
     let temp_dir = create_feature_rich_workspace();
 
     // Test with all flags combined (without changed files since that's not working)
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,    // offset
-        None,    // max
-        Some(5), // max_parallel - limit to 5 total packages
-        Some(4), // chunked - limit to 4 features per package
-        true,    // spread - distribute features
-        false,   // randomize
-        None,    // seed
-        None,    // features
-        None,    // skip_features
-        None,    // required_features
-        None,    // packages
-        None,    // changed_files - skip this for now since it's not working
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,   // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        max_parallel: Some(5),
+        chunked: Some(4),
+        spread: true,
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -719,37 +486,13 @@ async fn test_chunking_without_spreading() {
     let temp_dir = create_feature_rich_workspace();
 
     // Test chunking without spreading - should limit features but not spread
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,     // offset
-        None,     // max
-        None,     // max_parallel
-        Some(15), // chunked - limit to 15 features
-        false,    // spread - don't distribute
-        false,
-        None,
-        None, // features
-        None, // skip_features
-        None, // required_features
-        None, // packages
-        None, // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false, // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        chunked: Some(15),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -813,37 +556,17 @@ members = ["packages/minimal"]
     // Test with chunking enabled and skipping all non-default features
     // This was the bug: when all features are skipped, the chunked empty vec
     // caused zero matrix entries to be generated
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,                             // offset
-        None,                             // max
-        None,                             // max_parallel
-        Some(15),                         // chunked - enable chunking
-        true,                             // spread
-        true,                             // randomize
-        Some(42),                         // seed
-        None,                             // features
-        Some("fail-on-warnings,default"), // skip ALL features
-        None,                             // required_features
-        None,                             // packages
-        None,                             // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,                            // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        chunked: Some(15),
+        spread: true,
+        randomize: true,
+        seed: Some(42),
+        skip_features: Some("fail-on-warnings,default".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -908,37 +631,15 @@ members = ["packages/pkg_a", "packages/pkg_b", "packages/pkg_c"]
     switchy_fs::sync::write(temp_dir.path().join("Cargo.toml"), workspace_toml).unwrap();
 
     // Skip all features with chunking enabled
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,                             // offset
-        None,                             // max
-        None,                             // max_parallel
-        Some(15),                         // chunked
-        true,                             // spread
-        false,                            // randomize
-        None,                             // seed
-        None,                             // features
-        Some("fail-on-warnings,default"), // skip ALL features
-        None,                             // required_features
-        None,                             // packages
-        None,                             // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,                            // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        chunked: Some(15),
+        spread: true,
+        skip_features: Some("fail-on-warnings,default".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -968,37 +669,13 @@ async fn test_spreading_without_chunking() {
     let temp_dir = create_feature_rich_workspace();
 
     // Test spreading without chunking - should distribute all features
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None, // offset
-        None, // max
-        None, // max_parallel
-        None, // chunked - no limit
-        true, // spread - distribute features
-        false,
-        None,
-        None, // features
-        None, // skip_features
-        None, // required_features
-        None, // packages
-        None, // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false, // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        spread: true,
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -1071,37 +748,14 @@ os = "ubuntu"
     }
 
     // Test that clippier can find packages via glob expansion
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,                             // offset
-        None,                             // max
-        None,                             // max_parallel
-        Some(5),                          // chunked
-        false,                            // spread
-        false,                            // randomize
-        None,                             // seed
-        None,                             // features
-        Some("fail-on-warnings,default"), // skip features
-        None,                             // required_features
-        None,                             // packages
-        None,                             // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,                            // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        chunked: Some(5),
+        skip_features: Some("fail-on-warnings,default".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok(), "features command should succeed");
@@ -1179,37 +833,13 @@ os = "ubuntu"
         switchy_fs::sync::write(package_dir.join("clippier.toml"), clippier_toml).unwrap();
     }
 
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        Some("default"),
-        None,
-        None,
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        skip_features: Some("default".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -1296,37 +926,13 @@ os = "ubuntu"
         switchy_fs::sync::write(package_dir.join("clippier.toml"), clippier_toml).unwrap();
     }
 
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        Some("default"),
-        None,
-        None,
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        skip_features: Some("default".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -1419,37 +1025,13 @@ os = "ubuntu"
         switchy_fs::sync::write(package_dir.join("clippier.toml"), clippier_toml).unwrap();
     }
 
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        Some("default"),
-        None,
-        None,
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        skip_features: Some("default".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -1539,37 +1121,13 @@ edition = "2021"
     .unwrap();
     switchy_fs::sync::write(non_matching_dir.join("src/lib.rs"), "// should not match").unwrap();
 
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        Some("default"),
-        None,
-        None,
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        skip_features: Some("default".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -1657,37 +1215,13 @@ edition = "2021"
         switchy_fs::sync::write(package_dir.join("src/lib.rs"), "// should not match").unwrap();
     }
 
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        Some("default"),
-        None,
-        None,
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        skip_features: Some("default".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -1736,37 +1270,12 @@ members = ["nonexistent/*"]
 
     // Don't create any packages - the glob should match nothing
 
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     // Should succeed but return empty array (not error)
@@ -1832,37 +1341,13 @@ os = "ubuntu"
     // Create a file (not directory) that matches glob pattern
     switchy_fs::sync::write(packages_dir.join("just_a_file"), "not a directory").unwrap();
 
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        Some("default"),
-        None,
-        None,
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        skip_features: Some("default".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -1927,37 +1412,13 @@ os = "ubuntu"
         switchy_fs::sync::write(package_dir.join("clippier.toml"), clippier_toml).unwrap();
     }
 
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"),
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        Some("default"),
-        None,
-        None,
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        skip_features: Some("default".to_string()),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -7,15 +7,7 @@ async fn test_process_configs_basic() {
     let (temp_dir, _) = load_test_workspace("complex");
     let result = clippier::process_configs(
         &temp_dir.path().join("packages/cli"),
-        None,
-        None,
-        None,
-        false,
-        false, // randomize = false for test
-        None,
-        None,
-        None,
-        None,
+        &clippier::FeaturesOptions::default(),
     )
     .await;
     assert!(result.is_ok());
@@ -241,20 +233,9 @@ async fn test_git_submodules_enabled() {
     let (temp_dir, _) = load_test_workspace("git-submodules");
     let submodules_pkg = temp_dir.path().join("packages/with-submodules");
 
-    let result = clippier::process_configs(
-        &submodules_pkg,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-    )
-    .await
-    .unwrap();
+    let result = clippier::process_configs(&submodules_pkg, &clippier::FeaturesOptions::default())
+        .await
+        .unwrap();
 
     let json = serde_json::to_value(&result).unwrap();
     let git_submodules = json[0]["gitSubmodules"].as_bool();
@@ -266,11 +247,9 @@ async fn test_git_submodules_disabled_by_default() {
     let (temp_dir, _) = load_test_workspace("complex");
     let api_path = temp_dir.path().join("packages/api");
 
-    let result = clippier::process_configs(
-        &api_path, None, None, None, false, false, None, None, None, None,
-    )
-    .await
-    .unwrap();
+    let result = clippier::process_configs(&api_path, &clippier::FeaturesOptions::default())
+        .await
+        .unwrap();
 
     let json = serde_json::to_value(&result).unwrap();
     assert!(json[0].get("gitSubmodules").is_none() || json[0]["gitSubmodules"].is_null());
@@ -281,20 +260,9 @@ async fn test_git_submodules_multiple_os() {
     let (temp_dir, _) = load_test_workspace("git-submodules");
     let submodules_pkg = temp_dir.path().join("packages/with-submodules");
 
-    let result = clippier::process_configs(
-        &submodules_pkg,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-    )
-    .await
-    .unwrap();
+    let result = clippier::process_configs(&submodules_pkg, &clippier::FeaturesOptions::default())
+        .await
+        .unwrap();
 
     assert_eq!(result.len(), 3);
 
@@ -317,15 +285,7 @@ async fn test_git_submodules_not_present_without_config() {
 
     let result = clippier::process_configs(
         &without_submodules_pkg,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
+        &clippier::FeaturesOptions::default(),
     )
     .await
     .unwrap();
@@ -0,0 +1,211 @@
+//! Tests for `clippier.toml` base config inheritance.
+//!
+//! These tests verify that a package config inherits the fields of the config named by its
+//! `extends` key, overrides them per field, and reports missing or circular base configs.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use std::path::Path;
+
+use clippier::{ClippierConf, ClippierEnv, load_clippier_conf, load_clippier_toml};
+
+const BASE: &str = r#"
+git-submodules = true
+
+[env]
+RUST_BACKTRACE = "1"
+RUST_LOG = "info"
+
+[rust]
+nightly = false
+skip-features = ["default"]
+
+[[config]]
+os = "ubuntu"
+dependencies = [{ command = "sudo apt-get install libasound2-dev" }]
+
+[[config]]
+os = "windows"
+"#;
+
+fn write(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+    switchy_fs::sync::create_dir_all(path.parent().unwrap()).unwrap();
+    switchy_fs::sync::write(path, content).unwrap();
+}
+
+fn env_value(conf: &ClippierConf, name: &str) -> Option<String> {
+    match conf.env.as_ref()?.get(name)? {
+        ClippierEnv::Value(value) | ClippierEnv::FilteredValue { value, .. } => Some(value.clone()),
+    }
+}
+
+#[test]
+fn test_extends_inherits_base_config() {
+    let temp_dir = switchy_fs::tempdir().unwrap();
+    let root = temp_dir.path();
+    write(root, "shared/clippier.base.toml", BASE);
+    write(
+        root,
+        "packages/api/clippier.toml",
+        r#"extends = "../../shared/clippier.base.toml""#,
+    );
+
+    let conf = load_clippier_conf(&root.join("packages/api/clippier.toml")).unwrap();
+
+    assert_eq!(conf.git_submodules, Some(true));
+    assert_eq!(env_value(&conf, "RUST_LOG").as_deref(), Some("info"));
+    assert_eq!(env_value(&conf, "RUST_BACKTRACE").as_deref(), Some("1"));
+    assert_eq!(
+        conf.rust.unwrap().skip_features,
+        Some(vec!["default".to_string()])
+    );
+
+    let configs = conf.config.unwrap();
+    let oses: Vec<&str> = configs.iter().map(|x| x.os.as_str()).collect();
+    assert_eq!(oses, vec!["ubuntu", "windows"]);
+}
+
+#[test]
+fn test_extends_overrides_fields_per_field() {
+    let temp_dir = switchy_fs::tempdir().unwrap();
+    let root = temp_dir.path();
+    write(root, "shared/clippier.base.toml", BASE);
+    write(
+        root,
+        "packages/api/clippier.toml",
+        r#"
+extends = "../../shared/clippier.base.toml"
+git-submodules = false
+
+[env]
+RUST_LOG = "debug"
+
+[rust]
+nightly = true
+
+[[config]]
+os = "ubuntu"
+dependencies = [
+    { command = "sudo apt-get install libasound2-dev" },
+    { command = "sudo apt-get install libssl-dev" },
+]
+
+[[config]]
+os = "macos"
+"#,
+    );
+
+    let path = root.join("packages/api/clippier.toml");
+    let conf = load_clippier_conf(&path).unwrap();
+
+    // Enabled git submodules are never disabled by an extending config
+    assert_eq!(conf.git_submodules, Some(true));
+    assert_eq!(env_value(&conf, "RUST_LOG").as_deref(), Some("debug"));
+    assert_eq!(env_value(&conf, "RUST_BACKTRACE").as_deref(), Some("1"));
+
+    let rust = conf.rust.unwrap();
+    assert_eq!(rust.nightly, Some(true));
+    assert_eq!(rust.skip_features, Some(vec!["default".to_string()]));
+
+    let configs = conf.config.unwrap();
+    let oses: Vec<&str> = configs.iter().map(|x| x.os.as_str()).collect();
+    assert_eq!(oses, vec!["ubuntu", "windows", "macos"]);
+
+    // Dependencies are concatenated without duplicates
+    let value = load_clippier_toml(&path).unwrap();
+    assert!(value.get("extends").is_none());
+    let commands: Vec<&str> = value["config"][0]["dependencies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|x| x["command"].as_str())
+        .collect();
+    assert_eq!(
+        commands,
+        vec![
+            "sudo apt-get install libasound2-dev",
+            "sudo apt-get install libssl-dev",
+        ]
+    );
+}
+
+#[test]
+fn test_extends_chain_resolves_relative_to_each_file() {
+    let temp_dir = switchy_fs::tempdir().unwrap();
+    let root = temp_dir.path();
+    write(root, "shared/clippier.base.toml", BASE);
+    write(
+        root,
+        "shared/audio/clippier.toml",
+        r#"
+extends = "../clippier.base.toml"
+
+[env]
+AUDIO = "1"
+"#,
+    );
+    write(
+        root,
+        "packages/player/clippier.toml",
+        r#"extends = "../../shared/audio/clippier.toml""#,
+    );
+
+    let conf = load_clippier_conf(&root.join("packages/player/clippier.toml")).unwrap();
+
+    assert_eq!(env_value(&conf, "AUDIO").as_deref(), Some("1"));
+    assert_eq!(env_value(&conf, "RUST_LOG").as_deref(), Some("info"));
+}
+
+#[test]
+fn test_extends_missing_base_errors() {
+    let temp_dir = switchy_fs::tempdir().unwrap();
+    let root = temp_dir.path();
+    write(
+        root,
+        "packages/api/clippier.toml",
+        r#"extends = "../../shared/missing.toml""#,
+    );
+
+    let error = load_clippier_conf(&root.join("packages/api/clippier.toml"))
+        .unwrap_err()
+        .to_string();
+
+    assert!(
+        error.contains("extends '../../shared/missing.toml'"),
+        "{error}"
+    );
+    assert!(
+        error.contains("shared/missing.toml' does not exist"),
+        "{error}"
+    );
+}
+
+#[test]
+fn test_extends_circular_errors() {
+    let temp_dir = switchy_fs::tempdir().unwrap();
+    let root = temp_dir.path();
+    write(root, "a/clippier.toml", r#"extends = "../b/clippier.toml""#);
+    write(root, "b/clippier.toml", r#"extends = "../a/clippier.toml""#);
+
+    let error = load_clippier_conf(&root.join("a/clippier.toml"))
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.contains("Circular `extends`"), "{error}");
+}
+
+#[test]
+fn test_extends_must_be_a_string() {
+    let temp_dir = switchy_fs::tempdir().unwrap();
+    let root = temp_dir.path();
+    write(root, "clippier.toml", "extends = 1");
+
+    let error = load_clippier_conf(&root.join("clippier.toml"))
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.contains("must be a path string"), "{error}");
+}
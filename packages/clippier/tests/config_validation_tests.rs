@@ -0,0 +1,366 @@
+//! Tests for strict `clippier.toml` schema validation.
+//!
+//! These tests verify that unknown keys, misspelled fields, type mismatches and obviously
+//! wrong values are reported with the offending key path, and that valid configs across
+//! the workspace and their `extends` base configs produce no diagnostics.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use std::path::{Path, PathBuf};
+
+use clippier::config_validation::{
+    ConfigDiagnostic, format_config_validation_report, validate_workspace_configs,
+};
+
+const WORKSPACE: &str = r#"
+[workspace]
+members = ["packages/*"]
+"#;
+
+const VALID_CLIPPIER: &str = r#"
+flaky-retries = 3
+
+[[config]]
+os = "ubuntu"
+dependencies = [{ command = "sudo apt-get install -y libasound2-dev" }]
+
+[config.rust]
+nightly = false
+skip-features = ["fail-on-warnings"]
+
+[config.env]
+RUST_LOG = "debug"
+DATABASE_URL = { value = "sqlite://test.db", features = ["sqlite"] }
+
+[parallelization]
+chunked = 4
+"#;
+
+/// Creates a workspace whose `app` package has the given `clippier.toml`
+fn create_workspace(clippier_toml: &str) -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+
+    switchy_fs::sync::write(root.join("Cargo.toml"), WORKSPACE).unwrap();
+
+    let app_dir = root.join("packages/app");
+    switchy_fs::sync::create_dir_all(app_dir.join("src")).unwrap();
+    switchy_fs::sync::write(
+        app_dir.join("Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    switchy_fs::sync::write(app_dir.join("src/main.rs"), "fn main() {}").unwrap();
+    switchy_fs::sync::write(app_dir.join("clippier.toml"), clippier_toml).unwrap();
+
+    temp_dir
+}
+
+fn diagnostic(key: &str, message: &str) -> ConfigDiagnostic {
+    ConfigDiagnostic {
+        file: PathBuf::from("packages/app/clippier.toml"),
+        key: key.to_string(),
+        message: message.to_string(),
+    }
+}
+
+fn validate(clippier_toml: &str) -> Vec<ConfigDiagnostic> {
+    let temp_dir = create_workspace(clippier_toml);
+
+    validate_workspace_configs(temp_dir.path())
+        .unwrap()
+        .diagnostics
+}
+
+#[test]
+fn test_validate_accepts_valid_config() {
+    let temp_dir = create_workspace(VALID_CLIPPIER);
+
+    let report = validate_workspace_configs(temp_dir.path()).unwrap();
+
+    assert_eq!(
+        report.files_checked,
+        vec![PathBuf::from("packages/app/clippier.toml")]
+    );
+    assert!(report.is_clean(), "{:?}", report.diagnostics);
+}
+
+#[test]
+fn test_validate_reports_unknown_key() {
+    let diagnostics = validate(
+        r#"
+[[config]]
+os = "ubuntu"
+toolchain = "nightly"
+"#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![diagnostic("config[0].toolchain", "unknown key")]
+    );
+}
+
+#[test]
+fn test_validate_suggests_kebab_case_for_misspelled_field() {
+    let diagnostics = validate(
+        r#"
+[[config]]
+os = "ubuntu"
+
+[config.rust]
+skip_features = ["fail-on-warnings"]
+"#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![diagnostic(
+            "config[0].rust.skip_features",
+            "unknown key, did you mean `skip-features`?"
+        )]
+    );
+}
+
+#[test]
+fn test_validate_suggests_close_key_for_typo() {
+    let diagnostics = validate(
+        r#"
+git-submodule = true
+
+[[config]]
+os = "ubuntu"
+"#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![diagnostic(
+            "git-submodule",
+            "unknown key, did you mean `git-submodules`?"
+        )]
+    );
+}
+
+#[test]
+fn test_validate_reports_type_mismatches() {
+    let diagnostics = validate(
+        r#"
+[[config]]
+os = "ubuntu"
+git-submodules = "yes"
+
+[config.rust]
+skip-features = "fail-on-warnings"
+"#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            diagnostic(
+                "config[0].git-submodules",
+                "expected a boolean, found string"
+            ),
+            diagnostic(
+                "config[0].rust.skip-features",
+                "expected an array, found string"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_reports_empty_and_missing_os() {
+    let diagnostics = validate(
+        r#"
+[[config]]
+os = " "
+
+[[config]]
+name = "no-os"
+"#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            diagnostic("config[0].os", "must not be empty"),
+            diagnostic("config[1]", "missing required key `os`"),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_accepts_os_scoped_required_features() {
+    let diagnostics = validate(
+        r#"
+[rust]
+required-features = [
+    "production",
+    { feature = "pulseaudio", os = "ubuntu" },
+    { feature = "coreaudio", os = ["macos"] },
+]
+"#,
+    );
+
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
+#[test]
+fn test_validate_reports_invalid_os_scoped_required_features() {
+    let diagnostics = validate(
+        r#"
+[rust]
+required-features = [
+    { feature = "pulseaudio" },
+    { feature = "coreaudio", os = " " },
+    { feature = "alsa", os = "ubuntu", arch = "x86_64" },
+    1,
+]
+"#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            diagnostic("rust.required-features[0]", "missing required key `os`"),
+            diagnostic("rust.required-features[1].os", "must not be empty"),
+            diagnostic("rust.required-features[2].arch", "unknown key"),
+            diagnostic(
+                "rust.required-features[3]",
+                "expected a string or a table, found integer"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_reports_out_of_range_and_unknown_values() {
+    let diagnostics = validate(
+        r#"
+[node]
+package-manager = "yarn"
+
+[parallelization]
+chunked = 0
+"#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            diagnostic(
+                "node.package-manager",
+                "expected one of `npm`, `pnpm`, `bun`, found `yarn`"
+            ),
+            diagnostic(
+                "parallelization.chunked",
+                "must be between 1 and 65535, found 0"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_reports_invalid_toml() {
+    let diagnostics = validate("[[config]\nos = \"ubuntu\"\n");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].key, "");
+    assert!(
+        diagnostics[0].message.starts_with("invalid TOML"),
+        "{}",
+        diagnostics[0].message
+    );
+}
+
+#[test]
+fn test_validate_allows_tool_specific_tables() {
+    let diagnostics = validate(
+        r#"
+[tools]
+required = ["rustfmt"]
+
+[tools.clippier-md]
+max-line-length = 100
+"#,
+    );
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn test_validate_checks_extended_base_config() {
+    let temp_dir = create_workspace(
+        r#"
+extends = "../../clippier.base.toml"
+
+[[config]]
+os = "ubuntu"
+"#,
+    );
+    switchy_fs::sync::write(
+        temp_dir.path().join("clippier.base.toml"),
+        "[rust]\nnightyl = true\n",
+    )
+    .unwrap();
+
+    let report = validate_workspace_configs(temp_dir.path()).unwrap();
+
+    assert_eq!(
+        report.files_checked,
+        vec![
+            PathBuf::from("packages/app/clippier.toml"),
+            PathBuf::from("clippier.base.toml"),
+        ]
+    );
+    assert_eq!(
+        report.diagnostics,
+        vec![ConfigDiagnostic {
+            file: PathBuf::from("clippier.base.toml"),
+            key: "rust.nightyl".to_string(),
+            message: "unknown key, did you mean `nightly`?".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_validate_reports_missing_base_config() {
+    let diagnostics = validate(
+        r#"
+extends = "missing.toml"
+
+[[config]]
+os = "ubuntu"
+"#,
+    );
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].key, "extends");
+    assert!(
+        diagnostics[0].message.ends_with("does not exist"),
+        "{}",
+        diagnostics[0].message
+    );
+}
+
+#[test]
+fn test_format_config_validation_report() {
+    let temp_dir = create_workspace("[[config]]\nos = \"\"\n");
+
+    let report = validate_workspace_configs(temp_dir.path()).unwrap();
+    let output = format_config_validation_report(&report);
+
+    assert!(output.contains("Checked 1 clippier.toml files"), "{output}");
+    assert!(output.contains("❌ Found 1 problems:"), "{output}");
+    assert!(
+        output.contains(&format!(
+            "📄 {}: `config[0].os`: must not be empty",
+            Path::new("packages/app/clippier.toml").display()
+        )),
+        "{output}"
+    );
+}
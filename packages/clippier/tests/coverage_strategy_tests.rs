@@ -0,0 +1,208 @@
+//! Tests for `--coverage-strategy` feature combinations.
+//!
+//! These tests verify that `each-once` enables every feature and `pairwise` every pair of
+//! features in at least one combination, without exceeding the combination size and
+//! without combining mutually-exclusive features.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::{CoverageStrategy, cover_features};
+
+const CARGO_TOML: &str = r#"
+[package]
+name = "backend"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+default = ["sqlite"]
+sqlite = []
+postgres = []
+json = []
+tls = []
+"#;
+
+fn features(count: usize) -> Vec<String> {
+    (1..=count).map(|i| format!("feature{i}")).collect()
+}
+
+fn pairs(features: &[String]) -> Vec<(&String, &String)> {
+    features
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| features[(i + 1)..].iter().map(move |b| (a, b)))
+        .collect()
+}
+
+fn assert_max_size(combinations: &[Vec<String>], max_size: usize) {
+    for combination in combinations {
+        assert!(
+            combination.len() <= max_size,
+            "Combination exceeds {max_size} features: {combination:?}"
+        );
+    }
+}
+
+fn assert_each_feature_covered(features: &[String], combinations: &[Vec<String>]) {
+    for feature in features {
+        assert!(
+            combinations.iter().any(|x| x.contains(feature)),
+            "Feature {feature} is not covered by {combinations:?}"
+        );
+    }
+}
+
+fn assert_each_pair_covered(features: &[String], combinations: &[Vec<String>]) {
+    for (a, b) in pairs(features) {
+        assert!(
+            combinations.iter().any(|x| x.contains(a) && x.contains(b)),
+            "Pair ({a}, {b}) is not covered by {combinations:?}"
+        );
+    }
+}
+
+/// Creates a package that declares `sqlite` and `postgres` as mutually exclusive
+fn create_backend_package() -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+
+    switchy_fs::sync::write(temp_dir.path().join("Cargo.toml"), CARGO_TOML).unwrap();
+    switchy_fs::sync::write(
+        temp_dir.path().join("clippier.toml"),
+        r#"
+[[mutually-exclusive]]
+features = ["sqlite", "postgres"]
+"#,
+    )
+    .unwrap();
+    switchy_fs::sync::create_dir_all(temp_dir.path().join("src")).unwrap();
+    switchy_fs::sync::write(temp_dir.path().join("src/lib.rs"), "// test lib").unwrap();
+
+    temp_dir
+}
+
+async fn combinations(
+    temp_dir: &switchy_fs::TempDir,
+    chunked: Option<u16>,
+    strategy: CoverageStrategy,
+) -> Vec<Vec<String>> {
+    let jobs = clippier::process_configs(
+        temp_dir.path(),
+        &clippier::FeaturesOptions {
+            chunked,
+            coverage_strategy: Some(strategy),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(jobs.len(), 1, "{jobs:?}");
+
+    jobs[0]["features"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|x| x.as_str().unwrap().split(',').map(str::to_string).collect())
+        .collect()
+}
+
+#[test]
+fn test_each_once_covers_every_feature_with_minimal_combinations() {
+    let features = features(7);
+
+    for max_size in 1..=8 {
+        let combinations = cover_features(&features, Some(max_size), CoverageStrategy::EachOnce);
+
+        assert_each_feature_covered(&features, &combinations);
+        assert_max_size(&combinations, max_size);
+        assert_eq!(combinations.len(), features.len().div_ceil(max_size));
+    }
+}
+
+#[test]
+fn test_each_once_without_limit_enables_all_features_together() {
+    let features = features(5);
+
+    assert_eq!(
+        cover_features(&features, None, CoverageStrategy::EachOnce),
+        vec![features]
+    );
+}
+
+#[test]
+fn test_pairwise_covers_every_pair() {
+    let features = features(6);
+
+    for max_size in 2..=6 {
+        let combinations = cover_features(&features, Some(max_size), CoverageStrategy::Pairwise);
+
+        assert_each_pair_covered(&features, &combinations);
+        assert_max_size(&combinations, max_size);
+    }
+}
+
+#[test]
+fn test_pairwise_needs_fewer_combinations_than_pairs() {
+    let features = features(6);
+    let combinations = cover_features(&features, Some(3), CoverageStrategy::Pairwise);
+
+    assert!(
+        combinations.len() < pairs(&features).len(),
+        "{combinations:?}"
+    );
+}
+
+#[test]
+fn test_pairwise_widens_combinations_to_two_features() {
+    let features = features(3);
+    let combinations = cover_features(&features, Some(1), CoverageStrategy::Pairwise);
+
+    assert_each_pair_covered(&features, &combinations);
+    assert_max_size(&combinations, 2);
+}
+
+#[test]
+fn test_pairwise_covers_a_single_feature() {
+    let features = features(1);
+
+    assert_eq!(
+        cover_features(&features, Some(2), CoverageStrategy::Pairwise),
+        vec![features]
+    );
+}
+
+#[switchy_async::test]
+async fn test_each_once_never_combines_mutually_exclusive_features() {
+    let temp_dir = create_backend_package();
+    let combinations = combinations(&temp_dir, None, CoverageStrategy::EachOnce).await;
+
+    let features = ["default", "sqlite", "postgres", "json", "tls"].map(str::to_string);
+    assert_each_feature_covered(&features, &combinations);
+
+    for combination in &combinations {
+        assert!(
+            !(combination.contains(&"postgres".to_string())
+                && (combination.contains(&"sqlite".to_string())
+                    || combination.contains(&"default".to_string()))),
+            "Combination activates both sqlite and postgres: {combination:?}"
+        );
+    }
+}
+
+#[switchy_async::test]
+async fn test_pairwise_covers_every_compatible_pair_of_a_package() {
+    let temp_dir = create_backend_package();
+    let combinations = combinations(&temp_dir, Some(3), CoverageStrategy::Pairwise).await;
+
+    assert_max_size(&combinations, 3);
+    assert_each_pair_covered(
+        &["default", "sqlite", "json", "tls"].map(str::to_string),
+        &combinations,
+    );
+    assert_each_pair_covered(
+        &["postgres", "json", "tls"].map(str::to_string),
+        &combinations,
+    );
+}
@@ -19,6 +19,7 @@ async fn test_basic_dockerfile_generation() {
         &[],
         &[],
         None,
+        clippier::DEFAULT_DOCKER_TARGET_OS,
     )
     .await;
     assert!(result.is_ok());
@@ -43,6 +44,7 @@ async fn test_dockerfile_feature_inclusion() {
         &[],
         &[],
         None,
+        clippier::DEFAULT_DOCKER_TARGET_OS,
     )
     .await;
     assert!(result.is_ok());
@@ -143,6 +145,7 @@ async fn test_dockerfile_custom_images() {
         &[],
         &[],
         None,
+        clippier::DEFAULT_DOCKER_TARGET_OS,
     )
     .await;
     assert!(result.is_ok());
@@ -181,6 +184,7 @@ async fn test_dockerfile_minimal_workspace() {
         &[],
         &[],
         None,
+        clippier::DEFAULT_DOCKER_TARGET_OS,
     )
     .await;
     assert!(result.is_ok());
@@ -205,6 +209,7 @@ async fn test_dockerfile_with_custom_binary_name() {
         &[],
         &[],
         Some("my-custom-binary"),
+        clippier::DEFAULT_DOCKER_TARGET_OS,
     )
     .await;
     assert!(result.is_ok());
@@ -241,3 +246,284 @@ async fn test_get_binary_name_with_override() {
     // Should fall back to package name transformation since no explicit binary is defined
     assert_eq!(binary_name, "api");
 }
+
+/// Creates a workspace with a single `app` package using the given `clippier.toml`
+fn create_os_config_workspace(clippier_toml: &str) -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    let package_dir = root.join("packages/app");
+
+    switchy_fs::sync::create_dir_all(package_dir.join("src")).unwrap();
+    switchy_fs::sync::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"packages/app\"]\n",
+    )
+    .unwrap();
+    switchy_fs::sync::write(
+        package_dir.join("Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    switchy_fs::sync::write(package_dir.join("src/main.rs"), "fn main() {}").unwrap();
+    switchy_fs::sync::write(package_dir.join("clippier.toml"), clippier_toml).unwrap();
+
+    temp_dir
+}
+
+async fn generate_app_dockerfile(
+    temp_dir: &switchy_fs::TempDir,
+    target_os: &str,
+) -> Result<String, String> {
+    let dockerfile = temp_dir.path().join("Dockerfile");
+
+    clippier::generate_dockerfile(
+        temp_dir.path(),
+        "app",
+        None,
+        false,
+        &dockerfile,
+        "rust:1-bookworm",
+        "debian:bookworm-slim",
+        &[],
+        None,
+        false,
+        &[],
+        &[],
+        None,
+        target_os,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(switchy_fs::sync::read_to_string(dockerfile).unwrap())
+}
+
+const MULTI_OS_CLIPPIER_TOML: &str = r#"
+[[config]]
+os = "ubuntu"
+dependencies = [{ command = "sudo apt-get install libubuntu-only-dev" }]
+env = { UBUNTU_ONLY = "1" }
+
+[[config]]
+os = "debian"
+dependencies = [{ command = "sudo apt-get install libdebian-only-dev" }]
+env = { DEBIAN_ONLY = "1" }
+
+[[config]]
+os = "windows"
+dependencies = [{ command = "choco install windows-only" }]
+env = { WINDOWS_ONLY = "1" }
+"#;
+
+#[switchy_async::test]
+async fn test_dockerfile_uses_target_os_config() {
+    let temp_dir = create_os_config_workspace(MULTI_OS_CLIPPIER_TOML);
+
+    let ubuntu = generate_app_dockerfile(&temp_dir, clippier::DEFAULT_DOCKER_TARGET_OS)
+        .await
+        .unwrap();
+    assert!(ubuntu.contains("libubuntu-only-dev"), "{ubuntu}");
+    assert!(ubuntu.contains("ARG UBUNTU_ONLY"), "{ubuntu}");
+    assert!(!ubuntu.contains("libdebian-only-dev"), "{ubuntu}");
+    assert!(!ubuntu.contains("DEBIAN_ONLY"), "{ubuntu}");
+
+    let debian = generate_app_dockerfile(&temp_dir, "debian").await.unwrap();
+    assert!(debian.contains("libdebian-only-dev"), "{debian}");
+    assert!(debian.contains("ARG DEBIAN_ONLY"), "{debian}");
+    assert!(!debian.contains("libubuntu-only-dev"), "{debian}");
+    assert!(!debian.contains("UBUNTU_ONLY"), "{debian}");
+    assert!(!debian.contains("WINDOWS_ONLY"), "{debian}");
+}
+
+#[switchy_async::test]
+async fn test_dockerfile_rejects_non_linux_target_os() {
+    let temp_dir = create_os_config_workspace(MULTI_OS_CLIPPIER_TOML);
+
+    for os in ["windows", "macos"] {
+        let error = generate_app_dockerfile(&temp_dir, os).await.unwrap_err();
+        assert!(
+            error.contains(&format!(
+                "Cannot generate a Dockerfile for target OS '{os}': Docker images are Linux-based"
+            )),
+            "{error}"
+        );
+    }
+}
+
+#[switchy_async::test]
+async fn test_dockerfile_rejects_windows_only_package() {
+    let temp_dir = create_os_config_workspace(
+        r#"
+[[config]]
+os = "windows"
+dependencies = [{ command = "choco install windows-only" }]
+"#,
+    );
+
+    let error = generate_app_dockerfile(&temp_dir, clippier::DEFAULT_DOCKER_TARGET_OS)
+        .await
+        .unwrap_err();
+
+    assert!(
+        error.contains(
+            "Cannot generate a Dockerfile for app: it only has clippier.toml configurations for windows"
+        ),
+        "{error}"
+    );
+}
+
+const DUPLICATE_ENV_CLIPPIER_TOML: &str = r#"
+[[config]]
+os = "ubuntu"
+name = "first"
+env = { SHARED = "1", ZED = "1" }
+
+[[config]]
+os = "ubuntu"
+name = "second"
+env = { SHARED = "1", ALPHA = "1" }
+"#;
+
+async fn generate_dockerfile_with_env(temp_dir: &switchy_fs::TempDir) -> String {
+    let dockerfile = temp_dir.path().join("Dockerfile");
+
+    clippier::generate_dockerfile(
+        temp_dir.path(),
+        "app",
+        None,
+        false,
+        &dockerfile,
+        "rust:1-bookworm",
+        "debian:bookworm-slim",
+        &[],
+        Some("SHARED, BUILD_ONLY"),
+        false,
+        &[
+            "B=1".to_string(),
+            "A=2".to_string(),
+            "C=${B}".to_string(),
+            "B=3".to_string(),
+        ],
+        &["Y=1".to_string(), "X=1".to_string(), "Y=2".to_string()],
+        None,
+        clippier::DEFAULT_DOCKER_TARGET_OS,
+    )
+    .await
+    .unwrap();
+
+    switchy_fs::sync::read_to_string(dockerfile).unwrap()
+}
+
+#[switchy_async::test]
+async fn test_dockerfile_generation_is_byte_stable() {
+    let temp_dir = create_os_config_workspace(DUPLICATE_ENV_CLIPPIER_TOML);
+
+    let first = generate_dockerfile_with_env(&temp_dir).await;
+    let second = generate_dockerfile_with_env(&temp_dir).await;
+
+    assert_eq!(first, second);
+}
+
+#[switchy_async::test]
+async fn test_dockerfile_args_are_sorted_and_envs_keep_declaration_order() {
+    let temp_dir = create_os_config_workspace(DUPLICATE_ENV_CLIPPIER_TOML);
+
+    let dockerfile = generate_dockerfile_with_env(&temp_dir).await;
+    let (builder, runtime) = dockerfile.split_once("# Runtime").unwrap();
+
+    for stage in [builder, runtime] {
+        let args = stage
+            .lines()
+            .filter_map(|line| line.strip_prefix("ARG "))
+            .collect::<Vec<_>>();
+        let mut expected = args.clone();
+        expected.sort_unstable();
+        expected.dedup();
+
+        assert_eq!(args, expected, "{dockerfile}");
+        assert_eq!(
+            args.iter().filter(|arg| **arg == "SHARED").count(),
+            1,
+            "{dockerfile}"
+        );
+    }
+
+    let runtime_args = runtime
+        .lines()
+        .filter_map(|line| line.strip_prefix("ARG "))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        runtime_args,
+        vec!["ALPHA", "BUILD_ONLY", "SHARED", "ZED"],
+        "{dockerfile}"
+    );
+
+    assert!(builder.contains("ENV Y=2\nENV X=1\n"), "{dockerfile}");
+    assert!(!dockerfile.contains("ENV Y=1"), "{dockerfile}");
+    assert!(
+        runtime.contains("ENV B=3\nENV A=2\nENV C=${B}\n"),
+        "{dockerfile}"
+    );
+    assert!(!dockerfile.contains("ENV B=1"), "{dockerfile}");
+}
+
+async fn handle_app_dockerfile_command(
+    temp_dir: &switchy_fs::TempDir,
+    output: &std::path::Path,
+    dry_run: bool,
+) -> String {
+    clippier::handle_generate_dockerfile_command(
+        temp_dir.path(),
+        "app",
+        "master",
+        None,
+        false,
+        output,
+        "rust:1-bookworm",
+        "debian:bookworm-slim",
+        &[],
+        None,
+        true,
+        &[],
+        &[],
+        None,
+        clippier::DEFAULT_DOCKER_TARGET_OS,
+        dry_run,
+    )
+    .await
+    .unwrap()
+}
+
+#[switchy_async::test]
+async fn test_dockerfile_dry_run_returns_content_without_writing() {
+    let temp_dir = create_os_config_workspace(MULTI_OS_CLIPPIER_TOML);
+    let dockerfile = temp_dir.path().join("Dockerfile");
+    let dockerignore = clippier::GeneratedDockerfile::dockerignore_path(&dockerfile);
+
+    let dry_run = handle_app_dockerfile_command(&temp_dir, &dockerfile, true).await;
+
+    assert!(!switchy_fs::exists(&dockerfile));
+    assert!(!switchy_fs::exists(&dockerignore));
+
+    let message = handle_app_dockerfile_command(&temp_dir, &dockerfile, false).await;
+
+    assert_eq!(
+        message,
+        format!("Generated Dockerfile at: {}", dockerfile.display())
+    );
+
+    let written = clippier::GeneratedDockerfile {
+        dockerfile: switchy_fs::sync::read_to_string(&dockerfile).unwrap(),
+        dockerignore: Some(switchy_fs::sync::read_to_string(&dockerignore).unwrap()),
+    };
+
+    assert!(
+        written.dockerfile.contains("libubuntu-only-dev"),
+        "{dry_run}"
+    );
+    assert!(
+        dry_run.starts_with(&format!("==> {} <==\n", dockerfile.display())),
+        "{dry_run}"
+    );
+    assert_eq!(dry_run, written.to_dry_run_output(&dockerfile));
+}
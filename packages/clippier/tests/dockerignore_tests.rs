@@ -0,0 +1,90 @@
+//! Tests for `.dockerignore` generation.
+//!
+//! These tests verify that the generated ignore file re-includes the required packages and
+//! excludes the heavy subdirectories listed in each package's `dockerignore-extra`.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::generate_dockerignore_content;
+
+/// Creates a workspace with `server` and `models` packages, where `server` has the given
+/// `clippier.toml`
+fn create_workspace(server_clippier_toml: Option<&str>) -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+
+    for name in ["server", "models"] {
+        let package_dir = root.join("packages").join(name);
+        switchy_fs::sync::create_dir_all(package_dir.join("tests/fixtures")).unwrap();
+        switchy_fs::sync::write(
+            package_dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n"),
+        )
+        .unwrap();
+    }
+
+    if let Some(clippier_toml) = server_clippier_toml {
+        switchy_fs::sync::write(root.join("packages/server/clippier.toml"), clippier_toml).unwrap();
+    }
+
+    temp_dir
+}
+
+fn dependencies() -> Vec<(String, String)> {
+    vec![
+        ("server".to_string(), "packages/server".to_string()),
+        ("models".to_string(), "packages/models".to_string()),
+    ]
+}
+
+#[test]
+fn test_dockerignore_includes_packages_without_extra_exclusions() {
+    let temp_dir = create_workspace(None);
+
+    let content =
+        generate_dockerignore_content(temp_dir.path(), &dependencies(), "server", None).unwrap();
+
+    assert_eq!(
+        content,
+        "/packages/*\n\n!/packages/server\n!/packages/models\n\n"
+    );
+}
+
+#[test]
+fn test_dockerignore_excludes_package_fixtures() {
+    let temp_dir = create_workspace(Some(
+        r#"
+dockerignore-extra = ["tests/fixtures/**", "./target/**"]
+
+[[config]]
+os = "ubuntu"
+"#,
+    ));
+
+    let content =
+        generate_dockerignore_content(temp_dir.path(), &dependencies(), "server", None).unwrap();
+    let lines = content.lines().collect::<Vec<_>>();
+
+    let include = lines
+        .iter()
+        .position(|x| *x == "!/packages/server")
+        .unwrap();
+    let fixtures = lines
+        .iter()
+        .position(|x| *x == "/packages/server/tests/fixtures/**")
+        .unwrap();
+    assert!(include < fixtures, "{content}");
+    assert!(lines.contains(&"/packages/server/target/**"), "{content}");
+    assert!(!content.contains("/packages/models/tests"), "{content}");
+}
+
+#[test]
+fn test_dockerignore_reports_invalid_clippier_toml() {
+    let temp_dir = create_workspace(Some("dockerignore-extra = \"tests/fixtures/**\"\n"));
+
+    assert!(
+        generate_dockerignore_content(temp_dir.path(), &dependencies(), "server", None).is_err()
+    );
+}
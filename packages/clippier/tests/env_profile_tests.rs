@@ -0,0 +1,145 @@
+//! Tests for environment profiles on `clippier.toml` env vars.
+//!
+//! These tests verify that `--env-profile` selects profile-gated env vars and that
+//! profile and feature filters must both match.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+/// Creates a single package with plain, profile-gated and feature-gated env vars
+fn create_profiled_package() -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+
+    switchy_fs::sync::write(
+        temp_dir.path().join("Cargo.toml"),
+        r#"
+[package]
+name = "service"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+default = []
+telemetry = []
+"#,
+    )
+    .unwrap();
+    switchy_fs::sync::write(
+        temp_dir.path().join("clippier.toml"),
+        r#"
+[env]
+ALWAYS = "1"
+CI_ONLY = { value = "ci", profile = ["ci"] }
+RELEASE_ONLY = { value = "release", profile = ["release"] }
+SHARED = { value = "shared", profile = ["ci", "release"] }
+CI_TELEMETRY = { value = "on", profile = ["ci"], features = ["telemetry"] }
+
+[[config]]
+os = "ubuntu"
+"#,
+    )
+    .unwrap();
+    switchy_fs::sync::create_dir_all(temp_dir.path().join("src")).unwrap();
+    switchy_fs::sync::write(temp_dir.path().join("src/lib.rs"), "// test lib").unwrap();
+
+    temp_dir
+}
+
+/// Returns the names of the env vars included in every generated job
+async fn env_names(
+    temp_dir: &switchy_fs::TempDir,
+    features: Option<&[String]>,
+    env_profile: Option<&[String]>,
+) -> Vec<Vec<String>> {
+    let jobs = clippier::process_configs(
+        temp_dir.path(),
+        &clippier::FeaturesOptions {
+            features: features.map(|x| x.join(",")),
+            env_profile: env_profile.map(<[_]>::to_vec),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    jobs.iter()
+        .map(|job| {
+            job.get("env")
+                .and_then(|x| x.as_str())
+                .unwrap_or("")
+                .lines()
+                .filter_map(|line| line.split_once('=').map(|(name, _)| name.to_string()))
+                .collect()
+        })
+        .collect()
+}
+
+#[switchy_async::test]
+async fn test_env_profile_not_selected_excludes_profiled_vars() {
+    let temp_dir = create_profiled_package();
+
+    let envs = env_names(&temp_dir, None, None).await;
+
+    assert!(!envs.is_empty());
+    for env in envs {
+        assert_eq!(env, vec!["ALWAYS"]);
+    }
+}
+
+#[switchy_async::test]
+async fn test_env_profile_ci_selects_ci_vars() {
+    let temp_dir = create_profiled_package();
+    let profiles = vec!["ci".to_string()];
+    let features = vec!["default".to_string()];
+
+    let envs = env_names(&temp_dir, Some(&features), Some(&profiles)).await;
+
+    assert_eq!(envs, vec![vec!["ALWAYS", "CI_ONLY", "SHARED"]]);
+}
+
+#[switchy_async::test]
+async fn test_env_profile_release_selects_release_vars() {
+    let temp_dir = create_profiled_package();
+    let profiles = vec!["release".to_string()];
+    let features = vec!["default".to_string()];
+
+    let envs = env_names(&temp_dir, Some(&features), Some(&profiles)).await;
+
+    assert_eq!(envs, vec![vec!["ALWAYS", "RELEASE_ONLY", "SHARED"]]);
+}
+
+#[switchy_async::test]
+async fn test_env_profile_and_features_must_both_match() {
+    let temp_dir = create_profiled_package();
+    let ci = vec!["ci".to_string()];
+    let release = vec!["release".to_string()];
+    let telemetry = vec!["telemetry".to_string()];
+
+    let envs = env_names(&temp_dir, Some(&telemetry), Some(&ci)).await;
+    assert_eq!(
+        envs,
+        vec![vec!["ALWAYS", "CI_ONLY", "CI_TELEMETRY", "SHARED"]]
+    );
+
+    let envs = env_names(&temp_dir, Some(&telemetry), Some(&release)).await;
+    assert_eq!(
+        envs,
+        vec![vec!["ALWAYS", "RELEASE_ONLY", "SHARED"]],
+        "Feature-gated var must not be included when its profile is not selected"
+    );
+}
+
+#[switchy_async::test]
+async fn test_env_profile_multiple_profiles_selected() {
+    let temp_dir = create_profiled_package();
+    let profiles = vec!["ci".to_string(), "release".to_string()];
+    let features = vec!["default".to_string()];
+
+    let envs = env_names(&temp_dir, Some(&features), Some(&profiles)).await;
+
+    assert_eq!(
+        envs,
+        vec![vec!["ALWAYS", "CI_ONLY", "RELEASE_ONLY", "SHARED"]]
+    );
+}
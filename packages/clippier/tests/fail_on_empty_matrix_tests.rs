@@ -0,0 +1,126 @@
+//! Tests for the `--fail-on-empty-matrix` guard.
+//!
+//! These tests verify that the features and packages commands return an error listing
+//! the applied filters when the resulting matrix is empty, and succeed otherwise.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+#[cfg(feature = "cargo-workspace")]
+use clippier::{FeaturesOptions, OutputType, handle_packages_command};
+#[cfg(feature = "cargo-workspace")]
+use clippier_test_utilities::{features_matrix, test_resources::load_test_workspace};
+
+#[cfg(feature = "cargo-workspace")]
+async fn packages_matrix(
+    include_if: &[String],
+    fail_on_empty_matrix: bool,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let (temp_dir, _) = load_test_workspace("complex");
+
+    handle_packages_command(
+        temp_dir.path().to_str().unwrap(),
+        Some("ubuntu"),
+        None,
+        None,
+        #[cfg(feature = "git-diff")]
+        None,
+        #[cfg(feature = "git-diff")]
+        None,
+        #[cfg(feature = "git-diff")]
+        false,
+        None,
+        #[cfg(feature = "git-diff")]
+        None,
+        &[],
+        include_if,
+        #[cfg(feature = "_workspace")]
+        None,
+        fail_on_empty_matrix,
+        OutputType::Json,
+    )
+    .await
+}
+
+#[cfg(feature = "cargo-workspace")]
+#[switchy_async::test]
+async fn test_features_empty_matrix_errors_with_flag() {
+    let (temp_dir, _) = load_test_workspace("complex");
+    let result = features_matrix(
+        temp_dir.path(),
+        FeaturesOptions {
+            os: Some("ubuntu".to_string()),
+            packages: Some(vec!["does-not-exist".to_string()]),
+            fail_on_empty_matrix: true,
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let err = result.expect_err("Empty matrix should fail with --fail-on-empty-matrix");
+    let message = err.to_string();
+    assert!(message.contains("Resulting matrix is empty"), "{message}");
+    assert!(message.contains("--os=ubuntu"), "{message}");
+    assert!(message.contains("--packages=does-not-exist"), "{message}");
+}
+
+#[cfg(feature = "cargo-workspace")]
+#[switchy_async::test]
+async fn test_features_empty_matrix_succeeds_without_flag() {
+    let (temp_dir, _) = load_test_workspace("complex");
+    let result = features_matrix(
+        temp_dir.path(),
+        FeaturesOptions {
+            os: Some("ubuntu".to_string()),
+            packages: Some(vec!["does-not-exist".to_string()]),
+            fail_on_empty_matrix: false,
+            ..Default::default()
+        },
+    )
+    .await;
+
+    assert_eq!(result.unwrap(), "[]");
+}
+
+#[cfg(feature = "cargo-workspace")]
+#[switchy_async::test]
+async fn test_features_non_empty_matrix_succeeds_with_flag() {
+    let (temp_dir, _) = load_test_workspace("complex");
+    let result = features_matrix(
+        temp_dir.path(),
+        FeaturesOptions {
+            os: Some("ubuntu".to_string()),
+            packages: Some(vec!["api".to_string()]),
+            fail_on_empty_matrix: true,
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let json: Vec<serde_json::Value> = serde_json::from_str(&result.unwrap()).unwrap();
+    assert!(!json.is_empty());
+}
+
+#[cfg(feature = "cargo-workspace")]
+#[switchy_async::test]
+async fn test_packages_empty_matrix_errors_with_flag() {
+    let result = packages_matrix(&["package.name=does-not-exist".to_string()], true).await;
+
+    let err = result.expect_err("Empty matrix should fail with --fail-on-empty-matrix");
+    let message = err.to_string();
+    assert!(message.contains("Resulting matrix is empty"), "{message}");
+    assert!(
+        message.contains("--include-if=package.name=does-not-exist"),
+        "{message}"
+    );
+}
+
+#[cfg(feature = "cargo-workspace")]
+#[switchy_async::test]
+async fn test_packages_non_empty_matrix_succeeds_with_flag() {
+    let result = packages_matrix(&[], true).await;
+
+    let json: Vec<serde_json::Value> = serde_json::from_str(&result.unwrap()).unwrap();
+    assert!(!json.is_empty());
+}
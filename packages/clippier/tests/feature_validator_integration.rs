@@ -698,6 +698,7 @@ async fn test_validation_result_json_serialization() {
         overridden_errors: vec![],
         override_summary: None,
         parent_results: vec![],
+        ..Default::default()
     };
 
     // Should serialize to valid JSON
@@ -728,6 +729,7 @@ async fn test_validation_summary_pluralization() {
         overridden_errors: vec![],
         override_summary: None,
         parent_results: vec![],
+        ..Default::default()
     };
 
     assert_eq!(result_singular.errors.len(), 1);
@@ -751,6 +753,7 @@ async fn test_validation_summary_pluralization() {
         overridden_errors: vec![],
         override_summary: None,
         parent_results: vec![],
+        ..Default::default()
     };
 
     assert_eq!(result_plural.errors.len(), 2);
@@ -760,3 +763,108 @@ async fn test_validation_summary_pluralization() {
         result_plural.valid_packages + result_plural.errors.len()
     );
 }
+
+#[switchy_async::test]
+async fn test_validation_result_sarif_serialization() {
+    use clippier::feature_validator::{
+        FeatureError, IncorrectPropagation, MissingPropagation, PackageValidationError,
+        SARIF_RULE_INCORRECT_PROPAGATION, SARIF_RULE_MISSING_PROPAGATION, ValidationResult,
+        validation_result_to_sarif,
+    };
+
+    let temp_dir = switchy_fs::tempdir().unwrap();
+    let root_path = temp_dir.path();
+
+    switchy_fs::sync::write(
+        root_path.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"test_pkg\"]\n",
+    )
+    .unwrap();
+    create_package(
+        root_path,
+        "test_pkg",
+        r#"[package]
+name = "test_pkg"
+version = "0.1.0"
+
+[dependencies]
+dep1 = { path = "../dep1" }
+
+[features]
+default = []
+fail-on-warnings = ["nonexistent/feature"]
+"#,
+    );
+
+    let result = ValidationResult {
+        total_packages: 1,
+        valid_packages: 0,
+        errors: vec![PackageValidationError {
+            package: "test_pkg".to_string(),
+            errors: vec![FeatureError {
+                feature: "fail-on-warnings".to_string(),
+                missing_propagations: vec![MissingPropagation {
+                    dependency: "dep1".to_string(),
+                    expected: "dep1/fail-on-warnings".to_string(),
+                    reason: "Dependency has feature but not propagated".to_string(),
+                }],
+                incorrect_propagations: vec![IncorrectPropagation {
+                    entry: "nonexistent/feature".to_string(),
+                    reason: "Dependency doesn't have this feature".to_string(),
+                }],
+            }],
+        }],
+        warnings: vec![],
+        overridden_errors: vec![],
+        override_summary: None,
+        parent_results: vec![],
+        workspace_root: root_path.to_path_buf(),
+        package_paths: std::collections::BTreeMap::from([(
+            "test_pkg".to_string(),
+            "test_pkg".to_string(),
+        )]),
+    };
+
+    let sarif = validation_result_to_sarif(&result);
+
+    assert_eq!(sarif["version"], "2.1.0");
+    let runs = sarif["runs"].as_array().unwrap();
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0]["tool"]["driver"]["name"], "clippier");
+
+    let rule_ids: Vec<&str> = runs[0]["tool"]["driver"]["rules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|rule| rule["id"].as_str().unwrap())
+        .collect();
+    assert!(rule_ids.contains(&SARIF_RULE_MISSING_PROPAGATION));
+    assert!(rule_ids.contains(&SARIF_RULE_INCORRECT_PROPAGATION));
+
+    let results = runs[0]["results"].as_array().unwrap();
+    let result_rule_ids: Vec<&str> = results
+        .iter()
+        .map(|result| result["ruleId"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        result_rule_ids,
+        vec![
+            SARIF_RULE_MISSING_PROPAGATION,
+            SARIF_RULE_INCORRECT_PROPAGATION
+        ]
+    );
+
+    for result in results {
+        assert_eq!(result["level"], "error");
+        assert!(
+            result["message"]["text"]
+                .as_str()
+                .unwrap()
+                .contains("fail-on-warnings")
+        );
+
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "test_pkg/Cargo.toml");
+        assert_eq!(location["region"]["startLine"], 10);
+    }
+}
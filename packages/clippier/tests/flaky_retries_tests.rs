@@ -0,0 +1,117 @@
+//! Tests for the `flaky` and `flaky-retries` `clippier.toml` fields.
+//!
+//! These tests verify that matrix entries carry a `retries` field only for packages
+//! declaring flakiness, with the configured count or the default.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::DEFAULT_FLAKY_RETRIES;
+
+/// Creates a single package with two OS configs and the given top-level `clippier.toml`
+/// fields
+fn create_package(fields: &str) -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+
+    switchy_fs::sync::write(
+        temp_dir.path().join("Cargo.toml"),
+        r#"
+[package]
+name = "network_client"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+default = []
+tls = []
+"#,
+    )
+    .unwrap();
+    switchy_fs::sync::write(
+        temp_dir.path().join("clippier.toml"),
+        format!(
+            r#"
+{fields}
+
+[[config]]
+os = "ubuntu"
+
+[[config]]
+os = "windows"
+"#
+        ),
+    )
+    .unwrap();
+    switchy_fs::sync::create_dir_all(temp_dir.path().join("src")).unwrap();
+    switchy_fs::sync::write(temp_dir.path().join("src/lib.rs"), "// test lib").unwrap();
+
+    temp_dir
+}
+
+/// Returns the `retries` field of every generated job
+async fn retries(temp_dir: &switchy_fs::TempDir) -> Vec<Option<u64>> {
+    let jobs = clippier::process_configs(temp_dir.path(), &clippier::FeaturesOptions::default())
+        .await
+        .unwrap();
+
+    assert!(!jobs.is_empty());
+
+    jobs.iter()
+        .map(|job| job.get("retries").map(|x| x.as_u64().unwrap()))
+        .collect()
+}
+
+#[switchy_async::test]
+async fn test_no_retries_without_flaky() {
+    let temp_dir = create_package("");
+
+    for retries in retries(&temp_dir).await {
+        assert_eq!(retries, None);
+    }
+}
+
+#[switchy_async::test]
+async fn test_flaky_uses_default_retries() {
+    let temp_dir = create_package("flaky = true");
+
+    for retries in retries(&temp_dir).await {
+        assert_eq!(retries, Some(u64::from(DEFAULT_FLAKY_RETRIES)));
+    }
+}
+
+#[switchy_async::test]
+async fn test_flaky_retries_sets_the_count() {
+    let temp_dir = create_package("flaky = true\nflaky-retries = 5");
+
+    for retries in retries(&temp_dir).await {
+        assert_eq!(retries, Some(5));
+    }
+}
+
+#[switchy_async::test]
+async fn test_flaky_retries_implies_flaky() {
+    let temp_dir = create_package("flaky-retries = 3");
+
+    for retries in retries(&temp_dir).await {
+        assert_eq!(retries, Some(3));
+    }
+}
+
+#[switchy_async::test]
+async fn test_flaky_false_disables_retries() {
+    let temp_dir = create_package("flaky = false\nflaky-retries = 3");
+
+    for retries in retries(&temp_dir).await {
+        assert_eq!(retries, None);
+    }
+}
+
+#[switchy_async::test]
+async fn test_zero_flaky_retries_disables_retries() {
+    let temp_dir = create_package("flaky-retries = 0");
+
+    for retries in retries(&temp_dir).await {
+        assert_eq!(retries, None);
+    }
+}
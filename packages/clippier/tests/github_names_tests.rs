@@ -0,0 +1,159 @@
+//! Tests for annotating features matrix entries with GitHub Actions display names.
+//!
+//! These tests verify the `displayName` format composed by `--github-names` and that long
+//! feature lists are truncated with an ellipsis to fit GitHub's name-length limit.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::{FeaturesOptions, GITHUB_NAME_MAX_LENGTH, github_display_name};
+use clippier_test_utilities::features_matrix;
+
+/// Builds a matrix entry with the given name, features and OS
+fn entry(name: &str, features: &[&str], os: &str) -> serde_json::Map<String, serde_json::Value> {
+    serde_json::json!({
+        "name": name,
+        "features": features,
+        "os": os,
+    })
+    .as_object()
+    .unwrap()
+    .clone()
+}
+
+/// Creates a single package with the given features and an `ubuntu` config
+fn create_package(features: &[&str]) -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+
+    let features = features
+        .iter()
+        .map(|feature| format!("{feature} = []"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    switchy_fs::sync::write(
+        temp_dir.path().join("Cargo.toml"),
+        format!(
+            r#"
+[package]
+name = "moosicbox_player"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+default = []
+{features}
+"#
+        ),
+    )
+    .unwrap();
+    switchy_fs::sync::write(
+        temp_dir.path().join("clippier.toml"),
+        r#"
+[[config]]
+os = "ubuntu"
+"#,
+    )
+    .unwrap();
+    switchy_fs::sync::create_dir_all(temp_dir.path().join("src")).unwrap();
+    switchy_fs::sync::write(temp_dir.path().join("src/lib.rs"), "// test lib").unwrap();
+
+    temp_dir
+}
+
+/// Returns each entry's `displayName`
+async fn display_names(temp_dir: &switchy_fs::TempDir, chunked: Option<u16>) -> Vec<String> {
+    let result = features_matrix(
+        temp_dir.path(),
+        FeaturesOptions {
+            chunked,
+            skip_features: Some("default".to_string()),
+            github_names: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let matrix: Vec<serde_json::Map<String, serde_json::Value>> =
+        serde_json::from_str(&result).unwrap();
+
+    matrix
+        .into_iter()
+        .map(|entry| entry["displayName"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test_log::test]
+fn test_display_name_combines_package_features_and_os() {
+    assert_eq!(
+        github_display_name(&entry("moosicbox_player", &["flac", "mp3"], "ubuntu")),
+        "moosicbox_player [flac,mp3] ubuntu"
+    );
+}
+
+#[test_log::test]
+fn test_display_name_omits_empty_feature_chunk() {
+    assert_eq!(
+        github_display_name(&entry("moosicbox_player", &[], "macos")),
+        "moosicbox_player macos"
+    );
+}
+
+#[test_log::test]
+fn test_display_name_truncates_long_feature_list_with_ellipsis() {
+    let features = (0..40).map(|i| format!("feature-{i}")).collect::<Vec<_>>();
+    let features = features.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let display_name = github_display_name(&entry("moosicbox_player", &features, "windows"));
+
+    assert_eq!(display_name.chars().count(), GITHUB_NAME_MAX_LENGTH);
+    assert!(
+        display_name.starts_with("moosicbox_player [feature-0,feature-1,"),
+        "{display_name}"
+    );
+    // The package and OS are kept, only the features are abbreviated
+    assert!(display_name.ends_with("…] windows"), "{display_name}");
+}
+
+#[test_log::test]
+fn test_display_name_truncates_long_package_name() {
+    let name = "a".repeat(GITHUB_NAME_MAX_LENGTH);
+
+    let display_name = github_display_name(&entry(&name, &["flac"], "ubuntu"));
+
+    assert_eq!(display_name.chars().count(), GITHUB_NAME_MAX_LENGTH);
+    assert!(display_name.ends_with('…'), "{display_name}");
+}
+
+#[switchy_async::test]
+async fn test_features_command_adds_display_names() {
+    let temp_dir = create_package(&["flac", "mp3", "opus"]);
+
+    let display_names = display_names(&temp_dir, Some(2)).await;
+
+    assert_eq!(
+        display_names,
+        vec![
+            "moosicbox_player [flac,mp3] ubuntu".to_string(),
+            "moosicbox_player [opus] ubuntu".to_string(),
+        ]
+    );
+}
+
+#[switchy_async::test]
+async fn test_features_command_truncates_long_feature_chunks() {
+    let features = (0..30).map(|i| format!("codec-{i}")).collect::<Vec<_>>();
+    let temp_dir = create_package(&features.iter().map(String::as_str).collect::<Vec<_>>());
+
+    let display_names = display_names(&temp_dir, None).await;
+
+    assert_eq!(display_names.len(), 1);
+    assert_eq!(display_names[0].chars().count(), GITHUB_NAME_MAX_LENGTH);
+    assert!(
+        display_names[0].ends_with("…] ubuntu"),
+        "{}",
+        display_names[0]
+    );
+}
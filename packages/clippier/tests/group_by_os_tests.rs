@@ -0,0 +1,154 @@
+//! Tests for grouping the features matrix by OS.
+//!
+//! These tests verify that `--group-by-os` restructures the matrix into an object keyed by
+//! OS, and that each group contains exactly the entries generated for that OS.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use std::collections::BTreeMap;
+
+use clippier::{FeaturesOptions, OutputType, group_features_matrix_by_os, handle_features_command};
+use clippier_test_utilities::features_matrix;
+
+const SERVER_CLIPPIER: &str = r#"
+[[config]]
+os = "ubuntu"
+
+[[config]]
+os = "macos"
+"#;
+
+const CLI_CLIPPIER: &str = r#"
+[[config]]
+os = "ubuntu"
+"#;
+
+/// Creates a workspace where `server` builds on ubuntu and macos and `cli` only on ubuntu
+fn create_two_os_workspace() -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+
+    switchy_fs::sync::write(
+        temp_dir.path().join("Cargo.toml"),
+        r#"
+[workspace]
+members = ["packages/server", "packages/cli"]
+"#,
+    )
+    .unwrap();
+
+    for (package, clippier_toml) in [("server", SERVER_CLIPPIER), ("cli", CLI_CLIPPIER)] {
+        let pkg_dir = temp_dir.path().join("packages").join(package);
+        switchy_fs::sync::create_dir_all(pkg_dir.join("src")).unwrap();
+
+        switchy_fs::sync::write(
+            pkg_dir.join("Cargo.toml"),
+            format!(
+                r#"
+[package]
+name = "{package}"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+default = []
+fast = []
+"#
+            ),
+        )
+        .unwrap();
+        switchy_fs::sync::write(pkg_dir.join("clippier.toml"), clippier_toml).unwrap();
+        switchy_fs::sync::write(pkg_dir.join("src/lib.rs"), "// test lib").unwrap();
+    }
+
+    temp_dir
+}
+
+type Entry = serde_json::Map<String, serde_json::Value>;
+
+fn names(entries: &[Entry]) -> Vec<&str> {
+    entries
+        .iter()
+        .map(|x| x.get("name").and_then(|x| x.as_str()).unwrap())
+        .collect()
+}
+
+#[switchy_async::test]
+async fn test_group_by_os_groups_entries_per_os() {
+    let temp_dir = create_two_os_workspace();
+
+    let result = features_matrix(
+        temp_dir.path(),
+        FeaturesOptions {
+            group_by_os: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let groups: BTreeMap<String, Vec<Entry>> = serde_json::from_str(&result).unwrap();
+
+    assert_eq!(
+        groups.keys().collect::<Vec<_>>(),
+        vec!["macos", "ubuntu"],
+        "{result}"
+    );
+
+    let mut ubuntu = names(&groups["ubuntu"]);
+    ubuntu.sort_unstable();
+    assert_eq!(ubuntu, vec!["cli", "server"]);
+    assert_eq!(names(&groups["macos"]), vec!["server"]);
+
+    for (os, entries) in &groups {
+        assert!(
+            entries
+                .iter()
+                .all(|x| x.get("os").and_then(|x| x.as_str()) == Some(os.as_str())),
+            "{result}"
+        );
+    }
+}
+
+#[switchy_async::test]
+async fn test_group_by_os_contains_the_flat_matrix() {
+    let temp_dir = create_two_os_workspace();
+
+    let flat = features_matrix(temp_dir.path(), FeaturesOptions::default())
+        .await
+        .unwrap();
+    let flat: Vec<Entry> = serde_json::from_str(&flat).unwrap();
+    // Grouped output is always JSON, even with raw output
+    let grouped = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        group_by_os: true,
+        output: OutputType::Raw,
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    let grouped: BTreeMap<String, Vec<Entry>> = serde_json::from_str(&grouped).unwrap();
+
+    assert_eq!(grouped, group_features_matrix_by_os(flat.clone()));
+    assert_eq!(grouped.values().map(Vec::len).sum::<usize>(), flat.len());
+}
+
+#[switchy_async::test]
+async fn test_group_by_os_respects_os_filter() {
+    let temp_dir = create_two_os_workspace();
+
+    let result = features_matrix(
+        temp_dir.path(),
+        FeaturesOptions {
+            os: Some("macos".to_string()),
+            group_by_os: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let groups: BTreeMap<String, Vec<Entry>> = serde_json::from_str(&result).unwrap();
+
+    assert_eq!(groups.keys().collect::<Vec<_>>(), vec!["macos"], "{result}");
+    assert_eq!(names(&groups["macos"]), vec!["server"]);
+}
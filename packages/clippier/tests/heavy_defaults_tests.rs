@@ -0,0 +1,371 @@
+//! Tests for heavy default dependency detection.
+//!
+//! These tests verify that optional dependencies enabled by `default`, through `dep:`,
+//! `dep/feature` or implicit features, are flagged with the feature chain that enables
+//! them when their `Cargo.lock` dependency tree is large enough, that weak
+//! `dep?/feature` references don't count as enabling a dependency, and that packages using
+//! such a dependency with its default features are reported.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::heavy_defaults::{
+    HeavyDefaultDependency, HeavyDefaultFeaturesUse, find_heavy_default_dependencies,
+    format_heavy_defaults_report,
+};
+
+const WORKSPACE: &str = r#"
+[workspace]
+members = ["packages/*"]
+
+[workspace.dependencies]
+reqwest = { version = "0.12.0", default-features = false }
+"#;
+
+const MODELS: &str = r#"
+[package]
+name = "models"
+version = "0.1.0"
+
+[dependencies]
+log = { version = "0.4.0", optional = true }
+reqwest = { workspace = true, optional = true }
+serde_json = { version = "1.0.0", optional = true }
+
+[features]
+default = ["json", "logging", "net"]
+json = ["reqwest?/json", "serde_json?/std"]
+logging = ["log"]
+net = ["dep:reqwest"]
+"#;
+
+const SERVER: &str = r#"
+[package]
+name = "server"
+version = "0.1.0"
+
+[dependencies]
+models = { path = "../models", default-features = false }
+
+[target.'cfg(unix)'.dependencies]
+rt = { package = "tokio", version = "1.0.0", optional = true }
+
+[features]
+default = ["rt/full"]
+"#;
+
+const CARGO_LOCK: &str = r#"
+version = 4
+
+[[package]]
+name = "bytes"
+version = "1.0.0"
+
+[[package]]
+name = "http"
+version = "1.0.0"
+dependencies = ["bytes"]
+
+[[package]]
+name = "hyper"
+version = "1.0.0"
+dependencies = ["http", "tokio"]
+
+[[package]]
+name = "libc"
+version = "0.2.0"
+
+[[package]]
+name = "log"
+version = "0.4.0"
+
+[[package]]
+name = "mio"
+version = "1.0.0"
+dependencies = ["libc"]
+
+[[package]]
+name = "models"
+version = "0.1.0"
+dependencies = ["log", "reqwest", "serde_json"]
+
+[[package]]
+name = "reqwest"
+version = "0.12.0"
+dependencies = [
+ "hyper",
+ "serde 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)",
+ "tokio",
+]
+
+[[package]]
+name = "serde"
+version = "1.0.0"
+
+[[package]]
+name = "serde_json"
+version = "1.0.0"
+dependencies = ["serde 1.0.0"]
+
+[[package]]
+name = "server"
+version = "0.1.0"
+dependencies = ["models", "tokio"]
+
+[[package]]
+name = "tokio"
+version = "1.0.0"
+dependencies = ["bytes", "mio"]
+"#;
+
+/// Creates a workspace with a models and a server package, optionally with a `Cargo.lock`
+fn create_workspace(with_lock: bool) -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+
+    switchy_fs::sync::write(root.join("Cargo.toml"), WORKSPACE).unwrap();
+    if with_lock {
+        switchy_fs::sync::write(root.join("Cargo.lock"), CARGO_LOCK).unwrap();
+    }
+
+    for (name, cargo_toml) in [("models", MODELS), ("server", SERVER)] {
+        let package_dir = root.join("packages").join(name);
+        switchy_fs::sync::create_dir_all(package_dir.join("src")).unwrap();
+        switchy_fs::sync::write(package_dir.join("Cargo.toml"), cargo_toml).unwrap();
+        switchy_fs::sync::write(package_dir.join("src/lib.rs"), "// test lib").unwrap();
+    }
+
+    temp_dir
+}
+
+fn chain(entries: &[&str]) -> Vec<String> {
+    entries.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn test_heavy_defaults_flags_optional_deps_enabled_by_default() {
+    let temp_dir = create_workspace(true);
+
+    let report = find_heavy_default_dependencies(temp_dir.path(), 3, &[]).unwrap();
+
+    assert_eq!(report.packages_checked, 2);
+    assert_eq!(
+        report.flagged,
+        vec![
+            HeavyDefaultDependency {
+                package: "models".to_string(),
+                dependency: "reqwest".to_string(),
+                transitive_dependencies: 7,
+                chain: chain(&["default", "net", "dep:reqwest"]),
+            },
+            HeavyDefaultDependency {
+                package: "server".to_string(),
+                dependency: "rt".to_string(),
+                transitive_dependencies: 3,
+                chain: chain(&["default", "rt/full"]),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_heavy_defaults_reports_light_and_implicit_deps_below_threshold() {
+    let temp_dir = create_workspace(true);
+
+    let report = find_heavy_default_dependencies(temp_dir.path(), 0, &[]).unwrap();
+
+    let log = report
+        .flagged
+        .iter()
+        .find(|x| x.dependency == "log")
+        .expect("log is enabled through its implicit feature");
+    assert_eq!(log.transitive_dependencies, 0);
+    assert_eq!(log.chain, chain(&["default", "logging", "log"]));
+
+    // `serde_json?/std` only forwards a feature, it doesn't enable `serde_json`
+    assert!(!report.flagged.iter().any(|x| x.dependency == "serde_json"));
+}
+
+#[test]
+fn test_heavy_defaults_ignores_dependencies() {
+    let temp_dir = create_workspace(true);
+
+    let report =
+        find_heavy_default_dependencies(temp_dir.path(), 3, &["reqwest".to_string()]).unwrap();
+
+    let dependencies: Vec<&str> = report
+        .flagged
+        .iter()
+        .map(|x| x.dependency.as_str())
+        .collect();
+    assert_eq!(dependencies, vec!["rt"]);
+}
+
+#[test]
+fn test_heavy_defaults_without_cargo_lock() {
+    let temp_dir = create_workspace(false);
+
+    let report = find_heavy_default_dependencies(temp_dir.path(), 1, &[]).unwrap();
+
+    assert!(report.is_clean());
+    assert!(format_heavy_defaults_report(&report).contains(
+        "✅ No default features pull in optional dependencies with 1+ transitive dependencies"
+    ));
+}
+
+#[test]
+fn test_heavy_defaults_report_formatting() {
+    let temp_dir = create_workspace(true);
+
+    let report = find_heavy_default_dependencies(temp_dir.path(), 3, &[]).unwrap();
+    let output = format_heavy_defaults_report(&report);
+
+    assert!(
+        output.contains("Found 2 heavy optional dependencies enabled by default"),
+        "{output}"
+    );
+    assert!(
+        output.contains(
+            "📦 models: reqwest (7 transitive dependencies) via default → net → dep:reqwest"
+        ),
+        "{output}"
+    );
+    assert!(
+        output.contains("📦 server: rt (3 transitive dependencies) via default → rt/full"),
+        "{output}"
+    );
+}
+
+const CONSUMERS_WORKSPACE: &str = r#"
+[workspace]
+members = ["packages/*"]
+
+[workspace.dependencies]
+models = { path = "packages/models" }
+models_lean = { package = "models", path = "packages/models", default-features = false }
+reqwest = { version = "0.12.0", default-features = false }
+"#;
+
+const APP: &str = r#"
+[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+models = { workspace = true, default-features = false }
+"#;
+
+const CLI: &str = r#"
+[package]
+name = "cli"
+version = "0.1.0"
+
+[dependencies]
+models_lean = { workspace = true }
+"#;
+
+const WEB: &str = r#"
+[package]
+name = "web"
+version = "0.1.0"
+
+[dependencies]
+models = { path = "../models" }
+"#;
+
+/// Creates a workspace whose packages use `models`, which has heavy defaults, with and
+/// without its default features
+fn create_consumers_workspace() -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+
+    switchy_fs::sync::write(root.join("Cargo.toml"), CONSUMERS_WORKSPACE).unwrap();
+    switchy_fs::sync::write(root.join("Cargo.lock"), CARGO_LOCK).unwrap();
+
+    for (name, cargo_toml) in [
+        ("app", APP),
+        ("cli", CLI),
+        ("models", MODELS),
+        ("server", SERVER),
+        ("web", WEB),
+    ] {
+        let package_dir = root.join("packages").join(name);
+        switchy_fs::sync::create_dir_all(package_dir.join("src")).unwrap();
+        switchy_fs::sync::write(package_dir.join("Cargo.toml"), cargo_toml).unwrap();
+        switchy_fs::sync::write(package_dir.join("src/lib.rs"), "// test lib").unwrap();
+    }
+
+    temp_dir
+}
+
+#[test]
+fn test_heavy_defaults_flags_workspace_deps_used_with_heavy_defaults() {
+    let temp_dir = create_consumers_workspace();
+
+    let report = find_heavy_default_dependencies(temp_dir.path(), 3, &[]).unwrap();
+
+    // `app` can't disable the defaults the workspace dependency keeps, while `cli`
+    // inherits disabled defaults and `server` disables them itself
+    assert_eq!(
+        report.default_features_uses,
+        vec![
+            HeavyDefaultFeaturesUse {
+                package: "app".to_string(),
+                dependency: "models".to_string(),
+                heavy_dependencies: vec!["reqwest".to_string()],
+                transitive_dependencies: 7,
+            },
+            HeavyDefaultFeaturesUse {
+                package: "web".to_string(),
+                dependency: "models".to_string(),
+                heavy_dependencies: vec!["reqwest".to_string()],
+                transitive_dependencies: 7,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_heavy_defaults_counts_shared_transitive_deps_of_heavy_defaults_once() {
+    let temp_dir = create_consumers_workspace();
+
+    let report = find_heavy_default_dependencies(temp_dir.path(), 0, &[]).unwrap();
+    let web = report
+        .default_features_uses
+        .iter()
+        .find(|x| x.package == "web")
+        .unwrap();
+
+    assert_eq!(web.heavy_dependencies, vec!["log", "reqwest"]);
+    assert_eq!(web.transitive_dependencies, 7);
+}
+
+#[test]
+fn test_heavy_defaults_ignored_dependencies_are_not_reported_for_users() {
+    let temp_dir = create_consumers_workspace();
+
+    let report =
+        find_heavy_default_dependencies(temp_dir.path(), 3, &["reqwest".to_string()]).unwrap();
+
+    assert!(report.default_features_uses.is_empty());
+}
+
+#[test]
+fn test_heavy_defaults_report_formats_workspace_deps_used_with_heavy_defaults() {
+    let temp_dir = create_consumers_workspace();
+
+    let report = find_heavy_default_dependencies(temp_dir.path(), 3, &[]).unwrap();
+    let output = format_heavy_defaults_report(&report);
+
+    assert!(
+        output.contains("Found 2 workspace dependencies used with heavy default features"),
+        "{output}"
+    );
+    assert!(
+        output.contains(
+            "📦 web: models pulls in reqwest (7 transitive dependencies), consider `default-features = false`"
+        ),
+        "{output}"
+    );
+}
@@ -1,4 +1,7 @@
-use clippier::{find_affected_packages, find_affected_packages_with_reasoning};
+use clippier::{
+    CLIPPIER_IGNORE_FILE, find_affected_packages, find_affected_packages_with_reasoning,
+    load_ignore_patterns,
+};
 use clippier_test_utilities::test_resources::load_test_workspace;
 
 #[switchy_async::test]
@@ -336,3 +339,84 @@ async fn test_multiple_negation_patterns() {
     assert!(!packages.contains(&"core".to_string()));
     assert!(!packages.contains(&"cli".to_string()));
 }
+
+fn write_clippierignore(temp_dir: &switchy_fs::TempDir, contents: &str) {
+    switchy_fs::sync::write(temp_dir.path().join(CLIPPIER_IGNORE_FILE), contents).unwrap();
+}
+
+#[switchy_async::test]
+async fn test_clippierignore_skips_comments_and_blank_lines() {
+    let (temp_dir, _) = load_test_workspace("complex");
+    write_clippierignore(
+        &temp_dir,
+        "# Documentation\n**/*.md\n\n  # Except the important bits\n!**/IMPORTANT.md  \n",
+    );
+
+    let patterns = load_ignore_patterns(temp_dir.path(), &["**/*.txt".to_string()]).unwrap();
+
+    assert_eq!(
+        patterns,
+        vec![
+            "**/*.md".to_string(),
+            "!**/IMPORTANT.md".to_string(),
+            "**/*.txt".to_string(),
+        ]
+    );
+}
+
+#[switchy_async::test]
+async fn test_clippierignore_matches_equivalent_cli_patterns() {
+    let changed_files = vec![
+        "packages/core/README.md".to_string(),
+        "packages/api/IMPORTANT.md".to_string(),
+        "packages/web/CHANGELOG.txt".to_string(),
+    ];
+
+    let (from_file, _) = load_test_workspace("complex");
+    write_clippierignore(&from_file, "# Docs\n**/*.md\n!**/IMPORTANT.md\n**/*.txt\n");
+    let (from_cli, _) = load_test_workspace("complex");
+    let cli_patterns = vec![
+        "**/*.md".to_string(),
+        "!**/IMPORTANT.md".to_string(),
+        "**/*.txt".to_string(),
+    ];
+
+    let file_packages = find_affected_packages(from_file.path(), &changed_files, &[]).unwrap();
+    let cli_packages =
+        find_affected_packages(from_cli.path(), &changed_files, &cli_patterns).unwrap();
+
+    assert_eq!(file_packages, vec!["api"]);
+    assert_eq!(file_packages, cli_packages);
+
+    let file_reasoning =
+        find_affected_packages_with_reasoning(from_file.path(), &changed_files, &[]).unwrap();
+    let cli_reasoning =
+        find_affected_packages_with_reasoning(from_cli.path(), &changed_files, &cli_patterns)
+            .unwrap();
+
+    assert_eq!(
+        file_reasoning.iter().map(|x| &x.name).collect::<Vec<_>>(),
+        cli_reasoning.iter().map(|x| &x.name).collect::<Vec<_>>()
+    );
+}
+
+#[switchy_async::test]
+async fn test_cli_patterns_take_precedence_over_clippierignore() {
+    let (temp_dir, _) = load_test_workspace("complex");
+    write_clippierignore(&temp_dir, "**/*.md\n");
+    let changed_files = vec![
+        "packages/core/README.md".to_string(),
+        "packages/api/IMPORTANT.md".to_string(),
+    ];
+
+    let packages = find_affected_packages(temp_dir.path(), &changed_files, &[]).unwrap();
+    assert!(packages.is_empty(), "{packages:?}");
+
+    let packages = find_affected_packages(
+        temp_dir.path(),
+        &changed_files,
+        &["!**/IMPORTANT.md".to_string()],
+    )
+    .unwrap();
+    assert_eq!(packages, vec!["api"]);
+}
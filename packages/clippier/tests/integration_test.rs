@@ -187,42 +187,20 @@ async fn test_no_transitive_impact() {
 
 #[switchy_async::test]
 async fn test_git_submodules_with_chunking_and_spreading() {
-    use clippier::{OutputType, handle_features_command};
+    use clippier::{FeaturesOptions, OutputType, handle_features_command};
     use clippier_test_utilities::test_resources::load_test_workspace;
 
     let (temp_dir, _) = load_test_workspace("git-submodules");
 
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        Some(2),
-        None,
-        true,
-        true,
-        Some(12345),
-        None,
-        None,
-        None,
-        None,
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        max_parallel: Some(2),
+        spread: true,
+        randomize: true,
+        seed: Some(12345),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await
     .unwrap();
 
@@ -0,0 +1,126 @@
+//! Tests for listing a package's features with their transitive enablement.
+//!
+//! These tests verify that intra-package feature references are expanded recursively and
+//! that the optional dependencies activated by each feature are reported.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use std::collections::BTreeSet;
+
+use clippier::{
+    FeatureEnablement, OutputType, handle_list_features_command, list_package_features,
+};
+
+const PACKAGE: &str = r#"
+[package]
+name = "player"
+version = "0.1.0"
+
+[dependencies]
+log = { version = "0.4", optional = true }
+rand = { version = "0.8", optional = true }
+serde = { version = "1" }
+tokio = { version = "1", optional = true }
+
+[features]
+default = ["full"]
+full = ["playback", "log"]
+playback = ["decoder", "serde/std", "rand?/std"]
+decoder = ["dep:tokio"]
+"#;
+
+fn set(values: &[&str]) -> BTreeSet<String> {
+    values.iter().map(ToString::to_string).collect()
+}
+
+fn feature<'a>(features: &'a [FeatureEnablement], name: &str) -> &'a FeatureEnablement {
+    features
+        .iter()
+        .find(|x| x.name == name)
+        .unwrap_or_else(|| panic!("Missing feature {name}"))
+}
+
+#[test]
+fn test_list_features_expands_transitive_chain() {
+    let cargo_toml: toml::Value = toml::from_str(PACKAGE).unwrap();
+
+    let features = list_package_features(&cargo_toml);
+
+    assert_eq!(
+        features.iter().map(|x| x.name.as_str()).collect::<Vec<_>>(),
+        vec!["decoder", "default", "full", "playback"]
+    );
+
+    // `full` chains through `playback` and `decoder`
+    assert_eq!(
+        *feature(&features, "full"),
+        FeatureEnablement {
+            name: "full".to_string(),
+            enables: set(&["decoder", "playback"]),
+            dependency_features: set(&["rand?/std", "serde/std"]),
+            optional_dependencies: set(&["log", "tokio"]),
+        }
+    );
+    assert_eq!(
+        feature(&features, "default").enables,
+        set(&["decoder", "full", "playback"])
+    );
+}
+
+#[test]
+fn test_list_features_weak_dependency_features_do_not_activate_dependencies() {
+    let cargo_toml: toml::Value = toml::from_str(PACKAGE).unwrap();
+
+    let features = list_package_features(&cargo_toml);
+    let playback = feature(&features, "playback");
+
+    assert_eq!(playback.enables, set(&["decoder"]));
+    assert_eq!(playback.optional_dependencies, set(&["tokio"]));
+}
+
+#[test]
+fn test_list_features_without_features_table() {
+    let cargo_toml: toml::Value = toml::from_str(
+        r#"
+[package]
+name = "empty"
+version = "0.1.0"
+"#,
+    )
+    .unwrap();
+
+    assert!(list_package_features(&cargo_toml).is_empty());
+}
+
+#[test]
+fn test_list_features_command_output() {
+    let temp_dir = switchy_fs::tempdir().unwrap();
+    let root = temp_dir.path();
+
+    switchy_fs::sync::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"packages/player\"]\n",
+    )
+    .unwrap();
+    switchy_fs::sync::create_dir_all(root.join("packages/player/src")).unwrap();
+    switchy_fs::sync::write(root.join("packages/player/Cargo.toml"), PACKAGE).unwrap();
+    switchy_fs::sync::write(root.join("packages/player/src/lib.rs"), "// test lib").unwrap();
+
+    let json = handle_list_features_command(root, "player", OutputType::Json).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value.as_array().unwrap().len(), 4);
+    assert_eq!(value[2]["name"], "full");
+    assert_eq!(
+        value[2]["enables"],
+        serde_json::json!(["decoder", "playback"])
+    );
+
+    let raw = handle_list_features_command(root, "player", OutputType::Raw).unwrap();
+    assert!(raw.contains("player exposes 4 features"), "{raw}");
+    assert!(raw.contains("  optional dependencies: log, tokio"), "{raw}");
+
+    let missing = handle_list_features_command(root, "missing", OutputType::Raw);
+    assert!(missing.is_err());
+}
@@ -0,0 +1,190 @@
+//! Tests for comparing two features matrices.
+//!
+//! These tests verify that jobs are keyed by package, OS and normalized feature set, and
+//! that the added, removed and changed buckets are computed correctly for both flat and
+//! OS-grouped matrices.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::{
+    MatrixDiff, MatrixJob, PackageMatrixChange, diff_matrices, format_matrix_diff,
+    handle_matrix_diff_command, parse_matrix_jobs,
+};
+
+const BEFORE: &str = r#"[
+    {"name": "moosicbox_core", "os": "ubuntu", "features": ["default"], "path": "packages/core"},
+    {"name": "moosicbox_core", "os": "ubuntu", "features": ["serde", "db"], "path": "packages/core"},
+    {"name": "moosicbox_player", "os": "ubuntu", "features": ["cpal"]},
+    {"name": "moosicbox_player", "os": "macos", "features": ["cpal"]},
+    {"name": "moosicbox_legacy", "os": "ubuntu", "features": []}
+]"#;
+
+const AFTER: &str = r#"[
+    {"name": "moosicbox_core", "os": "ubuntu", "features": ["db", "serde"], "path": "packages/core", "env": "FOO=1"},
+    {"name": "moosicbox_core", "os": "ubuntu", "features": ["default"]},
+    {"name": "moosicbox_player", "os": "ubuntu", "features": ["cpal", "opus"]},
+    {"name": "moosicbox_player", "os": "ubuntu", "features": ["aac"]},
+    {"name": "moosicbox_player", "os": "macos", "features": ["cpal"]},
+    {"name": "moosicbox_server", "os": "ubuntu", "features": ["default"]}
+]"#;
+
+fn jobs(matrix: &str) -> Vec<MatrixJob> {
+    parse_matrix_jobs(&serde_json::from_str(matrix).unwrap()).unwrap()
+}
+
+fn job(package: &str, os: &str, features: &[&str]) -> MatrixJob {
+    MatrixJob {
+        package: package.to_string(),
+        os: os.to_string(),
+        features: features.iter().map(ToString::to_string).collect(),
+    }
+}
+
+fn fixture_diff() -> MatrixDiff {
+    diff_matrices(&jobs(BEFORE), &jobs(AFTER))
+}
+
+#[test]
+fn test_features_are_normalized() {
+    assert_eq!(
+        jobs(r#"[{"name": "a", "os": "ubuntu", "features": ["z", "a", "z"]}]"#),
+        vec![job("a", "ubuntu", &["a", "z"])]
+    );
+}
+
+#[test]
+fn test_added_jobs() {
+    let diff = fixture_diff();
+
+    assert_eq!(diff.jobs_before, 5);
+    assert_eq!(diff.jobs_after, 6);
+    assert_eq!(
+        diff.added,
+        vec![
+            job("moosicbox_player", "ubuntu", &["aac"]),
+            job("moosicbox_player", "ubuntu", &["cpal", "opus"]),
+            job("moosicbox_server", "ubuntu", &["default"]),
+        ]
+    );
+}
+
+#[test]
+fn test_removed_jobs() {
+    let diff = fixture_diff();
+
+    assert_eq!(
+        diff.removed,
+        vec![
+            job("moosicbox_legacy", "ubuntu", &[]),
+            job("moosicbox_player", "ubuntu", &["cpal"]),
+        ]
+    );
+}
+
+#[test]
+fn test_changed_packages() {
+    let diff = fixture_diff();
+
+    // moosicbox_core only reordered its jobs and features, and the macOS player job is
+    // untouched, so neither is reported as changed
+    assert_eq!(
+        diff.changed,
+        vec![PackageMatrixChange {
+            package: "moosicbox_player".to_string(),
+            os: "ubuntu".to_string(),
+            jobs_before: 1,
+            jobs_after: 2,
+            features_added: vec!["aac".to_string(), "opus".to_string()],
+            features_removed: vec![],
+        }]
+    );
+}
+
+#[test]
+fn test_identical_matrices_have_empty_diff() {
+    let diff = diff_matrices(&jobs(BEFORE), &jobs(BEFORE));
+
+    assert!(diff.is_empty());
+    assert!(diff.changed.is_empty());
+    assert!(format_matrix_diff(&diff).contains("No job changes"));
+}
+
+#[test]
+fn test_duplicate_jobs_are_counted() {
+    let before = vec![job("a", "ubuntu", &["x"])];
+    let after = vec![job("a", "ubuntu", &["x"]), job("a", "ubuntu", &["x"])];
+
+    let diff = diff_matrices(&before, &after);
+
+    assert_eq!(diff.added, vec![job("a", "ubuntu", &["x"])]);
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(diff.changed[0].jobs_before, 1);
+    assert_eq!(diff.changed[0].jobs_after, 2);
+}
+
+#[test]
+fn test_grouped_by_os_matrix_matches_flat_matrix() {
+    let grouped = r#"{
+        "macos": [{"name": "moosicbox_player", "os": "macos", "features": ["cpal"]}],
+        "ubuntu": [
+            {"name": "moosicbox_core", "os": "ubuntu", "features": ["serde", "db"]},
+            {"name": "moosicbox_core", "os": "ubuntu", "features": ["default"]},
+            {"name": "moosicbox_player", "os": "ubuntu", "features": ["cpal"]},
+            {"name": "moosicbox_legacy", "os": "ubuntu", "features": []}
+        ]
+    }"#;
+
+    assert!(diff_matrices(&jobs(BEFORE), &jobs(grouped)).is_empty());
+}
+
+#[test]
+fn test_invalid_matrix_errors() {
+    for matrix in [
+        r#""not a matrix""#,
+        r#"[{"os": "ubuntu"}]"#,
+        r#"[{"name": "a", "features": "x"}]"#,
+        r#"[{"name": "a", "features": [1]}]"#,
+        r#"{"ubuntu": {"name": "a"}}"#,
+    ] {
+        assert!(
+            parse_matrix_jobs(&serde_json::from_str(matrix).unwrap()).is_err(),
+            "{matrix}"
+        );
+    }
+}
+
+#[test]
+fn test_format_matrix_diff() {
+    let output = format_matrix_diff(&fixture_diff());
+
+    assert!(output.contains("Jobs: 5 -> 6 (+1)"), "{output}");
+    assert!(
+        output.contains("+ moosicbox_server (ubuntu): [default]"),
+        "{output}"
+    );
+    assert!(
+        output.contains("- moosicbox_legacy (ubuntu): []"),
+        "{output}"
+    );
+    assert!(
+        output.contains("moosicbox_player (ubuntu): 1 -> 2 jobs, +[aac, opus]"),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_handle_matrix_diff_command_reads_files() {
+    let temp_dir = switchy_fs::tempdir().unwrap();
+    let before = temp_dir.path().join("before.json");
+    let after = temp_dir.path().join("after.json");
+    switchy_fs::sync::write(&before, BEFORE).unwrap();
+    switchy_fs::sync::write(&after, AFTER).unwrap();
+
+    let diff = handle_matrix_diff_command(&before, &after).unwrap();
+
+    assert_eq!(diff, fixture_diff());
+    assert!(handle_matrix_diff_command(&before, &temp_dir.path().join("missing.json")).is_err());
+}
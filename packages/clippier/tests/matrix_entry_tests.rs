@@ -0,0 +1,140 @@
+//! Tests for the typed features matrix API.
+//!
+//! These tests verify that the [`MatrixEntry`]s returned by `features_matrix_entries` match
+//! the JSON matrix `handle_features_command` produces, and that entries convert losslessly
+//! to and from their JSON objects.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::{FeaturesOptions, MatrixEntry, features_matrix_entries};
+use clippier_test_utilities::{features_matrix, test_resources::load_test_workspace};
+
+/// Generates the matrix of the workspace with both the typed and the string API
+async fn generate_matrix(
+    workspace: &str,
+    needs: bool,
+    github_names: bool,
+) -> (
+    Vec<MatrixEntry>,
+    Vec<serde_json::Map<String, serde_json::Value>>,
+) {
+    let (temp_dir, _) = load_test_workspace(workspace);
+    let options = FeaturesOptions {
+        chunked: Some(2),
+        needs,
+        github_names,
+        ..Default::default()
+    };
+
+    let entries = features_matrix_entries(&FeaturesOptions {
+        file: temp_dir.path().display().to_string(),
+        ..options.clone()
+    })
+    .await
+    .unwrap();
+
+    let json = features_matrix(temp_dir.path(), options).await.unwrap();
+
+    (entries, serde_json::from_str(&json).unwrap())
+}
+
+/// Asserts that the typed entries and the JSON matrix describe the same jobs
+fn assert_entries_match_json(
+    entries: Vec<MatrixEntry>,
+    json: Vec<serde_json::Map<String, serde_json::Value>>,
+) {
+    assert!(!entries.is_empty());
+
+    let parsed = json
+        .iter()
+        .cloned()
+        .map(MatrixEntry::from_map)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(entries, parsed);
+
+    let maps = entries
+        .into_iter()
+        .map(MatrixEntry::into_map)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(maps, json);
+}
+
+#[switchy_async::test]
+async fn test_entries_match_json_matrix() {
+    let (entries, json) = generate_matrix("complex", false, false).await;
+
+    assert_entries_match_json(entries, json);
+}
+
+#[switchy_async::test]
+async fn test_entries_match_json_matrix_with_ci_steps() {
+    let (entries, json) = generate_matrix("ci-steps-regression", false, false).await;
+
+    assert!(entries.iter().any(|entry| entry.ci_steps.is_some()));
+    assert_entries_match_json(entries, json);
+}
+
+#[switchy_async::test]
+async fn test_entries_match_annotated_json_matrix() {
+    let (entries, json) = generate_matrix("complex", true, true).await;
+
+    for entry in &entries {
+        assert!(entry.needs.is_some(), "{}", entry.name);
+        assert!(
+            entry
+                .display_name
+                .as_deref()
+                .is_some_and(|x| x.starts_with(&entry.name)),
+            "{}",
+            entry.name
+        );
+    }
+    assert_entries_match_json(entries, json);
+}
+
+#[test_log::test]
+fn test_entry_keeps_unknown_fields() {
+    let map = serde_json::json!({
+        "name": "moosicbox_player",
+        "path": "packages/player",
+        "os": "ubuntu",
+        "features": ["flac"],
+        "requiredFeatures": null,
+        "nightly": false,
+        "retries": 2,
+        "timeout": 30,
+    })
+    .as_object()
+    .unwrap()
+    .clone();
+
+    let entry = MatrixEntry::from_map(map.clone()).unwrap();
+
+    assert_eq!(entry.name, "moosicbox_player");
+    assert_eq!(entry.features, vec!["flac".to_string()]);
+    assert_eq!(entry.retries, Some(2));
+    assert_eq!(
+        entry.extra,
+        serde_json::json!({ "timeout": 30 })
+            .as_object()
+            .unwrap()
+            .clone()
+    );
+    assert_eq!(entry.into_map().unwrap(), map);
+}
+
+#[test_log::test]
+fn test_entry_rejects_invalid_known_field() {
+    let map = serde_json::json!({ "name": "moosicbox_player", "features": "flac" })
+        .as_object()
+        .unwrap()
+        .clone();
+
+    let error = MatrixEntry::from_map(map).unwrap_err().to_string();
+
+    assert!(error.contains("moosicbox_player"), "{error}");
+}
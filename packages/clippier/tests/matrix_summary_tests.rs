@@ -0,0 +1,116 @@
+use clippier::{FeaturesMatrixSummary, FeaturesOptions, summarize_features_matrix};
+use clippier_test_utilities::features_matrix;
+
+/// Creates a workspace with two packages: `alpha` (4 features) and `beta` (2 features)
+fn create_summary_workspace() -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+
+    switchy_fs::sync::write(
+        temp_dir.path().join("Cargo.toml"),
+        r#"
+[workspace]
+members = ["packages/alpha", "packages/beta"]
+"#,
+    )
+    .unwrap();
+
+    for (package, features) in [("alpha", &["a", "b", "c"][..]), ("beta", &["x"][..])] {
+        let pkg_dir = temp_dir.path().join("packages").join(package);
+        switchy_fs::sync::create_dir_all(pkg_dir.join("src")).unwrap();
+
+        let features = features
+            .iter()
+            .map(|x| format!("{x} = []"))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        switchy_fs::sync::write(
+            pkg_dir.join("Cargo.toml"),
+            format!(
+                r#"
+[package]
+name = "{package}"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+default = []
+{features}
+"#
+            ),
+        )
+        .unwrap();
+        switchy_fs::sync::write(pkg_dir.join("src/lib.rs"), "// test lib").unwrap();
+    }
+
+    temp_dir
+}
+
+async fn summarize(
+    temp_dir: &switchy_fs::TempDir,
+    max_parallel: Option<u16>,
+    chunked: Option<u16>,
+) -> FeaturesMatrixSummary {
+    let result = features_matrix(
+        temp_dir.path(),
+        FeaturesOptions {
+            max_parallel,
+            chunked,
+            summary: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    serde_json::from_str(&result).unwrap()
+}
+
+#[switchy_async::test]
+async fn test_summary_counts_full_matrix() {
+    let temp_dir = create_summary_workspace();
+
+    let summary = summarize(&temp_dir, None, None).await;
+
+    assert_eq!(summary.total_jobs, 2);
+    assert_eq!(summary.packages, 2);
+    assert_eq!(summary.distinct_features, 6);
+    assert!((summary.average_features_per_job - 3.0).abs() < f64::EPSILON);
+    assert_eq!(summary.max_parallel, None);
+    assert_eq!(summary.max_parallel_utilization, None);
+}
+
+#[switchy_async::test]
+async fn test_summary_counts_chunked_matrix() {
+    let temp_dir = create_summary_workspace();
+
+    let summary = summarize(&temp_dir, None, Some(2)).await;
+
+    // alpha is split into two chunks of two features, beta fits in a single chunk
+    assert_eq!(summary.total_jobs, 3);
+    assert_eq!(summary.packages, 2);
+    assert_eq!(summary.distinct_features, 6);
+    assert!((summary.average_features_per_job - 2.0).abs() < f64::EPSILON);
+}
+
+#[switchy_async::test]
+async fn test_summary_reports_max_parallel_utilization() {
+    let temp_dir = create_summary_workspace();
+
+    let summary = summarize(&temp_dir, Some(4), None).await;
+
+    assert_eq!(summary.total_jobs, 2);
+    assert_eq!(summary.max_parallel, Some(4));
+    assert!((summary.max_parallel_utilization.unwrap() - 0.5).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_summarize_empty_matrix() {
+    let summary = summarize_features_matrix(&[], Some(8));
+
+    assert_eq!(summary.total_jobs, 0);
+    assert_eq!(summary.packages, 0);
+    assert_eq!(summary.distinct_features, 0);
+    assert!(summary.average_features_per_job.abs() < f64::EPSILON);
+    assert!(summary.max_parallel_utilization.unwrap().abs() < f64::EPSILON);
+}
@@ -0,0 +1,110 @@
+//! Tests for the `--max-total-jobs` guard.
+//!
+//! These tests verify that the features command errors with the packages contributing the
+//! most jobs when the resulting matrix exceeds the cap, and succeeds otherwise.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+#[cfg(feature = "cargo-workspace")]
+use clippier::FeaturesOptions;
+use clippier::check_max_total_jobs;
+#[cfg(feature = "cargo-workspace")]
+use clippier_test_utilities::{features_matrix, test_resources::load_test_workspace};
+
+fn matrix(names: &[&str]) -> Vec<serde_json::Map<String, serde_json::Value>> {
+    names
+        .iter()
+        .map(|name| {
+            let mut entry = serde_json::Map::new();
+            entry.insert("name".to_string(), serde_json::json!(name));
+            entry
+        })
+        .collect()
+}
+
+#[test]
+fn test_matrix_within_cap_passes() {
+    let matrix = matrix(&["a", "a", "b"]);
+
+    assert!(check_max_total_jobs(&matrix, None).is_ok());
+    assert!(check_max_total_jobs(&matrix, Some(3)).is_ok());
+}
+
+#[test]
+fn test_matrix_over_cap_lists_worst_offenders_first() {
+    let matrix = matrix(&["small", "wide", "wide", "medium", "wide", "medium"]);
+
+    let message = check_max_total_jobs(&matrix, Some(5))
+        .unwrap_err()
+        .to_string();
+
+    assert!(message.contains("has 6 jobs"), "{message}");
+    assert!(message.contains("--max-total-jobs=5"), "{message}");
+    assert!(
+        message.contains("wide (3 jobs), medium (2 jobs), small (1 jobs)"),
+        "{message}"
+    );
+}
+
+#[test]
+fn test_matrix_over_cap_lists_at_most_five_packages() {
+    let matrix = matrix(&["a", "a", "b", "c", "d", "e", "f"]);
+
+    let message = check_max_total_jobs(&matrix, Some(1))
+        .unwrap_err()
+        .to_string();
+
+    assert!(message.contains("a (2 jobs)"), "{message}");
+    assert!(message.contains("e (1 jobs)"), "{message}");
+    assert!(!message.contains("f (1 jobs)"), "{message}");
+}
+
+#[cfg(feature = "cargo-workspace")]
+async fn capped_features_matrix(
+    max_total_jobs: Option<usize>,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let (temp_dir, _) = load_test_workspace("complex");
+
+    features_matrix(
+        temp_dir.path(),
+        FeaturesOptions {
+            os: Some("ubuntu".to_string()),
+            chunked: Some(1),
+            max_total_jobs,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+#[cfg(feature = "cargo-workspace")]
+#[switchy_async::test]
+async fn test_features_matrix_exceeding_cap_errors() {
+    let uncapped: Vec<serde_json::Value> =
+        serde_json::from_str(&capped_features_matrix(None).await.unwrap()).unwrap();
+    assert!(uncapped.len() > 1);
+
+    let err = capped_features_matrix(Some(uncapped.len() - 1))
+        .await
+        .expect_err("Matrix over the cap should fail with --max-total-jobs");
+    let message = err.to_string();
+
+    assert!(
+        message.contains(&format!("has {} jobs", uncapped.len())),
+        "{message}"
+    );
+    assert!(message.contains("largest packages: "), "{message}");
+}
+
+#[cfg(feature = "cargo-workspace")]
+#[switchy_async::test]
+async fn test_features_matrix_within_cap_succeeds() {
+    let uncapped = capped_features_matrix(None).await.unwrap();
+    let jobs = serde_json::from_str::<Vec<serde_json::Value>>(&uncapped)
+        .unwrap()
+        .len();
+
+    assert_eq!(capped_features_matrix(Some(jobs)).await.unwrap(), uncapped);
+}
@@ -0,0 +1,160 @@
+//! Tests for reading workspace members from a `members-file`.
+//!
+//! These tests verify that members listed in the file configured by the root
+//! `clippier.toml`, or passed explicitly as with `--members-file`, are merged with the
+//! inline `workspace.members` and used when walking the workspace.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::{
+    find_affected_packages, find_affected_packages_for_target, resolve_members_file,
+    workspace_member_patterns,
+};
+
+/// Creates a workspace with `pkg-a` depending on `pkg-b`, where the root `Cargo.toml`
+/// declares `inline_members` and `members.toml` contains `members_file`
+fn create_workspace(inline_members: &str, members_file: &str) -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+
+    switchy_fs::sync::write(
+        root.join("Cargo.toml"),
+        format!("[workspace]\nmembers = [{inline_members}]\n"),
+    )
+    .unwrap();
+    switchy_fs::sync::write(
+        root.join("clippier.toml"),
+        "members-file = \"members.toml\"\n",
+    )
+    .unwrap();
+    switchy_fs::sync::write(root.join("members.toml"), members_file).unwrap();
+
+    for (name, dependencies) in [
+        ("pkg-a", "pkg-b = { path = \"../pkg-b\" }\n"),
+        ("pkg-b", ""),
+    ] {
+        let package_dir = root.join("packages").join(name);
+        switchy_fs::sync::create_dir_all(package_dir.join("src")).unwrap();
+        switchy_fs::sync::write(
+            package_dir.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n\n[dependencies]\n{dependencies}"
+            ),
+        )
+        .unwrap();
+        switchy_fs::sync::write(package_dir.join("src/lib.rs"), "").unwrap();
+    }
+
+    temp_dir
+}
+
+fn member_patterns(temp_dir: &switchy_fs::TempDir) -> Result<Option<Vec<String>>, String> {
+    let source = switchy_fs::sync::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap();
+    let value: toml::Value = toml::from_str(&source).unwrap();
+
+    let members_file = resolve_members_file(temp_dir.path(), None).unwrap();
+
+    workspace_member_patterns(temp_dir.path(), &value, members_file.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+#[test]
+fn test_members_file_members_are_merged_with_inline_members() {
+    let temp_dir = create_workspace(
+        "\"packages/pkg-a\"",
+        "members = [\"packages/pkg-a\", \"packages/pkg-b\"]\n",
+    );
+
+    assert_eq!(
+        member_patterns(&temp_dir).unwrap(),
+        Some(vec![
+            "packages/pkg-a".to_string(),
+            "packages/pkg-b".to_string(),
+        ])
+    );
+}
+
+#[test]
+fn test_members_file_accepts_workspace_table() {
+    let temp_dir = create_workspace("", "[workspace]\nmembers = [\"packages/*\"]\n");
+
+    assert_eq!(
+        member_patterns(&temp_dir).unwrap(),
+        Some(vec!["packages/*".to_string()])
+    );
+}
+
+#[test]
+fn test_members_file_affected_packages_match_inline_members() {
+    let changed_files = vec!["packages/pkg-b/src/lib.rs".to_string()];
+
+    let from_file = create_workspace("", "members = [\"packages/pkg-a\", \"packages/pkg-b\"]\n");
+    let inline = create_workspace("\"packages/pkg-a\", \"packages/pkg-b\"", "members = []\n");
+
+    let mut affected = find_affected_packages(from_file.path(), &changed_files, &[]).unwrap();
+    affected.sort();
+
+    assert_eq!(affected, vec!["pkg-a".to_string(), "pkg-b".to_string()]);
+
+    let mut expected = find_affected_packages(inline.path(), &changed_files, &[]).unwrap();
+    expected.sort();
+
+    assert_eq!(affected, expected);
+}
+
+#[test]
+fn test_members_file_option_overrides_configured_members_file() {
+    let changed_files = vec!["packages/pkg-b/src/lib.rs".to_string()];
+
+    let temp_dir = create_workspace("", "members = []\n");
+    let external_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let external = external_dir.path().join("members.toml");
+    switchy_fs::sync::write(
+        &external,
+        "members = [\"packages/pkg-a\", \"packages/pkg-b\"]\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        resolve_members_file(temp_dir.path(), Some(&external)).unwrap(),
+        Some(external.clone())
+    );
+
+    let mut affected = find_affected_packages_for_target(
+        temp_dir.path(),
+        &changed_files,
+        &[],
+        None,
+        Some(&external),
+    )
+    .unwrap();
+    affected.sort();
+
+    let inline = create_workspace("\"packages/pkg-a\", \"packages/pkg-b\"", "members = []\n");
+    let mut expected = find_affected_packages(inline.path(), &changed_files, &[]).unwrap();
+    expected.sort();
+
+    assert_eq!(affected, vec!["pkg-a".to_string(), "pkg-b".to_string()]);
+    assert_eq!(affected, expected);
+}
+
+#[test]
+fn test_members_file_missing_directory_errors() {
+    let temp_dir = create_workspace("", "members = [\"packages/pkg-a\", \"packages/missing\"]\n");
+
+    let error = member_patterns(&temp_dir).unwrap_err();
+
+    assert!(error.contains("'packages/missing'"), "{error}");
+    assert!(error.contains("not a directory"), "{error}");
+}
+
+#[test]
+fn test_members_file_without_members_array_errors() {
+    let temp_dir = create_workspace("", "exclude = [\"packages/pkg-a\"]\n");
+
+    let error = member_patterns(&temp_dir).unwrap_err();
+
+    assert!(error.contains("no `members` array"), "{error}");
+}
@@ -0,0 +1,165 @@
+//! Tests for `[[mutually-exclusive]]` feature groups in `clippier.toml`.
+//!
+//! These tests verify that matrix generation never produces a job that activates
+//! more than one member of a mutually-exclusive group.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::{FeaturesList, MutuallyExclusiveConfig, split_mutually_exclusive_features};
+
+const CARGO_TOML: &str = r#"
+[package]
+name = "backend"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+default = ["sqlite"]
+sqlite = []
+postgres = []
+all-backends = ["sqlite", "postgres"]
+json = []
+tls = []
+"#;
+
+/// Creates a single package that declares `sqlite` and `postgres` as mutually exclusive
+fn create_backend_package() -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+
+    switchy_fs::sync::write(temp_dir.path().join("Cargo.toml"), CARGO_TOML).unwrap();
+    switchy_fs::sync::write(
+        temp_dir.path().join("clippier.toml"),
+        r#"
+[[mutually-exclusive]]
+features = ["sqlite", "postgres"]
+"#,
+    )
+    .unwrap();
+    switchy_fs::sync::create_dir_all(temp_dir.path().join("src")).unwrap();
+    switchy_fs::sync::write(temp_dir.path().join("src/lib.rs"), "// test lib").unwrap();
+
+    temp_dir
+}
+
+fn job_features(job: &serde_json::Map<String, serde_json::Value>) -> Vec<String> {
+    job.get("features")
+        .and_then(|x| x.as_array())
+        .unwrap()
+        .iter()
+        .map(|x| x.as_str().unwrap().to_string())
+        .collect()
+}
+
+fn assert_no_conflicts(jobs: &[serde_json::Map<String, serde_json::Value>]) {
+    for job in jobs {
+        let features = job_features(job);
+        assert!(
+            !(features.contains(&"sqlite".to_string())
+                && features.contains(&"postgres".to_string())),
+            "Job activates both sqlite and postgres: {features:?}"
+        );
+        assert!(
+            !(features.contains(&"default".to_string())
+                && features.contains(&"postgres".to_string())),
+            "Job activates sqlite (via default) and postgres: {features:?}"
+        );
+        assert!(
+            !features.contains(&"all-backends".to_string()),
+            "Job contains a feature that activates both backends: {features:?}"
+        );
+    }
+}
+
+#[switchy_async::test]
+async fn test_mutually_exclusive_features_are_split_without_chunking() {
+    let temp_dir = create_backend_package();
+
+    let jobs = clippier::process_configs(temp_dir.path(), &clippier::FeaturesOptions::default())
+        .await
+        .unwrap();
+
+    assert_no_conflicts(&jobs);
+
+    let mut all_features = jobs.iter().flat_map(job_features).collect::<Vec<_>>();
+    all_features.sort();
+    assert_eq!(
+        all_features,
+        vec!["default", "json", "postgres", "sqlite", "tls"],
+        "Every compatible feature should still be covered by some job"
+    );
+}
+
+#[switchy_async::test]
+async fn test_mutually_exclusive_features_are_split_within_chunks() {
+    let temp_dir = create_backend_package();
+
+    for chunked in 1..=6 {
+        let jobs = clippier::process_configs(
+            temp_dir.path(),
+            &clippier::FeaturesOptions {
+                chunked: Some(chunked),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_no_conflicts(&jobs);
+
+        for job in &jobs {
+            assert!(
+                job_features(job).len() <= chunked as usize,
+                "Splitting must never grow a chunk beyond the chunk limit"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_split_mutually_exclusive_features_keeps_compatible_features_together() {
+    let cargo_toml: toml::Value = toml::from_str(CARGO_TOML).unwrap();
+    let groups = vec![MutuallyExclusiveConfig {
+        features: vec!["sqlite".to_string(), "postgres".to_string()],
+    }];
+
+    let result = split_mutually_exclusive_features(
+        &cargo_toml,
+        FeaturesList::Chunked(vec![vec![
+            "sqlite".to_string(),
+            "json".to_string(),
+            "postgres".to_string(),
+            "tls".to_string(),
+        ]]),
+        &groups,
+    );
+
+    let FeaturesList::Chunked(chunks) = result else {
+        panic!("Expected chunked features");
+    };
+    assert_eq!(
+        chunks,
+        vec![
+            vec!["sqlite".to_string(), "json".to_string(), "tls".to_string()],
+            vec!["postgres".to_string()],
+        ]
+    );
+}
+
+#[test]
+fn test_split_mutually_exclusive_features_without_groups_is_noop() {
+    let cargo_toml: toml::Value = toml::from_str(CARGO_TOML).unwrap();
+    let features = vec!["sqlite".to_string(), "postgres".to_string()];
+
+    let result = split_mutually_exclusive_features(
+        &cargo_toml,
+        FeaturesList::NotChunked(features.clone()),
+        &[],
+    );
+
+    let FeaturesList::NotChunked(result) = result else {
+        panic!("Expected unchunked features");
+    };
+    assert_eq!(result, features);
+}
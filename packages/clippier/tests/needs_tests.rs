@@ -0,0 +1,123 @@
+//! Tests for annotating features matrix entries with `needs` dependency ordering.
+//!
+//! These tests verify that `--needs` lists the entries for each package's workspace
+//! dependencies, and that edges closing a dependency cycle are omitted.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use std::collections::BTreeMap;
+
+use clippier::FeaturesOptions;
+use clippier_test_utilities::features_matrix;
+
+/// Creates a workspace where `app` depends on `server`, which depends on `core`
+///
+/// `core_dev_dependencies` is appended to `core`'s manifest, e.g. to close a cycle.
+fn create_chain_workspace(core_dev_dependencies: &str) -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+
+    switchy_fs::sync::write(
+        temp_dir.path().join("Cargo.toml"),
+        r#"
+[workspace]
+members = ["packages/app", "packages/server", "packages/core"]
+
+[workspace.dependencies]
+app = { path = "packages/app" }
+server = { path = "packages/server" }
+core = { path = "packages/core" }
+"#,
+    )
+    .unwrap();
+
+    for (package, dependencies) in [
+        ("app", "[dependencies]\nserver = { workspace = true }\n"),
+        ("server", "[dependencies]\ncore = { workspace = true }\n"),
+        ("core", core_dev_dependencies),
+    ] {
+        let pkg_dir = temp_dir.path().join("packages").join(package);
+        switchy_fs::sync::create_dir_all(pkg_dir.join("src")).unwrap();
+
+        switchy_fs::sync::write(
+            pkg_dir.join("Cargo.toml"),
+            format!(
+                r#"
+[package]
+name = "{package}"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+default = []
+
+{dependencies}
+"#
+            ),
+        )
+        .unwrap();
+        switchy_fs::sync::write(pkg_dir.join("src/lib.rs"), "// test lib").unwrap();
+    }
+
+    temp_dir
+}
+
+/// Returns each entry's `needs`, keyed by entry name
+async fn matrix_needs(temp_dir: &switchy_fs::TempDir) -> BTreeMap<String, Vec<String>> {
+    let result = features_matrix(
+        temp_dir.path(),
+        FeaturesOptions {
+            needs: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let matrix: Vec<serde_json::Map<String, serde_json::Value>> =
+        serde_json::from_str(&result).unwrap();
+
+    matrix
+        .into_iter()
+        .map(|entry| {
+            let name = entry["name"].as_str().unwrap().to_string();
+            let needs = serde_json::from_value(entry["needs"].clone()).unwrap();
+            (name, needs)
+        })
+        .collect()
+}
+
+#[switchy_async::test]
+async fn test_needs_lists_workspace_dependency_entries() {
+    let temp_dir = create_chain_workspace("");
+
+    let needs = matrix_needs(&temp_dir).await;
+
+    assert_eq!(needs["core"], Vec::<String>::new());
+    assert_eq!(needs["server"], vec!["core"]);
+    // Transitive dependencies are included
+    assert_eq!(needs["app"], vec!["core", "server"]);
+}
+
+#[switchy_async::test]
+async fn test_needs_omits_edges_closing_a_cycle() {
+    let temp_dir = create_chain_workspace("[dev-dependencies]\napp = { workspace = true }\n");
+
+    let needs = matrix_needs(&temp_dir).await;
+
+    // Entries are visited in name order, so `app` keeps its edges and the edges back to
+    // it are omitted
+    assert_eq!(needs["app"], vec!["core", "server"]);
+    assert_eq!(needs["core"], vec!["server"]);
+    assert_eq!(needs["server"], Vec::<String>::new());
+
+    for (name, dependencies) in &needs {
+        for dependency in dependencies {
+            assert!(
+                !needs[dependency].contains(name),
+                "{name} and {dependency} need each other"
+            );
+        }
+    }
+}
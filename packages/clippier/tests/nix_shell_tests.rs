@@ -0,0 +1,255 @@
+//! Tests for the nix-shell command.
+//!
+//! These tests verify that the workspace's system packages and toolchains are mapped to
+//! `nixpkgs` attributes, that overrides take precedence over the built-in mapping, and
+//! that unmapped dependencies are flagged as `TODO` comments instead of failing.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use std::collections::BTreeMap;
+
+use clippier::{
+    NixShell, NixShellFormat, UnmappedDependency, collect_workspace_toolchains, format_nix_shell,
+    handle_nix_shell_command, nixpkgs_attrs, parse_nix_overrides,
+    system_deps::collect_workspace_system_deps,
+};
+
+const WORKSPACE: &str = r#"
+[workspace]
+members = ["packages/*"]
+"#;
+
+const AUDIO_CLIPPIER: &str = r#"
+[[config]]
+os = "ubuntu"
+dependencies = [
+    { command = "sudo apt-get update && sudo apt-get install libasound2-dev libssl-dev libfancy-dev" },
+    { command = "curl -o vectors.tar.gz https://example.com/vectors.tar.gz" },
+    { toolchain = "cargo-machete" },
+]
+
+[[config]]
+os = "macos"
+dependencies = [{ command = "brew install pkg-config openssl" }]
+"#;
+
+const WEB_CLIPPIER: &str = r#"
+[env]
+NODE_ENV = "development"
+
+[[config]]
+os = "ubuntu"
+dependencies = [
+    { command = "sudo apt-get install -y nodejs npm" },
+    { toolchain = "pnpm" },
+    { toolchain = "mystery-tool" },
+    { toolchain = "free_disk_space" },
+]
+"#;
+
+/// Creates a workspace with an audio package and a web package
+fn create_workspace() -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+
+    switchy_fs::sync::write(root.join("Cargo.toml"), WORKSPACE).unwrap();
+
+    for (name, clippier_toml) in [("audio", AUDIO_CLIPPIER), ("web", WEB_CLIPPIER)] {
+        let package_dir = root.join("packages").join(name);
+        switchy_fs::sync::create_dir_all(package_dir.join("src")).unwrap();
+        switchy_fs::sync::write(
+            package_dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n"),
+        )
+        .unwrap();
+        switchy_fs::sync::write(package_dir.join("src/lib.rs"), "// test lib").unwrap();
+        switchy_fs::sync::write(package_dir.join("clippier.toml"), clippier_toml).unwrap();
+    }
+
+    temp_dir
+}
+
+fn nix_shell(root: &std::path::Path, os: &str, overrides: &[&str]) -> NixShell {
+    let overrides = parse_nix_overrides(
+        &overrides
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+    )
+    .unwrap();
+
+    NixShell::new(
+        &collect_workspace_system_deps(root, os).unwrap(),
+        &collect_workspace_toolchains(root, os).unwrap(),
+        &overrides,
+    )
+}
+
+#[test]
+fn test_nix_shell_maps_known_packages_and_toolchains() {
+    let temp_dir = create_workspace();
+
+    let shell = nix_shell(temp_dir.path(), "ubuntu", &[]);
+
+    assert_eq!(
+        shell.packages,
+        vec![
+            "alsa-lib",
+            "cargo-machete",
+            "nodejs",
+            "openssl",
+            "pnpm",
+            "rustup"
+        ]
+    );
+    assert_eq!(
+        shell.env,
+        BTreeMap::from([("NODE_ENV".to_string(), "development".to_string())])
+    );
+}
+
+#[test]
+fn test_nix_shell_flags_unmapped_dependencies() {
+    let temp_dir = create_workspace();
+
+    let shell = nix_shell(temp_dir.path(), "ubuntu", &[]);
+
+    assert_eq!(
+        shell.unmapped,
+        vec![
+            UnmappedDependency::Package("libfancy-dev".to_string()),
+            UnmappedDependency::Toolchain("mystery-tool".to_string()),
+            UnmappedDependency::Command(
+                "curl -o vectors.tar.gz https://example.com/vectors.tar.gz".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_nix_shell_overrides_take_precedence() {
+    let temp_dir = create_workspace();
+
+    let shell = nix_shell(
+        temp_dir.path(),
+        "ubuntu",
+        &[
+            "libfancy-dev=fancy,fancy.dev",
+            "libssl-dev=openssl_3",
+            "pnpm=",
+        ],
+    );
+
+    assert_eq!(
+        shell.packages,
+        vec![
+            "alsa-lib",
+            "cargo-machete",
+            "fancy",
+            "fancy.dev",
+            "nodejs",
+            "openssl_3",
+            "rustup"
+        ]
+    );
+    assert!(
+        !shell
+            .unmapped
+            .contains(&UnmappedDependency::Package("libfancy-dev".to_string()))
+    );
+}
+
+#[test]
+fn test_nix_shell_uses_os_specific_dependencies() {
+    let temp_dir = create_workspace();
+
+    let shell = nix_shell(temp_dir.path(), "macos", &[]);
+
+    assert_eq!(shell.packages, vec!["openssl", "pkg-config", "rustup"]);
+    assert!(shell.unmapped.is_empty());
+}
+
+#[test]
+fn test_builtin_mapping() {
+    assert_eq!(nixpkgs_attrs("libasound2-dev"), Some(&["alsa-lib"][..]));
+    assert_eq!(
+        nixpkgs_attrs("build-essential"),
+        Some(&["gcc", "gnumake"][..])
+    );
+    assert_eq!(nixpkgs_attrs("free_disk_space"), Some(&[][..]));
+    assert_eq!(nixpkgs_attrs("libfancy-dev"), None);
+}
+
+#[test]
+fn test_parse_nix_overrides_rejects_malformed_entries() {
+    assert!(parse_nix_overrides(&["libfoo".to_string()]).is_err());
+    assert!(parse_nix_overrides(&["=foo".to_string()]).is_err());
+}
+
+#[test]
+fn test_format_shell_nix() {
+    let temp_dir = create_workspace();
+
+    let output =
+        handle_nix_shell_command(temp_dir.path(), "ubuntu", NixShellFormat::Shell, &[]).unwrap();
+
+    assert!(
+        output.contains("{ pkgs ? import <nixpkgs> { } }:"),
+        "{output}"
+    );
+    assert!(output.contains("pkgs.mkShell {"), "{output}");
+    assert!(output.contains("    alsa-lib\n"), "{output}");
+    assert!(
+        output.contains("  # TODO: no nixpkgs mapping for system package `libfancy-dev`"),
+        "{output}"
+    );
+    assert!(
+        output.contains("  # TODO: no nixpkgs mapping for toolchain `mystery-tool`"),
+        "{output}"
+    );
+    assert!(
+        output.contains("  # TODO: translate dependency command `curl -o vectors.tar.gz"),
+        "{output}"
+    );
+    assert!(
+        output.contains("  \"NODE_ENV\" = \"development\";"),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_format_flake_nix() {
+    let temp_dir = create_workspace();
+
+    let output =
+        handle_nix_shell_command(temp_dir.path(), "ubuntu", NixShellFormat::Flake, &[]).unwrap();
+
+    assert!(
+        output.contains("nixpkgs.url = \"github:NixOS/nixpkgs/nixos-unstable\";"),
+        "{output}"
+    );
+    assert!(
+        output.contains("devShells.default = pkgs.mkShell {"),
+        "{output}"
+    );
+    assert!(output.contains("          alsa-lib\n"), "{output}");
+    assert!(output.trim_end().ends_with('}'), "{output}");
+}
+
+#[test]
+fn test_format_escapes_env_values() {
+    let shell = NixShell {
+        packages: vec!["rustup".to_string()],
+        env: BTreeMap::from([("GREETING".to_string(), "say \"${hi}\"".to_string())]),
+        ..NixShell::default()
+    };
+
+    let output = format_nix_shell(&shell, NixShellFormat::Shell);
+
+    assert!(
+        output.contains(r#""GREETING" = "say \"\${hi}\"";"#),
+        "{output}"
+    );
+}
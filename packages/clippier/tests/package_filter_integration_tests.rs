@@ -2,7 +2,10 @@
 //!
 //! Tests all operators against real Cargo.toml fixtures.
 
-use clippier::package_filter::{matches, parse_filter};
+use clippier::package_filter::{
+    apply_filters, evaluate_expression, matches, parse_expression, parse_filter,
+};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// Seed the test resources into the simulator if enabled
@@ -775,3 +778,149 @@ async fn test_combined_multiple_skip_conditions() {
     // At least one matches
     assert!(matches(&filter1, &toml).unwrap());
 }
+
+// ============================================================================
+// CUSTOM METADATA: metadata.* shorthand for package.metadata.*
+// ============================================================================
+
+#[switchy_async::test]
+async fn test_metadata_shorthand_string_match() {
+    let toml = load_fixture("custom-metadata");
+    let filter = parse_filter("metadata.clippier.tier == core").unwrap();
+    assert!(matches(&filter, &toml).unwrap());
+
+    let filter = parse_filter("metadata.clippier.tier == extra").unwrap();
+    assert!(!matches(&filter, &toml).unwrap());
+}
+
+#[switchy_async::test]
+async fn test_metadata_shorthand_boolean_match() {
+    let toml = load_fixture("custom-metadata");
+    assert!(matches(&parse_filter("metadata.clippier.gpu=true").unwrap(), &toml).unwrap());
+    assert!(
+        matches(
+            &parse_filter("metadata.clippier.experimental=false").unwrap(),
+            &toml
+        )
+        .unwrap()
+    );
+    assert!(
+        !matches(
+            &parse_filter("metadata.clippier.experimental=true").unwrap(),
+            &toml
+        )
+        .unwrap()
+    );
+}
+
+#[switchy_async::test]
+async fn test_metadata_shorthand_matches_full_path() {
+    let toml = load_fixture("custom-metadata");
+    let shorthand = parse_filter("metadata.clippier.tier=core").unwrap();
+    let full = parse_filter("package.metadata.clippier.tier=core").unwrap();
+    assert_eq!(
+        matches(&shorthand, &toml).unwrap(),
+        matches(&full, &toml).unwrap()
+    );
+}
+
+#[switchy_async::test]
+async fn test_metadata_shorthand_missing_key() {
+    let toml = load_fixture("no-metadata");
+    assert!(matches(&parse_filter("metadata.clippier.tier!?").unwrap(), &toml).unwrap());
+    assert!(!matches(&parse_filter("metadata.clippier.tier=core").unwrap(), &toml).unwrap());
+}
+
+#[switchy_async::test]
+async fn test_metadata_shorthand_with_logical_operators() {
+    let toml = load_fixture("custom-metadata");
+
+    let expr = parse_expression(
+        "metadata.clippier.tier == core AND NOT metadata.clippier.experimental=true",
+    )
+    .unwrap();
+    assert!(evaluate_expression(&expr, &toml).unwrap());
+
+    let expr =
+        parse_expression("metadata.clippier.tier == extra OR metadata.clippier.gpu=true").unwrap();
+    assert!(evaluate_expression(&expr, &toml).unwrap());
+
+    let expr =
+        parse_expression("metadata.clippier.tier == core AND metadata.clippier.gpu=false").unwrap();
+    assert!(!evaluate_expression(&expr, &toml).unwrap());
+}
+
+#[switchy_async::test]
+async fn test_metadata_shorthand_include_and_skip_packages() {
+    let temp_dir = switchy_fs::tempdir().unwrap();
+    let root = temp_dir.path();
+
+    let mut package_paths = BTreeMap::new();
+    for (name, tier, experimental) in [
+        ("core_pkg", "core", false),
+        ("extra_pkg", "extra", false),
+        ("lab_pkg", "core", true),
+    ] {
+        let dir = root.join(name);
+        switchy_fs::sync::create_dir_all(&dir).unwrap();
+        switchy_fs::sync::write(
+            dir.join("Cargo.toml"),
+            format!(
+                r#"
+                [package]
+                name = "{name}"
+                version = "0.1.0"
+
+                [package.metadata.clippier]
+                tier = "{tier}"
+                experimental = {experimental}
+                "#
+            ),
+        )
+        .unwrap();
+        package_paths.insert(name.to_string(), name.to_string());
+    }
+    switchy_fs::sync::create_dir_all(root.join("plain_pkg")).unwrap();
+    switchy_fs::sync::write(
+        root.join("plain_pkg/Cargo.toml"),
+        r#"
+        [package]
+        name = "plain_pkg"
+        version = "0.1.0"
+        "#,
+    )
+    .unwrap();
+    package_paths.insert("plain_pkg".to_string(), "plain_pkg".to_string());
+
+    let packages = package_paths.keys().cloned().collect::<Vec<_>>();
+
+    let included = apply_filters(
+        &packages,
+        &package_paths,
+        root,
+        &[],
+        &["metadata.clippier.tier == core".to_string()],
+    )
+    .unwrap();
+    assert_eq!(included, vec!["core_pkg", "lab_pkg"]);
+
+    let included = apply_filters(
+        &packages,
+        &package_paths,
+        root,
+        &["metadata.clippier.experimental=true".to_string()],
+        &["metadata.clippier.tier == core".to_string()],
+    )
+    .unwrap();
+    assert_eq!(included, vec!["core_pkg"]);
+
+    let remaining = apply_filters(
+        &packages,
+        &package_paths,
+        root,
+        &["metadata.clippier.tier == extra OR NOT metadata.clippier.tier?".to_string()],
+        &[],
+    )
+    .unwrap();
+    assert_eq!(remaining, vec!["core_pkg", "lab_pkg"]);
+}
@@ -0,0 +1,208 @@
+//! Tests for the workspace package graph export.
+//!
+//! These tests verify that every workspace member becomes a node and that dependencies
+//! between members become edges annotated with their section, whether they are optional
+//! and the features enabling them, while external dependencies are left out.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::package_graph::{
+    DependencyEdge, DependencyEdgeKind, build_package_graph, format_package_graph,
+};
+
+const WORKSPACE: &str = r#"
+[workspace]
+members = ["packages/*"]
+
+[workspace.dependencies]
+core = { path = "packages/core" }
+db = { path = "packages/db" }
+codegen = { path = "packages/codegen" }
+serde = { version = "1.0.0" }
+"#;
+
+const APP: &str = r#"
+[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+core = { workspace = true }
+db = { workspace = true, optional = true }
+serde = { workspace = true }
+
+[dev-dependencies]
+test-utils = { path = "../test-utils" }
+
+[build-dependencies]
+codegen = { workspace = true }
+
+[features]
+default = []
+storage = ["dep:db"]
+fast = ["db?/fast", "storage"]
+"#;
+
+const CORE: &str = r#"
+[package]
+name = "core"
+version = "0.1.0"
+
+[dependencies]
+helpers = { package = "test-utils", path = "../test-utils", optional = true }
+
+[features]
+testing = ["helpers/mock"]
+"#;
+
+const DB: &str = r#"
+[package]
+name = "db"
+version = "0.1.0"
+
+[dependencies]
+core = { workspace = true }
+
+[features]
+fast = []
+"#;
+
+const CODEGEN: &str = r#"
+[package]
+name = "codegen"
+version = "0.1.0"
+"#;
+
+const TEST_UTILS: &str = r#"
+[package]
+name = "test-utils"
+version = "0.1.0"
+
+[features]
+mock = []
+"#;
+
+fn create_workspace() -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+
+    switchy_fs::sync::write(root.join("Cargo.toml"), WORKSPACE).unwrap();
+
+    for (name, cargo_toml) in [
+        ("app", APP),
+        ("core", CORE),
+        ("db", DB),
+        ("codegen", CODEGEN),
+        ("test-utils", TEST_UTILS),
+    ] {
+        let package_dir = root.join("packages").join(name);
+        switchy_fs::sync::create_dir_all(package_dir.join("src")).unwrap();
+        switchy_fs::sync::write(package_dir.join("Cargo.toml"), cargo_toml).unwrap();
+        switchy_fs::sync::write(package_dir.join("src/lib.rs"), "// test lib").unwrap();
+    }
+
+    temp_dir
+}
+
+fn edge(
+    from: &str,
+    to: &str,
+    kind: DependencyEdgeKind,
+    activating_features: &[&str],
+) -> DependencyEdge {
+    DependencyEdge {
+        from: from.to_string(),
+        to: to.to_string(),
+        kind,
+        optional: !activating_features.is_empty(),
+        activating_features: activating_features
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+    }
+}
+
+#[test]
+fn test_package_graph_nodes() {
+    let temp_dir = create_workspace();
+
+    let graph = build_package_graph(temp_dir.path()).unwrap();
+
+    assert_eq!(graph.nodes.len(), 5);
+
+    let names: Vec<&str> = graph.nodes.iter().map(|x| x.name.as_str()).collect();
+    assert_eq!(names, vec!["app", "codegen", "core", "db", "test-utils"]);
+
+    let app = &graph.nodes[0];
+    assert_eq!(app.path, "packages/app");
+    assert_eq!(app.features, vec!["default", "fast", "storage"]);
+}
+
+#[test]
+fn test_package_graph_edges_with_mixed_kinds() {
+    let temp_dir = create_workspace();
+
+    let graph = build_package_graph(temp_dir.path()).unwrap();
+
+    // `serde` is an external dependency declared in the workspace, so it's not an edge.
+    // `fast` only enables `db` through `storage`, and `db?/fast` doesn't enable it at all.
+    assert_eq!(
+        graph.edges,
+        vec![
+            edge("app", "codegen", DependencyEdgeKind::Build, &[]),
+            edge("app", "core", DependencyEdgeKind::Normal, &[]),
+            edge("app", "db", DependencyEdgeKind::Normal, &["storage"]),
+            edge("app", "test-utils", DependencyEdgeKind::Dev, &[]),
+            edge(
+                "core",
+                "test-utils",
+                DependencyEdgeKind::Normal,
+                &["testing"]
+            ),
+            edge("db", "core", DependencyEdgeKind::Normal, &[]),
+        ]
+    );
+}
+
+#[test]
+fn test_package_graph_json_annotations() {
+    let temp_dir = create_workspace();
+
+    let graph = build_package_graph(temp_dir.path()).unwrap();
+    let json = serde_json::to_value(&graph).unwrap();
+
+    let kinds: Vec<&str> = json["edges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|x| x["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        kinds,
+        vec!["build", "normal", "normal", "dev", "normal", "normal"]
+    );
+
+    let optional = &json["edges"][2];
+    assert_eq!(optional["optional"], true);
+    assert_eq!(
+        optional["activating_features"],
+        serde_json::json!(["storage"])
+    );
+}
+
+#[test]
+fn test_package_graph_formatting() {
+    let temp_dir = create_workspace();
+
+    let graph = build_package_graph(temp_dir.path()).unwrap();
+    let output = format_package_graph(&graph);
+
+    assert!(output.contains("📦 5 packages, 6 dependencies"), "{output}");
+    assert!(output.contains("  app → codegen (build)\n"), "{output}");
+    assert!(
+        output.contains("  app → db [optional, enabled by: storage]\n"),
+        "{output}"
+    );
+}
@@ -0,0 +1,155 @@
+//! Tests for per-package randomization seeds.
+//!
+//! These tests verify that seeds derived from one base seed reproduce the same feature
+//! order for a package across runs, while different packages get different orders.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::{FeaturesList, package_seed, process_features};
+
+const BASE_SEED: u64 = 12345;
+
+const PACKAGE_NAMES: [&str; 4] = ["alpha", "beta", "gamma", "delta"];
+
+fn features() -> Vec<String> {
+    (1..=10).map(|i| format!("feature{i}")).collect()
+}
+
+fn shuffled_features(package_name: &str) -> Vec<String> {
+    match process_features(
+        features(),
+        None,
+        false,
+        true,
+        Some(package_seed(BASE_SEED, package_name)),
+    ) {
+        FeaturesList::NotChunked(features) => features,
+        FeaturesList::Chunked(_) => panic!("Expected unchunked features"),
+    }
+}
+
+/// Creates a standalone package named `name` with ten features
+fn create_package(root: &std::path::Path, name: &str) -> std::path::PathBuf {
+    let pkg_dir = root.join("packages").join(name);
+    switchy_fs::sync::create_dir_all(pkg_dir.join("src")).unwrap();
+
+    let features = features()
+        .iter()
+        .map(|feature| format!("{feature} = []"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let cargo_toml = format!(
+        r#"
+[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+default = []
+{features}
+"#
+    );
+    switchy_fs::sync::write(pkg_dir.join("Cargo.toml"), cargo_toml).unwrap();
+    switchy_fs::sync::write(pkg_dir.join("src/lib.rs"), "// test").unwrap();
+
+    pkg_dir
+}
+
+async fn process_package_features(pkg_dir: &std::path::Path) -> Vec<serde_json::Value> {
+    let result = clippier::process_configs(
+        pkg_dir,
+        &clippier::FeaturesOptions {
+            randomize: true,
+            seed: Some(BASE_SEED),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    result
+        .into_iter()
+        .map(|config| config["features"].clone())
+        .collect()
+}
+
+#[test]
+fn test_package_seed_is_deterministic() {
+    for name in PACKAGE_NAMES {
+        assert_eq!(package_seed(BASE_SEED, name), package_seed(BASE_SEED, name));
+    }
+}
+
+#[test]
+fn test_package_seed_differs_across_packages_and_base_seeds() {
+    let seeds = PACKAGE_NAMES
+        .iter()
+        .map(|name| package_seed(BASE_SEED, name))
+        .collect::<std::collections::BTreeSet<_>>();
+    assert_eq!(seeds.len(), PACKAGE_NAMES.len());
+
+    assert_ne!(
+        package_seed(BASE_SEED, "alpha"),
+        package_seed(54321, "alpha")
+    );
+    assert_ne!(package_seed(BASE_SEED, "alpha"), BASE_SEED);
+}
+
+#[test]
+fn test_derived_seeds_reproduce_feature_order_across_runs() {
+    for name in PACKAGE_NAMES {
+        assert_eq!(shuffled_features(name), shuffled_features(name));
+    }
+}
+
+#[test]
+fn test_derived_seeds_vary_feature_order_across_packages() {
+    let orders = PACKAGE_NAMES
+        .iter()
+        .map(|name| shuffled_features(name))
+        .collect::<std::collections::BTreeSet<_>>();
+
+    assert!(
+        orders.len() > 1,
+        "All packages were shuffled into the same order: {orders:?}"
+    );
+
+    let mut expected = features();
+    expected.sort();
+    for mut order in orders {
+        order.sort();
+        assert_eq!(order, expected);
+    }
+}
+
+#[switchy_async::test]
+async fn test_process_configs_reproduces_per_package_orders_for_base_seed() {
+    let temp_dir = switchy_fs::tempdir().unwrap();
+    let pkg_dirs = PACKAGE_NAMES
+        .iter()
+        .map(|name| create_package(temp_dir.path(), name))
+        .collect::<Vec<_>>();
+
+    let mut first_run = vec![];
+    for pkg_dir in &pkg_dirs {
+        first_run.push(process_package_features(pkg_dir).await);
+    }
+    let mut second_run = vec![];
+    for pkg_dir in &pkg_dirs {
+        second_run.push(process_package_features(pkg_dir).await);
+    }
+
+    assert_eq!(first_run, second_run);
+
+    let distinct_orders = first_run
+        .iter()
+        .map(|features| serde_json::to_string(features).unwrap())
+        .collect::<std::collections::BTreeSet<_>>();
+    assert!(
+        distinct_orders.len() > 1,
+        "All packages were shuffled into the same order: {first_run:?}"
+    );
+}
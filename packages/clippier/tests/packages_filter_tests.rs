@@ -1,5 +1,5 @@
 #[cfg(feature = "cargo-workspace")]
-use clippier::{OutputType, handle_features_command};
+use clippier::{FeaturesOptions, OutputType, handle_features_command};
 #[cfg(feature = "cargo-workspace")]
 use clippier_test_utilities::test_resources::load_test_workspace;
 #[cfg(feature = "cargo-workspace")]
@@ -10,37 +10,12 @@ use std::collections::HashSet;
 async fn test_packages_filter_single_package() {
     // Test filtering to a single package
     let (temp_dir, _) = load_test_workspace("complex");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,                       // os
-        None,                       // offset
-        None,                       // max
-        None,                       // max_parallel
-        None,                       // chunked
-        false,                      // spread
-        false,                      // randomize
-        None,                       // seed
-        None,                       // features
-        None,                       // skip_features
-        None,                       // required_features
-        Some(&["api".to_string()]), // packages - only select "api"
-        None,                       // changed_files
-        #[cfg(feature = "git-diff")]
-        None, // git_base
-        #[cfg(feature = "git-diff")]
-        None, // git_head
-        false,                      // include_reasoning
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec!["api".to_string()]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -61,37 +36,16 @@ async fn test_packages_filter_single_package() {
 async fn test_packages_filter_multiple_packages() {
     // Test filtering to multiple packages
     let (temp_dir, _) = load_test_workspace("complex");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&["api".to_string(), "web".to_string(), "cli".to_string()]),
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec![
+            "api".to_string(),
+            "web".to_string(),
+            "cli".to_string(),
+        ]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -119,70 +73,19 @@ async fn test_packages_filter_multiple_packages() {
 async fn test_packages_filter_empty_list() {
     // Test with empty packages list (should process all packages)
     let (temp_dir, _) = load_test_workspace("complex");
-    let result_empty = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&[]), // Empty list
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result_empty = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec![]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
-    let result_none = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        None, // No packages specified
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result_none = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     // Both should process all packages
@@ -208,37 +111,13 @@ async fn test_packages_filter_empty_list() {
 async fn test_packages_with_os_filter() {
     // Test combining --packages with --os
     let (temp_dir, _) = load_test_workspace("complex");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        Some("ubuntu"), // os filter
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&["api".to_string(), "web".to_string()]),
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        os: Some("ubuntu".to_string()),
+        packages: Some(vec!["api".to_string(), "web".to_string()]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -257,37 +136,13 @@ async fn test_packages_with_os_filter() {
 async fn test_packages_with_chunking() {
     // Test combining --packages with --chunked
     let (temp_dir, _) = load_test_workspace("complex");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        Some(2), // chunked
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&["web".to_string()]), // Package with multiple features
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        chunked: Some(2),
+        packages: Some(vec!["web".to_string()]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -315,37 +170,14 @@ async fn test_packages_with_chunking() {
 async fn test_packages_with_features_filter() {
     // Test combining --packages with --features and --skip-features
     let (temp_dir, _) = load_test_workspace("complex");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        Some("default"),  // specific feature
-        Some("advanced"), // skip feature
-        None,
-        Some(&["web".to_string()]),
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        features: Some("default".to_string()),
+        skip_features: Some("advanced".to_string()),
+        packages: Some(vec!["web".to_string()]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -370,37 +202,12 @@ async fn test_packages_with_features_filter() {
 async fn test_packages_nonexistent_package() {
     // Test with package name that doesn't exist
     let (temp_dir, _) = load_test_workspace("complex");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&["nonexistent_package".to_string()]),
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec!["nonexistent_package".to_string()]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -415,42 +222,17 @@ async fn test_packages_nonexistent_package() {
 async fn test_packages_mixed_valid_invalid() {
     // Test with mix of valid and invalid package names
     let (temp_dir, _) = load_test_workspace("complex");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&[
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec![
             "api".to_string(),
             "nonexistent".to_string(),
             "web".to_string(),
             "fake_package".to_string(),
         ]),
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -474,37 +256,12 @@ async fn test_packages_mixed_valid_invalid() {
 async fn test_packages_case_sensitivity() {
     // Test that package names are case-sensitive
     let (temp_dir, _) = load_test_workspace("complex");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&["API".to_string(), "Web".to_string()]), // Wrong case
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec!["API".to_string(), "Web".to_string()]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -519,37 +276,11 @@ async fn test_packages_case_sensitivity() {
 async fn test_packages_raw_output_format() {
     // Test that --packages works with Raw output format
     let (temp_dir, _) = load_test_workspace("complex");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&["api".to_string()]),
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None, // workspace_type filter
-        OutputType::Raw,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec!["api".to_string()]),
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok());
@@ -1,5 +1,5 @@
 #[cfg(feature = "cargo-workspace")]
-use clippier::{OutputType, handle_features_command};
+use clippier::{FeaturesOptions, OutputType, handle_features_command};
 #[cfg(feature = "cargo-workspace")]
 use clippier_test_utilities::test_resources::load_test_workspace;
 
@@ -7,37 +7,12 @@ use clippier_test_utilities::test_resources::load_test_workspace;
 #[switchy_async::test]
 async fn test_git_submodules_propagates_through_build_deps() {
     let (temp_dir, _) = load_test_workspace("propagation");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&["middle".to_string()]),
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec!["middle".to_string()]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok(), "Command failed: {:?}", result.err());
@@ -61,37 +36,12 @@ async fn test_git_submodules_propagates_through_build_deps() {
 #[switchy_async::test]
 async fn test_git_submodules_propagates_through_dev_and_regular_deps() {
     let (temp_dir, _) = load_test_workspace("propagation");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&["root".to_string()]),
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec!["root".to_string()]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok(), "Command failed: {:?}", result.err());
@@ -115,37 +65,12 @@ async fn test_git_submodules_propagates_through_dev_and_regular_deps() {
 #[switchy_async::test]
 async fn test_dependencies_propagate_and_merge() {
     let (temp_dir, _) = load_test_workspace("propagation");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&["middle".to_string()]),
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec!["middle".to_string()]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok(), "Command failed: {:?}", result.err());
@@ -181,37 +106,12 @@ async fn test_dependencies_propagate_and_merge() {
 #[switchy_async::test]
 async fn test_dependencies_propagate_to_root() {
     let (temp_dir, _) = load_test_workspace("propagation");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&["root".to_string()]),
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec!["root".to_string()]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok(), "Command failed: {:?}", result.err());
@@ -247,37 +147,12 @@ async fn test_dependencies_propagate_to_root() {
 #[switchy_async::test]
 async fn test_ci_steps_propagate_and_preserve_order() {
     let (temp_dir, _) = load_test_workspace("propagation");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&["root".to_string()]),
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec!["root".to_string()]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok(), "Command failed: {:?}", result.err());
@@ -317,37 +192,12 @@ async fn test_ci_steps_propagate_and_preserve_order() {
 #[switchy_async::test]
 async fn test_env_vars_propagate_with_overlay() {
     let (temp_dir, _) = load_test_workspace("propagation");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&["root".to_string()]),
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec!["root".to_string()]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok(), "Command failed: {:?}", result.err());
@@ -388,37 +238,12 @@ async fn test_env_vars_propagate_with_overlay() {
 #[switchy_async::test]
 async fn test_nightly_does_not_propagate() {
     let (temp_dir, _) = load_test_workspace("propagation");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&["leaf".to_string()]),
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec!["leaf".to_string()]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok(), "Command failed: {:?}", result.err());
@@ -439,37 +264,11 @@ async fn test_nightly_does_not_propagate() {
 #[switchy_async::test]
 async fn test_propagation_with_all_workspace_packages() {
     let (temp_dir, _) = load_test_workspace("propagation");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(result.is_ok(), "Command failed: {:?}", result.err());
@@ -493,37 +292,12 @@ async fn test_propagation_with_all_workspace_packages() {
 #[switchy_async::test]
 async fn test_external_deps_dont_break_propagation() {
     let (temp_dir, _) = load_test_workspace("propagation");
-    let result = handle_features_command(
-        temp_dir.path().to_str().unwrap(),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-        Some(&["leaf".to_string()]),
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        #[cfg(feature = "git-diff")]
-        None,
-        false,
-        None,
-        &[],
-        &[],
-        #[cfg(feature = "_transforms")]
-        &[],
-        #[cfg(feature = "_transforms")]
-        false,
-        #[cfg(feature = "_workspace")]
-        None,
-        OutputType::Json,
-    )
+    let result = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec!["leaf".to_string()]),
+        output: OutputType::Json,
+        ..Default::default()
+    })
     .await;
 
     assert!(
@@ -531,3 +305,50 @@ async fn test_external_deps_dont_break_propagation() {
         "Command should succeed even with external deps like serde"
     );
 }
+
+#[cfg(feature = "cargo-workspace")]
+async fn middle_dependencies(no_propagation: bool) -> String {
+    let (temp_dir, _) = load_test_workspace("propagation");
+    let output = handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().to_str().unwrap().to_string(),
+        packages: Some(vec!["middle".to_string()]),
+        no_propagation,
+        output: OutputType::Json,
+        ..Default::default()
+    })
+    .await
+    .expect("Command failed");
+
+    let packages: Vec<serde_json::Value> =
+        serde_json::from_str(&output).expect("Failed to parse JSON");
+
+    let middle = packages
+        .iter()
+        .find(|p| p["name"] == "middle")
+        .expect("middle package not found");
+
+    middle["dependencies"]
+        .as_str()
+        .expect("dependencies should be a string")
+        .to_string()
+}
+
+#[cfg(feature = "cargo-workspace")]
+#[switchy_async::test]
+async fn test_no_propagation_skips_dependency_system_deps() {
+    let propagated = middle_dependencies(false).await;
+    let own_only = middle_dependencies(true).await;
+
+    assert!(
+        propagated.contains("libfoo-dev"),
+        "Should inherit libfoo from leaf with propagation"
+    );
+    assert!(
+        !own_only.contains("libfoo-dev") && !own_only.contains("libbar-dev"),
+        "Should not inherit system deps from leaf without propagation: {own_only}"
+    );
+    assert!(
+        own_only.contains("libmiddle-dev"),
+        "Should keep its own libmiddle without propagation"
+    );
+}
@@ -0,0 +1,124 @@
+//! Tests for splitting the features matrix into shards.
+//!
+//! These tests verify that `--shards` partitions the matrix into exactly the requested
+//! number of shards, that the shards are balanced by job count, and that every job
+//! appears in exactly one shard.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+#[cfg(feature = "cargo-workspace")]
+use clippier::FeaturesOptions;
+use clippier::{FeaturesMatrixShard, shard_features_matrix};
+#[cfg(feature = "cargo-workspace")]
+use clippier_test_utilities::{features_matrix, test_resources::load_test_workspace};
+
+type Entry = serde_json::Map<String, serde_json::Value>;
+
+fn matrix(jobs: usize) -> Vec<Entry> {
+    (0..jobs)
+        .map(|job| {
+            let mut entry = serde_json::Map::new();
+            entry.insert("name".to_string(), serde_json::json!(format!("job-{job}")));
+            entry
+        })
+        .collect()
+}
+
+fn job_sizes(shards: &[FeaturesMatrixShard]) -> Vec<usize> {
+    shards.iter().map(|x| x.jobs.len()).collect()
+}
+
+#[test]
+fn test_shards_are_balanced_by_job_count() {
+    let shards = shard_features_matrix(matrix(10), 4).unwrap();
+
+    assert_eq!(
+        shards.iter().map(|x| x.shard).collect::<Vec<_>>(),
+        vec![0, 1, 2, 3]
+    );
+    assert_eq!(job_sizes(&shards), vec![3, 3, 2, 2]);
+}
+
+#[test]
+fn test_every_job_appears_in_exactly_one_shard() {
+    let matrix = matrix(17);
+
+    for shard_count in 1..=20 {
+        let shards = shard_features_matrix(matrix.clone(), shard_count).unwrap();
+        assert_eq!(shards.len(), shard_count);
+
+        let sizes = job_sizes(&shards);
+        let largest = sizes.iter().max().unwrap();
+        let smallest = sizes.iter().min().unwrap();
+        assert!(largest - smallest <= 1, "{shard_count} shards: {sizes:?}");
+
+        let jobs = shards.into_iter().flat_map(|x| x.jobs).collect::<Vec<_>>();
+        assert_eq!(jobs, matrix, "{shard_count} shards");
+    }
+}
+
+#[test]
+fn test_more_shards_than_jobs_leaves_trailing_shards_empty() {
+    let shards = shard_features_matrix(matrix(2), 4).unwrap();
+
+    assert_eq!(job_sizes(&shards), vec![1, 1, 0, 0]);
+}
+
+#[test]
+fn test_zero_shards_errors() {
+    assert!(shard_features_matrix(matrix(3), 0).is_err());
+}
+
+#[cfg(feature = "cargo-workspace")]
+async fn sharded_features_matrix(shards: Option<u16>) -> String {
+    let (temp_dir, _) = load_test_workspace("complex");
+
+    features_matrix(
+        temp_dir.path(),
+        FeaturesOptions {
+            os: Some("ubuntu".to_string()),
+            chunked: Some(1),
+            shards,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap()
+}
+
+#[cfg(feature = "cargo-workspace")]
+#[switchy_async::test]
+async fn test_features_command_emits_shards_covering_the_matrix() {
+    let flat: Vec<Entry> = serde_json::from_str(&sharded_features_matrix(None).await).unwrap();
+    assert!(flat.len() > 3);
+
+    let result = sharded_features_matrix(Some(3)).await;
+    let shards: Vec<FeaturesMatrixShard> = serde_json::from_str(&result).unwrap();
+
+    assert_eq!(shards.len(), 3, "{result}");
+    assert_eq!(shards, shard_features_matrix(flat.clone(), 3).unwrap());
+    assert_eq!(
+        shards.into_iter().flat_map(|x| x.jobs).collect::<Vec<_>>(),
+        flat
+    );
+}
+
+#[cfg(feature = "cargo-workspace")]
+#[switchy_async::test]
+async fn test_features_command_rejects_shards_with_raw_output() {
+    let (temp_dir, _) = load_test_workspace("complex");
+
+    let error = clippier::handle_features_command(&FeaturesOptions {
+        file: temp_dir.path().display().to_string(),
+        shards: Some(3),
+        output: clippier::OutputType::Raw,
+        ..Default::default()
+    })
+    .await
+    .unwrap_err()
+    .to_string();
+
+    assert!(error.contains("--output json"), "{error}");
+}
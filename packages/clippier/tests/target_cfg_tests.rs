@@ -0,0 +1,199 @@
+//! Tests for `--target` aware feature and dependency gating.
+//!
+//! These tests verify that `[target.'cfg(...)'.dependencies]` tables are evaluated against
+//! the requested target triple, so features and dependencies that are inactive for the
+//! target are left out of the features matrix and affected-package analysis.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::{
+    FeaturesOptions, TargetTriple, find_affected_packages, find_affected_packages_for_target,
+};
+use clippier_test_utilities::features_matrix;
+
+const LINUX: &str = "x86_64-unknown-linux-gnu";
+const MACOS: &str = "aarch64-apple-darwin";
+const WINDOWS: &str = "x86_64-pc-windows-msvc";
+
+/// Creates a workspace where `player` depends on `alsa-backend` only on linux
+fn create_linux_only_dependency_workspace() -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+
+    switchy_fs::sync::write(
+        temp_dir.path().join("Cargo.toml"),
+        r#"
+[workspace]
+members = ["packages/player", "packages/alsa-backend"]
+"#,
+    )
+    .unwrap();
+
+    let manifests = [
+        (
+            "player",
+            r#"
+[package]
+name = "player"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+default = []
+alsa = ["dep:alsa-backend"]
+audio = ["alsa"]
+fast = []
+
+[target.'cfg(target_os = "linux")'.dependencies]
+alsa-backend = { workspace = true, optional = true }
+"#,
+        ),
+        (
+            "alsa-backend",
+            r#"
+[package]
+name = "alsa-backend"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+default = []
+"#,
+        ),
+    ];
+
+    for (package, manifest) in manifests {
+        let pkg_dir = temp_dir.path().join("packages").join(package);
+        switchy_fs::sync::create_dir_all(pkg_dir.join("src")).unwrap();
+        switchy_fs::sync::write(pkg_dir.join("Cargo.toml"), manifest).unwrap();
+        switchy_fs::sync::write(pkg_dir.join("src/lib.rs"), "// test lib").unwrap();
+    }
+
+    temp_dir
+}
+
+/// Returns every feature listed for `package` across its matrix entries
+async fn package_features(
+    temp_dir: &switchy_fs::TempDir,
+    package: &str,
+    target: Option<&str>,
+) -> Vec<String> {
+    let result = features_matrix(
+        temp_dir.path(),
+        FeaturesOptions {
+            target: target.map(str::to_string),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let matrix: Vec<serde_json::Map<String, serde_json::Value>> =
+        serde_json::from_str(&result).unwrap();
+
+    let mut features = matrix
+        .iter()
+        .filter(|x| x.get("name").and_then(|x| x.as_str()) == Some(package))
+        .flat_map(|x| x.get("features").and_then(|x| x.as_array()).unwrap())
+        .map(|x| x.as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    features.sort();
+    features
+}
+
+#[switchy_async::test]
+async fn test_target_includes_cfg_gated_features_for_matching_target() {
+    let temp_dir = create_linux_only_dependency_workspace();
+
+    assert_eq!(
+        package_features(&temp_dir, "player", Some(LINUX)).await,
+        vec!["alsa", "audio", "default", "fast"]
+    );
+}
+
+#[switchy_async::test]
+async fn test_target_excludes_cfg_gated_features_for_other_targets() {
+    let temp_dir = create_linux_only_dependency_workspace();
+
+    for target in [MACOS, WINDOWS] {
+        assert_eq!(
+            package_features(&temp_dir, "player", Some(target)).await,
+            vec!["default", "fast"],
+            "{target}"
+        );
+    }
+}
+
+#[switchy_async::test]
+async fn test_no_target_keeps_all_features() {
+    let temp_dir = create_linux_only_dependency_workspace();
+
+    assert_eq!(
+        package_features(&temp_dir, "player", None).await,
+        vec!["alsa", "audio", "default", "fast"]
+    );
+}
+
+#[switchy_async::test]
+async fn test_invalid_target_triple_is_an_error() {
+    let temp_dir = create_linux_only_dependency_workspace();
+
+    let error = features_matrix(
+        temp_dir.path(),
+        FeaturesOptions {
+            target: Some("x86_64--linux".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap_err();
+
+    assert!(
+        error.to_string().contains("x86_64--linux"),
+        "unexpected error: {error}"
+    );
+}
+
+#[test]
+fn test_affected_packages_follow_cfg_gated_dependency_for_matching_target() {
+    let temp_dir = create_linux_only_dependency_workspace();
+    let changed_files = vec!["packages/alsa-backend/src/lib.rs".to_string()];
+    let linux: TargetTriple = LINUX.parse().unwrap();
+
+    let affected =
+        find_affected_packages_for_target(temp_dir.path(), &changed_files, &[], Some(&linux), None)
+            .unwrap();
+
+    assert_eq!(affected, vec!["alsa-backend", "player"]);
+}
+
+#[test]
+fn test_affected_packages_ignore_cfg_gated_dependency_for_other_targets() {
+    let temp_dir = create_linux_only_dependency_workspace();
+    let changed_files = vec!["packages/alsa-backend/src/lib.rs".to_string()];
+
+    for target in [MACOS, WINDOWS] {
+        let target: TargetTriple = target.parse().unwrap();
+
+        let affected = find_affected_packages_for_target(
+            temp_dir.path(),
+            &changed_files,
+            &[],
+            Some(&target),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(affected, vec!["alsa-backend"], "{target}");
+    }
+}
+
+#[test]
+fn test_affected_packages_without_target_follow_all_target_dependencies() {
+    let temp_dir = create_linux_only_dependency_workspace();
+    let changed_files = vec!["packages/alsa-backend/src/lib.rs".to_string()];
+
+    let affected = find_affected_packages(temp_dir.path(), &changed_files, &[]).unwrap();
+
+    assert_eq!(affected, vec!["alsa-backend", "player"]);
+}
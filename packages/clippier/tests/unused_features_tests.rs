@@ -0,0 +1,190 @@
+//! Tests for unreferenced feature detection.
+//!
+//! These tests verify that features referenced by other features, dependent packages,
+//! `clippier.toml` filters or target `required-features` are not reported, and that
+//! orphaned features are.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::unused_features::{
+    UnreferencedFeature, find_unreferenced_features, format_unused_features_report,
+};
+
+const WORKSPACE: &str = r#"
+[workspace]
+members = ["packages/*"]
+
+[workspace.dependencies]
+core_lib = { path = "packages/core", features = ["base"] }
+"#;
+
+const CORE: &str = r#"
+[package]
+name = "core_lib"
+version = "0.1.0"
+
+[features]
+default = ["simd"]
+base = []
+simd = []
+tls = ["base"]
+orphan = []
+"#;
+
+const APP: &str = r#"
+[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+core = { workspace = true, package = "core_lib", optional = true }
+
+[features]
+default = []
+secure = ["core?/tls"]
+ci-only = []
+forgotten = []
+extra = []
+
+[[bin]]
+name = "app-extra"
+path = "src/extra.rs"
+required-features = ["extra"]
+"#;
+
+const APP_CLIPPIER: &str = r#"
+[[config]]
+os = "ubuntu"
+dependencies = [{ command = "echo ci", features = ["ci-only"] }]
+"#;
+
+/// Creates a workspace with a library and a binary package
+fn create_workspace() -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+
+    switchy_fs::sync::write(root.join("Cargo.toml"), WORKSPACE).unwrap();
+
+    let core_dir = root.join("packages/core");
+    switchy_fs::sync::create_dir_all(core_dir.join("src")).unwrap();
+    switchy_fs::sync::write(core_dir.join("Cargo.toml"), CORE).unwrap();
+    switchy_fs::sync::write(core_dir.join("src/lib.rs"), "// test lib").unwrap();
+
+    let app_dir = root.join("packages/app");
+    switchy_fs::sync::create_dir_all(app_dir.join("src")).unwrap();
+    switchy_fs::sync::write(app_dir.join("Cargo.toml"), APP).unwrap();
+    switchy_fs::sync::write(app_dir.join("clippier.toml"), APP_CLIPPIER).unwrap();
+    switchy_fs::sync::write(app_dir.join("src/main.rs"), "fn main() {}").unwrap();
+
+    temp_dir
+}
+
+#[test]
+fn test_unused_features_reports_orphans() {
+    let temp_dir = create_workspace();
+
+    let report = find_unreferenced_features(temp_dir.path(), &[]).unwrap();
+
+    assert_eq!(report.features_checked, 10);
+    assert_eq!(
+        report.unreferenced,
+        vec![
+            UnreferencedFeature {
+                package: "app".to_string(),
+                feature: "forgotten".to_string(),
+                public_api: false,
+            },
+            UnreferencedFeature {
+                package: "app".to_string(),
+                feature: "secure".to_string(),
+                public_api: false,
+            },
+            UnreferencedFeature {
+                package: "core_lib".to_string(),
+                feature: "orphan".to_string(),
+                public_api: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_unused_features_counts_references() {
+    let temp_dir = create_workspace();
+
+    let report = find_unreferenced_features(temp_dir.path(), &[]).unwrap();
+    let reported = |package: &str, feature: &str| {
+        report
+            .unreferenced
+            .iter()
+            .any(|x| x.package == package && x.feature == feature)
+    };
+
+    // Enabled by the `default` feature
+    assert!(!reported("core_lib", "simd"));
+    // Enabled by `[workspace.dependencies]` and the `tls` feature
+    assert!(!reported("core_lib", "base"));
+    // Enabled through a renamed dependency with `core?/tls`
+    assert!(!reported("core_lib", "tls"));
+    // Named by a clippier.toml step filter
+    assert!(!reported("app", "ci-only"));
+    // Named by a target's `required-features`
+    assert!(!reported("app", "extra"));
+    // `default` is never reported
+    assert!(!reported("app", "default"));
+    assert!(!reported("core_lib", "default"));
+}
+
+#[test]
+fn test_unused_features_ignores_features() {
+    let temp_dir = create_workspace();
+
+    let report = find_unreferenced_features(
+        temp_dir.path(),
+        &["orphan".to_string(), "secure".to_string()],
+    )
+    .unwrap();
+
+    let features: Vec<&str> = report
+        .unreferenced
+        .iter()
+        .map(|x| x.feature.as_str())
+        .collect();
+    assert_eq!(features, vec!["forgotten"]);
+}
+
+#[test]
+fn test_unused_features_clean_workspace() {
+    let temp_dir = create_workspace();
+
+    let report = find_unreferenced_features(
+        temp_dir.path(),
+        &[
+            "forgotten".to_string(),
+            "orphan".to_string(),
+            "secure".to_string(),
+        ],
+    )
+    .unwrap();
+
+    assert!(report.is_clean());
+    assert!(format_unused_features_report(&report).contains("✅ All features are referenced"));
+}
+
+#[test]
+fn test_unused_features_report_formatting() {
+    let temp_dir = create_workspace();
+
+    let report = find_unreferenced_features(temp_dir.path(), &[]).unwrap();
+    let output = format_unused_features_report(&report);
+
+    assert!(output.contains("Found 3 unreferenced features"), "{output}");
+    assert!(output.contains("📦 app: forgotten\n"), "{output}");
+    assert!(
+        output
+            .contains("📦 core_lib: orphan (library feature, may be enabled by downstream crates)"),
+        "{output}"
+    );
+}
@@ -0,0 +1,223 @@
+//! Tests for watch mode.
+//!
+//! These tests verify that changes reported by a watcher regenerate the output once per
+//! burst of changes, and that the polling watcher notices edits to the workspace's
+//! manifests.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use std::{
+    collections::{BTreeSet, VecDeque},
+    path::PathBuf,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use clippier::watch::{PollingWatcher, WatchOutput, WorkspaceWatcher, watch, watched_files};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Watcher reporting a scripted sequence of changes, each after a delay, then stopping
+/// after staying idle for longer than the debounce window
+struct MockWatcher {
+    changes: VecDeque<(Duration, PathBuf)>,
+}
+
+impl MockWatcher {
+    fn new(changes: impl IntoIterator<Item = (Duration, &'static str)>) -> Self {
+        Self {
+            changes: changes
+                .into_iter()
+                .map(|(delay, path)| (delay, PathBuf::from(path)))
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl WorkspaceWatcher for MockWatcher {
+    async fn changed(&mut self) -> Result<Option<BTreeSet<PathBuf>>, BoxError> {
+        let Some((delay, _)) = self.changes.front() else {
+            switchy_async::time::sleep(DEBOUNCE * 2).await;
+            return Ok(None);
+        };
+
+        // Only consume the change once reported, as the debounce may cancel the wait
+        switchy_async::time::sleep(*delay).await;
+        let (_, path) = self.changes.pop_front().unwrap();
+
+        Ok(Some(BTreeSet::from([path])))
+    }
+}
+
+/// Runs [`watch`] with `watcher`, returning the emitted outputs
+async fn run_watch(watcher: &mut MockWatcher) -> Vec<String> {
+    let mut generations = 0;
+    let mut emitted = vec![];
+
+    watch(
+        watcher,
+        DEBOUNCE,
+        || {
+            generations += 1;
+            let generation = generations;
+            async move { Ok(format!("matrix {generation}")) }
+        },
+        |output| {
+            emitted.push(output.to_string());
+            Ok(())
+        },
+    )
+    .await
+    .unwrap();
+
+    emitted
+}
+
+#[switchy_async::test]
+async fn test_cargo_toml_edit_regenerates_once_after_debounce() {
+    let mut watcher = MockWatcher::new([
+        (Duration::from_millis(10), "packages/api/Cargo.toml"),
+        (Duration::from_millis(20), "packages/api/Cargo.toml"),
+        (Duration::from_millis(20), "packages/api/Cargo.toml"),
+    ]);
+
+    let emitted = run_watch(&mut watcher).await;
+
+    assert_eq!(emitted, vec!["matrix 1", "matrix 2"]);
+}
+
+#[switchy_async::test]
+async fn test_changes_further_apart_than_debounce_regenerate_each() {
+    let mut watcher = MockWatcher::new([
+        (Duration::from_millis(10), "Cargo.toml"),
+        (DEBOUNCE * 2, "packages/api/clippier.toml"),
+    ]);
+
+    let emitted = run_watch(&mut watcher).await;
+
+    assert_eq!(emitted, vec!["matrix 1", "matrix 2", "matrix 3"]);
+}
+
+#[switchy_async::test]
+async fn test_generation_error_keeps_watching() {
+    let mut watcher = MockWatcher::new([(Duration::from_millis(10), "Cargo.toml")]);
+    let mut generations = 0;
+    let mut emitted = vec![];
+
+    watch(
+        &mut watcher,
+        DEBOUNCE,
+        || {
+            generations += 1;
+            let generation = generations;
+            async move {
+                if generation == 1 {
+                    Err::<String, BoxError>("invalid manifest".into())
+                } else {
+                    Ok(format!("matrix {generation}"))
+                }
+            }
+        },
+        |output| {
+            emitted.push(output.to_string());
+            Ok(())
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(emitted, vec!["matrix 2"]);
+}
+
+/// Creates a workspace with the members `packages/api` and `packages/models`
+fn create_workspace() -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+
+    switchy_fs::sync::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"packages/*\"]\n",
+    )
+    .unwrap();
+
+    for name in ["api", "models"] {
+        let package_dir = root.join("packages").join(name);
+        switchy_fs::sync::create_dir_all(&package_dir).unwrap();
+        switchy_fs::sync::write(
+            package_dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n"),
+        )
+        .unwrap();
+    }
+
+    temp_dir
+}
+
+#[test_log::test]
+fn test_watched_files_include_member_manifests() {
+    let temp_dir = create_workspace();
+    let root = temp_dir.path();
+
+    let files = watched_files(root);
+
+    assert_eq!(
+        files,
+        vec![
+            root.join("Cargo.toml"),
+            root.join("clippier.toml"),
+            root.join("packages/api/Cargo.toml"),
+            root.join("packages/api/clippier.toml"),
+            root.join("packages/models/Cargo.toml"),
+            root.join("packages/models/clippier.toml"),
+        ]
+    );
+}
+
+#[switchy_async::test]
+async fn test_polling_watcher_reports_edited_cargo_toml() {
+    let temp_dir = create_workspace();
+    let root = temp_dir.path();
+    let mut watcher = PollingWatcher::new(root, Duration::from_millis(10));
+
+    let manifest = root.join("packages/api/Cargo.toml");
+    switchy_fs::sync::write(
+        &manifest,
+        "[package]\nname = \"api\"\nversion = \"0.2.0\"\n",
+    )
+    .unwrap();
+
+    let changed = watcher.changed().await.unwrap();
+
+    assert_eq!(changed, Some(BTreeSet::from([manifest])));
+}
+
+#[switchy_async::test]
+async fn test_polling_watcher_reports_created_clippier_toml() {
+    let temp_dir = create_workspace();
+    let root = temp_dir.path();
+    let mut watcher = PollingWatcher::new(root, Duration::from_millis(10));
+
+    let config = root.join("packages/models/clippier.toml");
+    switchy_fs::sync::write(&config, "[[config]]\nos = \"ubuntu\"\n").unwrap();
+
+    let changed = watcher.changed().await.unwrap();
+
+    assert_eq!(changed, Some(BTreeSet::from([config])));
+}
+
+#[test_log::test]
+fn test_file_output_rewrites_file() {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let path = temp_dir.path().join("matrix.json");
+    let output = WatchOutput::File(path.clone());
+
+    output.write("[1]").unwrap();
+    output.write("[2]").unwrap();
+
+    assert_eq!(switchy_fs::sync::read_to_string(&path).unwrap(), "[2]");
+}
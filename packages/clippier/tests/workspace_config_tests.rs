@@ -12,20 +12,9 @@ async fn test_workspace_config_defaults() {
     let workspace_path =
         PathBuf::from("test-resources/workspaces/workspace-config-test/packages/pkg1");
 
-    let result = clippier::process_configs(
-        &workspace_path,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-    )
-    .await
-    .expect("Failed to process configs");
+    let result = clippier::process_configs(&workspace_path, &clippier::FeaturesOptions::default())
+        .await
+        .expect("Failed to process configs");
 
     assert_eq!(result.len(), 1);
 
@@ -59,20 +48,9 @@ async fn test_workspace_config_with_package_overrides() {
     let workspace_path =
         PathBuf::from("test-resources/workspaces/workspace-config-test/packages/pkg2");
 
-    let result = clippier::process_configs(
-        &workspace_path,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-    )
-    .await
-    .expect("Failed to process configs");
+    let result = clippier::process_configs(&workspace_path, &clippier::FeaturesOptions::default())
+        .await
+        .expect("Failed to process configs");
 
     assert_eq!(result.len(), 1);
 
@@ -107,20 +85,9 @@ async fn test_backward_compatibility_no_workspace_config() {
     // Test that workspaces without workspace-level config still work
     let workspace_path = PathBuf::from("test-resources/workspaces/propagation/root");
 
-    let result = clippier::process_configs(
-        &workspace_path,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-    )
-    .await
-    .expect("Failed to process configs");
+    let result = clippier::process_configs(&workspace_path, &clippier::FeaturesOptions::default())
+        .await
+        .expect("Failed to process configs");
 
     assert_eq!(result.len(), 1);
 
@@ -0,0 +1,152 @@
+//! Tests for feature propagation in workspace dependency resolution.
+//!
+//! These tests verify that a transitively reached workspace dependency is resolved with
+//! the features its dependents enable on it, so that a feature forwarding a dependency's
+//! sub-feature (`dep/feature`) pulls in the workspace dependencies that sub-feature
+//! activates.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+const WORKSPACE: &str = r#"
+[workspace]
+members = ["packages/*"]
+
+[workspace.dependencies]
+app = { path = "packages/app" }
+core = { path = "packages/core" }
+db = { path = "packages/db" }
+report = { path = "packages/report" }
+util = { path = "packages/util" }
+"#;
+
+/// `app` → `core` → (`storage` → `cache` → `dep:db`) → `db` → `util`
+const APP: &str = r#"
+[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+core = { workspace = true }
+report = { workspace = true, optional = true }
+
+[features]
+default = []
+full = ["core/storage"]
+weak = ["core?/storage"]
+reports = ["dep:report"]
+"#;
+
+const REPORT: &str = r#"
+[package]
+name = "report"
+version = "0.1.0"
+
+[dependencies]
+core = { workspace = true, features = ["storage"] }
+"#;
+
+const CORE: &str = r#"
+[package]
+name = "core"
+version = "0.1.0"
+
+[dependencies]
+db = { workspace = true, optional = true }
+
+[features]
+default = []
+storage = ["cache"]
+cache = ["dep:db"]
+"#;
+
+const DB: &str = r#"
+[package]
+name = "db"
+version = "0.1.0"
+
+[dependencies]
+util = { workspace = true }
+"#;
+
+const UTIL: &str = r#"
+[package]
+name = "util"
+version = "0.1.0"
+"#;
+
+fn create_workspace() -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+
+    switchy_fs::sync::write(root.join("Cargo.toml"), WORKSPACE).unwrap();
+
+    for (name, cargo_toml) in [
+        ("app", APP),
+        ("report", REPORT),
+        ("core", CORE),
+        ("db", DB),
+        ("util", UTIL),
+    ] {
+        let package_dir = root.join("packages").join(name);
+        switchy_fs::sync::create_dir_all(package_dir.join("src")).unwrap();
+        switchy_fs::sync::write(package_dir.join("Cargo.toml"), cargo_toml).unwrap();
+        switchy_fs::sync::write(package_dir.join("src/lib.rs"), "// test lib").unwrap();
+    }
+
+    temp_dir
+}
+
+fn dependency_names(temp_dir: &switchy_fs::TempDir, features: Option<&[&str]>) -> Vec<String> {
+    let features = features.map(|x| x.iter().map(ToString::to_string).collect::<Vec<_>>());
+
+    let mut names: Vec<String> =
+        clippier::find_workspace_dependencies(temp_dir.path(), "app", features.as_deref(), false)
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_default_features_do_not_activate_sub_feature_dependencies() {
+    let temp_dir = create_workspace();
+
+    assert_eq!(dependency_names(&temp_dir, None), vec!["core"]);
+}
+
+#[test]
+fn test_forwarded_sub_feature_pulls_in_transitive_workspace_dependencies() {
+    let temp_dir = create_workspace();
+
+    assert_eq!(
+        dependency_names(&temp_dir, Some(&["full"])),
+        vec!["core", "db", "util"]
+    );
+}
+
+#[test]
+fn test_weak_sub_feature_propagates_to_enabled_dependency() {
+    let temp_dir = create_workspace();
+
+    // `core` is not optional, so `core?/storage` enables `storage` on it
+    assert_eq!(
+        dependency_names(&temp_dir, Some(&["weak"])),
+        vec!["core", "db", "util"]
+    );
+}
+
+#[test]
+fn test_declared_dependency_features_propagate_and_merge() {
+    let temp_dir = create_workspace();
+
+    // `app` enables no features on `core`, but `report` declares `features = ["storage"]`,
+    // and `core` is resolved with the union of both
+    assert_eq!(
+        dependency_names(&temp_dir, Some(&["reports"])),
+        vec!["core", "db", "report", "util"]
+    );
+}
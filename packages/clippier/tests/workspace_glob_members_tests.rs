@@ -20,19 +20,8 @@ async fn test_glob_members_workspace_loads() {
     assert_eq!(workspace_members, vec!["packages/*"]);
 
     // But clippier should be able to process this workspace correctly
-    let result = clippier::process_configs(
-        temp_dir.path(),
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-    )
-    .await;
+    let result =
+        clippier::process_configs(temp_dir.path(), &clippier::FeaturesOptions::default()).await;
 
     // Should succeed without errors
     assert!(
@@ -110,19 +99,8 @@ async fn test_glob_members_feature_generation() {
 
     // Process just pkg-a which has feature-a
     let pkg_a_path = temp_dir.path().join("packages/pkg-a");
-    let result = clippier::process_configs(
-        &pkg_a_path,
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-    )
-    .await;
+    let result =
+        clippier::process_configs(&pkg_a_path, &clippier::FeaturesOptions::default()).await;
 
     assert!(
         result.is_ok(),
@@ -196,19 +174,8 @@ edition = "2021"
     switchy_fs::sync::write(special_path.join("src/lib.rs"), "//! Special")
         .expect("Failed to write lib.rs");
 
-    let result = clippier::process_configs(
-        temp_dir.path(),
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-    )
-    .await;
+    let result =
+        clippier::process_configs(temp_dir.path(), &clippier::FeaturesOptions::default()).await;
 
     assert!(
         result.is_ok(),
@@ -255,19 +222,8 @@ edition = "2021"
             .expect("Failed to write lib.rs");
     }
 
-    let result = clippier::process_configs(
-        temp_dir.path(),
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-    )
-    .await;
+    let result =
+        clippier::process_configs(temp_dir.path(), &clippier::FeaturesOptions::default()).await;
 
     assert!(
         result.is_ok(),
@@ -294,19 +250,8 @@ resolver = "2"
 
     // Don't create any packages - the glob should match nothing
 
-    let result = clippier::process_configs(
-        temp_dir.path(),
-        None,
-        None,
-        None,
-        false,
-        false,
-        None,
-        None,
-        None,
-        None,
-    )
-    .await;
+    let result =
+        clippier::process_configs(temp_dir.path(), &clippier::FeaturesOptions::default()).await;
 
     // Should not panic, might return empty or error gracefully
     // The important thing is it doesn't crash
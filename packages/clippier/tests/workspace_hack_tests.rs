@@ -0,0 +1,206 @@
+//! Tests for the workspace-hack feature completeness check.
+//!
+//! These tests verify the union of external dependency features computed across
+//! workspace members and the diff against the designated workspace-hack package.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use clippier::workspace_hack::{MissingHackEntry, check_workspace_hack};
+
+const WORKSPACE: &str = r#"
+[workspace]
+members = ["packages/*"]
+
+[workspace.dependencies]
+log = { version = "0.4", features = ["std"] }
+serde = { version = "1", default-features = false }
+tokio = "1"
+workspace-hack = { path = "packages/workspace-hack" }
+"#;
+
+const API: &str = r#"
+[package]
+name = "api"
+version = "0.1.0"
+
+[dependencies]
+log = { workspace = true }
+serde = { workspace = true, features = ["derive"] }
+tokio = { workspace = true, features = ["macros"] }
+workspace-hack = { workspace = true }
+"#;
+
+const CLI: &str = r#"
+[package]
+name = "cli"
+version = "0.1.0"
+
+[dependencies]
+api = { path = "../api" }
+clap_builder = { package = "clap", version = "4", default-features = false, features = ["std"] }
+tokio = { workspace = true, optional = true, features = ["rt"] }
+
+[dev-dependencies]
+serde = { workspace = true, features = ["alloc"] }
+
+[features]
+default = []
+async = ["dep:tokio", "tokio?/time", "clap_builder/env"]
+"#;
+
+const COMPLETE_HACK: &str = r#"
+[package]
+name = "workspace-hack"
+version = "0.1.0"
+
+[dependencies]
+clap = { version = "4", features = ["env", "std"] }
+log = { workspace = true }
+serde = { workspace = true, features = ["alloc", "derive"] }
+tokio = { workspace = true, features = ["macros", "rt", "time"] }
+"#;
+
+/// Creates a workspace with two members and a workspace-hack package
+fn create_workspace(hack_manifest: &str) -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+
+    switchy_fs::sync::write(root.join("Cargo.toml"), WORKSPACE).unwrap();
+
+    for (name, manifest) in [
+        ("api", API),
+        ("cli", CLI),
+        ("workspace-hack", hack_manifest),
+    ] {
+        let package_dir = root.join("packages").join(name);
+        switchy_fs::sync::create_dir_all(package_dir.join("src")).unwrap();
+        switchy_fs::sync::write(package_dir.join("Cargo.toml"), manifest).unwrap();
+        switchy_fs::sync::write(package_dir.join("src/lib.rs"), "// test lib").unwrap();
+    }
+
+    temp_dir
+}
+
+fn features(values: &[&str]) -> BTreeSet<String> {
+    values.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn test_workspace_hack_computes_feature_union() {
+    let temp_dir = create_workspace(COMPLETE_HACK);
+
+    let report = check_workspace_hack(temp_dir.path(), "workspace-hack").unwrap();
+
+    let expected: BTreeMap<String, BTreeSet<String>> = [
+        ("clap", features(&["env", "std"])),
+        ("log", features(&["default", "std"])),
+        ("serde", features(&["alloc", "derive"])),
+        ("tokio", features(&["default", "macros", "rt", "time"])),
+    ]
+    .into_iter()
+    .map(|(name, features)| (name.to_string(), features))
+    .collect();
+
+    assert_eq!(report.unified_features, expected);
+}
+
+#[test]
+fn test_workspace_hack_complete() {
+    let temp_dir = create_workspace(COMPLETE_HACK);
+
+    let report = check_workspace_hack(temp_dir.path(), "workspace-hack").unwrap();
+
+    assert!(
+        report.is_complete(),
+        "Unexpected missing entries: {report:?}"
+    );
+}
+
+#[test]
+fn test_workspace_hack_excludes_workspace_members() {
+    let temp_dir = create_workspace(COMPLETE_HACK);
+
+    let report = check_workspace_hack(temp_dir.path(), "workspace-hack").unwrap();
+
+    assert!(!report.unified_features.contains_key("api"));
+    assert!(!report.unified_features.contains_key("workspace-hack"));
+}
+
+#[test]
+fn test_workspace_hack_reports_missing_features_and_dependencies() {
+    let temp_dir = create_workspace(
+        r#"
+[package]
+name = "workspace-hack"
+version = "0.1.0"
+
+[dependencies]
+log = { workspace = true }
+serde = { workspace = true, features = ["derive"] }
+tokio = { version = "1", default-features = false, features = ["macros"] }
+"#,
+    );
+
+    let report = check_workspace_hack(temp_dir.path(), "workspace-hack").unwrap();
+
+    assert!(!report.is_complete());
+    assert_eq!(
+        report.missing,
+        vec![
+            MissingHackEntry {
+                dependency: "clap".to_string(),
+                missing_dependency: true,
+                missing_features: features(&["env", "std"]),
+                required_by: features(&["cli"]),
+            },
+            MissingHackEntry {
+                dependency: "serde".to_string(),
+                missing_dependency: false,
+                missing_features: features(&["alloc"]),
+                required_by: features(&["cli"]),
+            },
+            MissingHackEntry {
+                dependency: "tokio".to_string(),
+                missing_dependency: false,
+                missing_features: features(&["default", "rt", "time"]),
+                required_by: features(&["api", "cli"]),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_workspace_hack_unknown_package_errors() {
+    let temp_dir = create_workspace(COMPLETE_HACK);
+
+    let result = check_workspace_hack(temp_dir.path(), "missing-hack");
+
+    assert!(result.is_err());
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("'missing-hack' is not a workspace member")
+    );
+}
+
+#[test]
+fn test_workspace_hack_report_formatting() {
+    let temp_dir = create_workspace(
+        r#"
+[package]
+name = "workspace-hack"
+version = "0.1.0"
+"#,
+    );
+
+    let report = check_workspace_hack(temp_dir.path(), "workspace-hack").unwrap();
+    let output = clippier::format_workspace_hack_report(&report);
+
+    assert!(output.contains("Found 4 dependencies with missing workspace-hack entries"));
+    assert!(output.contains("clap (not a dependency): [env, std] required by cli"));
+}
@@ -0,0 +1,192 @@
+//! Tests for the workspace-system-deps command.
+//!
+//! These tests verify that the package install commands of every `clippier.toml` in the
+//! workspace are merged into one deduplicated, sorted install command per package
+//! manager, and that other dependency commands are passed through verbatim.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use clippier::{
+    OutputType, PackageManager, handle_workspace_system_deps_command,
+    system_deps::{collect_workspace_system_deps, parse_install_command},
+};
+
+const WORKSPACE: &str = r#"
+[workspace]
+members = ["packages/*"]
+"#;
+
+const AUDIO_CLIPPIER: &str = r#"
+[[config]]
+os = "ubuntu"
+dependencies = [
+    { command = "sudo apt-get update && sudo apt-get install libasound2-dev libssl-dev" },
+    { command = "curl -o vectors.tar.gz https://example.com/vectors.tar.gz" },
+]
+
+[[config]]
+os = "macos"
+dependencies = [{ command = "brew install pkg-config openssl" }]
+"#;
+
+const DATABASE_CLIPPIER: &str = r#"
+[[config]]
+os = "ubuntu"
+dependencies = [
+    { command = "sudo apt-get install -y libssl-dev libsqlite3-dev", features = ["sqlite"] },
+    { command = "curl -o vectors.tar.gz https://example.com/vectors.tar.gz" },
+]
+
+[[config]]
+os = "macos"
+dependencies = [{ command = "brew install openssl sqlite3" }]
+"#;
+
+/// Creates a workspace with two packages declaring overlapping system dependencies
+fn create_workspace() -> switchy_fs::TempDir {
+    let temp_dir = switchy_fs::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+
+    switchy_fs::sync::write(root.join("Cargo.toml"), WORKSPACE).unwrap();
+
+    for (name, clippier_toml) in [("audio", AUDIO_CLIPPIER), ("database", DATABASE_CLIPPIER)] {
+        let package_dir = root.join("packages").join(name);
+        switchy_fs::sync::create_dir_all(package_dir.join("src")).unwrap();
+        switchy_fs::sync::write(
+            package_dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n"),
+        )
+        .unwrap();
+        switchy_fs::sync::write(package_dir.join("src/lib.rs"), "// test lib").unwrap();
+        switchy_fs::sync::write(package_dir.join("clippier.toml"), clippier_toml).unwrap();
+    }
+
+    temp_dir
+}
+
+#[test]
+fn test_workspace_system_deps_unions_overlapping_apt_packages() {
+    let temp_dir = create_workspace();
+
+    let deps = collect_workspace_system_deps(temp_dir.path(), "ubuntu").unwrap();
+
+    assert_eq!(
+        deps.packages[&PackageManager::Apt]
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>(),
+        vec!["libasound2-dev", "libsqlite3-dev", "libssl-dev"]
+    );
+    assert_eq!(
+        deps.install_commands,
+        vec![
+            "sudo apt-get update && sudo apt-get install -y libasound2-dev libsqlite3-dev libssl-dev"
+        ]
+    );
+    assert_eq!(
+        deps.other_commands,
+        vec!["curl -o vectors.tar.gz https://example.com/vectors.tar.gz"]
+    );
+}
+
+#[test]
+fn test_workspace_system_deps_filters_by_os() {
+    let temp_dir = create_workspace();
+
+    let deps = collect_workspace_system_deps(temp_dir.path(), "macos").unwrap();
+
+    assert_eq!(
+        deps.install_commands,
+        vec!["brew install openssl pkg-config sqlite3"]
+    );
+    assert!(deps.other_commands.is_empty());
+
+    let deps = collect_workspace_system_deps(temp_dir.path(), "windows").unwrap();
+
+    assert!(deps.packages.is_empty());
+    assert!(deps.install_commands.is_empty());
+}
+
+#[test]
+fn test_workspace_system_deps_includes_root_clippier_toml() {
+    let temp_dir = create_workspace();
+    switchy_fs::sync::write(
+        temp_dir.path().join("clippier.toml"),
+        r#"
+dependencies = [{ command = "sudo apt-get install -y build-essential" }]
+
+[[config]]
+os = "ubuntu"
+dependencies = [{ command = "sudo apt-get install libasound2-dev" }]
+"#,
+    )
+    .unwrap();
+
+    let deps = collect_workspace_system_deps(temp_dir.path(), "ubuntu").unwrap();
+
+    assert_eq!(
+        deps.install_commands,
+        vec![
+            "sudo apt-get update && sudo apt-get install -y build-essential libasound2-dev libsqlite3-dev libssl-dev"
+        ]
+    );
+}
+
+#[test]
+fn test_workspace_system_deps_raw_output_is_a_script() {
+    let temp_dir = create_workspace();
+
+    let output =
+        handle_workspace_system_deps_command(temp_dir.path(), "ubuntu", OutputType::Raw).unwrap();
+
+    assert_eq!(
+        output,
+        "sudo apt-get update && sudo apt-get install -y libasound2-dev libsqlite3-dev libssl-dev\n\
+         curl -o vectors.tar.gz https://example.com/vectors.tar.gz\n"
+    );
+}
+
+#[test]
+fn test_workspace_system_deps_json_output() {
+    let temp_dir = create_workspace();
+
+    let output =
+        handle_workspace_system_deps_command(temp_dir.path(), "macos", OutputType::Json).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(
+        parsed["packages"]["brew"],
+        serde_json::json!(["openssl", "pkg-config", "sqlite3"])
+    );
+    assert_eq!(
+        parsed["install_commands"],
+        serde_json::json!(["brew install openssl pkg-config sqlite3"])
+    );
+}
+
+#[test]
+fn test_parse_install_command() {
+    assert_eq!(
+        parse_install_command(
+            "sudo apt-get update && DEBIAN_FRONTEND=noninteractive sudo apt-get install -y --no-install-recommends jackd2 libjack-jackd2-dev"
+        ),
+        Some(vec![
+            (PackageManager::Apt, "jackd2".to_string()),
+            (PackageManager::Apt, "libjack-jackd2-dev".to_string()),
+        ])
+    );
+    assert_eq!(
+        parse_install_command("vcpkg install openssl:x64-windows-static-md"),
+        Some(vec![(
+            PackageManager::Vcpkg,
+            "openssl:x64-windows-static-md".to_string()
+        )])
+    );
+    assert_eq!(parse_install_command("vcpkg integrate install"), None);
+    assert_eq!(
+        parse_install_command("sudo apt-get install libvips-dev && ./configure"),
+        None
+    );
+}
@@ -698,6 +698,7 @@ fn process_start_tag(ctx: &mut MarkdownContext, tag: Tag) -> Result<(), Markdown
                 source_set: None,
                 sizes: None,
                 loading: None,
+                decoding: None,
             },
             classes: vec!["markdown-image".to_string()],
             ..Default::default()
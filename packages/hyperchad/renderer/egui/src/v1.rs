@@ -27,8 +27,8 @@ use hyperchad_router::{ClientInfo, RequestInfo, Router};
 use hyperchad_transformer::{
     Container, Element, Input, ResponsiveTrigger, TableIter, float_eq,
     models::{
-        Cursor, LayoutDirection, LayoutOverflow, LayoutPosition, Position, Route, SwapStrategy,
-        TextOverflow, Visibility,
+        Cursor, LayoutDirection, LayoutOverflow, LayoutPosition, PointerEvents, Position, Route,
+        SwapStrategy, TextOverflow, Visibility,
     },
 };
 use itertools::Itertools;
@@ -2601,6 +2601,13 @@ impl<C: EguiCalc + Clone + Send + Sync + 'static> EguiApp<C> {
         responses: Vec<Response>,
     ) {
         let responses = Arc::new(responses);
+        // Elements with `pointer-events: none` are still painted, but are never the target of
+        // the pointer, so clicks and hovers fall through to the elements beneath them.
+        let hit_responses = if container.pointer_events == Some(PointerEvents::None) {
+            Arc::new(vec![])
+        } else {
+            responses.clone()
+        };
         let viewport_rect = rect.map(|rect| {
             let (offset_x, offset_y) =
                 viewport.map_or((0.0, 0.0), |viewport| (viewport.pos.x, viewport.pos.y));
@@ -2612,7 +2619,7 @@ impl<C: EguiCalc + Clone + Send + Sync + 'static> EguiApp<C> {
             profiling::scope!("cursor side effects");
             let ctx = ctx.clone();
             let pointer = ctx.input(|x| x.pointer.clone());
-            let responses = responses.clone();
+            let responses = hit_responses.clone();
             self.trigger_side_effect(move |_render_context| {
                 if responses
                     .iter()
@@ -2637,7 +2644,7 @@ impl<C: EguiCalc + Clone + Send + Sync + 'static> EguiApp<C> {
                         let action = fx_action.effect.clone();
                         let id = container.id;
                         let pointer = ctx.input(|x| x.pointer.clone());
-                        let responses = responses.clone();
+                        let responses = hit_responses.clone();
                         self.trigger_side_effect(move |render_context| {
                             if responses
                                 .iter()
@@ -2674,7 +2681,7 @@ impl<C: EguiCalc + Clone + Send + Sync + 'static> EguiApp<C> {
                         let action = fx_action.effect.clone();
                         let id = container.id;
                         let pointer = ctx.input(|x| x.pointer.clone());
-                        let responses = responses.clone();
+                        let responses = hit_responses.clone();
                         self.trigger_side_effect(move |render_context| {
                             if responses
                                 .iter()
@@ -2712,7 +2719,7 @@ impl<C: EguiCalc + Clone + Send + Sync + 'static> EguiApp<C> {
                         profiling::scope!("hover side effects");
                         let action = fx_action.effect.clone();
                         let id = container.id;
-                        let responses = responses.clone();
+                        let responses = hit_responses.clone();
                         let pointer = ctx.input(|x| x.pointer.clone());
                         self.trigger_side_effect(move |render_context| {
                             if responses
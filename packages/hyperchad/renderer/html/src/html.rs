@@ -6,16 +6,17 @@
 
 #![allow(clippy::module_name_repetitions)]
 
-use std::{collections::BTreeMap, io::Write};
+use std::{borrow::Cow, collections::BTreeMap, io::Write};
 
 use hyperchad_renderer::{Color, HtmlTagRenderer};
 use hyperchad_router::Container;
 use hyperchad_transformer::{
     Calculation, Element, HeaderSize, Input, Number,
     models::{
-        AlignItems, Cursor, ImageFit, ImageLoading, JustifyContent, LayoutDirection,
-        LayoutOverflow, LinkTarget, OverflowWrap, Position, TextAlign, TextDecorationLine,
-        TextDecorationStyle, TextOverflow, UserSelect, Visibility, WhiteSpace,
+        AlignItems, Cursor, Display, ImageDecoding, ImageFit, ImageLoading, JustifyContent,
+        LayoutDirection, LayoutOverflow, LinkTarget, OverflowWrap, PointerEvents, Position,
+        TextAlign, TextDecorationLine, TextDecorationStyle, TextDirection, TextOverflow,
+        UserSelect, Visibility, WhiteSpace,
     },
 };
 
@@ -41,7 +42,8 @@ pub fn elements_to_html(
 
 /// Writes an HTML attribute with name and value to the output.
 ///
-/// Formats as ` name="value"` with proper escaping.
+/// Formats as ` name="value"`. The value is written as is, so it must already be escaped
+/// with [`escape_attr`]; use [`write_escaped_attr`] for unescaped values.
 ///
 /// # Errors
 ///
@@ -55,6 +57,36 @@ pub fn write_attr(f: &mut dyn Write, attr: &[u8], value: &[u8]) -> Result<(), st
     Ok(())
 }
 
+/// Writes an HTML attribute to the output, escaping its value with [`escape_attr`].
+///
+/// # Errors
+///
+/// * If there was an IO error writing the attribute
+pub fn write_escaped_attr(
+    f: &mut dyn Write,
+    attr: &[u8],
+    value: &str,
+) -> Result<(), std::io::Error> {
+    write_attr(f, attr, escape_attr(value).as_bytes())
+}
+
+/// Escapes a string for use as element text content.
+///
+/// Escapes `&`, `<` and `>`. Quotes can't end text content, so they are left as is.
+#[must_use]
+pub fn escape_text(value: &str) -> Cow<'_, str> {
+    html_escape::encode_text(value)
+}
+
+/// Escapes a string for use inside a double-quoted attribute value.
+///
+/// Escapes `&`, `<`, `>` and `"`, so the value can neither end the attribute nor be
+/// mistaken for markup.
+#[must_use]
+pub fn escape_attr(value: &str) -> Cow<'_, str> {
+    html_escape::encode_double_quoted_attribute(value)
+}
+
 /// Writes a CSS property declaration to the output.
 ///
 /// Formats as `property:value;` for use within a style attribute.
@@ -186,6 +218,22 @@ const fn is_grid_container(container: &Container) -> bool {
     matches!(container.overflow_x, LayoutOverflow::Wrap { grid: true })
 }
 
+/// Returns the CSS property names to use for a container's left and right sides.
+///
+/// Containers with an explicit text direction emit the logical inline-start/inline-end
+/// properties so the browser resolves them against the emitted `direction`.
+const fn inline_side_names(
+    direction: Option<TextDirection>,
+    physical: (&'static [u8], &'static [u8]),
+    logical: (&'static [u8], &'static [u8]),
+) -> (&'static [u8], &'static [u8]) {
+    match direction {
+        None => physical,
+        Some(direction) if direction.is_start_left() => logical,
+        Some(_) => (logical.1, logical.0),
+    }
+}
+
 /// Writes the style attribute for a container element to the output.
 ///
 /// Converts container properties like dimensions, positioning, flexbox settings,
@@ -262,16 +310,18 @@ pub fn element_style_to_html(
     let is_grid = is_grid_container(container);
     let is_flex = !is_grid && container.is_flex_container();
 
-    if is_flex {
+    if let Some(display) = container.display.filter(|x| *x != Display::None) {
+        write_css_attr!(b"display", display.to_string().as_bytes());
+    } else if is_flex {
         write_css_attr!(b"display", b"flex");
-
-        if container.direction == LayoutDirection::Column {
-            write_css_attr!(b"flex-direction", b"column");
-        }
     } else if is_grid {
         write_css_attr!(b"display", b"grid");
     }
 
+    if is_flex && container.direction == LayoutDirection::Column {
+        write_css_attr!(b"flex-direction", b"column");
+    }
+
     match container.overflow_x {
         LayoutOverflow::Auto => {
             write_css_attr!(b"overflow-x", b"auto");
@@ -288,7 +338,7 @@ pub fn element_style_to_html(
                             .as_bytes()
                     );
                 }
-            } else {
+            } else if container.flex_wrap.is_none() {
                 write_css_attr!(b"flex-wrap", b"wrap");
             }
         }
@@ -313,7 +363,7 @@ pub fn element_style_to_html(
                             .as_bytes()
                     );
                 }
-            } else {
+            } else if container.flex_wrap.is_none() {
                 write_css_attr!(b"flex-wrap", b"wrap");
             }
         }
@@ -323,6 +373,10 @@ pub fn element_style_to_html(
         LayoutOverflow::Expand | LayoutOverflow::Squash => {}
     }
 
+    if let Some(flex_wrap) = container.flex_wrap {
+        write_css_attr!(b"flex-wrap", flex_wrap.to_string().as_bytes());
+    }
+
     if let Some(position) = container.position {
         match position {
             Position::Relative => {
@@ -355,15 +409,20 @@ pub fn element_style_to_html(
         }
     }
 
+    let (margin_left_name, margin_right_name) = inline_side_names(
+        container.text_direction,
+        (b"margin-left", b"margin-right"),
+        (b"margin-inline-start", b"margin-inline-end"),
+    );
     if let Some(margin_left) = &container.margin_left {
         write_css_attr!(
-            b"margin-left",
+            margin_left_name,
             number_to_html_string(margin_left, true).as_bytes(),
         );
     }
     if let Some(margin_right) = &container.margin_right {
         write_css_attr!(
-            b"margin-right",
+            margin_right_name,
             number_to_html_string(margin_right, true).as_bytes(),
         );
     }
@@ -380,15 +439,20 @@ pub fn element_style_to_html(
         );
     }
 
+    let (padding_left_name, padding_right_name) = inline_side_names(
+        container.text_direction,
+        (b"padding-left", b"padding-right"),
+        (b"padding-inline-start", b"padding-inline-end"),
+    );
     if let Some(padding_left) = &container.padding_left {
         write_css_attr!(
-            b"padding-left",
+            padding_left_name,
             number_to_html_string(padding_left, true).as_bytes(),
         );
     }
     if let Some(padding_right) = &container.padding_right {
         write_css_attr!(
-            b"padding-right",
+            padding_right_name,
             number_to_html_string(padding_right, true).as_bytes(),
         );
     }
@@ -405,11 +469,16 @@ pub fn element_style_to_html(
         );
     }
 
+    let (left_name, right_name) = inline_side_names(
+        container.text_direction,
+        (b"left", b"right"),
+        (b"inset-inline-start", b"inset-inline-end"),
+    );
     if let Some(left) = &container.left {
-        write_css_attr!(b"left", number_to_html_string(left, true).as_bytes());
+        write_css_attr!(left_name, number_to_html_string(left, true).as_bytes());
     }
     if let Some(right) = &container.right {
-        write_css_attr!(b"right", number_to_html_string(right, true).as_bytes());
+        write_css_attr!(right_name, number_to_html_string(right, true).as_bytes());
     }
     if let Some(top) = &container.top {
         write_css_attr!(b"top", number_to_html_string(top, true).as_bytes());
@@ -461,7 +530,7 @@ pub fn element_style_to_html(
         }
     }
 
-    if container.hidden == Some(true) {
+    if container.is_hidden() {
         write_css_attr!(b"display", b"none");
     }
 
@@ -557,6 +626,10 @@ pub fn element_style_to_html(
         );
     }
 
+    if let Some(order) = container.order {
+        write_css_attr!(b"order", order.to_string().as_bytes());
+    }
+
     if let Some(background) = container.background {
         write_css_attr!(b"background", color_to_css_string(background).as_bytes());
     }
@@ -660,6 +733,10 @@ pub fn element_style_to_html(
         );
     }
 
+    if let Some(text_direction) = container.text_direction {
+        write_css_attr!(b"direction", text_direction.to_string().as_bytes());
+    }
+
     if let Some(white_space) = &container.white_space {
         write_css_attr!(
             b"white-space",
@@ -718,8 +795,33 @@ pub fn element_style_to_html(
         }
     }
 
+    if let Some(text_shadow) = &container.text_shadow
+        && !text_shadow.is_empty()
+    {
+        write_css_attr!(
+            b"text-shadow",
+            text_shadow
+                .iter()
+                .map(|shadow| {
+                    format!(
+                        "{} {} {} {}",
+                        number_to_html_string(&shadow.x, true),
+                        number_to_html_string(&shadow.y, true),
+                        number_to_html_string(&shadow.blur, true),
+                        color_to_css_string(shadow.color),
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",")
+                .as_bytes()
+        );
+    }
+
     if let Some(font_family) = &container.font_family {
-        write_css_attr!(b"font-family", font_family.join(",").as_bytes());
+        write_css_attr!(
+            b"font-family",
+            escape_attr(&font_family.join(",")).as_bytes()
+        );
     }
 
     if let Some(font_weight) = &container.font_weight {
@@ -771,6 +873,39 @@ pub fn element_style_to_html(
         );
     }
 
+    if let Some(pointer_events) = &container.pointer_events {
+        write_css_attr!(
+            b"pointer-events",
+            match pointer_events {
+                PointerEvents::Auto => b"auto",
+                PointerEvents::None => b"none",
+            }
+        );
+    }
+
+    if let Some(transition) = &container.transition
+        && !transition.is_empty()
+    {
+        write_css_attr!(
+            b"transition",
+            transition
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+                .as_bytes()
+        );
+    }
+
+    if let Some(will_change) = &container.will_change
+        && !will_change.is_empty()
+    {
+        write_css_attr!(
+            b"will-change",
+            escape_attr(&will_change.join(", ")).as_bytes()
+        );
+    }
+
     if let Some(overflow_wrap) = &container.overflow_wrap {
         write_css_attr!(
             b"overflow-wrap",
@@ -799,6 +934,25 @@ pub fn element_style_to_html(
     Ok(())
 }
 
+/// Returns the class generated for a container with [`Container::breakpoints`], which
+/// its `@media` rules target.
+///
+/// The class is derived from the breakpoints themselves, so containers with the same
+/// breakpoints share a class and rendering the same tree always produces the same classes.
+#[must_use]
+pub fn breakpoint_class_name(container: &Container) -> Option<String> {
+    use std::hash::{DefaultHasher, Hash as _, Hasher as _};
+
+    if container.breakpoints.is_empty() {
+        return None;
+    }
+
+    let mut hasher = DefaultHasher::new();
+    format!("{:?}", container.breakpoints).hash(&mut hasher);
+
+    Some(format!("hc-bp-{:x}", hasher.finish()))
+}
+
 /// Writes the class attribute for a container element to the output.
 ///
 /// Generates HTML class attribute including default classes for specific elements
@@ -842,11 +996,23 @@ pub fn element_classes_to_html(
         }
 
         for class in &container.classes {
-            f.write_all(class.as_bytes())?;
+            f.write_all(escape_attr(class).as_bytes())?;
             f.write_all(b" ")?;
         }
     }
 
+    if let Some(class) = breakpoint_class_name(container) {
+        if !printed_start {
+            printed_start = true;
+            f.write_all(b" class=\"")?;
+        } else if container.classes.is_empty() {
+            // Custom classes are each followed by a space already
+            f.write_all(b" ")?;
+        }
+
+        f.write_all(class.as_bytes())?;
+    }
+
     if printed_start {
         f.write_all(b"\"")?;
     }
@@ -873,9 +1039,7 @@ fn render_option_child(
         f.write_all(b"<option")?;
 
         if let Some(value) = value {
-            f.write_all(b" value=\"")?;
-            f.write_all(value.as_bytes())?;
-            f.write_all(b"\"")?;
+            write_escaped_attr(f, b"value", value)?;
 
             // Mark as selected if this option's value matches the select's selected value
             if selected_value == Some(value.as_str()) {
@@ -941,7 +1105,7 @@ pub fn element_to_html(
             return Ok(());
         }
         Element::Text { value } => {
-            f.write_all(html_escape::encode_text(value).as_bytes())?;
+            f.write_all(escape_text(value).as_bytes())?;
             return Ok(());
         }
         Element::Image {
@@ -950,20 +1114,17 @@ pub fn element_to_html(
             source_set,
             sizes,
             loading,
+            decoding,
             ..
         } => {
             const TAG_NAME: &[u8] = b"img";
             f.write_all(b"<")?;
             f.write_all(TAG_NAME)?;
             if let Some(source) = source {
-                f.write_all(b" src=\"")?;
-                f.write_all(source.as_bytes())?;
-                f.write_all(b"\"")?;
+                write_escaped_attr(f, b"src", source)?;
             }
             if let Some(srcset) = source_set {
-                f.write_all(b" srcset=\"")?;
-                f.write_all(srcset.as_bytes())?;
-                f.write_all(b"\"")?;
+                write_escaped_attr(f, b"srcset", srcset)?;
             }
             if let Some(sizes) = sizes {
                 f.write_all(b" sizes=\"")?;
@@ -971,9 +1132,7 @@ pub fn element_to_html(
                 f.write_all(b"\"")?;
             }
             if let Some(alt) = alt {
-                f.write_all(b" alt=\"")?;
-                f.write_all(alt.as_bytes())?;
-                f.write_all(b"\"")?;
+                write_escaped_attr(f, b"alt", alt)?;
             }
             if let Some(loading) = loading {
                 f.write_all(b" loading=\"")?;
@@ -983,6 +1142,15 @@ pub fn element_to_html(
                 })?;
                 f.write_all(b"\"")?;
             }
+            if let Some(decoding) = decoding {
+                f.write_all(b" decoding=\"")?;
+                f.write_all(match decoding {
+                    ImageDecoding::Auto => b"auto",
+                    ImageDecoding::Sync => b"sync",
+                    ImageDecoding::Async => b"async",
+                })?;
+                f.write_all(b"\"")?;
+            }
             tag_renderer.element_attrs_to_html(f, container, is_flex_child)?;
             f.write_all(b">")?;
             elements_to_html(
@@ -1001,9 +1169,7 @@ pub fn element_to_html(
             f.write_all(b"<")?;
             f.write_all(TAG_NAME)?;
             if let Some(href) = href {
-                f.write_all(b" href=\"")?;
-                f.write_all(href.as_bytes())?;
-                f.write_all(b"\"")?;
+                write_escaped_attr(f, b"href", href)?;
             }
             if let Some(target) = target {
                 f.write_all(b" target=\"")?;
@@ -1058,14 +1224,10 @@ pub fn element_to_html(
             f.write_all(b"<")?;
             f.write_all(TAG_NAME)?;
             if let Some(action) = action {
-                f.write_all(b" action=\"")?;
-                f.write_all(action.as_bytes())?;
-                f.write_all(b"\"")?;
+                write_escaped_attr(f, b"action", action)?;
             }
             if let Some(method) = method {
-                f.write_all(b" method=\"")?;
-                f.write_all(method.as_bytes())?;
-                f.write_all(b"\"")?;
+                write_escaped_attr(f, b"method", method)?;
             }
             tag_renderer.element_attrs_to_html(f, container, is_flex_child)?;
             f.write_all(b">")?;
@@ -1098,43 +1260,31 @@ pub fn element_to_html(
                 Input::Text { value, placeholder } => {
                     f.write_all(b" type=\"text\"")?;
                     if let Some(value) = value {
-                        f.write_all(b" value=\"")?;
-                        f.write_all(value.as_bytes())?;
-                        f.write_all(b"\"")?;
+                        write_escaped_attr(f, b"value", value)?;
                     }
                     if let Some(placeholder) = placeholder {
-                        f.write_all(b" placeholder=\"")?;
-                        f.write_all(placeholder.as_bytes())?;
-                        f.write_all(b"\"")?;
+                        write_escaped_attr(f, b"placeholder", placeholder)?;
                     }
                 }
                 Input::Password { value, placeholder } => {
                     f.write_all(b" type=\"password\"")?;
                     if let Some(value) = value {
-                        f.write_all(b" value=\"")?;
-                        f.write_all(value.as_bytes())?;
-                        f.write_all(b"\"")?;
+                        write_escaped_attr(f, b"value", value)?;
                     }
                     if let Some(placeholder) = placeholder {
-                        f.write_all(b" placeholder=\"")?;
-                        f.write_all(placeholder.as_bytes())?;
-                        f.write_all(b"\"")?;
+                        write_escaped_attr(f, b"placeholder", placeholder)?;
                     }
                 }
                 Input::Hidden { value } => {
                     f.write_all(b" type=\"hidden\"")?;
                     if let Some(value) = value {
-                        f.write_all(b" value=\"")?;
-                        f.write_all(value.as_bytes())?;
-                        f.write_all(b"\"")?;
+                        write_escaped_attr(f, b"value", value)?;
                     }
                 }
             }
 
             if let Some(name) = name {
-                f.write_all(b" name=\"")?;
-                f.write_all(name.as_bytes())?;
-                f.write_all(b"\"")?;
+                write_escaped_attr(f, b"name", name)?;
             }
 
             if matches!(autofocus, Some(true)) {
@@ -1159,14 +1309,10 @@ pub fn element_to_html(
             f.write_all(TAG_NAME)?;
 
             if let Some(name) = name {
-                f.write_all(b" name=\"")?;
-                f.write_all(name.as_bytes())?;
-                f.write_all(b"\"")?;
+                write_escaped_attr(f, b"name", name)?;
             }
             if let Some(placeholder) = placeholder {
-                f.write_all(b" placeholder=\"")?;
-                f.write_all(placeholder.as_bytes())?;
-                f.write_all(b"\"")?;
+                write_escaped_attr(f, b"placeholder", placeholder)?;
             }
             if let Some(rows) = rows {
                 f.write_all(b" rows=\"")?;
@@ -1181,7 +1327,7 @@ pub fn element_to_html(
 
             tag_renderer.element_attrs_to_html(f, container, is_flex_child)?;
             f.write_all(b">")?;
-            f.write_all(value.as_bytes())?;
+            f.write_all(escape_text(value).as_bytes())?;
             elements_to_html(
                 f,
                 &container.children,
@@ -1199,9 +1345,7 @@ pub fn element_to_html(
             f.write_all(b"<")?;
             f.write_all(TAG_NAME)?;
 
-            f.write_all(b" type=\"")?;
-            f.write_all(r#type.as_deref().map_or(b"button", str::as_bytes))?;
-            f.write_all(b"\"")?;
+            write_escaped_attr(f, b"type", r#type.as_deref().unwrap_or("button"))?;
 
             tag_renderer.element_attrs_to_html(f, container, is_flex_child)?;
             f.write_all(b">")?;
@@ -1316,14 +1460,10 @@ pub fn element_to_html(
             f.write_all(TAG_NAME)?;
 
             if let Some(name) = name {
-                f.write_all(b" name=\"")?;
-                f.write_all(name.as_bytes())?;
-                f.write_all(b"\"")?;
+                write_escaped_attr(f, b"name", name)?;
             }
             if let Some(selected) = selected {
-                f.write_all(b" data-selected=\"")?;
-                f.write_all(selected.as_bytes())?;
-                f.write_all(b"\"")?;
+                write_escaped_attr(f, b"data-selected", selected)?;
             }
             if *multiple == Some(true) {
                 f.write_all(b" multiple")?;
@@ -1354,9 +1494,7 @@ pub fn element_to_html(
             f.write_all(TAG_NAME)?;
 
             if let Some(value) = value {
-                f.write_all(b" value=\"")?;
-                f.write_all(value.as_bytes())?;
-                f.write_all(b"\"")?;
+                write_escaped_attr(f, b"value", value)?;
             }
             if *disabled == Some(true) {
                 f.write_all(b" disabled")?;
@@ -1864,6 +2002,10 @@ mod tests {
             override_item_to_css_name(&OverrideItem::TextAlign(TextAlign::Center)),
             b"text-align"
         );
+        assert_eq!(
+            override_item_to_css_name(&OverrideItem::TextDirection(TextDirection::Rtl)),
+            b"direction"
+        );
         assert_eq!(
             override_item_to_css_name(&OverrideItem::WhiteSpace(WhiteSpace::Normal)),
             b"white-space"
@@ -1959,6 +2101,7 @@ mod tests {
                 source_set: None,
                 sizes: None,
                 loading: None,
+                decoding: None,
             },
             ..Default::default()
         };
@@ -2310,6 +2453,30 @@ mod tests {
         assert!(style.contains("display:none"));
     }
 
+    #[test_log::test]
+    fn test_element_style_to_html_display() {
+        for (display, expected_css) in [
+            (Display::None, "display:none;"),
+            (Display::Block, "display:block;"),
+            (Display::InlineBlock, "display:inline-block;"),
+            (Display::Grid, "display:grid;"),
+        ] {
+            let container = Container {
+                display: Some(display),
+                ..Default::default()
+            };
+
+            let mut buffer = Vec::new();
+            element_style_to_html(&mut buffer, &container, false).unwrap();
+            let style = std::str::from_utf8(&buffer).unwrap();
+
+            assert!(
+                style.contains(expected_css),
+                "Expected style to contain '{expected_css}', got: {style}"
+            );
+        }
+    }
+
     #[test_log::test]
     fn test_element_style_to_html_overflow() {
         let container = Container {
@@ -2604,6 +2771,147 @@ mod tests {
         assert!(html.contains("data-value=\"hello\""));
     }
 
+    const HOSTILE: &str = r#"<script>alert("Tom & Jerry's")</script>"#;
+
+    fn render(container: &Container) -> String {
+        let mut buffer = Vec::new();
+        element_to_html(
+            &mut buffer,
+            container,
+            &DefaultHtmlTagRenderer::default(),
+            false,
+        )
+        .unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test_log::test]
+    fn test_escape_text_and_attr_policies() {
+        assert_eq!(
+            escape_text(HOSTILE),
+            r#"&lt;script&gt;alert("Tom &amp; Jerry's")&lt;/script&gt;"#
+        );
+        assert_eq!(
+            escape_attr(HOSTILE),
+            "&lt;script&gt;alert(&quot;Tom &amp; Jerry's&quot;)&lt;/script&gt;"
+        );
+    }
+
+    #[test_log::test]
+    fn test_element_to_html_escapes_text_content() {
+        let html = render(&Container {
+            element: hyperchad_transformer::Element::Span,
+            children: vec![Container {
+                element: hyperchad_transformer::Element::Text {
+                    value: HOSTILE.to_string(),
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        assert_eq!(
+            html,
+            r#"<span>&lt;script&gt;alert("Tom &amp; Jerry's")&lt;/script&gt;</span>"#
+        );
+    }
+
+    #[test_log::test]
+    fn test_element_to_html_escapes_attribute_values() {
+        let escaped = escape_attr(HOSTILE);
+
+        let html = render(&Container {
+            element: hyperchad_transformer::Element::Image {
+                source: Some(HOSTILE.to_string()),
+                alt: Some(HOSTILE.to_string()),
+                fit: None,
+                source_set: Some(HOSTILE.to_string()),
+                sizes: None,
+                loading: None,
+                decoding: None,
+            },
+            str_id: Some(HOSTILE.to_string()),
+            classes: vec![HOSTILE.to_string()],
+            font_family: Some(vec![HOSTILE.to_string()]),
+            will_change: Some(vec![HOSTILE.to_string()]),
+            ..Default::default()
+        });
+
+        assert!(!html.contains("<script"), "{html}");
+        assert!(html.contains(&format!("src=\"{escaped}\"")), "{html}");
+        assert!(html.contains(&format!("srcset=\"{escaped}\"")), "{html}");
+        assert!(html.contains(&format!("alt=\"{escaped}\"")), "{html}");
+        assert!(html.contains(&format!("id=\"{escaped}\"")), "{html}");
+        assert!(html.contains(&format!("class=\"{escaped} \"")), "{html}");
+        assert!(html.contains(&format!("font-family:{escaped};")), "{html}");
+        assert!(html.contains(&format!("will-change:{escaped};")), "{html}");
+    }
+
+    #[test_log::test]
+    fn test_element_to_html_escapes_form_control_attributes() {
+        let escaped = escape_attr(HOSTILE);
+
+        let anchor = render(&Container {
+            element: hyperchad_transformer::Element::Anchor {
+                href: Some(HOSTILE.to_string()),
+                target: None,
+            },
+            ..Default::default()
+        });
+        assert!(anchor.contains(&format!("href=\"{escaped}\"")), "{anchor}");
+
+        let form = render(&Container {
+            element: hyperchad_transformer::Element::Form {
+                action: Some(HOSTILE.to_string()),
+                method: Some(HOSTILE.to_string()),
+            },
+            ..Default::default()
+        });
+        assert!(form.contains(&format!("action=\"{escaped}\"")), "{form}");
+        assert!(form.contains(&format!("method=\"{escaped}\"")), "{form}");
+
+        let input = render(&Container {
+            element: hyperchad_transformer::Element::Input {
+                input: Input::Text {
+                    value: Some(HOSTILE.to_string()),
+                    placeholder: Some(HOSTILE.to_string()),
+                },
+                name: Some(HOSTILE.to_string()),
+                autofocus: None,
+            },
+            ..Default::default()
+        });
+        assert!(input.contains(&format!("value=\"{escaped}\"")), "{input}");
+        assert!(
+            input.contains(&format!("placeholder=\"{escaped}\"")),
+            "{input}"
+        );
+        assert!(input.contains(&format!("name=\"{escaped}\"")), "{input}");
+
+        let textarea = render(&Container {
+            element: hyperchad_transformer::Element::Textarea {
+                value: HOSTILE.to_string(),
+                placeholder: Some(HOSTILE.to_string()),
+                name: None,
+                rows: None,
+                cols: None,
+            },
+            ..Default::default()
+        });
+        assert!(
+            textarea.contains(&format!("placeholder=\"{escaped}\"")),
+            "{textarea}"
+        );
+        assert!(
+            textarea.contains(&format!(">{}</textarea>", escape_text(HOSTILE))),
+            "{textarea}"
+        );
+
+        for html in [anchor, form, input, textarea] {
+            assert!(!html.contains("<script"), "{html}");
+        }
+    }
+
     // Test for image with srcset and loading
     #[test_log::test]
     fn test_element_to_html_image_with_srcset_and_loading() {
@@ -2616,6 +2924,7 @@ mod tests {
                 source_set: Some("/img-small.jpg 300w, /img-large.jpg 600w".to_string()),
                 sizes: Some(Number::Integer(300)),
                 loading: Some(ImageLoading::Lazy),
+                decoding: None,
             },
             ..Default::default()
         };
@@ -2629,6 +2938,65 @@ mod tests {
         assert!(html.contains("loading=\"lazy\""));
     }
 
+    #[test_log::test]
+    fn test_element_to_html_image_with_responsive_attributes() {
+        let tag_renderer = DefaultHtmlTagRenderer::default();
+        let container = Container {
+            element: hyperchad_transformer::Element::Image {
+                source: Some("/albums/1/300".to_string()),
+                alt: Some("Album art".to_string()),
+                fit: Some(ImageFit::Cover),
+                source_set: Some("/albums/1/300 300w, /albums/1/600 600w".to_string()),
+                sizes: Some(Number::Integer(300)),
+                loading: Some(ImageLoading::Lazy),
+                decoding: Some(ImageDecoding::Async),
+            },
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        element_to_html(&mut buffer, &container, &tag_renderer, false).unwrap();
+        let html = std::str::from_utf8(&buffer).unwrap();
+
+        assert!(
+            html.starts_with(
+                "<img src=\"/albums/1/300\" srcset=\"/albums/1/300 300w, /albums/1/600 600w\" \
+                 sizes=\"300px\" alt=\"Album art\" loading=\"lazy\" decoding=\"async\""
+            ),
+            "{html}"
+        );
+    }
+
+    #[test_log::test]
+    fn test_element_to_html_image_decoding_values() {
+        let tag_renderer = DefaultHtmlTagRenderer::default();
+
+        for (decoding, expected) in [
+            (ImageDecoding::Auto, "decoding=\"auto\""),
+            (ImageDecoding::Sync, "decoding=\"sync\""),
+            (ImageDecoding::Async, "decoding=\"async\""),
+        ] {
+            let container = Container {
+                element: hyperchad_transformer::Element::Image {
+                    source: Some("/img.jpg".to_string()),
+                    alt: None,
+                    fit: None,
+                    source_set: None,
+                    sizes: None,
+                    loading: None,
+                    decoding: Some(decoding),
+                },
+                ..Default::default()
+            };
+
+            let mut buffer = Vec::new();
+            element_to_html(&mut buffer, &container, &tag_renderer, false).unwrap();
+            let html = std::str::from_utf8(&buffer).unwrap();
+
+            assert!(html.contains(expected), "{html}");
+        }
+    }
+
     // Test for input types
     #[test_log::test]
     fn test_element_to_html_input_password() {
@@ -2844,6 +3212,7 @@ mod tests {
                 source_set: None,
                 sizes: None,
                 loading: Some(ImageLoading::Eager),
+                decoding: None,
             },
             ..Default::default()
         };
@@ -2873,6 +3242,7 @@ mod tests {
                     source_set: None,
                     sizes: None,
                     loading: None,
+                    decoding: None,
                 },
                 ..Default::default()
             };
@@ -2888,6 +3258,174 @@ mod tests {
         }
     }
 
+    // Test element_style_to_html with pointer_events
+    #[test_log::test]
+    fn test_element_style_to_html_pointer_events() {
+        for (pointer_events, expected_css) in [
+            (PointerEvents::Auto, "pointer-events:auto"),
+            (PointerEvents::None, "pointer-events:none"),
+        ] {
+            let container = Container {
+                pointer_events: Some(pointer_events),
+                ..Default::default()
+            };
+
+            let mut buffer = Vec::new();
+            element_style_to_html(&mut buffer, &container, false).unwrap();
+            let style = std::str::from_utf8(&buffer).unwrap();
+
+            assert!(
+                style.contains(expected_css),
+                "Expected '{expected_css}', got: {style}"
+            );
+        }
+    }
+
+    // Test element_style_to_html with multiple transitions
+    #[test_log::test]
+    fn test_element_style_to_html_transition_multiple_properties() {
+        let container = Container {
+            transition: Some(vec![
+                hyperchad_transformer::Transition {
+                    property: "opacity".to_string(),
+                    duration: Number::Integer(300),
+                    timing: hyperchad_transformer::TimingFunction::EaseInOut,
+                    delay: Number::Integer(0),
+                },
+                hyperchad_transformer::Transition {
+                    property: "background".to_string(),
+                    duration: Number::Integer(150),
+                    timing: hyperchad_transformer::TimingFunction::Linear,
+                    delay: Number::Integer(50),
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        element_style_to_html(&mut buffer, &container, false).unwrap();
+        let style = std::str::from_utf8(&buffer).unwrap();
+
+        assert!(
+            style.contains("transition:opacity 300ms ease-in-out 0ms,background 150ms linear 50ms"),
+            "Expected comma-joined transitions, got: {style}"
+        );
+    }
+
+    // Test element_style_to_html with a cubic-bezier timing function
+    #[test_log::test]
+    fn test_element_style_to_html_transition_cubic_bezier() {
+        let container = Container {
+            transition: Some(vec![hyperchad_transformer::Transition {
+                property: "transform".to_string(),
+                duration: Number::Integer(200),
+                timing: hyperchad_transformer::TimingFunction::CubicBezier(0.25, 0.1, 0.25, 1.0),
+                delay: Number::Integer(0),
+            }]),
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        element_style_to_html(&mut buffer, &container, false).unwrap();
+        let style = std::str::from_utf8(&buffer).unwrap();
+
+        assert!(
+            style.contains("transition:transform 200ms cubic-bezier(0.25,0.1,0.25,1) 0ms"),
+            "Expected cubic-bezier timing function, got: {style}"
+        );
+    }
+
+    // Test element_style_to_html with a single text shadow
+    #[test_log::test]
+    fn test_element_style_to_html_text_shadow() {
+        let container = Container {
+            text_shadow: Some(vec![hyperchad_transformer::TextShadow {
+                x: Number::Integer(1),
+                y: Number::Integer(2),
+                blur: Number::Integer(4),
+                color: Color::from_hex("#000000"),
+            }]),
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        element_style_to_html(&mut buffer, &container, false).unwrap();
+        let style = std::str::from_utf8(&buffer).unwrap();
+
+        assert!(
+            style.contains("text-shadow:1px 2px 4px rgb(0,0,0);"),
+            "Expected text-shadow, got: {style}"
+        );
+    }
+
+    // Test element_style_to_html with multiple text shadows, rendered in order
+    #[test_log::test]
+    fn test_element_style_to_html_text_shadow_multiple_shadows() {
+        let container = Container {
+            text_shadow: Some(vec![
+                hyperchad_transformer::TextShadow {
+                    x: Number::Integer(0),
+                    y: Number::Integer(1),
+                    blur: Number::Integer(2),
+                    color: Color::from_hex("#000000"),
+                },
+                hyperchad_transformer::TextShadow {
+                    x: Number::Integer(-1),
+                    y: Number::Integer(0),
+                    blur: Number::Real(0.5),
+                    color: Color::from_hex("#FF000080"),
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        element_style_to_html(&mut buffer, &container, false).unwrap();
+        let style = std::str::from_utf8(&buffer).unwrap();
+
+        assert!(
+            style.contains(
+                "text-shadow:0px 1px 2px rgb(0,0,0),-1px 0px 0.5px rgba(255,0,0,0.5019607843137255);"
+            ),
+            "Expected comma-joined text shadows, got: {style}"
+        );
+    }
+
+    #[test_log::test]
+    fn test_element_style_to_html_order() {
+        let container = Container {
+            order: Some(-1),
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        element_style_to_html(&mut buffer, &container, false).unwrap();
+        let style = std::str::from_utf8(&buffer).unwrap();
+
+        assert!(
+            style.contains("order:-1;"),
+            "Expected style to contain 'order:-1;', got: {style}"
+        );
+    }
+
+    // Test element_style_to_html with multiple will-change properties
+    #[test_log::test]
+    fn test_element_style_to_html_will_change_multiple_properties() {
+        let container = Container {
+            will_change: Some(vec!["transform".to_string(), "opacity".to_string()]),
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        element_style_to_html(&mut buffer, &container, false).unwrap();
+        let style = std::str::from_utf8(&buffer).unwrap();
+
+        assert!(
+            style.contains("will-change:transform, opacity"),
+            "Expected comma-joined will-change properties, got: {style}"
+        );
+    }
+
     // Test element_style_to_html with user_select
     #[test_log::test]
     fn test_element_style_to_html_user_select() {
@@ -3089,6 +3627,99 @@ mod tests {
         assert!(style.contains("flex-wrap:wrap"));
     }
 
+    // Test element_style_to_html with all flex_wrap values
+    #[test_log::test]
+    fn test_element_style_to_html_flex_wrap_property() {
+        for (flex_wrap, expected_css) in [
+            (FlexWrap::NoWrap, "flex-wrap:nowrap"),
+            (FlexWrap::Wrap, "flex-wrap:wrap"),
+            (FlexWrap::WrapReverse, "flex-wrap:wrap-reverse"),
+        ] {
+            let container = Container {
+                direction: LayoutDirection::Row,
+                flex_wrap: Some(flex_wrap),
+                ..Default::default()
+            };
+
+            let mut buffer = Vec::new();
+            element_style_to_html(&mut buffer, &container, false).unwrap();
+            let style = std::str::from_utf8(&buffer).unwrap();
+
+            assert!(style.contains("display:flex"), "got: {style}");
+            assert!(
+                style.contains(expected_css),
+                "Expected '{expected_css}', got: {style}"
+            );
+            assert_eq!(style.matches("flex-wrap").count(), 1, "got: {style}");
+        }
+    }
+
+    #[test_log::test]
+    fn test_element_style_to_html_without_text_direction_uses_physical_sides() {
+        let container = Container {
+            margin_left: Some(Number::Integer(1)),
+            padding_right: Some(Number::Integer(2)),
+            left: Some(Number::Integer(3)),
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        element_style_to_html(&mut buffer, &container, false).unwrap();
+        let style = std::str::from_utf8(&buffer).unwrap();
+
+        assert!(style.contains("margin-left:1px"), "got: {style}");
+        assert!(style.contains("padding-right:2px"), "got: {style}");
+        assert!(style.contains("left:3px"), "got: {style}");
+        assert!(!style.contains("-inline-"), "got: {style}");
+    }
+
+    #[test_log::test]
+    fn test_element_style_to_html_text_direction_emits_logical_sides() {
+        for (text_direction, expected_css) in [
+            (
+                TextDirection::Ltr,
+                [
+                    "direction:ltr",
+                    "margin-inline-start:1px",
+                    "padding-inline-end:2px",
+                    "inset-inline-start:3px",
+                ],
+            ),
+            (
+                TextDirection::Rtl,
+                [
+                    "direction:rtl",
+                    "margin-inline-end:1px",
+                    "padding-inline-start:2px",
+                    "inset-inline-end:3px",
+                ],
+            ),
+        ] {
+            let container = Container {
+                text_direction: Some(text_direction),
+                margin_left: Some(Number::Integer(1)),
+                margin_top: Some(Number::Integer(4)),
+                padding_right: Some(Number::Integer(2)),
+                left: Some(Number::Integer(3)),
+                ..Default::default()
+            };
+
+            let mut buffer = Vec::new();
+            element_style_to_html(&mut buffer, &container, false).unwrap();
+            let style = std::str::from_utf8(&buffer).unwrap();
+
+            for expected in expected_css {
+                assert!(
+                    style.contains(expected),
+                    "Expected '{expected}', got: {style}"
+                );
+            }
+            assert!(style.contains("margin-top:4px"), "got: {style}");
+            assert!(!style.contains("margin-left"), "got: {style}");
+            assert!(!style.contains("padding-right"), "got: {style}");
+        }
+    }
+
     // Test element_style_to_html with justify_content all values
     #[test_log::test]
     fn test_element_style_to_html_justify_content_all() {
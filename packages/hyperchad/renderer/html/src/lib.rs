@@ -41,8 +41,8 @@ use std::{collections::BTreeMap, io::Write};
 use async_trait::async_trait;
 use flume::Receiver;
 use html::{
-    color_to_css_string, element_classes_to_html, element_style_to_html, number_to_html_string,
-    write_css_attr_important,
+    breakpoint_class_name, color_to_css_string, element_classes_to_html, element_style_to_html,
+    number_to_html_string, write_css_attr_important, write_escaped_attr,
 };
 use hyperchad_renderer::{
     Color, Handle, HtmlTagRenderer, RenderRunner, Renderer, ToRenderRunner, View,
@@ -52,8 +52,8 @@ use hyperchad_router::Container;
 use hyperchad_transformer::{
     OverrideCondition, OverrideItem, ResponsiveTrigger,
     models::{
-        AlignItems, LayoutDirection, OverflowWrap, TextAlign, TextOverflow, UserSelect, Visibility,
-        WhiteSpace,
+        AlignItems, LayoutDirection, OverflowWrap, PointerEvents, TextAlign, TextOverflow,
+        UserSelect, Visibility, WhiteSpace,
     },
 };
 use maud::{DOCTYPE, PreEscaped, html};
@@ -128,9 +128,7 @@ impl HtmlTagRenderer for DefaultHtmlTagRenderer {
         is_flex_child: bool,
     ) -> Result<(), std::io::Error> {
         if let Some(id) = &container.str_id {
-            f.write_all(b" id=\"")?;
-            f.write_all(id.as_bytes())?;
-            f.write_all(b"\"")?;
+            write_escaped_attr(f, b"id", id)?;
         }
 
         element_style_to_html(f, container, is_flex_child)?;
@@ -150,12 +148,13 @@ impl HtmlTagRenderer for DefaultHtmlTagRenderer {
     /// Writes CSS media queries for responsive conditions to the output.
     ///
     /// Generates CSS `@media` rules based on responsive triggers configured in the
-    /// renderer, applying responsive overrides to container styles.
+    /// renderer, applying responsive overrides to container styles. Container
+    /// breakpoints are compiled into `@media(min-width:...)` rules of the class
+    /// generated for the container (see [`html::breakpoint_class_name`]).
     ///
     /// # Errors
     ///
     /// * If the `HtmlTagRenderer` fails to write the css media-queries
-    #[allow(clippy::too_many_lines)]
     fn reactive_conditions_to_css(
         &self,
         f: &mut dyn Write,
@@ -193,314 +192,28 @@ impl HtmlTagRenderer for DefaultHtmlTagRenderer {
             f.write_all(id.as_bytes())?;
             f.write_all(b"{")?;
 
-            for o in &config.overrides {
-                match o {
-                    OverrideItem::Direction(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            match x {
-                                LayoutDirection::Row => b"row",
-                                LayoutDirection::Column => b"column",
-                            },
-                        )?;
-                    }
-                    OverrideItem::Visibility(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            match x {
-                                Visibility::Visible => b"visible",
-                                Visibility::Hidden => b"hidden",
-                            },
-                        )?;
-                    }
-                    OverrideItem::UserSelect(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            match x {
-                                UserSelect::Auto => b"auto",
-                                UserSelect::None => b"none",
-                                UserSelect::Text => b"text",
-                                UserSelect::All => b"all",
-                            },
-                        )?;
-                    }
-                    OverrideItem::OverflowWrap(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            match x {
-                                OverflowWrap::Normal => b"normal",
-                                OverflowWrap::BreakWord => b"break-word",
-                                OverflowWrap::Anywhere => b"anywhere",
-                            },
-                        )?;
-                    }
-                    OverrideItem::TextOverflow(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            match x {
-                                TextOverflow::Clip => b"clip",
-                                TextOverflow::Ellipsis => b"ellipsis",
-                            },
-                        )?;
-                    }
-                    OverrideItem::Hidden(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            if *x { b"none" } else { b"initial" },
-                        )?;
-                    }
-                    OverrideItem::AlignItems(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            match x {
-                                AlignItems::Start => b"start",
-                                AlignItems::Center => b"center",
-                                AlignItems::End => b"end",
-                            },
-                        )?;
-                    }
-                    OverrideItem::TextAlign(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            match x {
-                                TextAlign::Start => b"start",
-                                TextAlign::Center => b"center",
-                                TextAlign::End => b"end",
-                                TextAlign::Justify => b"justify",
-                            },
-                        )?;
-                    }
-                    OverrideItem::WhiteSpace(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            match x {
-                                WhiteSpace::Normal => b"normal",
-                                WhiteSpace::Preserve => b"pre",
-                                WhiteSpace::PreserveWrap => b"pre-wrap",
-                            },
-                        )?;
-                    }
-                    OverrideItem::MarginLeft(x)
-                    | OverrideItem::MarginRight(x)
-                    | OverrideItem::MarginTop(x)
-                    | OverrideItem::MarginBottom(x)
-                    | OverrideItem::Width(x)
-                    | OverrideItem::MinWidth(x)
-                    | OverrideItem::MaxWidth(x)
-                    | OverrideItem::Height(x)
-                    | OverrideItem::MinHeight(x)
-                    | OverrideItem::MaxHeight(x)
-                    | OverrideItem::Left(x)
-                    | OverrideItem::Right(x)
-                    | OverrideItem::Top(x)
-                    | OverrideItem::Bottom(x)
-                    | OverrideItem::ColumnGap(x)
-                    | OverrideItem::RowGap(x)
-                    | OverrideItem::BorderTopLeftRadius(x)
-                    | OverrideItem::BorderTopRightRadius(x)
-                    | OverrideItem::BorderBottomLeftRadius(x)
-                    | OverrideItem::BorderBottomRightRadius(x)
-                    | OverrideItem::PaddingLeft(x)
-                    | OverrideItem::PaddingRight(x)
-                    | OverrideItem::PaddingTop(x)
-                    | OverrideItem::PaddingBottom(x)
-                    | OverrideItem::Opacity(x)
-                    | OverrideItem::TranslateX(x)
-                    | OverrideItem::TranslateY(x)
-                    | OverrideItem::FontSize(x)
-                    | OverrideItem::GridCellSize(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            number_to_html_string(x, true).as_bytes(),
-                        )?;
-                    }
-                    OverrideItem::JustifyContent(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            match x {
-                                hyperchad_transformer::models::JustifyContent::Start => b"start",
-                                hyperchad_transformer::models::JustifyContent::Center => b"center",
-                                hyperchad_transformer::models::JustifyContent::End => b"end",
-                                hyperchad_transformer::models::JustifyContent::SpaceBetween => {
-                                    b"space-between"
-                                }
-                                hyperchad_transformer::models::JustifyContent::SpaceEvenly => {
-                                    b"space-evenly"
-                                }
-                            },
-                        )?;
-                    }
-                    OverrideItem::OverflowX(x) | OverrideItem::OverflowY(x) => {
-                        let value = match x {
-                            hyperchad_transformer::models::LayoutOverflow::Auto => {
-                                Some(b"auto".as_slice())
-                            }
-                            hyperchad_transformer::models::LayoutOverflow::Scroll => {
-                                Some(b"scroll".as_slice())
-                            }
-                            hyperchad_transformer::models::LayoutOverflow::Hidden => {
-                                Some(b"hidden".as_slice())
-                            }
-                            hyperchad_transformer::models::LayoutOverflow::Expand
-                            | hyperchad_transformer::models::LayoutOverflow::Squash
-                            | hyperchad_transformer::models::LayoutOverflow::Wrap { .. } => None,
-                        };
+            override_items_to_css(f, &config.overrides)?;
 
-                        if let Some(value) = value {
-                            write_css_attr_important(f, override_item_to_css_name(o), value)?;
-                        }
-                    }
-                    OverrideItem::TextDecoration(x) => {
-                        if let Some(color) = x.color {
-                            write_css_attr_important(
-                                f,
-                                b"text-decoration-color",
-                                color_to_css_string(color).as_bytes(),
-                            )?;
-                        }
-                        if !x.line.is_empty() {
-                            let line = x
-                                .line
-                                .iter()
-                                .map(|line| match line {
-                                    hyperchad_transformer::models::TextDecorationLine::Inherit => {
-                                        "inherit"
-                                    }
-                                    hyperchad_transformer::models::TextDecorationLine::None => {
-                                        "none"
-                                    }
-                                    hyperchad_transformer::models::TextDecorationLine::Underline => {
-                                        "underline"
-                                    }
-                                    hyperchad_transformer::models::TextDecorationLine::Overline => {
-                                        "overline"
-                                    }
-                                    hyperchad_transformer::models::TextDecorationLine::LineThrough => {
-                                        "line-through"
-                                    }
-                                })
-                                .collect::<Vec<_>>()
-                                .join(" ");
-                            write_css_attr_important(f, b"text-decoration-line", line.as_bytes())?;
-                        }
-                        if let Some(style) = x.style {
-                            write_css_attr_important(
-                                f,
-                                b"text-decoration-style",
-                                match style {
-                                    hyperchad_transformer::models::TextDecorationStyle::Inherit => {
-                                        b"inherit"
-                                    }
-                                    hyperchad_transformer::models::TextDecorationStyle::Solid => {
-                                        b"solid"
-                                    }
-                                    hyperchad_transformer::models::TextDecorationStyle::Double => {
-                                        b"double"
-                                    }
-                                    hyperchad_transformer::models::TextDecorationStyle::Dotted => {
-                                        b"dotted"
-                                    }
-                                    hyperchad_transformer::models::TextDecorationStyle::Dashed => {
-                                        b"dashed"
-                                    }
-                                    hyperchad_transformer::models::TextDecorationStyle::Wavy => {
-                                        b"wavy"
-                                    }
-                                },
-                            )?;
-                        }
+            f.write_all(b"}")?; // container id
+            f.write_all(b"}")?; // media query
+        }
 
-                        if let Some(thickness) = &x.thickness {
-                            write_css_attr_important(
-                                f,
-                                b"text-decoration-thickness",
-                                number_to_html_string(thickness, false).as_bytes(),
-                            )?;
-                        }
-                    }
-                    OverrideItem::FontFamily(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            x.join(",").as_bytes(),
-                        )?;
-                    }
-                    OverrideItem::FontWeight(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            x.to_string().as_bytes(),
-                        )?;
-                    }
-                    OverrideItem::Flex(x) => {
-                        write_css_attr_important(
-                            f,
-                            b"flex-grow",
-                            number_to_html_string(&x.grow, false).as_bytes(),
-                        )?;
-                        write_css_attr_important(
-                            f,
-                            b"flex-shrink",
-                            number_to_html_string(&x.shrink, false).as_bytes(),
-                        )?;
-                        write_css_attr_important(
-                            f,
-                            b"flex-basis",
-                            number_to_html_string(&x.basis, false).as_bytes(),
-                        )?;
-                    }
-                    OverrideItem::Cursor(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            x.to_string().as_bytes(),
-                        )?;
-                    }
-                    OverrideItem::Position(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            x.to_string().as_bytes(),
-                        )?;
-                    }
-                    OverrideItem::Background(x) | OverrideItem::Color(x) => {
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            color_to_css_string(*x).as_bytes(),
-                        )?;
-                    }
-                    OverrideItem::BorderTop((color, size))
-                    | OverrideItem::BorderRight((color, size))
-                    | OverrideItem::BorderBottom((color, size))
-                    | OverrideItem::BorderLeft((color, size)) => {
-                        let value = format!(
-                            "{} solid {}",
-                            number_to_html_string(size, true),
-                            color_to_css_string(*color)
-                        );
-                        write_css_attr_important(
-                            f,
-                            override_item_to_css_name(o),
-                            value.as_bytes(),
-                        )?;
-                    }
-                    OverrideItem::StrId(..) | OverrideItem::Classes(..) => {}
-                }
-            }
+        for (container, breakpoint) in container.iter_breakpoints(true) {
+            let Some(class) = breakpoint_class_name(container) else {
+                continue;
+            };
 
-            f.write_all(b"}")?; // container id
+            f.write_all(b"@media(min-width:")?;
+            f.write_all(number_to_html_string(&breakpoint.min_width, true).as_bytes())?;
+            f.write_all(b"){")?;
+
+            f.write_all(b".")?;
+            f.write_all(class.as_bytes())?;
+            f.write_all(b"{")?;
+
+            override_items_to_css(f, &breakpoint.overrides)?;
+
+            f.write_all(b"}")?; // breakpoint class
             f.write_all(b"}")?; // media query
         }
 
@@ -612,6 +325,337 @@ impl HtmlTagRenderer for DefaultHtmlTagRenderer {
     }
 }
 
+/// Writes the CSS declarations of `overrides`, marked `!important` so they take
+/// precedence over the element's inline style.
+#[allow(clippy::too_many_lines)]
+fn override_items_to_css(
+    f: &mut dyn Write,
+    overrides: &[OverrideItem],
+) -> Result<(), std::io::Error> {
+    for o in overrides {
+        match o {
+            OverrideItem::Direction(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    match x {
+                        LayoutDirection::Row => b"row",
+                        LayoutDirection::Column => b"column",
+                    },
+                )?;
+            }
+            OverrideItem::Visibility(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    match x {
+                        Visibility::Visible => b"visible",
+                        Visibility::Hidden => b"hidden",
+                    },
+                )?;
+            }
+            OverrideItem::UserSelect(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    match x {
+                        UserSelect::Auto => b"auto",
+                        UserSelect::None => b"none",
+                        UserSelect::Text => b"text",
+                        UserSelect::All => b"all",
+                    },
+                )?;
+            }
+            OverrideItem::PointerEvents(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    match x {
+                        PointerEvents::Auto => b"auto",
+                        PointerEvents::None => b"none",
+                    },
+                )?;
+            }
+            OverrideItem::OverflowWrap(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    match x {
+                        OverflowWrap::Normal => b"normal",
+                        OverflowWrap::BreakWord => b"break-word",
+                        OverflowWrap::Anywhere => b"anywhere",
+                    },
+                )?;
+            }
+            OverrideItem::TextOverflow(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    match x {
+                        TextOverflow::Clip => b"clip",
+                        TextOverflow::Ellipsis => b"ellipsis",
+                    },
+                )?;
+            }
+            OverrideItem::Hidden(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    if *x { b"none" } else { b"initial" },
+                )?;
+            }
+            OverrideItem::Display(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    x.to_string().as_bytes(),
+                )?;
+            }
+            OverrideItem::AlignItems(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    match x {
+                        AlignItems::Start => b"start",
+                        AlignItems::Center => b"center",
+                        AlignItems::End => b"end",
+                    },
+                )?;
+            }
+            OverrideItem::FlexWrap(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    x.to_string().as_bytes(),
+                )?;
+            }
+            OverrideItem::TextAlign(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    match x {
+                        TextAlign::Start => b"start",
+                        TextAlign::Center => b"center",
+                        TextAlign::End => b"end",
+                        TextAlign::Justify => b"justify",
+                    },
+                )?;
+            }
+            OverrideItem::TextDirection(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    x.to_string().as_bytes(),
+                )?;
+            }
+            OverrideItem::WhiteSpace(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    match x {
+                        WhiteSpace::Normal => b"normal",
+                        WhiteSpace::Preserve => b"pre",
+                        WhiteSpace::PreserveWrap => b"pre-wrap",
+                    },
+                )?;
+            }
+            OverrideItem::MarginLeft(x)
+            | OverrideItem::MarginRight(x)
+            | OverrideItem::MarginTop(x)
+            | OverrideItem::MarginBottom(x)
+            | OverrideItem::Width(x)
+            | OverrideItem::MinWidth(x)
+            | OverrideItem::MaxWidth(x)
+            | OverrideItem::Height(x)
+            | OverrideItem::MinHeight(x)
+            | OverrideItem::MaxHeight(x)
+            | OverrideItem::Left(x)
+            | OverrideItem::Right(x)
+            | OverrideItem::Top(x)
+            | OverrideItem::Bottom(x)
+            | OverrideItem::ColumnGap(x)
+            | OverrideItem::RowGap(x)
+            | OverrideItem::BorderTopLeftRadius(x)
+            | OverrideItem::BorderTopRightRadius(x)
+            | OverrideItem::BorderBottomLeftRadius(x)
+            | OverrideItem::BorderBottomRightRadius(x)
+            | OverrideItem::PaddingLeft(x)
+            | OverrideItem::PaddingRight(x)
+            | OverrideItem::PaddingTop(x)
+            | OverrideItem::PaddingBottom(x)
+            | OverrideItem::Opacity(x)
+            | OverrideItem::TranslateX(x)
+            | OverrideItem::TranslateY(x)
+            | OverrideItem::FontSize(x)
+            | OverrideItem::GridCellSize(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    number_to_html_string(x, true).as_bytes(),
+                )?;
+            }
+            OverrideItem::JustifyContent(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    match x {
+                        hyperchad_transformer::models::JustifyContent::Start => b"start",
+                        hyperchad_transformer::models::JustifyContent::Center => b"center",
+                        hyperchad_transformer::models::JustifyContent::End => b"end",
+                        hyperchad_transformer::models::JustifyContent::SpaceBetween => {
+                            b"space-between"
+                        }
+                        hyperchad_transformer::models::JustifyContent::SpaceEvenly => {
+                            b"space-evenly"
+                        }
+                    },
+                )?;
+            }
+            OverrideItem::OverflowX(x) | OverrideItem::OverflowY(x) => {
+                let value = match x {
+                    hyperchad_transformer::models::LayoutOverflow::Auto => Some(b"auto".as_slice()),
+                    hyperchad_transformer::models::LayoutOverflow::Scroll => {
+                        Some(b"scroll".as_slice())
+                    }
+                    hyperchad_transformer::models::LayoutOverflow::Hidden => {
+                        Some(b"hidden".as_slice())
+                    }
+                    hyperchad_transformer::models::LayoutOverflow::Expand
+                    | hyperchad_transformer::models::LayoutOverflow::Squash
+                    | hyperchad_transformer::models::LayoutOverflow::Wrap { .. } => None,
+                };
+
+                if let Some(value) = value {
+                    write_css_attr_important(f, override_item_to_css_name(o), value)?;
+                }
+            }
+            OverrideItem::TextDecoration(x) => {
+                if let Some(color) = x.color {
+                    write_css_attr_important(
+                        f,
+                        b"text-decoration-color",
+                        color_to_css_string(color).as_bytes(),
+                    )?;
+                }
+                if !x.line.is_empty() {
+                    let line = x
+                        .line
+                        .iter()
+                        .map(|line| match line {
+                            hyperchad_transformer::models::TextDecorationLine::Inherit => "inherit",
+                            hyperchad_transformer::models::TextDecorationLine::None => "none",
+                            hyperchad_transformer::models::TextDecorationLine::Underline => {
+                                "underline"
+                            }
+                            hyperchad_transformer::models::TextDecorationLine::Overline => {
+                                "overline"
+                            }
+                            hyperchad_transformer::models::TextDecorationLine::LineThrough => {
+                                "line-through"
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    write_css_attr_important(f, b"text-decoration-line", line.as_bytes())?;
+                }
+                if let Some(style) = x.style {
+                    write_css_attr_important(
+                        f,
+                        b"text-decoration-style",
+                        match style {
+                            hyperchad_transformer::models::TextDecorationStyle::Inherit => {
+                                b"inherit"
+                            }
+                            hyperchad_transformer::models::TextDecorationStyle::Solid => b"solid",
+                            hyperchad_transformer::models::TextDecorationStyle::Double => b"double",
+                            hyperchad_transformer::models::TextDecorationStyle::Dotted => b"dotted",
+                            hyperchad_transformer::models::TextDecorationStyle::Dashed => b"dashed",
+                            hyperchad_transformer::models::TextDecorationStyle::Wavy => b"wavy",
+                        },
+                    )?;
+                }
+
+                if let Some(thickness) = &x.thickness {
+                    write_css_attr_important(
+                        f,
+                        b"text-decoration-thickness",
+                        number_to_html_string(thickness, false).as_bytes(),
+                    )?;
+                }
+            }
+            OverrideItem::FontFamily(x) => {
+                write_css_attr_important(f, override_item_to_css_name(o), x.join(",").as_bytes())?;
+            }
+            OverrideItem::FontWeight(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    x.to_string().as_bytes(),
+                )?;
+            }
+            OverrideItem::Flex(x) => {
+                write_css_attr_important(
+                    f,
+                    b"flex-grow",
+                    number_to_html_string(&x.grow, false).as_bytes(),
+                )?;
+                write_css_attr_important(
+                    f,
+                    b"flex-shrink",
+                    number_to_html_string(&x.shrink, false).as_bytes(),
+                )?;
+                write_css_attr_important(
+                    f,
+                    b"flex-basis",
+                    number_to_html_string(&x.basis, false).as_bytes(),
+                )?;
+            }
+            OverrideItem::Order(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    x.to_string().as_bytes(),
+                )?;
+            }
+            OverrideItem::Cursor(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    x.to_string().as_bytes(),
+                )?;
+            }
+            OverrideItem::Position(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    x.to_string().as_bytes(),
+                )?;
+            }
+            OverrideItem::Background(x) | OverrideItem::Color(x) => {
+                write_css_attr_important(
+                    f,
+                    override_item_to_css_name(o),
+                    color_to_css_string(*x).as_bytes(),
+                )?;
+            }
+            OverrideItem::BorderTop((color, size))
+            | OverrideItem::BorderRight((color, size))
+            | OverrideItem::BorderBottom((color, size))
+            | OverrideItem::BorderLeft((color, size)) => {
+                let value = format!(
+                    "{} solid {}",
+                    number_to_html_string(size, true),
+                    color_to_css_string(*color)
+                );
+                write_css_attr_important(f, override_item_to_css_name(o), value.as_bytes())?;
+            }
+            OverrideItem::StrId(..) | OverrideItem::Classes(..) => {}
+        }
+    }
+
+    Ok(())
+}
+
 const fn override_item_to_css_name(item: &OverrideItem) -> &'static [u8] {
     match item {
         OverrideItem::StrId(..) => b"id",
@@ -622,7 +666,9 @@ const fn override_item_to_css_name(item: &OverrideItem) -> &'static [u8] {
         OverrideItem::GridCellSize(..) => b"grid-template-columns",
         OverrideItem::JustifyContent(..) => b"justify-content",
         OverrideItem::AlignItems(..) => b"align-items",
+        OverrideItem::FlexWrap(..) => b"flex-wrap",
         OverrideItem::TextAlign(..) => b"text-align",
+        OverrideItem::TextDirection(..) => b"direction",
         OverrideItem::WhiteSpace(..) => b"white-space",
         OverrideItem::TextDecoration(..) => b"text-decoration",
         OverrideItem::FontFamily(..) => b"font-family",
@@ -634,6 +680,7 @@ const fn override_item_to_css_name(item: &OverrideItem) -> &'static [u8] {
         OverrideItem::MinHeight(..) => b"min-height",
         OverrideItem::MaxHeight(..) => b"max-height",
         OverrideItem::Flex(..) => b"flex",
+        OverrideItem::Order(..) => b"order",
         OverrideItem::ColumnGap(..) => b"column-gap",
         OverrideItem::RowGap(..) => b"row-gap",
         OverrideItem::Opacity(..) => b"opacity",
@@ -644,6 +691,7 @@ const fn override_item_to_css_name(item: &OverrideItem) -> &'static [u8] {
         OverrideItem::TranslateX(..) | OverrideItem::TranslateY(..) => b"transform",
         OverrideItem::Cursor(..) => b"cursor",
         OverrideItem::UserSelect(..) => b"user-select",
+        OverrideItem::PointerEvents(..) => b"pointer-events",
         OverrideItem::OverflowWrap(..) => b"overflow-wrap",
         OverrideItem::TextOverflow(..) => b"text-overflow",
         OverrideItem::Position(..) => b"position",
@@ -666,7 +714,7 @@ const fn override_item_to_css_name(item: &OverrideItem) -> &'static [u8] {
         OverrideItem::PaddingBottom(..) => b"padding-bottom",
         OverrideItem::FontSize(..) => b"font-size",
         OverrideItem::Color(..) => b"color",
-        OverrideItem::Hidden(..) => b"display",
+        OverrideItem::Hidden(..) | OverrideItem::Display(..) => b"display",
         OverrideItem::Visibility(..) => b"visibility",
     }
 }
@@ -1141,7 +1189,7 @@ impl<T: HtmlApp + ToRenderRunner + Send + Sync> Renderer for HtmlRenderer<T> {
 mod tests {
     use super::*;
     use hyperchad_transformer::{
-        ConfigOverride, Flex, Number, OverrideCondition, OverrideItem, TextDecoration,
+        Breakpoint, ConfigOverride, Flex, Number, OverrideCondition, OverrideItem, TextDecoration,
         models::{
             Cursor, FontWeight, JustifyContent, LayoutDirection, LayoutOverflow, OverflowWrap,
             Position, TextAlign, TextDecorationLine, TextDecorationStyle, TextOverflow, UserSelect,
@@ -1299,6 +1347,76 @@ mod tests {
         assert_eq!(css, "<style></style>");
     }
 
+    #[test_log::test]
+    fn test_reactive_conditions_to_css_breakpoint_targets_generated_class() {
+        let tag_renderer = DefaultHtmlTagRenderer::default();
+
+        let container = Container {
+            element: hyperchad_transformer::Element::Div,
+            classes: vec!["card".to_string()],
+            breakpoints: vec![Breakpoint {
+                min_width: Number::Integer(768),
+                overrides: vec![
+                    OverrideItem::Direction(LayoutDirection::Row),
+                    OverrideItem::Width(Number::Integer(50)),
+                ],
+            }],
+            ..Default::default()
+        };
+        let class = html::breakpoint_class_name(&container).unwrap();
+
+        let mut buffer = Vec::new();
+        tag_renderer
+            .reactive_conditions_to_css(&mut buffer, &container)
+            .unwrap();
+        let css = std::str::from_utf8(&buffer).unwrap();
+
+        assert_eq!(
+            css,
+            format!(
+                "<style>@media(min-width:768px){{.{class}{{\
+                flex-direction:row !important;width:50px !important;}}}}</style>"
+            )
+        );
+
+        let mut buffer = Vec::new();
+        tag_renderer
+            .element_attrs_to_html(&mut buffer, &container, false)
+            .unwrap();
+        let html = std::str::from_utf8(&buffer).unwrap();
+
+        assert!(html.contains(&format!("class=\"card {class}\"")), "{html}");
+    }
+
+    #[test_log::test]
+    fn test_breakpoint_class_name_is_only_generated_for_breakpoints() {
+        let breakpoint = Breakpoint {
+            min_width: Number::Integer(768),
+            overrides: vec![OverrideItem::Hidden(true)],
+        };
+        let container = Container {
+            breakpoints: vec![breakpoint.clone()],
+            ..Default::default()
+        };
+        let other = Container {
+            breakpoints: vec![Breakpoint {
+                min_width: Number::Integer(1024),
+                ..breakpoint
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(html::breakpoint_class_name(&Container::default()), None);
+        assert_eq!(
+            html::breakpoint_class_name(&container),
+            html::breakpoint_class_name(&container.clone())
+        );
+        assert_ne!(
+            html::breakpoint_class_name(&container),
+            html::breakpoint_class_name(&other)
+        );
+    }
+
     #[test_log::test]
     fn test_reactive_conditions_to_css_unknown_trigger_skipped() {
         let responsive_triggers = BTreeMap::new(); // Empty triggers
@@ -1595,6 +1713,48 @@ mod tests {
         assert!(result.contains("background:rgb(255,128,64)"));
     }
 
+    #[test_log::test]
+    fn test_reactive_conditions_to_css_pointer_events_overrides() {
+        let mut responsive_triggers = BTreeMap::new();
+        responsive_triggers.insert(
+            "mobile".to_string(),
+            ResponsiveTrigger::MaxWidth(Number::Integer(768)),
+        );
+
+        let tag_renderer = DefaultHtmlTagRenderer {
+            responsive_triggers,
+        };
+
+        for (pointer_events, expected_css) in [
+            (PointerEvents::Auto, "pointer-events:auto !important;"),
+            (PointerEvents::None, "pointer-events:none !important;"),
+        ] {
+            let container = Container {
+                str_id: Some("test-element".to_string()),
+                element: hyperchad_transformer::Element::Div,
+                overrides: vec![ConfigOverride {
+                    condition: OverrideCondition::ResponsiveTarget {
+                        name: "mobile".to_string(),
+                    },
+                    overrides: vec![OverrideItem::PointerEvents(pointer_events)],
+                    default: None,
+                }],
+                ..Default::default()
+            };
+
+            let mut buffer = Vec::new();
+            tag_renderer
+                .reactive_conditions_to_css(&mut buffer, &container)
+                .unwrap();
+            let css = std::str::from_utf8(&buffer).unwrap();
+
+            assert!(
+                css.contains(expected_css),
+                "Expected CSS to contain '{expected_css}', got: {css}"
+            );
+        }
+    }
+
     #[test_log::test]
     fn test_reactive_conditions_to_css_user_select_overrides() {
         let mut responsive_triggers = BTreeMap::new();
@@ -43,7 +43,7 @@ use hyperchad_renderer::{Color, HtmlTagRenderer, RendererEvent, View, canvas};
 use hyperchad_renderer_html::{
     DefaultHtmlTagRenderer,
     extend::{ExtendHtmlRenderer, HtmlRendererEventPub},
-    html::write_attr,
+    html::{write_attr, write_escaped_attr},
 };
 use hyperchad_transformer::{
     Container, ResponsiveTrigger,
@@ -873,23 +873,23 @@ impl HtmlTagRenderer for VanillaJsTagRenderer {
                     // Output HTTP method
                     match route {
                         Route::Get { .. } => {
-                            write_attr(f, b"hx-get", path.as_bytes())?;
+                            write_escaped_attr(f, b"hx-get", path)?;
                         }
                         Route::Post { .. } => {
-                            write_attr(f, b"hx-post", path.as_bytes())?;
+                            write_escaped_attr(f, b"hx-post", path)?;
                         }
                         Route::Put { .. } => {
-                            write_attr(f, b"hx-put", path.as_bytes())?;
+                            write_escaped_attr(f, b"hx-put", path)?;
                         }
                         Route::Delete { .. } => {
-                            write_attr(f, b"hx-delete", path.as_bytes())?;
+                            write_escaped_attr(f, b"hx-delete", path)?;
                         }
                         Route::Patch { .. } => {
-                            write_attr(f, b"hx-patch", path.as_bytes())?;
+                            write_escaped_attr(f, b"hx-patch", path)?;
                         }
                     }
                     if let Some(trigger) = trigger {
-                        write_attr(f, b"hx-trigger", trigger.as_bytes())?;
+                        write_escaped_attr(f, b"hx-trigger", trigger)?;
                     }
                 }
             }
@@ -2697,6 +2697,28 @@ mod tests {
             assert!(result.contains(r#"hx-trigger="click""#));
         }
 
+        #[test_log::test]
+        fn test_route_escapes_path_and_trigger() {
+            let container = Container {
+                route: Some(Route::Get {
+                    route: r#"/search?q="a"&artist=<b>"#.to_string(),
+                    trigger: Some(r#"keyup[key=="Enter"]"#.to_string()),
+                    target: Selector::SelfTarget,
+                    strategy: SwapStrategy::This,
+                }),
+                ..Default::default()
+            };
+            let result = render_attrs(&container);
+            assert!(
+                result.contains(r#"hx-get="/search?q=&quot;a&quot;&amp;artist=&lt;b&gt;""#),
+                "{result}"
+            );
+            assert!(
+                result.contains(r#"hx-trigger="keyup[key==&quot;Enter&quot;]""#),
+                "{result}"
+            );
+        }
+
         #[test_log::test]
         fn test_route_post() {
             let container = Container {
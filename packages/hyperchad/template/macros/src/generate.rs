@@ -569,7 +569,7 @@ impl Generator {
                 "anchor" => matches!(name_str.as_str(), "href" | "target"),
                 "image" => matches!(
                     name_str.as_str(),
-                    "src" | "alt" | "srcset" | "sizes" | "loading" | "fit"
+                    "src" | "alt" | "srcset" | "sizes" | "loading" | "decoding" | "fit"
                 ),
                 "details" => matches!(name_str.as_str(), "open"),
                 "select" => matches!(
@@ -1296,6 +1296,7 @@ impl Generator {
         let mut srcset = None;
         let mut sizes = None;
         let mut loading = None;
+        let mut decoding = None;
         let mut fit = None;
 
         for (attr_name, attr_type) in element_attrs {
@@ -1320,6 +1321,9 @@ impl Generator {
                     "loading" => {
                         loading = Some(Self::markup_to_image_loading_tokens(attr_value));
                     }
+                    "decoding" => {
+                        decoding = Some(Self::markup_to_image_decoding_tokens(attr_value));
+                    }
                     "fit" => {
                         fit = Some(Self::markup_to_image_fit_tokens(attr_value));
                     }
@@ -1333,6 +1337,7 @@ impl Generator {
         let srcset_field = srcset.map_or_else(|| quote! { None }, |s| quote! { Some(#s) });
         let sizes_field = sizes.map_or_else(|| quote! { None }, |s| quote! { Some(#s) });
         let loading_field = loading.map_or_else(|| quote! { None }, |l| quote! { Some(#l) });
+        let decoding_field = decoding.map_or_else(|| quote! { None }, |d| quote! { Some(#d) });
         let fit_field = fit.map_or_else(|| quote! { None }, |f| quote! { Some(#f) });
 
         quote! {
@@ -1342,6 +1347,7 @@ impl Generator {
                 source_set: #srcset_field,
                 sizes: #sizes_field,
                 loading: #loading_field,
+                decoding: #decoding_field,
                 fit: #fit_field
             }
         }
@@ -1395,6 +1401,29 @@ impl Generator {
         }
     }
 
+    fn markup_to_image_decoding_tokens(value: Markup<NoElement>) -> TokenStream {
+        match value {
+            Markup::Lit(lit) => {
+                if let syn::Lit::Str(lit_str) = &lit.lit {
+                    let value_str = lit_str.value();
+                    match value_str.as_str() {
+                        "auto" => quote! { hyperchad_transformer_models::ImageDecoding::Auto },
+                        "sync" => quote! { hyperchad_transformer_models::ImageDecoding::Sync },
+                        "async" => quote! { hyperchad_transformer_models::ImageDecoding::Async },
+                        _ => quote! { hyperchad_transformer_models::ImageDecoding::default() },
+                    }
+                } else {
+                    let lit = &lit.lit;
+                    quote! { (#lit).into() }
+                }
+            }
+            Markup::Splice { expr, .. } => {
+                quote! { (#expr).into() }
+            }
+            _ => quote! { hyperchad_transformer_models::ImageDecoding::default() },
+        }
+    }
+
     /// Extract a compile-time input type (string literal or identifier)
     fn extract_compile_time_input_type(value: &Markup<NoElement>) -> Option<String> {
         match value {
@@ -2013,9 +2042,13 @@ impl Generator {
                 "padding"
                 | "padding-x"
                 | "padding-y"
+                | "padding-inline"
+                | "padding-block"
                 | "margin"
                 | "margin-x"
                 | "margin-y"
+                | "margin-inline"
+                | "margin-block"
                 | "border"
                 | "border-x"
                 | "border-y"
@@ -2061,7 +2094,7 @@ impl Generator {
             } else {
                 let name_str = name.to_string();
                 let error_msg = format!(
-                    "Unknown attribute '{name_str}'. Supported attributes include: class, width, height, padding, padding-x, padding-y, padding-left, padding-right, padding-top, padding-bottom, margin, margin-x, margin-y, margin-left, margin-right, margin-top, margin-bottom, border, border-x, border-y, border-top, border-right, border-bottom, border-left, background, color, align-items, justify-content, text-align, white-space, text-decoration, direction, position, cursor, user-select, overflow-wrap, text-overflow, visibility, overflow-x, overflow-y, font-family, font-size, font-weight, opacity, border-radius, gap, hidden, debug, flex, flex-grow, flex-shrink, flex-basis, HTMX attributes (hx-get, hx-post, hx-put, hx-delete, hx-patch, hx-trigger, hx-target, hx-swap), and action attributes (fx-click, fx-click-outside, fx-resize, fx-immediate, fx-hover, fx-change, fx-mousedown, fx-http-before-request, fx-http-after-request, fx-http-success, fx-http-error, fx-http-abort, fx-http-timeout, and any other fx-* event)"
+                    "Unknown attribute '{name_str}'. Supported attributes include: class, width, height, padding, padding-x, padding-y, padding-inline, padding-block, padding-left, padding-right, padding-top, padding-bottom, margin, margin-x, margin-y, margin-inline, margin-block, margin-left, margin-right, margin-top, margin-bottom, border, border-x, border-y, border-top, border-right, border-bottom, border-left, background, color, align-items, justify-content, text-align, text-direction, white-space, text-decoration, direction, position, cursor, user-select, pointer-events, overflow-wrap, flex-wrap, text-overflow, visibility, display, overflow-x, overflow-y, font-family, font-size, font-weight, opacity, border-radius, gap, hidden, debug, flex, flex-grow, flex-shrink, flex-basis, HTMX attributes (hx-get, hx-post, hx-put, hx-delete, hx-patch, hx-trigger, hx-target, hx-swap), and action attributes (fx-click, fx-click-outside, fx-resize, fx-immediate, fx-hover, fx-change, fx-mousedown, fx-http-before-request, fx-http-after-request, fx-http-success, fx-http-error, fx-http-abort, fx-http-timeout, and any other fx-* event)"
                 );
                 return Err(error_msg);
             }
@@ -2127,7 +2160,10 @@ impl Generator {
             );
         }
 
-        if let Some((_, AttributeType::Normal { value, .. })) = shorthand_attrs.get("padding-x") {
+        if let Some((_, AttributeType::Normal { value, .. })) = shorthand_attrs
+            .get("padding-x")
+            .or_else(|| shorthand_attrs.get("padding-inline"))
+        {
             let value_tokens = Self::markup_to_number_tokens(value.clone());
             field_assignments.insert(
                 "padding_left".to_string(),
@@ -2151,7 +2187,10 @@ impl Generator {
             );
         }
 
-        if let Some((_, AttributeType::Normal { value, .. })) = shorthand_attrs.get("padding-y") {
+        if let Some((_, AttributeType::Normal { value, .. })) = shorthand_attrs
+            .get("padding-y")
+            .or_else(|| shorthand_attrs.get("padding-block"))
+        {
             let value_tokens = Self::markup_to_number_tokens(value.clone());
             field_assignments.insert(
                 "padding_top".to_string(),
@@ -2220,7 +2259,10 @@ impl Generator {
             );
         }
 
-        if let Some((_, AttributeType::Normal { value, .. })) = shorthand_attrs.get("margin-x") {
+        if let Some((_, AttributeType::Normal { value, .. })) = shorthand_attrs
+            .get("margin-x")
+            .or_else(|| shorthand_attrs.get("margin-inline"))
+        {
             let value_tokens = Self::markup_to_number_tokens(value.clone());
             field_assignments.insert(
                 "margin_left".to_string(),
@@ -2244,7 +2286,10 @@ impl Generator {
             );
         }
 
-        if let Some((_, AttributeType::Normal { value, .. })) = shorthand_attrs.get("margin-y") {
+        if let Some((_, AttributeType::Normal { value, .. })) = shorthand_attrs
+            .get("margin-y")
+            .or_else(|| shorthand_attrs.get("margin-block"))
+        {
             let value_tokens = Self::markup_to_number_tokens(value.clone());
             field_assignments.insert(
                 "margin_top".to_string(),
@@ -2737,6 +2782,9 @@ impl Generator {
             "text-align" => Self::responsive_enum_override_statement(value, "TextAlign")
                 .into_iter()
                 .collect(),
+            "text-direction" => Self::responsive_enum_override_statement(value, "TextDirection")
+                .into_iter()
+                .collect(),
             "white-space" => Self::responsive_enum_override_statement(value, "WhiteSpace")
                 .into_iter()
                 .collect(),
@@ -2749,15 +2797,24 @@ impl Generator {
             "user-select" => Self::responsive_enum_override_statement(value, "UserSelect")
                 .into_iter()
                 .collect(),
+            "pointer-events" => Self::responsive_enum_override_statement(value, "PointerEvents")
+                .into_iter()
+                .collect(),
             "overflow-wrap" => Self::responsive_enum_override_statement(value, "OverflowWrap")
                 .into_iter()
                 .collect(),
+            "flex-wrap" => Self::responsive_enum_override_statement(value, "FlexWrap")
+                .into_iter()
+                .collect(),
             "text-overflow" => Self::responsive_enum_override_statement(value, "TextOverflow")
                 .into_iter()
                 .collect(),
             "visibility" => Self::responsive_enum_override_statement(value, "Visibility")
                 .into_iter()
                 .collect(),
+            "display" => Self::responsive_enum_override_statement(value, "Display")
+                .into_iter()
+                .collect(),
             "overflow-x" => Self::responsive_enum_override_statement(value, "OverflowX")
                 .into_iter()
                 .collect(),
@@ -3102,7 +3159,8 @@ impl Generator {
                 fit: None,
                 source_set: None,
                 sizes: None,
-                loading: None
+                loading: None,
+                decoding: None
             } },
             "input" => quote! { hyperchad_transformer::Element::Input {
                 input: hyperchad_transformer::Input::Text { value: None, placeholder: None },
@@ -3218,6 +3276,7 @@ impl Generator {
                     Some(Self::enum_attr("justify_content", "JustifyContent", value))
                 }
                 "text-align" => Some(Self::enum_attr("text_align", "TextAlign", value)),
+                "text-direction" => Some(Self::enum_attr("text_direction", "TextDirection", value)),
                 "white-space" => Some(Self::enum_attr("white_space", "WhiteSpace", value)),
                 "text-decoration" => Some(Self::text_decoration_attr("text_decoration", value)),
                 "direction" => Some(Self::direct_enum_attr(
@@ -3228,9 +3287,12 @@ impl Generator {
                 "position" => Some(Self::enum_attr("position", "Position", value)),
                 "cursor" => Some(Self::enum_attr("cursor", "Cursor", value)),
                 "user-select" => Some(Self::enum_attr("user_select", "UserSelect", value)),
+                "pointer-events" => Some(Self::enum_attr("pointer_events", "PointerEvents", value)),
                 "overflow-wrap" => Some(Self::enum_attr("overflow_wrap", "OverflowWrap", value)),
+                "flex-wrap" => Some(Self::enum_attr("flex_wrap", "FlexWrap", value)),
                 "text-overflow" => Some(Self::enum_attr("text_overflow", "TextOverflow", value)),
                 "visibility" => Some(Self::enum_attr("visibility", "Visibility", value)),
+                "display" => Some(Self::enum_attr("display", "Display", value)),
                 "overflow-x" => Some(Self::direct_enum_attr(
                     "overflow_x",
                     "LayoutOverflow",
@@ -5275,6 +5337,7 @@ fn kebab_to_pascal_case(s: &str) -> String {
         "flex-start" => return "FlexStart".to_string(),
         "flex-end" => return "FlexEnd".to_string(),
         "line-through" => return "LineThrough".to_string(),
+        "nowrap" => return "NoWrap".to_string(),
         _ => {}
     }
 
@@ -5300,6 +5363,7 @@ mod tests {
         assert_eq!(kebab_to_pascal_case("flex-start"), "FlexStart");
         assert_eq!(kebab_to_pascal_case("flex-end"), "FlexEnd");
         assert_eq!(kebab_to_pascal_case("line-through"), "LineThrough");
+        assert_eq!(kebab_to_pascal_case("nowrap"), "NoWrap");
     }
 
     #[test_log::test]
@@ -347,6 +347,17 @@ fn test_image_enum_attributes() {
     } else {
         panic!("Expected Image element");
     }
+
+    // Test image decoding with string literals
+    let containers = container! {
+        image decoding="async" { }
+    };
+    assert_eq!(containers.len(), 1);
+    if let hyperchad_transformer::Element::Image { decoding, .. } = &containers[0].element {
+        assert_eq!(decoding, &Some(ImageDecoding::Async));
+    } else {
+        panic!("Expected Image element");
+    }
 }
 
 #[test]
@@ -0,0 +1,41 @@
+use hyperchad_template::container;
+use hyperchad_transformer_models::FlexWrap;
+
+#[test_log::test]
+fn test_flex_wrap_with_quoted_string_literals() {
+    let containers = container! {
+        div direction="row" flex-wrap="nowrap" { "No wrapping" }
+    };
+    assert_eq!(containers[0].flex_wrap, Some(FlexWrap::NoWrap));
+
+    let containers = container! {
+        div direction="row" flex-wrap="wrap" { "Wrap" }
+    };
+    assert_eq!(containers[0].flex_wrap, Some(FlexWrap::Wrap));
+
+    let containers = container! {
+        div direction="row" flex-wrap="wrap-reverse" { "Wrap reverse" }
+    };
+    assert_eq!(containers[0].flex_wrap, Some(FlexWrap::WrapReverse));
+}
+
+#[test_log::test]
+fn test_flex_wrap_with_unquoted_identifiers() {
+    let containers = container! {
+        div direction=row flex-wrap=nowrap { "No wrapping" }
+    };
+    assert_eq!(containers[0].flex_wrap, Some(FlexWrap::NoWrap));
+
+    let containers = container! {
+        div direction=row flex-wrap=wrap-reverse { "Wrap reverse" }
+    };
+    assert_eq!(containers[0].flex_wrap, Some(FlexWrap::WrapReverse));
+}
+
+#[test_log::test]
+fn test_flex_wrap_defaults_to_none() {
+    let containers = container! {
+        div direction=row { "Default" }
+    };
+    assert_eq!(containers[0].flex_wrap, None);
+}
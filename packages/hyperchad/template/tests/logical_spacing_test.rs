@@ -0,0 +1,29 @@
+use hyperchad_template::container;
+use hyperchad_transformer::Number;
+use hyperchad_transformer_models::TextDirection;
+
+#[test_log::test]
+fn test_text_direction_attribute() {
+    let containers = container! {
+        div text-direction="rtl" { "Right to left" }
+    };
+    assert_eq!(containers[0].text_direction, Some(TextDirection::Rtl));
+
+    let containers = container! {
+        div text-direction=ltr { "Left to right" }
+    };
+    assert_eq!(containers[0].text_direction, Some(TextDirection::Ltr));
+}
+
+#[test_log::test]
+fn test_inline_and_block_spacing_shorthands() {
+    let containers = container! {
+        div margin-inline=10 padding-block=5 { "Spaced" }
+    };
+    assert_eq!(containers[0].margin_left, Some(Number::Integer(10)));
+    assert_eq!(containers[0].margin_right, Some(Number::Integer(10)));
+    assert_eq!(containers[0].margin_top, None);
+    assert_eq!(containers[0].padding_top, Some(Number::Integer(5)));
+    assert_eq!(containers[0].padding_bottom, Some(Number::Integer(5)));
+    assert_eq!(containers[0].padding_left, None);
+}
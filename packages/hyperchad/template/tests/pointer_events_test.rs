@@ -0,0 +1,59 @@
+use hyperchad_template::container;
+use hyperchad_transformer_models::PointerEvents;
+
+#[test_log::test]
+fn test_pointer_events_with_quoted_string_literals() {
+    let containers = container! {
+        div pointer-events="auto" { "Auto" }
+    };
+    assert_eq!(containers[0].pointer_events, Some(PointerEvents::Auto));
+
+    let containers = container! {
+        div pointer-events="none" { "None" }
+    };
+    assert_eq!(containers[0].pointer_events, Some(PointerEvents::None));
+}
+
+#[test_log::test]
+fn test_pointer_events_with_unquoted_identifiers() {
+    let containers = container! {
+        div pointer-events=auto { "Auto" }
+    };
+    assert_eq!(containers[0].pointer_events, Some(PointerEvents::Auto));
+
+    let containers = container! {
+        div pointer-events=none { "None" }
+    };
+    assert_eq!(containers[0].pointer_events, Some(PointerEvents::None));
+}
+
+#[test_log::test]
+fn test_pointer_events_with_expressions() {
+    let passthrough = true;
+
+    let containers = container! {
+        div pointer-events=(if passthrough { PointerEvents::None } else { PointerEvents::Auto }) {
+            "Conditional pointer-events"
+        }
+    };
+    assert_eq!(containers[0].pointer_events, Some(PointerEvents::None));
+}
+
+#[test_log::test]
+fn test_pointer_events_html_output() {
+    let containers = container! {
+        div pointer-events=none { "Overlay" }
+    };
+    let html = containers[0]
+        .display_to_string_default(false, false)
+        .unwrap();
+    assert!(html.contains("sx-pointer-events=\"none\""));
+}
+
+#[test_log::test]
+fn test_pointer_events_default_is_none() {
+    let containers = container! {
+        div { "No pointer-events specified" }
+    };
+    assert_eq!(containers[0].pointer_events, None);
+}
@@ -8,9 +8,9 @@
 //! * [`LayoutDirection`], [`LayoutOverflow`], [`JustifyContent`], [`AlignItems`] - Flexbox-style layout controls
 //! * [`Selector`], [`ElementTarget`] - CSS-style element targeting
 //! * [`Route`], [`SwapStrategy`] - HTTP routing and DOM content swapping (htmx-inspired)
-//! * [`Position`], [`Cursor`], [`Visibility`] - Element positioning and styling
+//! * [`Position`], [`Cursor`], [`Visibility`], [`Display`], [`PointerEvents`] - Element positioning and styling
 //! * [`TextAlign`], [`FontWeight`], [`WhiteSpace`] - Text styling and formatting
-//! * [`ImageFit`], [`ImageLoading`] - Image display controls
+//! * [`ImageFit`], [`ImageLoading`], [`ImageDecoding`] - Image display controls
 //!
 //! # Features
 //!
@@ -782,6 +782,43 @@ impl std::fmt::Display for Visibility {
     }
 }
 
+/// Element display type.
+///
+/// Unlike [`Visibility::Hidden`], [`Display::None`] removes the element from layout
+/// entirely, so it occupies no space, while keeping it in the element tree.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[cfg_attr(feature = "arb", derive(test_strategy::Arbitrary))]
+pub enum Display {
+    /// Element is laid out as a block. This is the default.
+    #[default]
+    Block,
+    /// Element is laid out inline with its siblings.
+    Inline,
+    /// Element is laid out inline, but sized as a block.
+    InlineBlock,
+    /// Element is a flex container.
+    Flex,
+    /// Element is a grid container.
+    Grid,
+    /// Element is removed from layout and occupies no space.
+    None,
+}
+
+impl std::fmt::Display for Display {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Block => f.write_str("block"),
+            Self::Inline => f.write_str("inline"),
+            Self::InlineBlock => f.write_str("inline-block"),
+            Self::Flex => f.write_str("flex"),
+            Self::Grid => f.write_str("grid"),
+            Self::None => f.write_str("none"),
+        }
+    }
+}
+
 /// Image loading strategy.
 ///
 /// Controls when images are loaded relative to page load.
@@ -806,6 +843,33 @@ impl std::fmt::Display for ImageLoading {
     }
 }
 
+/// Image decoding hint.
+///
+/// Controls whether an image is decoded before or after the surrounding content is shown.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[cfg_attr(feature = "arb", derive(test_strategy::Arbitrary))]
+pub enum ImageDecoding {
+    /// Let the renderer decide. This is the default.
+    #[default]
+    Auto,
+    /// Decode the image before showing it together with the surrounding content.
+    Sync,
+    /// Decode the image off the main thread, showing the surrounding content first.
+    Async,
+}
+
+impl std::fmt::Display for ImageDecoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Auto => f.write_str("auto"),
+            Self::Sync => f.write_str("sync"),
+            Self::Async => f.write_str("async"),
+        }
+    }
+}
+
 /// How an image should fit within its container.
 ///
 /// Controls the sizing behavior of images relative to their container.
@@ -1078,6 +1142,103 @@ impl std::fmt::Display for UserSelect {
     }
 }
 
+/// Pointer event targeting behavior.
+///
+/// Controls whether an element can be the target of pointer events (clicks, hovers).
+/// Elements with [`PointerEvents::None`] are still painted, but pointer events pass
+/// through them to the elements beneath.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[cfg_attr(feature = "arb", derive(test_strategy::Arbitrary))]
+pub enum PointerEvents {
+    /// Element can be the target of pointer events. This is the default.
+    #[default]
+    Auto,
+    /// Element is never the target of pointer events.
+    None,
+}
+
+impl std::fmt::Display for PointerEvents {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Auto => f.write_str("auto"),
+            Self::None => f.write_str("none"),
+        }
+    }
+}
+
+/// Wrapping of flex children onto multiple lines.
+///
+/// Controls whether children that overflow the main axis break onto new cross-axis lines.
+///
+/// The layout engine currently only wraps [`LayoutDirection::Row`] containers, and flex
+/// growth is distributed across the whole container rather than per line.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[cfg_attr(feature = "arb", derive(test_strategy::Arbitrary))]
+pub enum FlexWrap {
+    /// Keep all children on a single line. This is the default.
+    #[default]
+    NoWrap,
+    /// Break overflowing children onto new lines after the first.
+    Wrap,
+    /// Break overflowing children onto new lines stacked before the first.
+    WrapReverse,
+}
+
+impl FlexWrap {
+    /// Whether children break onto multiple lines.
+    #[must_use]
+    pub const fn is_wrapping(self) -> bool {
+        !matches!(self, Self::NoWrap)
+    }
+}
+
+impl std::fmt::Display for FlexWrap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoWrap => f.write_str("nowrap"),
+            Self::Wrap => f.write_str("wrap"),
+            Self::WrapReverse => f.write_str("wrap-reverse"),
+        }
+    }
+}
+
+/// Inline base direction of text.
+///
+/// Determines which physical side the logical inline-start and inline-end sides (e.g.
+/// `margin-inline-start`) map to.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[cfg_attr(feature = "arb", derive(test_strategy::Arbitrary))]
+pub enum TextDirection {
+    /// Left to right, with inline-start on the left. This is the default.
+    #[default]
+    Ltr,
+    /// Right to left, with inline-start on the right.
+    Rtl,
+}
+
+impl TextDirection {
+    /// Whether the inline-start side is the physical left side.
+    #[must_use]
+    pub const fn is_start_left(self) -> bool {
+        matches!(self, Self::Ltr)
+    }
+}
+
+impl std::fmt::Display for TextDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ltr => f.write_str("ltr"),
+            Self::Rtl => f.write_str("rtl"),
+        }
+    }
+}
+
 /// Word breaking and wrapping behavior.
 ///
 /// Controls how words break when they exceed the container width.
@@ -1179,6 +1340,55 @@ mod tests {
         assert_eq!(UserSelect::default(), UserSelect::Auto);
     }
 
+    #[test]
+    fn test_display_display() {
+        assert_eq!(Display::Block.to_string(), "block");
+        assert_eq!(Display::Inline.to_string(), "inline");
+        assert_eq!(Display::InlineBlock.to_string(), "inline-block");
+        assert_eq!(Display::Flex.to_string(), "flex");
+        assert_eq!(Display::Grid.to_string(), "grid");
+        assert_eq!(Display::None.to_string(), "none");
+    }
+
+    #[test]
+    fn test_pointer_events_display() {
+        assert_eq!(PointerEvents::Auto.to_string(), "auto");
+        assert_eq!(PointerEvents::None.to_string(), "none");
+    }
+
+    #[test]
+    fn test_pointer_events_default() {
+        assert_eq!(PointerEvents::default(), PointerEvents::Auto);
+    }
+
+    #[test]
+    fn test_flex_wrap_display() {
+        assert_eq!(FlexWrap::NoWrap.to_string(), "nowrap");
+        assert_eq!(FlexWrap::Wrap.to_string(), "wrap");
+        assert_eq!(FlexWrap::WrapReverse.to_string(), "wrap-reverse");
+    }
+
+    #[test]
+    fn test_flex_wrap_default() {
+        assert_eq!(FlexWrap::default(), FlexWrap::NoWrap);
+        assert!(!FlexWrap::NoWrap.is_wrapping());
+        assert!(FlexWrap::Wrap.is_wrapping());
+        assert!(FlexWrap::WrapReverse.is_wrapping());
+    }
+
+    #[test]
+    fn test_text_direction_display() {
+        assert_eq!(TextDirection::Ltr.to_string(), "ltr");
+        assert_eq!(TextDirection::Rtl.to_string(), "rtl");
+    }
+
+    #[test]
+    fn test_text_direction_default() {
+        assert_eq!(TextDirection::default(), TextDirection::Ltr);
+        assert!(TextDirection::Ltr.is_start_left());
+        assert!(!TextDirection::Rtl.is_start_left());
+    }
+
     #[test]
     fn test_overflow_wrap_display() {
         assert_eq!(OverflowWrap::Normal.to_string(), "normal");
@@ -14,7 +14,8 @@ use proptest::prelude::*;
 
 use crate::{
     Calculation, ConfigOverride, Container, Element, Flex, HeaderSize, Input, Number,
-    OverrideCondition, OverrideItem, OverrideItemType, TextDecoration,
+    OverrideCondition, OverrideItem, OverrideItemType, TextDecoration, TextShadow, TimingFunction,
+    Transition,
 };
 
 /// Strategy for generating non-Calc Number variants only.
@@ -155,6 +156,50 @@ impl Arbitrary for HeaderSize {
     }
 }
 
+impl Arbitrary for TimingFunction {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(Self::Ease),
+            Just(Self::Linear),
+            Just(Self::EaseIn),
+            Just(Self::EaseOut),
+            Just(Self::EaseInOut),
+            (
+                any::<JsonF32>(),
+                any::<JsonF32>(),
+                any::<JsonF32>(),
+                any::<JsonF32>()
+            )
+                .prop_map(|(x1, y1, x2, y2)| Self::CubicBezier(x1.0, y1.0, x2.0, y2.0)),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for Transition {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        (
+            any::<CssIdentifierString>(),
+            0..10_000_i64,
+            any::<TimingFunction>(),
+            0..10_000_i64,
+        )
+            .prop_map(|(property, duration, timing, delay)| Self {
+                property: property.0,
+                duration: Number::Integer(duration),
+                timing,
+                delay: Number::Integer(delay),
+            })
+            .boxed()
+    }
+}
+
 impl Arbitrary for TextDecoration {
     type Parameters = ();
     type Strategy = BoxedStrategy<Self>;
@@ -179,6 +224,27 @@ impl Arbitrary for TextDecoration {
     }
 }
 
+impl Arbitrary for TextShadow {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        (
+            -100..100_i64,
+            -100..100_i64,
+            0..100_i64,
+            any::<hyperchad_color::Color>(),
+        )
+            .prop_map(|(x, y, blur, color)| Self {
+                x: Number::Integer(x),
+                y: Number::Integer(y),
+                blur: Number::Integer(blur),
+                color,
+            })
+            .boxed()
+    }
+}
+
 impl Arbitrary for Flex {
     type Parameters = ();
     type Strategy = BoxedStrategy<Self>;
@@ -260,19 +326,23 @@ impl Arbitrary for Element {
                 any::<Option<XmlString>>(),
                 any::<Option<hyperchad_transformer_models::ImageFit>>(),
                 any::<Option<hyperchad_transformer_models::ImageLoading>>(),
+                any::<Option<hyperchad_transformer_models::ImageDecoding>>(),
                 any::<Option<XmlString>>(),
                 any::<Option<Number>>(),
             )
-                .prop_map(|(source, alt, fit, loading, source_set, sizes)| {
-                    Self::Image {
-                        source: source.map(|x| x.0),
-                        alt: alt.map(|x| x.0),
-                        fit,
-                        loading,
-                        source_set: source_set.map(|x| x.0),
-                        sizes,
+                .prop_map(
+                    |(source, alt, fit, loading, decoding, source_set, sizes)| {
+                        Self::Image {
+                            source: source.map(|x| x.0),
+                            alt: alt.map(|x| x.0),
+                            fit,
+                            loading,
+                            decoding,
+                            source_set: source_set.map(|x| x.0),
+                            sizes,
+                        }
                     }
-                }),
+                ),
             (
                 any::<Option<hyperchad_transformer_models::LinkTarget>>(),
                 any::<Option<XmlString>>()
@@ -388,8 +458,12 @@ impl Arbitrary for OverrideItem {
             any::<hyperchad_transformer_models::JustifyContent>().prop_map(Self::JustifyContent),
             // AlignItems
             any::<hyperchad_transformer_models::AlignItems>().prop_map(Self::AlignItems),
+            // FlexWrap
+            any::<hyperchad_transformer_models::FlexWrap>().prop_map(Self::FlexWrap),
             // TextAlign
             any::<hyperchad_transformer_models::TextAlign>().prop_map(Self::TextAlign),
+            // TextDirection
+            any::<hyperchad_transformer_models::TextDirection>().prop_map(Self::TextDirection),
             // WhiteSpace
             any::<hyperchad_transformer_models::WhiteSpace>().prop_map(Self::WhiteSpace),
             // TextDecoration
@@ -420,6 +494,8 @@ impl Arbitrary for OverrideItem {
             any::<Number>().prop_map(Self::MaxHeight),
             // Flex
             any::<Flex>().prop_map(Self::Flex),
+            // Order
+            any::<i32>().prop_map(Self::Order),
             // ColumnGap
             any::<Number>().prop_map(Self::ColumnGap),
             // RowGap
@@ -442,6 +518,8 @@ impl Arbitrary for OverrideItem {
             any::<hyperchad_transformer_models::Cursor>().prop_map(Self::Cursor),
             // UserSelect
             any::<hyperchad_transformer_models::UserSelect>().prop_map(Self::UserSelect),
+            // PointerEvents
+            any::<hyperchad_transformer_models::PointerEvents>().prop_map(Self::PointerEvents),
             // OverflowWrap
             any::<hyperchad_transformer_models::OverflowWrap>().prop_map(Self::OverflowWrap),
             // TextOverflow
@@ -490,6 +568,8 @@ impl Arbitrary for OverrideItem {
             any::<bool>().prop_map(Self::Hidden),
             // Visibility
             any::<hyperchad_transformer_models::Visibility>().prop_map(Self::Visibility),
+            // Display
+            any::<hyperchad_transformer_models::Display>().prop_map(Self::Display),
         ]
         .boxed()
     }
@@ -581,11 +661,13 @@ prop_compose! {
         grid_cell_size in any::<Option<Number>>(),
         justify_content in any::<Option<hyperchad_transformer_models::JustifyContent>>(),
         align_items in any::<Option<hyperchad_transformer_models::AlignItems>>(),
+        flex_wrap in any::<Option<hyperchad_transformer_models::FlexWrap>>(),
         text_align in any::<Option<hyperchad_transformer_models::TextAlign>>(),
+        text_direction in any::<Option<hyperchad_transformer_models::TextDirection>>(),
         white_space in any::<Option<hyperchad_transformer_models::WhiteSpace>>(),
         text_decoration in any::<Option<TextDecoration>>(),
-    ) -> (Element, hyperchad_transformer_models::LayoutDirection, hyperchad_transformer_models::LayoutOverflow, hyperchad_transformer_models::LayoutOverflow, Option<Number>, Option<hyperchad_transformer_models::JustifyContent>, Option<hyperchad_transformer_models::AlignItems>, Option<hyperchad_transformer_models::TextAlign>, Option<hyperchad_transformer_models::WhiteSpace>, Option<TextDecoration>) {
-        (element, direction, overflow_x, overflow_y, grid_cell_size, justify_content, align_items, text_align, white_space, text_decoration)
+    ) -> (Element, hyperchad_transformer_models::LayoutDirection, hyperchad_transformer_models::LayoutOverflow, hyperchad_transformer_models::LayoutOverflow, Option<Number>, Option<hyperchad_transformer_models::JustifyContent>, Option<hyperchad_transformer_models::AlignItems>, Option<hyperchad_transformer_models::FlexWrap>, Option<hyperchad_transformer_models::TextAlign>, Option<hyperchad_transformer_models::TextDirection>, Option<hyperchad_transformer_models::WhiteSpace>, Option<TextDecoration>) {
+        (element, direction, overflow_x, overflow_y, grid_cell_size, justify_content, align_items, flex_wrap, text_align, text_direction, white_space, text_decoration)
     }
 }
 
@@ -599,11 +681,12 @@ prop_compose! {
         min_height in any::<Option<Number>>(),
         max_height in any::<Option<Number>>(),
         flex in any::<Option<Flex>>(),
+        order in any::<Option<i32>>(),
         column_gap in any::<Option<Number>>(),
         row_gap in any::<Option<Number>>(),
         opacity in any::<Option<Number>>(),
-    ) -> (Option<Number>, Option<Number>, Option<Number>, Option<Number>, Option<Number>, Option<Number>, Option<Flex>, Option<Number>, Option<Number>, Option<Number>) {
-        (width, min_width, max_width, height, min_height, max_height, flex, column_gap, row_gap, opacity)
+    ) -> (Option<Number>, Option<Number>, Option<Number>, Option<Number>, Option<Number>, Option<Number>, Option<Flex>, Option<i32>, Option<Number>, Option<Number>, Option<Number>) {
+        (width, min_width, max_width, height, min_height, max_height, flex, order, column_gap, row_gap, opacity)
     }
 }
 
@@ -668,11 +751,16 @@ prop_compose! {
         hidden in any::<Option<bool>>(),
         debug in any::<Option<bool>>(),
         visibility in any::<Option<hyperchad_transformer_models::Visibility>>(),
+        display in any::<Option<hyperchad_transformer_models::Display>>(),
+        pointer_events in any::<Option<hyperchad_transformer_models::PointerEvents>>(),
+        transition in any::<Option<Vec<Transition>>>(),
+        text_shadow in any::<Option<Vec<TextShadow>>>(),
+        will_change in prop::option::of(prop::collection::vec(prop::sample::select(crate::WILL_CHANGE_PROPERTIES).prop_map(str::to_string), 1..3)),
         route in any::<Option<hyperchad_transformer_models::Route>>(),
         actions in prop::collection::vec(any::<hyperchad_actions::Action>(), 0..2),
         overrides in prop::collection::vec(any::<ConfigOverride>(), 0..2),
-    ) -> (Option<serde_json::Value>, Option<bool>, Option<bool>, Option<hyperchad_transformer_models::Visibility>, Option<hyperchad_transformer_models::Route>, Vec<hyperchad_actions::Action>, Vec<ConfigOverride>) {
-        (state.map(|x| x.0), hidden, debug, visibility, route, actions, overrides)
+    ) -> (Option<serde_json::Value>, Option<bool>, Option<bool>, Option<hyperchad_transformer_models::Visibility>, Option<hyperchad_transformer_models::Display>, Option<hyperchad_transformer_models::PointerEvents>, Option<Vec<Transition>>, Option<Vec<TextShadow>>, Option<Vec<String>>, Option<hyperchad_transformer_models::Route>, Vec<hyperchad_actions::Action>, Vec<ConfigOverride>) {
+        (state.map(|x| x.0), hidden, debug, visibility, display, pointer_events, transition, text_shadow, will_change, route, actions, overrides)
     }
 }
 
@@ -699,7 +787,9 @@ fn container_fields_strategy() -> BoxedStrategy<Container> {
                 grid_cell_size,
                 justify_content,
                 align_items,
+                flex_wrap,
                 text_align,
+                text_direction,
                 white_space,
                 text_decoration,
             ) = g2;
@@ -711,6 +801,7 @@ fn container_fields_strategy() -> BoxedStrategy<Container> {
                 min_height,
                 max_height,
                 flex,
+                order,
                 column_gap,
                 row_gap,
                 opacity,
@@ -751,7 +842,20 @@ fn container_fields_strategy() -> BoxedStrategy<Container> {
                 font_size,
                 color,
             ) = g6;
-            let (state, hidden, debug, visibility, route, actions, overrides) = g7;
+            let (
+                state,
+                hidden,
+                debug,
+                visibility,
+                display,
+                pointer_events,
+                transition,
+                text_shadow,
+                will_change,
+                route,
+                actions,
+                overrides,
+            ) = g7;
 
             Container {
                 id,
@@ -768,9 +872,12 @@ fn container_fields_strategy() -> BoxedStrategy<Container> {
                 grid_cell_size,
                 justify_content,
                 align_items,
+                flex_wrap,
                 text_align,
+                text_direction,
                 white_space,
                 text_decoration,
+                text_shadow,
                 width,
                 min_width,
                 max_width,
@@ -778,6 +885,7 @@ fn container_fields_strategy() -> BoxedStrategy<Container> {
                 min_height,
                 max_height,
                 flex,
+                order,
                 column_gap,
                 row_gap,
                 opacity,
@@ -789,6 +897,9 @@ fn container_fields_strategy() -> BoxedStrategy<Container> {
                 translate_y,
                 cursor,
                 user_select,
+                pointer_events,
+                transition,
+                will_change,
                 overflow_wrap,
                 text_overflow,
                 position,
@@ -815,9 +926,11 @@ fn container_fields_strategy() -> BoxedStrategy<Container> {
                 hidden,
                 debug,
                 visibility,
+                display,
                 route,
                 actions,
                 overrides: deduplicate_overrides(overrides),
+                breakpoints: vec![],
                 #[cfg(feature = "layout")]
                 calculated_margin_left: None,
                 #[cfg(feature = "layout")]
@@ -8,9 +8,10 @@ use std::{borrow::Cow, collections::BTreeMap, iter::once};
 use hyperchad_actions::{Action, ActionEffect, ActionTrigger, ActionType};
 use hyperchad_color::{Color, ParseHexError};
 use hyperchad_transformer_models::{
-    AlignItems, Cursor, FontWeight, ImageFit, ImageLoading, JustifyContent, LayoutDirection,
-    LayoutOverflow, LinkTarget, OverflowWrap, Position, Route, Selector, SwapStrategy, TextAlign,
-    TextDecorationLine, TextDecorationStyle, TextOverflow, UserSelect, Visibility, WhiteSpace,
+    AlignItems, Cursor, Display, FlexWrap, FontWeight, ImageDecoding, ImageFit, ImageLoading,
+    JustifyContent, LayoutDirection, LayoutOverflow, LinkTarget, OverflowWrap, PointerEvents,
+    Position, Route, Selector, SwapStrategy, TextAlign, TextDecorationLine, TextDecorationStyle,
+    TextDirection, TextOverflow, UserSelect, Visibility, WhiteSpace,
 };
 use serde::Deserialize;
 use serde_json::Value;
@@ -19,7 +20,8 @@ pub use tl::ParseError;
 use tl::{Children, HTMLTag, Node, NodeHandle, Parser, ParserOptions};
 
 use crate::{
-    ConfigOverride, Flex, Number, OverrideItem, TextDecoration,
+    ConfigOverride, Flex, Number, OverrideItem, TextDecoration, TextShadow, TimingFunction,
+    Transition,
     parse::{GetNumberError, parse_number},
 };
 
@@ -68,7 +70,7 @@ impl<'a> TryFrom<&'a str> for crate::Container {
         let result = tl::parse(value, ParserOptions::new())?;
 
         Ok(Self {
-            children: parse_children(result.children(), result.parser()),
+            children: parse_children(result.children(), result.parser(), TextDirection::default()),
             overflow_x: LayoutOverflow::Squash,
             overflow_y: LayoutOverflow::Squash,
             ..Default::default()
@@ -76,21 +78,28 @@ impl<'a> TryFrom<&'a str> for crate::Container {
     }
 }
 
+/// Parses the children of an element whose text direction, explicit or inherited, is
+/// `direction`.
 fn parse_top_children(
     children: Option<Children<'_, '_>>,
     parser: &Parser<'_>,
+    direction: TextDirection,
 ) -> Vec<crate::Container> {
     children.map_or_else(Vec::new, |children| {
-        parse_children(&children.top().to_vec(), parser)
+        parse_children(&children.top().to_vec(), parser, direction)
     })
 }
 
-fn parse_children(children: &[NodeHandle], parser: &Parser<'_>) -> Vec<crate::Container> {
+fn parse_children(
+    children: &[NodeHandle],
+    parser: &Parser<'_>,
+    direction: TextDirection,
+) -> Vec<crate::Container> {
     let mut elements = vec![];
 
     for node in children {
         if let Some(node) = node.get(parser)
-            && let Some(element) = parse_child(node, parser)
+            && let Some(element) = parse_child(node, parser, direction)
         {
             elements.push(element);
         }
@@ -186,6 +195,27 @@ fn parse_visibility(value: &str) -> Result<Visibility, ParseAttrError> {
     })
 }
 
+fn parse_display(value: &str) -> Result<Display, ParseAttrError> {
+    Ok(match value {
+        "block" => Display::Block,
+        "inline" => Display::Inline,
+        "inline-block" => Display::InlineBlock,
+        "flex" => Display::Flex,
+        "grid" => Display::Grid,
+        "none" => Display::None,
+        value => {
+            return Err(ParseAttrError::InvalidValue(value.to_string()));
+        }
+    })
+}
+
+fn parse_order(value: &str) -> Result<i32, ParseAttrError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| ParseAttrError::InvalidValue(value.to_string()))
+}
+
 fn parse_target(value: &str) -> Result<Selector, ParseAttrError> {
     Ok(match value {
         "this" => Selector::SelfTarget,
@@ -400,6 +430,17 @@ fn parse_align_items(value: &str) -> Result<AlignItems, ParseAttrError> {
     })
 }
 
+fn parse_flex_wrap(value: &str) -> Result<FlexWrap, ParseAttrError> {
+    Ok(match value {
+        "nowrap" | "no-wrap" => FlexWrap::NoWrap,
+        "wrap" => FlexWrap::Wrap,
+        "wrap-reverse" => FlexWrap::WrapReverse,
+        value => {
+            return Err(ParseAttrError::InvalidValue(value.to_string()));
+        }
+    })
+}
+
 fn parse_text_align(value: &str) -> Result<TextAlign, ParseAttrError> {
     Ok(match value {
         "start" => TextAlign::Start,
@@ -412,6 +453,16 @@ fn parse_text_align(value: &str) -> Result<TextAlign, ParseAttrError> {
     })
 }
 
+fn parse_text_direction(value: &str) -> Result<TextDirection, ParseAttrError> {
+    Ok(match value {
+        "ltr" => TextDirection::Ltr,
+        "rtl" => TextDirection::Rtl,
+        value => {
+            return Err(ParseAttrError::InvalidValue(value.to_string()));
+        }
+    })
+}
+
 fn parse_white_space(value: &str) -> Result<WhiteSpace, ParseAttrError> {
     Ok(match value {
         "normal" => WhiteSpace::Normal,
@@ -435,6 +486,156 @@ fn parse_user_select(value: &str) -> Result<UserSelect, ParseAttrError> {
     })
 }
 
+fn parse_pointer_events(value: &str) -> Result<PointerEvents, ParseAttrError> {
+    Ok(match value {
+        "auto" => PointerEvents::Auto,
+        "none" => PointerEvents::None,
+        _ => {
+            return Err(ParseAttrError::InvalidValue(value.to_string()));
+        }
+    })
+}
+
+fn parse_timing_function(value: &str) -> Result<TimingFunction, ParseAttrError> {
+    Ok(match value {
+        "ease" => TimingFunction::Ease,
+        "linear" => TimingFunction::Linear,
+        "ease-in" => TimingFunction::EaseIn,
+        "ease-out" => TimingFunction::EaseOut,
+        "ease-in-out" => TimingFunction::EaseInOut,
+        _ => {
+            let points = value
+                .strip_prefix("cubic-bezier(")
+                .and_then(|x| x.strip_suffix(')'))
+                .ok_or_else(|| ParseAttrError::InvalidValue(value.to_string()))?
+                .split(',')
+                .map(|x| x.trim().parse::<f32>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| ParseAttrError::InvalidValue(value.to_string()))?;
+
+            let [x1, y1, x2, y2] = points[..] else {
+                return Err(ParseAttrError::InvalidValue(value.to_string()));
+            };
+
+            TimingFunction::CubicBezier(x1, y1, x2, y2)
+        }
+    })
+}
+
+fn parse_millis(value: &str) -> Result<Number, ParseAttrError> {
+    Ok(parse_number(value.strip_suffix("ms").unwrap_or(value))?)
+}
+
+fn parse_transition(value: &str) -> Result<Transition, ParseAttrError> {
+    let mut parts = value.split_whitespace();
+
+    let (Some(property), Some(duration)) = (parts.next(), parts.next()) else {
+        return Err(ParseAttrError::InvalidValue(value.to_string()));
+    };
+
+    let transition = Transition {
+        property: property.to_string(),
+        duration: parse_millis(duration)?,
+        timing: parts
+            .next()
+            .map(parse_timing_function)
+            .transpose()?
+            .unwrap_or_default(),
+        delay: parts
+            .next()
+            .map(parse_millis)
+            .transpose()?
+            .unwrap_or(Number::Integer(0)),
+    };
+
+    if parts.next().is_some() {
+        return Err(ParseAttrError::InvalidValue(value.to_string()));
+    }
+
+    Ok(transition)
+}
+
+/// Parses a comma-separated transition list, ignoring commas nested in parentheses
+/// (e.g. inside `cubic-bezier(...)`).
+fn parse_transitions(value: &str) -> Result<Vec<Transition>, ParseAttrError> {
+    let mut transitions = vec![];
+    let mut depth = 0_usize;
+    let mut start = 0;
+
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                transitions.push(parse_transition(value[start..i].trim())?);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    let last = value[start..].trim();
+    if !last.is_empty() {
+        transitions.push(parse_transition(last)?);
+    }
+
+    Ok(transitions)
+}
+
+/// Parses a text shadow of the form `x y [blur] color`, where `blur` defaults to `0`.
+fn parse_text_shadow(value: &str) -> Result<TextShadow, ParseAttrError> {
+    let parts = value.split_whitespace().collect::<Vec<_>>();
+
+    let (x, y, blur, color) = match parts[..] {
+        [x, y, color] => (x, y, None, color),
+        [x, y, blur, color] => (x, y, Some(blur), color),
+        _ => {
+            return Err(ParseAttrError::InvalidValue(value.to_string()));
+        }
+    };
+
+    Ok(TextShadow {
+        x: parse_number(x)?,
+        y: parse_number(y)?,
+        blur: blur
+            .map(parse_number)
+            .transpose()?
+            .unwrap_or(Number::Integer(0)),
+        color: Color::try_from_hex(color)?,
+    })
+}
+
+/// Parses a comma-separated text shadow list.
+fn parse_text_shadows(value: &str) -> Result<Vec<TextShadow>, ParseAttrError> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(parse_text_shadow)
+        .collect()
+}
+
+/// Parses a comma-separated `will-change` property list.
+///
+/// In debug builds, warns about values that aren't known animatable properties.
+fn parse_will_change(value: &str) -> Vec<String> {
+    let properties = value
+        .split(',')
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    #[cfg(debug_assertions)]
+    for property in &properties {
+        if !crate::WILL_CHANGE_PROPERTIES.contains(&property.as_str()) {
+            log::warn!("sx-will-change: unknown animatable property '{property}'");
+        }
+    }
+
+    properties
+}
+
 fn parse_overflow_wrap(value: &str) -> Result<OverflowWrap, ParseAttrError> {
     Ok(match value {
         "normal" => OverflowWrap::Normal,
@@ -684,6 +885,17 @@ fn parse_image_loading(value: &str) -> Result<ImageLoading, ParseAttrError> {
     })
 }
 
+fn parse_image_decoding(value: &str) -> Result<ImageDecoding, ParseAttrError> {
+    Ok(match value {
+        "auto" => ImageDecoding::Auto,
+        "sync" => ImageDecoding::Sync,
+        "async" => ImageDecoding::Async,
+        value => {
+            return Err(ParseAttrError::InvalidValue(value.to_string()));
+        }
+    })
+}
+
 fn parse_std_action(action: &str) -> Option<ActionEffect> {
     if let Ok(action) = serde_json::from_str::<ActionEffect>(action) {
         return Some(action);
@@ -922,11 +1134,17 @@ macro_rules! iter_once {
     ($val:expr) => {{ |x| std::iter::once($val(x)) }};
 }
 
+/// Parses an element whose parent has the text direction `inherited_direction`.
+///
+/// Logical inline sides (e.g. `sx-margin-inline-start`) resolve against the element's own
+/// `sx-text-direction`, or the nearest ancestor's one if it has none, like CSS's inherited
+/// `direction`.
 #[allow(clippy::too_many_lines)]
 fn parse_element(
     tag: &HTMLTag<'_>,
     node: &Node<'_>,
     parser: &Parser<'_>,
+    inherited_direction: TextDirection,
 ) -> Result<crate::Container, ParseAttrWrapperError> {
     static CURRENT_ID: std::sync::LazyLock<std::sync::Arc<std::sync::atomic::AtomicUsize>> =
         std::sync::LazyLock::new(|| std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(1)));
@@ -1028,6 +1246,16 @@ fn parse_element(
     .or_else(|| border_radius_right.clone())
     .or_else(|| border_radius.clone());
 
+    let text_direction = pmrv(
+        tag,
+        once("sx-text-direction"),
+        &mut overrides,
+        parse_text_direction,
+        iter_once!(OverrideItem::TextDirection),
+    )?;
+    let direction = text_direction.unwrap_or(inherited_direction);
+    let inline_start_left = direction.is_start_left();
+
     let margin = pmrv(tag, once("sx-margin"), &mut overrides, parse_number, |x| {
         std::iter::once(OverrideItem::MarginTop(x.clone())).chain(
             std::iter::once(OverrideItem::MarginRight(x.clone())).chain(
@@ -1038,7 +1266,7 @@ fn parse_element(
     })?;
     let margin_x = pmrv(
         tag,
-        once("sx-margin-x"),
+        ["sx-margin-x", "sx-margin-inline"].into_iter(),
         &mut overrides,
         parse_number,
         |x| {
@@ -1048,7 +1276,7 @@ fn parse_element(
     )?;
     let margin_y = pmrv(
         tag,
-        once("sx-margin-y"),
+        ["sx-margin-y", "sx-margin-block"].into_iter(),
         &mut overrides,
         parse_number,
         |x| {
@@ -1056,6 +1284,37 @@ fn parse_element(
                 .chain(std::iter::once(OverrideItem::MarginBottom(x)))
         },
     )?;
+    let margin_inline_start = pmrv(
+        tag,
+        once("sx-margin-inline-start"),
+        &mut overrides,
+        parse_number,
+        move |x| {
+            once(if inline_start_left {
+                OverrideItem::MarginLeft(x)
+            } else {
+                OverrideItem::MarginRight(x)
+            })
+        },
+    )?;
+    let margin_inline_end = pmrv(
+        tag,
+        once("sx-margin-inline-end"),
+        &mut overrides,
+        parse_number,
+        move |x| {
+            once(if inline_start_left {
+                OverrideItem::MarginRight(x)
+            } else {
+                OverrideItem::MarginLeft(x)
+            })
+        },
+    )?;
+    let (margin_inline_left, margin_inline_right) = if inline_start_left {
+        (margin_inline_start, margin_inline_end)
+    } else {
+        (margin_inline_end, margin_inline_start)
+    };
     let margin_left = pmrv(
         tag,
         once("sx-margin-left"),
@@ -1063,6 +1322,7 @@ fn parse_element(
         parse_number,
         iter_once!(OverrideItem::MarginLeft),
     )?
+    .or(margin_inline_left)
     .or_else(|| margin_x.clone().or_else(|| margin.clone()));
     let margin_right = pmrv(
         tag,
@@ -1071,10 +1331,11 @@ fn parse_element(
         parse_number,
         iter_once!(OverrideItem::MarginRight),
     )?
+    .or(margin_inline_right)
     .or_else(|| margin_x.clone().or_else(|| margin.clone()));
     let margin_top = pmrv(
         tag,
-        once("sx-margin-top"),
+        ["sx-margin-top", "sx-margin-block-start"].into_iter(),
         &mut overrides,
         parse_number,
         iter_once!(OverrideItem::MarginTop),
@@ -1082,7 +1343,7 @@ fn parse_element(
     .or_else(|| margin_y.clone().or_else(|| margin.clone()));
     let margin_bottom = pmrv(
         tag,
-        once("sx-margin-bottom"),
+        ["sx-margin-bottom", "sx-margin-block-end"].into_iter(),
         &mut overrides,
         parse_number,
         iter_once!(OverrideItem::MarginBottom),
@@ -1099,7 +1360,7 @@ fn parse_element(
     })?;
     let padding_x = pmrv(
         tag,
-        once("sx-padding-x"),
+        ["sx-padding-x", "sx-padding-inline"].into_iter(),
         &mut overrides,
         parse_number,
         |x| {
@@ -1109,7 +1370,7 @@ fn parse_element(
     )?;
     let padding_y = pmrv(
         tag,
-        once("sx-padding-y"),
+        ["sx-padding-y", "sx-padding-block"].into_iter(),
         &mut overrides,
         parse_number,
         |x| {
@@ -1117,6 +1378,37 @@ fn parse_element(
                 .chain(std::iter::once(OverrideItem::PaddingBottom(x)))
         },
     )?;
+    let padding_inline_start = pmrv(
+        tag,
+        once("sx-padding-inline-start"),
+        &mut overrides,
+        parse_number,
+        move |x| {
+            once(if inline_start_left {
+                OverrideItem::PaddingLeft(x)
+            } else {
+                OverrideItem::PaddingRight(x)
+            })
+        },
+    )?;
+    let padding_inline_end = pmrv(
+        tag,
+        once("sx-padding-inline-end"),
+        &mut overrides,
+        parse_number,
+        move |x| {
+            once(if inline_start_left {
+                OverrideItem::PaddingRight(x)
+            } else {
+                OverrideItem::PaddingLeft(x)
+            })
+        },
+    )?;
+    let (padding_inline_left, padding_inline_right) = if inline_start_left {
+        (padding_inline_start, padding_inline_end)
+    } else {
+        (padding_inline_end, padding_inline_start)
+    };
     let padding_left = pmrv(
         tag,
         once("sx-padding-left"),
@@ -1124,6 +1416,7 @@ fn parse_element(
         parse_number,
         iter_once!(OverrideItem::PaddingLeft),
     )?
+    .or(padding_inline_left)
     .or_else(|| padding_x.clone().or_else(|| padding.clone()));
     let padding_right = pmrv(
         tag,
@@ -1132,10 +1425,11 @@ fn parse_element(
         parse_number,
         iter_once!(OverrideItem::PaddingRight),
     )?
+    .or(padding_inline_right)
     .or_else(|| padding_x.clone().or_else(|| padding.clone()));
     let padding_top = pmrv(
         tag,
-        once("sx-padding-top"),
+        ["sx-padding-top", "sx-padding-block-start"].into_iter(),
         &mut overrides,
         parse_number,
         iter_once!(OverrideItem::PaddingTop),
@@ -1143,13 +1437,73 @@ fn parse_element(
     .or_else(|| padding_y.clone().or_else(|| padding.clone()));
     let padding_bottom = pmrv(
         tag,
-        once("sx-padding-bottom"),
+        ["sx-padding-bottom", "sx-padding-block-end"].into_iter(),
         &mut overrides,
         parse_number,
         iter_once!(OverrideItem::PaddingBottom),
     )?
     .or_else(|| padding_y.clone().or_else(|| padding.clone()));
 
+    let inset = pmrv(tag, once("sx-inset"), &mut overrides, parse_number, |x| {
+        std::iter::once(OverrideItem::Top(x.clone())).chain(
+            std::iter::once(OverrideItem::Right(x.clone())).chain(
+                std::iter::once(OverrideItem::Bottom(x.clone()))
+                    .chain(std::iter::once(OverrideItem::Left(x))),
+            ),
+        )
+    })?;
+    let inset_inline = pmrv(
+        tag,
+        once("sx-inset-inline"),
+        &mut overrides,
+        parse_number,
+        |x| {
+            std::iter::once(OverrideItem::Left(x.clone()))
+                .chain(std::iter::once(OverrideItem::Right(x)))
+        },
+    )?;
+    let inset_block = pmrv(
+        tag,
+        once("sx-inset-block"),
+        &mut overrides,
+        parse_number,
+        |x| {
+            std::iter::once(OverrideItem::Top(x.clone()))
+                .chain(std::iter::once(OverrideItem::Bottom(x)))
+        },
+    )?;
+    let inset_inline_start = pmrv(
+        tag,
+        once("sx-inset-inline-start"),
+        &mut overrides,
+        parse_number,
+        move |x| {
+            once(if inline_start_left {
+                OverrideItem::Left(x)
+            } else {
+                OverrideItem::Right(x)
+            })
+        },
+    )?;
+    let inset_inline_end = pmrv(
+        tag,
+        once("sx-inset-inline-end"),
+        &mut overrides,
+        parse_number,
+        move |x| {
+            once(if inline_start_left {
+                OverrideItem::Right(x)
+            } else {
+                OverrideItem::Left(x)
+            })
+        },
+    )?;
+    let (inset_inline_left, inset_inline_right) = if inline_start_left {
+        (inset_inline_start, inset_inline_end)
+    } else {
+        (inset_inline_end, inset_inline_start)
+    };
+
     let mut text_decoration = pmrv(
         tag,
         once("sx-text-decoration"),
@@ -1438,6 +1792,13 @@ fn parse_element(
             parse_visibility,
             iter_once!(OverrideItem::Visibility),
         )?,
+        display: pmrv(
+            tag,
+            once("sx-display"),
+            &mut overrides,
+            parse_display,
+            iter_once!(OverrideItem::Display),
+        )?,
         overflow_x: pmrv(
             tag,
             once("sx-overflow-x"),
@@ -1475,6 +1836,13 @@ fn parse_element(
             parse_align_items,
             iter_once!(OverrideItem::AlignItems),
         )?,
+        flex_wrap: pmrv(
+            tag,
+            once("sx-flex-wrap"),
+            &mut overrides,
+            parse_flex_wrap,
+            iter_once!(OverrideItem::FlexWrap),
+        )?,
         text_align: pmrv(
             tag,
             once("sx-text-align"),
@@ -1482,6 +1850,7 @@ fn parse_element(
             parse_text_align,
             iter_once!(OverrideItem::TextAlign),
         )?,
+        text_direction,
         white_space: pmrv(
             tag,
             once("sx-white-space"),
@@ -1512,7 +1881,7 @@ fn parse_element(
             parse_font_weight,
             iter_once!(OverrideItem::FontWeight),
         )?,
-        children: parse_top_children(node.children(), parser),
+        children: parse_top_children(node.children(), parser, direction),
         width: pmrv(
             tag,
             once("sx-width"),
@@ -1556,34 +1925,47 @@ fn parse_element(
             iter_once!(OverrideItem::MaxHeight),
         )?,
         flex,
+        order: pmrv(
+            tag,
+            once("sx-order"),
+            &mut overrides,
+            parse_order,
+            iter_once!(OverrideItem::Order),
+        )?,
         left: pmrv(
             tag,
             once("sx-left"),
             &mut overrides,
             parse_number,
             iter_once!(OverrideItem::Left),
-        )?,
+        )?
+        .or(inset_inline_left)
+        .or_else(|| inset_inline.clone().or_else(|| inset.clone())),
         right: pmrv(
             tag,
             once("sx-right"),
             &mut overrides,
             parse_number,
             iter_once!(OverrideItem::Right),
-        )?,
+        )?
+        .or(inset_inline_right)
+        .or_else(|| inset_inline.clone().or_else(|| inset.clone())),
         top: pmrv(
             tag,
-            once("sx-top"),
+            ["sx-top", "sx-inset-block-start"].into_iter(),
             &mut overrides,
             parse_number,
             iter_once!(OverrideItem::Top),
-        )?,
+        )?
+        .or_else(|| inset_block.clone().or_else(|| inset.clone())),
         bottom: pmrv(
             tag,
-            once("sx-bottom"),
+            ["sx-bottom", "sx-inset-block-end"].into_iter(),
             &mut overrides,
             parse_number,
             iter_once!(OverrideItem::Bottom),
-        )?,
+        )?
+        .or_else(|| inset_block.clone().or_else(|| inset.clone())),
         translate_x: pmrv(
             tag,
             once("sx-translate-x"),
@@ -1643,6 +2025,32 @@ fn parse_element(
             parse_user_select,
             iter_once!(OverrideItem::UserSelect),
         )?,
+        pointer_events: pmrv(
+            tag,
+            once("sx-pointer-events"),
+            &mut overrides,
+            parse_pointer_events,
+            iter_once!(OverrideItem::PointerEvents),
+        )?,
+        transition: get_tag_attr_value_decoded(tag, "sx-transition")
+            .as_deref()
+            .map(parse_transitions)
+            .transpose()
+            .map_err(|e| ParseAttrWrapperError::Parse {
+                name: "sx-transition".to_string(),
+                error: e,
+            })?,
+        will_change: get_tag_attr_value_decoded(tag, "sx-will-change")
+            .as_deref()
+            .map(parse_will_change),
+        text_shadow: get_tag_attr_value_decoded(tag, "sx-text-shadow")
+            .as_deref()
+            .map(parse_text_shadows)
+            .transpose()
+            .map_err(|e| ParseAttrWrapperError::Parse {
+                name: "sx-text-shadow".to_string(),
+                error: e,
+            })?,
         overflow_wrap: pmrv(
             tag,
             once("sx-overflow-wrap"),
@@ -1672,10 +2080,14 @@ fn parse_element(
 }
 
 #[allow(clippy::too_many_lines)]
-fn parse_child(node: &Node<'_>, parser: &Parser<'_>) -> Option<crate::Container> {
+fn parse_child(
+    node: &Node<'_>,
+    parser: &Parser<'_>,
+    direction: TextDirection,
+) -> Option<crate::Container> {
     match node {
         Node::Tag(tag) => {
-            let mut container = parse_element(tag, node, parser).unwrap();
+            let mut container = parse_element(tag, node, parser, direction).unwrap();
 
             match tag.name().as_utf8_str().to_lowercase().as_str() {
                 "input" => match get_tag_attr_value_lower(tag, "type").as_deref() {
@@ -1814,6 +2226,11 @@ fn parse_child(node: &Node<'_>, parser: &Parser<'_>) -> Option<crate::Container>
                             .map(parse_image_loading)
                             .transpose()
                             .unwrap(),
+                        decoding: get_tag_attr_value_decoded(tag, "decoding")
+                            .as_deref()
+                            .map(parse_image_decoding)
+                            .transpose()
+                            .unwrap(),
                         source_set: get_tag_attr_value_owned(tag, "srcset"),
                         sizes: get_tag_attr_value_decoded(tag, "sizes")
                             .as_deref()
@@ -1943,8 +2360,8 @@ mod test {
     use crate::{Container, Flex, Input, Number, TextDecoration};
     use hyperchad_color::Color;
     use hyperchad_transformer_models::{
-        FontWeight, ImageFit, ImageLoading, LayoutDirection, LinkTarget, Route, Selector,
-        SwapStrategy, TextDecorationLine, TextDecorationStyle,
+        FontWeight, ImageDecoding, ImageFit, ImageLoading, LayoutDirection, LinkTarget, Route,
+        Selector, SwapStrategy, TextDecorationLine, TextDecorationStyle, TextDirection,
     };
 
     /// Module for diff generation between Container values.
@@ -2004,9 +2421,12 @@ mod test {
             check_field!(grid_cell_size);
             check_field!(justify_content);
             check_field!(align_items);
+            check_field!(flex_wrap);
             check_field!(text_align);
+            check_field!(text_direction);
             check_field!(white_space);
             check_field!(text_decoration);
+            check_field!(text_shadow);
             check_field!(font_family);
             check_field!(font_weight);
             check_field!(width);
@@ -2016,6 +2436,7 @@ mod test {
             check_field!(min_height);
             check_field!(max_height);
             check_field!(flex);
+            check_field!(order);
             check_field!(column_gap);
             check_field!(row_gap);
             check_field!(opacity);
@@ -2027,6 +2448,9 @@ mod test {
             check_field!(translate_y);
             check_field!(cursor);
             check_field!(user_select);
+            check_field!(pointer_events);
+            check_field!(transition);
+            check_field!(will_change);
             check_field!(overflow_wrap);
             check_field!(text_overflow);
             check_field!(position);
@@ -2054,6 +2478,7 @@ mod test {
             check_field!(hidden);
             check_field!(debug);
             check_field!(visibility);
+            check_field!(display);
 
             // Actions - compare element by element for better diff reporting
             if left.actions.len() == right.actions.len() {
@@ -2346,6 +2771,56 @@ Line 3</textarea>"#;
         }
     }
 
+    #[test]
+    fn test_text_and_form_attribute_html_escaping() {
+        let hostile = r#"<script>alert("Tom & Jerry")</script>"#;
+        let form = Container {
+            element: crate::Element::Form {
+                action: Some(hostile.to_string()),
+                method: None,
+            },
+            children: vec![Container {
+                element: crate::Element::Text {
+                    value: hostile.to_string(),
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let html = form
+            .display_to_string(
+                false,
+                false,
+                #[cfg(feature = "format")]
+                false,
+                #[cfg(feature = "syntax-highlighting")]
+                false,
+            )
+            .unwrap();
+        assert!(!html.contains("<script"), "{html}");
+        assert!(
+            html.contains(
+                r#"action="&lt;script&gt;alert(&quot;Tom &amp; Jerry&quot;)&lt;/script&gt;""#
+            ),
+            "{html}"
+        );
+        assert!(
+            html.contains(r#">&lt;script&gt;alert("Tom &amp; Jerry")&lt;/script&gt;</form>"#),
+            "{html}"
+        );
+
+        let parsed: Container = html.as_str().try_into().unwrap();
+        let child = &parsed.children[0];
+        assert_eq!(
+            child.element,
+            crate::Element::Form {
+                action: Some(hostile.to_string()),
+                method: None,
+            }
+        );
+    }
+
     #[test_log::test]
     fn parse_text_decoration_parses_full_shorthand_with_multiple_lines_style_color_and_thickness() {
         let html = r#"<div sx-text-decoration="underline overline solid #ff0000 2">text</div>"#;
@@ -2380,6 +2855,144 @@ Line 3</textarea>"#;
         );
     }
 
+    #[test_log::test]
+    fn parse_transition_parses_multiple_transitions_with_cubic_bezier() {
+        let html = r#"<div sx-transition="opacity 300ms ease-in-out 0ms, transform 150ms cubic-bezier(0.25, 0.1, 0.25, 1) 50ms">text</div>"#;
+        let container: Container = html.try_into().unwrap();
+        let child = &container.children[0];
+
+        assert_eq!(
+            child.transition,
+            Some(vec![
+                crate::Transition {
+                    property: "opacity".to_string(),
+                    duration: Number::Integer(300),
+                    timing: crate::TimingFunction::EaseInOut,
+                    delay: Number::Integer(0),
+                },
+                crate::Transition {
+                    property: "transform".to_string(),
+                    duration: Number::Integer(150),
+                    timing: crate::TimingFunction::CubicBezier(0.25, 0.1, 0.25, 1.0),
+                    delay: Number::Integer(50),
+                },
+            ])
+        );
+    }
+
+    #[test_log::test]
+    fn parse_transition_defaults_timing_and_delay() {
+        let html = r#"<div sx-transition="all 200ms">text</div>"#;
+        let container: Container = html.try_into().unwrap();
+        let child = &container.children[0];
+
+        assert_eq!(
+            child.transition,
+            Some(vec![crate::Transition {
+                property: "all".to_string(),
+                duration: Number::Integer(200),
+                timing: crate::TimingFunction::Ease,
+                delay: Number::Integer(0),
+            }])
+        );
+    }
+
+    #[test_log::test]
+    fn parse_text_shadow_parses_single_shadow() {
+        let html = r##"<div sx-text-shadow="1 2 4 #000000">text</div>"##;
+        let container: Container = html.try_into().unwrap();
+        let child = &container.children[0];
+
+        assert_eq!(
+            child.text_shadow,
+            Some(vec![crate::TextShadow {
+                x: Number::Integer(1),
+                y: Number::Integer(2),
+                blur: Number::Integer(4),
+                color: Color::from_hex("#000000"),
+            }])
+        );
+    }
+
+    #[test_log::test]
+    fn parse_text_shadow_parses_multiple_shadows_in_order() {
+        let html = r##"<div sx-text-shadow="0 1 2 #000000, -1 0 #FF0000">text</div>"##;
+        let container: Container = html.try_into().unwrap();
+        let child = &container.children[0];
+
+        assert_eq!(
+            child.text_shadow,
+            Some(vec![
+                crate::TextShadow {
+                    x: Number::Integer(0),
+                    y: Number::Integer(1),
+                    blur: Number::Integer(2),
+                    color: Color::from_hex("#000000"),
+                },
+                crate::TextShadow {
+                    x: Number::Integer(-1),
+                    y: Number::Integer(0),
+                    blur: Number::Integer(0),
+                    color: Color::from_hex("#FF0000"),
+                },
+            ])
+        );
+    }
+
+    #[test_log::test]
+    fn parse_text_shadow_rejects_missing_color() {
+        assert!(super::parse_text_shadows("1 2").is_err());
+        assert!(super::parse_text_shadows("1 2 4 #000000, 1 2").is_err());
+    }
+
+    #[test_log::test]
+    fn parse_will_change_parses_multiple_properties() {
+        let html = r#"<div sx-will-change="transform, opacity">text</div>"#;
+        let container: Container = html.try_into().unwrap();
+        let child = &container.children[0];
+
+        assert_eq!(
+            child.will_change,
+            Some(vec!["transform".to_string(), "opacity".to_string()])
+        );
+        assert!(child.unknown_will_change_properties().is_empty());
+    }
+
+    #[test_log::test]
+    fn parse_will_change_keeps_unknown_properties() {
+        let html = r#"<div sx-will-change="transform, not-a-property">text</div>"#;
+        let container: Container = html.try_into().unwrap();
+        let child = &container.children[0];
+
+        assert_eq!(
+            child.will_change,
+            Some(vec!["transform".to_string(), "not-a-property".to_string()])
+        );
+        assert_eq!(
+            child.unknown_will_change_properties(),
+            vec!["not-a-property"]
+        );
+    }
+
+    #[test_log::test]
+    fn parse_display_none() {
+        let html = r#"<div sx-display="none">text</div>"#;
+        let container: Container = html.try_into().unwrap();
+        let child = &container.children[0];
+
+        assert_eq!(child.display, Some(Display::None));
+        assert!(child.is_hidden());
+    }
+
+    #[test_log::test]
+    fn parse_order() {
+        let html = r#"<div sx-order="-1">a</div><div>b</div>"#;
+        let container: Container = html.try_into().unwrap();
+
+        assert_eq!(container.children[0].order, Some(-1));
+        assert_eq!(container.children[1].order, None);
+    }
+
     #[test_log::test]
     fn parse_flex_with_single_value_sets_grow_only() {
         let html = r#"<div sx-flex="2">content</div>"#;
@@ -2703,6 +3316,19 @@ Line 3</textarea>"#;
         }
     }
 
+    #[test_log::test]
+    fn parse_image_with_decoding_attribute() {
+        let html = r#"<img src="/test.jpg" decoding="async">"#;
+        let container: Container = html.try_into().unwrap();
+        let child = &container.children[0];
+
+        if let crate::Element::Image { decoding, .. } = &child.element {
+            assert_eq!(*decoding, Some(ImageDecoding::Async));
+        } else {
+            panic!("Expected Image element");
+        }
+    }
+
     #[test_log::test]
     fn parse_table_row_sets_row_direction() {
         let html = r"<table><tr><td>Cell</td></tr></table>";
@@ -2740,4 +3366,77 @@ Line 3</textarea>"#;
             panic!("Expected Anchor element");
         }
     }
+
+    #[test_log::test]
+    fn parse_inset_expands_to_all_offsets() {
+        let html = r#"<div sx-inset="0" sx-inset-block="10"></div>"#;
+        let container: Container = html.try_into().unwrap();
+        let child = &container.children[0];
+
+        assert_eq!(child.top, Some(Number::Integer(10)));
+        assert_eq!(child.bottom, Some(Number::Integer(10)));
+        assert_eq!(child.left, Some(Number::Integer(0)));
+        assert_eq!(child.right, Some(Number::Integer(0)));
+    }
+
+    #[test_log::test]
+    fn parse_logical_spacing_maps_to_physical_sides_left_to_right() {
+        let html = r#"<div sx-margin-inline-start="1" sx-padding-inline-end="2" sx-padding-block="3" sx-inset-inline-start="4"></div>"#;
+        let container: Container = html.try_into().unwrap();
+        let child = &container.children[0];
+
+        assert_eq!(child.text_direction, None);
+        assert_eq!(child.margin_left, Some(Number::Integer(1)));
+        assert_eq!(child.margin_right, None);
+        assert_eq!(child.padding_right, Some(Number::Integer(2)));
+        assert_eq!(child.padding_left, None);
+        assert_eq!(child.padding_top, Some(Number::Integer(3)));
+        assert_eq!(child.padding_bottom, Some(Number::Integer(3)));
+        assert_eq!(child.left, Some(Number::Integer(4)));
+        assert_eq!(child.right, None);
+    }
+
+    #[test_log::test]
+    fn parse_logical_spacing_maps_to_physical_sides_right_to_left() {
+        let html = r#"<div sx-text-direction="rtl" sx-margin-inline-start="1" sx-margin-inline-end="2" sx-padding-inline-start="3" sx-inset-inline-end="4"></div>"#;
+        let container: Container = html.try_into().unwrap();
+        let child = &container.children[0];
+
+        assert_eq!(child.text_direction, Some(TextDirection::Rtl));
+        assert_eq!(child.margin_right, Some(Number::Integer(1)));
+        assert_eq!(child.margin_left, Some(Number::Integer(2)));
+        assert_eq!(child.padding_right, Some(Number::Integer(3)));
+        assert_eq!(child.padding_left, None);
+        assert_eq!(child.left, Some(Number::Integer(4)));
+        assert_eq!(child.right, None);
+    }
+
+    #[test_log::test]
+    fn parse_logical_spacing_inherits_text_direction_from_ancestors() {
+        let html = r#"<div sx-text-direction="rtl"><section><div sx-margin-inline-start="1" sx-padding-inline-end="2"></div></section><div sx-text-direction="ltr"><div sx-margin-inline-start="3"></div></div></div>"#;
+        let container: Container = html.try_into().unwrap();
+        let rtl = &container.children[0];
+
+        let nested = &rtl.children[0].children[0];
+        assert_eq!(nested.text_direction, None);
+        assert_eq!(nested.margin_right, Some(Number::Integer(1)));
+        assert_eq!(nested.margin_left, None);
+        assert_eq!(nested.padding_left, Some(Number::Integer(2)));
+        assert_eq!(nested.padding_right, None);
+
+        let ltr = &rtl.children[1].children[0];
+        assert_eq!(ltr.margin_left, Some(Number::Integer(3)));
+        assert_eq!(ltr.margin_right, None);
+    }
+
+    #[test_log::test]
+    fn parse_physical_side_takes_precedence_over_logical_side() {
+        let html =
+            r#"<div sx-margin-left="5" sx-margin-inline-start="1" sx-margin-inline="2"></div>"#;
+        let container: Container = html.try_into().unwrap();
+        let child = &container.children[0];
+
+        assert_eq!(child.margin_left, Some(Number::Integer(5)));
+        assert_eq!(child.margin_right, Some(Number::Integer(2)));
+    }
 }
@@ -7,7 +7,7 @@
 use bumpalo::Bump;
 use paste::paste;
 
-use crate::Container;
+use crate::{Container, render_context::RenderContext};
 
 use super::{Calc, font::FontMetrics};
 
@@ -62,6 +62,7 @@ pub struct CalculatorDefaults {
 pub struct Calculator<F: FontMetrics> {
     font_metrics: F,
     defaults: CalculatorDefaults,
+    render_context: Option<RenderContext>,
 }
 
 impl<F: FontMetrics> Calculator<F> {
@@ -71,8 +72,30 @@ impl<F: FontMetrics> Calculator<F> {
         Self {
             font_metrics,
             defaults,
+            render_context: None,
         }
     }
+
+    /// Lays out containers in `render_context` instead of the size already calculated for
+    /// the root container.
+    ///
+    /// The root container is sized to the context's viewport, and its root font size
+    /// replaces [`CalculatorDefaults::font_size`]. Use this for headless rendering, where
+    /// there is no window to size the root container.
+    #[must_use]
+    pub const fn with_render_context(mut self, render_context: RenderContext) -> Self {
+        self.render_context = Some(render_context);
+        self
+    }
+
+    /// The defaults to lay out with, taking the root font size of the render context.
+    const fn effective_defaults(&self) -> CalculatorDefaults {
+        let mut defaults = self.defaults;
+        if let Some(render_context) = &self.render_context {
+            defaults.font_size = render_context.root_font_size;
+        }
+        defaults
+    }
 }
 
 #[cfg(feature = "benchmark")]
@@ -109,6 +132,11 @@ impl<F: FontMetrics> Calc for Calculator<F> {
     fn calc(&self, container: &mut Container) -> bool {
         log::trace!("calc: container={container}");
 
+        if let Some(render_context) = &self.render_context {
+            container.calculated_width = Some(render_context.view_width);
+            container.calculated_height = Some(render_context.view_height);
+        }
+
         time!("calc", {
             let arena = time!("arena", Bump::new());
             let context = arena.alloc(Container::default());
@@ -155,7 +183,7 @@ macro_rules! calc_size_on_axis {
         log::trace!("{LABEL}:\n{}", $container);
 
         let root_id = $container.id;
-        let defaults = $self.defaults;
+        let defaults = $self.effective_defaults();
         let view_width = $container.calculated_width.expect("Missing view_width");
         let view_height = $container.calculated_height.expect("Missing view_height");
 
@@ -197,7 +225,7 @@ macro_rules! calc_size_on_axis {
                 }
 
                 let direction = parent.direction;
-                let overflow = paste!(parent.[<overflow_ $unit>]);
+                let overflow = paste!(parent.[<layout_overflow_ $unit>]());
 
                 for child in &mut parent.children {
                     log::trace!("{LABEL}: container:\n{child}");
@@ -598,6 +626,7 @@ macro_rules! flex_on_axis {
                                             for size in parent
                                                 .relative_positioned_elements()
                                                 .filter(|x| x.$size.is_none() && x.is_expandable(parent))
+                                                .filter(|x| paste!(x.[<can_grow_ $size>]()))
                                                 .filter_map(|x| paste!(x.[<calculated_ $size>]))
                                             {
                                                 if smallest > size {
@@ -611,7 +640,11 @@ macro_rules! flex_on_axis {
                                                 }
                                             }
 
-                                            moosicbox_assert::assert!(smallest_count > 0, "expected at least one smallest item");
+                                            if smallest_count == 0 {
+                                                log::trace!("{LABEL}: all expandable children reached their max size");
+                                                break;
+                                            }
+
                                             moosicbox_assert::assert!(smallest.is_finite(), "expected smallest to be finite");
 
                                             let smallest_countf = f32::from(smallest_count);
@@ -645,10 +678,12 @@ macro_rules! flex_on_axis {
                                             moosicbox_assert::assert!(target.is_finite(), "expected target to be finite");
 
                                             let mut dynamic_child_size = false;
+                                            let mut reached_max = false;
 
                                             for child in parent
                                                 .relative_positioned_elements_mut()
                                                 .filter(|x| x.$size.is_none())
+                                                .filter(|x| paste!(x.[<can_grow_ $size>]()))
                                                 .filter(|x| paste!(x.[<calculated_ $size>]).is_some_and(|x| float_eq!(x, smallest)))
                                             {
                                                 let mut clipped = false;
@@ -676,6 +711,18 @@ macro_rules! flex_on_axis {
                                                     }
                                                 }
 
+                                                if let Some(max) = paste!(child.[<calculated_max_ $size>]) {
+                                                    log::trace!("{LABEL}: calculated_max={max}");
+                                                    let max = max - paste!(child.[<padding_ $unit>]()).unwrap_or_default() - paste!(child.[<margin_ $unit>]()).unwrap_or_default();
+                                                    log::trace!("{LABEL}: calculated_max={max} without padding/margins");
+                                                    if target > max {
+                                                        remaining_size += target - max;
+                                                        target = max;
+                                                        clipped = true;
+                                                        reached_max = true;
+                                                    }
+                                                }
+
                                                 if !clipped {
                                                     dynamic_child_size = true;
                                                 }
@@ -685,6 +732,12 @@ macro_rules! flex_on_axis {
                                                 log::trace!("{LABEL}: increasing child size prev={prev} to target={target}:\n{child}");
                                             }
 
+                                            // Hand the size that was clipped off by max sizes to
+                                            // the children that can still grow
+                                            if reached_max && float_gt!(remaining_size, 0.0) {
+                                                continue;
+                                            }
+
                                             if last_iteration || !dynamic_child_size {
                                                 break;
                                             }
@@ -859,7 +912,7 @@ macro_rules! wrap_on_axis {
                     $each_child(child, container_width, view_width, view_height);
                 }
 
-                if !matches!(parent.$overflow, LayoutOverflow::Wrap { .. }) {
+                if !matches!(parent.$overflow(), LayoutOverflow::Wrap { .. }) {
                     return;
                 }
 
@@ -871,7 +924,7 @@ macro_rules! wrap_on_axis {
                 let mut col = 0;
                 let gap = paste!(parent.[<calculated_ $gap>]);
 
-                for child in parent.relative_positioned_elements_mut() {
+                for child in parent.relative_positioned_elements_by_order_mut() {
                     let child_size = paste!(child.[<bounding_calculated_ $size>]()).expect("Missing child calculated bounding size");
 
                     let mut position = LayoutPosition::Wrap { row, col };
@@ -1208,7 +1261,14 @@ mod pass_wrap_horizontal {
                 };
 
             wrap_on_axis!(
-                "wrap", Row, bfs, container, width, overflow_x, column_gap, each_child,
+                "wrap",
+                Row,
+                bfs,
+                container,
+                width,
+                layout_overflow_x,
+                column_gap,
+                each_child,
             );
         }
     }
@@ -1277,8 +1337,8 @@ mod pass_flex_height {
 mod pass_positioning {
     use bumpalo::Bump;
     use hyperchad_transformer_models::{
-        AlignItems, JustifyContent, LayoutDirection, LayoutOverflow, LayoutPosition, Position,
-        TextAlign, Visibility,
+        AlignItems, FlexWrap, JustifyContent, LayoutDirection, LayoutOverflow, LayoutPosition,
+        Position, TextAlign, Visibility,
     };
 
     use crate::{
@@ -1354,18 +1414,21 @@ mod pass_positioning {
                         .calculated_height
                         .expect("Missing parent calculated_height");
 
-                    if let LayoutOverflow::Wrap { grid } = parent.overflow_x {
+                    if let LayoutOverflow::Wrap { grid } = parent.layout_overflow_x() {
                         let mut last_row = 0;
                         let mut col_count = 0;
                         let mut max_col_count = 0;
                         let mut row_width = 0.0;
+                        let mut row_height = 0.0;
                         let gaps = &mut bumpalo::collections::Vec::new_in(arena);
+                        let line_heights = &mut bumpalo::collections::Vec::new_in(arena);
                         let grid_cell_size = parent
                             .grid_cell_size
                             .as_ref()
                             .map(|x| x.calc(container_width, view_width, view_height));
                         let row_gap = parent.calculated_row_gap.unwrap_or_default();
                         let column_gap = parent.calculated_column_gap.unwrap_or_default();
+                        let reverse = parent.flex_wrap == Some(FlexWrap::WrapReverse);
 
                         #[allow(clippy::cast_precision_loss)]
                         let mut add_gap = |row_width, col_count| {
@@ -1402,7 +1465,10 @@ mod pass_positioning {
                             gap
                         };
 
-                        for child in parent.relative_positioned_elements_mut().filter(|x| x.visibility != Some(Visibility::Hidden)) {
+                        let mut children = parent.relative_positioned_elements_by_order_mut();
+                        children.retain(|x| x.visibility != Some(Visibility::Hidden));
+
+                        for child in &children {
                             let Some(LayoutPosition::Wrap { row, col }) = child.calculated_position
                             else {
                                 continue;
@@ -1419,11 +1485,19 @@ mod pass_positioning {
                                     max_col_count = col_count;
                                 }
 
+                                line_heights.push(row_height);
                                 row_width = 0.0;
+                                row_height = 0.0;
                                 col_count = 0;
                                 last_row = row;
                             }
 
+                            if let Some(height) = child.bounding_calculated_height()
+                                && height > row_height
+                            {
+                                row_height = height;
+                            }
+
                             row_width += grid_cell_size.unwrap_or_else(|| {
                                 child
                                     .calculated_width
@@ -1433,6 +1507,7 @@ mod pass_positioning {
                         }
 
                         add_gap(row_width, col_count);
+                        line_heights.push(row_height);
 
                         #[allow(unused_assignments)]
                         if col_count > max_col_count {
@@ -1455,7 +1530,7 @@ mod pass_positioning {
                         let mut max_height = 0.0;
                         last_row = 0;
 
-                        for child in parent.relative_positioned_elements_mut().filter(|x| x.visibility != Some(Visibility::Hidden)) {
+                        for child in &mut children {
                             let Some(LayoutPosition::Wrap { row, col }) = child.calculated_position
                             else {
                                 continue;
@@ -1492,6 +1567,16 @@ mod pass_positioning {
                                 max_height = child_height;
                             }
 
+                            // Lines stack from the cross-axis end when wrapping in reverse,
+                            // with each child still aligned to the top of its own line
+                            let y = if reverse {
+                                let line_height =
+                                    line_heights.get(row as usize).copied().unwrap_or_default();
+                                container_height - y - line_height
+                            } else {
+                                y
+                            };
+
                             log::trace!(
                                 "position_elements: setting wrapped position ({x}, {y}):\n{child}"
                             );
@@ -1522,13 +1607,6 @@ mod pass_positioning {
                             }};
                         }
 
-                        macro_rules! visible_elements_mut {
-                            () => {{
-                                parent
-                                    .relative_positioned_elements_mut()
-                                    .filter(|x| x.visibility != Some(Visibility::Hidden))
-                            }};
-                        }
 
                         macro_rules! sizes_on_axis {
                             ($direction:expr) => {{
@@ -1623,7 +1701,11 @@ mod pass_positioning {
                                 }
                             }
 
-                        for (i, child) in visible_elements_mut!().enumerate()
+                        for (i, child) in parent
+                            .relative_positioned_elements_by_order_mut()
+                            .into_iter()
+                            .filter(|x| x.visibility != Some(Visibility::Hidden))
+                            .enumerate()
                         {
                             let start_x = x;
                             let start_y = y;
@@ -1745,6 +1827,31 @@ impl Container {
     }
 }
 
+macro_rules! can_grow_func {
+    ($size:ident, $unit:ident $(,)?) => {
+        paste! {
+            impl Container {
+                /// Whether the flex distribution may still grow this container on the given
+                /// axis, i.e. it has not reached its calculated max size yet.
+                fn [<can_grow_ $size>](&self) -> bool {
+                    let Some(max) = self.[<calculated_max_ $size>] else {
+                        return true;
+                    };
+                    let max = max
+                        - self.[<padding_ $unit>]().unwrap_or_default()
+                        - self.[<margin_ $unit>]().unwrap_or_default();
+
+                    self.[<calculated_ $size>]
+                        .is_none_or(|size| crate::float_gt!(max, size))
+                }
+            }
+        }
+    };
+}
+
+can_grow_func!(width, x);
+can_grow_func!(height, y);
+
 macro_rules! axis_sum_func {
     ($prop:ident, $unit:ident, $x:ident, $y:ident $(,)?) => {
         paste! {
@@ -1846,7 +1953,7 @@ mod test {
             font::{FontMetrics, FontMetricsBounds, FontMetricsRow},
             get_scrollbar_size,
         },
-        models::{JustifyContent, LayoutDirection, LayoutOverflow, LayoutPosition},
+        models::{FlexWrap, JustifyContent, LayoutDirection, LayoutOverflow, LayoutPosition},
     };
 
     use super::{Calculator, CalculatorDefaults};
@@ -1959,6 +2066,41 @@ mod test {
         },
     );
 
+    #[test_log::test]
+    fn calc_resolves_units_against_render_context() {
+        use crate::render_context::RenderContext;
+
+        let render = |render_context: RenderContext| {
+            let mut container: Container = html! {
+                div sx-width="50vw" sx-height="25vh" {
+                    div sx-width="calc(10vw + 20)" {}
+                }
+            }
+            .into_string()
+            .try_into()
+            .unwrap();
+
+            Calculator::new(DefaultFontMetrics, CALCULATOR.defaults)
+                .with_render_context(render_context)
+                .calc(&mut container);
+
+            container.children[0].clone()
+        };
+
+        let small = render(RenderContext::new(800.0, 600.0).with_root_font_size(12.0));
+        let large = render(RenderContext::new(1600.0, 1000.0));
+
+        assert_eq!(small.calculated_width, Some(400.0));
+        assert_eq!(small.calculated_height, Some(150.0));
+        assert_eq!(small.children[0].calculated_width, Some(100.0));
+        assert_eq!(small.calculated_font_size, Some(12.0));
+
+        assert_eq!(large.calculated_width, Some(800.0));
+        assert_eq!(large.calculated_height, Some(250.0));
+        assert_eq!(large.children[0].calculated_width, Some(180.0));
+        assert_eq!(large.calculated_font_size, Some(16.0));
+    }
+
     mod scrollbar {
         use super::*;
 
@@ -5315,6 +5457,172 @@ mod test {
         );
     }
 
+    fn overflowing_flex_wrap_container(flex_wrap: FlexWrap) -> Container {
+        let child = Container {
+            width: Some(Number::Integer(25)),
+            height: Some(Number::Integer(40)),
+            ..Default::default()
+        };
+
+        Container {
+            children: vec![child.clone(), child.clone(), child],
+            calculated_width: Some(50.0),
+            calculated_height: Some(80.0),
+            direction: LayoutDirection::Row,
+            flex_wrap: Some(flex_wrap),
+            ..Default::default()
+        }
+    }
+
+    #[test_log::test]
+    fn flex_wrap_wraps_overflowing_children_onto_a_second_line() {
+        let mut container = overflowing_flex_wrap_container(FlexWrap::Wrap);
+
+        CALCULATOR.calc(&mut container);
+        log::trace!("container:\n{container}");
+
+        compare_containers(
+            &container,
+            &Container {
+                children: vec![
+                    Container {
+                        calculated_width: Some(25.0),
+                        calculated_height: Some(40.0),
+                        calculated_x: Some(0.0),
+                        calculated_y: Some(0.0),
+                        calculated_position: Some(LayoutPosition::Wrap { row: 0, col: 0 }),
+                        ..container.children[0].clone()
+                    },
+                    Container {
+                        calculated_width: Some(25.0),
+                        calculated_height: Some(40.0),
+                        calculated_x: Some(25.0),
+                        calculated_y: Some(0.0),
+                        calculated_position: Some(LayoutPosition::Wrap { row: 0, col: 1 }),
+                        ..container.children[1].clone()
+                    },
+                    Container {
+                        calculated_width: Some(25.0),
+                        calculated_height: Some(40.0),
+                        calculated_x: Some(0.0),
+                        calculated_y: Some(40.0),
+                        calculated_position: Some(LayoutPosition::Wrap { row: 1, col: 0 }),
+                        ..container.children[2].clone()
+                    },
+                ],
+                ..container.clone()
+            },
+        );
+    }
+
+    #[test_log::test]
+    fn flex_wrap_reverse_stacks_lines_from_the_cross_axis_end() {
+        let mut container = overflowing_flex_wrap_container(FlexWrap::WrapReverse);
+
+        CALCULATOR.calc(&mut container);
+        log::trace!("container:\n{container}");
+
+        compare_containers(
+            &container,
+            &Container {
+                children: vec![
+                    Container {
+                        calculated_width: Some(25.0),
+                        calculated_height: Some(40.0),
+                        calculated_x: Some(0.0),
+                        calculated_y: Some(40.0),
+                        calculated_position: Some(LayoutPosition::Wrap { row: 0, col: 0 }),
+                        ..container.children[0].clone()
+                    },
+                    Container {
+                        calculated_width: Some(25.0),
+                        calculated_height: Some(40.0),
+                        calculated_x: Some(25.0),
+                        calculated_y: Some(40.0),
+                        calculated_position: Some(LayoutPosition::Wrap { row: 0, col: 1 }),
+                        ..container.children[1].clone()
+                    },
+                    Container {
+                        calculated_width: Some(25.0),
+                        calculated_height: Some(40.0),
+                        calculated_x: Some(0.0),
+                        calculated_y: Some(0.0),
+                        calculated_position: Some(LayoutPosition::Wrap { row: 1, col: 0 }),
+                        ..container.children[2].clone()
+                    },
+                ],
+                ..container.clone()
+            },
+        );
+    }
+
+    #[test_log::test]
+    fn flex_wrap_reverse_aligns_mixed_height_children_to_the_top_of_their_line() {
+        let mut container = Container {
+            children: vec![
+                Container {
+                    width: Some(Number::Integer(25)),
+                    height: Some(Number::Integer(40)),
+                    ..Default::default()
+                },
+                Container {
+                    width: Some(Number::Integer(25)),
+                    height: Some(Number::Integer(20)),
+                    ..Default::default()
+                },
+                Container {
+                    width: Some(Number::Integer(25)),
+                    height: Some(Number::Integer(30)),
+                    ..Default::default()
+                },
+            ],
+            calculated_width: Some(50.0),
+            calculated_height: Some(100.0),
+            direction: LayoutDirection::Row,
+            flex_wrap: Some(FlexWrap::WrapReverse),
+            ..Default::default()
+        };
+
+        CALCULATOR.calc(&mut container);
+        log::trace!("container:\n{container}");
+
+        assert_eq!(
+            container
+                .children
+                .iter()
+                .map(|x| (x.calculated_x, x.calculated_y))
+                .collect::<Vec<_>>(),
+            vec![
+                (Some(0.0), Some(60.0)),
+                (Some(25.0), Some(60.0)),
+                (Some(0.0), Some(30.0)),
+            ]
+        );
+    }
+
+    #[test_log::test]
+    fn flex_nowrap_keeps_overflowing_children_on_one_line() {
+        let mut container = overflowing_flex_wrap_container(FlexWrap::NoWrap);
+
+        CALCULATOR.calc(&mut container);
+        log::trace!("container:\n{container}");
+
+        assert!(
+            container
+                .children
+                .iter()
+                .all(|x| !matches!(x.calculated_position, Some(LayoutPosition::Wrap { .. })))
+        );
+        assert_eq!(
+            container
+                .children
+                .iter()
+                .map(|x| x.calculated_y)
+                .collect::<Vec<_>>(),
+            vec![Some(0.0), Some(0.0), Some(0.0)]
+        );
+    }
+
     #[test_log::test]
     fn handle_overflow_wraps_multi_row_overflow_content_correctly() {
         let row_height = 40.0;
@@ -10823,6 +11131,162 @@ mod test {
             );
         }
 
+        #[test_log::test]
+        fn does_clamp_flex_child_width_to_explicit_fixed_max_width() {
+            let mut container: Container = html! {
+                div sx-dir=(LayoutDirection::Row) sx-width=(400) {
+                    div sx-flex=(1) sx-max-width=(100) {}
+                    div sx-flex=(1) {}
+                }
+            }
+            .into_string()
+            .try_into()
+            .unwrap();
+
+            container.calculated_width = Some(400.0);
+            container.calculated_height = Some(100.0);
+
+            CALCULATOR.calc(&mut container);
+            log::trace!("full container:\n{container}");
+            container = container.children[0].clone();
+            log::trace!("container:\n{container}");
+
+            compare_containers(
+                &container,
+                &Container {
+                    children: vec![
+                        Container {
+                            calculated_width: Some(100.0),
+                            ..container.children[0].clone()
+                        },
+                        Container {
+                            calculated_width: Some(300.0),
+                            ..container.children[1].clone()
+                        },
+                    ],
+                    calculated_width: Some(400.0),
+                    ..container.clone()
+                },
+            );
+        }
+
+        #[test_log::test]
+        fn does_clamp_flex_child_width_to_explicit_dynamic_max_width() {
+            let mut container: Container = html! {
+                div sx-dir=(LayoutDirection::Row) sx-width=(400) {
+                    div sx-flex=(1) sx-max-width="25%" {}
+                    div sx-flex=(1) {}
+                }
+            }
+            .into_string()
+            .try_into()
+            .unwrap();
+
+            container.calculated_width = Some(400.0);
+            container.calculated_height = Some(100.0);
+
+            CALCULATOR.calc(&mut container);
+            log::trace!("full container:\n{container}");
+            container = container.children[0].clone();
+            log::trace!("container:\n{container}");
+
+            compare_containers(
+                &container,
+                &Container {
+                    children: vec![
+                        Container {
+                            calculated_width: Some(100.0),
+                            ..container.children[0].clone()
+                        },
+                        Container {
+                            calculated_width: Some(300.0),
+                            ..container.children[1].clone()
+                        },
+                    ],
+                    calculated_width: Some(400.0),
+                    ..container.clone()
+                },
+            );
+        }
+
+        #[test_log::test]
+        fn does_force_flex_child_height_to_explicit_fixed_min_height() {
+            let mut container: Container = html! {
+                div sx-height=(400) {
+                    div sx-flex=(1) sx-min-height=(300) {}
+                    div sx-flex=(1) {}
+                }
+            }
+            .into_string()
+            .try_into()
+            .unwrap();
+
+            container.calculated_width = Some(100.0);
+            container.calculated_height = Some(400.0);
+
+            CALCULATOR.calc(&mut container);
+            log::trace!("full container:\n{container}");
+            container = container.children[0].clone();
+            log::trace!("container:\n{container}");
+
+            compare_containers(
+                &container,
+                &Container {
+                    children: vec![
+                        Container {
+                            calculated_height: Some(300.0),
+                            ..container.children[0].clone()
+                        },
+                        Container {
+                            calculated_height: Some(100.0),
+                            ..container.children[1].clone()
+                        },
+                    ],
+                    calculated_height: Some(400.0),
+                    ..container.clone()
+                },
+            );
+        }
+
+        #[test_log::test]
+        fn does_force_flex_child_height_to_explicit_dynamic_min_height() {
+            let mut container: Container = html! {
+                div sx-height=(400) {
+                    div sx-flex=(1) sx-min-height="75%" {}
+                    div sx-flex=(1) {}
+                }
+            }
+            .into_string()
+            .try_into()
+            .unwrap();
+
+            container.calculated_width = Some(100.0);
+            container.calculated_height = Some(400.0);
+
+            CALCULATOR.calc(&mut container);
+            log::trace!("full container:\n{container}");
+            container = container.children[0].clone();
+            log::trace!("container:\n{container}");
+
+            compare_containers(
+                &container,
+                &Container {
+                    children: vec![
+                        Container {
+                            calculated_height: Some(300.0),
+                            ..container.children[0].clone()
+                        },
+                        Container {
+                            calculated_height: Some(100.0),
+                            ..container.children[1].clone()
+                        },
+                    ],
+                    calculated_height: Some(400.0),
+                    ..container.clone()
+                },
+            );
+        }
+
         #[test_log::test]
         #[ignore = "Unimplemented"]
         fn flex_child_does_take_full_width_if_flex_is_specified() {
@@ -11435,6 +11899,73 @@ mod test {
             );
         }
 
+        #[test_log::test]
+        fn does_not_include_display_none_children_in_position() {
+            let mut container: Container = html! {
+                div sx-width=(100) sx-height=(50) {
+                    div sx-display="none" sx-height=(20) {}
+                    "test"
+                }
+            }
+            .into_string()
+            .try_into()
+            .unwrap();
+
+            container.calculated_width = Some(400.0);
+            container.calculated_height = Some(100.0);
+
+            CALCULATOR.calc(&mut container);
+            log::trace!("full container:\n{container}");
+            container = container.children[0].clone();
+            log::trace!("container:\n{container}");
+
+            compare_containers(
+                &container,
+                &Container {
+                    children: vec![
+                        Container {
+                            calculated_y: None,
+                            ..container.children[0].clone()
+                        },
+                        Container {
+                            calculated_y: Some(0.0),
+                            ..container.children[1].clone()
+                        },
+                    ],
+                    ..container.clone()
+                },
+            );
+        }
+
+        #[test_log::test]
+        fn order_reorders_children_along_main_axis_without_changing_children() {
+            let mut container: Container = html! {
+                div sx-dir=(LayoutDirection::Row) sx-width=(100) sx-height=(50) {
+                    div sx-width=(20) sx-height=(20) {}
+                    div sx-width=(30) sx-height=(20) sx-order=(-1) {}
+                    div sx-width=(10) sx-height=(20) {}
+                }
+            }
+            .into_string()
+            .try_into()
+            .unwrap();
+
+            container.calculated_width = Some(400.0);
+            container.calculated_height = Some(100.0);
+
+            CALCULATOR.calc(&mut container);
+            log::trace!("full container:\n{container}");
+            container = container.children[0].clone();
+
+            assert_eq!(container.children[0].width, Some(Number::Integer(20)));
+            assert_eq!(container.children[1].order, Some(-1));
+
+            assert_eq!(container.children[1].calculated_x, Some(0.0));
+            assert_eq!(container.children[0].calculated_x, Some(30.0));
+            // Equal orders keep their source order
+            assert_eq!(container.children[2].calculated_x, Some(50.0));
+        }
+
         #[test_log::test]
         fn does_center_child_correctly_with_dir_row_and_multiple_children() {
             let mut container: Container = html! {
@@ -0,0 +1,229 @@
+//! Indented outline of a container tree with its computed layout.
+//!
+//! The outline is meant for diagnosing misplaced elements, so it lays out a copy of the
+//! tree itself instead of relying on sizes a renderer may or may not have calculated.
+//! Text is measured with approximate fixed-width metrics, so text sizes are close to, but
+//! not the same as, the ones a renderer with real fonts computes.
+
+use std::fmt::Write as _;
+
+use hyperchad_transformer_models::LayoutDirection;
+
+use crate::{Container, Element, render_context::RenderContext};
+
+use super::{
+    Calc as _,
+    calc::{Calculator, CalculatorDefaults},
+    font::{FontMetrics, FontMetricsBounds, FontMetricsRow},
+};
+
+/// Width of a glyph relative to the font size, for [`FixedWidthFontMetrics`].
+const GLYPH_WIDTH_RATIO: f32 = 0.5;
+
+/// Longest text shown for a text element before it is truncated.
+const MAX_TEXT_LEN: usize = 32;
+
+/// Measures every glyph as [`GLYPH_WIDTH_RATIO`] times the font size wide and one font
+/// size tall.
+struct FixedWidthFontMetrics;
+
+impl FontMetrics for FixedWidthFontMetrics {
+    fn measure_text(&self, text: &str, size: f32, wrap_width: f32) -> FontMetricsBounds {
+        #[allow(clippy::cast_precision_loss)]
+        let mut width = text.chars().count() as f32 * size * GLYPH_WIDTH_RATIO;
+        let mut rows = vec![];
+
+        if wrap_width > 0.0 {
+            #[allow(clippy::while_float)]
+            while width > wrap_width {
+                rows.push(FontMetricsRow {
+                    width: wrap_width,
+                    height: size,
+                });
+                width -= wrap_width;
+            }
+        }
+
+        if width > 0.0 {
+            rows.push(FontMetricsRow {
+                width,
+                height: size,
+            });
+        }
+
+        FontMetricsBounds { rows }
+    }
+}
+
+/// Browser default font sizes, relative to the root font size of `ctx`, without margins.
+const fn calculator_defaults(ctx: &RenderContext) -> CalculatorDefaults {
+    let size = ctx.root_font_size;
+
+    CalculatorDefaults {
+        font_size: size,
+        font_margin_top: 0.0,
+        font_margin_bottom: 0.0,
+        h1_font_size: size * 2.0,
+        h1_font_margin_top: 0.0,
+        h1_font_margin_bottom: 0.0,
+        h2_font_size: size * 1.5,
+        h2_font_margin_top: 0.0,
+        h2_font_margin_bottom: 0.0,
+        h3_font_size: size * 1.17,
+        h3_font_margin_top: 0.0,
+        h3_font_margin_bottom: 0.0,
+        h4_font_size: size,
+        h4_font_margin_top: 0.0,
+        h4_font_margin_bottom: 0.0,
+        h5_font_size: size * 0.83,
+        h5_font_margin_top: 0.0,
+        h5_font_margin_bottom: 0.0,
+        h6_font_size: size * 0.67,
+        h6_font_margin_top: 0.0,
+        h6_font_margin_bottom: 0.0,
+    }
+}
+
+/// Formats a computed length, or `-` if it wasn't computed.
+fn format_px(value: Option<f32>) -> String {
+    match value {
+        Some(value) if value.fract() == 0.0 => format!("{value}"),
+        Some(value) => format!("{value:.2}"),
+        None => "-".to_string(),
+    }
+}
+
+fn write_container(output: &mut String, container: &Container, depth: usize) {
+    let _ = write!(
+        output,
+        "{:indent$}{}",
+        "",
+        container.element.tag_display_str(),
+        indent = depth * 2
+    );
+
+    if let Some(str_id) = &container.str_id {
+        let _ = write!(output, "#{str_id}");
+    }
+    for class in &container.classes {
+        let _ = write!(output, ".{class}");
+    }
+
+    match &container.element {
+        Element::Text { value } | Element::Raw { value } => {
+            let text = value.chars().take(MAX_TEXT_LEN).collect::<String>();
+            let ellipsis = if text.len() < value.len() { "…" } else { "" };
+            let _ = write!(output, " {text:?}{ellipsis}");
+        }
+        _ => {}
+    }
+
+    if container.direction == LayoutDirection::Row {
+        let _ = write!(output, " direction={}", container.direction);
+    }
+    if let Some(position) = container.position {
+        let _ = write!(output, " position={position}");
+    }
+    if container.hidden == Some(true) {
+        let _ = write!(output, " hidden");
+    }
+
+    let _ = writeln!(
+        output,
+        " x={} y={} width={} height={}",
+        format_px(container.calculated_x),
+        format_px(container.calculated_y),
+        format_px(container.calculated_width),
+        format_px(container.calculated_height),
+    );
+
+    for child in &container.children {
+        write_container(output, child, depth + 1);
+    }
+}
+
+impl Container {
+    /// Renders an indented outline of the container tree, one element per line, with its
+    /// computed layout.
+    ///
+    /// A copy of the tree is laid out in `ctx` first, so the root takes the size of the
+    /// viewport. Each line shows the element's tag, id, classes and a few layout
+    /// properties, followed by its computed `x`, `y` (relative to its parent), `width` and
+    /// `height` in pixels. Values the layout didn't compute are shown as `-`.
+    ///
+    /// Text is measured with approximate fixed-width font metrics.
+    #[must_use]
+    pub fn debug_tree(&self, ctx: &RenderContext) -> String {
+        let mut container = self.clone();
+
+        Calculator::new(FixedWidthFontMetrics, calculator_defaults(ctx))
+            .with_render_context(*ctx)
+            .calc(&mut container);
+
+        let mut output = String::new();
+        write_container(&mut output, &container, 0);
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Number;
+
+    use super::*;
+
+    #[test_log::test]
+    fn debug_tree_shows_computed_layout_of_column_children() {
+        let container = Container {
+            children: vec![
+                Container {
+                    str_id: Some("first".to_string()),
+                    height: Some(Number::Integer(20)),
+                    ..Default::default()
+                },
+                Container {
+                    classes: vec!["second".to_string()],
+                    height: Some(Number::Integer(30)),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let output = container.debug_tree(&RenderContext::new(100.0, 50.0));
+        let lines = output.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 3, "{output}");
+        assert!(lines[0].starts_with("Div "), "{output}");
+        assert!(lines[0].ends_with(" width=100 height=50"), "{output}");
+        assert_eq!(
+            lines[1], "  Div#first x=0 y=0 width=100 height=20",
+            "{output}"
+        );
+        assert_eq!(
+            lines[2], "  Div.second x=0 y=20 width=100 height=30",
+            "{output}"
+        );
+    }
+
+    #[test_log::test]
+    fn debug_tree_leaves_the_original_tree_untouched() {
+        let container = Container {
+            children: vec![Container {
+                element: Element::Text {
+                    value: "hello".to_string(),
+                },
+                ..Default::default()
+            }],
+            direction: LayoutDirection::Row,
+            ..Default::default()
+        };
+
+        let output = container.debug_tree(&RenderContext::new(100.0, 50.0));
+
+        assert!(output.starts_with("Div direction=row "), "{output}");
+        assert!(output.contains("  Text \"hello\" "), "{output}");
+        assert_eq!(container.calculated_width, None);
+        assert_eq!(container.children[0].calculated_width, None);
+    }
+}
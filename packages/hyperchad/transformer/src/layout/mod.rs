@@ -6,10 +6,14 @@
 
 use std::sync::atomic::AtomicU16;
 
+use hyperchad_transformer_models::PointerEvents;
+
 use crate::Container;
 
 /// Layout calculation implementation with the `Calculator` type and layout algorithms.
 pub mod calc;
+/// Indented outline of a container tree with its computed layout, for debugging.
+mod debug_tree;
 /// Font metrics traits and types for text measurement during layout.
 pub mod font;
 
@@ -221,12 +225,122 @@ pub fn set_float(opt: &mut Option<f32>, value: f32) -> Option<f32> {
     None
 }
 
+/// Finds the top-most element under the point `(x, y)`.
+///
+/// Coordinates are relative to `container`'s padding box, and each child's calculated
+/// position is resolved against its parent's content box, the same way the layout engine
+/// positions elements. Later siblings paint over earlier ones, so they are tested first.
+/// Hidden elements are skipped entirely.
+///
+/// Elements with [`PointerEvents::None`] are still painted but are transparent to
+/// hit-testing, so the point falls through to whatever lies beneath them. As in CSS,
+/// `pointer-events` is inherited: descendants of such an element are transparent too unless
+/// they opt back in with [`PointerEvents::Auto`].
+#[must_use]
+pub fn hit_test(container: &Container, x: f32, y: f32) -> Option<&Container> {
+    hit_test_inner(container, x, y, PointerEvents::Auto)
+}
+
+fn hit_test_inner(
+    container: &Container,
+    x: f32,
+    y: f32,
+    inherited: PointerEvents,
+) -> Option<&Container> {
+    if container.is_hidden() {
+        return None;
+    }
+
+    let pointer_events = container.pointer_events.unwrap_or(inherited);
+    let content_x = x - container.calculated_padding_left.unwrap_or(0.0);
+    let content_y = y - container.calculated_padding_top.unwrap_or(0.0);
+
+    for child in container.children.iter().rev() {
+        let child_x = content_x
+            - child.calculated_x.unwrap_or(0.0)
+            - child.calculated_margin_left.unwrap_or(0.0);
+        let child_y = content_y
+            - child.calculated_y.unwrap_or(0.0)
+            - child.calculated_margin_top.unwrap_or(0.0);
+
+        if let Some(hit) = hit_test_inner(child, child_x, child_y, pointer_events) {
+            return Some(hit);
+        }
+    }
+
+    if pointer_events == PointerEvents::None {
+        return None;
+    }
+
+    let (Some(width), Some(height)) = (container.calculated_width, container.calculated_height)
+    else {
+        return None;
+    };
+    let width = width + container.padding_x().unwrap_or(0.0);
+    let height = height + container.padding_y().unwrap_or(0.0);
+
+    (x >= 0.0 && y >= 0.0 && x < width && y < height).then_some(container)
+}
+
 #[cfg(test)]
 mod tests {
     use serial_test::serial;
 
     use super::*;
 
+    fn sized_container(id: usize, width: f32, height: f32) -> Container {
+        Container {
+            id,
+            calculated_x: Some(0.0),
+            calculated_y: Some(0.0),
+            calculated_width: Some(width),
+            calculated_height: Some(height),
+            ..Default::default()
+        }
+    }
+
+    #[test_log::test]
+    fn hit_test_returns_top_most_sibling() {
+        let mut root = sized_container(0, 100.0, 100.0);
+        root.children = vec![
+            sized_container(1, 50.0, 50.0),
+            sized_container(2, 100.0, 100.0),
+        ];
+
+        assert_eq!(hit_test(&root, 10.0, 10.0).map(|x| x.id), Some(2));
+    }
+
+    #[test_log::test]
+    fn hit_test_pointer_events_none_overlay_does_not_capture_click() {
+        let button = sized_container(1, 50.0, 50.0);
+        let mut overlay = sized_container(2, 100.0, 100.0);
+        overlay.pointer_events = Some(PointerEvents::None);
+        overlay.children = vec![sized_container(3, 100.0, 20.0)];
+
+        let mut root = sized_container(0, 100.0, 100.0);
+        root.children = vec![button, overlay];
+
+        // The click lands on the button beneath the overlay and its inheriting child
+        assert_eq!(hit_test(&root, 10.0, 10.0).map(|x| x.id), Some(1));
+        // Outside the button, the click falls through to the root
+        assert_eq!(hit_test(&root, 75.0, 75.0).map(|x| x.id), Some(0));
+    }
+
+    #[test_log::test]
+    fn hit_test_pointer_events_auto_child_opts_back_in() {
+        let mut child = sized_container(3, 100.0, 20.0);
+        child.pointer_events = Some(PointerEvents::Auto);
+        let mut overlay = sized_container(2, 100.0, 100.0);
+        overlay.pointer_events = Some(PointerEvents::None);
+        overlay.children = vec![child];
+
+        let mut root = sized_container(0, 100.0, 100.0);
+        root.children = vec![sized_container(1, 50.0, 50.0), overlay];
+
+        assert_eq!(hit_test(&root, 10.0, 10.0).map(|x| x.id), Some(3));
+        assert_eq!(hit_test(&root, 10.0, 30.0).map(|x| x.id), Some(1));
+    }
+
     #[test_log::test]
     #[serial(scrollbar_size)]
     fn get_and_set_scrollbar_size_updates_global_value() {
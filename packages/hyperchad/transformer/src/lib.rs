@@ -55,16 +55,16 @@
 
 use std::{any::Any, collections::BTreeMap, io::Write};
 
-use switchy_env::var;
-
 use hyperchad_actions::Action;
 use hyperchad_color::Color;
 use hyperchad_transformer_models::{
-    AlignItems, Cursor, FontWeight, ImageFit, ImageLoading, JustifyContent, LayoutDirection,
-    LayoutOverflow, LinkTarget, OverflowWrap, Position, Route, TextAlign, TextDecorationLine,
-    TextDecorationStyle, TextOverflow, UserSelect, Visibility, WhiteSpace,
+    AlignItems, Cursor, Display, FlexWrap, FontWeight, ImageDecoding, ImageFit, ImageLoading,
+    JustifyContent, LayoutDirection, LayoutOverflow, LinkTarget, OverflowWrap, PointerEvents,
+    Position, Route, TextAlign, TextDecorationLine, TextDecorationStyle, TextDirection,
+    TextOverflow, UserSelect, Visibility, WhiteSpace,
 };
 use parse::parse_number;
+use render_context::env_flag;
 use serde::{Deserialize, Serialize, de::Error};
 use serde_json::Value;
 
@@ -83,6 +83,8 @@ pub mod html;
 pub mod layout;
 /// Parsing utilities for numeric values and CSS calculation expressions.
 pub mod parse;
+/// Root font size and viewport defaults for rendering without a display.
+pub mod render_context;
 
 /// Represents a calculation expression that can be evaluated with context.
 ///
@@ -111,35 +113,74 @@ pub enum Calculation {
     Max(Box<Self>, Box<Self>),
 }
 
+/// Largest magnitude that a [`Calculation`] evaluates to.
+///
+/// Results beyond it (including `inf` from e.g. a division by zero or `100vw * 100vw` on
+/// a huge viewport) are saturated to it so layouts stay finite.
+pub const MAX_CALCULATION_VALUE: f32 = 1.0e9;
+
 impl Calculation {
+    /// Evaluates the expression, saturating the result to [`MAX_CALCULATION_VALUE`].
+    ///
+    /// Intermediate results are computed in `f64` so nested expressions don't accumulate
+    /// `f32` rounding errors or overflow before the final result is saturated. `NaN`
+    /// results evaluate to `0`.
     fn calc(&self, container: f32, view_width: f32, view_height: f32) -> f32 {
+        let value = self.calc_f64(
+            f64::from(container),
+            f64::from(view_width),
+            f64::from(view_height),
+        );
+
+        if value.is_nan() {
+            log::debug!("calc: '{self}' evaluated to NaN, using 0");
+            return 0.0;
+        }
+
+        let max = f64::from(MAX_CALCULATION_VALUE);
+        if value.abs() > max {
+            log::debug!("calc: '{self}' evaluated to {value}, saturating to {max}");
+            return if value.is_sign_negative() {
+                -MAX_CALCULATION_VALUE
+            } else {
+                MAX_CALCULATION_VALUE
+            };
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            value as f32
+        }
+    }
+
+    fn calc_f64(&self, container: f64, view_width: f64, view_height: f64) -> f64 {
         match self {
-            Self::Number(number) => number.calc(container, view_width, view_height),
+            Self::Number(number) => number.calc_f64(container, view_width, view_height),
             Self::Add(left, right) => {
-                left.calc(container, view_width, view_height)
-                    + right.calc(container, view_width, view_height)
+                left.calc_f64(container, view_width, view_height)
+                    + right.calc_f64(container, view_width, view_height)
             }
             Self::Subtract(left, right) => {
-                left.calc(container, view_width, view_height)
-                    - right.calc(container, view_width, view_height)
+                left.calc_f64(container, view_width, view_height)
+                    - right.calc_f64(container, view_width, view_height)
             }
             Self::Multiply(left, right) => {
-                left.calc(container, view_width, view_height)
-                    * right.calc(container, view_width, view_height)
+                left.calc_f64(container, view_width, view_height)
+                    * right.calc_f64(container, view_width, view_height)
             }
             Self::Divide(left, right) => {
-                left.calc(container, view_width, view_height)
-                    / right.calc(container, view_width, view_height)
+                left.calc_f64(container, view_width, view_height)
+                    / right.calc_f64(container, view_width, view_height)
             }
-            Self::Grouping(value) => value.calc(container, view_width, view_height),
+            Self::Grouping(value) => value.calc_f64(container, view_width, view_height),
             Self::Min(left, right) => {
-                let a = left.calc(container, view_width, view_height);
-                let b = right.calc(container, view_width, view_height);
+                let a = left.calc_f64(container, view_width, view_height);
+                let b = right.calc_f64(container, view_width, view_height);
                 if a > b { b } else { a }
             }
             Self::Max(left, right) => {
-                let a = left.calc(container, view_width, view_height);
-                let b = right.calc(container, view_width, view_height);
+                let a = left.calc_f64(container, view_width, view_height);
+                let b = right.calc_f64(container, view_width, view_height);
                 if a > b { a } else { b }
             }
         }
@@ -393,6 +434,25 @@ impl Number {
         }
     }
 
+    /// Evaluates this number in `f64`, for use within [`Calculation`]s.
+    fn calc_f64(&self, container: f64, view_width: f64, view_height: f64) -> f64 {
+        match self {
+            Self::Real(x) => f64::from(*x),
+            #[allow(clippy::cast_precision_loss)]
+            Self::Integer(x) => *x as f64,
+            Self::RealPercent(x) => container * (f64::from(*x) / 100.0),
+            #[allow(clippy::cast_precision_loss)]
+            Self::IntegerPercent(x) => container * (*x as f64 / 100.0),
+            Self::RealVw(x) | Self::RealDvw(x) => view_width * (f64::from(*x) / 100.0),
+            #[allow(clippy::cast_precision_loss)]
+            Self::IntegerVw(x) | Self::IntegerDvw(x) => view_width * (*x as f64 / 100.0),
+            Self::RealVh(x) | Self::RealDvh(x) => view_height * (f64::from(*x) / 100.0),
+            #[allow(clippy::cast_precision_loss)]
+            Self::IntegerVh(x) | Self::IntegerDvh(x) => view_height * (*x as f64 / 100.0),
+            Self::Calc(x) => x.calc_f64(container, view_width, view_height),
+        }
+    }
+
     /// Returns a reference to this number if it is dynamic.
     ///
     /// Dynamic numbers depend on container size (percentages).
@@ -479,6 +539,61 @@ mod test_number_deserialize {
             assert_eq!(number, deserialized);
         }
     }
+
+    #[test_log::test]
+    fn rejects_malformed_number_strings() {
+        for input in [
+            "",
+            "px",
+            "%",
+            "abc",
+            "12pq",
+            "12em",
+            "12px3",
+            "10%x",
+            "5vwx",
+            "1.2.3",
+            "NaN",
+            "inf",
+            "-infinity",
+            "(1 + 2",
+            "1 + 2)",
+            "calc",
+            "calc()",
+            "calc(1 +)",
+            "calc(1 + 2",
+            "calc(10vw",
+            "calc(* 2)",
+            "calc(1 + 2))",
+            "calc(min(1, ))",
+            "calc(max(1 2))",
+            "calc(é + 1)",
+        ] {
+            let json = serde_json::to_string(input).unwrap();
+            let result = serde_json::from_str::<Number>(&json);
+            assert!(
+                result.is_err(),
+                "Expected '{input}' to fail to deserialize, got {result:?}"
+            );
+        }
+    }
+
+    #[test_log::test]
+    fn malformed_number_error_is_descriptive() {
+        let error = serde_json::from_str::<Number>("\"12pq\"").unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("Failed to parse number '12pq'"),
+            "Unexpected error message: {error}"
+        );
+    }
+
+    #[test_log::test]
+    fn deserializes_number_strings_with_surrounding_whitespace() {
+        let number: Number = serde_json::from_str("\" 10% \"").unwrap();
+        assert_eq!(number, Number::IntegerPercent(10));
+    }
 }
 
 #[cfg(test)]
@@ -630,7 +745,7 @@ mod test_number_calc {
 
 #[cfg(test)]
 mod test_calculation_calc {
-    use crate::{Calculation, Number};
+    use crate::{Calculation, MAX_CALCULATION_VALUE, Number};
 
     #[test_log::test]
     fn calculation_add_computes_sum() {
@@ -801,12 +916,83 @@ mod test_calculation_calc {
             Calculation::Grouping(Box::new(Calculation::Number(Box::new(Number::Integer(50)))));
         assert!(calc.is_fixed());
     }
+
+    fn number(number: Number) -> Box<Calculation> {
+        Box::new(Calculation::Number(Box::new(number)))
+    }
+
+    #[test_log::test]
+    fn calculation_overflowing_f32_saturates_to_max() {
+        let calc = Calculation::Multiply(
+            number(Number::IntegerVw(100)),
+            number(Number::IntegerVw(100)),
+        );
+        let result = calc.calc(100.0, 1.0e20, 1080.0);
+        assert!(result.is_finite());
+        assert!((result - MAX_CALCULATION_VALUE).abs() < f32::EPSILON);
+    }
+
+    #[test_log::test]
+    fn calculation_overflowing_f32_negatively_saturates_to_negative_max() {
+        let calc = Calculation::Subtract(
+            number(Number::Integer(0)),
+            Box::new(Calculation::Multiply(
+                number(Number::IntegerVh(100)),
+                number(Number::IntegerVh(100)),
+            )),
+        );
+        let result = calc.calc(100.0, 1920.0, 1.0e20);
+        assert!((result + MAX_CALCULATION_VALUE).abs() < f32::EPSILON);
+    }
+
+    #[test_log::test]
+    fn calculation_division_by_zero_saturates_to_max() {
+        let calc = Calculation::Divide(number(Number::Integer(10)), number(Number::Integer(0)));
+        let result = calc.calc(100.0, 1920.0, 1080.0);
+        assert!((result - MAX_CALCULATION_VALUE).abs() < f32::EPSILON);
+    }
+
+    #[test_log::test]
+    fn calculation_nan_evaluates_to_zero() {
+        let calc = Calculation::Divide(number(Number::Integer(0)), number(Number::Integer(0)));
+        let result = calc.calc(100.0, 1920.0, 1080.0);
+        assert!(result.abs() < f32::EPSILON);
+    }
+
+    #[test_log::test]
+    fn calculation_recovers_from_intermediate_f32_overflow() {
+        // (100vw * 100vw) / 100vw / 1e25 overflows f32 after the multiplication, but not f64
+        let calc = Calculation::Divide(
+            Box::new(Calculation::Divide(
+                Box::new(Calculation::Multiply(
+                    number(Number::IntegerVw(100)),
+                    number(Number::IntegerVw(100)),
+                )),
+                number(Number::IntegerVw(100)),
+            )),
+            number(Number::Real(1.0e25)),
+        );
+        let result = calc.calc(100.0, 1.0e30, 1080.0);
+        assert!((result - 100_000.0).abs() < 1.0);
+    }
+
+    #[test_log::test]
+    fn number_calc_saturates_nested_calculation() {
+        let num = Number::Calc(Calculation::Multiply(
+            number(Number::IntegerPercent(100)),
+            number(Number::IntegerPercent(100)),
+        ));
+        let result = num.calc(f32::MAX, 1920.0, 1080.0);
+        assert!((result - MAX_CALCULATION_VALUE).abs() < f32::EPSILON);
+    }
 }
 
 #[cfg(test)]
 mod test_container_methods {
     use crate::{Container, Element, Flex, Number};
-    use hyperchad_transformer_models::{AlignItems, JustifyContent, LayoutDirection, Position};
+    use hyperchad_transformer_models::{
+        AlignItems, FlexWrap, JustifyContent, LayoutDirection, LayoutOverflow, Position,
+    };
 
     #[test_log::test]
     fn container_is_fixed_returns_true_for_fixed_position() {
@@ -844,6 +1030,28 @@ mod test_container_methods {
         assert!(!container.is_fixed());
     }
 
+    #[test_log::test]
+    fn container_unknown_will_change_properties_returns_unrecognized_values() {
+        let container = Container {
+            will_change: Some(vec![
+                "transform".to_string(),
+                "not-a-property".to_string(),
+                "opacity".to_string(),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            container.unknown_will_change_properties(),
+            vec!["not-a-property"]
+        );
+    }
+
+    #[test_log::test]
+    fn container_unknown_will_change_properties_is_empty_when_unset() {
+        let container = Container::default();
+        assert!(container.unknown_will_change_properties().is_empty());
+    }
+
     #[test_log::test]
     fn container_is_fixed_returns_false_for_static_position() {
         let container = Container {
@@ -922,6 +1130,22 @@ mod test_container_methods {
         assert!(!Container::default().is_hidden());
     }
 
+    #[test_log::test]
+    fn container_is_hidden_returns_true_when_display_is_none() {
+        let container = Container {
+            display: Some(Display::None),
+            ..Default::default()
+        };
+        assert!(container.is_hidden());
+        assert!(!container.is_visible());
+
+        let container = Container {
+            display: Some(Display::Flex),
+            ..Default::default()
+        };
+        assert!(!container.is_hidden());
+    }
+
     #[test_log::test]
     fn container_is_span_returns_true_for_raw_element() {
         let container = Container {
@@ -1186,6 +1410,42 @@ mod test_container_methods {
         assert!(container.is_flex_container());
     }
 
+    #[test_log::test]
+    fn is_flex_container_returns_true_with_flex_wrap() {
+        let container = Container {
+            flex_wrap: Some(FlexWrap::Wrap),
+            ..Default::default()
+        };
+        assert!(container.is_flex_container());
+    }
+
+    #[test_log::test]
+    fn layout_overflow_x_wraps_wrapping_row_containers() {
+        let mut container = Container {
+            direction: LayoutDirection::Row,
+            flex_wrap: Some(FlexWrap::WrapReverse),
+            ..Default::default()
+        };
+        assert_eq!(
+            container.layout_overflow_x(),
+            LayoutOverflow::Wrap { grid: false }
+        );
+
+        container.flex_wrap = Some(FlexWrap::NoWrap);
+        assert_eq!(container.layout_overflow_x(), LayoutOverflow::Expand);
+
+        container.flex_wrap = Some(FlexWrap::Wrap);
+        container.overflow_x = LayoutOverflow::Wrap { grid: true };
+        assert_eq!(
+            container.layout_overflow_x(),
+            LayoutOverflow::Wrap { grid: true }
+        );
+
+        container.direction = LayoutDirection::Column;
+        container.overflow_x = LayoutOverflow::Squash;
+        assert_eq!(container.layout_overflow_x(), LayoutOverflow::Squash);
+    }
+
     #[test_log::test]
     fn is_flex_container_returns_true_with_column_gap() {
         let container = Container {
@@ -1563,7 +1823,8 @@ mod test_element_methods {
                 fit: None,
                 source_set: None,
                 sizes: None,
-                loading: None
+                loading: None,
+                decoding: None
             }
             .allows_children()
         );
@@ -2051,6 +2312,149 @@ pub struct TextDecoration {
     pub thickness: Option<Number>,
 }
 
+/// Shadow drawn behind a container's text.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TextShadow {
+    /// Horizontal offset of the shadow.
+    pub x: Number,
+    /// Vertical offset of the shadow.
+    pub y: Number,
+    /// Blur radius of the shadow.
+    pub blur: Number,
+    /// Color of the shadow.
+    pub color: Color,
+}
+
+impl std::fmt::Display for TextShadow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {} {}", self.x, self.y, self.blur, self.color)
+    }
+}
+
+/// Easing curve used by a [`Transition`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum TimingFunction {
+    /// Slow start, fast middle, slow end.
+    #[default]
+    Ease,
+    /// Constant speed.
+    Linear,
+    /// Slow start.
+    EaseIn,
+    /// Slow end.
+    EaseOut,
+    /// Slow start and end.
+    EaseInOut,
+    /// Custom cubic Bézier curve with control points `(x1, y1, x2, y2)`.
+    CubicBezier(f32, f32, f32, f32),
+}
+
+impl std::fmt::Display for TimingFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ease => f.write_str("ease"),
+            Self::Linear => f.write_str("linear"),
+            Self::EaseIn => f.write_str("ease-in"),
+            Self::EaseOut => f.write_str("ease-out"),
+            Self::EaseInOut => f.write_str("ease-in-out"),
+            Self::CubicBezier(x1, y1, x2, y2) => {
+                write!(f, "cubic-bezier({x1},{y1},{x2},{y2})")
+            }
+        }
+    }
+}
+
+/// Animated transition of a single style property.
+///
+/// `duration` and `delay` are measured in milliseconds.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Transition {
+    /// Name of the property to animate (e.g. `opacity`, `all`).
+    pub property: String,
+    /// Length of the transition.
+    pub duration: Number,
+    /// Easing curve of the transition.
+    pub timing: TimingFunction,
+    /// Time to wait before starting the transition.
+    pub delay: Number,
+}
+
+impl std::fmt::Display for Transition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn write_millis(f: &mut std::fmt::Formatter<'_>, value: &Number) -> std::fmt::Result {
+            match value {
+                Number::Integer(_) | Number::Real(_) => write!(f, "{value}ms"),
+                _ => write!(f, "{value}"),
+            }
+        }
+
+        write!(f, "{} ", self.property)?;
+        write_millis(f, &self.duration)?;
+        write!(f, " {} ", self.timing)?;
+        write_millis(f, &self.delay)
+    }
+}
+
+/// Values accepted in [`Container::will_change`] hints: the `will-change` keywords and the
+/// style properties that can be animated.
+pub const WILL_CHANGE_PROPERTIES: &[&str] = &[
+    "auto",
+    "scroll-position",
+    "contents",
+    "all",
+    "transform",
+    "translate",
+    "scale",
+    "rotate",
+    "opacity",
+    "filter",
+    "backdrop-filter",
+    "clip-path",
+    "box-shadow",
+    "left",
+    "right",
+    "top",
+    "bottom",
+    "inset",
+    "width",
+    "min-width",
+    "max-width",
+    "height",
+    "min-height",
+    "max-height",
+    "flex",
+    "gap",
+    "column-gap",
+    "row-gap",
+    "margin",
+    "margin-left",
+    "margin-right",
+    "margin-top",
+    "margin-bottom",
+    "padding",
+    "padding-left",
+    "padding-right",
+    "padding-top",
+    "padding-bottom",
+    "background",
+    "background-color",
+    "color",
+    "font-size",
+    "font-weight",
+    "border",
+    "border-top",
+    "border-right",
+    "border-bottom",
+    "border-left",
+    "border-color",
+    "border-radius",
+    "border-top-left-radius",
+    "border-top-right-radius",
+    "border-bottom-left-radius",
+    "border-bottom-right-radius",
+    "visibility",
+];
+
 /// Flexbox sizing configuration with grow, shrink, and basis values.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Flex {
@@ -2101,6 +2505,19 @@ pub struct ConfigOverride {
     pub default: Option<OverrideItem>,
 }
 
+/// Style properties applied while the viewport is at least a given width.
+///
+/// Unlike [`ConfigOverride`]s, breakpoints aren't evaluated at the model level. The `html`
+/// renderers compile them into `@media (min-width: ...)` rules of a generated class, so
+/// the browser applies them on resize without a round-trip.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Breakpoint {
+    /// Minimum viewport width at which the overrides apply.
+    pub min_width: Number,
+    /// Style properties to override at or above `min_width`.
+    pub overrides: Vec<OverrideItem>,
+}
+
 /// Condition type for configuration overrides.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum OverrideCondition {
@@ -2148,8 +2565,12 @@ pub enum OverrideItem {
     JustifyContent(JustifyContent),
     /// Cross axis alignment override (flex-start, center, stretch, etc.).
     AlignItems(AlignItems),
+    /// Flex line wrapping override (nowrap, wrap, wrap-reverse).
+    FlexWrap(FlexWrap),
     /// Text alignment override (left, center, right, justify).
     TextAlign(TextAlign),
+    /// Inline base direction override (ltr, rtl).
+    TextDirection(TextDirection),
     /// White space handling override (normal, nowrap, pre, etc.).
     WhiteSpace(WhiteSpace),
     /// Text decoration styling override (underline, line-through, etc.).
@@ -2172,6 +2593,8 @@ pub enum OverrideItem {
     MaxHeight(Number),
     /// Flex sizing override (grow, shrink, basis).
     Flex(Flex),
+    /// Flex item order override.
+    Order(i32),
     /// Column gap override for flex/grid layouts.
     ColumnGap(Number),
     /// Row gap override for flex/grid layouts.
@@ -2194,6 +2617,8 @@ pub enum OverrideItem {
     Cursor(Cursor),
     /// User selection behavior override (auto, none, text, all).
     UserSelect(UserSelect),
+    /// Pointer event targeting override (auto, none).
+    PointerEvents(PointerEvents),
     /// Text wrapping behavior override (normal, break-word, anywhere).
     OverflowWrap(OverflowWrap),
     /// Text overflow handling override (clip, ellipsis).
@@ -2242,6 +2667,8 @@ pub enum OverrideItem {
     Hidden(bool),
     /// CSS visibility property override (visible, hidden, collapse).
     Visibility(Visibility),
+    /// Display type override (block, inline, inline-block, flex, grid, none).
+    Display(Display),
 }
 
 impl OverrideItem {
@@ -2257,12 +2684,15 @@ impl OverrideItem {
             Self::OverflowX(x) | Self::OverflowY(x) => serde_json::to_string(x),
             Self::JustifyContent(x) => serde_json::to_string(x),
             Self::AlignItems(x) => serde_json::to_string(x),
+            Self::FlexWrap(x) => serde_json::to_string(x),
             Self::TextAlign(x) => serde_json::to_string(x),
+            Self::TextDirection(x) => serde_json::to_string(x),
             Self::WhiteSpace(x) => serde_json::to_string(x),
             Self::TextDecoration(x) => serde_json::to_string(x),
             Self::Classes(x) | Self::FontFamily(x) => serde_json::to_string(x),
             Self::FontWeight(x) => serde_json::to_string(x),
             Self::Flex(x) => serde_json::to_string(x),
+            Self::Order(x) => serde_json::to_string(x),
             Self::Width(x)
             | Self::MinWidth(x)
             | Self::MaxWidth(x)
@@ -2294,6 +2724,7 @@ impl OverrideItem {
             | Self::GridCellSize(x) => serde_json::to_string(x),
             Self::Cursor(x) => serde_json::to_string(x),
             Self::UserSelect(x) => serde_json::to_string(x),
+            Self::PointerEvents(x) => serde_json::to_string(x),
             Self::OverflowWrap(x) => serde_json::to_string(x),
             Self::TextOverflow(x) => serde_json::to_string(x),
             Self::Position(x) => serde_json::to_string(x),
@@ -2304,6 +2735,7 @@ impl OverrideItem {
             Self::Background(x) | Self::Color(x) => serde_json::to_string(x),
             Self::Hidden(x) => serde_json::to_string(x),
             Self::Visibility(x) => serde_json::to_string(x),
+            Self::Display(x) => serde_json::to_string(x),
         }
     }
 
@@ -2319,12 +2751,15 @@ impl OverrideItem {
             Self::OverflowX(x) | Self::OverflowY(x) => serde_json::to_value(x),
             Self::JustifyContent(x) => serde_json::to_value(x),
             Self::AlignItems(x) => serde_json::to_value(x),
+            Self::FlexWrap(x) => serde_json::to_value(x),
             Self::TextAlign(x) => serde_json::to_value(x),
+            Self::TextDirection(x) => serde_json::to_value(x),
             Self::WhiteSpace(x) => serde_json::to_value(x),
             Self::TextDecoration(x) => serde_json::to_value(x),
             Self::Classes(x) | Self::FontFamily(x) => serde_json::to_value(x),
             Self::FontWeight(x) => serde_json::to_value(x),
             Self::Flex(x) => serde_json::to_value(x),
+            Self::Order(x) => serde_json::to_value(x),
             Self::Width(x)
             | Self::MinWidth(x)
             | Self::MaxWidth(x)
@@ -2356,6 +2791,7 @@ impl OverrideItem {
             | Self::GridCellSize(x) => serde_json::to_value(x),
             Self::Cursor(x) => serde_json::to_value(x),
             Self::UserSelect(x) => serde_json::to_value(x),
+            Self::PointerEvents(x) => serde_json::to_value(x),
             Self::OverflowWrap(x) => serde_json::to_value(x),
             Self::TextOverflow(x) => serde_json::to_value(x),
             Self::Position(x) => serde_json::to_value(x),
@@ -2366,6 +2802,7 @@ impl OverrideItem {
             Self::Background(x) | Self::Color(x) => serde_json::to_value(x),
             Self::Hidden(x) => serde_json::to_value(x),
             Self::Visibility(x) => serde_json::to_value(x),
+            Self::Display(x) => serde_json::to_value(x),
         }
     }
 
@@ -2378,12 +2815,15 @@ impl OverrideItem {
             Self::OverflowX(x) | Self::OverflowY(x) => Box::new(x),
             Self::JustifyContent(x) => Box::new(x),
             Self::AlignItems(x) => Box::new(x),
+            Self::FlexWrap(x) => Box::new(x),
             Self::TextAlign(x) => Box::new(x),
+            Self::TextDirection(x) => Box::new(x),
             Self::WhiteSpace(x) => Box::new(x),
             Self::TextDecoration(x) => Box::new(x),
             Self::Classes(x) | Self::FontFamily(x) => Box::new(x),
             Self::FontWeight(x) => Box::new(x),
             Self::Flex(x) => Box::new(x),
+            Self::Order(x) => Box::new(x),
             Self::Width(x)
             | Self::MinWidth(x)
             | Self::MaxWidth(x)
@@ -2415,6 +2855,7 @@ impl OverrideItem {
             | Self::GridCellSize(x) => Box::new(x),
             Self::Cursor(x) => Box::new(x),
             Self::UserSelect(x) => Box::new(x),
+            Self::PointerEvents(x) => Box::new(x),
             Self::OverflowWrap(x) => Box::new(x),
             Self::TextOverflow(x) => Box::new(x),
             Self::Position(x) => Box::new(x),
@@ -2425,6 +2866,7 @@ impl OverrideItem {
             Self::Background(x) | Self::Color(x) => Box::new(x),
             Self::Hidden(x) => Box::new(x),
             Self::Visibility(x) => Box::new(x),
+            Self::Display(x) => Box::new(x),
         }
     }
 
@@ -2484,6 +2926,15 @@ impl OverrideItem {
 
                 serde_json::to_string(&expr)
             }
+            Self::FlexWrap(x) => {
+                let mut expr = responsive.then::<&FlexWrap>(x);
+
+                if let Some(Self::FlexWrap(default)) = default {
+                    expr = expr.or_else(default);
+                }
+
+                serde_json::to_string(&expr)
+            }
             Self::TextAlign(x) => {
                 let mut expr = responsive.then::<&TextAlign>(x);
 
@@ -2493,6 +2944,15 @@ impl OverrideItem {
 
                 serde_json::to_string(&expr)
             }
+            Self::TextDirection(x) => {
+                let mut expr = responsive.then::<&TextDirection>(x);
+
+                if let Some(Self::TextDirection(default)) = default {
+                    expr = expr.or_else(default);
+                }
+
+                serde_json::to_string(&expr)
+            }
             Self::WhiteSpace(x) => {
                 let mut expr = responsive.then::<&WhiteSpace>(x);
 
@@ -2538,6 +2998,15 @@ impl OverrideItem {
 
                 serde_json::to_string(&expr)
             }
+            Self::Order(x) => {
+                let mut expr = responsive.then::<&i32>(x);
+
+                if let Some(Self::Order(default)) = default {
+                    expr = expr.or_else(default);
+                }
+
+                serde_json::to_string(&expr)
+            }
             Self::Width(x)
             | Self::MinWidth(x)
             | Self::MaxWidth(x)
@@ -2624,6 +3093,15 @@ impl OverrideItem {
 
                 serde_json::to_string(&expr)
             }
+            Self::PointerEvents(x) => {
+                let mut expr = responsive.then::<&PointerEvents>(x);
+
+                if let Some(Self::PointerEvents(default)) = default {
+                    expr = expr.or_else(default);
+                }
+
+                serde_json::to_string(&expr)
+            }
             Self::OverflowWrap(x) => {
                 let mut expr = responsive.then::<&OverflowWrap>(x);
 
@@ -2694,6 +3172,15 @@ impl OverrideItem {
                     expr = expr.or_else(default);
                 }
 
+                serde_json::to_string(&expr)
+            }
+            Self::Display(x) => {
+                let mut expr = responsive.then::<&Display>(x);
+
+                if let Some(Self::Display(default)) = default {
+                    expr = expr.or_else(default);
+                }
+
                 serde_json::to_string(&expr)
             }
         }
@@ -2731,12 +3218,15 @@ macro_rules! override_item {
             OverrideItem::OverflowX($name) | OverrideItem::OverflowY($name) => $action,
             OverrideItem::JustifyContent($name) => $action,
             OverrideItem::AlignItems($name) => $action,
+            OverrideItem::FlexWrap($name) => $action,
             OverrideItem::TextAlign($name) => $action,
+            OverrideItem::TextDirection($name) => $action,
             OverrideItem::WhiteSpace($name) => $action,
             OverrideItem::TextDecoration($name) => $action,
             OverrideItem::Classes($name) | OverrideItem::FontFamily($name) => $action,
             OverrideItem::FontWeight($name) => $action,
             OverrideItem::Flex($name) => $action,
+            OverrideItem::Order($name) => $action,
             OverrideItem::Width($name)
             | OverrideItem::MinWidth($name)
             | OverrideItem::MaxWidth($name)
@@ -2768,6 +3258,7 @@ macro_rules! override_item {
             | OverrideItem::GridCellSize($name) => $action,
             OverrideItem::Cursor($name) => $action,
             OverrideItem::UserSelect($name) => $action,
+            OverrideItem::PointerEvents($name) => $action,
             OverrideItem::OverflowWrap($name) => $action,
             OverrideItem::TextOverflow($name) => $action,
             OverrideItem::Position($name) => $action,
@@ -2778,6 +3269,7 @@ macro_rules! override_item {
             OverrideItem::Background($name) | OverrideItem::Color($name) => $action,
             OverrideItem::Hidden($name) | OverrideItem::Debug($name) => $action,
             OverrideItem::Visibility($name) => $action,
+            OverrideItem::Display($name) => $action,
         }
     }};
 }
@@ -2812,12 +3304,18 @@ pub struct Container {
     pub justify_content: Option<JustifyContent>,
     /// Cross axis alignment (flex-start, center, stretch, etc.).
     pub align_items: Option<AlignItems>,
+    /// Wrapping of children onto multiple flex lines.
+    pub flex_wrap: Option<FlexWrap>,
     /// Text alignment (left, center, right, justify).
     pub text_align: Option<TextAlign>,
+    /// Inline base direction, which logical inline-start/end sides are resolved against.
+    pub text_direction: Option<TextDirection>,
     /// White space handling (normal, nowrap, pre, etc.).
     pub white_space: Option<WhiteSpace>,
     /// Text decoration styling.
     pub text_decoration: Option<TextDecoration>,
+    /// Shadows drawn behind the text, painted in order.
+    pub text_shadow: Option<Vec<TextShadow>>,
     /// Font family list.
     pub font_family: Option<Vec<String>>,
     /// Font weight.
@@ -2836,6 +3334,11 @@ pub struct Container {
     pub max_height: Option<Number>,
     /// Flex sizing (grow, shrink, basis).
     pub flex: Option<Flex>,
+    /// Position among its flex siblings along the main axis (`order`).
+    ///
+    /// Layout places children by ascending order, keeping children with equal orders
+    /// (unset is `0`) in source order, without changing [`Container::children`].
+    pub order: Option<i32>,
     /// Gap between columns in flex/grid layouts.
     pub column_gap: Option<Number>,
     /// Gap between rows in flex/grid layouts.
@@ -2858,6 +3361,14 @@ pub struct Container {
     pub cursor: Option<Cursor>,
     /// User selection behavior.
     pub user_select: Option<UserSelect>,
+    /// Pointer event targeting behavior.
+    pub pointer_events: Option<PointerEvents>,
+    /// Animated transitions between style changes.
+    pub transition: Option<Vec<Transition>>,
+    /// Properties the renderer should prepare to animate (`will-change`).
+    ///
+    /// Paint-only compositing hint; ignored by layout.
+    pub will_change: Option<Vec<String>>,
     /// Text wrapping behavior.
     pub overflow_wrap: Option<OverflowWrap>,
     /// Text overflow handling.
@@ -2910,12 +3421,17 @@ pub struct Container {
     pub debug: Option<bool>,
     /// CSS visibility property.
     pub visibility: Option<Visibility>,
+    /// Display type. [`Display::None`] removes the container from layout entirely.
+    pub display: Option<Display>,
     /// Associated route for navigation.
     pub route: Option<Route>,
     /// Interactive actions bound to this container.
     pub actions: Vec<Action>,
     /// Conditional style overrides.
     pub overrides: Vec<ConfigOverride>,
+    /// Style overrides applied at viewport width breakpoints. Later breakpoints take
+    /// precedence where they overlap, so they should be ordered by ascending `min_width`.
+    pub breakpoints: Vec<Breakpoint>,
     /// Calculated left margin in pixels (requires `layout` feature).
     #[cfg(feature = "layout")]
     pub calculated_margin_left: Option<f32>,
@@ -3036,6 +3552,18 @@ impl AsRef<Self> for Container {
 }
 
 impl Container {
+    /// Returns the [`Container::will_change`] values that aren't known animatable properties
+    /// (see [`WILL_CHANGE_PROPERTIES`]).
+    #[must_use]
+    pub fn unknown_will_change_properties(&self) -> Vec<&str> {
+        self.will_change
+            .iter()
+            .flatten()
+            .map(String::as_str)
+            .filter(|value| !WILL_CHANGE_PROPERTIES.contains(value))
+            .collect()
+    }
+
     /// Returns an iterator over config overrides for this container and optionally its children.
     ///
     /// # Parameters
@@ -3058,6 +3586,28 @@ impl Container {
         iter
     }
 
+    /// Returns an iterator over breakpoints for this container and optionally its children.
+    ///
+    /// # Parameters
+    ///
+    /// * `recurse` - If true, includes breakpoints from all descendant containers
+    pub fn iter_breakpoints(&self, recurse: bool) -> impl Iterator<Item = (&Self, &Breakpoint)> {
+        let mut iter: Box<dyn Iterator<Item = (&Self, &Breakpoint)>> =
+            if self.breakpoints.is_empty() {
+                Box::new(std::iter::empty())
+            } else {
+                Box::new(self.breakpoints.iter().map(move |x| (self, x)))
+            };
+
+        if recurse {
+            for child in &self.children {
+                iter = Box::new(iter.chain(child.iter_breakpoints(true)));
+            }
+        }
+
+        iter
+    }
+
     /// Creates a breadth-first search iterator for traversing the container tree.
     ///
     /// Returns a `BfsPaths` structure that can be used to traverse containers level by level.
@@ -3555,11 +4105,11 @@ impl From<Vec<Self>> for Container {
 }
 
 fn visible_elements(elements: &[Container]) -> impl Iterator<Item = &Container> {
-    elements.iter().filter(|x| x.hidden != Some(true))
+    elements.iter().filter(|x| x.is_visible())
 }
 
 fn visible_elements_mut(elements: &mut [Container]) -> impl Iterator<Item = &mut Container> {
-    elements.iter_mut().filter(|x| x.hidden != Some(true))
+    elements.iter_mut().filter(|x| x.is_visible())
 }
 
 fn relative_positioned_elements(elements: &[Container]) -> impl Iterator<Item = &Container> {
@@ -3626,18 +4176,321 @@ impl Container {
     }
 }
 
+/// Shorthand builders for margin, padding and insets.
+///
+/// These only expand into the individual side fields (e.g. [`Container::padding_left`]), so
+/// the serialized model is unchanged.
+///
+/// The logical builders follow the horizontal writing mode: the block axis maps to top and
+/// bottom and the inline axis to left and right, with the inline-start side resolved
+/// against the container's [`Container::text_direction`] (left to right by default), so set
+/// the direction before using them.
+impl Container {
+    /// Whether the inline-start side of this container is its physical left side.
+    fn is_inline_start_left(&self) -> bool {
+        self.text_direction.unwrap_or_default().is_start_left()
+    }
+
+    /// Sets the top, right, bottom and left offsets of a positioned container.
+    #[must_use]
+    pub fn with_inset(self, inset: impl Into<Number>) -> Self {
+        let inset = inset.into();
+        self.with_inset_inline(inset.clone())
+            .with_inset_block(inset)
+    }
+
+    /// Sets the left and right offsets.
+    #[must_use]
+    pub fn with_inset_inline(mut self, inset: impl Into<Number>) -> Self {
+        let inset = inset.into();
+        self.left = Some(inset.clone());
+        self.right = Some(inset);
+        self
+    }
+
+    /// Sets the top and bottom offsets.
+    #[must_use]
+    pub fn with_inset_block(mut self, inset: impl Into<Number>) -> Self {
+        let inset = inset.into();
+        self.top = Some(inset.clone());
+        self.bottom = Some(inset);
+        self
+    }
+
+    /// Sets the offset from the inline-start side.
+    #[must_use]
+    pub fn with_inset_inline_start(mut self, inset: impl Into<Number>) -> Self {
+        if self.is_inline_start_left() {
+            self.left = Some(inset.into());
+        } else {
+            self.right = Some(inset.into());
+        }
+        self
+    }
+
+    /// Sets the offset from the inline-end side.
+    #[must_use]
+    pub fn with_inset_inline_end(mut self, inset: impl Into<Number>) -> Self {
+        if self.is_inline_start_left() {
+            self.right = Some(inset.into());
+        } else {
+            self.left = Some(inset.into());
+        }
+        self
+    }
+
+    /// Sets the padding on all four sides.
+    #[must_use]
+    pub fn with_padding(self, padding: impl Into<Number>) -> Self {
+        let padding = padding.into();
+        self.with_padding_x(padding.clone()).with_padding_y(padding)
+    }
+
+    /// Sets the left and right padding.
+    #[must_use]
+    pub fn with_padding_x(mut self, padding: impl Into<Number>) -> Self {
+        let padding = padding.into();
+        self.padding_left = Some(padding.clone());
+        self.padding_right = Some(padding);
+        self
+    }
+
+    /// Sets the top and bottom padding.
+    #[must_use]
+    pub fn with_padding_y(mut self, padding: impl Into<Number>) -> Self {
+        let padding = padding.into();
+        self.padding_top = Some(padding.clone());
+        self.padding_bottom = Some(padding);
+        self
+    }
+
+    /// Sets the padding on both inline sides, the same as [`Self::with_padding_x`].
+    #[must_use]
+    pub fn with_padding_inline(self, padding: impl Into<Number>) -> Self {
+        self.with_padding_x(padding)
+    }
+
+    /// Sets the padding on both block sides, the same as [`Self::with_padding_y`].
+    #[must_use]
+    pub fn with_padding_block(self, padding: impl Into<Number>) -> Self {
+        self.with_padding_y(padding)
+    }
+
+    /// Sets the padding on the inline-start side.
+    #[must_use]
+    pub fn with_padding_inline_start(mut self, padding: impl Into<Number>) -> Self {
+        if self.is_inline_start_left() {
+            self.padding_left = Some(padding.into());
+        } else {
+            self.padding_right = Some(padding.into());
+        }
+        self
+    }
+
+    /// Sets the padding on the inline-end side.
+    #[must_use]
+    pub fn with_padding_inline_end(mut self, padding: impl Into<Number>) -> Self {
+        if self.is_inline_start_left() {
+            self.padding_right = Some(padding.into());
+        } else {
+            self.padding_left = Some(padding.into());
+        }
+        self
+    }
+
+    /// Sets the margin on all four sides.
+    #[must_use]
+    pub fn with_margin(self, margin: impl Into<Number>) -> Self {
+        let margin = margin.into();
+        self.with_margin_x(margin.clone()).with_margin_y(margin)
+    }
+
+    /// Sets the left and right margin.
+    #[must_use]
+    pub fn with_margin_x(mut self, margin: impl Into<Number>) -> Self {
+        let margin = margin.into();
+        self.margin_left = Some(margin.clone());
+        self.margin_right = Some(margin);
+        self
+    }
+
+    /// Sets the top and bottom margin.
+    #[must_use]
+    pub fn with_margin_y(mut self, margin: impl Into<Number>) -> Self {
+        let margin = margin.into();
+        self.margin_top = Some(margin.clone());
+        self.margin_bottom = Some(margin);
+        self
+    }
+
+    /// Sets the margin on both inline sides, the same as [`Self::with_margin_x`].
+    #[must_use]
+    pub fn with_margin_inline(self, margin: impl Into<Number>) -> Self {
+        self.with_margin_x(margin)
+    }
+
+    /// Sets the margin on both block sides, the same as [`Self::with_margin_y`].
+    #[must_use]
+    pub fn with_margin_block(self, margin: impl Into<Number>) -> Self {
+        self.with_margin_y(margin)
+    }
+
+    /// Sets the margin on the inline-start side.
+    #[must_use]
+    pub fn with_margin_inline_start(mut self, margin: impl Into<Number>) -> Self {
+        if self.is_inline_start_left() {
+            self.margin_left = Some(margin.into());
+        } else {
+            self.margin_right = Some(margin.into());
+        }
+        self
+    }
+
+    /// Sets the margin on the inline-end side.
+    #[must_use]
+    pub fn with_margin_inline_end(mut self, margin: impl Into<Number>) -> Self {
+        if self.is_inline_start_left() {
+            self.margin_right = Some(margin.into());
+        } else {
+            self.margin_left = Some(margin.into());
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod test_spacing_shorthands {
+    use crate::{Container, Number};
+    use hyperchad_transformer_models::TextDirection;
+
+    #[test_log::test]
+    fn with_padding_sets_all_sides() {
+        let container = Container::default().with_padding(10);
+
+        assert_eq!(container.padding_top, Some(Number::Integer(10)));
+        assert_eq!(container.padding_right, Some(Number::Integer(10)));
+        assert_eq!(container.padding_bottom, Some(Number::Integer(10)));
+        assert_eq!(container.padding_left, Some(Number::Integer(10)));
+        assert_eq!(container.margin_top, None);
+    }
+
+    #[test_log::test]
+    fn with_padding_x_sets_only_left_and_right() {
+        let container = Container::default().with_padding_x(10);
+
+        assert_eq!(container.padding_left, Some(Number::Integer(10)));
+        assert_eq!(container.padding_right, Some(Number::Integer(10)));
+        assert_eq!(container.padding_top, None);
+        assert_eq!(container.padding_bottom, None);
+    }
+
+    #[test_log::test]
+    fn with_padding_y_overrides_only_top_and_bottom() {
+        let container = Container::default()
+            .with_padding(10)
+            .with_padding_y(Number::RealPercent(5.0));
+
+        assert_eq!(container.padding_top, Some(Number::RealPercent(5.0)));
+        assert_eq!(container.padding_bottom, Some(Number::RealPercent(5.0)));
+        assert_eq!(container.padding_left, Some(Number::Integer(10)));
+        assert_eq!(container.padding_right, Some(Number::Integer(10)));
+    }
+
+    #[test_log::test]
+    fn margin_shorthands_set_margin_fields() {
+        let container = Container::default().with_margin_x(4).with_margin_y(8);
+
+        assert_eq!(container.margin_left, Some(Number::Integer(4)));
+        assert_eq!(container.margin_right, Some(Number::Integer(4)));
+        assert_eq!(container.margin_top, Some(Number::Integer(8)));
+        assert_eq!(container.margin_bottom, Some(Number::Integer(8)));
+        assert_eq!(container.padding_left, None);
+
+        let container = Container::default().with_margin(2);
+        assert_eq!(container.margin_top, Some(Number::Integer(2)));
+        assert_eq!(container.margin_left, Some(Number::Integer(2)));
+    }
+
+    #[test_log::test]
+    fn with_inset_sets_all_offsets() {
+        let container = Container::default().with_inset(0);
+
+        assert_eq!(container.top, Some(Number::Integer(0)));
+        assert_eq!(container.right, Some(Number::Integer(0)));
+        assert_eq!(container.bottom, Some(Number::Integer(0)));
+        assert_eq!(container.left, Some(Number::Integer(0)));
+        assert_eq!(container.margin_top, None);
+    }
+
+    #[test_log::test]
+    fn inset_block_and_inline_set_only_their_axis() {
+        let container = Container::default().with_inset_block(Number::RealPercent(10.0));
+
+        assert_eq!(container.top, Some(Number::RealPercent(10.0)));
+        assert_eq!(container.bottom, Some(Number::RealPercent(10.0)));
+        assert_eq!(container.left, None);
+        assert_eq!(container.right, None);
+
+        let container = Container::default().with_inset(5).with_inset_inline(2);
+        assert_eq!(container.left, Some(Number::Integer(2)));
+        assert_eq!(container.right, Some(Number::Integer(2)));
+        assert_eq!(container.top, Some(Number::Integer(5)));
+    }
+
+    #[test_log::test]
+    fn logical_sides_map_to_physical_sides_left_to_right() {
+        let container = Container::default()
+            .with_margin_inline_start(1)
+            .with_padding_inline_end(2)
+            .with_inset_inline_start(3)
+            .with_margin_block(4)
+            .with_padding_inline(5);
+
+        assert_eq!(container.margin_left, Some(Number::Integer(1)));
+        assert_eq!(container.margin_right, None);
+        assert_eq!(container.padding_left, Some(Number::Integer(5)));
+        assert_eq!(container.padding_right, Some(Number::Integer(5)));
+        assert_eq!(container.left, Some(Number::Integer(3)));
+        assert_eq!(container.right, None);
+        assert_eq!(container.margin_top, Some(Number::Integer(4)));
+        assert_eq!(container.margin_bottom, Some(Number::Integer(4)));
+    }
+
+    #[test_log::test]
+    fn logical_sides_map_to_physical_sides_right_to_left() {
+        let container = Container {
+            text_direction: Some(TextDirection::Rtl),
+            ..Default::default()
+        }
+        .with_margin_inline_start(1)
+        .with_padding_inline_start(2)
+        .with_padding_inline_end(3)
+        .with_inset_inline_end(4);
+
+        assert_eq!(container.margin_right, Some(Number::Integer(1)));
+        assert_eq!(container.margin_left, None);
+        assert_eq!(container.padding_right, Some(Number::Integer(2)));
+        assert_eq!(container.padding_left, Some(Number::Integer(3)));
+        assert_eq!(container.left, Some(Number::Integer(4)));
+        assert_eq!(container.right, None);
+    }
+}
+
 #[cfg_attr(feature = "profiling", profiling::all_functions)]
 impl Container {
     /// Checks if this container is visible (not hidden).
     #[must_use]
     pub fn is_visible(&self) -> bool {
-        self.hidden != Some(true)
+        !self.is_hidden()
     }
 
-    /// Checks if this container is hidden.
+    /// Checks if this container is hidden, either with `hidden` or [`Display::None`].
+    ///
+    /// Hidden containers are removed from layout entirely, unlike containers with
+    /// [`Visibility::Hidden`], which still occupy space.
     #[must_use]
     pub fn is_hidden(&self) -> bool {
-        self.hidden == Some(true)
+        self.hidden == Some(true) || self.display == Some(Display::None)
     }
 
     /// Checks if this container is a span element.
@@ -3661,10 +4514,33 @@ impl Container {
         self.direction == LayoutDirection::Row
             || self.justify_content.is_some()
             || self.align_items.is_some()
+            || self.flex_wrap.is_some()
             || self.children.iter().any(|x| x.flex.is_some())
             || self.column_gap.is_some()
     }
 
+    /// Horizontal overflow behavior used by the layout engine.
+    ///
+    /// Row containers whose [`FlexWrap`] wraps lay their children out on multiple lines
+    /// like [`LayoutOverflow::Wrap`] without a grid.
+    #[must_use]
+    pub fn layout_overflow_x(&self) -> LayoutOverflow {
+        if self.direction == LayoutDirection::Row
+            && self.flex_wrap.is_some_and(FlexWrap::is_wrapping)
+            && !matches!(self.overflow_x, LayoutOverflow::Wrap { .. })
+        {
+            LayoutOverflow::Wrap { grid: false }
+        } else {
+            self.overflow_x
+        }
+    }
+
+    /// Vertical overflow behavior used by the layout engine.
+    #[must_use]
+    pub const fn layout_overflow_y(&self) -> LayoutOverflow {
+        self.overflow_y
+    }
+
     /// Returns an iterator over visible child elements.
     pub fn visible_elements(&self) -> impl Iterator<Item = &Self> {
         visible_elements(&self.children)
@@ -3685,6 +4561,18 @@ impl Container {
         relative_positioned_elements_mut(&mut self.children)
     }
 
+    /// Returns the relatively positioned child elements sorted by [`Container::order`],
+    /// keeping children with equal orders in source order.
+    ///
+    /// Unlike [`Self::relative_positioned_elements_mut`] this collects and sorts the
+    /// children, so layout collects them once per parent where order matters.
+    #[must_use]
+    pub fn relative_positioned_elements_by_order_mut(&mut self) -> Vec<&mut Self> {
+        let mut elements = relative_positioned_elements_mut(&mut self.children).collect::<Vec<_>>();
+        elements.sort_by_key(|x| x.order.unwrap_or_default());
+        elements
+    }
+
     /// Returns an iterator over absolutely positioned child elements.
     pub fn absolute_positioned_elements(&self) -> impl Iterator<Item = &Self> {
         absolute_positioned_elements(&self.children)
@@ -4143,6 +5031,8 @@ pub enum Element {
         sizes: Option<Number>,
         /// Loading strategy (lazy, eager).
         loading: Option<ImageLoading>,
+        /// Decoding hint (auto, sync, async).
+        decoding: Option<ImageDecoding>,
     },
     /// Anchor element for hyperlinks.
     Anchor {
@@ -4379,7 +5269,9 @@ impl Container {
                 self.justify_content.map(OverrideItem::JustifyContent)
             }
             OverrideItem::AlignItems(_) => self.align_items.map(OverrideItem::AlignItems),
+            OverrideItem::FlexWrap(_) => self.flex_wrap.map(OverrideItem::FlexWrap),
             OverrideItem::TextAlign(_) => self.text_align.map(OverrideItem::TextAlign),
+            OverrideItem::TextDirection(_) => self.text_direction.map(OverrideItem::TextDirection),
             OverrideItem::WhiteSpace(_) => self.white_space.map(OverrideItem::WhiteSpace),
             OverrideItem::TextDecoration(_) => self
                 .text_decoration
@@ -4394,6 +5286,7 @@ impl Container {
             OverrideItem::MinHeight(_) => self.min_height.clone().map(OverrideItem::MinHeight),
             OverrideItem::MaxHeight(_) => self.max_height.clone().map(OverrideItem::MaxHeight),
             OverrideItem::Flex(_) => self.flex.clone().map(OverrideItem::Flex),
+            OverrideItem::Order(_) => self.order.map(OverrideItem::Order),
             OverrideItem::ColumnGap(_) => self.column_gap.clone().map(OverrideItem::ColumnGap),
             OverrideItem::RowGap(_) => self.row_gap.clone().map(OverrideItem::RowGap),
             OverrideItem::Opacity(_) => self.opacity.clone().map(OverrideItem::Opacity),
@@ -4405,6 +5298,7 @@ impl Container {
             OverrideItem::TranslateY(_) => self.translate_y.clone().map(OverrideItem::TranslateY),
             OverrideItem::Cursor(_) => self.cursor.map(OverrideItem::Cursor),
             OverrideItem::UserSelect(_) => self.user_select.map(OverrideItem::UserSelect),
+            OverrideItem::PointerEvents(_) => self.pointer_events.map(OverrideItem::PointerEvents),
             OverrideItem::OverflowWrap(_) => self.overflow_wrap.map(OverrideItem::OverflowWrap),
             OverrideItem::TextOverflow(_) => self.text_overflow.map(OverrideItem::TextOverflow),
             OverrideItem::Position(_) => self.position.map(OverrideItem::Position),
@@ -4455,6 +5349,7 @@ impl Container {
             OverrideItem::Color(_) => self.color.map(OverrideItem::Color),
             OverrideItem::Hidden(_) => self.hidden.map(OverrideItem::Hidden),
             OverrideItem::Visibility(_) => self.visibility.map(OverrideItem::Visibility),
+            OverrideItem::Display(_) => self.display.map(OverrideItem::Display),
         }
     }
 
@@ -4475,10 +5370,12 @@ impl Container {
                 sizes,
                 alt,
                 loading,
+                decoding,
                 ..
             } => {
                 attrs.add_opt("sx-fit", *fit);
                 attrs.add_opt("loading", *loading);
+                attrs.add_opt("decoding", *decoding);
                 attrs.add_opt("srcset", source_set.as_ref());
                 attrs.add_opt("sizes", sizes.as_ref());
                 attrs.add_opt("alt", alt.as_ref());
@@ -4647,8 +5544,10 @@ impl Container {
 
         attrs.add_opt("sx-justify-content", self.justify_content.as_ref());
         attrs.add_opt("sx-align-items", self.align_items.as_ref());
+        attrs.add_opt("sx-flex-wrap", self.flex_wrap.as_ref());
 
         attrs.add_opt("sx-text-align", self.text_align.as_ref());
+        attrs.add_opt("sx-text-direction", self.text_direction.as_ref());
         attrs.add_opt("sx-white-space", self.white_space.as_ref());
 
         if let Some(text_decoration) = &self.text_decoration {
@@ -4705,6 +5604,8 @@ impl Container {
             attrs.add("sx-flex-basis", &flex.basis);
         }
 
+        attrs.add_opt("sx-order", self.order);
+
         attrs.add_opt("sx-col-gap", self.column_gap.as_ref());
         attrs.add_opt("sx-row-gap", self.row_gap.as_ref());
         attrs.add_opt("sx-grid-cell-size", self.grid_cell_size.as_ref());
@@ -4721,6 +5622,30 @@ impl Container {
 
         attrs.add_opt("sx-cursor", self.cursor.as_ref());
         attrs.add_opt("sx-user-select", self.user_select.as_ref());
+        attrs.add_opt("sx-pointer-events", self.pointer_events.as_ref());
+        if let Some(transition) = &self.transition {
+            attrs.add(
+                "sx-transition",
+                transition
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+        }
+        if let Some(will_change) = &self.will_change {
+            attrs.add("sx-will-change", will_change.join(","));
+        }
+        if let Some(text_shadow) = &self.text_shadow {
+            attrs.add(
+                "sx-text-shadow",
+                text_shadow
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+        }
         attrs.add_opt("sx-overflow-wrap", self.overflow_wrap.as_ref());
         attrs.add_opt("sx-text-overflow", self.text_overflow.as_ref());
 
@@ -4736,6 +5661,7 @@ impl Container {
 
         attrs.add_opt("sx-hidden", self.hidden.as_ref());
         attrs.add_opt("sx-visibility", self.visibility.as_ref());
+        attrs.add_opt("sx-display", self.display.as_ref());
 
         attrs.add_opt("sx-font-size", self.font_size.as_ref());
         attrs.add_opt("sx-color", self.color.as_ref());
@@ -4874,8 +5800,7 @@ impl Container {
 
         #[cfg(feature = "layout")]
         if with_debug_attrs {
-            let skip_default =
-                matches!(var("SKIP_DEFAULT_DEBUG_ATTRS").as_deref(), Ok("1" | "true"));
+            let skip_default = env_flag("SKIP_DEFAULT_DEBUG_ATTRS");
 
             attrs.add_opt_skip_default("calc-x", self.calculated_x, skip_default);
             attrs.add_opt_skip_default("calc-y", self.calculated_y, skip_default);
@@ -5090,6 +6015,7 @@ impl Container {
                 }
             }
             Element::Text { value } => {
+                let value = html_escape::encode_text(value);
                 if wrap_raw_in_element {
                     f.write_fmt(format_args!(
                         "<text{attrs}>",
@@ -5150,16 +6076,12 @@ impl Container {
                 f.write_fmt(format_args!("</section>"))?;
             }
             Element::Form { action, method } => {
-                let action_attr = action
-                    .as_ref()
-                    .map_or_else(String::new, |a| format!(r#" action="{a}""#));
-                let method_attr = method
-                    .as_ref()
-                    .map_or_else(String::new, |m| format!(r#" method="{m}""#));
                 f.write_fmt(format_args!(
-                    "<form{action}{method}{attrs}>",
-                    action = action_attr,
-                    method = method_attr,
+                    "<form{form_attrs}{attrs}>",
+                    form_attrs = Attrs::new()
+                        .with_attr_opt("action", action.to_owned())
+                        .with_attr_opt("method", method.to_owned())
+                        .to_string_pad_left(),
                     attrs = self.attrs_to_string_pad_left(with_debug_attrs)
                 ))?;
                 display_elements(&self.children, f, with_debug_attrs, wrap_raw_in_element)?;
@@ -5503,7 +6425,9 @@ const fn override_item_to_attr_name(item: &OverrideItem) -> &'static str {
         OverrideItem::GridCellSize(..) => "sx-grid-cell-size",
         OverrideItem::JustifyContent(..) => "sx-justify-content",
         OverrideItem::AlignItems(..) => "sx-align-items",
+        OverrideItem::FlexWrap(..) => "sx-flex-wrap",
         OverrideItem::TextAlign(..) => "sx-text-align",
+        OverrideItem::TextDirection(..) => "sx-text-direction",
         OverrideItem::WhiteSpace(..) => "sx-white-space",
         OverrideItem::TextDecoration(..) => "sx-text-decoration",
         OverrideItem::FontFamily(..) => "sx-font-family",
@@ -5515,6 +6439,7 @@ const fn override_item_to_attr_name(item: &OverrideItem) -> &'static str {
         OverrideItem::MinHeight(..) => "sx-min-height",
         OverrideItem::MaxHeight(..) => "sx-max-height",
         OverrideItem::Flex(..) => "sx-flex",
+        OverrideItem::Order(..) => "sx-order",
         OverrideItem::ColumnGap(..) => "sx-column-gap",
         OverrideItem::RowGap(..) => "sx-row-gap",
         OverrideItem::Opacity(..) => "sx-opacity",
@@ -5526,6 +6451,7 @@ const fn override_item_to_attr_name(item: &OverrideItem) -> &'static str {
         OverrideItem::TranslateY(..) => "sx-translate-y",
         OverrideItem::Cursor(..) => "sx-cursor",
         OverrideItem::UserSelect(..) => "sx-user-select",
+        OverrideItem::PointerEvents(..) => "sx-pointer-events",
         OverrideItem::OverflowWrap(..) => "sx-overflow-wrap",
         OverrideItem::TextOverflow(..) => "sx-text-overflow",
         OverrideItem::Position(..) => "sx-position",
@@ -5550,6 +6476,7 @@ const fn override_item_to_attr_name(item: &OverrideItem) -> &'static str {
         OverrideItem::Color(..) => "sx-color",
         OverrideItem::Hidden(..) => "sx-hidden",
         OverrideItem::Visibility(..) => "sx-visibility",
+        OverrideItem::Display(..) => "sx-display",
     }
 }
 
@@ -5562,12 +6489,12 @@ impl std::fmt::Display for Container {
                     if cfg!(test) {
                         true
                     } else {
-                        matches!(var("DEBUG_ATTRS").as_deref(), Ok("1" | "true"))
+                        env_flag("DEBUG_ATTRS")
                     },
                     if cfg!(test) {
                         true
                     } else {
-                        matches!(var("DEBUG_RAW_ATTRS").as_deref(), Ok("1" | "true"))
+                        env_flag("DEBUG_RAW_ATTRS")
                     },
                     #[cfg(feature = "format")]
                     true,
@@ -33,10 +33,10 @@ pub fn split_on_char(
 ) -> Result<Option<(&str, &str)>, GetNumberError> {
     let mut pop_stack = vec![];
 
-    for (i, char) in haystack.chars().enumerate().skip(start) {
+    for (i, char) in haystack.char_indices().skip(start) {
         if pop_stack.is_empty() && char == needle {
             let (a, b) = haystack.split_at(i);
-            return Ok(Some((a, &b[1..])));
+            return Ok(Some((a, &b[needle.len_utf8()..])));
         }
 
         match char {
@@ -44,27 +44,23 @@ pub fn split_on_char(
                 pop_stack.insert(0, '}');
             }
             '}' => {
-                moosicbox_assert::assert_or_err!(
-                    pop_stack.first() == Some(&'}'),
-                    GetNumberError::Parse(format!(
+                if pop_stack.first() != Some(&'}') {
+                    return Err(GetNumberError::Parse(format!(
                         "Failed to find ending match to '{{' in \"{haystack}\""
-                    )),
-                );
+                    )));
+                }
                 pop_stack.remove(0);
             }
             '(' => {
                 pop_stack.insert(0, ')');
             }
             ')' => {
-                moosicbox_assert::assert_or_err!(
-                    pop_stack.first() == Some(&')'),
-                    GetNumberError::Parse(format!(
+                if pop_stack.first() != Some(&')') {
+                    return Err(GetNumberError::Parse(format!(
                         "Failed to find ending match to '(' in \"{haystack}\""
-                    )),
-                );
-                if pop_stack.first() == Some(&')') {
-                    pop_stack.remove(0);
+                    )));
                 }
+                pop_stack.remove(0);
             }
             _ => {}
         }
@@ -263,21 +259,29 @@ fn parse_signed_operation(
 ///
 /// Supports integers, floats, and various CSS unit suffixes.
 ///
+/// Surrounding whitespace is ignored, but any other trailing characters after the unit are
+/// rejected.
+///
 /// # Errors
 ///
 /// * If the input string is not a valid number.
+/// * If the input has an unknown unit or trailing characters.
+/// * If the input is a malformed or unterminated `calc()` expression.
+/// * If the number is not finite (e.g. `NaN` or `inf`).
 #[allow(clippy::too_many_lines)]
 pub fn parse_number(number: &str) -> Result<Number, GetNumberError> {
     static EPSILON: f32 = 0.00001;
 
-    let mut number = if let Ok(calc) = parse_calc(number) {
-        calc
-    } else if let Some((number, _)) = number.split_once("dvw") {
+    let input = number.trim();
+
+    let mut number = if input.starts_with("calc") {
+        parse_calc(input)?
+    } else if let Some(number) = input.strip_suffix("dvw") {
         if number.contains('.') {
             Number::RealDvw(
                 number
                     .parse::<f32>()
-                    .map_err(|_| GetNumberError::Parse(number.to_string()))?,
+                    .map_err(|_| GetNumberError::Parse(input.to_string()))?,
             )
         } else {
             number
@@ -285,14 +289,14 @@ pub fn parse_number(number: &str) -> Result<Number, GetNumberError> {
                 .ok()
                 .map(Number::IntegerDvw)
                 .or_else(|| number.parse::<f32>().ok().map(Number::RealDvw))
-                .ok_or_else(|| GetNumberError::Parse(number.to_string()))?
+                .ok_or_else(|| GetNumberError::Parse(input.to_string()))?
         }
-    } else if let Some((number, _)) = number.split_once("dvh") {
+    } else if let Some(number) = input.strip_suffix("dvh") {
         if number.contains('.') {
             Number::RealDvh(
                 number
                     .parse::<f32>()
-                    .map_err(|_| GetNumberError::Parse(number.to_string()))?,
+                    .map_err(|_| GetNumberError::Parse(input.to_string()))?,
             )
         } else {
             number
@@ -300,14 +304,14 @@ pub fn parse_number(number: &str) -> Result<Number, GetNumberError> {
                 .ok()
                 .map(Number::IntegerDvh)
                 .or_else(|| number.parse::<f32>().ok().map(Number::RealDvh))
-                .ok_or_else(|| GetNumberError::Parse(number.to_string()))?
+                .ok_or_else(|| GetNumberError::Parse(input.to_string()))?
         }
-    } else if let Some((number, _)) = number.split_once("vw") {
+    } else if let Some(number) = input.strip_suffix("vw") {
         if number.contains('.') {
             Number::RealVw(
                 number
                     .parse::<f32>()
-                    .map_err(|_| GetNumberError::Parse(number.to_string()))?,
+                    .map_err(|_| GetNumberError::Parse(input.to_string()))?,
             )
         } else {
             number
@@ -315,14 +319,14 @@ pub fn parse_number(number: &str) -> Result<Number, GetNumberError> {
                 .ok()
                 .map(Number::IntegerVw)
                 .or_else(|| number.parse::<f32>().ok().map(Number::RealVw))
-                .ok_or_else(|| GetNumberError::Parse(number.to_string()))?
+                .ok_or_else(|| GetNumberError::Parse(input.to_string()))?
         }
-    } else if let Some((number, _)) = number.split_once("vh") {
+    } else if let Some(number) = input.strip_suffix("vh") {
         if number.contains('.') {
             Number::RealVh(
                 number
                     .parse::<f32>()
-                    .map_err(|_| GetNumberError::Parse(number.to_string()))?,
+                    .map_err(|_| GetNumberError::Parse(input.to_string()))?,
             )
         } else {
             number
@@ -330,14 +334,14 @@ pub fn parse_number(number: &str) -> Result<Number, GetNumberError> {
                 .ok()
                 .map(Number::IntegerVh)
                 .or_else(|| number.parse::<f32>().ok().map(Number::RealVh))
-                .ok_or_else(|| GetNumberError::Parse(number.to_string()))?
+                .ok_or_else(|| GetNumberError::Parse(input.to_string()))?
         }
-    } else if let Some((number, _)) = number.split_once('%') {
+    } else if let Some(number) = input.strip_suffix('%') {
         if number.contains('.') {
             Number::RealPercent(
                 number
                     .parse::<f32>()
-                    .map_err(|_| GetNumberError::Parse(number.to_string()))?,
+                    .map_err(|_| GetNumberError::Parse(input.to_string()))?,
             )
         } else {
             number
@@ -345,23 +349,23 @@ pub fn parse_number(number: &str) -> Result<Number, GetNumberError> {
                 .ok()
                 .map(Number::IntegerPercent)
                 .or_else(|| number.parse::<f32>().ok().map(Number::RealPercent))
-                .ok_or_else(|| GetNumberError::Parse(number.to_string()))?
+                .ok_or_else(|| GetNumberError::Parse(input.to_string()))?
         }
-    } else if number.contains('.') {
-        let number = number.strip_suffix("px").unwrap_or(number);
+    } else if input.contains('.') {
+        let number = input.strip_suffix("px").unwrap_or(input);
         Number::Real(
             number
                 .parse::<f32>()
-                .map_err(|_| GetNumberError::Parse(number.to_string()))?,
+                .map_err(|_| GetNumberError::Parse(input.to_string()))?,
         )
     } else {
-        let number = number.strip_suffix("px").unwrap_or(number);
+        let number = input.strip_suffix("px").unwrap_or(input);
         number
             .parse::<i64>()
             .ok()
             .map(Number::Integer)
             .or_else(|| number.parse::<f32>().ok().map(Number::Real))
-            .ok_or_else(|| GetNumberError::Parse(number.to_string()))?
+            .ok_or_else(|| GetNumberError::Parse(input.to_string()))?
     };
 
     match &mut number {
@@ -371,6 +375,9 @@ pub fn parse_number(number: &str) -> Result<Number, GetNumberError> {
         | Number::RealVh(x)
         | Number::RealDvw(x)
         | Number::RealDvh(x) => {
+            if !x.is_finite() {
+                return Err(GetNumberError::Parse(input.to_string()));
+            }
             if x.is_sign_negative() && x.abs() < EPSILON {
                 *x = 0.0;
             }
@@ -0,0 +1,97 @@
+//! Rendering environment defaults.
+//!
+//! Layout resolves viewport units (`vw`, `vh`, `dvw`, `dvh`) against the size of the root
+//! container and inherits font sizes from a root font size. In a browser or a window these
+//! come from the display, but headless rendering (e.g. generating static pages) has no
+//! display to ask. A [`RenderContext`] supplies them explicitly so the same tree always
+//! resolves to the same pixel dimensions.
+//!
+//! This module is also the one place the transformer reads environment variables from.
+
+use switchy_env::var;
+
+/// Root font size in pixels, matching the browser default.
+pub const DEFAULT_ROOT_FONT_SIZE: f32 = 16.0;
+
+/// Viewport width in pixels.
+pub const DEFAULT_VIEW_WIDTH: f32 = 1280.0;
+
+/// Viewport height in pixels.
+pub const DEFAULT_VIEW_HEIGHT: f32 = 720.0;
+
+/// Environment a container tree is rendered in.
+///
+/// All sizes are in CSS pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderContext {
+    /// Font size of the root container, inherited by descendants without a font size
+    pub root_font_size: f32,
+    /// Width of the viewport that `vw` and `dvw` units resolve against
+    pub view_width: f32,
+    /// Height of the viewport that `vh` and `dvh` units resolve against
+    pub view_height: f32,
+}
+
+impl Default for RenderContext {
+    fn default() -> Self {
+        Self::new(DEFAULT_VIEW_WIDTH, DEFAULT_VIEW_HEIGHT)
+    }
+}
+
+impl RenderContext {
+    /// Creates a context for a `view_width` by `view_height` viewport, with the default
+    /// root font size.
+    #[must_use]
+    pub const fn new(view_width: f32, view_height: f32) -> Self {
+        Self {
+            root_font_size: DEFAULT_ROOT_FONT_SIZE,
+            view_width,
+            view_height,
+        }
+    }
+
+    /// Sets the root font size.
+    #[must_use]
+    pub const fn with_root_font_size(mut self, root_font_size: f32) -> Self {
+        self.root_font_size = root_font_size;
+        self
+    }
+}
+
+/// Whether the environment variable `name` is set to `1` or `true`.
+pub(crate) fn env_flag(name: &str) -> bool {
+    matches!(var(name).as_deref(), Ok("1" | "true"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn new_keeps_default_font_size() {
+        let context = RenderContext::new(800.0, 600.0);
+
+        assert_eq!(
+            context,
+            RenderContext {
+                root_font_size: DEFAULT_ROOT_FONT_SIZE,
+                view_width: 800.0,
+                view_height: 600.0,
+            }
+        );
+    }
+
+    #[test_log::test]
+    fn with_root_font_size_keeps_viewport() {
+        let context = RenderContext::default().with_root_font_size(20.0);
+
+        assert_eq!(
+            context,
+            RenderContext {
+                root_font_size: 20.0,
+                view_width: DEFAULT_VIEW_WIDTH,
+                view_height: DEFAULT_VIEW_HEIGHT,
+            }
+        );
+    }
+}
@@ -18,7 +18,7 @@
 use actix_web::{
     Result, Scope,
     dev::{ServiceFactory, ServiceRequest},
-    error::{ErrorBadRequest, ErrorInternalServerError, ErrorNotFound},
+    error::{ErrorBadRequest, ErrorGatewayTimeout, ErrorInternalServerError, ErrorNotFound},
     get, post,
     web::{self, Json},
 };
@@ -105,10 +105,13 @@ impl From<PlayerError> for actix_web::Error {
             PlayerError::AlbumFetchFailed(album_id) => {
                 ErrorInternalServerError(format!("Failed to fetch album: {album_id}"))
             }
-            PlayerError::NoPlayersPlaying => ErrorBadRequest(err),
+            PlayerError::NoPlayersPlaying | PlayerError::EmptyQueue => ErrorBadRequest(err),
             PlayerError::PositionOutOfBounds(position) => {
                 ErrorBadRequest(format!("Position out of bounds: {position}"))
             }
+            PlayerError::ChapterOutOfBounds(index) => {
+                ErrorBadRequest(format!("Chapter out of bounds: {index}"))
+            }
             PlayerError::PlaybackNotPlaying(id) => {
                 ErrorBadRequest(format!("Playback not playing: {id}"))
             }
@@ -136,6 +139,15 @@ impl From<PlayerError> for actix_web::Error {
             PlayerError::Acquire(err) => ErrorInternalServerError(err),
             PlayerError::Seek(err) => ErrorInternalServerError(err),
             PlayerError::AudioOutput(err) => ErrorInternalServerError(err),
+            PlayerError::Timeout(..) => ErrorGatewayTimeout(err),
+            PlayerError::Operation { .. } => match err.root_cause() {
+                PlayerError::TrackNotFound(..) => ErrorNotFound(err),
+                PlayerError::Timeout(..) => ErrorGatewayTimeout(err),
+                PlayerError::TrackNotLocal(..)
+                | PlayerError::UnsupportedFormat(..)
+                | PlayerError::PositionOutOfBounds(..) => ErrorBadRequest(err),
+                _ => ErrorInternalServerError(err),
+            },
         }
     }
 }
@@ -193,11 +205,7 @@ async fn get_player(
                     .with_playback(playback)
                     .with_output(output);
 
-                local_player
-                    .playback_handler
-                    .write()
-                    .unwrap()
-                    .replace(handler.clone());
+                local_player.set_playback_handler(&handler);
 
                 handler
             } else {
@@ -216,11 +224,7 @@ async fn get_player(
                     .with_playback(playback)
                     .with_output(output);
 
-                local_player
-                    .playback_handler
-                    .write()
-                    .unwrap()
-                    .replace(handler.clone());
+                local_player.set_playback_handler(&handler);
 
                 handler
             })
@@ -997,6 +1001,44 @@ mod tests {
         assert!(actix_error.to_string().contains("42"));
     }
 
+    #[test_log::test]
+    fn test_player_error_to_actix_error_wrapped_track_not_found() {
+        let error = PlayerError::TrackNotFound(42.into()).with_track_context(
+            crate::PlayerOperation::Play,
+            &42.into(),
+            None,
+        );
+        let actix_error: actix_web::Error = error.into();
+
+        // Should keep the 404 Not Found of the wrapped error
+        assert_eq!(actix_error.as_response_error().status_code(), 404);
+        assert!(actix_error.to_string().contains("Failed to play track 42"));
+        assert!(actix_error.to_string().contains("Track not found"));
+    }
+
+    #[test_log::test]
+    fn test_player_error_to_actix_error_wrapped_position_out_of_bounds() {
+        let error = PlayerError::PositionOutOfBounds(99).with_track_context(
+            crate::PlayerOperation::Seek,
+            &42.into(),
+            None,
+        );
+        let actix_error: actix_web::Error = error.into();
+
+        // Should keep the 400 Bad Request of the wrapped error
+        assert_eq!(actix_error.as_response_error().status_code(), 400);
+        assert!(actix_error.to_string().contains("Position out of bounds"));
+    }
+
+    #[test_log::test]
+    fn test_player_error_to_actix_error_timeout() {
+        let error = PlayerError::Timeout(std::time::Duration::from_secs(5));
+        let actix_error: actix_web::Error = error.into();
+
+        // Should be a 504 Gateway Timeout error
+        assert_eq!(actix_error.as_response_error().status_code(), 504);
+    }
+
     #[test_log::test]
     fn test_player_error_to_actix_error_track_not_local() {
         let error = PlayerError::TrackNotLocal(123.into());
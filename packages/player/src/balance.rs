@@ -0,0 +1,164 @@
+//! Left/right stereo balance.
+//!
+//! The balance ranges from `-1.0` (full left) through `0.0` (center) to `1.0` (full
+//! right). Gains follow an equal-power pan law normalized to the center position: the
+//! channel being panned away from is attenuated by `√2 · cos(θ)`, and the other one stays
+//! at unity gain, so a centered balance leaves the output untouched and no channel is
+//! ever boosted.
+//!
+//! Balance is applied to the front left and right channels of the decoded audio, on top
+//! of (and independently from) the output volume. Mono output has no left and right to
+//! balance, so it's left as is.
+
+use std::f32::consts::{FRAC_PI_4, SQRT_2};
+
+use symphonia::core::audio::{AudioBuffer, Channels, Signal};
+
+/// Balance that leaves both channels at unity gain.
+pub const CENTER: f32 = 0.0;
+
+/// Clamps `balance` to `-1.0..=1.0`, treating NaN as [`CENTER`].
+#[must_use]
+pub const fn clamp_balance(balance: f32) -> f32 {
+    if balance.is_nan() {
+        CENTER
+    } else {
+        balance.clamp(-1.0, 1.0)
+    }
+}
+
+/// Gains of the left and right channels at `balance`.
+///
+/// `balance` is clamped to `-1.0..=1.0`.
+#[must_use]
+pub fn balance_gains(balance: f32) -> (f32, f32) {
+    let balance = clamp_balance(balance);
+
+    // Pan angle from 0 (full left) through π/4 (center) to π/2 (full right)
+    let angle = (balance + 1.0) * FRAC_PI_4;
+    let left = (SQRT_2 * angle.cos()).min(1.0);
+    let right = (SQRT_2 * angle.sin()).min(1.0);
+
+    // Snap the floating point error of cos(π/2) so full pans fully mute the other side
+    let snap = |gain: f32| if gain < 1e-6 { 0.0 } else { gain };
+
+    (snap(left), snap(right))
+}
+
+/// Applies `balance` to the front left and right channels of `buffer`.
+///
+/// Buffers without both a front left and a front right channel, e.g. mono audio, are left
+/// untouched.
+pub fn apply_balance(buffer: &mut AudioBuffer<f32>, balance: f32) {
+    let stereo = Channels::FRONT_LEFT | Channels::FRONT_RIGHT;
+    if !buffer.spec().channels.contains(stereo) {
+        return;
+    }
+
+    let (left, right) = balance_gains(balance);
+
+    // Front left and right are the lowest channel bits, so they're always planes 0 and 1
+    for (channel, gain) in [(0, left), (1, right)] {
+        if (gain - 1.0).abs() < f32::EPSILON {
+            continue;
+        }
+        for sample in buffer.chan_mut(channel) {
+            *sample *= gain;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use symphonia::core::audio::{Layout, SignalSpec};
+
+    use super::*;
+
+    fn assert_gains(balance: f32, expected: (f32, f32)) {
+        let (left, right) = balance_gains(balance);
+        assert!(
+            (left - expected.0).abs() < 1e-5 && (right - expected.1).abs() < 1e-5,
+            "balance {balance}: expected {expected:?}, got {:?}",
+            (left, right)
+        );
+    }
+
+    fn filled_buffer(channels: Channels, value: f32) -> AudioBuffer<f32> {
+        let mut buffer = AudioBuffer::<f32>::new(16, SignalSpec::new(48000, channels));
+        buffer.render_reserved(Some(16));
+        for channel in 0..channels.count() {
+            buffer.chan_mut(channel).fill(value);
+        }
+        buffer
+    }
+
+    #[test_log::test]
+    fn full_left_mutes_right_channel() {
+        assert_gains(-1.0, (1.0, 0.0));
+    }
+
+    #[test_log::test]
+    fn center_is_unity_gain() {
+        assert_gains(CENTER, (1.0, 1.0));
+    }
+
+    #[test_log::test]
+    fn full_right_mutes_left_channel() {
+        assert_gains(1.0, (0.0, 1.0));
+    }
+
+    #[test_log::test]
+    fn partial_balance_follows_equal_power_curve() {
+        // √2 · cos(3π/8)
+        assert_gains(0.5, (0.541_196, 1.0));
+        assert_gains(-0.5, (1.0, 0.541_196));
+    }
+
+    #[test_log::test]
+    fn out_of_range_balance_is_clamped() {
+        assert_gains(-3.0, (1.0, 0.0));
+        assert_gains(3.0, (0.0, 1.0));
+        assert_gains(f32::NAN, (1.0, 1.0));
+    }
+
+    #[test_log::test]
+    fn apply_balance_scales_stereo_channels() {
+        let mut buffer = filled_buffer(Layout::Stereo.into_channels(), 0.5);
+
+        apply_balance(&mut buffer, 0.5);
+
+        assert!(buffer.chan(0).iter().all(|x| (x - 0.270_598).abs() < 1e-5));
+        assert!(buffer.chan(1).iter().all(|x| (x - 0.5).abs() < 1e-5));
+    }
+
+    #[test_log::test]
+    fn apply_balance_leaves_mono_untouched() {
+        let mut buffer = filled_buffer(Layout::Mono.into_channels(), 0.5);
+
+        apply_balance(&mut buffer, -1.0);
+
+        assert!(
+            buffer
+                .chan(0)
+                .iter()
+                .all(|x| (x - 0.5).abs() < f32::EPSILON)
+        );
+    }
+
+    #[test_log::test]
+    fn apply_balance_only_touches_front_channels_of_surround() {
+        let mut buffer = filled_buffer(Layout::FivePointOne.into_channels(), 0.5);
+
+        apply_balance(&mut buffer, 1.0);
+
+        assert!(buffer.chan(0).iter().all(|x| x.abs() < f32::EPSILON));
+        for channel in 1..buffer.spec().channels.count() {
+            assert!(
+                buffer
+                    .chan(channel)
+                    .iter()
+                    .all(|x| (x - 0.5).abs() < f32::EPSILON)
+            );
+        }
+    }
+}
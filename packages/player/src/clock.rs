@@ -0,0 +1,203 @@
+//! Time source driving the time-based features of a [`PlaybackHandler`](crate::PlaybackHandler).
+//!
+//! Fades and volume ramps wait on a [`Clock`] rather than the async runtime's timer, and
+//! [`QuietHours`](crate::volume_ceiling::QuietHours) are evaluated against its time of day,
+//! so tests can attach a [`MockClock`] with
+//! [`with_clock`](crate::PlaybackHandler::with_clock) and advance time deterministically
+//! instead of waiting on the wall clock.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Local};
+
+/// Source of the current time and of delays for the time-based features of a
+/// [`PlaybackHandler`](crate::PlaybackHandler).
+#[async_trait]
+pub trait Clock: std::fmt::Debug + Send + Sync {
+    /// The current local date and time on this clock.
+    fn now(&self) -> DateTime<Local>;
+
+    /// Waits until `duration` has passed on this clock.
+    async fn sleep(&self, duration: Duration);
+}
+
+/// [`Clock`] backed by `switchy_time` and the async runtime's timer. This is the default.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RuntimeClock;
+
+#[async_trait]
+impl Clock for RuntimeClock {
+    fn now(&self) -> DateTime<Local> {
+        switchy_time::datetime_local_now()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        switchy_async::time::sleep(duration).await;
+    }
+}
+
+/// [`Clock`] whose time only passes when [`MockClock::advance`] is called.
+///
+/// Clones share the same time, so a test can keep a clone to drive the clock attached to
+/// a handler.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    state: Arc<Mutex<MockClockState>>,
+    sleeper_added: (flume::Sender<()>, flume::Receiver<()>),
+}
+
+#[derive(Debug, Default)]
+struct MockClockState {
+    start: DateTime<Local>,
+    now: Duration,
+    sleepers: Vec<(Duration, flume::Sender<()>)>,
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::starting_at(DateTime::default())
+    }
+}
+
+impl MockClock {
+    /// Creates a clock whose [`now`](Clock::now) is `start` until it is advanced.
+    #[must_use]
+    pub fn starting_at(start: DateTime<Local>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MockClockState {
+                start,
+                ..Default::default()
+            })),
+            sleeper_added: flume::unbounded(),
+        }
+    }
+
+    /// Time that has passed on this clock since it was created.
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.state.lock().unwrap().now
+    }
+
+    /// Number of sleeps currently waiting on this clock.
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    #[must_use]
+    pub fn sleepers(&self) -> usize {
+        self.state.lock().unwrap().sleepers.len()
+    }
+
+    /// Waits until at least one sleep is waiting on this clock.
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    pub async fn wait_for_sleepers(&self) {
+        while self.sleepers() == 0 {
+            // Both ends live in `self`, so the channel can't disconnect
+            let _ = self.sleeper_added.1.recv_async().await;
+        }
+    }
+
+    /// Advances the clock by `duration`, waking every sleep whose deadline has passed.
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.now += duration;
+
+        let now = state.now;
+        state.sleepers.retain(|(deadline, waker)| {
+            if *deadline <= now {
+                // The sleep may have been dropped, in which case there's nothing to wake
+                let _ = waker.send(());
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl Clock for MockClock {
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    /// * If the clock was advanced past the range of [`DateTime`]
+    fn now(&self) -> DateTime<Local> {
+        let state = self.state.lock().unwrap();
+        state.start + chrono::Duration::from_std(state.now).unwrap()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        let receiver = {
+            let mut state = self.state.lock().unwrap();
+            if duration.is_zero() {
+                return;
+            }
+
+            let (sender, receiver) = flume::bounded(1);
+            let deadline = state.now + duration;
+            state.sleepers.push((deadline, sender));
+            receiver
+        };
+        let _ = self.sleeper_added.0.send(());
+
+        let _ = receiver.recv_async().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone as _;
+
+    use super::*;
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_mock_clock_wakes_sleeps_once_their_deadline_passes() {
+        let clock = MockClock::default();
+
+        let sleep = switchy_async::runtime::Handle::current().spawn_with_name("test: sleep", {
+            let clock = clock.clone();
+            async move { clock.sleep(Duration::from_millis(100)).await }
+        });
+
+        clock.wait_for_sleepers().await;
+
+        clock.advance(Duration::from_millis(99));
+        assert_eq!(clock.sleepers(), 1);
+
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(clock.sleepers(), 0);
+
+        sleep.await.unwrap();
+        assert_eq!(clock.elapsed(), Duration::from_millis(100));
+    }
+
+    #[test_log::test]
+    fn test_mock_clock_now_advances_from_its_start() {
+        let start = Local.with_ymd_and_hms(2024, 1, 1, 21, 59, 0).unwrap();
+        let clock = MockClock::starting_at(start);
+
+        assert_eq!(clock.now(), start);
+
+        clock.advance(Duration::from_secs(2 * 60 * 60));
+
+        assert_eq!(
+            clock.now(),
+            Local.with_ymd_and_hms(2024, 1, 1, 23, 59, 0).unwrap()
+        );
+    }
+}
@@ -0,0 +1,781 @@
+//! Crossfading between the end of one track and the start of the next.
+//!
+//! A [`Crossfade`] mixes the incoming track into the outgoing one over an overlap,
+//! following equal-power curves so the combined level stays constant instead of dipping
+//! halfway through. When two tracks were mastered at very different levels the louder one
+//! still dominates the overlap, so the crossfade can optionally match their perceived
+//! levels: given the [`Loudness`] of both tracks, either from `ReplayGain` tags or
+//! approximated with [`measure_rms_level`], the level of the overlap glides from the outgoing track's
+//! loudness to the incoming track's, with each track scaled to that level. Neither track
+//! jumps in level at the edges of the overlap. Without loudness data for both tracks the
+//! crossfade falls back to plain equal power.
+//!
+//! During playback a [`TrackMixer`] drives the crossfade: it decodes the start of the
+//! incoming track ahead of the overlap while the outgoing one nears its end and mixes it in,
+//! so the incoming track then resumes from [`TrackMixer::mixed_secs`] instead of its start.
+
+use std::{collections::VecDeque, f64::consts::FRAC_PI_2, time::Duration};
+
+use symphonia::core::audio::{AudioBuffer, Signal, SignalSpec};
+
+use crate::limiter::db_to_gain;
+
+/// Default length of the overlap between two tracks.
+pub const DEFAULT_DURATION: Duration = Duration::from_secs(5);
+
+/// Loudness in LUFS that a `ReplayGain` 2.0 track gain brings a track to.
+pub const REPLAY_GAIN_REFERENCE_LUFS: f64 = -18.0;
+
+/// Upper bound on the gain adjustment applied to either track when matching loudness, so
+/// bogus loudness data can't blow up a near-silent track.
+pub const MAX_MATCH_GAIN_DB: f64 = 12.0;
+
+/// Settings of a [`Crossfade`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrossfadeConfig {
+    /// Length of the overlap between the outgoing and the incoming track
+    pub duration: Duration,
+    /// Match the perceived levels of both tracks during the overlap when their loudness is
+    /// known
+    pub match_loudness: bool,
+}
+
+impl Default for CrossfadeConfig {
+    fn default() -> Self {
+        Self {
+            duration: DEFAULT_DURATION,
+            match_loudness: true,
+        }
+    }
+}
+
+/// Perceived loudness of a track.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Loudness {
+    /// `ReplayGain` track gain in dB, relative to [`REPLAY_GAIN_REFERENCE_LUFS`]
+    ReplayGain(f64),
+    /// Integrated loudness in LUFS
+    Lufs(f64),
+    /// Unweighted, ungated RMS level in dB, offset to the LUFS scale (see
+    /// [`measure_rms_level`]). Treated as an approximation of the loudness in LUFS
+    Rms(f64),
+}
+
+impl Loudness {
+    /// The loudness in LUFS, approximated for [`Loudness::Rms`].
+    #[must_use]
+    pub fn lufs(self) -> f64 {
+        match self {
+            // The gain is what it takes to bring the track to the reference level
+            Self::ReplayGain(gain_db) => REPLAY_GAIN_REFERENCE_LUFS - gain_db,
+            Self::Lufs(lufs) | Self::Rms(lufs) => lufs,
+        }
+    }
+}
+
+/// Gains applied to both tracks at one point of the overlap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrossfadeGains {
+    /// Gain of the outgoing track
+    pub outgoing: f64,
+    /// Gain of the incoming track
+    pub incoming: f64,
+}
+
+/// Mixes an incoming track into an outgoing one over a fixed overlap.
+///
+/// Keeps its position between [`process`](Self::process) calls, so use one crossfade per
+/// transition and feed it consecutive buffers of both tracks.
+#[derive(Debug, Clone)]
+pub struct Crossfade {
+    config: CrossfadeConfig,
+    /// Gap in dB between the incoming and the outgoing track, if it is matched
+    loudness_difference_db: Option<f64>,
+    total_frames: u64,
+    position: u64,
+}
+
+impl Crossfade {
+    /// Creates a crossfade over audio at `sample_rate`.
+    ///
+    /// Loudness matching only applies when it is enabled in `config` and the loudness of
+    /// both tracks is known.
+    #[must_use]
+    pub fn new(
+        config: CrossfadeConfig,
+        sample_rate: u32,
+        outgoing: Option<Loudness>,
+        incoming: Option<Loudness>,
+    ) -> Self {
+        let loudness_difference_db = match (config.match_loudness, outgoing, incoming) {
+            (true, Some(outgoing), Some(incoming)) => Some(incoming.lufs() - outgoing.lufs()),
+            _ => None,
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let total_frames = (config.duration.as_secs_f64() * f64::from(sample_rate)).round() as u64;
+
+        Self {
+            config,
+            loudness_difference_db,
+            total_frames,
+            position: 0,
+        }
+    }
+
+    /// The crossfade's settings.
+    #[must_use]
+    pub const fn config(&self) -> CrossfadeConfig {
+        self.config
+    }
+
+    /// Whether the perceived levels of both tracks are matched during the overlap.
+    #[must_use]
+    pub const fn is_loudness_matched(&self) -> bool {
+        self.loudness_difference_db.is_some()
+    }
+
+    /// Whether the whole overlap has been mixed.
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        self.position >= self.total_frames
+    }
+
+    /// The gains of both tracks at `progress` through the overlap, from `0.0` at its start
+    /// to `1.0` at its end.
+    #[must_use]
+    pub fn gains(&self, progress: f64) -> CrossfadeGains {
+        let progress = progress.clamp(0.0, 1.0);
+        let angle = progress * FRAC_PI_2;
+        let mut gains = CrossfadeGains {
+            outgoing: angle.cos(),
+            incoming: angle.sin(),
+        };
+
+        if let Some(difference) = self.loudness_difference_db {
+            // The overlap's level glides from the outgoing track's loudness to the incoming
+            // one's, and both tracks are scaled to that level
+            let outgoing_db = (difference * progress).clamp(-MAX_MATCH_GAIN_DB, MAX_MATCH_GAIN_DB);
+            let incoming_db =
+                (-difference * (1.0 - progress)).clamp(-MAX_MATCH_GAIN_DB, MAX_MATCH_GAIN_DB);
+            gains.outgoing *= db_to_gain(outgoing_db);
+            gains.incoming *= db_to_gain(incoming_db);
+        }
+
+        gains
+    }
+
+    /// Mixes `incoming` into `outgoing` in place, advancing through the overlap by the
+    /// number of frames in `outgoing`.
+    ///
+    /// Frames past the end of the overlap are replaced by the incoming track unchanged,
+    /// and missing incoming frames or channels are treated as silence.
+    pub fn process(&mut self, outgoing: &mut AudioBuffer<f32>, incoming: &AudioBuffer<f32>) {
+        let channels = outgoing.spec().channels.count();
+        let incoming_channels = incoming.spec().channels.count();
+        let frames = outgoing.frames();
+
+        for frame in 0..frames {
+            #[allow(clippy::cast_precision_loss)]
+            let progress = if self.total_frames == 0 {
+                1.0
+            } else {
+                self.position as f64 / self.total_frames as f64
+            };
+            let gains = self.gains(progress);
+            #[allow(clippy::cast_possible_truncation)]
+            let (outgoing_gain, incoming_gain) = (gains.outgoing as f32, gains.incoming as f32);
+
+            for channel in 0..channels {
+                let incoming_sample = if channel < incoming_channels && frame < incoming.frames() {
+                    incoming.chan(channel)[frame]
+                } else {
+                    0.0
+                };
+                let sample = &mut outgoing.chan_mut(channel)[frame];
+                *sample = sample.mul_add(outgoing_gain, incoming_sample * incoming_gain);
+            }
+
+            self.position = (self.position + 1).min(self.total_frames);
+        }
+
+        log::trace!(
+            "Crossfade: mixed {frames} frames ({}/{} through the overlap, loudness_matched={})",
+            self.position,
+            self.total_frames,
+            self.is_loudness_matched()
+        );
+    }
+}
+
+/// Measures the RMS level of `buffer` in dB, or `None` if it is silent.
+///
+/// This is the mean square of all channels in dB with the -0.691 dB offset of ITU-R BS.1770,
+/// but without its K-weighting filter or gating, so it is not a LUFS measurement. It tracks
+/// the loudness in LUFS closely enough to compare two tracks of similar material when no
+/// `ReplayGain` tags are available, and is reported as [`Loudness::Rms`].
+#[must_use]
+pub fn measure_rms_level(buffer: &AudioBuffer<f32>) -> Option<f64> {
+    let mut meter = RmsLevelMeter::default();
+    meter.add(buffer);
+    meter.level_db()
+}
+
+/// Measures the RMS level of consecutive buffers the way [`measure_rms_level`] measures one.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RmsLevelMeter {
+    sum_of_squares: f64,
+    samples: u64,
+}
+
+impl RmsLevelMeter {
+    /// Adds the samples of all channels of `buffer` to the measurement.
+    pub fn add(&mut self, buffer: &AudioBuffer<f32>) {
+        let channels = buffer.spec().channels.count();
+
+        self.sum_of_squares += (0..channels)
+            .flat_map(|channel| buffer.chan(channel).iter())
+            .map(|x| f64::from(*x).powi(2))
+            .sum::<f64>();
+        self.samples += (channels * buffer.frames()) as u64;
+    }
+
+    /// The RMS level in dB of the samples added so far, or `None` if they are silent.
+    #[must_use]
+    pub fn level_db(&self) -> Option<f64> {
+        if self.samples == 0 {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mean_square = self.sum_of_squares / self.samples as f64;
+
+        (mean_square > 0.0).then(|| 10.0f64.mul_add(mean_square.log10(), -0.691))
+    }
+}
+
+/// Decoded audio of the track a [`TrackMixer`] fades in.
+pub trait IncomingAudio: Send {
+    /// Decodes the next buffer of the track, or `None` once it ends or fails to decode.
+    fn next_buffer(&mut self) -> Option<AudioBuffer<f32>>;
+}
+
+/// Crossfades the start of the next track into the end of the playing one.
+///
+/// Feed it every decoded buffer of the outgoing track with [`process`](Self::process).
+/// Over the stretch before the overlap, the mixer decodes the start of the incoming track a
+/// buffer's worth at a time, keeping pace with the outgoing track, so the overlap doesn't
+/// stall the output decoding it all at once. Once the outgoing track is within the
+/// crossfade's duration of its end, the mixer mixes the incoming track in, only decoding
+/// what's still missing of the overlap (e.g. after seeking into it). When loudness matching
+/// is enabled, the outgoing track's loudness is measured over the stretch before the overlap
+/// and the incoming track's over the start of the overlap.
+///
+/// The overlap is abandoned, leaving the outgoing track untouched, if the incoming track
+/// can't be decoded or its sample rate or channels differ from the outgoing track's.
+pub struct TrackMixer {
+    config: CrossfadeConfig,
+    incoming: Option<Box<dyn IncomingAudio>>,
+    /// Decoded incoming samples that haven't been mixed in yet, per channel
+    pending: Vec<VecDeque<f32>>,
+    outgoing_loudness: RmsLevelMeter,
+    incoming_loudness: RmsLevelMeter,
+    crossfade: Option<Crossfade>,
+    sample_rate: u32,
+    mixed_frames: u64,
+}
+
+impl std::fmt::Debug for TrackMixer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrackMixer")
+            .field("config", &self.config)
+            .field("crossfade", &self.crossfade)
+            .field("mixed_frames", &self.mixed_frames)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TrackMixer {
+    /// Creates a mixer fading `incoming` in over the end of the outgoing track.
+    #[must_use]
+    pub fn new(config: CrossfadeConfig, incoming: Box<dyn IncomingAudio>) -> Self {
+        Self {
+            config,
+            incoming: Some(incoming),
+            pending: vec![],
+            outgoing_loudness: RmsLevelMeter::default(),
+            incoming_loudness: RmsLevelMeter::default(),
+            crossfade: None,
+            sample_rate: 0,
+            mixed_frames: 0,
+        }
+    }
+
+    /// Whether any of the incoming track has been mixed in.
+    #[must_use]
+    pub const fn is_mixing(&self) -> bool {
+        self.mixed_frames > 0
+    }
+
+    /// How far into the incoming track the mix got, in seconds. The incoming track resumes
+    /// from there once the outgoing one ends.
+    #[must_use]
+    pub fn mixed_secs(&self) -> f64 {
+        if self.sample_rate == 0 {
+            return 0.0;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let secs = self.mixed_frames as f64 / f64::from(self.sample_rate);
+        secs
+    }
+
+    /// Mixes the incoming track into `outgoing` if it falls within the overlap.
+    ///
+    /// `position_secs` is where `outgoing` starts in the outgoing track and `duration_secs`
+    /// the outgoing track's duration.
+    pub fn process(
+        &mut self,
+        outgoing: &mut AudioBuffer<f32>,
+        position_secs: f64,
+        duration_secs: f64,
+    ) {
+        let overlap_secs = self.config.duration.as_secs_f64();
+        let start_secs = (duration_secs - overlap_secs).max(0.0);
+
+        if self.incoming.is_none() && self.crossfade.is_none() {
+            return;
+        }
+
+        if position_secs < start_secs {
+            if position_secs >= start_secs - overlap_secs {
+                if self.config.match_loudness {
+                    self.outgoing_loudness.add(outgoing);
+                }
+                let frames = self.pending_frames() + outgoing.frames();
+                self.decode(*outgoing.spec(), frames);
+            }
+            return;
+        }
+
+        let spec = *outgoing.spec();
+
+        if self.crossfade.is_none() && !self.start(spec, duration_secs - position_secs) {
+            return;
+        }
+
+        let frames = outgoing.frames();
+        self.fill(frames);
+        let incoming = self.take(frames, spec);
+
+        if let Some(crossfade) = &mut self.crossfade {
+            crossfade.process(outgoing, &incoming);
+            self.mixed_frames += frames as u64;
+        }
+    }
+
+    /// Starts the overlap over the `remaining_secs` left of the outgoing track, returning
+    /// whether the incoming track can be mixed into audio of `spec`.
+    fn start(&mut self, spec: SignalSpec, remaining_secs: f64) -> bool {
+        self.sample_rate = spec.rate;
+
+        // The overlap starts on a buffer boundary, so fade over what's actually left for the
+        // outgoing track to end silent
+        let config = CrossfadeConfig {
+            duration: Duration::from_secs_f64(remaining_secs.max(0.0)),
+            ..self.config
+        };
+
+        // The incoming track's loudness is measured over the whole overlap, most of which
+        // has been decoded ahead
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let overlap_frames =
+            (config.duration.as_secs_f64() * f64::from(spec.rate)).round() as usize;
+
+        if !self.decode(spec, overlap_frames.max(1)) {
+            return false;
+        }
+
+        if self.pending_frames() == 0 {
+            log::warn!("TrackMixer: Not crossfading, the incoming track has no audio");
+            self.incoming = None;
+            return false;
+        }
+
+        let (outgoing, incoming) = if self.config.match_loudness {
+            (
+                self.outgoing_loudness.level_db().map(Loudness::Rms),
+                self.incoming_loudness.level_db().map(Loudness::Rms),
+            )
+        } else {
+            (None, None)
+        };
+
+        log::debug!(
+            "TrackMixer: Starting crossfade over {:?} outgoing={outgoing:?} incoming={incoming:?}",
+            config.duration
+        );
+        self.crossfade = Some(Crossfade::new(config, spec.rate, outgoing, incoming));
+
+        true
+    }
+
+    /// Decodes the incoming track until at least `frames` frames are pending or it ends,
+    /// measuring its loudness.
+    ///
+    /// Returns `false` and abandons the overlap if the incoming track can't be mixed into
+    /// audio of `spec`.
+    fn decode(&mut self, spec: SignalSpec, frames: usize) -> bool {
+        let channels = spec.channels.count();
+        if self.pending.is_empty() {
+            self.pending = vec![VecDeque::new(); channels];
+        }
+
+        while self.pending_frames() < frames {
+            let Some(buffer) = self.incoming.as_mut().and_then(|x| x.next_buffer()) else {
+                break;
+            };
+
+            let incoming_spec = *buffer.spec();
+            if incoming_spec.rate != spec.rate || incoming_spec.channels.count() != channels {
+                log::warn!(
+                    "TrackMixer: Not crossfading, incoming spec {incoming_spec:?} differs from outgoing spec {spec:?}"
+                );
+                self.incoming = None;
+                self.pending.clear();
+                return false;
+            }
+
+            self.incoming_loudness.add(&buffer);
+            self.push(&buffer);
+        }
+
+        true
+    }
+
+    fn pending_frames(&self) -> usize {
+        self.pending.first().map_or(0, VecDeque::len)
+    }
+
+    fn push(&mut self, buffer: &AudioBuffer<f32>) {
+        for (channel, pending) in self.pending.iter_mut().enumerate() {
+            pending.extend(buffer.chan(channel));
+        }
+    }
+
+    /// Decodes the incoming track until at least `frames` frames are pending or it ends.
+    fn fill(&mut self, frames: usize) {
+        while self.pending_frames() < frames {
+            let Some(buffer) = self.incoming.as_mut().and_then(|x| x.next_buffer()) else {
+                self.incoming = None;
+                break;
+            };
+
+            if buffer.spec().channels.count() == self.pending.len() {
+                self.push(&buffer);
+            }
+        }
+    }
+
+    /// Takes up to `frames` pending frames as a buffer of `spec`.
+    fn take(&mut self, frames: usize, spec: SignalSpec) -> AudioBuffer<f32> {
+        let frames = frames.min(self.pending_frames());
+        let mut buffer = AudioBuffer::<f32>::new(frames.max(1) as u64, spec);
+        buffer.render_reserved(Some(frames));
+
+        for (channel, pending) in self.pending.iter_mut().enumerate() {
+            for (sample, pending) in buffer
+                .chan_mut(channel)
+                .iter_mut()
+                .zip(pending.drain(..frames))
+            {
+                *sample = pending;
+            }
+        }
+
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::TAU;
+    use std::f64::consts::FRAC_PI_4;
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use symphonia::core::audio::{Channels, SignalSpec};
+
+    use super::*;
+
+    const SAMPLE_RATE: u32 = 48_000;
+
+    /// Samples of a sine wave at `frequency` Hz
+    fn sine(frames: usize, frequency: f32, amplitude: f32) -> Vec<f32> {
+        (0..frames)
+            .map(|i| {
+                #[allow(clippy::cast_precision_loss)]
+                let time = i as f32 / SAMPLE_RATE as f32;
+                amplitude * (TAU * frequency * time).sin()
+            })
+            .collect()
+    }
+
+    /// Creates a stereo buffer with `samples` in both channels
+    fn stereo_buffer(samples: &[f32]) -> AudioBuffer<f32> {
+        let spec = SignalSpec::new(SAMPLE_RATE, Channels::FRONT_LEFT | Channels::FRONT_RIGHT);
+        let mut buffer = AudioBuffer::<f32>::new(samples.len() as u64, spec);
+        buffer.render_reserved(Some(samples.len()));
+        buffer.chan_mut(0).copy_from_slice(samples);
+        buffer.chan_mut(1).copy_from_slice(samples);
+        buffer
+    }
+
+    fn config(duration: Duration) -> CrossfadeConfig {
+        CrossfadeConfig {
+            duration,
+            match_loudness: true,
+        }
+    }
+
+    #[test_log::test]
+    fn test_crossfade_falls_back_to_equal_power_without_loudness() {
+        let crossfade = Crossfade::new(
+            CrossfadeConfig::default(),
+            SAMPLE_RATE,
+            Some(Loudness::Lufs(-8.0)),
+            None,
+        );
+        assert!(!crossfade.is_loudness_matched());
+
+        for step in 0..=10 {
+            let gains = crossfade.gains(f64::from(step) / 10.0);
+            let power = gains
+                .outgoing
+                .mul_add(gains.outgoing, gains.incoming.powi(2));
+            assert!((power - 1.0).abs() < 1e-9, "step {step} had power {power}");
+        }
+
+        let start = crossfade.gains(0.0);
+        let end = crossfade.gains(1.0);
+        assert!((start.outgoing - 1.0).abs() < 1e-9 && start.incoming.abs() < 1e-9);
+        assert!(end.outgoing.abs() < 1e-9 && (end.incoming - 1.0).abs() < 1e-9);
+    }
+
+    #[test_log::test]
+    fn test_crossfade_ignores_loudness_when_matching_is_disabled() {
+        let crossfade = Crossfade::new(
+            CrossfadeConfig {
+                match_loudness: false,
+                ..CrossfadeConfig::default()
+            },
+            SAMPLE_RATE,
+            Some(Loudness::Lufs(-8.0)),
+            Some(Loudness::Lufs(-20.0)),
+        );
+
+        assert!(!crossfade.is_loudness_matched());
+        let gains = crossfade.gains(0.5);
+        assert!((gains.outgoing - gains.incoming).abs() < 1e-9);
+    }
+
+    #[test_log::test]
+    fn test_crossfade_matches_differently_scaled_signals() {
+        // The incoming track is mastered 12 dB quieter than the outgoing one
+        let loud = stereo_buffer(&sine(SAMPLE_RATE as usize, 440.0, 0.8));
+        let quiet = stereo_buffer(&sine(SAMPLE_RATE as usize, 440.0, 0.2));
+        let loud_level = measure_rms_level(&loud).unwrap();
+        let quiet_level = measure_rms_level(&quiet).unwrap();
+        assert!((loud_level - quiet_level - 12.04).abs() < 0.01);
+
+        let crossfade = Crossfade::new(
+            config(Duration::from_secs(1)),
+            SAMPLE_RATE,
+            Some(Loudness::Rms(loud_level)),
+            Some(Loudness::Rms(quiet_level)),
+        );
+        assert!(crossfade.is_loudness_matched());
+
+        for step in 0..=10 {
+            let progress = f64::from(step) / 10.0;
+            let gains = crossfade.gains(progress);
+            let angle = progress * FRAC_PI_2;
+
+            // Once the fade curves are divided out, both tracks play at the same level
+            if step > 0 && step < 10 {
+                let outgoing_level = 0.8 * gains.outgoing / angle.cos();
+                let incoming_level = 0.2 * gains.incoming / angle.sin();
+                assert!(
+                    (outgoing_level - incoming_level).abs() < 1e-6,
+                    "step {step}: outgoing at {outgoing_level}, incoming at {incoming_level}"
+                );
+            }
+        }
+
+        // Each track keeps its own level at its end of the overlap
+        assert!((crossfade.gains(0.0).outgoing - 1.0).abs() < 1e-9);
+        assert!((crossfade.gains(1.0).incoming - 1.0).abs() < 1e-9);
+    }
+
+    #[test_log::test]
+    fn test_crossfade_process_mixes_matched_levels() {
+        const FRAMES: usize = 4_800;
+
+        let mut outgoing = stereo_buffer(&vec![0.8; FRAMES]);
+        let incoming = stereo_buffer(&vec![0.2; FRAMES]);
+
+        let mut crossfade = Crossfade::new(
+            config(Duration::from_millis(100)),
+            SAMPLE_RATE,
+            Some(Loudness::ReplayGain(-4.0)),
+            Some(Loudness::ReplayGain(8.04)),
+        );
+        crossfade.process(&mut outgoing, &incoming);
+
+        assert!(crossfade.is_finished());
+
+        // Halfway through, both tracks are scaled to the same level before being summed
+        let halfway = outgoing.chan(0)[FRAMES / 2];
+        let gains = crossfade.gains(0.5);
+        #[allow(clippy::cast_possible_truncation)]
+        let expected = 0.8f64.mul_add(gains.outgoing, 0.2 * gains.incoming) as f32;
+        assert!((halfway - expected).abs() < 1e-4);
+        assert!((0.8 * gains.outgoing - 0.2 * gains.incoming).abs() < 1e-3);
+
+        // The first frame is the outgoing track untouched
+        assert!((outgoing.chan(1)[0] - 0.8).abs() < 1e-6);
+    }
+
+    /// Incoming audio served from pre-made buffers
+    struct Buffers(VecDeque<AudioBuffer<f32>>);
+
+    impl IncomingAudio for Buffers {
+        fn next_buffer(&mut self) -> Option<AudioBuffer<f32>> {
+            self.0.pop_front()
+        }
+    }
+
+    #[test_log::test]
+    fn test_track_mixer_fades_into_incoming_over_end_of_outgoing() {
+        const FRAMES: usize = 480;
+
+        let incoming = (0..20).map(|_| stereo_buffer(&[0.2; FRAMES])).collect();
+        let mut mixer = TrackMixer::new(
+            CrossfadeConfig {
+                duration: Duration::from_millis(45),
+                match_loudness: false,
+            },
+            Box::new(Buffers(incoming)),
+        );
+
+        // 100ms of outgoing audio in 10ms buffers. The overlap starts with the first buffer
+        // past 55ms, fading over the 40ms left
+        let outputs = (0..10)
+            .map(|i| {
+                let mut outgoing = stereo_buffer(&[0.8; FRAMES]);
+                mixer.process(&mut outgoing, f64::from(i) * 0.01, 0.1);
+                outgoing
+            })
+            .collect::<Vec<_>>();
+
+        // The stretch before the overlap plays untouched
+        assert!(
+            outputs[..6]
+                .iter()
+                .all(|x| x.chan(0).iter().all(|x| (x - 0.8).abs() < 1e-6))
+        );
+        assert!(mixer.is_mixing());
+        assert!((mixer.mixed_secs() - 0.04).abs() < 1e-9);
+
+        // The outgoing track ends on the incoming one
+        let last = outputs[9].chan(1)[FRAMES - 1];
+        assert!((last - 0.2).abs() < 1e-3, "last sample was {last}");
+    }
+
+    /// Incoming audio that counts the buffers decoded from it
+    struct CountedBuffers {
+        buffers: Buffers,
+        decoded: Arc<AtomicUsize>,
+    }
+
+    impl IncomingAudio for CountedBuffers {
+        fn next_buffer(&mut self) -> Option<AudioBuffer<f32>> {
+            let buffer = self.buffers.next_buffer()?;
+            self.decoded.fetch_add(1, Ordering::SeqCst);
+            Some(buffer)
+        }
+    }
+
+    #[test_log::test]
+    fn test_track_mixer_decodes_incoming_ahead_of_overlap() {
+        const FRAMES: usize = 480;
+
+        let decoded = Arc::new(AtomicUsize::new(0));
+        let mut mixer = TrackMixer::new(
+            config(Duration::from_millis(45)),
+            Box::new(CountedBuffers {
+                buffers: Buffers((0..20).map(|_| stereo_buffer(&[0.2; FRAMES])).collect()),
+                decoded: decoded.clone(),
+            }),
+        );
+
+        let counts = (0..10)
+            .map(|i| {
+                let mut outgoing = stereo_buffer(&[0.8; FRAMES]);
+                mixer.process(&mut outgoing, f64::from(i) * 0.01, 0.1);
+                decoded.load(Ordering::SeqCst)
+            })
+            .collect::<Vec<_>>();
+
+        // The incoming track is decoded a buffer at a time before the overlap, which then
+        // starts without decoding the rest of it at once
+        assert!(counts[5] >= 4, "{counts:?}");
+        assert!(counts.windows(2).all(|x| x[1] - x[0] <= 1), "{counts:?}");
+        assert!(mixer.is_mixing());
+    }
+
+    #[test_log::test]
+    fn test_track_mixer_skips_incoming_with_different_sample_rate() {
+        let spec = SignalSpec::new(44_100, Channels::FRONT_LEFT | Channels::FRONT_RIGHT);
+        let mut incoming = AudioBuffer::<f32>::new(480, spec);
+        incoming.render_reserved(Some(480));
+        let mut mixer = TrackMixer::new(
+            config(Duration::from_millis(10)),
+            Box::new(Buffers(VecDeque::from([incoming]))),
+        );
+
+        let mut outgoing = stereo_buffer(&[0.8; 480]);
+        mixer.process(&mut outgoing, 0.0, 0.01);
+
+        assert!(!mixer.is_mixing());
+        assert!(outgoing.chan(0).iter().all(|x| (x - 0.8).abs() < 1e-6));
+    }
+
+    #[test_log::test]
+    fn test_replay_gain_converts_to_lufs() {
+        assert!((Loudness::ReplayGain(-6.0).lufs() - -12.0).abs() < f64::EPSILON);
+        assert!((Loudness::ReplayGain(3.5).lufs() - -21.5).abs() < f64::EPSILON);
+        assert!((Loudness::Lufs(-14.0).lufs() - -14.0).abs() < f64::EPSILON);
+    }
+
+    #[test_log::test]
+    fn test_measure_rms_level_of_silence_is_none() {
+        assert_eq!(measure_rms_level(&stereo_buffer(&[0.0; 480])), None);
+    }
+
+    #[test_log::test]
+    fn test_match_gain_is_bounded() {
+        let crossfade = Crossfade::new(
+            config(Duration::from_secs(1)),
+            SAMPLE_RATE,
+            Some(Loudness::Lufs(-5.0)),
+            Some(Loudness::Lufs(-70.0)),
+        );
+
+        let gains = crossfade.gains(0.5);
+        let incoming_match = gains.incoming / FRAC_PI_4.sin();
+        assert!(incoming_match <= db_to_gain(MAX_MATCH_GAIN_DB) + 1e-9);
+    }
+}
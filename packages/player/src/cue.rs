@@ -0,0 +1,693 @@
+//! Cue sheets splitting a single-file album into its tracks.
+//!
+//! Albums are often ripped to one audio file plus a `.cue` file listing where each track
+//! starts. [`CueSheet::parse`] reads such a sheet, and [`CueSheet::split_track`] turns the
+//! file's [`Track`] into one virtual track per cue track, each with the [`TrackRegion`] of
+//! the file it covers. Apply a sheet to a queue entry with
+//! [`PlaybackHandler::apply_cue_sheet`](crate::PlaybackHandler::apply_cue_sheet), or let
+//! [`PlaybackHandler::apply_detected_cue_sheet`](crate::PlaybackHandler::apply_detected_cue_sheet)
+//! look for one next to a local file.
+//!
+//! Each track ends where the next one's `INDEX 01` starts, so pregaps (`INDEX 00`) play
+//! at the end of the previous track and the album plays back gaplessly.
+
+use std::path::{Path, PathBuf};
+
+use moosicbox_music_models::{Track, TrackApiSource};
+use thiserror::Error;
+
+use crate::{PlaybackHandler, PlayerError, TrackRegion, trigger_playback_event};
+
+/// Number of frames per second in cue sheet `mm:ss:ff` timestamps.
+const FRAMES_PER_SECOND: f64 = 75.0;
+
+/// Errors from reading a cue sheet.
+#[derive(Debug, Error)]
+pub enum CueSheetError {
+    /// Reading the cue sheet file failed
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+    /// A `TRACK` command without a valid track number
+    #[error("Invalid track number on line {line}: {value}")]
+    InvalidTrackNumber {
+        /// Line of the cue sheet, starting at 1
+        line: usize,
+        /// The invalid value
+        value: String,
+    },
+    /// An `INDEX` command without a valid `mm:ss:ff` timestamp
+    #[error("Invalid index on line {line}: {value}")]
+    InvalidIndex {
+        /// Line of the cue sheet, starting at 1
+        line: usize,
+        /// The invalid value
+        value: String,
+    },
+    /// The cue sheet spans more than one audio file
+    #[error("Cue sheets referencing more than one FILE are not supported")]
+    MultipleFiles,
+    /// A track without an `INDEX 01` marking its start
+    #[error("Track {0} has no INDEX 01")]
+    MissingStart(u32),
+    /// The cue sheet doesn't list any tracks
+    #[error("Cue sheet has no tracks")]
+    NoTracks,
+}
+
+/// A track listed in a [`CueSheet`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CueTrack {
+    /// Track number
+    pub number: u32,
+    /// Track title
+    pub title: Option<String>,
+    /// Track artist
+    pub performer: Option<String>,
+    /// Position in the file where the track starts (its `INDEX 01`), in seconds
+    pub start_secs: f64,
+}
+
+/// A parsed cue sheet describing the tracks of a single audio file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CueSheet {
+    /// Album title
+    pub title: Option<String>,
+    /// Album artist
+    pub performer: Option<String>,
+    /// Name of the audio file the tracks are in
+    pub file: Option<String>,
+    /// Tracks ordered by their start
+    pub tracks: Vec<CueTrack>,
+}
+
+impl CueSheet {
+    /// Parses the contents of a cue sheet.
+    ///
+    /// Commands are case-insensitive, values may be quoted, and `REM` comments and
+    /// commands without an effect on playback (e.g. `FLAGS` and `ISRC`) are ignored.
+    ///
+    /// # Errors
+    ///
+    /// * If a `TRACK` or `INDEX` command is invalid
+    /// * If the sheet references more than one `FILE`
+    /// * If a track has no `INDEX 01`
+    /// * If the sheet has no tracks
+    pub fn parse(contents: &str) -> Result<Self, CueSheetError> {
+        let mut sheet = Self {
+            title: None,
+            performer: None,
+            file: None,
+            tracks: vec![],
+        };
+        // Tracks without their start yet
+        let mut tracks: Vec<(u32, Option<String>, Option<String>, Option<f64>)> = vec![];
+
+        for (index, line) in contents.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim().trim_start_matches('\u{feff}');
+            let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let rest = rest.trim();
+
+            match command.to_ascii_uppercase().as_str() {
+                "FILE" => {
+                    if sheet.file.is_some() {
+                        return Err(CueSheetError::MultipleFiles);
+                    }
+                    sheet.file = Some(parse_file_name(rest));
+                }
+                "TRACK" => {
+                    let value = rest.split_whitespace().next().unwrap_or_default();
+                    let number =
+                        value
+                            .parse::<u32>()
+                            .map_err(|_| CueSheetError::InvalidTrackNumber {
+                                line: line_number,
+                                value: rest.to_string(),
+                            })?;
+                    tracks.push((number, None, None, None));
+                }
+                "TITLE" => match tracks.last_mut() {
+                    Some(track) => track.1 = Some(unquote(rest)),
+                    None => sheet.title = Some(unquote(rest)),
+                },
+                "PERFORMER" => match tracks.last_mut() {
+                    Some(track) => track.2 = Some(unquote(rest)),
+                    None => sheet.performer = Some(unquote(rest)),
+                },
+                "INDEX" => {
+                    let invalid = || CueSheetError::InvalidIndex {
+                        line: line_number,
+                        value: rest.to_string(),
+                    };
+                    let mut parts = rest.split_whitespace();
+                    let (Some(index), Some(time)) = (parts.next(), parts.next()) else {
+                        return Err(invalid());
+                    };
+                    let index = index.parse::<u32>().map_err(|_| invalid())?;
+                    let time = parse_time(time).ok_or_else(invalid)?;
+
+                    if index == 1
+                        && let Some(track) = tracks.last_mut()
+                    {
+                        track.3 = Some(time);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if tracks.is_empty() {
+            return Err(CueSheetError::NoTracks);
+        }
+
+        sheet.tracks = tracks
+            .into_iter()
+            .map(|(number, title, performer, start_secs)| {
+                Ok(CueTrack {
+                    number,
+                    title,
+                    performer,
+                    start_secs: start_secs.ok_or(CueSheetError::MissingStart(number))?,
+                })
+            })
+            .collect::<Result<_, CueSheetError>>()?;
+        sheet
+            .tracks
+            .sort_by(|a, b| a.start_secs.total_cmp(&b.start_secs));
+
+        Ok(sheet)
+    }
+
+    /// Reads and parses the cue sheet at `path`.
+    ///
+    /// # Errors
+    ///
+    /// * If the file can't be read
+    /// * If the cue sheet is invalid (see [`CueSheet::parse`])
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, CueSheetError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// The regions of the file the tracks cover, in track order.
+    ///
+    /// Each track ends where the next one starts, and the last track plays to the end of
+    /// the file.
+    #[must_use]
+    pub fn regions(&self) -> Vec<TrackRegion> {
+        self.tracks
+            .iter()
+            .enumerate()
+            .map(|(index, track)| TrackRegion {
+                start_offset: Some(track.start_secs),
+                end_offset: self.tracks.get(index + 1).map(|next| next.start_secs),
+            })
+            .collect()
+    }
+
+    /// Splits the `track` of the whole file into one virtual track per cue track, paired
+    /// with the region of the file it plays.
+    ///
+    /// The virtual tracks keep the file's id and sources, so they all play the same file.
+    /// Their number and title come from the cue sheet, falling back to the cue track number,
+    /// and their artist and album from the cue sheet when it has them.
+    #[must_use]
+    pub fn split_track(&self, track: &Track) -> Vec<(Track, TrackRegion)> {
+        self.tracks
+            .iter()
+            .zip(self.regions())
+            .map(|(cue_track, region)| {
+                let mut virtual_track = track.clone();
+                virtual_track.number = cue_track.number;
+                virtual_track.title = cue_track
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| format!("Track {:02}", cue_track.number));
+                if let Some(performer) = cue_track.performer.as_ref().or(self.performer.as_ref()) {
+                    virtual_track.artist.clone_from(performer);
+                }
+                if let Some(title) = &self.title {
+                    virtual_track.album.clone_from(title);
+                }
+                virtual_track.duration = region.duration(track.duration);
+
+                (virtual_track, region)
+            })
+            .collect()
+    }
+}
+
+/// Finds the cue sheet stored next to the file of a local `track`.
+///
+/// Looks for the file name with its extension replaced by `.cue` (`album.cue` for
+/// `album.flac`) and with `.cue` appended (`album.flac.cue`). Returns `None` for tracks
+/// that aren't local files or have no cue sheet.
+#[must_use]
+pub fn find_cue_sheet(track: &Track) -> Option<PathBuf> {
+    if !matches!(track.track_source, TrackApiSource::Local) {
+        return None;
+    }
+
+    let file = PathBuf::from(track.file.as_ref()?);
+    let mut appended = file.clone().into_os_string();
+    appended.push(".cue");
+
+    [file.with_extension("cue"), PathBuf::from(appended)]
+        .into_iter()
+        .find(|path| path.is_file())
+}
+
+/// Removes the quotes around a cue sheet value, if any.
+fn unquote(value: &str) -> String {
+    value
+        .strip_prefix('"')
+        .and_then(|x| x.strip_suffix('"'))
+        .unwrap_or(value)
+        .to_string()
+}
+
+/// Parses the file name of a `FILE "name" TYPE` command.
+fn parse_file_name(value: &str) -> String {
+    if let Some(quoted) = value.strip_prefix('"') {
+        return quoted
+            .split_once('"')
+            .map_or(quoted, |(name, _)| name)
+            .to_string();
+    }
+
+    value
+        .rsplit_once(char::is_whitespace)
+        .map_or(value, |(name, _)| name.trim())
+        .to_string()
+}
+
+/// Parses an `mm:ss:ff` timestamp into seconds.
+fn parse_time(value: &str) -> Option<f64> {
+    let mut parts = value.split(':');
+    let (Some(minutes), Some(seconds), Some(frames), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+
+    let minutes = minutes.parse::<u32>().ok()?;
+    let seconds = seconds.parse::<u32>().ok()?;
+    let frames = frames.parse::<u32>().ok()?;
+
+    if seconds >= 60 || f64::from(frames) >= FRAMES_PER_SECOND {
+        return None;
+    }
+
+    Some(f64::from(minutes * 60 + seconds) + f64::from(frames) / FRAMES_PER_SECOND)
+}
+
+#[cfg_attr(feature = "profiling", profiling::all_functions)]
+impl PlaybackHandler {
+    /// Splits the queue entry at `position`, a single file holding a whole album, into the
+    /// tracks listed in `cue_sheet`, and returns the number of entries it was split into.
+    ///
+    /// The updated queue is sent to the session. See [`Playback::apply_cue_sheet`](crate::Playback::apply_cue_sheet).
+    ///
+    /// # Panics
+    ///
+    /// * If the `playback` `RwLock` is poisoned
+    ///
+    /// # Errors
+    ///
+    /// * If there is no active playback
+    /// * If `position` is out of bounds of the queue
+    pub fn apply_cue_sheet(
+        &mut self,
+        position: u16,
+        cue_sheet: &CueSheet,
+    ) -> Result<u16, PlayerError> {
+        let (playback, old, count) = {
+            let mut binding = self.playback.write().unwrap();
+            let playback = binding.as_mut().ok_or(PlayerError::NoPlayersPlaying)?;
+            let old = playback.clone();
+            let count = playback
+                .apply_cue_sheet(position, cue_sheet)
+                .ok_or(PlayerError::PositionOutOfBounds(position))?;
+            log::debug!(
+                "apply_cue_sheet: split position={position} into {count} tracks current_position={}",
+                playback.position,
+            );
+            (playback.clone(), old, count)
+        };
+
+        self.playback_updated(&playback);
+        trigger_playback_event(&playback, &old);
+
+        Ok(count)
+    }
+
+    /// Splits the queue entry at `position` with the cue sheet stored next to its file, if
+    /// it is a local track that has one (see [`find_cue_sheet`]).
+    ///
+    /// Returns the number of entries it was split into, or `None` if no cue sheet was
+    /// found.
+    ///
+    /// # Panics
+    ///
+    /// * If the `playback` `RwLock` is poisoned
+    ///
+    /// # Errors
+    ///
+    /// * If there is no active playback
+    /// * If `position` is out of bounds of the queue
+    /// * If the cue sheet can't be read or is invalid
+    pub fn apply_detected_cue_sheet(&mut self, position: u16) -> Result<Option<u16>, PlayerError> {
+        let path = {
+            let binding = self.playback.read().unwrap();
+            let playback = binding.as_ref().ok_or(PlayerError::NoPlayersPlaying)?;
+            let track = playback
+                .tracks
+                .get(position as usize)
+                .ok_or(PlayerError::PositionOutOfBounds(position))?;
+            find_cue_sheet(track)
+        };
+
+        let Some(path) = path else {
+            return Ok(None);
+        };
+
+        log::debug!(
+            "apply_detected_cue_sheet: position={position} path={}",
+            path.display()
+        );
+
+        let cue_sheet = CueSheet::from_file(path)?;
+
+        self.apply_cue_sheet(position, &cue_sheet).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use atomic_float::AtomicF64;
+    use moosicbox_music_models::{ApiSource, PlaybackQuality, id::Id};
+
+    use super::*;
+    use crate::{
+        Playback, SeekPastEnd, testing,
+        tests::{
+            TEST_REGION, chapter, create_seek_test_handler, create_test_track, ids, queue_ids,
+            queue_regions, queue_sources,
+        },
+    };
+
+    const SAMPLE_CUE: &str = r#"REM GENRE Rock
+REM DATE 1994
+PERFORMER "The Band"
+TITLE "Live at the Hall"
+FILE "Live at the Hall.flac" WAVE
+  TRACK 01 AUDIO
+    TITLE "Opening"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE "Second Song"
+    PERFORMER "The Band feat. Guest"
+    INDEX 00 03:58:50
+    INDEX 01 04:00:00
+  TRACK 03 AUDIO
+    TITLE "Closer"
+    INDEX 01 09:30:37
+"#;
+
+    fn create_file_track() -> Track {
+        Track {
+            id: 7.into(),
+            number: 1,
+            title: "Live at the Hall".to_string(),
+            duration: 900.0,
+            album: "Live at the Hall".to_string(),
+            artist: "Unknown".to_string(),
+            file: Some("/music/Live at the Hall.flac".to_string()),
+            track_source: TrackApiSource::Local,
+            ..Default::default()
+        }
+    }
+
+    #[test_log::test]
+    fn test_parse_reads_album_and_tracks() {
+        let sheet = CueSheet::parse(SAMPLE_CUE).unwrap();
+
+        assert_eq!(sheet.title.as_deref(), Some("Live at the Hall"));
+        assert_eq!(sheet.performer.as_deref(), Some("The Band"));
+        assert_eq!(sheet.file.as_deref(), Some("Live at the Hall.flac"));
+        assert_eq!(
+            sheet.tracks,
+            vec![
+                CueTrack {
+                    number: 1,
+                    title: Some("Opening".to_string()),
+                    performer: None,
+                    start_secs: 0.0,
+                },
+                CueTrack {
+                    number: 2,
+                    title: Some("Second Song".to_string()),
+                    performer: Some("The Band feat. Guest".to_string()),
+                    start_secs: 240.0,
+                },
+                CueTrack {
+                    number: 3,
+                    title: Some("Closer".to_string()),
+                    performer: None,
+                    start_secs: 570.0 + 37.0 / 75.0,
+                },
+            ]
+        );
+    }
+
+    #[test_log::test]
+    fn test_regions_end_where_the_next_track_starts() {
+        let sheet = CueSheet::parse(SAMPLE_CUE).unwrap();
+
+        assert_eq!(
+            sheet.regions(),
+            vec![
+                TrackRegion {
+                    start_offset: Some(0.0),
+                    end_offset: Some(240.0),
+                },
+                TrackRegion {
+                    start_offset: Some(240.0),
+                    end_offset: Some(570.0 + 37.0 / 75.0),
+                },
+                TrackRegion {
+                    start_offset: Some(570.0 + 37.0 / 75.0),
+                    end_offset: None,
+                },
+            ]
+        );
+    }
+
+    #[test_log::test]
+    fn test_split_track_derives_titles_and_durations() {
+        let sheet = CueSheet::parse(SAMPLE_CUE).unwrap();
+
+        let tracks = sheet.split_track(&create_file_track());
+
+        let summary = tracks
+            .iter()
+            .map(|(track, _)| {
+                (
+                    track.id.clone(),
+                    track.number,
+                    track.title.as_str(),
+                    track.artist.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (7.into(), 1, "Opening", "The Band"),
+                (7.into(), 2, "Second Song", "The Band feat. Guest"),
+                (7.into(), 3, "Closer", "The Band"),
+            ]
+        );
+
+        let expected = [240.0, 330.0 + 37.0 / 75.0, 330.0 - 37.0 / 75.0];
+        for ((track, _), expected) in tracks.iter().zip(expected) {
+            assert!(
+                (track.duration - expected).abs() < 1e-9,
+                "{}",
+                track.duration
+            );
+        }
+    }
+
+    #[test_log::test]
+    fn test_parse_accepts_unquoted_values_and_lowercase_commands() {
+        let sheet =
+            CueSheet::parse("file album.wav WAVE\ntrack 1 audio\ntitle Intro\nindex 01 01:02:03\n")
+                .unwrap();
+
+        assert_eq!(sheet.file.as_deref(), Some("album.wav"));
+        assert_eq!(sheet.tracks[0].title.as_deref(), Some("Intro"));
+        assert!((sheet.tracks[0].start_secs - (62.0 + 3.0 / 75.0)).abs() < f64::EPSILON);
+    }
+
+    #[test_log::test]
+    fn test_parse_rejects_invalid_sheets() {
+        assert!(matches!(
+            CueSheet::parse("FILE \"a.flac\" WAVE\n"),
+            Err(CueSheetError::NoTracks)
+        ));
+        assert!(matches!(
+            CueSheet::parse("TRACK 01 AUDIO\nTITLE \"No start\"\n"),
+            Err(CueSheetError::MissingStart(1))
+        ));
+        assert!(matches!(
+            CueSheet::parse("TRACK 01 AUDIO\nINDEX 01 00:61:00\n"),
+            Err(CueSheetError::InvalidIndex { line: 2, .. })
+        ));
+        assert!(matches!(
+            CueSheet::parse("FILE \"a.flac\" WAVE\nFILE \"b.flac\" WAVE\n"),
+            Err(CueSheetError::MultipleFiles)
+        ));
+    }
+
+    #[test_log::test]
+    fn test_find_cue_sheet_next_to_local_file() {
+        let directory = std::env::temp_dir().join(format!(
+            "moosicbox_player_find_cue_sheet_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        let file = directory.join("album.flac");
+        std::fs::write(&file, b"not really flac").unwrap();
+
+        let mut track = create_file_track();
+        track.file = Some(file.to_str().unwrap().to_string());
+        assert_eq!(find_cue_sheet(&track), None);
+
+        std::fs::write(directory.join("album.cue"), SAMPLE_CUE).unwrap();
+        assert_eq!(find_cue_sheet(&track), Some(directory.join("album.cue")));
+
+        track.track_source = TrackApiSource::Api(moosicbox_music_models::ApiSource::library());
+        assert_eq!(find_cue_sheet(&track), None);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    const TEST_CUE_SHEET: &str = r#"TITLE "Album"
+FILE "album.flac" WAVE
+  TRACK 01 AUDIO
+    TITLE "First"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE "Second"
+    INDEX 01 01:00:00
+  TRACK 03 AUDIO
+    TITLE "Third"
+    INDEX 01 02:30:00
+"#;
+
+    #[test_log::test]
+    fn test_apply_cue_sheet_splits_entry_and_shifts_following_entries() {
+        let mut playback = Playback::new(
+            (1..=3).map(create_test_track).collect(),
+            Some(2),
+            AtomicF64::new(1.0),
+            PlaybackQuality::default(),
+            1,
+            "default".to_string(),
+            None,
+        );
+        playback.tracks[1].sources = Some(vec![ApiSource::library()]);
+        playback.tracks[1].chapters = Some(vec![chapter("Dropped", 0.0)]);
+        playback.tracks[2].region = Some(TEST_REGION);
+        let cue_sheet = CueSheet::parse(TEST_CUE_SHEET).unwrap();
+
+        assert_eq!(playback.apply_cue_sheet(1, &cue_sheet), Some(3));
+
+        assert_eq!(
+            playback
+                .tracks
+                .iter()
+                .map(|x| (x.id.clone(), x.title.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (Id::from(1_u64), "Track 1"),
+                (Id::from(2_u64), "First"),
+                (Id::from(2_u64), "Second"),
+                (Id::from(2_u64), "Third"),
+                (Id::from(3_u64), "Track 3"),
+            ]
+        );
+        // The current track moved with the entries after the split one
+        assert_eq!(playback.position, 4);
+        assert_eq!(
+            queue_regions(&playback),
+            vec![
+                None,
+                Some(TrackRegion {
+                    start_offset: Some(0.0),
+                    end_offset: Some(60.0),
+                }),
+                Some(TrackRegion {
+                    start_offset: Some(60.0),
+                    end_offset: Some(150.0),
+                }),
+                Some(TrackRegion {
+                    start_offset: Some(150.0),
+                    end_offset: None,
+                }),
+                Some(TEST_REGION),
+            ]
+        );
+        assert_eq!(
+            queue_sources(&playback),
+            vec![
+                None,
+                Some(vec![ApiSource::library()]),
+                Some(vec![ApiSource::library()]),
+                Some(vec![ApiSource::library()]),
+                None,
+            ]
+        );
+        assert!(playback.tracks.iter().all(|x| x.chapters.is_none()));
+        assert!(playback.apply_cue_sheet(5, &cue_sheet).is_none());
+    }
+
+    #[test_log::test]
+    fn test_apply_cue_sheet_out_of_bounds() {
+        let mut handler =
+            create_seek_test_handler(&testing::FakePlayer::new(), 0.0, SeekPastEnd::default());
+        let cue_sheet = CueSheet::parse(TEST_CUE_SHEET).unwrap();
+
+        assert!(matches!(
+            handler.apply_cue_sheet(2, &cue_sheet),
+            Err(PlayerError::PositionOutOfBounds(2))
+        ));
+    }
+
+    #[test_log::test]
+    fn test_apply_detected_cue_sheet_next_to_local_file() {
+        let directory = std::env::temp_dir().join(format!(
+            "moosicbox_player_detected_cue_sheet_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        let file = directory.join("album.flac");
+        std::fs::write(&file, b"not really flac").unwrap();
+
+        let mut handler =
+            create_seek_test_handler(&testing::FakePlayer::new(), 0.0, SeekPastEnd::default());
+        handler.playback.write().unwrap().as_mut().unwrap().tracks[1]
+            .track
+            .file = Some(file.to_str().unwrap().to_string());
+
+        assert_eq!(handler.apply_detected_cue_sheet(1).unwrap(), None);
+
+        std::fs::write(directory.join("album.cue"), TEST_CUE_SHEET).unwrap();
+        assert_eq!(handler.apply_detected_cue_sheet(1).unwrap(), Some(3));
+        assert_eq!(queue_ids(&handler), ids(&[1, 2, 2, 2]));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
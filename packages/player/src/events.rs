@@ -0,0 +1,36 @@
+//! Events emitted by a [`PlaybackHandler`](crate::PlaybackHandler).
+//!
+//! Unlike the session updates sent to [`on_playback_event`](crate::on_playback_event)
+//! listeners, these describe why the playback changed. Subscribe with
+//! [`PlaybackHandler::subscribe_events`](crate::PlaybackHandler::subscribe_events).
+
+use moosicbox_music_models::id::Id;
+
+/// An event emitted by a [`PlaybackHandler`](crate::PlaybackHandler).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayerEvent {
+    /// A track was deleted from the library before it could be played, and was removed
+    /// from the queue
+    TrackUnavailable {
+        /// ID of the removed track
+        track_id: Id,
+        /// Queue position the track was removed from
+        position: u16,
+    },
+    /// The audio output device was disconnected (e.g. headphones were unplugged) and the
+    /// playback was paused, keeping its position so it can be resumed
+    DeviceLost {
+        /// ID of the track that was playing
+        track_id: Id,
+        /// Queue position of the track that was playing
+        position: u16,
+    },
+    /// Playback was requested while the queue was empty, so there was nothing to play
+    EmptyQueue,
+    /// Radio mode appended recommended tracks to the queue while its last track was
+    /// playing
+    RadioTracksQueued {
+        /// IDs of the appended tracks, in queue order
+        track_ids: Vec<Id>,
+    },
+}
@@ -0,0 +1,354 @@
+//! Peak limiter that keeps decoded audio below a ceiling.
+//!
+//! Gain stages that boost the signal above unity can push peaks past full scale, and even
+//! when every sample fits, the waveform reconstructed between samples can overshoot
+//! (inter-sample peaks). A [`Limiter`] estimates these true peaks by oversampling and
+//! reduces the gain just enough to keep them under the configured ceiling, recovering at
+//! the configured release rate. Signals that stay below the ceiling pass through
+//! untouched.
+//!
+//! Enable it on a [`PlaybackHandler`](crate::PlaybackHandler) with
+//! [`with_limiter`](crate::PlaybackHandler::with_limiter). The local player runs it as the
+//! last decode filter, after any other processing and before the audio output.
+
+use std::time::Duration;
+
+use symphonia::core::audio::{AudioBuffer, Signal};
+
+/// Default ceiling in dBTP (decibels relative to full scale, true peak).
+pub const DEFAULT_CEILING_DB: f64 = -1.0;
+
+/// Default time for the gain to recover after a peak.
+pub const DEFAULT_RELEASE: Duration = Duration::from_millis(100);
+
+/// Number of points estimated between neighbouring samples when searching for true peaks.
+const OVERSAMPLING: u8 = 4;
+
+/// Gains this close to unity are treated as unity so the limiter becomes transparent
+/// again once it has fully released.
+const UNITY_EPSILON: f32 = 1e-6;
+
+/// Settings of a [`Limiter`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LimiterConfig {
+    /// Maximum true peak level of the output in dBTP, e.g. `-1.0`
+    pub ceiling_db: f64,
+    /// Time constant of the gain recovering after a peak
+    pub release: Duration,
+}
+
+impl Default for LimiterConfig {
+    fn default() -> Self {
+        Self {
+            ceiling_db: DEFAULT_CEILING_DB,
+            release: DEFAULT_RELEASE,
+        }
+    }
+}
+
+impl LimiterConfig {
+    /// The ceiling as a linear amplitude.
+    #[must_use]
+    pub fn ceiling(&self) -> f64 {
+        db_to_gain(self.ceiling_db)
+    }
+}
+
+/// Converts a level in decibels to a linear amplitude.
+#[must_use]
+pub fn db_to_gain(db: f64) -> f64 {
+    10.0_f64.powf(db / 20.0)
+}
+
+/// A brick-wall peak limiter with instant attack and exponential release.
+///
+/// Keeps state between [`process`](Self::process) calls, so use one limiter per
+/// continuous stream of audio.
+#[derive(Debug, Clone)]
+pub struct Limiter {
+    config: LimiterConfig,
+    ceiling: f32,
+    sample_rate: u32,
+    release_coefficient: f32,
+    gain: f32,
+    /// Last two samples of each channel from the previous buffer
+    history: Vec<[f32; 2]>,
+    /// Estimated true peak of each frame in the buffer being processed
+    peaks: Vec<f32>,
+}
+
+impl Limiter {
+    /// Creates a limiter for audio at `sample_rate`.
+    #[must_use]
+    pub fn new(config: LimiterConfig, sample_rate: u32) -> Self {
+        #[allow(clippy::cast_possible_truncation)]
+        let ceiling = config.ceiling() as f32;
+
+        Self {
+            config,
+            ceiling,
+            sample_rate,
+            release_coefficient: release_coefficient(config.release, sample_rate),
+            gain: 1.0,
+            history: vec![],
+            peaks: vec![],
+        }
+    }
+
+    /// The limiter's settings.
+    #[must_use]
+    pub const fn config(&self) -> LimiterConfig {
+        self.config
+    }
+
+    /// The gain currently applied, where `1.0` means no gain reduction.
+    #[must_use]
+    pub const fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Limits `buffer` in place.
+    pub fn process(&mut self, buffer: &mut AudioBuffer<f32>) {
+        let spec = *buffer.spec();
+        let channels = spec.channels.count();
+        let frames = buffer.frames();
+
+        if frames == 0 {
+            return;
+        }
+
+        if spec.rate != self.sample_rate {
+            self.sample_rate = spec.rate;
+            self.release_coefficient = release_coefficient(self.config.release, spec.rate);
+        }
+
+        self.history.resize(channels, [0.0; 2]);
+        self.peaks.clear();
+        self.peaks.resize(frames, 0.0);
+
+        for (channel, history) in self.history.iter_mut().enumerate() {
+            let samples = buffer.chan(channel);
+            let sample = |index: isize| -> f32 {
+                usize::try_from(index).map_or_else(
+                    |_| history[usize::try_from(index + 2).unwrap_or_default()],
+                    |index| samples[index.min(frames - 1)],
+                )
+            };
+
+            for (frame, peak) in self.peaks.iter_mut().enumerate() {
+                #[allow(clippy::cast_possible_wrap)]
+                let i = frame as isize;
+                let before = segment_peak(sample(i - 2), sample(i - 1), sample(i), sample(i + 1));
+                let after = segment_peak(sample(i - 1), sample(i), sample(i + 1), sample(i + 2));
+                *peak = peak.max(before).max(after);
+            }
+
+            *history = if frames >= 2 {
+                [samples[frames - 2], samples[frames - 1]]
+            } else {
+                [history[1], samples[0]]
+            };
+        }
+
+        let mut limited_frames = 0;
+
+        for (frame, peak) in self.peaks.iter().enumerate() {
+            let target = if *peak > self.ceiling {
+                self.ceiling / peak
+            } else {
+                1.0
+            };
+
+            self.gain = if target < self.gain {
+                target
+            } else {
+                (self.gain - target).mul_add(self.release_coefficient, target)
+            };
+
+            if 1.0 - self.gain < UNITY_EPSILON {
+                self.gain = 1.0;
+                continue;
+            }
+
+            limited_frames += 1;
+
+            for channel in 0..channels {
+                buffer.chan_mut(channel)[frame] *= self.gain;
+            }
+        }
+
+        if limited_frames > 0 {
+            log::trace!(
+                "Limiter: reduced gain on {limited_frames}/{frames} frames (gain={:.3})",
+                self.gain
+            );
+        }
+    }
+}
+
+/// Per-sample coefficient of an exponential release with time constant `release`.
+fn release_coefficient(release: Duration, sample_rate: u32) -> f32 {
+    let samples = release.as_secs_f64() * f64::from(sample_rate);
+
+    if samples <= 0.0 {
+        return 0.0;
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    let coefficient = (-1.0 / samples).exp() as f32;
+    coefficient
+}
+
+/// Estimated absolute peak of the waveform between `p1` and `p2`, including both.
+///
+/// Interpolates the segment with a Catmull-Rom spline through the neighbouring samples
+/// `p0` and `p3`.
+fn segment_peak(p0: f32, p1: f32, p2: f32, p3: f32) -> f32 {
+    (1..OVERSAMPLING)
+        .map(|step| {
+            let t = f32::from(step) / f32::from(OVERSAMPLING);
+            let a = 3.0f32.mul_add(p1, -p0) - 3.0f32.mul_add(p2, -p3);
+            let b = 2.0f32.mul_add(p0, -5.0 * p1) + 4.0f32.mul_add(p2, -p3);
+            let c = p2 - p0;
+            0.5 * a.mul_add(t, b).mul_add(t, c).mul_add(t, 2.0 * p1)
+        })
+        .fold(p1.abs().max(p2.abs()), |peak, x| peak.max(x.abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::TAU;
+
+    use symphonia::core::audio::{Channels, SignalSpec};
+
+    use super::*;
+    use crate::volume_mixer::mix_volume;
+
+    const SAMPLE_RATE: u32 = 48_000;
+
+    /// Samples of a sine wave at `frequency` Hz
+    fn sine(frames: usize, frequency: f32, amplitude: f32) -> Vec<f32> {
+        (0..frames)
+            .map(|i| {
+                #[allow(clippy::cast_precision_loss)]
+                let time = i as f32 / SAMPLE_RATE as f32;
+                amplitude * (TAU * frequency * time).sin()
+            })
+            .collect()
+    }
+
+    /// Creates a stereo buffer with `samples` in both channels
+    fn stereo_buffer(samples: &[f32]) -> AudioBuffer<f32> {
+        let spec = SignalSpec::new(SAMPLE_RATE, Channels::FRONT_LEFT | Channels::FRONT_RIGHT);
+        let mut buffer = AudioBuffer::<f32>::new(samples.len() as u64, spec);
+        buffer.render_reserved(Some(samples.len()));
+        buffer.chan_mut(0).copy_from_slice(samples);
+        buffer.chan_mut(1).copy_from_slice(samples);
+        buffer
+    }
+
+    fn max_abs(buffer: &AudioBuffer<f32>) -> f32 {
+        (0..buffer.spec().channels.count())
+            .flat_map(|channel| buffer.chan(channel).iter())
+            .fold(0.0, |peak, x| peak.max(x.abs()))
+    }
+
+    #[test_log::test]
+    fn test_limiter_is_transparent_below_ceiling() {
+        let input = sine(4_800, 440.0, 0.5);
+        let mut buffer = stereo_buffer(&input);
+
+        let mut limiter = Limiter::new(LimiterConfig::default(), SAMPLE_RATE);
+        limiter.process(&mut buffer);
+
+        assert_eq!(buffer.chan(0), input.as_slice());
+        assert_eq!(buffer.chan(1), input.as_slice());
+        assert!((limiter.gain() - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test_log::test]
+    fn test_limiter_keeps_boosted_signal_under_ceiling() {
+        const CHUNK: usize = 4_800;
+
+        let config = LimiterConfig::default();
+        #[allow(clippy::cast_possible_truncation)]
+        let ceiling = config.ceiling() as f32;
+        let input = sine(CHUNK * 10, 1_000.0, 0.9);
+        let mut limiter = Limiter::new(config, SAMPLE_RATE);
+
+        for (index, chunk) in input.chunks(CHUNK).enumerate() {
+            // A +6 dB boost takes the 0.9 peak to ~1.8, well past full scale
+            let mut buffer = stereo_buffer(chunk);
+            mix_volume(&mut buffer, db_to_gain(6.0));
+            let boosted = buffer.chan(0).to_vec();
+            assert!(max_abs(&buffer) > 1.7);
+
+            limiter.process(&mut buffer);
+
+            let peak = max_abs(&buffer);
+            assert!(
+                peak <= ceiling + 1e-6,
+                "chunk {index} peaked at {peak}, above the {ceiling} ceiling"
+            );
+
+            // Once the gain has settled, the output is a scaled copy of the input rather
+            // than a waveform flattened at the ceiling
+            if index > 0 {
+                let gains = buffer
+                    .chan(0)
+                    .iter()
+                    .zip(&boosted)
+                    .filter(|(_, x)| x.abs() > 0.1)
+                    .map(|(y, x)| y / x)
+                    .collect::<Vec<_>>();
+                let min = gains.iter().copied().fold(f32::MAX, f32::min);
+                let max = gains.iter().copied().fold(f32::MIN, f32::max);
+
+                assert!(
+                    max - min < 0.01,
+                    "chunk {index} gain varied from {min} to {max}"
+                );
+                assert!(peak > ceiling * 0.95, "chunk {index} peaked at {peak}");
+            }
+        }
+    }
+
+    #[test_log::test]
+    fn test_limiter_catches_inter_sample_peaks() {
+        // A quarter-sample-rate sine with a 45° phase offset is sampled at ±0.707 and
+        // never hits its true peak of 1.0 on a sample
+        let input = (0..480)
+            .map(|i| if i % 4 < 2 { 0.707 } else { -0.707 })
+            .collect::<Vec<_>>();
+        let mut buffer = stereo_buffer(&input);
+
+        let mut limiter = Limiter::new(
+            LimiterConfig {
+                ceiling_db: -2.0,
+                ..LimiterConfig::default()
+            },
+            SAMPLE_RATE,
+        );
+        limiter.process(&mut buffer);
+
+        assert!(limiter.gain() < 1.0);
+        assert!(max_abs(&buffer) < 0.707);
+    }
+
+    #[test_log::test]
+    fn test_limiter_releases_after_peak() {
+        let mut limiter = Limiter::new(
+            LimiterConfig {
+                release: Duration::from_millis(10),
+                ..LimiterConfig::default()
+            },
+            SAMPLE_RATE,
+        );
+
+        limiter.process(&mut stereo_buffer(&sine(480, 1_000.0, 1.8)));
+        assert!(limiter.gain() < 0.6);
+
+        limiter.process(&mut stereo_buffer(&sine(48_000, 1_000.0, 0.1)));
+        assert!((limiter.gain() - 1.0).abs() < f32::EPSILON);
+    }
+}
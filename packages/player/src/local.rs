@@ -9,26 +9,47 @@
 
 #![allow(clippy::module_name_repetitions)]
 
-use std::sync::{
-    Arc, Mutex, RwLock,
-    atomic::{AtomicBool, AtomicUsize, Ordering},
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    sync::{
+        Arc, Mutex, RwLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
 };
 
 use atomic_float::AtomicF64;
 
 use async_trait::async_trait;
 
-use moosicbox_audio_decoder::{AudioDecodeError, AudioDecodeHandler};
-use moosicbox_audio_output::{AudioHandle, AudioOutput, AudioOutputFactory};
+use moosicbox_audio_decoder::{AudioDecodeError, AudioDecodeHandler, decode};
+use moosicbox_audio_output::{AudioHandle, AudioOutput, AudioOutputFactory, UnderrunTracker};
 use moosicbox_music_api::models::TrackAudioQuality;
-use moosicbox_music_models::TrackApiSource;
+use moosicbox_music_models::{PlaybackQuality, Track, TrackApiSource, id::Id};
 use moosicbox_session::models::UpdateSession;
 use switchy_async::util::CancellationToken;
-use symphonia::core::io::{MediaSourceStream, MediaSourceStreamOptions};
+use symphonia::core::{
+    audio::AudioBuffer,
+    codecs::{Decoder, DecoderOptions},
+    errors::Error as SymphoniaError,
+    formats::{Cue, FormatReader},
+    io::{MediaSourceStream, MediaSourceStreamOptions},
+    meta::StandardTagKey,
+    probe::Hint,
+    units::TimeBase,
+};
 
 use crate::{
-    ApiPlaybackStatus, Playback, PlaybackHandler, PlaybackType, Player, PlayerError, PlayerSource,
-    send_playback_event, symphonia::play_media_source, track_or_id_to_playable,
+    ApiPlaybackStatus, Chapter, PlayableTrack, Playback, PlaybackHandler, PlaybackType, Player,
+    PlayerError, PlayerOperation, PlayerSource, QueuedTrack, SourceKind, StreamInfo,
+    crossfade::{CrossfadeConfig, IncomingAudio, TrackMixer},
+    limiter::Limiter,
+    seek_table::{self, DecodeSkip, SeekTable},
+    send_playback_event,
+    track_cache::TrackCache,
+    track_or_id_to_playable,
+    transition::TransitionKind,
+    url_resolver::UrlResolver,
 };
 
 #[derive(Debug, Clone)]
@@ -56,15 +77,30 @@ pub struct LocalPlayer {
 
     /// Current playback session state
     pub playback: Arc<RwLock<Option<Playback>>>,
-    /// Playback handler for this player
+    /// Playback handler for this player, set with [`LocalPlayer::set_playback_handler`]
     pub playback_handler: Arc<RwLock<Option<PlaybackHandler>>>,
     /// Shared volume for immediate audio output updates
     pub shared_volume: Arc<AtomicF64>,
     /// Handle for immediate audio control
     pub audio_handle: Arc<RwLock<Option<AudioHandle>>>,
+    /// Shared counter of audio output underruns, reported in the player status
+    pub underrun_tracker: UnderrunTracker,
     session_command_forwarder:
         Arc<RwLock<Option<flume::Sender<moosicbox_audio_output::CommandMessage>>>>,
     session_coordinator_handle: Arc<RwLock<Option<switchy_async::task::JoinHandle<()>>>>,
+    seek_tables: Arc<RwLock<BTreeMap<Id, SeekTable>>>,
+    /// Track whose start was crossfaded into the previous track, and the position it
+    /// resumes from
+    crossfaded: Arc<RwLock<Option<(Id, f64)>>>,
+    prepared: Arc<Mutex<Option<PreparedTrack>>>,
+}
+
+/// A track loaded by [`Player::trigger_prepare`], whose decoder has opened the audio output
+/// and waits for [`Player::trigger_resume`] to release its audio.
+struct PreparedTrack {
+    track_id: Id,
+    start: flume::Sender<()>,
+    task: switchy_async::task::JoinHandle<Result<(), PlayerError>>,
 }
 
 impl std::fmt::Debug for LocalPlayer {
@@ -79,6 +115,7 @@ impl std::fmt::Debug for LocalPlayer {
                 "shared_volume",
                 &self.shared_volume.load(std::sync::atomic::Ordering::SeqCst),
             )
+            .field("underrun_count", &self.underrun_tracker.underrun_count())
             .finish_non_exhaustive()
     }
 }
@@ -89,22 +126,6 @@ impl Player for LocalPlayer {
         Ok(())
     }
 
-    async fn after_update_playback(&self) -> Result<(), PlayerError> {
-        // Sync current playback volume to shared volume atomic
-        // This ensures audio output gets the correct volume immediately after update
-        if let Some(playback) = self.playback.read().unwrap().as_ref() {
-            let current_volume = playback.volume.load(std::sync::atomic::Ordering::SeqCst);
-            self.shared_volume
-                .store(current_volume, std::sync::atomic::Ordering::SeqCst);
-            log::debug!(
-                "🔊 LocalPlayer {}: synced volume to shared atomic after update: {:.3}",
-                self.id,
-                current_volume
-            );
-        }
-        Ok(())
-    }
-
     async fn before_play_playback(&self, seek: Option<f64>) -> Result<(), PlayerError> {
         let playing = {
             self.playback
@@ -116,6 +137,8 @@ impl Player for LocalPlayer {
         };
 
         log::trace!("before_play_playback: playing={playing} seek={seek:?}");
+        // A new play starts tracks from where it's told, not where a crossfade left off
+        self.crossfaded.write().unwrap().take();
         if playing {
             self.trigger_stop().await?;
         }
@@ -123,13 +146,16 @@ impl Player for LocalPlayer {
         Ok(())
     }
 
-    #[allow(clippy::too_many_lines)]
-    async fn trigger_play(&self, seek: Option<f64>) -> Result<(), PlayerError> {
+    async fn trigger_play(
+        &self,
+        seek: Option<f64>,
+        cancel: CancellationToken,
+    ) -> Result<(), PlayerError> {
         let Some(playback) = self.playback.read().unwrap().clone() else {
             return Err(PlayerError::NoPlayersPlaying);
         };
 
-        let track = &playback.tracks[playback.position as usize];
+        let track = current_track(&playback)?;
         let track_id = &track.id;
         log::info!(
             "Playing track with Symphonia: {} {:?} {track:?}",
@@ -137,72 +163,32 @@ impl Player for LocalPlayer {
             playback.abort,
         );
 
-        #[allow(clippy::match_wildcard_for_single_variants)]
-        let playback_type = match track.track_source {
-            TrackApiSource::Local => self.playback_type,
-            #[allow(unreachable_patterns)]
-            _ => PlaybackType::Stream,
+        // Skip the part of the track that was already mixed into the end of the previous one
+        let seek = match (seek, self.crossfaded.write().unwrap().take()) {
+            (None, Some((id, position))) if &id == track_id => {
+                log::debug!(
+                    "trigger_play: Resuming crossfaded track_id={track_id} at {position:.2}s"
+                );
+                Some(position)
+            }
+            _ => seek,
         };
 
-        let playable_track = track_or_id_to_playable(
-            playback_type,
-            track,
-            playback.quality,
-            TrackAudioQuality::Low,
-            &self.source,
-            playback.abort.clone(),
-        )
-        .await?;
-        let mss =
-            MediaSourceStream::new(playable_track.source, MediaSourceStreamOptions::default());
-
-        // Cleanup old session coordinator before creating new one
-        self.cleanup_session_coordinator().await;
-
-        switchy_async::runtime::Handle::current()
-            .spawn_blocking_with_name("player: Play media source", {
-                let playback = self.playback.clone();
-                let shared_volume = self.shared_volume.clone();
-                let output = self.output.clone().unwrap();
-                let audio_handle_storage = self.audio_handle.clone();
-                let session_coordinator_handle_storage = self.session_coordinator_handle.clone();
-                let player_self = self.clone();
-                move || {
-                    // CREATE AUDIO HANDLE AND SESSION COORDINATOR
-                    let (session_command_sender, session_command_receiver) = flume::unbounded();
-                    let handle = moosicbox_audio_output::AudioHandle::new(session_command_sender);
-
-                    // STORE HANDLE IMMEDIATELY - available for pause() right now
-                    *audio_handle_storage.write().unwrap() = Some(handle);
-                    log::debug!("trigger_play: created and stored audio handle immediately");
-
-                    // START INSTANCE SESSION COMMAND COORDINATOR (no CPAL streams involved)
-                    let coordinator_handle =
-                        player_self.start_instance_session_coordinator(session_command_receiver);
-                    *session_coordinator_handle_storage.write().unwrap() = Some(coordinator_handle);
-                    log::debug!("trigger_play: started instance session command coordinator");
+        let prepared = self.prepared.lock().unwrap().take();
+        let task = match prepared {
+            Some(prepared) if &prepared.track_id == track_id => {
+                log::debug!("trigger_play: Playing prepared track_id={track_id}");
+                // Usually already released by `trigger_resume`
+                let _ = prepared.start.try_send(());
+                prepared.task
+            }
+            _ => self.start_track(&playback, seek, &cancel, None).await?.0,
+        };
 
-                    let mut handler = get_audio_decode_handler_with_command_receiver(
-                        &playback,
-                        shared_volume,
-                        output,
-                        seek,
-                        player_self.clone(),
-                    )?;
-
-                    play_media_source(
-                        mss,
-                        &playable_track.hint,
-                        &mut handler,
-                        true,
-                        true,
-                        None,
-                        seek,
-                    )
-                    .map_err(PlayerError::PlaybackError)
-                }
-            })
-            .await??;
+        task.await
+            .map_err(PlayerError::from)
+            .and_then(std::convert::identity)
+            .map_err(|e| e.with_track_context(PlayerOperation::Play, track_id, None))?;
 
         log::info!("Finished playback for track_id={track_id}");
 
@@ -216,7 +202,9 @@ impl Player for LocalPlayer {
                 .map_or(0.0, |p| p.progress)
         };
 
-        let expected_duration = track.duration;
+        let expected_duration = playback
+            .current_track_region()
+            .map_or(track.duration, |region| region.duration(track.duration));
         let duration_tolerance = 1.0; // Allow 1 second tolerance
 
         log::debug!(
@@ -234,12 +222,106 @@ impl Player for LocalPlayer {
         Ok(())
     }
 
+    async fn trigger_prepare(&self, seek: Option<f64>) -> Result<Option<StreamInfo>, PlayerError> {
+        let Some(playback) = self.playback.read().unwrap().clone() else {
+            return Err(PlayerError::NoPlayersPlaying);
+        };
+
+        let track_id = current_track(&playback)?.id.clone();
+        log::info!("Preparing track: {track_id} seek={seek:?}");
+
+        // Dropping an earlier prepared track's gate stops its decoder
+        self.prepared.lock().unwrap().take();
+
+        // The decoder opens the output, then holds back its audio until `trigger_resume`
+        let (start, gate) = flume::bounded(1);
+        let (task, stream_info) = self
+            .start_track(&playback, seek, &playback.abort, Some(gate))
+            .await?;
+
+        let Ok(stream_info) = stream_info.recv_async().await else {
+            // The task ended before it probed the stream
+            task.await??;
+            return Ok(None);
+        };
+
+        log::debug!("trigger_prepare: stream_info={stream_info:?}");
+        *self.prepared.lock().unwrap() = Some(PreparedTrack {
+            track_id,
+            start,
+            task,
+        });
+
+        Ok(Some(stream_info))
+    }
+
+    async fn trigger_prefetch(
+        &self,
+        track: &Track,
+        cancel: CancellationToken,
+    ) -> Result<(), PlayerError> {
+        // Prefetched bytes are kept in the track cache, which only holds remote tracks
+        let Some(track_cache) = self.track_cache() else {
+            return Ok(());
+        };
+        if matches!(track.track_source, TrackApiSource::Local) {
+            return Ok(());
+        }
+
+        let quality = self
+            .playback
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(Playback::effective_quality)
+            .unwrap_or_default();
+        let url_resolver = self.url_resolver();
+
+        #[allow(clippy::redundant_pub_crate)]
+        let playable_track = switchy_async::select! {
+            () = cancel.cancelled() => return Err(PlayerError::Cancelled),
+            playable_track = track_or_id_to_playable(
+                PlaybackType::Stream,
+                track,
+                quality,
+                TrackAudioQuality::Low,
+                &self.source,
+                url_resolver.as_ref(),
+                Some(&track_cache),
+                cancel.clone(),
+            ) => playable_track
+                .map_err(|e| e.with_track_context(PlayerOperation::Prefetch, &track.id, None))?,
+        };
+
+        if playable_track.source_kind == SourceKind::Cache {
+            log::debug!("trigger_prefetch: track_id={} is already cached", track.id);
+            return Ok(());
+        }
+
+        log::debug!("trigger_prefetch: Prefetching track_id={}", track.id);
+
+        // Reading the source to its end fills the track cache
+        let mut source = playable_track.source;
+        switchy_async::runtime::Handle::current()
+            .spawn_blocking_with_name("player: Prefetch track", move || {
+                std::io::copy(&mut source, &mut std::io::sink())
+            })
+            .await??;
+
+        Ok(())
+    }
+
     async fn trigger_stop(&self) -> Result<(), PlayerError> {
         log::info!("Stopping playback");
+        self.prepared.lock().unwrap().take();
+        self.crossfaded.write().unwrap().take();
 
         // 1. Take ownership of the handle for immediate control and cleanup
         if let Some(handle) = self.take_current_audio_handle() {
-            handle.reset().await?;
+            handle
+                .reset()
+                .await
+                .map_err(|e| self.with_current_track_context(PlayerOperation::Stop, e.into()))?;
             log::debug!("Audio output reset successfully via handle");
             // Handle is automatically dropped here, ensuring cleanup
         } else {
@@ -265,10 +347,14 @@ impl Player for LocalPlayer {
 
     async fn trigger_pause(&self) -> Result<(), PlayerError> {
         log::info!("Pausing playback");
+        self.prepared.lock().unwrap().take();
 
         // 1. Take ownership of the handle to pause and cleanup
         if let Some(handle) = self.take_current_audio_handle() {
-            handle.pause().await?;
+            handle
+                .pause()
+                .await
+                .map_err(|e| self.with_current_track_context(PlayerOperation::Pause, e.into()))?;
             log::debug!("Audio output paused successfully via handle");
             // Handle is automatically dropped here, ensuring cleanup
         } else {
@@ -310,13 +396,26 @@ impl Player for LocalPlayer {
 
         log::info!("Resuming playback from position: {progress:.2}s");
 
+        // A prepared track starts its output right away. The playback loop then waits on its
+        // decoder rather than loading the track again.
+        if let Some(prepared) = self.prepared.lock().unwrap().as_ref() {
+            log::debug!(
+                "trigger_resume: Starting prepared track_id={}",
+                prepared.track_id
+            );
+            let _ = prepared.start.try_send(());
+        }
+
         let mut playback_handler = { self.playback_handler.read().unwrap().clone().unwrap() };
-        playback_handler.play_playback(Some(progress), None).await?;
+        playback_handler
+            .play_playback(Some(progress), None)
+            .await
+            .map_err(|e| self.with_current_track_context(PlayerOperation::Resume, e))?;
 
         Ok(())
     }
 
-    async fn trigger_seek(&self, seek: f64) -> Result<(), PlayerError> {
+    async fn trigger_seek(&self, seek: f64, _cancel: CancellationToken) -> Result<(), PlayerError> {
         let playing = {
             self.playback
                 .read()
@@ -331,7 +430,10 @@ impl Player for LocalPlayer {
         }
 
         let mut playback_handler = { self.playback_handler.read().unwrap().clone().unwrap() };
-        playback_handler.play_playback(Some(seek), None).await?;
+        playback_handler
+            .play_playback(Some(seek), None)
+            .await
+            .map_err(|e| self.with_current_track_context(PlayerOperation::Seek, e))?;
 
         Ok(())
     }
@@ -345,12 +447,30 @@ impl Player for LocalPlayer {
                 .unwrap()
                 .clone()
                 .map(Into::into),
+            underrun_count: self.underrun_tracker.underrun_count(),
         })
     }
 
     fn get_source(&self) -> &PlayerSource {
         &self.source
     }
+
+    fn supports_crossfade(&self) -> bool {
+        true
+    }
+
+    fn apply_output_volume(&self, volume: f64) {
+        let previous = self
+            .shared_volume
+            .swap(volume, std::sync::atomic::Ordering::SeqCst);
+
+        if (previous - volume).abs() > f64::EPSILON {
+            log::debug!(
+                "🔊 LocalPlayer {}: applied output volume: {volume:.3}",
+                self.id
+            );
+        }
+    }
 }
 
 impl LocalPlayer {
@@ -385,8 +505,12 @@ impl LocalPlayer {
             playback_handler: Arc::new(RwLock::new(None)),
             shared_volume,
             audio_handle: Arc::new(RwLock::new(None)),
+            underrun_tracker: UnderrunTracker::default(),
             session_command_forwarder: Arc::new(RwLock::new(None)),
             session_coordinator_handle: Arc::new(RwLock::new(None)),
+            seek_tables: Arc::new(RwLock::new(BTreeMap::new())),
+            crossfaded: Arc::new(RwLock::new(None)),
+            prepared: Arc::new(Mutex::new(None)),
         })
     }
 
@@ -397,6 +521,336 @@ impl LocalPlayer {
         self
     }
 
+    /// Sets the playback handler driving this player.
+    ///
+    /// See [`PlaybackHandler::store_in`].
+    ///
+    /// # Panics
+    ///
+    /// * If the `playback_handler` `RwLock` is poisoned
+    pub fn set_playback_handler(&self, handler: &PlaybackHandler) {
+        handler.store_in(&self.playback_handler);
+    }
+
+    /// Opens the current track of `playback` and spawns the task decoding it into a new audio
+    /// output, returning the task and a receiver for the stream's properties once probed.
+    ///
+    /// With a `start` gate, the decoder opens the output but holds back its audio until the
+    /// gate is released, and stops if its sender is dropped.
+    #[allow(clippy::too_many_lines)]
+    async fn start_track(
+        &self,
+        playback: &Playback,
+        seek: Option<f64>,
+        cancel: &CancellationToken,
+        start: Option<flume::Receiver<()>>,
+    ) -> Result<
+        (
+            switchy_async::task::JoinHandle<Result<(), PlayerError>>,
+            flume::Receiver<StreamInfo>,
+        ),
+        PlayerError,
+    > {
+        let track = current_track(playback)?.into_owned();
+        let track_id = &track.id;
+
+        #[allow(clippy::match_wildcard_for_single_variants)]
+        let playback_type = match track.track_source {
+            TrackApiSource::Local => self.playback_type,
+            #[allow(unreachable_patterns)]
+            _ => PlaybackType::Stream,
+        };
+
+        // Dropping the in-flight request closes its connection if a newer play operation
+        // supersedes this one before the stream is opened
+        let quality = playback.effective_quality();
+        let url_resolver = self.url_resolver();
+        let track_cache = self.track_cache();
+
+        #[allow(clippy::redundant_pub_crate)]
+        let playable_track = switchy_async::select! {
+            () = cancel.cancelled() => {
+                log::debug!("start_track: Superseded while opening track_id={track_id}");
+                return Err(PlayerError::Cancelled);
+            }
+            playable_track = track_or_id_to_playable(
+                playback_type,
+                &track,
+                quality,
+                TrackAudioQuality::Low,
+                &self.source,
+                url_resolver.as_ref(),
+                track_cache.as_ref(),
+                playback.abort.clone(),
+            ) => playable_track
+                .map_err(|e| e.with_track_context(PlayerOperation::Play, track_id, None))?,
+        };
+
+        if cancel.is_cancelled() {
+            log::debug!("start_track: Superseded before decoding track_id={track_id}");
+            return Err(PlayerError::Cancelled);
+        }
+
+        if let Some(playback) = self.playback.write().unwrap().as_mut() {
+            playback.open_stream_info(
+                &track,
+                playable_track.source_kind,
+                quality,
+                playable_track.resolved_source.clone(),
+            );
+        }
+
+        let mut source = playable_track.source;
+        let seek_table = playable_track
+            .seek_table
+            .or_else(|| self.seek_tables.read().unwrap().get(track_id).cloned());
+
+        // With a seek table, jump straight to the closest preceding seek point and decode
+        // from there rather than letting Symphonia seek. The audio between the seek point
+        // and the seek target is decoded and dropped, so playback resumes at the target.
+        let (decode_seek, skip) = match (seek, seek_table) {
+            (Some(time), Some(table)) if time > 0.0 => {
+                match seek_table::seek_source(source.as_mut(), &table, time) {
+                    Ok(Some(point)) => {
+                        log::debug!(
+                            "start_track: Seeked track_id={track_id} to {time}s using seek table point {point:?}"
+                        );
+                        (None, Some(DecodeSkip::new(time - point.time)))
+                    }
+                    Ok(None) => (seek, None),
+                    Err(e) => {
+                        log::warn!(
+                            "start_track: Failed to seek track_id={track_id} using seek table: {e:?}"
+                        );
+                        (seek, None)
+                    }
+                }
+            }
+            _ => (seek, None),
+        };
+
+        let mss = MediaSourceStream::new(source, MediaSourceStreamOptions::default());
+        let hint = playable_track.hint;
+        let source_kind = playable_track.source_kind;
+        let resolved_source = playable_track.resolved_source;
+        let crossfade = self.open_crossfade(playback, cancel).await;
+
+        // Cleanup old session coordinator before creating new one
+        self.cleanup_session_coordinator().await;
+
+        let (stream_info_sender, stream_info_receiver) = flume::bounded(1);
+
+        let task = switchy_async::runtime::Handle::current().spawn_blocking_with_name(
+            "player: Play media source",
+            {
+                let playback = self.playback.clone();
+                let shared_volume = self.shared_volume.clone();
+                let output = self.output.clone().unwrap();
+                let audio_handle_storage = self.audio_handle.clone();
+                let session_coordinator_handle_storage = self.session_coordinator_handle.clone();
+                let crossfaded = self.crossfaded.clone();
+                let player_self = self.clone();
+                move || {
+                    // CREATE AUDIO HANDLE AND SESSION COORDINATOR
+                    let (session_command_sender, session_command_receiver) = flume::unbounded();
+                    let handle = moosicbox_audio_output::AudioHandle::new(session_command_sender);
+
+                    // STORE HANDLE IMMEDIATELY - available for pause() right now
+                    *audio_handle_storage.write().unwrap() = Some(handle);
+                    log::debug!("start_track: created and stored audio handle immediately");
+
+                    // START INSTANCE SESSION COMMAND COORDINATOR (no CPAL streams involved)
+                    let coordinator_handle =
+                        player_self.start_instance_session_coordinator(session_command_receiver);
+                    *session_coordinator_handle_storage.write().unwrap() = Some(coordinator_handle);
+                    log::debug!("start_track: started instance session command coordinator");
+
+                    let format = probe_format(mss, &hint)?;
+                    let _ = stream_info_sender.send(stream_info_from_format(
+                        format.as_ref(),
+                        &track,
+                        source_kind,
+                        quality,
+                        resolved_source,
+                    ));
+
+                    let mixer = crossfade.and_then(|(config, next_id, next_track)| {
+                        match IncomingTrack::open(next_track) {
+                            Ok(incoming) => Some((
+                                next_id,
+                                Arc::new(Mutex::new(TrackMixer::new(config, Box::new(incoming)))),
+                            )),
+                            Err(e) => {
+                                log::warn!("start_track: Not crossfading, failed to open track_id={next_id}: {e:?}");
+                                None
+                            }
+                        }
+                    });
+
+                    let mut handler = get_audio_decode_handler_with_command_receiver(
+                        &playback,
+                        shared_volume,
+                        output,
+                        seek,
+                        track.duration,
+                        mixer.as_ref().map(|(_, mixer)| mixer.clone()),
+                        start,
+                        player_self.clone(),
+                    )?;
+                    if let Some(mut skip) = skip {
+                        handler = handler.with_filter(Box::new(move |decoded, _packet, _track| {
+                            skip.apply(decoded);
+                            Ok(())
+                        }));
+                    }
+
+                    decode(
+                        format,
+                        &mut handler,
+                        None,
+                        decode_seek,
+                        DecoderOptions { verify: true },
+                    )
+                    .map_err(|e| PlayerError::PlaybackError(e.into()))?;
+
+                    if let Some((next_id, mixer)) = mixer {
+                        let mixer = mixer.lock().unwrap();
+                        if mixer.is_mixing() {
+                            log::debug!(
+                                "start_track: Crossfaded {:.2}s of track_id={next_id}",
+                                mixer.mixed_secs()
+                            );
+                            *crossfaded.write().unwrap() = Some((next_id, mixer.mixed_secs()));
+                        }
+                    }
+
+                    Ok::<_, PlayerError>(())
+                }
+            },
+        );
+
+        Ok((task, stream_info_receiver))
+    }
+
+    /// Opens the next track if the playback handler crossfades into it at the end of the
+    /// current track, see [`PlaybackHandler::next_transition`].
+    async fn open_crossfade(
+        &self,
+        playback: &Playback,
+        cancel: &CancellationToken,
+    ) -> Option<(CrossfadeConfig, Id, PlayableTrack)> {
+        let config = {
+            let handler = self.playback_handler.read().unwrap();
+            let handler = handler.as_ref()?;
+            handler
+                .crossfade()
+                .filter(|_| handler.next_transition() == TransitionKind::Crossfade)?
+        };
+
+        let next = playback
+            .tracks
+            .get(playback.position as usize + 1)?
+            .playable_track();
+
+        #[allow(clippy::match_wildcard_for_single_variants)]
+        let playback_type = match next.track_source {
+            TrackApiSource::Local => self.playback_type,
+            #[allow(unreachable_patterns)]
+            _ => PlaybackType::Stream,
+        };
+        let url_resolver = self.url_resolver();
+        let track_cache = self.track_cache();
+
+        #[allow(clippy::redundant_pub_crate)]
+        let playable_track = switchy_async::select! {
+            () = cancel.cancelled() => return None,
+            playable_track = track_or_id_to_playable(
+                playback_type,
+                &next,
+                playback.effective_quality(),
+                TrackAudioQuality::Low,
+                &self.source,
+                url_resolver.as_ref(),
+                track_cache.as_ref(),
+                playback.abort.clone(),
+            ) => playable_track,
+        };
+
+        match playable_track {
+            Ok(playable_track) => {
+                log::debug!(
+                    "open_crossfade: Crossfading into track_id={} config={config:?}",
+                    next.id
+                );
+                Some((config, next.id.clone(), playable_track))
+            }
+            Err(e) => {
+                log::warn!(
+                    "open_crossfade: Not crossfading, failed to open track_id={}: {e:?}",
+                    next.id
+                );
+                None
+            }
+        }
+    }
+
+    /// Registers a seek table for the track with `track_id`.
+    ///
+    /// Seeks within the track jump to the table's closest preceding byte offset instead of
+    /// using Symphonia's seeking. A seek table attached to the track's [`PlayableTrack`]
+    /// takes precedence.
+    ///
+    /// [`PlayableTrack`]: crate::PlayableTrack
+    pub fn set_seek_table(&self, track_id: Id, seek_table: SeekTable) {
+        self.seek_tables
+            .write()
+            .unwrap()
+            .insert(track_id, seek_table);
+    }
+
+    /// Removes the seek table registered for the track with `track_id`, returning it.
+    pub fn remove_seek_table(&self, track_id: &Id) -> Option<SeekTable> {
+        self.seek_tables.write().unwrap().remove(track_id)
+    }
+
+    /// Adds the current track to `error`'s context, if there is a current track.
+    fn with_current_track_context(
+        &self,
+        operation: PlayerOperation,
+        error: PlayerError,
+    ) -> PlayerError {
+        let track_id = self
+            .playback
+            .read()
+            .unwrap()
+            .as_ref()
+            .and_then(|x| x.tracks.get(x.position as usize))
+            .map(|x| x.id.clone());
+
+        match track_id {
+            Some(track_id) => error.with_track_context(operation, &track_id, None),
+            None => error,
+        }
+    }
+
+    /// The hook rewriting the URL of a remote track before it is fetched, from the
+    /// playback handler.
+    fn url_resolver(&self) -> Option<UrlResolver> {
+        self.playback_handler
+            .read()
+            .unwrap()
+            .as_ref()
+            .and_then(PlaybackHandler::url_resolver)
+    }
+
+    fn track_cache(&self) -> Option<TrackCache> {
+        self.playback_handler
+            .read()
+            .unwrap()
+            .as_ref()
+            .and_then(PlaybackHandler::track_cache)
+    }
+
     /// Takes ownership of the current audio handle.
     ///
     /// Returns the audio handle if one exists, leaving `None` in its place.
@@ -539,19 +993,189 @@ impl LocalPlayer {
     }
 }
 
+/// Decodes the track a [`TrackMixer`] fades in.
+struct IncomingTrack {
+    format: Box<dyn FormatReader>,
+    decoder: Box<dyn Decoder>,
+    track_id: u32,
+}
+
+impl IncomingTrack {
+    fn open(playable_track: PlayableTrack) -> Result<Self, PlayerError> {
+        let mss =
+            MediaSourceStream::new(playable_track.source, MediaSourceStreamOptions::default());
+        let format = probe_format(mss, &playable_track.hint)?;
+        let (track_id, decoder) =
+            crate::symphonia::make_decoder(format.as_ref(), &DecoderOptions::default())?;
+
+        Ok(Self {
+            format,
+            decoder,
+            track_id,
+        })
+    }
+}
+
+impl IncomingAudio for IncomingTrack {
+    fn next_buffer(&mut self) -> Option<AudioBuffer<f32>> {
+        loop {
+            let packet = match self.format.next_packet() {
+                Ok(packet) => packet,
+                Err(SymphoniaError::IoError(e))
+                    if e.kind() == std::io::ErrorKind::UnexpectedEof =>
+                {
+                    return None;
+                }
+                Err(e) => {
+                    log::warn!("IncomingTrack: Failed to read packet: {e:?}");
+                    return None;
+                }
+            };
+
+            if packet.track_id() != self.track_id {
+                continue;
+            }
+
+            match self.decoder.decode(&packet) {
+                Ok(decoded) => {
+                    let mut buffer = decoded.make_equivalent::<f32>();
+                    decoded.convert(&mut buffer);
+                    return Some(buffer);
+                }
+                Err(SymphoniaError::DecodeError(e)) => {
+                    log::debug!("IncomingTrack: Skipping undecodable packet: {e}");
+                }
+                Err(e) => {
+                    log::warn!("IncomingTrack: Failed to decode packet: {e:?}");
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+/// Returns the track at the current position of `playback`'s queue.
+///
+/// # Errors
+///
+/// * [`PlayerError::EmptyQueue`] if the queue is empty
+/// * [`PlayerError::PositionOutOfBounds`] if the position is past the end of the queue
+fn current_track(playback: &Playback) -> Result<Cow<'_, Track>, PlayerError> {
+    playback
+        .tracks
+        .get(playback.position as usize)
+        .map(QueuedTrack::playable_track)
+        .ok_or(if playback.tracks.is_empty() {
+            PlayerError::EmptyQueue
+        } else {
+            PlayerError::PositionOutOfBounds(playback.position)
+        })
+}
+
+/// Probes `mss` for its container format, with gapless playback enabled.
+fn probe_format(mss: MediaSourceStream, hint: &Hint) -> Result<Box<dyn FormatReader>, PlayerError> {
+    Ok(crate::symphonia::probe_format(mss, hint, true)?)
+}
+
+/// The properties of the stream `format` reads, falling back to `track`'s metadata.
+fn stream_info_from_format(
+    format: &dyn FormatReader,
+    track: &moosicbox_music_models::Track,
+    source_kind: SourceKind,
+    quality: PlaybackQuality,
+    resolved_source: Option<String>,
+) -> StreamInfo {
+    let Some(params) = format.default_track().map(|x| x.codec_params.clone()) else {
+        return StreamInfo {
+            source_kind,
+            quality,
+            resolved_source,
+            ..StreamInfo::from(track)
+        };
+    };
+
+    #[allow(clippy::cast_precision_loss)]
+    let duration = match (params.n_frames, params.sample_rate) {
+        (Some(frames), Some(rate)) if rate > 0 => frames as f64 / f64::from(rate),
+        _ => track.duration,
+    };
+
+    StreamInfo {
+        sample_rate: params.sample_rate.or(track.sample_rate),
+        channels: params
+            .channels
+            .and_then(|x| u8::try_from(x.count()).ok())
+            .or(track.channels),
+        bit_depth: params
+            .bits_per_sample
+            .and_then(|x| u8::try_from(x).ok())
+            .or(track.bit_depth),
+        duration,
+        source: track.api_source.clone(),
+        source_kind,
+        quality,
+        chapters: chapters_from_cues(format.cues(), params.time_base),
+        resolved_source,
+    }
+}
+
+/// Converts the cues of a container (e.g. chapters in an audiobook) into [`Chapter`]s.
+///
+/// Cues without a title tag are named after their index.
+fn chapters_from_cues(cues: &[Cue], time_base: Option<TimeBase>) -> Vec<Chapter> {
+    let Some(time_base) = time_base else {
+        return vec![];
+    };
+
+    let mut chapters = cues
+        .iter()
+        .map(|cue| {
+            let time = time_base.calc_time(cue.start_ts);
+            let title = cue
+                .tags
+                .iter()
+                .find(|tag| tag.std_key == Some(StandardTagKey::TrackTitle))
+                .map_or_else(
+                    || format!("Chapter {}", cue.index),
+                    |tag| tag.value.to_string(),
+                );
+
+            #[allow(clippy::cast_precision_loss)]
+            let start_secs = time.seconds as f64 + time.frac;
+
+            Chapter { title, start_secs }
+        })
+        .collect::<Vec<_>>();
+
+    crate::sort_chapters(&mut chapters);
+
+    chapters
+}
+
 #[allow(clippy::too_many_lines)]
+#[allow(clippy::too_many_arguments)]
 fn get_audio_decode_handler_with_command_receiver(
     playback: &Arc<RwLock<Option<Playback>>>,
     shared_volume: Arc<AtomicF64>,
     output: Arc<Mutex<AudioOutputFactory>>,
     seek: Option<f64>,
+    duration: f64,
+    mixer: Option<Arc<Mutex<TrackMixer>>>,
+    mut start: Option<flume::Receiver<()>>,
     player: LocalPlayer,
 ) -> Result<AudioDecodeHandler, PlayerError> {
-    // Initialize shared volume with the current playback volume
+    // Initialize shared volume with the output gain of the current playback volume, mapped
+    // through the handler's volume curve and clamped to its volume ceiling
     let initial_volume = {
-        playback.read().unwrap().as_ref().map_or(1.0, |playback| {
+        let volume = playback.read().unwrap().as_ref().map_or(1.0, |playback| {
             playback.volume.load(std::sync::atomic::Ordering::SeqCst)
-        })
+        });
+        player
+            .playback_handler
+            .read()
+            .unwrap()
+            .as_ref()
+            .map_or(volume, |handler| handler.output_gain(volume))
     };
 
     shared_volume.store(initial_volume, std::sync::atomic::Ordering::SeqCst);
@@ -560,11 +1184,62 @@ fn get_audio_decode_handler_with_command_receiver(
     );
 
     let sent_playback_start_event = AtomicBool::new(false);
+    let track_region = playback
+        .read()
+        .unwrap()
+        .as_ref()
+        .and_then(Playback::current_track_region);
+
+    let abort = playback
+        .read()
+        .unwrap()
+        .as_ref()
+        .map(|playback| playback.abort.clone())
+        .unwrap_or_else(CancellationToken::new);
 
     let mut audio_decode_handler = AudioDecodeHandler::new()
+        .with_filter(Box::new(move |_decoded, _packet, _track| {
+            // Hold back the first audio of a prepared track until it is resumed. The output
+            // has already been opened by now.
+            if let Some(start) = start.take() {
+                log::debug!("trigger_prepare: Waiting for the prepared track to be resumed");
+                #[allow(clippy::redundant_pub_crate)]
+                let resumed = futures::executor::block_on(async {
+                    switchy_async::select! {
+                        () = abort.cancelled() => false,
+                        result = start.recv_async() => result.is_ok(),
+                    }
+                });
+                if !resumed {
+                    log::debug!("trigger_prepare: Prepared track was discarded");
+                    return Err(AudioDecodeError::Interrupt);
+                }
+            }
+            Ok(())
+        }))
+        .with_filter(Box::new(move |_decoded, packet, track| {
+            // Stop decoding once the end of the track's region has been reached so playback
+            // advances to the next track instead of continuing to the end of the file
+            if let Some(region) = track_region
+                && let Some(time_base) = track.codec_params.time_base
+            {
+                let time = time_base.calc_time(packet.ts());
+                #[allow(clippy::cast_precision_loss)]
+                let position = time.seconds as f64 + time.frac;
+                if region.is_past_end(position) {
+                    log::debug!(
+                        "trigger_play: reached end of track region={region:?} at position={position:.2}s"
+                    );
+                    return Err(AudioDecodeError::StreamEnd);
+                }
+            }
+            Ok(())
+        }))
         .with_filter(Box::new({
             let playback = playback.clone();
-            let initial_seek_position = seek.unwrap_or(0.0);
+            let initial_seek_position = track_region.map_or(seek.unwrap_or(0.0), |region| {
+                region.to_relative_position(seek.unwrap_or(0.0))
+            });
             move |_decoded, _packet, _track| {
                 // Just send the initial playback start event, don't track progress here
                 if !sent_playback_start_event.load(std::sync::atomic::Ordering::SeqCst) {
@@ -597,9 +1272,28 @@ fn get_audio_decode_handler_with_command_receiver(
                 Ok(())
             }
         }))
+        .with_filter(Box::new(move |decoded, packet, track| {
+            // Fade the next track in over the end of this one
+            if let Some(mixer) = &mixer
+                && let Some(time_base) = track.codec_params.time_base
+            {
+                let time = time_base.calc_time(packet.ts());
+                #[allow(clippy::cast_precision_loss)]
+                let position = time.seconds as f64 + time.frac;
+                #[allow(clippy::cast_precision_loss)]
+                let duration = match (track.codec_params.n_frames, track.codec_params.sample_rate) {
+                    (Some(frames), Some(rate)) if rate > 0 => frames as f64 / f64::from(rate),
+                    _ => duration,
+                };
+                mixer.lock().unwrap().process(decoded, position, duration);
+            }
+            Ok(())
+        }))
         .with_output(Box::new({
             let seek_position = seek.unwrap_or(0.0);
             let shared_volume_local = shared_volume;
+            let underrun_tracker = player.underrun_tracker.clone();
+            let playback_handler = player.playback_handler.clone();
             let playback_for_callback = playback.clone();
             move |spec, _duration| {
                 use moosicbox_audio_output::AudioWrite;
@@ -626,10 +1320,18 @@ fn get_audio_decode_handler_with_command_receiver(
                 // Set the consumed samples counter on the audio output
                 output.set_consumed_samples(consumed_samples);
 
+                if let Some(handler) = playback_handler.read().unwrap().as_ref() {
+                    output.set_resampler_quality(handler.resampler_quality());
+                    output.set_latency_mode(handler.latency_mode());
+                }
+
                 // Pass the shared volume atomic to the audio output
                 output.set_shared_volume(shared_volume_local.clone());
                 log::info!("Audio output creation: set shared volume reference");
 
+                // Share the player's underrun tracker so underruns are reported in the player status
+                output.set_underrun_tracker(underrun_tracker.clone());
+
                 // REGISTER THREAD-LOCAL PROCESSOR with instance session coordinator
                 player.register_thread_local_processor(output.handle());
                 log::debug!("Audio output creation: registered thread-local processor");
@@ -640,15 +1342,25 @@ fn get_audio_decode_handler_with_command_receiver(
 
                 // Spawn a task to handle progress updates from the audio thread
                 let playback_for_handler = playback_for_callback.clone();
+                let playback_handler = playback_handler.clone();
                 switchy_async::runtime::Handle::current().spawn_with_name("player: Progress handler", async move {
                     let mut last_reported_second: Option<u64> = None;
 
-                    while let Ok(progress_update) = progress_rx.recv_async().await {
+                    while let Ok(mut progress_update) = progress_rx.recv_async().await {
+                        // Report positions relative to the start of the track's region
+                        if let Some(region) = track_region {
+                            progress_update.current_position =
+                                region.to_relative_position(progress_update.current_position);
+                        }
+
                         let old = {
                             let mut binding = playback_for_handler.write().unwrap();
                             if let Some(playback) = binding.as_mut() {
                                 let old = playback.clone();
                                 playback.progress = progress_update.current_position;
+                                if let Some(handler) = playback_handler.read().unwrap().as_ref() {
+                                    handler.playback_updated(playback);
+                                }
                                 Some(old)
                             } else {
                                 log::warn!("Progress handler: no playback available to update");
@@ -732,6 +1444,44 @@ fn get_audio_decode_handler_with_command_receiver(
             }
         }));
 
+    // Read the balance per packet so changes apply immediately. The output volume is a
+    // separate gain applied by the audio output, so the two multiply
+    if let Some(balance) = playback
+        .read()
+        .unwrap()
+        .as_ref()
+        .map(|playback| playback.balance.clone())
+    {
+        audio_decode_handler =
+            audio_decode_handler.with_filter(Box::new(move |decoded, _packet, _track| {
+                crate::balance::apply_balance(
+                    decoded,
+                    balance.load(std::sync::atomic::Ordering::SeqCst),
+                );
+                Ok(())
+            }));
+    }
+
+    // Limit last so the ceiling holds regardless of the gain applied by earlier filters
+    let limiter_config = player
+        .playback_handler
+        .read()
+        .unwrap()
+        .as_ref()
+        .and_then(PlaybackHandler::limiter);
+
+    if let Some(config) = limiter_config {
+        log::debug!("trigger_play: enabling limiter {config:?}");
+        let mut limiter: Option<Limiter> = None;
+        audio_decode_handler =
+            audio_decode_handler.with_filter(Box::new(move |decoded, _packet, _track| {
+                limiter
+                    .get_or_insert_with(|| Limiter::new(config, decoded.spec().rate))
+                    .process(decoded);
+                Ok(())
+            }));
+    }
+
     if let Some(playback) = playback.read().unwrap().as_ref() {
         audio_decode_handler = audio_decode_handler.with_cancellation_token(playback.abort.clone());
     }
@@ -0,0 +1,405 @@
+//! Hooks for integrating playback with OS media controls.
+//!
+//! Platform layers (media keys, MPRIS, `SMTC`, `MPNowPlayingInfoCenter`, etc.) implement
+//! [`MediaControls`] and attach it with
+//! [`PlaybackHandler::attach_media_controls`](crate::PlaybackHandler::attach_media_controls).
+//! The handler registers callbacks that route incoming control events back to it, and pushes
+//! the current track metadata and playback state out whenever they change.
+
+use std::sync::{Arc, Mutex};
+
+use moosicbox_music_models::id::Id;
+
+use crate::{Playback, PlaybackHandler, PlayerError};
+
+/// Callback invoked by the platform layer when a media control is activated.
+pub type MediaControlCallback = Box<dyn Fn() + Send + Sync>;
+
+/// Callback invoked by the platform layer when a seek is requested, with the target
+/// position in seconds.
+pub type MediaSeekCallback = Box<dyn Fn(f64) + Send + Sync>;
+
+/// A control event received from the platform layer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MediaControlEvent {
+    /// Resume or start playback
+    Play,
+    /// Pause playback
+    Pause,
+    /// Skip to the next track
+    Next,
+    /// Skip to the previous track
+    Previous,
+    /// Seek to the given position in seconds
+    Seek(f64),
+}
+
+/// Metadata of the current track, pushed to the platform layer on track change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaMetadata {
+    /// ID of the track
+    pub track_id: Id,
+    /// Track title
+    pub title: String,
+    /// Track artist
+    pub artist: String,
+    /// Album title
+    pub album: String,
+    /// Playable duration of the track in seconds
+    pub duration: f64,
+}
+
+/// Playback state pushed to the platform layer when it changes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MediaPlaybackState {
+    /// Whether the playback is currently playing
+    pub playing: bool,
+    /// Position in the current track in seconds
+    pub position_secs: f64,
+}
+
+/// Platform integration for OS media controls.
+///
+/// The `on_*` methods are called once when the controls are attached to a
+/// [`PlaybackHandler`](crate::PlaybackHandler). Implementations should store the callbacks
+/// and invoke them when the corresponding OS event arrives.
+pub trait MediaControls: std::fmt::Debug + Send + Sync {
+    /// Registers the callback to invoke when play is requested.
+    fn on_play(&self, callback: MediaControlCallback);
+
+    /// Registers the callback to invoke when pause is requested.
+    fn on_pause(&self, callback: MediaControlCallback);
+
+    /// Registers the callback to invoke when the next track is requested.
+    fn on_next(&self, callback: MediaControlCallback);
+
+    /// Registers the callback to invoke when the previous track is requested.
+    fn on_previous(&self, callback: MediaControlCallback);
+
+    /// Registers the callback to invoke when a seek is requested.
+    ///
+    /// Platforms without seek support can leave this unimplemented.
+    fn on_seek(&self, _callback: MediaSeekCallback) {}
+
+    /// Updates the metadata displayed for the current track.
+    ///
+    /// Called with `None` when there is no current track.
+    fn update_metadata(&self, metadata: Option<&MediaMetadata>);
+
+    /// Updates the displayed playback state.
+    fn update_playback_state(&self, state: &MediaPlaybackState);
+}
+
+/// [`MediaControls`] attached to a handler, along with the last state pushed to them.
+#[derive(Debug)]
+pub(crate) struct AttachedMediaControls {
+    controls: Arc<dyn MediaControls>,
+    last: Mutex<Option<(Option<Id>, bool)>>,
+}
+
+impl AttachedMediaControls {
+    pub(crate) fn new(controls: Arc<dyn MediaControls>) -> Self {
+        Self {
+            controls,
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Pushes the metadata and playback state of `playback` out if they changed since the
+    /// last push.
+    ///
+    /// Progress updates alone don't trigger a push, so this is cheap to call on every
+    /// playback update.
+    pub(crate) fn sync(&self, playback: Option<&Playback>) {
+        let track = playback.and_then(|x| x.tracks.get(x.position as usize));
+        let track_id = track.map(|x| x.id.clone());
+        let playing = playback.is_some_and(|x| x.playing);
+
+        let mut last = self.last.lock().unwrap();
+        let track_changed = last.as_ref().is_none_or(|(id, _)| *id != track_id);
+        let playing_changed = last.as_ref().is_none_or(|(_, x)| *x != playing);
+
+        if !track_changed && !playing_changed {
+            return;
+        }
+
+        *last = Some((track_id, playing));
+        drop(last);
+
+        if track_changed {
+            let metadata = playback.zip(track).map(|(playback, track)| MediaMetadata {
+                track_id: track.id.clone(),
+                title: track.title.clone(),
+                artist: track.artist.clone(),
+                album: track.album.clone(),
+                duration: playback.current_track_duration().unwrap_or(track.duration),
+            });
+            self.controls.update_metadata(metadata.as_ref());
+        }
+
+        self.controls.update_playback_state(&MediaPlaybackState {
+            playing,
+            position_secs: playback.map_or(0.0, |x| x.progress),
+        });
+    }
+}
+
+#[cfg_attr(feature = "profiling", profiling::all_functions)]
+impl PlaybackHandler {
+    /// Attaches OS media controls to this handler.
+    ///
+    /// Control events received through the registered callbacks are routed to this
+    /// handler, and the current track metadata and playback state are pushed to `controls`
+    /// now and whenever they change. Replaces any previously attached controls.
+    ///
+    /// # Panics
+    ///
+    /// * If called outside of an async runtime
+    /// * If the `media_controls` or `playback` `RwLock` is poisoned
+    pub fn attach_media_controls(&self, controls: impl MediaControls + 'static) {
+        let controls: Arc<dyn MediaControls> = Arc::new(controls);
+        let (tx, rx) = flume::unbounded::<MediaControlEvent>();
+
+        let send = move |event: MediaControlEvent| {
+            if let Err(e) = tx.send(event) {
+                log::error!("Failed to send media control event {event:?}: {e:?}");
+            }
+        };
+
+        controls.on_play(Box::new({
+            let send = send.clone();
+            move || send(MediaControlEvent::Play)
+        }));
+        controls.on_pause(Box::new({
+            let send = send.clone();
+            move || send(MediaControlEvent::Pause)
+        }));
+        controls.on_next(Box::new({
+            let send = send.clone();
+            move || send(MediaControlEvent::Next)
+        }));
+        controls.on_previous(Box::new({
+            let send = send.clone();
+            move || send(MediaControlEvent::Previous)
+        }));
+        controls.on_seek(Box::new(move |seek| send(MediaControlEvent::Seek(seek))));
+
+        let attached = AttachedMediaControls::new(controls);
+        attached.sync(self.playback.read().unwrap().as_ref());
+        self.media_controls.write().unwrap().replace(attached);
+
+        let mut handler = self.detached();
+
+        switchy_async::runtime::Handle::current().spawn_with_name(
+            "player: Media controls",
+            async move {
+                while let Ok(event) = rx.recv_async().await {
+                    if let Err(e) = handler.handle_media_control_event(event).await {
+                        log::error!("Failed to handle media control event {event:?}: {e:?}");
+                    }
+                }
+            },
+        );
+    }
+
+    /// Applies a control event received from OS media controls.
+    ///
+    /// # Errors
+    ///
+    /// * If there is no active playback
+    /// * If failed to apply the event to the playback
+    pub async fn handle_media_control_event(
+        &mut self,
+        event: MediaControlEvent,
+    ) -> Result<(), PlayerError> {
+        log::debug!("handle_media_control_event: event={event:?}");
+
+        let (playing, seek) = match event {
+            MediaControlEvent::Play => (Some(true), None),
+            MediaControlEvent::Pause => (Some(false), None),
+            MediaControlEvent::Seek(seek) => (None, Some(seek)),
+            MediaControlEvent::Next => return self.next_track(None, None).await,
+            MediaControlEvent::Previous => return self.previous_track(None, None).await,
+        };
+
+        self.update_playback(
+            true, None, None, playing, None, seek, None, None, None, None, None, None, true, None,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SeekPastEnd, testing, tests::create_seek_test_handler};
+
+    /// Mock media controls that record pushed updates and expose the `on_next` callback.
+    struct MockMediaControls {
+        next: Arc<std::sync::Mutex<Option<MediaControlCallback>>>,
+        metadata: Arc<std::sync::Mutex<Vec<Option<Id>>>>,
+        states: Arc<std::sync::Mutex<Vec<MediaPlaybackState>>>,
+        /// Notified after each metadata update
+        metadata_updated: (flume::Sender<()>, flume::Receiver<()>),
+    }
+
+    impl Default for MockMediaControls {
+        fn default() -> Self {
+            Self {
+                next: Arc::default(),
+                metadata: Arc::default(),
+                states: Arc::default(),
+                metadata_updated: flume::unbounded(),
+            }
+        }
+    }
+
+    impl std::fmt::Debug for MockMediaControls {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("MockMediaControls").finish_non_exhaustive()
+        }
+    }
+
+    impl MediaControls for MockMediaControls {
+        fn on_play(&self, _callback: MediaControlCallback) {}
+        fn on_pause(&self, _callback: MediaControlCallback) {}
+        fn on_next(&self, callback: MediaControlCallback) {
+            self.next.lock().unwrap().replace(callback);
+        }
+        fn on_previous(&self, _callback: MediaControlCallback) {}
+        fn update_metadata(&self, metadata: Option<&MediaMetadata>) {
+            self.metadata
+                .lock()
+                .unwrap()
+                .push(metadata.map(|x| x.track_id.clone()));
+            let _ = self.metadata_updated.0.send(());
+        }
+        fn update_playback_state(&self, state: &MediaPlaybackState) {
+            self.states.lock().unwrap().push(*state);
+        }
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_media_controls_on_next_advances_queue() {
+        let handler =
+            create_seek_test_handler(&testing::FakePlayer::new(), 0.0, SeekPastEnd::default());
+        let controls = MockMediaControls::default();
+        let next = controls.next.clone();
+        let metadata = controls.metadata.clone();
+        let metadata_updated = controls.metadata_updated.1.clone();
+
+        handler.attach_media_controls(controls);
+        assert_eq!(*metadata.lock().unwrap(), vec![Some(Id::from(1_u64))]);
+
+        let on_next = next.lock().unwrap().take().expect("on_next not registered");
+        on_next();
+
+        // The controls stay attached to the handler, so the channel can't disconnect
+        while metadata.lock().unwrap().len() < 2 {
+            metadata_updated.recv_async().await.unwrap();
+        }
+
+        let playback = handler.playback.read().unwrap().clone().unwrap();
+        assert_eq!(playback.position, 1);
+        assert_eq!(
+            *metadata.lock().unwrap(),
+            vec![Some(Id::from(1_u64)), Some(Id::from(2_u64))]
+        );
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_media_controls_receive_state_on_track_change() {
+        let mut handler =
+            create_seek_test_handler(&testing::FakePlayer::new(), 30.0, SeekPastEnd::default());
+        let controls = MockMediaControls::default();
+        let metadata = controls.metadata.clone();
+        let states = controls.states.clone();
+
+        handler.attach_media_controls(controls);
+
+        handler
+            .update_playback(
+                false,
+                None,
+                None,
+                None,
+                Some(1),
+                Some(0.0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                false,
+                None,
+            )
+            .await
+            .unwrap();
+
+        // Progress changes alone don't push new state
+        handler
+            .update_playback(
+                false,
+                None,
+                None,
+                None,
+                None,
+                Some(10.0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                false,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *metadata.lock().unwrap(),
+            vec![Some(Id::from(1_u64)), Some(Id::from(2_u64))]
+        );
+        assert_eq!(
+            *states.lock().unwrap(),
+            vec![
+                MediaPlaybackState {
+                    playing: true,
+                    position_secs: 30.0,
+                },
+                MediaPlaybackState {
+                    playing: true,
+                    position_secs: 0.0,
+                },
+            ]
+        );
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_media_controls_pause_event_pushes_state() {
+        let mut handler =
+            create_seek_test_handler(&testing::FakePlayer::new(), 30.0, SeekPastEnd::default());
+        let controls = MockMediaControls::default();
+        let metadata = controls.metadata.clone();
+        let states = controls.states.clone();
+
+        handler.attach_media_controls(controls);
+
+        handler
+            .handle_media_control_event(MediaControlEvent::Pause)
+            .await
+            .unwrap();
+
+        assert!(!handler.is_playing());
+        assert_eq!(*metadata.lock().unwrap(), vec![Some(Id::from(1_u64))]);
+        assert_eq!(
+            states.lock().unwrap().last(),
+            Some(&MediaPlaybackState {
+                playing: false,
+                position_secs: 30.0,
+            })
+        );
+    }
+}
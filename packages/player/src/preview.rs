@@ -0,0 +1,423 @@
+//! Short preview clips played without touching the queue.
+//!
+//! [`PlaybackHandler::play_preview`] plays part of a track as a temporary single-track
+//! playback, then puts back the playback it replaced once the clip ends.
+
+use atomic_float::AtomicF64;
+use moosicbox_music_models::{PlaybackQuality, Track};
+use switchy_async::util::CancellationToken;
+
+use crate::{PREVIEW_FADE_DURATION, Playback, PlaybackHandler, PlayerError, VOLUME_RAMP_INTERVAL};
+
+/// A clip started by [`PlaybackHandler::play_preview`] that hasn't finished yet.
+#[derive(Debug)]
+pub struct ActivePreview {
+    /// Id of the single-track playback playing the clip
+    playback_id: u64,
+    /// Cancelled when the preview is superseded
+    cancel: CancellationToken,
+    /// The playback the preview temporarily replaced, restored once it finishes
+    main: Option<Playback>,
+}
+
+#[cfg_attr(feature = "profiling", profiling::all_functions)]
+impl PlaybackHandler {
+    /// Fades the output gain down to silence over `duration` without touching the stored
+    /// playback volume.
+    ///
+    /// The gain is restored by the next playback update.
+    pub(crate) async fn fade_out_output(&self, duration: std::time::Duration) {
+        let from = self.applied_volume();
+        let steps = u32::try_from(duration.as_millis() / VOLUME_RAMP_INTERVAL.as_millis())
+            .unwrap_or(u32::MAX)
+            .max(1);
+
+        log::debug!("fade_out_output: from={from} duration={duration:?} steps={steps}");
+
+        for step in 1..=steps {
+            self.clock.sleep(duration / steps).await;
+            self.player
+                .apply_output_volume(from * (1.0 - f64::from(step) / f64::from(steps)));
+        }
+    }
+
+    /// Plays a short preview clip of `track` without touching the current queue or session.
+    ///
+    /// Playback starts `clip_start_fraction` of the way into the track (e.g. `0.25` to land
+    /// near the chorus) and, once `clip_duration` has passed, fades out over
+    /// [`PREVIEW_FADE_DURATION`] and stops. Resolves when the clip has finished.
+    ///
+    /// The clip plays as a temporary single-track playback without a playback target, so no
+    /// session updates are sent for it. The previous playback is put back, unchanged, once
+    /// the clip finishes. Starting another preview cancels this one, as does starting a new
+    /// playback with [`Self::play_tracks`] and friends, which replaces the playback instead.
+    ///
+    /// # Panics
+    ///
+    /// * If the `playback` `RwLock` or the preview `Mutex` is poisoned
+    ///
+    /// # Errors
+    ///
+    /// * If `clip_start_fraction` is not in `0.0..1.0`
+    /// * If the current playback is playing, since the output can only play one of them
+    /// * If failed to play or stop the clip
+    /// * [`PlayerError::Cancelled`] if the preview was superseded before it finished
+    pub async fn play_preview(
+        &mut self,
+        track: Track,
+        clip_start_fraction: f64,
+        clip_duration: std::time::Duration,
+    ) -> Result<(), PlayerError> {
+        if !(0.0..1.0).contains(&clip_start_fraction) {
+            return Err(PlayerError::Seek(format!(
+                "Invalid preview clip start: {clip_start_fraction}"
+            )));
+        }
+
+        let cancel = CancellationToken::new();
+
+        let preview = {
+            let mut active = self.preview.lock().unwrap();
+
+            let main = if let Some(previous) = active.take() {
+                log::debug!("play_preview: Cancelling previous preview");
+                previous.cancel.cancel();
+                previous.main
+            } else {
+                let main = self.playback.read().unwrap().clone();
+                if let Some(main) = main.as_ref().filter(|x| x.playing) {
+                    return Err(PlayerError::PlaybackAlreadyPlaying(main.id));
+                }
+                main
+            };
+
+            let mut preview = Playback::new(
+                vec![track],
+                None,
+                AtomicF64::new(
+                    main.as_ref()
+                        .map_or(1.0, |x| x.volume.load(std::sync::atomic::Ordering::SeqCst)),
+                ),
+                main.as_ref()
+                    .map_or_else(PlaybackQuality::default, |x| x.quality),
+                main.as_ref().map_or(0, |x| x.session_id),
+                main.as_ref()
+                    .map_or_else(String::new, |x| x.profile.clone()),
+                None,
+            );
+            preview.playing = true;
+
+            *active = Some(ActivePreview {
+                playback_id: preview.id,
+                cancel: cancel.clone(),
+                main,
+            });
+
+            preview
+        };
+
+        let seek = preview
+            .current_track_duration()
+            .map(|duration| duration * clip_start_fraction);
+
+        log::debug!(
+            "play_preview: track={:?} seek={seek:?} duration={clip_duration:?}",
+            preview.tracks[0].id
+        );
+
+        // Stop whatever a superseded preview or the paused playback left in the output
+        if let Err(e) = self.stop(None).await {
+            self.finish_preview(preview.id);
+            return Err(e);
+        }
+
+        {
+            // Superseding previews are cancelled while holding the lock, so a newer preview's
+            // playback is never overwritten
+            let _active = self.preview.lock().unwrap();
+            if cancel.is_cancelled() {
+                log::debug!("play_preview: Preview superseded before starting");
+                return Err(PlayerError::Cancelled);
+            }
+            self.playback.write().unwrap().replace(preview.clone());
+        }
+        // Undo the fade-out of a superseded preview
+        self.apply_output_volume();
+
+        let clock = self.clock.clone();
+
+        #[allow(clippy::redundant_pub_crate)]
+        let (result, clip_elapsed) = switchy_async::select! {
+            () = cancel.cancelled() => (Err(PlayerError::Cancelled), false),
+            () = clock.sleep(clip_duration) => (Ok(()), true),
+            result = self.play_with_cancellation(seek, None, cancel.clone()) => (result, false),
+        };
+
+        if clip_elapsed && !cancel.is_cancelled() {
+            self.fade_out_output(PREVIEW_FADE_DURATION).await;
+        }
+
+        if cancel.is_cancelled() {
+            log::debug!("play_preview: Preview superseded");
+            return Err(PlayerError::Cancelled);
+        }
+
+        let stopped = self.stop(None).await;
+        self.finish_preview(preview.id);
+
+        result.and(stopped)
+    }
+
+    /// Ends the preview playing as `playback_id`, if it is still the active one, putting
+    /// back the playback it replaced and undoing its fade-out.
+    fn finish_preview(&self, playback_id: u64) {
+        let finished = {
+            let mut active = self.preview.lock().unwrap();
+            if active
+                .as_ref()
+                .is_some_and(|x| x.playback_id == playback_id)
+            {
+                active.take()
+            } else {
+                None
+            }
+        };
+
+        if let Some(finished) = finished {
+            let mut playback = self.playback.write().unwrap();
+            if playback.as_ref().is_some_and(|x| x.id == playback_id) {
+                *playback = finished.main;
+            }
+        }
+
+        self.apply_output_volume();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use moosicbox_music_models::id::Id;
+
+    use super::*;
+    use crate::{
+        clock, testing,
+        tests::{
+            create_endless_player, create_test_track, create_volume_test_handler, mock_clock_at,
+        },
+    };
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_fade_out_completes_by_advancing_mock_clock() {
+        let player = testing::FakePlayer::new();
+        let clock = mock_clock_at(12, 0);
+        let handler = create_volume_test_handler(&player, &clock);
+        let before = player.output_volumes().len();
+
+        let fade = switchy_async::runtime::Handle::current().spawn_with_name("test: fade", {
+            let handler = handler.clone();
+            async move { handler.fade_out_output(VOLUME_RAMP_INTERVAL * 4).await }
+        });
+
+        // Each step applies its volume before sleeping until the next one
+        for step in 1..=4 {
+            clock.wait_for_sleepers().await;
+            assert_eq!(player.output_volumes().len() - before, step - 1);
+
+            clock.advance(VOLUME_RAMP_INTERVAL);
+        }
+
+        fade.await.unwrap();
+
+        assert_eq!(clock.elapsed(), VOLUME_RAMP_INTERVAL * 4);
+        assert_eq!(player.output_volumes()[before..], [0.75, 0.5, 0.25, 0.0]);
+    }
+
+    const PREVIEW_CLIP: std::time::Duration = std::time::Duration::from_secs(30);
+
+    /// Creates a handler with a paused three-track queue, 42 seconds into its second track.
+    fn create_preview_test_handler(
+        player: &testing::FakePlayer,
+        playing: bool,
+    ) -> (PlaybackHandler, clock::MockClock) {
+        let clock = clock::MockClock::default();
+        let mut playback = Playback::new(
+            vec![
+                create_test_track(1),
+                create_test_track(2),
+                create_test_track(3),
+            ],
+            Some(1),
+            AtomicF64::new(0.8),
+            PlaybackQuality::default(),
+            1,
+            "default".to_string(),
+            None,
+        );
+        playback.progress = 42.0;
+        playback.playing = playing;
+
+        let handler = PlaybackHandler::new(player.clone())
+            .with_playback(Arc::new(std::sync::RwLock::new(Some(playback))))
+            .with_clock(clock.clone());
+
+        (handler, clock)
+    }
+
+    fn spawn_preview(
+        handler: &PlaybackHandler,
+        track_id: u64,
+        clip_start_fraction: f64,
+    ) -> switchy_async::task::JoinHandle<Result<(), PlayerError>> {
+        switchy_async::runtime::Handle::current().spawn_with_name("test: preview", {
+            let mut handler = handler.clone();
+            async move {
+                handler
+                    .play_preview(
+                        create_test_track(track_id),
+                        clip_start_fraction,
+                        PREVIEW_CLIP,
+                    )
+                    .await
+            }
+        })
+    }
+
+    /// Advances `clock` through the clip and the fade-out until `preview` resolves.
+    async fn finish_preview(
+        clock: &clock::MockClock,
+        preview: switchy_async::task::JoinHandle<Result<(), PlayerError>>,
+    ) -> Result<(), PlayerError> {
+        let mut preview = std::pin::pin!(preview);
+
+        loop {
+            switchy_async::select! {
+                result = &mut preview => return result.unwrap(),
+                () = clock.wait_for_sleepers() => clock.advance(VOLUME_RAMP_INTERVAL),
+            }
+        }
+    }
+
+    fn current_track_id(handler: &PlaybackHandler) -> Option<Id> {
+        let playback = handler.playback.read().unwrap();
+        let playback = playback.as_ref()?;
+        playback
+            .tracks
+            .get(playback.position as usize)
+            .map(|x| x.id.clone())
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_play_preview_starts_at_offset_and_stops_after_clip() {
+        let (player, _hold) = create_endless_player();
+        let (handler, clock) = create_preview_test_handler(&player, false);
+
+        let preview = spawn_preview(&handler, 9, 0.25);
+        player.wait_until(|x| x.plays().len() == 1).await;
+
+        // 25% into the 180 second track
+        assert_eq!(player.plays(), vec![Some(45.0)]);
+        assert_eq!(current_track_id(&handler), Some(9.into()));
+        let stops_before_clip = player.call_count(testing::PlayerCall::Stop);
+
+        clock.wait_for_sleepers().await;
+        clock.advance(PREVIEW_CLIP - std::time::Duration::from_millis(1));
+
+        // Still playing the clip
+        assert_eq!(clock.sleepers(), 1);
+        assert_eq!(current_track_id(&handler), Some(9.into()));
+        assert_eq!(
+            player.call_count(testing::PlayerCall::Stop),
+            stops_before_clip
+        );
+
+        clock.advance(std::time::Duration::from_millis(1));
+        finish_preview(&clock, preview).await.unwrap();
+
+        assert_eq!(clock.elapsed(), PREVIEW_CLIP + PREVIEW_FADE_DURATION);
+        assert_eq!(
+            player.call_count(testing::PlayerCall::Stop),
+            stops_before_clip + 1
+        );
+        // Faded out before stopping, then restored to the playback's volume
+        let applied = player.output_volumes();
+        assert!(applied.contains(&0.0));
+        assert!((applied.last().unwrap() - 0.8).abs() < f64::EPSILON);
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_play_preview_leaves_main_queue_untouched() {
+        let (player, _hold) = create_endless_player();
+        let (handler, clock) = create_preview_test_handler(&player, false);
+        let events = handler.subscribe_events();
+        let main = handler.playback.read().unwrap().clone().unwrap();
+
+        let preview = spawn_preview(&handler, 9, 0.5);
+        player.wait_until(|x| x.plays().len() == 1).await;
+        finish_preview(&clock, preview).await.unwrap();
+
+        let playback = handler.playback.read().unwrap().clone().unwrap();
+        assert_eq!(playback.id, main.id);
+        assert_eq!(
+            playback.tracks.iter().map(|x| &x.id).collect::<Vec<_>>(),
+            main.tracks.iter().map(|x| &x.id).collect::<Vec<_>>()
+        );
+        assert_eq!(playback.position, 1);
+        assert!((playback.progress - 42.0).abs() < f64::EPSILON);
+        assert!(!playback.playing);
+        assert!(!handler.is_playing());
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_play_preview_cancels_previous_preview() {
+        let (player, _hold) = create_endless_player();
+        let (handler, clock) = create_preview_test_handler(&player, false);
+        let main_id = handler.playback.read().unwrap().as_ref().unwrap().id;
+
+        let first = spawn_preview(&handler, 9, 0.25);
+        player.wait_until(|x| x.plays().len() == 1).await;
+
+        let second = spawn_preview(&handler, 10, 0.5);
+        assert!(matches!(first.await.unwrap(), Err(PlayerError::Cancelled)));
+        player.wait_until(|x| x.plays().len() == 2).await;
+
+        assert_eq!(player.plays(), vec![Some(45.0), Some(90.0)]);
+        assert_eq!(current_track_id(&handler), Some(10.into()));
+
+        finish_preview(&clock, second).await.unwrap();
+
+        // The playback from before the first preview is restored
+        assert_eq!(
+            handler.playback.read().unwrap().as_ref().unwrap().id,
+            main_id
+        );
+        assert_eq!(current_track_id(&handler), Some(2.into()));
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_play_preview_rejects_playing_playback_and_invalid_offset() {
+        let (mut handler, _clock) = create_preview_test_handler(&testing::FakePlayer::new(), true);
+
+        assert!(matches!(
+            handler
+                .play_preview(create_test_track(9), 0.25, PREVIEW_CLIP)
+                .await,
+            Err(PlayerError::PlaybackAlreadyPlaying(_))
+        ));
+        assert_eq!(current_track_id(&handler), Some(2.into()));
+
+        let (mut handler, _clock) = create_preview_test_handler(&testing::FakePlayer::new(), false);
+
+        for fraction in [-0.1, 1.0, f64::NAN] {
+            assert!(matches!(
+                handler
+                    .play_preview(create_test_track(9), fraction, PREVIEW_CLIP)
+                    .await,
+                Err(PlayerError::Seek(_))
+            ));
+        }
+        assert_eq!(current_track_id(&handler), Some(2.into()));
+    }
+}
@@ -0,0 +1,330 @@
+//! Radio mode, which keeps playback going once the queue runs dry.
+//!
+//! When [`RadioMode::On`] is set on a [`PlaybackHandler`](crate::PlaybackHandler) with a
+//! [`RadioRecommender`] (see
+//! [`with_radio_recommender`](crate::PlaybackHandler::with_radio_recommender)), starting
+//! the last track in the queue asks the recommender for more tracks based on the most
+//! recently played ones and appends them while it plays, so playback continues without a
+//! gap instead of stopping. Playback stops as usual once the recommender has nothing new
+//! to offer.
+
+use futures::future::BoxFuture;
+use moosicbox_music_models::Track;
+
+use crate::{Playback, PlaybackHandler, QueuedTrack, events::PlayerEvent, trigger_playback_event};
+
+/// Maximum number of recently played tracks passed to [`RadioRecommender::fetch_more`].
+pub const RADIO_SEED_SIZE: usize = 5;
+
+/// Whether the queue is extended with recommended tracks when it runs dry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RadioMode {
+    /// Stop playback after the last track in the queue
+    #[default]
+    Off,
+    /// Append recommended tracks while the last track in the queue plays
+    On,
+}
+
+/// Source of recommended tracks for [`RadioMode`].
+///
+/// Implementations typically ask a [`MusicApi`](moosicbox_music_api::MusicApi) for tracks
+/// similar to `seed`.
+pub trait RadioRecommender: std::fmt::Debug + Send + Sync {
+    /// Fetches tracks to append to the queue, given the most recently played tracks in
+    /// play order.
+    ///
+    /// Returning no tracks stops playback.
+    fn fetch_more<'a>(&'a self, seed: &'a [Track]) -> BoxFuture<'a, Vec<Track>>;
+}
+
+/// Returns the tracks to seed recommendations with: up to [`RADIO_SEED_SIZE`] tracks
+/// ending at `position`.
+#[must_use]
+pub fn radio_seed<T>(tracks: &[T], position: usize) -> &[T] {
+    let end = (position + 1).min(tracks.len());
+    &tracks[end.saturating_sub(RADIO_SEED_SIZE)..end]
+}
+
+#[cfg_attr(feature = "profiling", profiling::all_functions)]
+impl PlaybackHandler {
+    /// Turns radio mode on or off.
+    ///
+    /// While on, finishing the last track in the queue appends tracks from the
+    /// [`RadioRecommender`] instead of stopping. Has no effect without a recommender.
+    ///
+    /// # Panics
+    ///
+    /// * If the `radio_mode` `RwLock` is poisoned
+    pub fn set_radio_mode(&self, mode: RadioMode) {
+        *self.radio_mode.write().unwrap() = mode;
+    }
+
+    /// Whether radio mode is on.
+    ///
+    /// # Panics
+    ///
+    /// * If the `radio_mode` `RwLock` is poisoned
+    #[must_use]
+    pub fn radio_mode(&self) -> RadioMode {
+        *self.radio_mode.read().unwrap()
+    }
+
+    /// Fetches recommended tracks to append to `playback`'s queue, seeded with the most
+    /// recently played tracks.
+    ///
+    /// Returns no tracks if radio mode is off, there is no recommender, or the recommender
+    /// only offers tracks that are already queued.
+    async fn fetch_radio_tracks(&self, playback: &Playback) -> Vec<Track> {
+        let Some(recommender) = &self.radio_recommender else {
+            return vec![];
+        };
+        if self.radio_mode() != RadioMode::On {
+            return vec![];
+        }
+
+        let seed = radio_seed(&playback.tracks, playback.position as usize)
+            .iter()
+            .map(|x| x.track.clone())
+            .collect::<Vec<_>>();
+        let mut tracks = recommender.fetch_more(&seed).await;
+        tracks.retain(|track| !playback.tracks.iter().any(|x| x.queues(track)));
+
+        let available = usize::from(u16::MAX).saturating_sub(playback.tracks.len());
+        tracks.truncate(available);
+
+        log::debug!(
+            "fetch_radio_tracks: Recommended tracks={:?}",
+            tracks.iter().map(|t| &t.id).collect::<Vec<_>>()
+        );
+
+        tracks
+    }
+
+    /// Fetches radio tracks for `playback` and appends them to the queue of the active
+    /// playback, if it is still the same playback, emitting
+    /// [`PlayerEvent::RadioTracksQueued`].
+    ///
+    /// Returns the appended tracks, so the play task can append them to its own copy of
+    /// the queue if it already adopted the queue (see [`Self::sync_queue`]).
+    pub(crate) async fn queue_radio_tracks(&self, playback: &Playback) -> Vec<QueuedTrack> {
+        let tracks = self.fetch_radio_tracks(playback).await;
+        if tracks.is_empty() || playback.abort.is_cancelled() {
+            return vec![];
+        }
+
+        let (updated, old, queued) = {
+            let mut binding = self.playback.write().unwrap();
+            let Some(current) = binding.as_mut().filter(|x| x.id == playback.id) else {
+                return vec![];
+            };
+
+            let old = current.clone();
+            let available = usize::from(u16::MAX).saturating_sub(current.tracks.len());
+            let queued = tracks
+                .into_iter()
+                .filter(|track| !current.tracks.iter().any(|x| x.queues(track)))
+                .take(available)
+                .map(QueuedTrack::from)
+                .collect::<Vec<_>>();
+            current.tracks.extend(queued.iter().cloned());
+
+            (current.clone(), old, queued)
+        };
+
+        if queued.is_empty() {
+            return queued;
+        }
+
+        log::debug!(
+            "queue_radio_tracks: Extended queue with {} radio tracks",
+            queued.len()
+        );
+
+        self.playback_updated(&updated);
+        trigger_playback_event(&updated, &old);
+        self.emit_event(&PlayerEvent::RadioTracksQueued {
+            track_ids: queued.iter().map(|x| x.track.id.clone()).collect(),
+        });
+
+        queued
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use atomic_float::AtomicF64;
+    use moosicbox_music_models::{PlaybackQuality, id::Id};
+
+    use super::*;
+    use crate::{
+        testing,
+        tests::{create_test_track, ids, wait_for_playback_to_finish},
+    };
+
+    /// Mock recommender that returns the given batches of track IDs in order, then nothing.
+    #[derive(Debug, Default)]
+    struct MockRecommender {
+        batches: std::sync::Mutex<Vec<Vec<u64>>>,
+        seeds: Arc<std::sync::Mutex<Vec<Vec<Id>>>>,
+    }
+
+    impl RadioRecommender for MockRecommender {
+        fn fetch_more<'a>(
+            &'a self,
+            seed: &'a [Track],
+        ) -> futures::future::BoxFuture<'a, Vec<Track>> {
+            self.seeds
+                .lock()
+                .unwrap()
+                .push(seed.iter().map(|x| x.id.clone()).collect());
+
+            let mut batches = self.batches.lock().unwrap();
+            let batch = if batches.is_empty() {
+                vec![]
+            } else {
+                batches.remove(0)
+            };
+            drop(batches);
+
+            Box::pin(async move { batch.into_iter().map(create_test_track).collect() })
+        }
+    }
+
+    /// Creates a handler with radio mode on whose queue holds `track_ids`.
+    fn create_radio_test_handler(
+        track_ids: &[u64],
+        recommender: MockRecommender,
+    ) -> (PlaybackHandler, testing::FakePlayer) {
+        let playback = Arc::new(std::sync::RwLock::new(Some(Playback::new(
+            track_ids.iter().copied().map(create_test_track).collect(),
+            Some(0),
+            AtomicF64::new(1.0),
+            PlaybackQuality::default(),
+            1,
+            "default".to_string(),
+            None,
+        ))));
+        let player = testing::FakePlayer::new().with_playback(playback.clone());
+
+        let handler = PlaybackHandler::new(player.clone())
+            .with_playback(playback)
+            .with_radio_recommender(recommender);
+        handler.set_radio_mode(RadioMode::On);
+
+        (handler, player)
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_radio_extends_queue_before_running_out() {
+        let recommender = MockRecommender {
+            batches: std::sync::Mutex::new(vec![vec![2]]),
+            ..Default::default()
+        };
+        let (mut handler, player) = create_radio_test_handler(&[1], recommender);
+        let release = player.hold_plays();
+        let events = handler.subscribe_events();
+
+        handler.play_playback(None, None).await.unwrap();
+
+        // The last track is still playing when the radio tracks are queued
+        assert_eq!(
+            events.recv_async().await.unwrap(),
+            PlayerEvent::RadioTracksQueued {
+                track_ids: ids(&[2]),
+            }
+        );
+        assert_eq!(player.plays().len(), 1);
+        assert!(player.played().is_empty());
+        assert!(handler.is_playing());
+
+        drop(release);
+        wait_for_playback_to_finish(&handler).await;
+
+        assert_eq!(player.played(), ids(&[1, 2]));
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_radio_keeps_extending_queue_until_recommender_runs_dry() {
+        let recommender = MockRecommender {
+            batches: std::sync::Mutex::new(vec![vec![3, 4], vec![5]]),
+            ..Default::default()
+        };
+        let seeds = recommender.seeds.clone();
+        let (mut handler, player) = create_radio_test_handler(&[1, 2], recommender);
+
+        handler.play_playback(None, None).await.unwrap();
+        wait_for_playback_to_finish(&handler).await;
+
+        assert_eq!(player.played(), ids(&[1, 2, 3, 4, 5]));
+        assert_eq!(
+            *seeds.lock().unwrap(),
+            vec![ids(&[1, 2]), ids(&[1, 2, 3, 4]), ids(&[1, 2, 3, 4, 5])]
+        );
+
+        let playback = handler.playback.read().unwrap().clone().unwrap();
+        assert_eq!(playback.tracks.len(), 5);
+        assert_eq!(playback.position, 4);
+        assert!(!playback.playing);
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_radio_stops_when_recommender_offers_only_queued_tracks() {
+        let recommender = MockRecommender {
+            batches: std::sync::Mutex::new(vec![vec![1, 2]]),
+            ..Default::default()
+        };
+        let (mut handler, player) = create_radio_test_handler(&[1, 2], recommender);
+
+        handler.play_playback(None, None).await.unwrap();
+        wait_for_playback_to_finish(&handler).await;
+
+        assert_eq!(player.played(), ids(&[1, 2]));
+        assert_eq!(
+            handler
+                .playback
+                .read()
+                .unwrap()
+                .as_ref()
+                .unwrap()
+                .tracks
+                .len(),
+            2
+        );
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_radio_mode_off_stops_at_end_of_queue() {
+        let recommender = MockRecommender {
+            batches: std::sync::Mutex::new(vec![vec![3]]),
+            ..Default::default()
+        };
+        let seeds = recommender.seeds.clone();
+        let (mut handler, player) = create_radio_test_handler(&[1], recommender);
+        handler.set_radio_mode(RadioMode::Off);
+
+        handler.play_playback(None, None).await.unwrap();
+        wait_for_playback_to_finish(&handler).await;
+
+        assert_eq!(player.played(), ids(&[1]));
+        assert!(seeds.lock().unwrap().is_empty());
+    }
+
+    #[test_log::test]
+    fn test_radio_seed_takes_recent_tracks() {
+        let tracks: Vec<Track> = (1..=8).map(create_test_track).collect();
+
+        let seed = |position| {
+            radio_seed(&tracks, position)
+                .iter()
+                .map(|x| x.id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(seed(0), ids(&[1]));
+        assert_eq!(seed(6), ids(&[3, 4, 5, 6, 7]));
+        assert_eq!(seed(20), ids(&[4, 5, 6, 7, 8]));
+    }
+}
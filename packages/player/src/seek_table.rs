@@ -0,0 +1,244 @@
+//! Externally computed seek tables.
+//!
+//! Symphonia seeks some containers poorly, e.g. MP3 files without a Xing/VBRI table of
+//! contents or raw ADTS streams, where it has to scan the stream from the start. A
+//! [`SeekTable`] maps timestamps to byte offsets so playback can jump directly to the
+//! closest preceding seek point instead. Attach one to a
+//! [`PlayableTrack`](crate::PlayableTrack) or register it for a track with
+//! [`LocalPlayer::set_seek_table`](crate::local::LocalPlayer::set_seek_table).
+//!
+//! The byte offsets must point at the start of a frame the decoder can resume from,
+//! since the stream is probed again from that offset. Only headerless streams, e.g. raw
+//! ADTS or MP3 frames, can be probed from the middle of the stream: containers that
+//! describe their streams in a header, like MP4, Ogg or FLAC, can't, so seek tables must
+//! not be used for them.
+//!
+//! The decoder resumes at the seek point, so the audio between the seek point and the
+//! seek target is decoded and dropped with a [`DecodeSkip`] before playback continues.
+
+use std::io::SeekFrom;
+
+use symphonia::core::{
+    audio::{AudioBuffer, Signal as _},
+    io::MediaSource,
+};
+
+/// A timestamp and the byte offset its frame starts at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeekPoint {
+    /// Position in the track, in seconds
+    pub time: f64,
+    /// Offset of the frame at `time` from the start of the media source, in bytes
+    pub byte_offset: u64,
+}
+
+/// Seek points of a track, ordered by time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SeekTable {
+    points: Vec<SeekPoint>,
+}
+
+impl SeekTable {
+    /// Creates a seek table from `points` in any order.
+    ///
+    /// Points with a negative or non-finite time are dropped.
+    #[must_use]
+    pub fn new(points: impl IntoIterator<Item = SeekPoint>) -> Self {
+        let mut points = points
+            .into_iter()
+            .filter(|point| point.time.is_finite() && point.time >= 0.0)
+            .collect::<Vec<_>>();
+        points.sort_by(|a, b| a.time.total_cmp(&b.time));
+
+        Self { points }
+    }
+
+    /// The seek points, ordered by time.
+    #[must_use]
+    pub fn points(&self) -> &[SeekPoint] {
+        &self.points
+    }
+
+    /// Whether the table has no seek points.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The last seek point at or before `time`, if any.
+    #[must_use]
+    pub fn lookup(&self, time: f64) -> Option<&SeekPoint> {
+        let index = self.points.partition_point(|point| point.time <= time);
+        index.checked_sub(1).map(|index| &self.points[index])
+    }
+}
+
+/// Moves `source` to the seek point of `table` closest before `time`.
+///
+/// Returns the seek point playback resumes from, or `None` if the source isn't seekable
+/// or the table has no point at or before `time`, in which case `source` is left as is.
+///
+/// # Errors
+///
+/// * If seeking `source` fails
+pub fn seek_source(
+    source: &mut dyn MediaSource,
+    table: &SeekTable,
+    time: f64,
+) -> std::io::Result<Option<SeekPoint>> {
+    if !source.is_seekable() {
+        return Ok(None);
+    }
+
+    let Some(point) = table.lookup(time).copied() else {
+        return Ok(None);
+    };
+
+    source.seek(SeekFrom::Start(point.byte_offset))?;
+
+    Ok(Some(point))
+}
+
+/// Drops the first `secs` of decoded audio, e.g. the audio between the seek point
+/// [`seek_source`] resumed from and the time that was seeked to.
+#[derive(Debug, Clone, Copy)]
+pub struct DecodeSkip {
+    secs: f64,
+    remaining_frames: Option<usize>,
+}
+
+impl DecodeSkip {
+    /// Creates a skip of the first `secs` of decoded audio.
+    #[must_use]
+    pub const fn new(secs: f64) -> Self {
+        Self {
+            secs,
+            remaining_frames: None,
+        }
+    }
+
+    /// Drops the frames of `decoded` that fall within the skipped audio.
+    ///
+    /// Buffers are expected in decoding order. Once the skipped audio has passed, buffers
+    /// are left as is.
+    pub fn apply(&mut self, decoded: &mut AudioBuffer<f32>) {
+        let secs = self.secs;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let remaining = self
+            .remaining_frames
+            .get_or_insert_with(|| (secs * f64::from(decoded.spec().rate)).round() as usize);
+
+        let skipped = (*remaining).min(decoded.frames());
+        decoded.shift(skipped);
+        *remaining -= skipped;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read, Seek as _};
+
+    use symphonia::core::audio::{Channels, SignalSpec};
+
+    use super::*;
+
+    fn point(time: f64, byte_offset: u64) -> SeekPoint {
+        SeekPoint { time, byte_offset }
+    }
+
+    fn table() -> SeekTable {
+        SeekTable::new([
+            point(20.0, 320_000),
+            point(0.0, 417),
+            point(10.0, 160_417),
+            point(f64::NAN, 1),
+            point(-1.0, 2),
+        ])
+    }
+
+    #[test_log::test]
+    fn new_sorts_points_and_drops_invalid_times() {
+        assert_eq!(
+            table().points(),
+            &[point(0.0, 417), point(10.0, 160_417), point(20.0, 320_000)]
+        );
+    }
+
+    #[test_log::test]
+    fn lookup_returns_last_point_at_or_before_time() {
+        let table = table();
+
+        assert_eq!(table.lookup(0.0), Some(&point(0.0, 417)));
+        assert_eq!(table.lookup(9.99), Some(&point(0.0, 417)));
+        assert_eq!(table.lookup(10.0), Some(&point(10.0, 160_417)));
+        assert_eq!(table.lookup(15.5), Some(&point(10.0, 160_417)));
+        assert_eq!(table.lookup(3600.0), Some(&point(20.0, 320_000)));
+    }
+
+    #[test_log::test]
+    fn lookup_before_first_point_returns_none() {
+        let table = SeekTable::new([point(5.0, 1000)]);
+
+        assert_eq!(table.lookup(4.0), None);
+        assert_eq!(SeekTable::default().lookup(4.0), None);
+    }
+
+    #[test_log::test]
+    fn seek_source_jumps_to_table_byte_offset() {
+        let data = (0..=u8::MAX).cycle().take(400_000).collect::<Vec<_>>();
+        let mut source = Cursor::new(data.clone());
+
+        let resumed = seek_source(&mut source, &table(), 12.5).unwrap();
+
+        assert_eq!(resumed, Some(point(10.0, 160_417)));
+        assert_eq!(source.stream_position().unwrap(), 160_417);
+
+        let mut byte = [0];
+        source.read_exact(&mut byte).unwrap();
+        assert_eq!(byte[0], data[160_417]);
+    }
+
+    #[test_log::test]
+    fn seek_source_without_matching_point_leaves_source_untouched() {
+        let mut source = Cursor::new(vec![0_u8; 100]);
+        source.seek(SeekFrom::Start(42)).unwrap();
+
+        let resumed = seek_source(&mut source, &SeekTable::new([point(5.0, 10)]), 1.0).unwrap();
+
+        assert_eq!(resumed, None);
+        assert_eq!(source.stream_position().unwrap(), 42);
+    }
+
+    /// A mono 1kHz buffer of `frames` frames counting up from `first`.
+    fn buffer(first: u16, frames: u16) -> AudioBuffer<f32> {
+        let mut buffer = AudioBuffer::new(
+            u64::from(frames),
+            SignalSpec::new(1_000, Channels::FRONT_LEFT),
+        );
+        buffer.render_reserved(Some(usize::from(frames)));
+        for (sample, value) in buffer.chan_mut(0).iter_mut().zip(first..) {
+            *sample = f32::from(value);
+        }
+        buffer
+    }
+
+    #[test_log::test]
+    fn decode_skip_drops_audio_up_to_the_target_across_buffers() {
+        // 250ms at 1kHz
+        let mut skip = DecodeSkip::new(0.25);
+
+        let mut first = buffer(0, 200);
+        skip.apply(&mut first);
+        assert_eq!(first.frames(), 0);
+
+        let mut second = buffer(200, 200);
+        skip.apply(&mut second);
+        assert_eq!(second.frames(), 150);
+        assert!((second.chan(0)[0] - 250.0).abs() < f32::EPSILON);
+
+        let mut third = buffer(400, 200);
+        skip.apply(&mut third);
+        assert_eq!(third.frames(), 200);
+        assert!((third.chan(0)[0] - 400.0).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,229 @@
+//! Listening statistics of the current session.
+//!
+//! Played time is accumulated from the progress reported by the player while it is
+//! playing, so time spent paused doesn't count, and neither does the span jumped over by
+//! a seek. Track durations are never used: a track skipped halfway contributes half of
+//! its length.
+//!
+//! Statistics belong to a session. They reset when the playback switches to a different
+//! session (see [`Playback::session_id`]) and are not persisted.
+
+use std::time::Duration;
+
+use crate::{Playback, PlaybackHandler};
+
+/// Largest progress step, in seconds, counted as played time.
+///
+/// The player reports its progress many times per second, so a larger step between two
+/// updates is a seek that wasn't announced through [`crate::PlaybackHandler::seek`], e.g.
+/// one performed by the player itself.
+pub const MAX_PROGRESS_STEP_SECS: f64 = 2.0;
+
+/// Listening statistics of a session, see [`crate::PlaybackHandler::session_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionStats {
+    /// Time actually spent playing
+    pub played_duration: Duration,
+    /// Number of tracks that played until their end
+    pub tracks_completed: u32,
+    /// Number of tracks left with [`crate::PlaybackHandler::next_track`] or
+    /// [`crate::PlaybackHandler::previous_track`] while they were playing
+    pub tracks_skipped: u32,
+}
+
+/// Accumulates the [`SessionStats`] of the session a handler is playing.
+#[derive(Debug, Default)]
+pub(crate) struct SessionStatsTracker {
+    /// Session the statistics belong to
+    session_id: Option<u64>,
+    /// Playback id, queue position and progress of the last update while playing
+    last_progress: Option<(u64, u16, f64)>,
+    stats: SessionStats,
+}
+
+impl SessionStatsTracker {
+    /// Resets the statistics if `session_id` is not the session they belong to.
+    fn enter_session(&mut self, session_id: u64) {
+        if self.session_id != Some(session_id) {
+            *self = Self {
+                session_id: Some(session_id),
+                ..Self::default()
+            };
+        }
+    }
+
+    /// Adds the progress made since the previous update of the same track to the played
+    /// time.
+    pub(crate) fn record_progress(&mut self, playback: &Playback) {
+        self.enter_session(playback.session_id);
+
+        if !playback.playing {
+            self.last_progress = None;
+            return;
+        }
+
+        let current = (playback.id, playback.position, playback.progress);
+        if let Some((id, position, progress)) = self.last_progress.replace(current)
+            && id == playback.id
+            && position == playback.position
+        {
+            let step = playback.progress - progress;
+            if step > 0.0 && step <= MAX_PROGRESS_STEP_SECS {
+                self.stats.played_duration += Duration::from_secs_f64(step);
+            }
+        }
+    }
+
+    /// Makes the next progress update the new starting point, e.g. after a seek.
+    pub(crate) const fn rebase(&mut self) {
+        self.last_progress = None;
+    }
+
+    /// Counts a track of `session_id` that played until its end.
+    pub(crate) fn record_completed(&mut self, session_id: u64) {
+        self.enter_session(session_id);
+        self.stats.tracks_completed += 1;
+    }
+
+    /// Counts a track of `session_id` left before its end.
+    pub(crate) fn record_skipped(&mut self, session_id: u64) {
+        self.enter_session(session_id);
+        self.stats.tracks_skipped += 1;
+    }
+
+    /// Statistics accumulated so far.
+    pub(crate) const fn stats(&self) -> SessionStats {
+        self.stats
+    }
+}
+
+#[cfg_attr(feature = "profiling", profiling::all_functions)]
+impl PlaybackHandler {
+    /// Listening statistics of the session this handler is playing, accumulated from the
+    /// actual playback progress. See the [module documentation](self).
+    ///
+    /// # Panics
+    ///
+    /// * If the `session_stats` `Mutex` is poisoned
+    #[must_use]
+    pub fn session_stats(&self) -> SessionStats {
+        self.session_stats.lock().unwrap().stats()
+    }
+
+    /// Counts the current track of `playback` as skipped in the [`SessionStats`] if it was
+    /// playing when the handler moved on from it.
+    pub(crate) fn record_skip(&self, playback: &Playback) {
+        if playback.playing {
+            self.session_stats
+                .lock()
+                .unwrap()
+                .record_skipped(playback.session_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        SeekPastEnd, testing,
+        tests::{create_seek_test_handler, ids, start_held_queue, wait_for_playback_to_finish},
+    };
+
+    /// Reports progress like the player does while playing, from `from` to `to` seconds in
+    /// quarter-second steps.
+    fn report_progress(handler: &PlaybackHandler, playing: bool, from: f64, to: f64) {
+        let mut progress = from;
+        loop {
+            let playback = {
+                let mut binding = handler.playback.write().unwrap();
+                let playback = binding.as_mut().unwrap();
+                playback.playing = playing;
+                playback.progress = progress.min(to);
+                playback.clone()
+            };
+            handler.playback_updated(&playback);
+
+            if progress >= to {
+                break;
+            }
+            progress += 0.25;
+        }
+    }
+
+    fn assert_played_secs(handler: &PlaybackHandler, expected: f64) {
+        let played = handler.session_stats().played_duration.as_secs_f64();
+        assert!(
+            (played - expected).abs() < 0.01,
+            "expected {expected}s of played time, got {played}s"
+        );
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_session_stats_count_progress_excluding_pauses_and_seeks() {
+        let mut handler =
+            create_seek_test_handler(&testing::FakePlayer::new(), 0.0, SeekPastEnd::default());
+
+        report_progress(&handler, true, 0.0, 30.0);
+        assert_played_secs(&handler, 30.0);
+
+        // Paused: progress reported while paused doesn't count, and resuming picks up from
+        // the paused position
+        report_progress(&handler, false, 30.0, 31.0);
+        report_progress(&handler, true, 30.0, 40.0);
+        assert_played_secs(&handler, 40.0);
+
+        // Seeked forward: the seeked-over span doesn't count
+        handler.seek(100.0, None).await.unwrap();
+        report_progress(&handler, true, 100.0, 105.0);
+        assert_played_secs(&handler, 45.0);
+
+        // A jump the handler wasn't told about doesn't count either
+        report_progress(&handler, true, 150.0, 152.0);
+        assert_played_secs(&handler, 47.0);
+
+        let stats = handler.session_stats();
+        assert_eq!(stats.tracks_completed, 0);
+        assert_eq!(stats.tracks_skipped, 0);
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_session_stats_count_completed_and_skipped_tracks() {
+        let (mut handler, player, release) = start_held_queue(&[1, 2, 3]).await;
+
+        report_progress(&handler, true, 0.0, 12.5);
+        handler.next_track(None, None).await.unwrap();
+        drop(release);
+        wait_for_playback_to_finish(&handler).await;
+
+        // The skipped track was cancelled rather than finished
+        assert_eq!(player.plays().len(), 3);
+        assert_eq!(player.played(), ids(&[2, 3]));
+        assert_eq!(
+            handler.session_stats(),
+            SessionStats {
+                played_duration: std::time::Duration::from_secs_f64(12.5),
+                tracks_completed: 2,
+                tracks_skipped: 1,
+            }
+        );
+    }
+
+    #[test_log::test]
+    fn test_session_stats_reset_on_new_session() {
+        let handler =
+            create_seek_test_handler(&testing::FakePlayer::new(), 0.0, SeekPastEnd::default());
+
+        report_progress(&handler, true, 0.0, 10.0);
+        assert_played_secs(&handler, 10.0);
+
+        {
+            let mut binding = handler.playback.write().unwrap();
+            let playback = binding.as_mut().unwrap();
+            playback.session_id = 2;
+            playback.progress = 0.0;
+        }
+        report_progress(&handler, true, 0.0, 4.0);
+        assert_played_secs(&handler, 4.0);
+    }
+}
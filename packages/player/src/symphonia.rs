@@ -9,8 +9,8 @@ use std::{fs::File, path::Path};
 use moosicbox_audio_decoder::{AudioDecodeHandler, DecodeError, decode};
 use switchy_async::task::JoinError;
 use symphonia::core::{
-    codecs::DecoderOptions,
-    formats::FormatOptions,
+    codecs::{Decoder, DecoderOptions},
+    formats::{FormatOptions, FormatReader},
     io::{MediaSourceStream, MediaSourceStreamOptions},
     meta::MetadataOptions,
     probe::Hint,
@@ -172,6 +172,31 @@ pub fn play_media_source(
     track_num: Option<usize>,
     seek: Option<f64>,
 ) -> Result<i32, PlaybackError> {
+    let format = probe_format(media_source_stream, hint, enable_gapless)?;
+
+    // Set the decoder options.
+    let decode_opts = DecoderOptions { verify };
+
+    // Play it!
+    Ok(decode(
+        format,
+        audio_decode_handler,
+        track_num,
+        seek,
+        decode_opts,
+    )?)
+}
+
+/// Probes a media source stream for its container format and returns its format reader.
+///
+/// # Errors
+///
+/// * If the input is not supported by any format reader
+pub fn probe_format(
+    media_source_stream: MediaSourceStream,
+    hint: &Hint,
+    enable_gapless: bool,
+) -> Result<Box<dyn FormatReader>, PlaybackError> {
     // Use the default options for format readers other than for gapless playback.
     let format_opts = FormatOptions {
         enable_gapless,
@@ -188,22 +213,7 @@ pub fn play_media_source(
         &format_opts,
         &metadata_opts,
     ) {
-        Ok(probed) => {
-            // If present, parse the seek argument.
-            let seek_time = seek;
-
-            // Set the decoder options.
-            let decode_opts = DecoderOptions { verify };
-
-            // Play it!
-            Ok(decode(
-                probed.format,
-                audio_decode_handler,
-                track_num,
-                seek_time,
-                decode_opts,
-            )?)
-        }
+        Ok(probed) => Ok(probed.format),
         Err(err) => {
             // The input was not supported by any format reader.
             log::info!("the input is not supported: {err:?}");
@@ -212,6 +222,22 @@ pub fn play_media_source(
     }
 }
 
+/// Creates a decoder for the default track of `format`, returning the track's ID with it.
+///
+/// # Errors
+///
+/// * If `format` has no tracks
+/// * If no decoder supports the track's codec
+pub fn make_decoder(
+    format: &dyn FormatReader,
+    decode_opts: &DecoderOptions,
+) -> Result<(u32, Box<dyn Decoder>), PlaybackError> {
+    let track = format.default_track().ok_or(PlaybackError::InvalidSource)?;
+    let decoder = symphonia::default::get_codecs().make(&track.codec_params, decode_opts)?;
+
+    Ok((track.id, decoder))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
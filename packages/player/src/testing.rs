@@ -0,0 +1,825 @@
+//! Test doubles for code built on top of the player.
+//!
+//! [`FakePlayer`] implements [`Player`] without decoding or outputting any audio. It
+//! records every call made to it and lets tests drive its state and inject failures, so
+//! crates that wrap a [`PlaybackHandler`](crate::PlaybackHandler) can test their own logic
+//! without hand-rolling a mock.
+//!
+//! Clones of a [`FakePlayer`] share their state, so keep a clone around to inspect the
+//! calls after handing the player to a [`PlaybackHandler`](crate::PlaybackHandler):
+//!
+//! ```rust
+//! # use moosicbox_player::{PlaybackHandler, testing::{FakePlayer, FakePlayerState, PlayerCall}};
+//! # async fn example() -> Result<(), moosicbox_player::PlayerError> {
+//! let player = FakePlayer::new();
+//! let mut handler = PlaybackHandler::new(player.clone());
+//!
+//! handler.pause(None).await?;
+//!
+//! assert_eq!(player.calls(), vec![PlayerCall::Pause]);
+//! assert_eq!(player.state(), FakePlayerState::Paused);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Plays finish instantly by default. [`FakePlayer::hold_plays`] keeps them playing until
+//! released or cancelled, and [`FakePlayer::wait_until`] waits for the player to reach a
+//! state instead of polling it.
+//!
+//! Requires the `test-utils` feature.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use async_trait::async_trait;
+use moosicbox_music_models::{Track, id::Id};
+use switchy_async::util::CancellationToken;
+
+use crate::{ApiPlaybackStatus, Playback, Player, PlayerError, PlayerSource, StreamInfo};
+
+/// A call made to a [`FakePlayer`], along with its arguments.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlayerCall {
+    /// [`Player::trigger_play`]
+    Play {
+        /// Position playback was started from, in seconds
+        seek: Option<f64>,
+    },
+    /// [`Player::trigger_prepare`]
+    Prepare {
+        /// Position the track was buffered from, in seconds
+        seek: Option<f64>,
+    },
+    /// [`Player::trigger_stop`]
+    Stop,
+    /// [`Player::trigger_seek`]
+    Seek {
+        /// Position seeked to, in seconds
+        seek: f64,
+    },
+    /// [`Player::trigger_pause`]
+    Pause,
+    /// [`Player::trigger_resume`]
+    Resume,
+}
+
+/// Playback state of a [`FakePlayer`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FakePlayerState {
+    /// Nothing is playing
+    #[default]
+    Stopped,
+    /// A track is loaded but output hasn't started
+    Prepared,
+    /// Audio is playing
+    Playing,
+    /// Playback is paused
+    Paused,
+}
+
+#[derive(Debug, Default)]
+struct FakePlayerInner {
+    calls: Vec<PlayerCall>,
+    state: FakePlayerState,
+    failures: VecDeque<PlayerError>,
+    prefetches: Vec<Id>,
+    prefetch_failures: VecDeque<PlayerError>,
+    played: Vec<Id>,
+    plays_in_progress: usize,
+    hold: Option<flume::Receiver<()>>,
+    stream_info: Option<StreamInfo>,
+    active_playback: Option<Playback>,
+    output_volumes: Vec<f64>,
+    underrun_count: u64,
+}
+
+/// Hook run by [`FakePlayer::trigger_play`] on the attached playback, see
+/// [`FakePlayer::on_play`].
+type PlayHook = dyn Fn(&mut Playback) -> Result<(), PlayerError> + Send + Sync;
+
+/// [`Player`] that records its calls instead of playing audio.
+///
+/// Successful calls move the player between [`FakePlayerState`]s like a real player would:
+/// playing and resuming start playing, preparing loads the track, pausing pauses and
+/// stopping stops. Seeking leaves the state as is. Failed calls are recorded but don't
+/// change the state, except for plays failing after they started, which stop the player.
+///
+/// Attach the handler's playback with [`FakePlayer::with_playback`] to have the player
+/// report it as its status and record the tracks it plays, like `LocalPlayer` does.
+///
+/// Prefetches run in the background of playback, so they are recorded separately from the
+/// other calls (see [`FakePlayer::prefetches`]) and fail independently of them.
+#[derive(Clone)]
+pub struct FakePlayer {
+    inner: Arc<Mutex<FakePlayerInner>>,
+    source: PlayerSource,
+    crossfades: bool,
+    playback: Option<Arc<RwLock<Option<Playback>>>>,
+    on_play: Option<Arc<PlayHook>>,
+    updated: (flume::Sender<()>, flume::Receiver<()>),
+}
+
+impl std::fmt::Debug for FakePlayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FakePlayer")
+            .field("inner", &self.inner)
+            .field("source", &self.source)
+            .field("crossfades", &self.crossfades)
+            .field("playback", &self.playback)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for FakePlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakePlayer {
+    /// Creates a stopped local fake player.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(FakePlayerInner::default())),
+            source: PlayerSource::Local,
+            crossfades: false,
+            playback: None,
+            on_play: None,
+            updated: flume::unbounded(),
+        }
+    }
+
+    /// Sets the source returned by [`Player::get_source`].
+    #[must_use]
+    pub fn with_source(mut self, source: PlayerSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Sets whether [`Player::supports_crossfade`] reports crossfading support.
+    #[must_use]
+    pub const fn with_crossfade(mut self, crossfades: bool) -> Self {
+        self.crossfades = crossfades;
+        self
+    }
+
+    /// Attaches the playback of the handler driving this player.
+    ///
+    /// The player then reports the playback as its status, and records the track at the
+    /// playback's position when a play finishes (see [`Self::played`]).
+    #[must_use]
+    pub fn with_playback(mut self, playback: Arc<RwLock<Option<Playback>>>) -> Self {
+        self.playback = Some(playback);
+        self
+    }
+
+    /// Runs `hook` on the attached playback (see [`Self::with_playback`]) when a play is
+    /// released, before it finishes.
+    ///
+    /// The hook can update the playback like a playing track would, e.g. its progress, and
+    /// fail the play by returning an error, e.g. for tracks the player can't play.
+    #[must_use]
+    pub fn on_play(
+        mut self,
+        hook: impl Fn(&mut Playback) -> Result<(), PlayerError> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_play = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets the stream properties returned by [`Player::trigger_prepare`].
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    #[must_use]
+    pub fn with_stream_info(self, stream_info: StreamInfo) -> Self {
+        self.inner.lock().unwrap().stream_info = Some(stream_info);
+        self
+    }
+
+    /// Every call made to the player so far, in order.
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    #[must_use]
+    pub fn calls(&self) -> Vec<PlayerCall> {
+        self.inner.lock().unwrap().calls.clone()
+    }
+
+    /// Forgets the calls recorded so far.
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    pub fn clear_calls(&self) {
+        self.inner.lock().unwrap().calls.clear();
+    }
+
+    /// Positions passed to [`Player::trigger_play`], in order.
+    #[must_use]
+    pub fn plays(&self) -> Vec<Option<f64>> {
+        self.calls()
+            .into_iter()
+            .filter_map(|call| match call {
+                PlayerCall::Play { seek } => Some(seek),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Positions passed to [`Player::trigger_seek`], in order.
+    #[must_use]
+    pub fn seeks(&self) -> Vec<f64> {
+        self.calls()
+            .into_iter()
+            .filter_map(|call| match call {
+                PlayerCall::Seek { seek } => Some(seek),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Ids of the tracks whose plays finished successfully, in order.
+    ///
+    /// Only recorded with an attached playback (see [`Self::with_playback`]).
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    #[must_use]
+    pub fn played(&self) -> Vec<Id> {
+        self.inner.lock().unwrap().played.clone()
+    }
+
+    /// Number of plays that started but haven't finished yet, see [`Self::hold_plays`].
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    #[must_use]
+    pub fn plays_in_progress(&self) -> usize {
+        self.inner.lock().unwrap().plays_in_progress
+    }
+
+    /// Holds every following play until it's released through the returned sender, like a
+    /// track that is still playing.
+    ///
+    /// Each message releases one held play, and dropping the sender releases all of them,
+    /// held now or later. A held play that is cancelled returns [`PlayerError::Cancelled`].
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    #[must_use]
+    pub fn hold_plays(&self) -> flume::Sender<()> {
+        let (release, hold) = flume::unbounded();
+        self.inner.lock().unwrap().hold = Some(hold);
+        release
+    }
+
+    /// Waits until `predicate` holds for this player, checking again after every call.
+    pub async fn wait_until(&self, predicate: impl Fn(&Self) -> bool) {
+        while !predicate(self) {
+            // Both ends live in `self`, so the channel can't disconnect
+            let _ = self.updated.1.recv_async().await;
+        }
+    }
+
+    /// Ids of the tracks passed to [`Player::trigger_prefetch`], in order.
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    #[must_use]
+    pub fn prefetches(&self) -> Vec<Id> {
+        self.inner.lock().unwrap().prefetches.clone()
+    }
+
+    /// Number of recorded calls equal to `call`.
+    #[must_use]
+    pub fn call_count(&self, call: PlayerCall) -> usize {
+        self.calls().into_iter().filter(|x| *x == call).count()
+    }
+
+    /// The current playback state.
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    #[must_use]
+    pub fn state(&self) -> FakePlayerState {
+        self.inner.lock().unwrap().state
+    }
+
+    /// Moves the player to `state`, e.g. to simulate playback stopping on its own.
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    pub fn set_state(&self, state: FakePlayerState) {
+        self.inner.lock().unwrap().state = state;
+    }
+
+    /// Makes the next call fail with `error`.
+    ///
+    /// Queued failures are consumed one per call, in order.
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    pub fn fail_next(&self, error: PlayerError) {
+        self.inner.lock().unwrap().failures.push_back(error);
+    }
+
+    /// Makes the next [`Player::trigger_prefetch`] fail with `error`.
+    ///
+    /// Queued prefetch failures are consumed one per prefetch, in order, and don't affect
+    /// the other calls.
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    pub fn fail_next_prefetch(&self, error: PlayerError) {
+        self.inner
+            .lock()
+            .unwrap()
+            .prefetch_failures
+            .push_back(error);
+    }
+
+    /// Sets the playback reported by [`Player::player_status`] when no playback is attached
+    /// (see [`Self::with_playback`]).
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    pub fn set_active_playback(&self, playback: Option<Playback>) {
+        self.inner.lock().unwrap().active_playback = playback;
+    }
+
+    /// Sets the underrun count reported by [`Player::player_status`].
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    pub fn set_underrun_count(&self, underrun_count: u64) {
+        self.inner.lock().unwrap().underrun_count = underrun_count;
+    }
+
+    /// The last gain passed to [`Player::apply_output_volume`], if any.
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    #[must_use]
+    pub fn output_volume(&self) -> Option<f64> {
+        self.inner.lock().unwrap().output_volumes.last().copied()
+    }
+
+    /// Every gain passed to [`Player::apply_output_volume`] so far, in order.
+    ///
+    /// # Panics
+    ///
+    /// * If the state `Mutex` is poisoned
+    #[must_use]
+    pub fn output_volumes(&self) -> Vec<f64> {
+        self.inner.lock().unwrap().output_volumes.clone()
+    }
+
+    /// Records `call` and, unless a failure is queued, moves to `state`.
+    fn record(&self, call: PlayerCall, state: Option<FakePlayerState>) -> Result<(), PlayerError> {
+        let result = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.calls.push(call);
+
+            inner.failures.pop_front().map_or_else(
+                || {
+                    if let Some(state) = state {
+                        inner.state = state;
+                    }
+                    Ok(())
+                },
+                Err,
+            )
+        };
+        self.notify();
+
+        result
+    }
+
+    /// Wakes up [`Self::wait_until`].
+    fn notify(&self) {
+        // Both ends live in `self`, so the channel can't disconnect
+        let _ = self.updated.0.send(());
+    }
+
+    /// Id of the track at the position of the attached playback.
+    fn current_track_id(&self) -> Option<Id> {
+        let binding = self.playback.as_ref()?.read().unwrap();
+        let playback = binding.as_ref()?;
+        playback
+            .tracks
+            .get(playback.position as usize)
+            .map(|x| x.id.clone())
+    }
+
+    /// Waits for a held play to be released, then runs the [`Self::on_play`] hook.
+    async fn finish_play(
+        &self,
+        hold: Option<flume::Receiver<()>>,
+        cancel: CancellationToken,
+    ) -> Result<(), PlayerError> {
+        if let Some(hold) = hold {
+            #[allow(clippy::redundant_pub_crate)]
+            let released = switchy_async::select! {
+                () = cancel.cancelled() => false,
+                // A dropped sender releases every play
+                _ = hold.recv_async() => true,
+            };
+            if !released {
+                return Err(PlayerError::Cancelled);
+            }
+        }
+
+        if let (Some(hook), Some(playback)) = (&self.on_play, &self.playback)
+            && let Some(playback) = playback.write().unwrap().as_mut()
+        {
+            hook(playback)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Player for FakePlayer {
+    async fn trigger_play(
+        &self,
+        seek: Option<f64>,
+        cancel: CancellationToken,
+    ) -> Result<(), PlayerError> {
+        if cancel.is_cancelled() {
+            return Err(PlayerError::Cancelled);
+        }
+        self.record(PlayerCall::Play { seek }, Some(FakePlayerState::Playing))?;
+
+        let track_id = self.current_track_id();
+        let hold = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.plays_in_progress += 1;
+            inner.hold.clone()
+        };
+
+        let result = self.finish_play(hold, cancel).await;
+
+        {
+            let mut inner = self.inner.lock().unwrap();
+            inner.plays_in_progress -= 1;
+            match &result {
+                Ok(()) => inner.played.extend(track_id),
+                Err(_) => inner.state = FakePlayerState::Stopped,
+            }
+        }
+        self.notify();
+
+        result
+    }
+
+    async fn trigger_stop(&self) -> Result<(), PlayerError> {
+        self.record(PlayerCall::Stop, Some(FakePlayerState::Stopped))
+    }
+
+    async fn trigger_seek(&self, seek: f64, cancel: CancellationToken) -> Result<(), PlayerError> {
+        if cancel.is_cancelled() {
+            return Err(PlayerError::Cancelled);
+        }
+        self.record(PlayerCall::Seek { seek }, None)
+    }
+
+    fn supports_crossfade(&self) -> bool {
+        self.crossfades
+    }
+
+    fn apply_output_volume(&self, volume: f64) {
+        self.inner.lock().unwrap().output_volumes.push(volume);
+    }
+
+    async fn trigger_prefetch(
+        &self,
+        track: &Track,
+        cancel: CancellationToken,
+    ) -> Result<(), PlayerError> {
+        if cancel.is_cancelled() {
+            return Err(PlayerError::Cancelled);
+        }
+
+        let result = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.prefetches.push(track.id.clone());
+            inner.prefetch_failures.pop_front().map_or(Ok(()), Err)
+        };
+        self.notify();
+
+        result
+    }
+
+    async fn trigger_prepare(&self, seek: Option<f64>) -> Result<Option<StreamInfo>, PlayerError> {
+        self.record(
+            PlayerCall::Prepare { seek },
+            Some(FakePlayerState::Prepared),
+        )?;
+        Ok(self.inner.lock().unwrap().stream_info.clone())
+    }
+
+    async fn trigger_pause(&self) -> Result<(), PlayerError> {
+        self.record(PlayerCall::Pause, Some(FakePlayerState::Paused))
+    }
+
+    async fn trigger_resume(&self) -> Result<(), PlayerError> {
+        self.record(PlayerCall::Resume, Some(FakePlayerState::Playing))
+    }
+
+    fn player_status(&self) -> Result<ApiPlaybackStatus, PlayerError> {
+        let inner = self.inner.lock().unwrap();
+        let active_playback = match &self.playback {
+            Some(playback) => playback.read().unwrap().clone(),
+            None => inner.active_playback.clone(),
+        };
+
+        Ok(ApiPlaybackStatus {
+            active_playbacks: active_playback.map(Into::into),
+            underrun_count: inner.underrun_count,
+        })
+    }
+
+    fn get_source(&self) -> &PlayerSource {
+        &self.source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test(switchy_async::test)]
+    async fn records_calls_and_arguments_in_order() {
+        let player = FakePlayer::new();
+
+        player
+            .trigger_play(Some(12.5), CancellationToken::new())
+            .await
+            .unwrap();
+        player
+            .trigger_seek(30.0, CancellationToken::new())
+            .await
+            .unwrap();
+        player.trigger_pause().await.unwrap();
+        player.trigger_resume().await.unwrap();
+        player.trigger_stop().await.unwrap();
+
+        assert_eq!(
+            player.calls(),
+            vec![
+                PlayerCall::Play { seek: Some(12.5) },
+                PlayerCall::Seek { seek: 30.0 },
+                PlayerCall::Pause,
+                PlayerCall::Resume,
+                PlayerCall::Stop,
+            ]
+        );
+        assert_eq!(player.plays(), vec![Some(12.5)]);
+        assert_eq!(player.seeks(), vec![30.0]);
+        assert_eq!(player.call_count(PlayerCall::Stop), 1);
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn calls_drive_state_transitions() {
+        let player = FakePlayer::new();
+        assert_eq!(player.state(), FakePlayerState::Stopped);
+
+        player.trigger_prepare(None).await.unwrap();
+        assert_eq!(player.state(), FakePlayerState::Prepared);
+
+        player.trigger_resume().await.unwrap();
+        assert_eq!(player.state(), FakePlayerState::Playing);
+
+        player
+            .trigger_seek(5.0, CancellationToken::new())
+            .await
+            .unwrap();
+        assert_eq!(player.state(), FakePlayerState::Playing);
+
+        player.trigger_pause().await.unwrap();
+        assert_eq!(player.state(), FakePlayerState::Paused);
+
+        player.set_state(FakePlayerState::Stopped);
+        assert_eq!(player.state(), FakePlayerState::Stopped);
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn queued_failures_are_consumed_in_order() {
+        let player = FakePlayer::new();
+        player.fail_next(PlayerError::NoAudioOutputs);
+        player.fail_next(PlayerError::InvalidState);
+
+        assert!(matches!(
+            player.trigger_play(None, CancellationToken::new()).await,
+            Err(PlayerError::NoAudioOutputs)
+        ));
+        assert!(matches!(
+            player.trigger_pause().await,
+            Err(PlayerError::InvalidState)
+        ));
+        player.trigger_pause().await.unwrap();
+
+        assert_eq!(
+            player.calls(),
+            vec![
+                PlayerCall::Play { seek: None },
+                PlayerCall::Pause,
+                PlayerCall::Pause
+            ]
+        );
+        assert_eq!(player.state(), FakePlayerState::Paused);
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn prefetches_are_recorded_and_fail_separately() {
+        let player = FakePlayer::new();
+        player.fail_next(PlayerError::NoAudioOutputs);
+        player.fail_next_prefetch(PlayerError::RetryRequested);
+
+        let track = Track {
+            id: 7.into(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            player
+                .trigger_prefetch(&track, CancellationToken::new())
+                .await,
+            Err(PlayerError::RetryRequested)
+        ));
+        player
+            .trigger_prefetch(&track, CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert_eq!(player.prefetches(), vec![7.into(), 7.into()]);
+        assert!(player.calls().is_empty());
+        assert!(matches!(
+            player.trigger_stop().await,
+            Err(PlayerError::NoAudioOutputs)
+        ));
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn cancelled_play_is_not_recorded() {
+        let player = FakePlayer::new();
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        assert!(matches!(
+            player.trigger_play(None, cancel).await,
+            Err(PlayerError::Cancelled)
+        ));
+        assert!(player.calls().is_empty());
+        assert_eq!(player.state(), FakePlayerState::Stopped);
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn clones_share_recorded_calls() {
+        let player = FakePlayer::new();
+        let clone = player.clone();
+
+        clone.trigger_stop().await.unwrap();
+        clone.apply_output_volume(0.5);
+
+        assert_eq!(player.calls(), vec![PlayerCall::Stop]);
+        assert_eq!(player.output_volume(), Some(0.5));
+
+        player.clear_calls();
+        assert!(clone.calls().is_empty());
+    }
+
+    fn attached_playback(track_ids: &[u64]) -> Arc<RwLock<Option<Playback>>> {
+        Arc::new(RwLock::new(Some(Playback::new(
+            track_ids
+                .iter()
+                .map(|id| Track {
+                    id: (*id).into(),
+                    ..Default::default()
+                })
+                .collect(),
+            Some(0),
+            atomic_float::AtomicF64::new(1.0),
+            moosicbox_music_models::PlaybackQuality::default(),
+            1,
+            "default".to_string(),
+            None,
+        ))))
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn held_plays_finish_once_released() {
+        let playback = attached_playback(&[1, 2]);
+        let player = FakePlayer::new().with_playback(playback.clone());
+        let release = player.hold_plays();
+
+        let play = switchy_async::runtime::Handle::current().spawn_with_name("test: play", {
+            let player = player.clone();
+            async move { player.trigger_play(None, CancellationToken::new()).await }
+        });
+
+        player.wait_until(|x| x.plays_in_progress() == 1).await;
+        assert_eq!(player.state(), FakePlayerState::Playing);
+        assert!(player.played().is_empty());
+
+        release.send(()).unwrap();
+        play.await.unwrap().unwrap();
+        assert_eq!(player.played(), vec![1.into()]);
+
+        // Dropping the sender releases every following play
+        drop(release);
+        playback.write().unwrap().as_mut().unwrap().position = 1;
+        player
+            .trigger_play(None, CancellationToken::new())
+            .await
+            .unwrap();
+        assert_eq!(player.played(), vec![1.into(), 2.into()]);
+        assert_eq!(player.plays_in_progress(), 0);
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn cancelled_held_play_stops() {
+        let player = FakePlayer::new().with_playback(attached_playback(&[1]));
+        let _release = player.hold_plays();
+        let cancel = CancellationToken::new();
+
+        let play = switchy_async::runtime::Handle::current().spawn_with_name("test: play", {
+            let player = player.clone();
+            let cancel = cancel.clone();
+            async move { player.trigger_play(None, cancel).await }
+        });
+
+        player.wait_until(|x| x.plays_in_progress() == 1).await;
+        cancel.cancel();
+
+        assert!(matches!(play.await.unwrap(), Err(PlayerError::Cancelled)));
+        assert!(player.played().is_empty());
+        assert_eq!(player.state(), FakePlayerState::Stopped);
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn on_play_hook_updates_attached_playback() {
+        let playback = attached_playback(&[1]);
+        let player = FakePlayer::new()
+            .with_playback(playback.clone())
+            .on_play(|playback| {
+                playback.progress = 42.0;
+                Err(PlayerError::NoAudioOutputs)
+            });
+
+        assert!(matches!(
+            player.trigger_play(None, CancellationToken::new()).await,
+            Err(PlayerError::NoAudioOutputs)
+        ));
+
+        assert!((playback.read().unwrap().as_ref().unwrap().progress - 42.0).abs() < f64::EPSILON);
+        assert!(player.played().is_empty());
+        assert_eq!(player.state(), FakePlayerState::Stopped);
+        assert!(
+            player
+                .player_status()
+                .unwrap()
+                .active_playbacks
+                .is_some_and(|x| (x.seek - 42.0).abs() < f64::EPSILON)
+        );
+    }
+
+    #[test_log::test]
+    fn player_status_reports_configured_playback() {
+        let player = FakePlayer::new();
+        player.set_underrun_count(3);
+
+        let status = player.player_status().unwrap();
+        assert!(status.active_playbacks.is_none());
+        assert_eq!(status.underrun_count, 3);
+
+        player.set_active_playback(Some(Playback::new(
+            vec![],
+            None,
+            atomic_float::AtomicF64::new(1.0),
+            moosicbox_music_models::PlaybackQuality::default(),
+            1,
+            "default".to_string(),
+            None,
+        )));
+
+        assert!(player.player_status().unwrap().active_playbacks.is_some());
+    }
+}
@@ -0,0 +1,715 @@
+//! Disk cache of recently played remote tracks.
+//!
+//! Streaming a remote track downloads it again on every replay, and seeking backward
+//! issues new range requests. A [`TrackCache`] stores the bytes of fetched tracks in a
+//! directory so later plays read them from disk instead. The cache is bounded by a maximum
+//! total size and evicts the least recently used tracks first.
+//!
+//! Downloads are written to a `.partial` file as they are read and only become visible in
+//! the cache once every byte of the track has been written, so an interrupted or partial
+//! download is never served as a complete track. Every download gets its own partial file,
+//! so concurrent downloads of the same track don't overwrite each other.
+//!
+//! Enable it for a handler with
+//! [`with_track_cache`](crate::PlaybackHandler::with_track_cache).
+
+use std::{
+    collections::BTreeMap,
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use moosicbox_music_api::models::TrackAudioQuality;
+use moosicbox_music_models::{ApiSource, PlaybackQuality, id::Id};
+use symphonia::core::io::MediaSource;
+
+/// Extension of files that are still being downloaded
+const PARTIAL_EXTENSION: &str = "partial";
+
+/// Distinguishes the partial files of concurrent downloads
+static NEXT_FILL_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Settings of a [`TrackCache`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackCacheConfig {
+    /// Directory the cached tracks are stored in
+    pub directory: PathBuf,
+    /// Maximum total size of the cached tracks in bytes
+    pub max_size: u64,
+}
+
+/// A cached track opened for reading.
+pub struct CachedTrack {
+    /// Contents of the track
+    pub source: Box<dyn MediaSource>,
+    /// File extension describing the track's format, if known (e.g. `flac`)
+    pub extension: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    file_name: String,
+    extension: Option<String>,
+    size: u64,
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct CacheIndex {
+    entries: BTreeMap<String, CacheEntry>,
+    total_size: u64,
+    clock: u64,
+}
+
+impl CacheIndex {
+    const fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn remove(&mut self, directory: &Path, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.total_size -= entry.size;
+
+            if let Err(e) = std::fs::remove_file(directory.join(&entry.file_name)) {
+                log::warn!("Failed to remove cached track {}: {e}", entry.file_name);
+            }
+        }
+    }
+
+    /// Evicts the least recently used entries until the total size fits in `max_size`.
+    fn evict(&mut self, directory: &Path, max_size: u64) {
+        while self.total_size > max_size {
+            let Some(key) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+
+            log::debug!("Evicting cached track {key}");
+            self.remove(directory, &key);
+        }
+    }
+}
+
+/// A size-bounded LRU cache of remote tracks on disk.
+///
+/// Cheap to clone; clones share the same index.
+#[derive(Debug, Clone)]
+pub struct TrackCache {
+    config: Arc<TrackCacheConfig>,
+    index: Arc<Mutex<CacheIndex>>,
+}
+
+impl TrackCache {
+    /// Opens the cache in the configured directory, creating it if needed.
+    ///
+    /// Tracks already in the directory are indexed, oldest first, and leftover partial
+    /// downloads are removed.
+    ///
+    /// # Errors
+    ///
+    /// * If the directory cannot be created or read
+    pub fn open(config: TrackCacheConfig) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&config.directory)?;
+
+        let mut files = vec![];
+
+        for entry in std::fs::read_dir(&config.directory)? {
+            let entry = entry?;
+            let path = entry.path();
+            let Some(file_name) = path.file_name().and_then(|x| x.to_str()) else {
+                continue;
+            };
+
+            if path.extension().is_some_and(|x| x == PARTIAL_EXTENSION) {
+                log::debug!("Removing incomplete cached track {file_name}");
+                std::fs::remove_file(&path)?;
+                continue;
+            }
+
+            let metadata = entry.metadata()?;
+            if metadata.is_file() {
+                files.push((
+                    metadata.modified().ok(),
+                    file_name.to_string(),
+                    metadata.len(),
+                ));
+            }
+        }
+
+        files.sort();
+
+        let mut index = CacheIndex::default();
+
+        for (_, file_name, size) in files {
+            let (key, extension) = file_name
+                .split_once('.')
+                .map_or((file_name.as_str(), None), |(key, extension)| {
+                    (key, Some(extension.to_string()))
+                });
+            let last_used = index.tick();
+
+            index.total_size += size;
+            index.entries.insert(
+                key.to_string(),
+                CacheEntry {
+                    file_name: file_name.clone(),
+                    extension,
+                    size,
+                    last_used,
+                },
+            );
+        }
+
+        index.evict(&config.directory, config.max_size);
+
+        log::debug!(
+            "Opened track cache at {} with {} tracks ({} bytes)",
+            config.directory.display(),
+            index.entries.len(),
+            index.total_size
+        );
+
+        Ok(Self {
+            config: Arc::new(config),
+            index: Arc::new(Mutex::new(index)),
+        })
+    }
+
+    /// The cache's settings.
+    #[must_use]
+    pub fn config(&self) -> &TrackCacheConfig {
+        &self.config
+    }
+
+    /// Cache key of a track fetched from `source` in the given format and quality.
+    #[must_use]
+    pub fn key(
+        track_id: &Id,
+        source: &ApiSource,
+        format: PlaybackQuality,
+        quality: TrackAudioQuality,
+    ) -> String {
+        format!("{source}_{track_id}_{}_{}", format.format, quality.as_ref())
+            .chars()
+            .map(|x| {
+                if x.is_ascii_alphanumeric() || x == '-' || x == '_' {
+                    x
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+
+    /// Whether a complete copy of the track is cached.
+    ///
+    /// # Panics
+    ///
+    /// * If the `index` `Mutex` is poisoned
+    #[must_use]
+    pub fn contains(&self, key: &str) -> bool {
+        self.index.lock().unwrap().entries.contains_key(key)
+    }
+
+    /// Total size of the cached tracks in bytes.
+    ///
+    /// # Panics
+    ///
+    /// * If the `index` `Mutex` is poisoned
+    #[must_use]
+    pub fn size(&self) -> u64 {
+        self.index.lock().unwrap().total_size
+    }
+
+    /// Opens the cached track, marking it as the most recently used.
+    ///
+    /// # Panics
+    ///
+    /// * If the `index` `Mutex` is poisoned
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<CachedTrack> {
+        let mut index = self.index.lock().unwrap();
+        let last_used = index.tick();
+        let entry = index.entries.get_mut(key)?;
+        entry.last_used = last_used;
+        let path = self.config.directory.join(&entry.file_name);
+        let extension = entry.extension.clone();
+
+        match File::open(path) {
+            Ok(file) => Some(CachedTrack {
+                source: Box::new(file),
+                extension,
+            }),
+            Err(e) => {
+                log::warn!("Failed to open cached track {key}: {e}");
+                index.remove(&self.config.directory, key);
+                None
+            }
+        }
+    }
+
+    /// Wraps `source` so the bytes read from it are stored in the cache under `key`.
+    ///
+    /// The track is added to the cache when the wrapper is dropped, but only if every byte
+    /// was read: `size` bytes, or up to the end of the stream if the size is unknown.
+    /// Returns `source` unchanged if the partial file cannot be created.
+    pub fn fill(
+        &self,
+        key: &str,
+        source: Box<dyn MediaSource>,
+        size: Option<u64>,
+        extension: Option<String>,
+    ) -> Box<dyn MediaSource> {
+        if size.is_some_and(|x| x > self.config.max_size) {
+            log::debug!("Not caching track {key}: larger than the cache");
+            return source;
+        }
+
+        let partial_path = self.config.directory.join(format!(
+            "{key}.{}-{}.{PARTIAL_EXTENSION}",
+            std::process::id(),
+            NEXT_FILL_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let partial = match OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&partial_path)
+        {
+            Ok(file) => file,
+            Err(e) => {
+                log::warn!("Failed to create partial cached track {key}: {e}");
+                return source;
+            }
+        };
+
+        Box::new(CacheFillingSource {
+            inner: source,
+            inner_position: 0,
+            position: 0,
+            size,
+            partial: Some(partial),
+            partial_path,
+            written: 0,
+            reached_end: false,
+            cache: self.clone(),
+            key: key.to_string(),
+            extension,
+        })
+    }
+
+    /// Moves a fully downloaded track into the cache.
+    fn commit(&self, key: &str, partial_path: &Path, extension: Option<String>, size: u64) {
+        let file_name = extension
+            .as_ref()
+            .map_or_else(|| key.to_string(), |x| format!("{key}.{x}"));
+
+        let mut index = self.index.lock().unwrap();
+        index.remove(&self.config.directory, key);
+
+        if size > self.config.max_size {
+            log::debug!("Not caching track {key}: larger than the cache");
+            let _ = std::fs::remove_file(partial_path);
+            return;
+        }
+
+        if let Err(e) = std::fs::rename(partial_path, self.config.directory.join(&file_name)) {
+            log::warn!("Failed to store cached track {key}: {e}");
+            let _ = std::fs::remove_file(partial_path);
+            return;
+        }
+
+        let last_used = index.tick();
+        index.total_size += size;
+        index.entries.insert(
+            key.to_string(),
+            CacheEntry {
+                file_name,
+                extension,
+                size,
+                last_used,
+            },
+        );
+        index.evict(&self.config.directory, self.config.max_size);
+
+        log::debug!("Cached track {key} ({size} bytes)");
+    }
+}
+
+/// Reads a remote track while writing the bytes to a partial cache file.
+///
+/// Bytes that have already been written are read back from the partial file, so seeking
+/// backward doesn't hit the network. Reads past the written range come from the inner
+/// source, and only extend the partial file when they continue it without a gap.
+struct CacheFillingSource {
+    inner: Box<dyn MediaSource>,
+    inner_position: u64,
+    position: u64,
+    size: Option<u64>,
+    partial: Option<File>,
+    partial_path: PathBuf,
+    written: u64,
+    reached_end: bool,
+    cache: TrackCache,
+    key: String,
+    extension: Option<String>,
+}
+
+impl CacheFillingSource {
+    fn abandon(&mut self) {
+        if self.partial.take().is_some() {
+            let _ = std::fs::remove_file(&self.partial_path);
+        }
+    }
+
+    fn read_written(&mut self, buf: &mut [u8]) -> std::io::Result<Option<usize>> {
+        let Some(partial) = self.partial.as_mut() else {
+            return Ok(None);
+        };
+
+        let available = usize::try_from(self.written - self.position).unwrap_or(usize::MAX);
+        let len = buf.len().min(available);
+
+        partial.seek(SeekFrom::Start(self.position))?;
+        let read = partial.read(&mut buf[..len])?;
+        self.position += read as u64;
+
+        Ok(Some(read))
+    }
+}
+
+impl Read for CacheFillingSource {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.position < self.written
+            && let Some(read) = self.read_written(buf)?
+        {
+            return Ok(read);
+        }
+
+        if self.inner_position != self.position {
+            self.inner_position = self.inner.seek(SeekFrom::Start(self.position))?;
+        }
+
+        let read = self.inner.read(buf)?;
+
+        if read == 0 {
+            if self.position == self.written {
+                self.reached_end = true;
+            }
+            return Ok(0);
+        }
+
+        if self.position == self.written
+            && let Some(partial) = self.partial.as_mut()
+        {
+            let result = partial
+                .seek(SeekFrom::Start(self.written))
+                .and_then(|_| partial.write_all(&buf[..read]));
+
+            if let Err(e) = result {
+                log::warn!("Failed to write partial cached track {}: {e}", self.key);
+                self.abandon();
+            } else {
+                self.written += read as u64;
+            }
+        }
+
+        self.position += read as u64;
+        self.inner_position += read as u64;
+
+        Ok(read)
+    }
+}
+
+impl Seek for CacheFillingSource {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let position = match (pos, self.size) {
+            (SeekFrom::Start(x), _) => Some(x),
+            (SeekFrom::End(x), Some(size)) => size.checked_add_signed(x),
+            (SeekFrom::Current(x), _) => self.position.checked_add_signed(x),
+            (SeekFrom::End(_), None) => {
+                self.inner_position = self.inner.seek(pos)?;
+                Some(self.inner_position)
+            }
+        };
+
+        self.position = position.ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        Ok(self.position)
+    }
+}
+
+impl MediaSource for CacheFillingSource {
+    fn is_seekable(&self) -> bool {
+        self.inner.is_seekable()
+    }
+
+    fn byte_len(&self) -> Option<u64> {
+        self.size.or_else(|| self.inner.byte_len())
+    }
+}
+
+impl Drop for CacheFillingSource {
+    fn drop(&mut self) {
+        if self.partial.take().is_none() {
+            return;
+        }
+
+        let complete = self
+            .size
+            .map_or(self.reached_end, |size| self.written == size);
+
+        if complete {
+            self.cache.commit(
+                &self.key,
+                &self.partial_path,
+                self.extension.take(),
+                self.written,
+            );
+        } else {
+            log::debug!(
+                "Discarding incomplete cached track {} ({} bytes)",
+                self.key,
+                self.written
+            );
+            let _ = std::fs::remove_file(&self.partial_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /// Opens a cache in a new empty directory, which is removed when the guard is dropped
+    fn open_cache(max_size: u64) -> (TempDir, TrackCache) {
+        let directory = TempDir::new().unwrap();
+
+        let cache = TrackCache::open(TrackCacheConfig {
+            directory: directory.path().to_path_buf(),
+            max_size,
+        })
+        .unwrap();
+
+        (directory, cache)
+    }
+
+    fn track_bytes(len: usize, seed: u8) -> Vec<u8> {
+        (0..len)
+            .map(|i| u8::try_from(i % 251).unwrap().wrapping_add(seed))
+            .collect()
+    }
+
+    /// Reads `bytes` through the cache to the end, as a first play would
+    fn fill(cache: &TrackCache, key: &str, bytes: &[u8]) {
+        let mut source = cache.fill(
+            key,
+            Box::new(Cursor::new(bytes.to_vec())),
+            Some(bytes.len() as u64),
+            Some("flac".to_string()),
+        );
+        let mut read = vec![];
+        source.read_to_end(&mut read).unwrap();
+        assert_eq!(read, bytes);
+    }
+
+    fn read_cached(cache: &TrackCache, key: &str) -> Option<Vec<u8>> {
+        cache.get(key).map(|mut track| {
+            let mut bytes = vec![];
+            track.source.read_to_end(&mut bytes).unwrap();
+            bytes
+        })
+    }
+
+    #[test_log::test]
+    fn test_track_cache_hits_on_replay() {
+        let (_directory, cache) = open_cache(1_000_000);
+        let bytes = track_bytes(10_000, 0);
+
+        assert!(cache.get("track_1").is_none());
+
+        fill(&cache, "track_1", &bytes);
+
+        let track = cache.get("track_1").expect("track should be cached");
+        assert_eq!(track.extension.as_deref(), Some("flac"));
+        assert_eq!(read_cached(&cache, "track_1"), Some(bytes));
+        assert_eq!(cache.size(), 10_000);
+    }
+
+    #[test_log::test]
+    fn test_track_cache_serves_backward_seeks_while_filling() {
+        let (_directory, cache) = open_cache(1_000_000);
+        let bytes = track_bytes(10_000, 3);
+
+        let mut source = cache.fill(
+            "track_1",
+            Box::new(Cursor::new(bytes.clone())),
+            Some(bytes.len() as u64),
+            None,
+        );
+
+        let mut start = vec![0; 6_000];
+        source.read_exact(&mut start).unwrap();
+        source.seek(SeekFrom::Start(1_000)).unwrap();
+        let mut reread = vec![0; 2_000];
+        source.read_exact(&mut reread).unwrap();
+        assert_eq!(reread, bytes[1_000..3_000]);
+
+        source.seek(SeekFrom::Start(6_000)).unwrap();
+        let mut rest = vec![];
+        source.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, bytes[6_000..]);
+        drop(source);
+
+        assert_eq!(read_cached(&cache, "track_1"), Some(bytes));
+    }
+
+    #[test_log::test]
+    fn test_track_cache_does_not_serve_incomplete_downloads() {
+        let (_directory, cache) = open_cache(1_000_000);
+        let bytes = track_bytes(10_000, 5);
+
+        let mut source = cache.fill(
+            "track_1",
+            Box::new(Cursor::new(bytes.clone())),
+            Some(bytes.len() as u64),
+            None,
+        );
+        let mut start = vec![0; 4_000];
+        source.read_exact(&mut start).unwrap();
+        drop(source);
+
+        assert!(!cache.contains("track_1"));
+        assert!(cache.get("track_1").is_none());
+        assert_eq!(cache.size(), 0);
+        assert_eq!(
+            std::fs::read_dir(&cache.config().directory)
+                .unwrap()
+                .count(),
+            0
+        );
+
+        // Skipping ahead leaves a gap, so the download can't complete either
+        let mut source = cache.fill(
+            "track_1",
+            Box::new(Cursor::new(bytes.clone())),
+            Some(bytes.len() as u64),
+            None,
+        );
+        source.seek(SeekFrom::Start(5_000)).unwrap();
+        let mut rest = vec![];
+        source.read_to_end(&mut rest).unwrap();
+        drop(source);
+
+        assert!(!cache.contains("track_1"));
+    }
+
+    #[test_log::test]
+    fn test_track_cache_evicts_least_recently_used_past_size_limit() {
+        let (_directory, cache) = open_cache(2_500);
+
+        fill(&cache, "track_1", &track_bytes(1_000, 1));
+        fill(&cache, "track_2", &track_bytes(1_000, 2));
+
+        // Replaying track 1 makes track 2 the least recently used
+        assert!(read_cached(&cache, "track_1").is_some());
+
+        fill(&cache, "track_3", &track_bytes(1_000, 3));
+
+        assert!(cache.contains("track_1"));
+        assert!(!cache.contains("track_2"));
+        assert!(cache.contains("track_3"));
+        assert_eq!(cache.size(), 2_000);
+        assert_eq!(
+            std::fs::read_dir(&cache.config().directory)
+                .unwrap()
+                .count(),
+            2
+        );
+    }
+
+    #[test_log::test]
+    fn test_track_cache_does_not_store_tracks_larger_than_the_cache() {
+        let (_directory, cache) = open_cache(500);
+
+        fill(&cache, "track_1", &track_bytes(1_000, 1));
+
+        assert!(!cache.contains("track_1"));
+        assert_eq!(cache.size(), 0);
+    }
+
+    #[test_log::test]
+    fn test_track_cache_concurrent_fills_of_the_same_track_do_not_clobber_each_other() {
+        let (_directory, cache) = open_cache(1_000_000);
+        let bytes = track_bytes(10_000, 9);
+        let open = || {
+            cache.fill(
+                "track_1",
+                Box::new(Cursor::new(bytes.clone())),
+                Some(bytes.len() as u64),
+                None,
+            )
+        };
+
+        let mut first = open();
+        let mut start = vec![0; 4_000];
+        first.read_exact(&mut start).unwrap();
+
+        // A second play of the same track completes while the first is still downloading
+        let mut second = open();
+        let mut read = vec![];
+        second.read_to_end(&mut read).unwrap();
+        drop(second);
+        assert_eq!(read_cached(&cache, "track_1").as_ref(), Some(&bytes));
+
+        // Reading back the first download's own bytes still returns them unchanged
+        first.seek(SeekFrom::Start(0)).unwrap();
+        let mut read = vec![];
+        first.read_to_end(&mut read).unwrap();
+        drop(first);
+        assert_eq!(read, bytes);
+
+        assert_eq!(read_cached(&cache, "track_1"), Some(bytes));
+        assert_eq!(cache.size(), 10_000);
+        assert_eq!(
+            std::fs::read_dir(&cache.config().directory)
+                .unwrap()
+                .count(),
+            1
+        );
+    }
+
+    #[test_log::test]
+    fn test_track_cache_reindexes_existing_tracks_on_open() {
+        let (_directory, cache) = open_cache(1_000_000);
+        let bytes = track_bytes(1_000, 7);
+        fill(&cache, "track_1", &bytes);
+        std::fs::write(cache.config().directory.join("track_2.partial"), b"partial").unwrap();
+
+        let reopened = TrackCache::open(cache.config().clone()).unwrap();
+
+        assert_eq!(read_cached(&reopened, "track_1"), Some(bytes));
+        assert!(!reopened.contains("track_2"));
+        assert!(!cache.config().directory.join("track_2.partial").exists());
+    }
+}
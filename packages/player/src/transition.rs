@@ -0,0 +1,81 @@
+//! How playback moves from one track to the next.
+//!
+//! Consecutive tracks decoded to the same sample rate and channel count play back to back,
+//! or overlap when the player crossfades them. When the formats differ the tracks can't be
+//! mixed and the output is reopened for the next track, which leaves an audible cut. [`TransitionKind::between`] decides which of
+//! these a pair of tracks gets, for UIs to show ahead of the transition (see
+//! [`PlaybackHandler::next_transition`](crate::PlaybackHandler::next_transition)).
+
+use moosicbox_music_models::Track;
+
+use crate::StreamInfo;
+
+/// The format a track is decoded to, as far as transitions are concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrackFormat {
+    /// Sample rate in Hz
+    pub sample_rate: u32,
+    /// Number of audio channels
+    pub channels: u8,
+}
+
+impl TrackFormat {
+    /// The format of `track` from its metadata, or `None` if it isn't known.
+    #[must_use]
+    pub fn from_track(track: &Track) -> Option<Self> {
+        Some(Self {
+            sample_rate: track.sample_rate?,
+            channels: track.channels?,
+        })
+    }
+
+    /// The format of an opened track, or `None` if it isn't known.
+    #[must_use]
+    pub fn from_stream_info(stream_info: &StreamInfo) -> Option<Self> {
+        Some(Self {
+            sample_rate: stream_info.sample_rate?,
+            channels: stream_info.channels?,
+        })
+    }
+}
+
+/// How playback will move from the current track to the next one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionKind {
+    /// The next track starts right after the current one, without a gap
+    Gapless,
+    /// The next track fades in over the end of the current one
+    Crossfade,
+    /// The output is reopened for the next track since the formats differ, leaving a gap
+    Cut,
+    /// Playback stops after the current track
+    End,
+    /// The format of the current or the next track isn't known yet
+    Unknown,
+}
+
+impl TransitionKind {
+    /// The transition from a track of format `current` to one of format `next`.
+    ///
+    /// Tracks of the same format are crossfaded if the player will `crossfade` them and
+    /// played gaplessly otherwise. Tracks of different formats are always cut, as they can't
+    /// share the output.
+    #[must_use]
+    pub fn between(
+        current: Option<TrackFormat>,
+        next: Option<TrackFormat>,
+        crossfade: bool,
+    ) -> Self {
+        let (Some(current), Some(next)) = (current, next) else {
+            return Self::Unknown;
+        };
+
+        if current != next {
+            Self::Cut
+        } else if crossfade {
+            Self::Crossfade
+        } else {
+            Self::Gapless
+        }
+    }
+}
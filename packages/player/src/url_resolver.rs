@@ -0,0 +1,48 @@
+//! Hook for rewriting the URL of a remote track right before it is fetched.
+//!
+//! A [`UrlResolver`] set with
+//! [`set_url_resolver`](crate::PlaybackHandler::set_url_resolver) is called every time the
+//! player fetches a remote track, after the track's URL has been resolved and before the
+//! first request is sent. A retried play resolves the URL again, so the resolver runs once
+//! per attempt. This is where to sign URLs, pick a CDN or refresh short-lived tokens.
+
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use moosicbox_music_models::id::Id;
+
+/// URL a remote track is about to be fetched from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedUrl {
+    /// Track being fetched
+    pub track_id: Id,
+    /// URL the track's bytes are fetched from
+    pub url: String,
+}
+
+type ResolveFn = dyn Fn(ResolvedUrl) -> BoxFuture<'static, ResolvedUrl> + Send + Sync;
+
+/// Async hook rewriting a [`ResolvedUrl`] before the track is fetched from it.
+#[derive(Clone)]
+pub struct UrlResolver(Arc<ResolveFn>);
+
+impl std::fmt::Debug for UrlResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("UrlResolver").finish_non_exhaustive()
+    }
+}
+
+impl UrlResolver {
+    /// Creates a resolver from an async function returning the URL to fetch instead.
+    #[must_use]
+    pub fn new(
+        resolve: impl Fn(ResolvedUrl) -> BoxFuture<'static, ResolvedUrl> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(resolve))
+    }
+
+    /// Rewrites `url`.
+    pub async fn resolve(&self, url: ResolvedUrl) -> ResolvedUrl {
+        (self.0)(url).await
+    }
+}
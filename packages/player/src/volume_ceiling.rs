@@ -0,0 +1,266 @@
+//! Maximum output volume and scheduled quiet hours.
+//!
+//! A [`VolumeCeiling`] caps the gain applied to the audio output without changing the
+//! volume requested by the user, so lifting the ceiling restores the requested volume.
+//! During [`QuietHours`] the ceiling tightens automatically. Attach it to a
+//! [`PlaybackHandler`](crate::PlaybackHandler) with
+//! [`with_volume_ceiling`](crate::PlaybackHandler::with_volume_ceiling) and
+//! [`with_quiet_hours`](crate::PlaybackHandler::with_quiet_hours). Quiet hours follow the
+//! time of day of the handler's [`Clock`](crate::clock::Clock).
+
+use chrono::Timelike;
+
+use crate::PlaybackHandler;
+
+/// Number of minutes in a day.
+pub const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// A daily time range during which the output volume is capped.
+///
+/// Times are minutes since local midnight. The range includes `start` and excludes `end`,
+/// and wraps around midnight when `end` is before `start` (e.g. 22:00 to 07:00).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuietHours {
+    /// Minute of the day the quiet hours start at
+    pub start: u16,
+    /// Minute of the day the quiet hours end at
+    pub end: u16,
+    /// Maximum output volume during the quiet hours (0.0 to 1.0)
+    pub ceiling: f64,
+}
+
+impl QuietHours {
+    /// Creates quiet hours from `start` to `end`, given as `(hour, minute)` pairs.
+    #[must_use]
+    pub fn new(start: (u8, u8), end: (u8, u8), ceiling: f64) -> Self {
+        let minute_of_day =
+            |(hour, minute): (u8, u8)| (u16::from(hour) * 60 + u16::from(minute)) % MINUTES_PER_DAY;
+
+        Self {
+            start: minute_of_day(start),
+            end: minute_of_day(end),
+            ceiling,
+        }
+    }
+
+    /// Whether `minute_of_day` falls within the quiet hours.
+    #[must_use]
+    pub const fn contains(&self, minute_of_day: u16) -> bool {
+        if self.start <= self.end {
+            minute_of_day >= self.start && minute_of_day < self.end
+        } else {
+            minute_of_day >= self.start || minute_of_day < self.end
+        }
+    }
+}
+
+/// Limits applied to the output volume of a playback.
+///
+/// Ceilings outside of 0.0 to 1.0 are clamped to that range, and `NaN` ceilings are
+/// ignored.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct VolumeCeiling {
+    /// Maximum output volume at any time (0.0 to 1.0)
+    pub max: Option<f64>,
+    /// Schedule during which a tighter ceiling applies
+    pub quiet_hours: Option<QuietHours>,
+}
+
+impl VolumeCeiling {
+    /// The effective ceiling at `minute_of_day`, if any.
+    #[must_use]
+    pub fn ceiling_at(&self, minute_of_day: u16) -> Option<f64> {
+        let quiet = self
+            .quiet_hours
+            .filter(|x| x.contains(minute_of_day))
+            .and_then(|x| valid_ceiling(x.ceiling));
+
+        match (self.max.and_then(valid_ceiling), quiet) {
+            (Some(max), Some(quiet)) => Some(max.min(quiet)),
+            (max, quiet) => max.or(quiet),
+        }
+    }
+
+    /// Clamps `volume` to the effective ceiling at `minute_of_day`.
+    #[must_use]
+    pub fn clamp(&self, volume: f64, minute_of_day: u16) -> f64 {
+        self.ceiling_at(minute_of_day)
+            .map_or(volume, |ceiling| volume.min(ceiling))
+    }
+}
+
+/// Clamps `ceiling` to 0.0 to 1.0, or `None` if it is `NaN`.
+fn valid_ceiling(ceiling: f64) -> Option<f64> {
+    if ceiling.is_nan() {
+        log::warn!("Ignoring NaN volume ceiling");
+        return None;
+    }
+
+    Some(ceiling.clamp(0.0, 1.0))
+}
+
+/// Minutes since midnight of `time`, the unit [`QuietHours`] are evaluated in.
+#[must_use]
+pub fn minute_of_day(time: &impl Timelike) -> u16 {
+    u16::try_from(time.hour() * 60 + time.minute()).unwrap_or_default() % MINUTES_PER_DAY
+}
+
+#[cfg_attr(feature = "profiling", profiling::all_functions)]
+impl PlaybackHandler {
+    /// Replaces the limits applied to the output volume and applies them immediately.
+    ///
+    /// # Panics
+    ///
+    /// * If the `volume_ceiling` `RwLock` is poisoned
+    pub fn set_volume_ceiling(&self, ceiling: VolumeCeiling) {
+        *self.volume_ceiling.write().unwrap() = ceiling;
+        self.apply_output_volume();
+    }
+
+    /// The limits applied to the output volume.
+    ///
+    /// # Panics
+    ///
+    /// * If the `volume_ceiling` `RwLock` is poisoned
+    #[must_use]
+    pub fn volume_ceiling(&self) -> VolumeCeiling {
+        *self.volume_ceiling.read().unwrap()
+    }
+
+    /// The output volume ceiling currently in effect, taking the quiet hours into account.
+    ///
+    /// # Panics
+    ///
+    /// * If the `volume_ceiling` `RwLock` is poisoned
+    #[must_use]
+    pub fn effective_volume_ceiling(&self) -> Option<f64> {
+        self.volume_ceiling
+            .read()
+            .unwrap()
+            .ceiling_at(minute_of_day(&self.clock.now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        VOLUME_RAMP_INTERVAL, testing,
+        tests::{create_volume_test_handler, last_applied, mock_clock_at},
+    };
+
+    #[test_log::test]
+    fn test_volume_ceiling_ignores_nan_and_clamps_out_of_range_ceilings() {
+        let ceiling = |max, quiet| VolumeCeiling {
+            max: Some(max),
+            quiet_hours: Some(QuietHours::new((0, 0), (12, 0), quiet)),
+        };
+
+        assert_eq!(ceiling(f64::NAN, 0.4).ceiling_at(60), Some(0.4));
+        assert_eq!(ceiling(0.6, f64::NAN).ceiling_at(60), Some(0.6));
+        assert_eq!(ceiling(f64::NAN, f64::NAN).ceiling_at(60), None);
+        assert_eq!(ceiling(-0.5, 0.4).ceiling_at(60), Some(0.0));
+        assert_eq!(ceiling(1.5, 2.0).ceiling_at(60), Some(1.0));
+        assert!(ceiling(-0.5, 0.4).clamp(0.8, 60).abs() < f64::EPSILON);
+        assert!((ceiling(f64::NAN, f64::NAN).clamp(0.8, 60) - 0.8).abs() < f64::EPSILON);
+    }
+
+    #[test_log::test]
+    fn test_quiet_hours_wrap_around_midnight() {
+        let quiet_hours = QuietHours::new((22, 0), (7, 0), 0.2);
+
+        assert!(!quiet_hours.contains(21 * 60 + 59));
+        assert!(quiet_hours.contains(22 * 60));
+        assert!(quiet_hours.contains(0));
+        assert!(quiet_hours.contains(6 * 60 + 59));
+        assert!(!quiet_hours.contains(7 * 60));
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_set_volume_clamps_applied_volume_to_ceiling() {
+        let player = testing::FakePlayer::new();
+        let mut handler = create_volume_test_handler(&player, &mock_clock_at(12, 0))
+            .with_volume_ceiling(Some(0.5));
+
+        handler.set_volume(0.8, None).await.unwrap();
+
+        // The requested volume is kept, but the applied gain is clamped
+        assert!((handler.volume() - 0.8).abs() < f64::EPSILON);
+        assert!((handler.applied_volume() - 0.5).abs() < f64::EPSILON);
+        assert!((last_applied(&player) - 0.5).abs() < f64::EPSILON);
+
+        handler.set_volume(0.3, None).await.unwrap();
+
+        assert!((handler.applied_volume() - 0.3).abs() < f64::EPSILON);
+        assert!((last_applied(&player) - 0.3).abs() < f64::EPSILON);
+
+        // Lifting the ceiling restores the requested volume
+        handler.set_volume(0.9, None).await.unwrap();
+        handler.set_volume_ceiling(VolumeCeiling::default());
+
+        assert!((last_applied(&player) - 0.9).abs() < f64::EPSILON);
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_quiet_hours_tighten_ceiling_on_schedule() {
+        let player = testing::FakePlayer::new();
+        let clock = mock_clock_at(21, 59);
+        let mut handler = create_volume_test_handler(&player, &clock)
+            .with_volume_ceiling(Some(0.8))
+            .with_quiet_hours(Some(QuietHours::new((22, 0), (7, 0), 0.2)));
+
+        handler.set_volume(1.0, None).await.unwrap();
+
+        assert_eq!(handler.effective_volume_ceiling(), Some(0.8));
+        assert!((last_applied(&player) - 0.8).abs() < f64::EPSILON);
+
+        // Progress updates re-evaluate the ceiling as the clock enters the quiet hours
+        let playback = handler.playback.read().unwrap().clone().unwrap();
+        clock.advance(std::time::Duration::from_secs(60));
+        handler.playback_updated(&playback);
+
+        assert_eq!(handler.effective_volume_ceiling(), Some(0.2));
+        assert!((last_applied(&player) - 0.2).abs() < f64::EPSILON);
+        assert!((handler.volume() - 1.0).abs() < f64::EPSILON);
+
+        clock.advance(std::time::Duration::from_secs(9 * 60 * 60));
+        handler.playback_updated(&playback);
+
+        assert_eq!(handler.effective_volume_ceiling(), Some(0.8));
+        assert!((last_applied(&player) - 0.8).abs() < f64::EPSILON);
+    }
+
+    #[test_log::test(switchy_async::test)]
+    async fn test_ramp_volume_never_exceeds_ceiling() {
+        let player = testing::FakePlayer::new();
+        let clock = mock_clock_at(12, 0);
+        let mut handler =
+            create_volume_test_handler(&player, &clock).with_volume_ceiling(Some(0.5));
+
+        handler.set_volume(0.2, None).await.unwrap();
+        let before = player.output_volumes().len();
+
+        let ramp = switchy_async::runtime::Handle::current().spawn_with_name("test: ramp", {
+            let mut handler = handler.clone();
+            async move {
+                handler
+                    .ramp_volume(1.0, VOLUME_RAMP_INTERVAL * 4, None)
+                    .await
+            }
+        });
+
+        for _ in 0..4 {
+            clock.wait_for_sleepers().await;
+            clock.advance(VOLUME_RAMP_INTERVAL);
+        }
+
+        ramp.await.unwrap().unwrap();
+
+        assert!((handler.volume() - 1.0).abs() < f64::EPSILON);
+
+        let applied = player.output_volumes().split_off(before);
+        assert!(applied.len() > 1);
+        assert!(applied.iter().all(|x| *x <= 0.5));
+        assert!((applied.last().unwrap() - 0.5).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,99 @@
+//! Example tests driving a [`PlaybackHandler`] with the [`FakePlayer`] test double.
+//!
+//! These tests double as a reference for downstream crates testing their own code on top
+//! of the player.
+
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use std::sync::{Arc, RwLock};
+
+use atomic_float::AtomicF64;
+use moosicbox_music_models::{PlaybackQuality, Track};
+use moosicbox_player::{
+    Playback, PlaybackHandler, PlayerError,
+    testing::{FakePlayer, FakePlayerState, PlayerCall},
+};
+
+/// Creates a handler playing the first of two 180 second tracks at `progress`.
+fn create_handler(player: &FakePlayer, progress: f64) -> PlaybackHandler {
+    let track = |id: u64| Track {
+        id: id.into(),
+        title: format!("Track {id}"),
+        duration: 180.0,
+        ..Default::default()
+    };
+    let mut playback = Playback::new(
+        vec![track(1), track(2)],
+        Some(0),
+        AtomicF64::new(1.0),
+        PlaybackQuality::default(),
+        1,
+        "default".to_string(),
+        None,
+    );
+    playback.playing = true;
+    playback.progress = progress;
+
+    PlaybackHandler::new(player.clone()).with_playback(Arc::new(RwLock::new(Some(playback))))
+}
+
+#[test_log::test(switchy_async::test)]
+async fn test_handler_forwards_controls_to_player() {
+    let player = FakePlayer::new();
+    let mut handler = create_handler(&player, 0.0);
+
+    handler.play(Some(10.0), None).await.unwrap();
+    assert_eq!(player.state(), FakePlayerState::Playing);
+
+    handler.seek(42.0, None).await.unwrap();
+    handler.pause(None).await.unwrap();
+    assert_eq!(player.state(), FakePlayerState::Paused);
+
+    handler.resume(None).await.unwrap();
+    handler.stop(None).await.unwrap();
+    assert_eq!(player.state(), FakePlayerState::Stopped);
+
+    assert_eq!(
+        player.calls(),
+        vec![
+            PlayerCall::Play { seek: Some(10.0) },
+            PlayerCall::Seek { seek: 42.0 },
+            PlayerCall::Pause,
+            PlayerCall::Resume,
+            PlayerCall::Stop,
+        ]
+    );
+}
+
+#[test_log::test(switchy_async::test)]
+async fn test_seek_relative_seeks_from_playback_progress() {
+    let player = FakePlayer::new();
+    let mut handler = create_handler(&player, 60.0);
+
+    handler.seek_relative(30.0, None).await.unwrap();
+    handler.seek_relative(-90.0, None).await.unwrap();
+
+    // The fake doesn't report progress back, so both seeks are relative to 60s
+    assert_eq!(player.seeks(), vec![90.0, 0.0]);
+}
+
+#[test_log::test(switchy_async::test)]
+async fn test_player_failure_is_returned_by_handler() {
+    let player = FakePlayer::new();
+    let mut handler = create_handler(&player, 0.0);
+    player.set_state(FakePlayerState::Playing);
+
+    player.fail_next(PlayerError::NoAudioOutputs);
+
+    assert!(matches!(
+        handler.pause(None).await,
+        Err(PlayerError::NoAudioOutputs)
+    ));
+    assert_eq!(player.state(), FakePlayerState::Playing);
+
+    handler.pause(None).await.unwrap();
+    assert_eq!(player.state(), FakePlayerState::Paused);
+    assert_eq!(player.call_count(PlayerCall::Pause), 2);
+}
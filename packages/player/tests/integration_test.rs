@@ -20,9 +20,10 @@ mod local {
     use moosicbox_audio_output::AudioOutputFactory;
     use moosicbox_music_models::{ApiSource, PlaybackQuality, Track};
     use moosicbox_player::{
-        PlaybackType, Player, PlayerSource, local::LocalPlayer, set_service_port,
+        PlaybackType, Player, PlayerSource, crossfade::CrossfadeConfig, local::LocalPlayer,
+        set_service_port,
     };
-    use switchy_async::time::sleep;
+    use switchy_async::{time::sleep, util::CancellationToken};
     use symphonia::core::audio::Signal;
 
     /// Helper function to create a test track
@@ -203,7 +204,7 @@ mod local {
             .with_playback(playback_ref)
             .with_output(player.output.clone());
 
-        *player.playback_handler.write().unwrap() = Some(handler.clone());
+        player.set_playback_handler(&handler);
 
         // Set playback as playing to trigger the race condition path
         {
@@ -241,7 +242,10 @@ mod local {
                     i,
                     10.0 + i as f64
                 );
-                if let Err(e) = player_clone1.trigger_seek(10.0 + i as f64).await {
+                if let Err(e) = player_clone1
+                    .trigger_seek(10.0 + i as f64, CancellationToken::new())
+                    .await
+                {
                     println!("  ⚠️  Seek task 1 failed: {e}");
                 }
                 // Very short delay to increase race condition probability
@@ -256,7 +260,10 @@ mod local {
                     i,
                     20.0 + i as f64
                 );
-                if let Err(e) = player_clone2.trigger_seek(20.0 + i as f64).await {
+                if let Err(e) = player_clone2
+                    .trigger_seek(20.0 + i as f64, CancellationToken::new())
+                    .await
+                {
                     println!("  ⚠️  Seek task 2 failed: {e}");
                 }
                 // Very short delay to increase race condition probability
@@ -357,7 +364,7 @@ mod local {
             .with_playback(playback_ref)
             .with_output(player.output.clone());
 
-        *player.playback_handler.write().unwrap() = Some(handler.clone());
+        player.set_playback_handler(&handler);
 
         println!("🚀 Starting normal single playback...");
 
@@ -380,7 +387,7 @@ mod local {
 
         // Start normal playback (no seeks)
         let result = switchy_async::time::timeout(Duration::from_secs(3), async {
-            player.trigger_play(None).await
+            player.trigger_play(None, CancellationToken::new()).await
         })
         .await;
 
@@ -601,7 +608,9 @@ mod local {
                     println!(
                         "🔊 Player1: Starting trigger_play (this simulates the OLD AudioOutput)"
                     );
-                    player1.trigger_play(Some(30.0)).await
+                    player1
+                        .trigger_play(Some(30.0), CancellationToken::new())
+                        .await
                 }
             },
         );
@@ -615,7 +624,7 @@ mod local {
                 println!(
                     "🔊 Player2: Starting trigger_play (this simulates the NEW AudioOutput for seek)"
                 );
-                player2.trigger_play(Some(60.0)).await
+                player2.trigger_play(Some(60.0), CancellationToken::new()).await
             }
         });
 
@@ -686,6 +695,265 @@ mod local {
         }
     }
 
+    /// Test that the handler stored in a `LocalPlayer` doesn't keep its playback alive, as it
+    /// would through the player held by the handler
+    #[test_log::test(switchy_async::test)]
+    async fn test_dropping_local_player_handler_stops_background_work() {
+        let player = LocalPlayer::new(PlayerSource::Local, None)
+            .await
+            .expect("Failed to create LocalPlayer")
+            .with_output(create_test_audio_factory());
+
+        let handler = moosicbox_player::PlaybackHandler::new(player.clone())
+            .with_playback(player.playback.clone())
+            .with_output(player.output.clone());
+        player.set_playback_handler(&handler);
+
+        let cancel = handler.cancellation_token();
+        let events = handler.subscribe_events();
+
+        drop(handler);
+
+        assert!(cancel.is_cancelled());
+        assert!(events.is_disconnected());
+        // The player can still reach the handler it stores
+        assert!(player.playback_handler.read().unwrap().is_some());
+    }
+
+    /// Writes `seconds` of 44.1kHz stereo 16-bit PCM holding the constant `level` as a WAV file
+    fn write_wav(path: &std::path::Path, seconds: f64, level: f32) {
+        let frames = (seconds * 44_100.0) as u32;
+        let sample = (level * f32::from(i16::MAX)) as i16;
+        let data_len = frames * 4;
+
+        let mut bytes = Vec::with_capacity(44 + data_len as usize);
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        bytes.extend_from_slice(&2u16.to_le_bytes()); // Channels
+        bytes.extend_from_slice(&44_100u32.to_le_bytes());
+        bytes.extend_from_slice(&(44_100u32 * 4).to_le_bytes()); // Byte rate
+        bytes.extend_from_slice(&4u16.to_le_bytes()); // Block align
+        bytes.extend_from_slice(&16u16.to_le_bytes()); // Bits per sample
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        for _ in 0..frames * 2 {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    /// `AudioWrite` recording the left channel of everything written to it
+    struct RecordingAudioWrite {
+        samples: Arc<Mutex<Vec<f32>>>,
+    }
+
+    impl moosicbox_audio_output::AudioWrite for RecordingAudioWrite {
+        fn write(
+            &mut self,
+            decoded: symphonia::core::audio::AudioBuffer<f32>,
+        ) -> Result<usize, moosicbox_audio_output::AudioOutputError> {
+            self.samples
+                .lock()
+                .unwrap()
+                .extend_from_slice(decoded.chan(0));
+            Ok(decoded.frames())
+        }
+
+        fn flush(&mut self) -> Result<(), moosicbox_audio_output::AudioOutputError> {
+            Ok(())
+        }
+
+        fn handle(&self) -> moosicbox_audio_output::AudioHandle {
+            moosicbox_audio_output::AudioHandle::new(flume::unbounded().0)
+        }
+    }
+
+    /// Samples recorded by each output a [`create_recording_audio_factory`] opened, in order
+    type RecordedOutputs = Arc<Mutex<Vec<Arc<Mutex<Vec<f32>>>>>>;
+
+    /// Creates an audio output factory whose outputs record what's played through them
+    fn create_recording_audio_factory() -> (AudioOutputFactory, RecordedOutputs) {
+        let outputs = RecordedOutputs::default();
+        let factory = AudioOutputFactory::new(
+            "recording".to_string(),
+            "Recording".to_string(),
+            symphonia::core::audio::SignalSpec {
+                rate: 44100,
+                channels: symphonia::core::audio::Layout::Stereo.into_channels(),
+            },
+            {
+                let outputs = outputs.clone();
+                move || {
+                    let samples = Arc::new(Mutex::new(vec![]));
+                    outputs.lock().unwrap().push(samples.clone());
+                    Ok(Box::new(RecordingAudioWrite { samples }))
+                }
+            },
+        );
+
+        (factory, outputs)
+    }
+
+    /// Creates a one second track played from a WAV file in `dir` holding the constant `level`
+    fn create_wav_track(dir: &std::path::Path, id: u64, level: f32) -> Track {
+        let path = dir.join(format!("{id}.wav"));
+        write_wav(&path, 1.0, level);
+
+        Track {
+            id: id.into(),
+            duration: 1.0,
+            file: Some(path.to_str().unwrap().to_string()),
+            ..create_test_track()
+        }
+    }
+
+    #[test_log::test(switchy_async::test(real_time))]
+    async fn test_local_player_crossfades_into_next_track() {
+        let dir = tempfile::tempdir().unwrap();
+        let tracks = vec![
+            create_wav_track(dir.path(), 1, 0.5),
+            create_wav_track(dir.path(), 2, 0.25),
+        ];
+        let (factory, outputs) = create_recording_audio_factory();
+
+        let player = LocalPlayer::new(PlayerSource::Local, Some(PlaybackType::File))
+            .await
+            .expect("Failed to create LocalPlayer")
+            .with_output(factory);
+        *player.playback.write().unwrap() = Some(moosicbox_player::Playback::new(
+            tracks,
+            Some(0),
+            AtomicF64::new(1.0),
+            PlaybackQuality {
+                format: moosicbox_music_models::AudioFormat::Source,
+            },
+            1,
+            "default".to_string(),
+            None,
+        ));
+
+        let handler = moosicbox_player::PlaybackHandler::new(player.clone())
+            .with_playback(player.playback.clone())
+            .with_output(player.output.clone())
+            .with_crossfade(Some(CrossfadeConfig {
+                duration: Duration::from_millis(300),
+                match_loudness: false,
+            }));
+        player.set_playback_handler(&handler);
+
+        // Advance through the queue the way the playback handler does
+        player
+            .trigger_play(None, CancellationToken::new())
+            .await
+            .unwrap();
+        if let Some(playback) = player.playback.write().unwrap().as_mut() {
+            playback.position = 1;
+            playback.progress = 0.0;
+        }
+        player
+            .trigger_play(None, CancellationToken::new())
+            .await
+            .unwrap();
+
+        let outputs = outputs.lock().unwrap();
+        assert_eq!(outputs.len(), 2);
+        let first = outputs[0].lock().unwrap();
+        let second = outputs[1].lock().unwrap();
+        let level_a = first[0];
+        let level_b = second[second.len() / 2];
+        assert!((level_a - 2.0 * level_b).abs() < 1e-3);
+
+        // The first track fades out into the second
+        let overlap_start = first.len() - 13_230;
+        assert!((first[overlap_start - 1] - level_a).abs() < 1e-6);
+        assert!(first[first.len() - 2_646] < level_a - 0.05);
+        assert!((first[first.len() - 1] - level_b).abs() < 1e-3);
+
+        // The second track picks up after the part mixed into the first
+        assert_eq!(first.len(), 44_100);
+        assert!(
+            second.len().abs_diff(44_100 - 13_230) < 2_048,
+            "second track played {} frames",
+            second.len()
+        );
+    }
+
+    #[test_log::test(switchy_async::test(real_time))]
+    async fn test_local_player_resumes_prepared_track_without_reloading() {
+        let dir = tempfile::tempdir().unwrap();
+        let track = create_wav_track(dir.path(), 1, 0.5);
+        let (factory, outputs) = create_recording_audio_factory();
+
+        let player = LocalPlayer::new(PlayerSource::Local, Some(PlaybackType::File))
+            .await
+            .expect("Failed to create LocalPlayer")
+            .with_output(factory);
+        let mut handler = moosicbox_player::PlaybackHandler::new(player.clone())
+            .with_playback(player.playback.clone())
+            .with_output(player.output.clone());
+        *player.playback_handler.write().unwrap() = Some(handler.clone());
+
+        handler
+            .prepare_track(
+                1,
+                "default".to_string(),
+                track,
+                None,
+                None,
+                PlaybackQuality {
+                    format: moosicbox_music_models::AudioFormat::Source,
+                },
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        // The stream was probed, but no audio reaches the output until the resume
+        let stream_info = player
+            .playback
+            .read()
+            .unwrap()
+            .as_ref()
+            .and_then(|x| x.stream_info.clone())
+            .unwrap();
+        assert_eq!(stream_info.sample_rate, Some(44100));
+        assert!((stream_info.duration - 1.0).abs() < 1e-3);
+        sleep(Duration::from_millis(200)).await;
+        assert!(
+            outputs
+                .lock()
+                .unwrap()
+                .iter()
+                .all(|x| x.lock().unwrap().is_empty())
+        );
+
+        handler.resume(None).await.unwrap();
+
+        let start = Instant::now();
+        while outputs
+            .lock()
+            .unwrap()
+            .first()
+            .is_none_or(|x| x.lock().unwrap().len() < 44_100)
+        {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "the prepared track didn't finish playing"
+            );
+            sleep(Duration::from_millis(10)).await;
+        }
+
+        // The track played through the output opened while preparing it
+        let outputs = outputs.lock().unwrap();
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].lock().unwrap().len(), 44_100);
+    }
+
     /// AudioOutput implementation that simulates slow drain behavior to reproduce the race condition
     struct SlowDrainAudioOutput<F>
     where
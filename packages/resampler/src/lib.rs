@@ -34,11 +34,41 @@ use symphonia::core::audio::{AudioBuffer, Signal, SignalSpec};
 use symphonia::core::conv::{IntoSample, ReversibleSample};
 use symphonia::core::sample::Sample;
 
+/// Trade-off between CPU use and fidelity of a [`Resampler`].
+///
+/// The FFT resampler's anti-aliasing filter is as long as its FFT, and each input chunk is
+/// processed as [`sub_chunks`](Self::sub_chunks) FFTs. Fewer sub-chunks make for a longer
+/// filter with a sharper cutoff and less aliasing, at the cost of more work and latency
+/// per chunk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ResamplerQuality {
+    /// Shortest filter, for low-power devices
+    Fast,
+    /// Moderate filter length
+    #[default]
+    Balanced,
+    /// Longest filter, for the best fidelity
+    High,
+}
+
+impl ResamplerQuality {
+    /// Number of FFTs each input chunk is split into.
+    #[must_use]
+    pub const fn sub_chunks(self) -> usize {
+        match self {
+            Self::Fast => 8,
+            Self::Balanced => 2,
+            Self::High => 1,
+        }
+    }
+}
+
 /// Audio resampler for converting between sample rates.
 ///
 /// Uses FFT-based resampling to convert audio from one sample rate to another.
 pub struct Resampler<T> {
     resampler: rubato::FftFixedIn<f32>,
+    quality: ResamplerQuality,
     input: Vec<Vec<f32>>,
     output: Vec<Vec<f32>>,
     interleaved: Vec<T>,
@@ -96,7 +126,7 @@ impl<T> Resampler<T>
 where
     T: Sample + ReversibleSample<f32>,
 {
-    /// Creates a new resampler.
+    /// Creates a new resampler with the default [`ResamplerQuality`].
     ///
     /// # Panics
     ///
@@ -114,6 +144,35 @@ where
     /// ```
     #[must_use]
     pub fn new(spec: SignalSpec, to_sample_rate: usize, duration: u64) -> Self {
+        Self::with_quality(spec, to_sample_rate, duration, ResamplerQuality::default())
+    }
+
+    /// Creates a new resampler with the given `quality`.
+    ///
+    /// # Panics
+    ///
+    /// * If the `duration` cannot be converted to a `usize`
+    /// * If failed to create the `FftFixedIn` resampler
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use moosicbox_resampler::{Resampler, ResamplerQuality};
+    /// use symphonia::core::audio::{Channels, SignalSpec};
+    ///
+    /// let spec = SignalSpec::new(44_100, Channels::FRONT_LEFT | Channels::FRONT_RIGHT);
+    /// let resampler: Resampler<f32> =
+    ///     Resampler::with_quality(spec, 48_000, 1_024, ResamplerQuality::Fast);
+    ///
+    /// assert_eq!(resampler.quality(), ResamplerQuality::Fast);
+    /// ```
+    #[must_use]
+    pub fn with_quality(
+        spec: SignalSpec,
+        to_sample_rate: usize,
+        duration: u64,
+        quality: ResamplerQuality,
+    ) -> Self {
         let duration = usize::try_from(duration).unwrap();
         let num_channels = spec.channels.count();
 
@@ -121,7 +180,7 @@ where
             spec.rate as usize,
             to_sample_rate,
             duration,
-            2,
+            quality.sub_chunks(),
             num_channels,
         )
         .unwrap();
@@ -134,6 +193,7 @@ where
 
         Self {
             resampler,
+            quality,
             input,
             output,
             duration,
@@ -142,6 +202,12 @@ where
         }
     }
 
+    /// The quality the resampler was created with.
+    #[must_use]
+    pub const fn quality(&self) -> ResamplerQuality {
+        self.quality
+    }
+
     /// Resamples a planar/non-interleaved input.
     ///
     /// Returns the resampled samples in an interleaved format. Returns `None`
@@ -1071,4 +1137,94 @@ mod tests {
             "Signal should be preserved through 1:1 resampling"
         );
     }
+
+    #[test_log::test]
+    fn test_resampler_quality_selects_sub_chunks() {
+        assert_eq!(ResamplerQuality::default(), ResamplerQuality::Balanced);
+        assert_eq!(ResamplerQuality::Fast.sub_chunks(), 8);
+        assert_eq!(ResamplerQuality::Balanced.sub_chunks(), 2);
+        assert_eq!(ResamplerQuality::High.sub_chunks(), 1);
+
+        let spec = SignalSpec::new(44100, Channels::FRONT_LEFT | Channels::FRONT_RIGHT);
+        let resampler: Resampler<f32> = Resampler::new(spec, 48000, 1024);
+        assert_eq!(resampler.quality(), ResamplerQuality::Balanced);
+
+        let resampler: Resampler<f32> =
+            Resampler::with_quality(spec, 48000, 1024, ResamplerQuality::High);
+        assert_eq!(resampler.quality(), ResamplerQuality::High);
+    }
+
+    /// Upsamples a 882 Hz sine from 44.1 kHz to 88.2 kHz and returns the amplitude of the
+    /// output tone and the RMS of whatever isn't the tone, relative to the input amplitude.
+    ///
+    /// The tone is fitted by least squares over whole periods, so the resampler's delay
+    /// doesn't affect the result.
+    fn upsampled_sine_error(quality: ResamplerQuality) -> (f32, f32) {
+        const AMPLITUDE: f32 = 0.5;
+        // 882 Hz has a period of exactly 50 input and 100 output samples
+        const PERIOD: usize = 100;
+        const CHUNK: usize = 1024;
+
+        let spec = SignalSpec::new(44100, Channels::FRONT_LEFT);
+        let mut resampler: Resampler<f32> =
+            Resampler::with_quality(spec, 88200, CHUNK as u64, quality);
+
+        let mut output = vec![];
+        for chunk in 0..40 {
+            let mut input: AudioBuffer<f32> = AudioBuffer::new(CHUNK as u64, spec);
+            input.render_reserved(Some(CHUNK));
+            for (i, sample) in input.chan_mut(0).iter_mut().enumerate() {
+                let n = chunk * CHUNK + i;
+                *sample = AMPLITUDE * (std::f32::consts::TAU * n as f32 / 50.0).sin();
+            }
+            if let Some(samples) = resampler.resample(&input) {
+                output.extend_from_slice(samples);
+            }
+        }
+
+        // Skip the resampler's warm-up
+        let window = &output[8192..8192 + 200 * PERIOD];
+        let omega = std::f32::consts::TAU / PERIOD as f32;
+        let len = window.len() as f32;
+
+        let (mut sin_sum, mut cos_sum) = (0.0, 0.0);
+        for (n, sample) in window.iter().enumerate() {
+            sin_sum += sample * (omega * n as f32).sin();
+            cos_sum += sample * (omega * n as f32).cos();
+        }
+        let a = 2.0 * sin_sum / len;
+        let b = 2.0 * cos_sum / len;
+
+        let residual = window
+            .iter()
+            .enumerate()
+            .map(|(n, sample)| {
+                let tone = a * (omega * n as f32).sin() + b * (omega * n as f32).cos();
+                (sample - tone).powi(2)
+            })
+            .sum::<f32>()
+            / len;
+
+        (a.hypot(b) / AMPLITUDE, residual.sqrt() / AMPLITUDE)
+    }
+
+    #[test_log::test]
+    fn test_upsampling_stays_within_error_bounds_per_quality() {
+        for (quality, max_error) in [
+            (ResamplerQuality::Fast, 1e-2),
+            (ResamplerQuality::Balanced, 5e-3),
+            (ResamplerQuality::High, 5e-3),
+        ] {
+            let (gain, error) = upsampled_sine_error(quality);
+
+            assert!(
+                (gain - 1.0).abs() < 1e-2,
+                "{quality:?}: tone amplitude changed by a factor of {gain}"
+            );
+            assert!(
+                error < max_error,
+                "{quality:?}: relative error {error} exceeds {max_error}"
+            );
+        }
+    }
 }
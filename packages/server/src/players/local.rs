@@ -168,11 +168,7 @@ fn handle_server_playback_update(
                         .with_playback(playback)
                         .with_output(output);
 
-                    local_player
-                        .playback_handler
-                        .write()
-                        .unwrap()
-                        .replace(player.clone());
+                    local_player.set_playback_handler(&player);
 
                     if let Ok(Some(session)) = get_session(&db, update.session_id).await
                         && let Err(e) = player
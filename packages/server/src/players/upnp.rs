@@ -150,11 +150,7 @@ pub async fn load_upnp_players() -> Result<(), switchy_upnp::UpnpDeviceScannerEr
                 .with_playback(playback)
                 .with_output(Some(Arc::new(std::sync::Mutex::new(output.clone()))));
 
-            player
-                .playback_handler
-                .write()
-                .unwrap()
-                .replace(handler.clone());
+            player.set_playback_handler(&handler);
 
             players.push((output.clone(), player.clone(), handler));
         }
@@ -58,7 +58,8 @@ pub struct UpnpPlayer {
     transport_uri: Arc<AsyncRwLock<Option<String>>>,
     /// Current playback state and information.
     pub playback: Arc<RwLock<Option<Playback>>>,
-    /// Handler for managing playback operations.
+    /// Handler for managing playback operations, set with
+    /// [`UpnpPlayer::set_playback_handler`].
     pub playback_handler: Arc<RwLock<Option<PlaybackHandler>>>,
     /// Receiver for playback completion notifications.
     pub receiver: Arc<AsyncRwLock<Option<Receiver<()>>>>,
@@ -115,7 +116,11 @@ impl Player for UpnpPlayer {
         Ok(())
     }
 
-    async fn trigger_play(&self, seek: Option<f64>) -> Result<(), PlayerError> {
+    async fn trigger_play(
+        &self,
+        seek: Option<f64>,
+        cancel: CancellationToken,
+    ) -> Result<(), PlayerError> {
         log::debug!("trigger_play: seek={seek:?}");
         let transport_uri = self.update_av_transport().await?;
 
@@ -127,7 +132,7 @@ impl Player for UpnpPlayer {
             && seek > 0.0
         {
             log::debug!("trigger_play: Seeking track to seek={seek}");
-            self.trigger_seek(seek).await?;
+            self.trigger_seek(seek, cancel.clone()).await?;
         }
 
         play(&self.service, self.device.url(), self.instance_id, 1.0)
@@ -159,6 +164,11 @@ impl Player for UpnpPlayer {
                 log::debug!("playback cancelled");
                 self.unsubscribe(sub_id);
             }
+            () = cancel.cancelled() => {
+                log::debug!("playback superseded by a newer play operation");
+                self.unsubscribe(sub_id);
+                return Err(PlayerError::Cancelled);
+            }
             retry = finished_rx.recv_async() => {
                 self.unsubscribe(sub_id);
                 match retry {
@@ -230,7 +240,7 @@ impl Player for UpnpPlayer {
         Ok(())
     }
 
-    async fn trigger_seek(&self, seek: f64) -> Result<(), PlayerError> {
+    async fn trigger_seek(&self, seek: f64, _cancel: CancellationToken) -> Result<(), PlayerError> {
         log::info!("trigger_seek: seek={seek}");
 
         if self.expected_state.read().unwrap().is_none() {
@@ -327,6 +337,7 @@ impl Player for UpnpPlayer {
                 .unwrap()
                 .clone()
                 .map(Into::into),
+            underrun_count: 0,
         })
     }
 
@@ -362,13 +373,24 @@ impl UpnpPlayer {
         }
     }
 
+    /// Sets the playback handler driving this player.
+    ///
+    /// See [`PlaybackHandler::store_in`].
+    ///
+    /// # Panics
+    ///
+    /// * If the `playback_handler` `RwLock` is poisoned
+    pub fn set_playback_handler(&self, handler: &PlaybackHandler) {
+        handler.store_in(&self.playback_handler);
+    }
+
     async fn update_av_transport(&self) -> Result<String, PlayerError> {
         log::debug!("update_av_transport");
         let Some(playback) = self.playback.read().unwrap().clone() else {
             return Err(PlayerError::NoPlayersPlaying);
         };
 
-        let track = &playback.tracks[playback.position as usize];
+        let track = playback.tracks[playback.position as usize].playable_track();
         let track_id = &track.id;
         log::info!(
             "update_av_transport: Updating UPnP AV Transport URI: {} {:?} {track:?}",
@@ -380,7 +402,7 @@ impl UpnpPlayer {
             track_id,
             &track.api_source,
             &self.source,
-            playback.quality,
+            playback.effective_quality(),
             TrackAudioQuality::FlacHighestRes,
             true,
         )
@@ -398,7 +420,7 @@ impl UpnpPlayer {
             track_id,
             &track.api_source,
             &self.source,
-            playback.quality,
+            playback.effective_quality(),
             TrackAudioQuality::FlacHighestRes,
             false,
         )
@@ -527,11 +549,13 @@ impl UpnpPlayer {
                 self.service.service_id().to_owned(),
                 Box::new({
                     let active_playback = self.playback.clone();
+                    let playback_handler = self.playback_handler.clone();
                     let transport_uri = self.transport_uri.read().await.clone();
                     let handle = self.handle.clone();
                     let this_sub = this_sub.clone();
                     move |position_info| {
                         let active_playback = active_playback.clone();
+                        let playback_handler = playback_handler.clone();
                         let current_seek = current_seek.clone();
                         let finished_tx = finished_tx.clone();
                         let sent_playback_start_event = sent_playback_start_event.clone();
@@ -600,6 +624,9 @@ impl UpnpPlayer {
                                 let old = playback.clone();
                                 playback.progress = f64::from(position);
                                 current_seek.write().unwrap().replace(playback.progress);
+                                if let Some(handler) = playback_handler.read().unwrap().as_ref() {
+                                    handler.playback_updated(playback);
+                                }
                                 trigger_playback_event(playback, &old);
                             }
                         })